@@ -117,55 +117,23 @@ macro_rules! make_string_interval_month_day_nano {
     }};
 }
 
-macro_rules! make_string_date {
+macro_rules! make_string_numeric {
     ($array_type:ty, $column: ident, $row: ident) => {{
         let array = $column.as_any().downcast_ref::<$array_type>().unwrap();
 
-        Ok(array
-            .value_as_date($row)
-            .map(|d| d.to_string())
-            .unwrap_or_else(|| "ERROR CONVERTING DATE".to_string()))
+        Ok(lexical_to_string(array.value($row)))
     }};
 }
 
 macro_rules! make_string_time {
-    ($array_type:ty, $column: ident, $row: ident) => {{
+    ($array_type:ty, $column: ident, $row: ident, $options: ident) => {{
         let array = $column.as_any().downcast_ref::<$array_type>().unwrap();
 
-        Ok(array
-            .value_as_time($row)
-            .map(|d| d.to_string())
-            .unwrap_or_else(|| "ERROR CONVERTING DATE".to_string()))
-    }};
-}
-
-macro_rules! make_string_datetime {
-    ($array_type:ty, $column: ident, $row: ident) => {{
-        let array = $column.as_any().downcast_ref::<$array_type>().unwrap();
-
-        Ok(array
-            .value_as_datetime($row)
-            .map(|d| format!("{:?}", d))
-            .unwrap_or_else(|| "ERROR CONVERTING DATE".to_string()))
-    }};
-}
-
-macro_rules! make_string_datetime_with_tz {
-    ($array_type:ty, $tz_string: ident, $column: ident, $row: ident) => {{
-        let array = $column.as_any().downcast_ref::<$array_type>().unwrap();
-
-        let s = match $tz_string.parse::<Tz>() {
-            Ok(tz) => array
-                .value_as_datetime_with_tz($row, tz)
-                .map(|d| format!("{}", d.to_rfc3339()))
-                .unwrap_or_else(|| "ERROR CONVERTING DATE".to_string()),
-            Err(_) => array
-                .value_as_datetime($row)
-                .map(|d| format!("{:?} (Unknown Time Zone '{}')", d, $tz_string))
-                .unwrap_or_else(|| "ERROR CONVERTING DATE".to_string()),
-        };
-
-        Ok(s)
+        match (array.value_as_time($row), $options.time_format.as_deref()) {
+            (Some(t), Some(format)) => write_formatted(t.format(format), format),
+            (Some(t), None) => Ok(t.to_string()),
+            (None, _) => Ok("ERROR CONVERTING DATE".to_string()),
+        }
     }};
 }
 
@@ -185,7 +153,7 @@ macro_rules! make_string_hex {
 }
 
 macro_rules! make_string_from_list {
-    ($column: ident, $row: ident) => {{
+    ($column: ident, $row: ident, $options: ident) => {{
         let list = $column
             .as_any()
             .downcast_ref::<array::ListArray>()
@@ -194,14 +162,14 @@ macro_rules! make_string_from_list {
             )))?
             .value($row);
         let string_values = (0..list.len())
-            .map(|i| array_value_to_string(&list.clone(), i))
+            .map(|i| nested_value_to_string(&list, i, $options))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(format!("[{}]", string_values.join(", ")))
     }};
 }
 
 macro_rules! make_string_from_large_list {
-    ($column: ident, $row: ident) => {{
+    ($column: ident, $row: ident, $options: ident) => {{
         let list = $column
             .as_any()
             .downcast_ref::<array::LargeListArray>()
@@ -210,14 +178,14 @@ macro_rules! make_string_from_large_list {
             )))?
             .value($row);
         let string_values = (0..list.len())
-            .map(|i| array_value_to_string(&list, i))
+            .map(|i| nested_value_to_string(&list, i, $options))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(format!("[{}]", string_values.join(", ")))
     }};
 }
 
 macro_rules! make_string_from_fixed_size_list {
-    ($column: ident, $row: ident) => {{
+    ($column: ident, $row: ident, $options: ident) => {{
         let list = $column
             .as_any()
             .downcast_ref::<array::FixedSizeListArray>()
@@ -226,7 +194,7 @@ macro_rules! make_string_from_fixed_size_list {
             )))?
             .value($row);
         let string_values = (0..list.len())
-            .map(|i| array_value_to_string(&list.clone(), i))
+            .map(|i| nested_value_to_string(&list, i, $options))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(format!("[{}]", string_values.join(", ")))
     }};
@@ -258,27 +226,293 @@ fn append_struct_field_string(
     name: &str,
     field_col: &Arc<dyn Array>,
     row: usize,
+    options: &FormatOptions,
 ) -> Result<(), ArrowError> {
-    target.push('"');
     target.push_str(name);
-    target.push_str("\": ");
-
-    if field_col.is_null(row) {
-        target.push_str("null");
-    } else {
-        match field_col.data_type() {
-            DataType::Utf8 | DataType::LargeUtf8 => {
-                target.push('"');
-                target.push_str(array_value_to_string(field_col, row)?.as_str());
-                target.push('"');
-            }
-            _ => {
-                target.push_str(array_value_to_string(field_col, row)?.as_str());
-            }
+    target.push_str(": ");
+    target.push_str(&nested_value_to_string(field_col, row, options)?);
+
+    Ok(())
+}
+
+/// Get the value at the given row in an array nested within another as a String,
+/// with null values written as `null`
+fn nested_value_to_string(
+    column: &ArrayRef,
+    row: usize,
+    options: &FormatOptions,
+) -> Result<String, ArrowError> {
+    Ok(try_format_value(column, row, options)?.unwrap_or_else(|| "null".to_string()))
+}
+
+/// The notation used to format decimal values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecimalNotation {
+    /// Plain notation, e.g. `-123.45`
+    #[default]
+    Plain,
+    /// Scientific notation, e.g. `-1.2345e2`
+    Scientific,
+}
+
+/// Options for formatting array values with [`ArrayFormatter`]
+///
+/// Date and time formats are [`chrono` format strings], if not set dates and times are
+/// formatted as ISO 8601, and timestamps with a timezone as RFC 3339
+///
+/// [`chrono` format strings]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    null: String,
+    date_format: Option<String>,
+    datetime_format: Option<String>,
+    timestamp_tz_format: Option<String>,
+    time_format: Option<String>,
+    decimal_notation: DecimalNotation,
+}
+
+impl FormatOptions {
+    /// Create a new [`FormatOptions`] with the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the string to format null values as, defaults to an empty string
+    ///
+    /// Null values nested within lists, structs, maps and unions are always
+    /// formatted as `null`
+    pub fn with_null(mut self, null: impl Into<String>) -> Self {
+        self.null = null.into();
+        self
+    }
+
+    /// Sets the format of [`DataType::Date32`] values
+    pub fn with_date_format(mut self, date_format: Option<String>) -> Self {
+        self.date_format = date_format;
+        self
+    }
+
+    /// Sets the format of [`DataType::Date64`] values and timestamps without a timezone
+    pub fn with_datetime_format(mut self, datetime_format: Option<String>) -> Self {
+        self.datetime_format = datetime_format;
+        self
+    }
+
+    /// Sets the format of timestamps with a timezone, which are formatted in their
+    /// timezone
+    pub fn with_timestamp_tz_format(
+        mut self,
+        timestamp_tz_format: Option<String>,
+    ) -> Self {
+        self.timestamp_tz_format = timestamp_tz_format;
+        self
+    }
+
+    /// Sets the format of [`DataType::Time32`] and [`DataType::Time64`] values
+    pub fn with_time_format(mut self, time_format: Option<String>) -> Self {
+        self.time_format = time_format;
+        self
+    }
+
+    /// Sets the notation of [`DataType::Decimal128`] and [`DataType::Decimal256`] values
+    pub fn with_decimal_notation(mut self, decimal_notation: DecimalNotation) -> Self {
+        self.decimal_notation = decimal_notation;
+        self
+    }
+}
+
+/// Formats the values of an array as strings with the provided [`FormatOptions`]
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{ArrayRef, Date32Array};
+/// # use arrow_cast::display::{ArrayFormatter, FormatOptions};
+/// let array = Arc::new(Date32Array::from(vec![Some(0), None])) as ArrayRef;
+/// let options = FormatOptions::new()
+///     .with_date_format(Some("%d/%m/%Y".to_string()))
+///     .with_null("NULL");
+/// let formatter = ArrayFormatter::new(&array, &options).unwrap();
+/// assert_eq!(formatter.value(0).unwrap(), "01/01/1970");
+/// assert_eq!(formatter.value(1).unwrap(), "NULL");
+/// ```
+pub struct ArrayFormatter<'a> {
+    array: &'a ArrayRef,
+    options: &'a FormatOptions,
+    temporal: Option<TemporalFormat<'a>>,
+}
+
+impl<'a> std::fmt::Debug for ArrayFormatter<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArrayFormatter")
+            .field("array", self.array)
+            .field("options", self.options)
+            .finish()
+    }
+}
+
+impl<'a> ArrayFormatter<'a> {
+    /// Create a new [`ArrayFormatter`] formatting the values of `array` with `options`
+    ///
+    /// Returns an error if `array` is a timestamp array with an invalid timezone
+    pub fn new(
+        array: &'a ArrayRef,
+        options: &'a FormatOptions,
+    ) -> Result<Self, ArrowError> {
+        let tz = match array.data_type() {
+            DataType::Timestamp(_, Some(tz)) => Some(tz.parse::<Tz>()?),
+            _ => None,
+        };
+        let temporal = temporal_format(array, tz, options);
+        Ok(Self {
+            array,
+            options,
+            temporal,
+        })
+    }
+
+    /// Returns the value at `idx` formatted as a string
+    ///
+    /// Null values, and dates and timestamps without a timezone that are out of range,
+    /// are formatted as the null string of the [`FormatOptions`]
+    ///
+    /// Returns an error if the type of the array is not supported, a date or time
+    /// format is invalid, or a timestamp with a timezone is out of range
+    pub fn value(&self, idx: usize) -> Result<String, ArrowError> {
+        Ok(self
+            .try_value(idx)?
+            .unwrap_or_else(|| self.options.null.clone()))
+    }
+
+    /// Returns the value at `idx` formatted as a string, or `None` if it is null or an
+    /// out of range date or timestamp without a timezone
+    pub(crate) fn try_value(&self, idx: usize) -> Result<Option<String>, ArrowError> {
+        match &self.temporal {
+            Some(_) if self.array.is_null(idx) => Ok(None),
+            Some(format) => format(idx),
+            None => try_format_value(self.array, idx, self.options),
         }
     }
+}
 
-    Ok(())
+/// Formats the value at an index of a date or timestamp array, returning `None` if
+/// the value is out of range
+type TemporalFormat<'a> = Box<dyn Fn(usize) -> Result<Option<String>, ArrowError> + 'a>;
+
+/// Returns a [`TemporalFormat`] for `array` if it is a date or timestamp array
+///
+/// Timestamps are formatted in `tz` if provided, and without a timezone otherwise
+fn temporal_format<'a>(
+    array: &'a ArrayRef,
+    tz: Option<Tz>,
+    options: &'a FormatOptions,
+) -> Option<TemporalFormat<'a>> {
+    let format = match array.data_type() {
+        DataType::Date32 => {
+            let array = array.as_any().downcast_ref::<Date32Array>().unwrap();
+            Box::new(move |idx| match array.value_as_date(idx) {
+                Some(d) => match options.date_format.as_deref() {
+                    Some(format) => write_formatted(d.format(format), format).map(Some),
+                    None => Ok(Some(d.to_string())),
+                },
+                None => Ok(None),
+            })
+        }
+        DataType::Date64 => datetime_format::<Date64Type>(array, None, options),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            datetime_format::<TimestampSecondType>(array, tz, options)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            datetime_format::<TimestampMillisecondType>(array, tz, options)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            datetime_format::<TimestampMicrosecondType>(array, tz, options)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            datetime_format::<TimestampNanosecondType>(array, tz, options)
+        }
+        _ => return None,
+    };
+    Some(format)
+}
+
+/// Returns a [`TemporalFormat`] for `array`, a Date64 or timestamp array
+///
+/// Values out of range are `None` without a timezone, and an error with a timezone
+fn datetime_format<'a, T>(
+    array: &'a ArrayRef,
+    tz: Option<Tz>,
+    options: &'a FormatOptions,
+) -> TemporalFormat<'a>
+where
+    T: ArrowTemporalType,
+    i64: From<T::Native>,
+{
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    match tz {
+        Some(tz) => Box::new(move |idx| match array.value_as_datetime_with_tz(idx, tz) {
+            Some(d) => match options.timestamp_tz_format.as_deref() {
+                Some(format) => write_formatted(d.format(format), format).map(Some),
+                None => Ok(Some(d.to_rfc3339())),
+            },
+            None => Err(ArrowError::ComputeError(
+                "Unable to read value as datetime".to_string(),
+            )),
+        }),
+        None => Box::new(move |idx| match array.value_as_datetime(idx) {
+            Some(d) => match options.datetime_format.as_deref() {
+                Some(format) => write_formatted(d.format(format), format).map(Some),
+                None => Ok(Some(format!("{:?}", d))),
+            },
+            None => Ok(None),
+        }),
+    }
+}
+
+/// Writes `value`, a date or time formatted with `format`, to a string, returning an
+/// error if `format` is invalid
+fn write_formatted(
+    value: impl std::fmt::Display,
+    format: &str,
+) -> Result<String, ArrowError> {
+    let mut s = String::new();
+    write!(s, "{}", value).map_err(|_| {
+        ArrowError::InvalidArgumentError(format!(
+            "Invalid date or time format string: {:?}",
+            format
+        ))
+    })?;
+    Ok(s)
+}
+
+/// Formats `value`, a decimal in plain notation, with the decimal notation of `options`
+fn format_decimal(value: String, options: &FormatOptions) -> String {
+    match options.decimal_notation {
+        DecimalNotation::Plain => value,
+        DecimalNotation::Scientific => {
+            let (sign, value) = match value.strip_prefix('-') {
+                Some(value) => ("-", value),
+                None => ("", value.as_str()),
+            };
+            let integer_len = value.find('.').unwrap_or(value.len());
+            let digits: String = value.chars().filter(|c| *c != '.').collect();
+            let first = match digits.find(|c| c != '0') {
+                Some(first) => first,
+                None => return "0e0".to_string(),
+            };
+            let exponent = integer_len as i64 - 1 - first as i64;
+            let fraction = digits[first + 1..].trim_end_matches('0');
+            match fraction.is_empty() {
+                true => format!("{}{}e{}", sign, &digits[first..first + 1], exponent),
+                false => format!(
+                    "{}{}.{}e{}",
+                    sign,
+                    &digits[first..first + 1],
+                    fraction,
+                    exponent
+                ),
+            }
+        }
+    }
 }
 
 /// Get the value at the given row in an array as a String.
@@ -289,9 +523,55 @@ pub fn array_value_to_string(
     column: &ArrayRef,
     row: usize,
 ) -> Result<String, ArrowError> {
+    format_value(column, row, &FormatOptions::default())
+}
+
+/// Get the value at the given row in an array as a String, formatted with `options`
+fn format_value(
+    column: &ArrayRef,
+    row: usize,
+    options: &FormatOptions,
+) -> Result<String, ArrowError> {
+    Ok(try_format_value(column, row, options)?.unwrap_or_else(|| options.null.clone()))
+}
+
+/// Get the value at the given row in an array as a String, formatted with `options`,
+/// or `None` if it is null or an out of range date or timestamp without a timezone
+///
+/// Timestamps with an invalid timezone are formatted without a timezone, followed by
+/// the timezone
+fn try_format_value(
+    column: &ArrayRef,
+    row: usize,
+    options: &FormatOptions,
+) -> Result<Option<String>, ArrowError> {
     if column.is_null(row) {
-        return Ok("".to_string());
+        return Ok(None);
+    }
+    let tz = match column.data_type() {
+        DataType::Timestamp(_, Some(tz_string)) => match tz_string.parse::<Tz>() {
+            Ok(tz) => Some(tz),
+            Err(_) => {
+                let format = temporal_format(column, None, options).unwrap();
+                return Ok(format(row)?
+                    .map(|d| format!("{} (Unknown Time Zone '{}')", d, tz_string)));
+            }
+        },
+        _ => None,
+    };
+    if let Some(format) = temporal_format(column, tz, options) {
+        return format(row);
     }
+    format_valid_value(column, row, options).map(Some)
+}
+
+/// Get the value at the given row in an array as a String, formatted with `options`,
+/// where the value is not null and the array is not a date or timestamp array
+fn format_valid_value(
+    column: &ArrayRef,
+    row: usize,
+    options: &FormatOptions,
+) -> Result<String, ArrowError> {
     match column.data_type() {
         DataType::Utf8 => make_string!(array::StringArray, column, row),
         DataType::LargeUtf8 => make_string!(array::LargeStringArray, column, row),
@@ -301,81 +581,36 @@ pub fn array_value_to_string(
             make_string_hex!(array::FixedSizeBinaryArray, column, row)
         }
         DataType::Boolean => make_string!(array::BooleanArray, column, row),
-        DataType::Int8 => make_string!(array::Int8Array, column, row),
-        DataType::Int16 => make_string!(array::Int16Array, column, row),
-        DataType::Int32 => make_string!(array::Int32Array, column, row),
-        DataType::Int64 => make_string!(array::Int64Array, column, row),
-        DataType::UInt8 => make_string!(array::UInt8Array, column, row),
-        DataType::UInt16 => make_string!(array::UInt16Array, column, row),
-        DataType::UInt32 => make_string!(array::UInt32Array, column, row),
-        DataType::UInt64 => make_string!(array::UInt64Array, column, row),
+        DataType::Int8 => make_string_numeric!(array::Int8Array, column, row),
+        DataType::Int16 => make_string_numeric!(array::Int16Array, column, row),
+        DataType::Int32 => make_string_numeric!(array::Int32Array, column, row),
+        DataType::Int64 => make_string_numeric!(array::Int64Array, column, row),
+        DataType::UInt8 => make_string_numeric!(array::UInt8Array, column, row),
+        DataType::UInt16 => make_string_numeric!(array::UInt16Array, column, row),
+        DataType::UInt32 => make_string_numeric!(array::UInt32Array, column, row),
+        DataType::UInt64 => make_string_numeric!(array::UInt64Array, column, row),
         DataType::Float16 => make_string!(array::Float16Array, column, row),
-        DataType::Float32 => make_string!(array::Float32Array, column, row),
-        DataType::Float64 => make_string!(array::Float64Array, column, row),
-        DataType::Decimal128(..) => make_string_from_decimal(column, row),
-        DataType::Timestamp(unit, tz_string_opt) if *unit == TimeUnit::Second => {
-            match tz_string_opt {
-                Some(tz_string) => make_string_datetime_with_tz!(
-                    array::TimestampSecondArray,
-                    tz_string,
-                    column,
-                    row
-                ),
-                None => make_string_datetime!(array::TimestampSecondArray, column, row),
-            }
-        }
-        DataType::Timestamp(unit, tz_string_opt) if *unit == TimeUnit::Millisecond => {
-            match tz_string_opt {
-                Some(tz_string) => make_string_datetime_with_tz!(
-                    array::TimestampMillisecondArray,
-                    tz_string,
-                    column,
-                    row
-                ),
-                None => {
-                    make_string_datetime!(array::TimestampMillisecondArray, column, row)
-                }
-            }
+        DataType::Float32 => make_string_numeric!(array::Float32Array, column, row),
+        DataType::Float64 => make_string_numeric!(array::Float64Array, column, row),
+        DataType::Decimal128(..) => Ok(format_decimal(
+            make_string_from_decimal(column, row)?,
+            options,
+        )),
+        DataType::Decimal256(..) => {
+            let array = column.as_any().downcast_ref::<Decimal256Array>().unwrap();
+            Ok(format_decimal(array.value_as_string(row), options))
         }
-        DataType::Timestamp(unit, tz_string_opt) if *unit == TimeUnit::Microsecond => {
-            match tz_string_opt {
-                Some(tz_string) => make_string_datetime_with_tz!(
-                    array::TimestampMicrosecondArray,
-                    tz_string,
-                    column,
-                    row
-                ),
-                None => {
-                    make_string_datetime!(array::TimestampMicrosecondArray, column, row)
-                }
-            }
-        }
-        DataType::Timestamp(unit, tz_string_opt) if *unit == TimeUnit::Nanosecond => {
-            match tz_string_opt {
-                Some(tz_string) => make_string_datetime_with_tz!(
-                    array::TimestampNanosecondArray,
-                    tz_string,
-                    column,
-                    row
-                ),
-                None => {
-                    make_string_datetime!(array::TimestampNanosecondArray, column, row)
-                }
-            }
-        }
-        DataType::Date32 => make_string_date!(array::Date32Array, column, row),
-        DataType::Date64 => make_string_date!(array::Date64Array, column, row),
         DataType::Time32(unit) if *unit == TimeUnit::Second => {
-            make_string_time!(array::Time32SecondArray, column, row)
+            make_string_time!(array::Time32SecondArray, column, row, options)
         }
         DataType::Time32(unit) if *unit == TimeUnit::Millisecond => {
-            make_string_time!(array::Time32MillisecondArray, column, row)
+            make_string_time!(array::Time32MillisecondArray, column, row, options)
         }
         DataType::Time64(unit) if *unit == TimeUnit::Microsecond => {
-            make_string_time!(array::Time64MicrosecondArray, column, row)
+            make_string_time!(array::Time64MicrosecondArray, column, row, options)
         }
         DataType::Time64(unit) if *unit == TimeUnit::Nanosecond => {
-            make_string_time!(array::Time64NanosecondArray, column, row)
+            make_string_time!(array::Time64NanosecondArray, column, row, options)
         }
         DataType::Interval(unit) => match unit {
             IntervalUnit::DayTime => {
@@ -388,23 +623,41 @@ pub fn array_value_to_string(
                 make_string_interval_month_day_nano!(column, row)
             }
         },
-        DataType::List(_) => make_string_from_list!(column, row),
-        DataType::LargeList(_) => make_string_from_large_list!(column, row),
+        DataType::List(_) => make_string_from_list!(column, row, options),
+        DataType::LargeList(_) => make_string_from_large_list!(column, row, options),
         DataType::Dictionary(index_type, _value_type) => match **index_type {
-            DataType::Int8 => dict_array_value_to_string::<Int8Type>(column, row),
-            DataType::Int16 => dict_array_value_to_string::<Int16Type>(column, row),
-            DataType::Int32 => dict_array_value_to_string::<Int32Type>(column, row),
-            DataType::Int64 => dict_array_value_to_string::<Int64Type>(column, row),
-            DataType::UInt8 => dict_array_value_to_string::<UInt8Type>(column, row),
-            DataType::UInt16 => dict_array_value_to_string::<UInt16Type>(column, row),
-            DataType::UInt32 => dict_array_value_to_string::<UInt32Type>(column, row),
-            DataType::UInt64 => dict_array_value_to_string::<UInt64Type>(column, row),
+            DataType::Int8 => {
+                dict_array_value_to_string::<Int8Type>(column, row, options)
+            }
+            DataType::Int16 => {
+                dict_array_value_to_string::<Int16Type>(column, row, options)
+            }
+            DataType::Int32 => {
+                dict_array_value_to_string::<Int32Type>(column, row, options)
+            }
+            DataType::Int64 => {
+                dict_array_value_to_string::<Int64Type>(column, row, options)
+            }
+            DataType::UInt8 => {
+                dict_array_value_to_string::<UInt8Type>(column, row, options)
+            }
+            DataType::UInt16 => {
+                dict_array_value_to_string::<UInt16Type>(column, row, options)
+            }
+            DataType::UInt32 => {
+                dict_array_value_to_string::<UInt32Type>(column, row, options)
+            }
+            DataType::UInt64 => {
+                dict_array_value_to_string::<UInt64Type>(column, row, options)
+            }
             _ => Err(ArrowError::InvalidArgumentError(format!(
                 "Pretty printing not supported for {:?} due to index type",
                 column.data_type()
             ))),
         },
-        DataType::FixedSizeList(_, _) => make_string_from_fixed_size_list!(column, row),
+        DataType::FixedSizeList(_, _) => {
+            make_string_from_fixed_size_list!(column, row, options)
+        }
         DataType::Struct(_) => {
             let st = column
                 .as_any()
@@ -420,18 +673,44 @@ pub fn array_value_to_string(
             s.push('{');
             let mut kv_iter = st.columns().iter().zip(st.column_names());
             if let Some((col, name)) = kv_iter.next() {
-                append_struct_field_string(&mut s, name, col, row)?;
+                append_struct_field_string(&mut s, name, col, row, options)?;
             }
             for (col, name) in kv_iter {
                 s.push_str(", ");
-                append_struct_field_string(&mut s, name, col, row)?;
+                append_struct_field_string(&mut s, name, col, row, options)?;
             }
             s.push('}');
 
             Ok(s)
         }
+        DataType::Map(_, _) => {
+            let map = column
+                .as_any()
+                .downcast_ref::<array::MapArray>()
+                .ok_or_else(|| {
+                    ArrowError::InvalidArgumentError(
+                        "Repl error: could not convert map column to map array."
+                            .to_string(),
+                    )
+                })?;
+
+            let (keys, values) = (map.keys(), map.values());
+            let start = map.value_offsets()[row] as usize;
+            let end = map.value_offsets()[row + 1] as usize;
+            let entries = (start..end)
+                .map(|i| {
+                    Ok(format!(
+                        "{}: {}",
+                        nested_value_to_string(&keys, i, options)?,
+                        nested_value_to_string(&values, i, options)?
+                    ))
+                })
+                .collect::<Result<Vec<_>, ArrowError>>()?;
+
+            Ok(format!("{{{}}}", entries.join(", ")))
+        }
         DataType::Union(field_vec, type_ids, mode) => {
-            union_to_string(column, row, field_vec, type_ids, mode)
+            union_to_string(column, row, field_vec, type_ids, mode, options)
         }
         DataType::Duration(unit) => match *unit {
             TimeUnit::Second => {
@@ -461,6 +740,7 @@ fn union_to_string(
     fields: &[Field],
     type_ids: &[i8],
     mode: &UnionMode,
+    options: &FormatOptions,
 ) -> Result<String, ArrowError> {
     let list = column
         .as_any()
@@ -479,12 +759,13 @@ fn union_to_string(
     })?;
     let name = fields.get(field_idx).unwrap().name();
 
-    let value = array_value_to_string(
+    let value = nested_value_to_string(
         list.child(type_id),
         match mode {
             UnionMode::Dense => list.value_offset(row) as usize,
             UnionMode::Sparse => row,
         },
+        options,
     )?;
 
     Ok(format!("{{{}={}}}", name, value))
@@ -493,17 +774,18 @@ fn union_to_string(
 fn dict_array_value_to_string<K: ArrowPrimitiveType>(
     colum: &ArrayRef,
     row: usize,
+    options: &FormatOptions,
 ) -> Result<String, ArrowError> {
     let dict_array = colum.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
 
     let keys_array = dict_array.keys();
 
     if keys_array.is_null(row) {
-        return Ok(String::from(""));
+        return Ok(options.null.clone());
     }
 
     let dict_index = keys_array.value(row).as_usize();
-    array_value_to_string(dict_array.values(), dict_index)
+    format_value(dict_array.values(), dict_index, options)
 }
 
 /// Converts numeric type to a `String`
@@ -526,6 +808,8 @@ pub fn lexical_to_string<N: lexical_core::ToLexical>(n: N) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow_buffer::{i256, Buffer};
+    use arrow_data::ArrayData;
 
     #[test]
     fn test_array_value_to_string_duration() {
@@ -552,4 +836,155 @@ mod tests {
         assert_eq!(array_value_to_string(&s_array, 0).unwrap(), "PT1S");
         assert_eq!(array_value_to_string(&s_array, 1).unwrap(), "");
     }
+
+    #[test]
+    fn test_array_value_to_string_nested() {
+        let values = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let map = MapArray::new_from_strings(
+            ["a", "b", "c"].into_iter(),
+            &values,
+            &[0, 2, 2, 3],
+        )
+        .unwrap();
+        let map = Arc::new(map) as ArrayRef;
+        assert_eq!(array_value_to_string(&map, 0).unwrap(), "{a: 1, b: null}");
+        assert_eq!(array_value_to_string(&map, 1).unwrap(), "{}");
+        assert_eq!(array_value_to_string(&map, 2).unwrap(), "{c: 3}");
+
+        let list = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), None]),
+            None,
+        ])) as ArrayRef;
+        assert_eq!(array_value_to_string(&list, 0).unwrap(), "[1, null]");
+        assert_eq!(array_value_to_string(&list, 1).unwrap(), "");
+
+        let dict: DictionaryArray<Int8Type> =
+            vec![Some("x"), None, Some("y")].into_iter().collect();
+        let offsets = Buffer::from_slice_ref([0_i32, 3]);
+        let field = Field::new("item", dict.data_type().clone(), true);
+        let data = ArrayData::builder(DataType::List(Box::new(field)))
+            .len(1)
+            .add_buffer(offsets)
+            .add_child_data(dict.into_data())
+            .build()
+            .unwrap();
+        let list = make_array(data);
+        assert_eq!(array_value_to_string(&list, 0).unwrap(), "[x, null, y]");
+    }
+
+    #[test]
+    fn test_array_formatter() {
+        let options = FormatOptions::new()
+            .with_null("NULL")
+            .with_date_format(Some("%d/%m/%Y".to_string()))
+            .with_datetime_format(Some("%Y-%m-%d %H:%M:%S".to_string()))
+            .with_timestamp_tz_format(Some("%Y-%m-%d %H:%M %:z".to_string()))
+            .with_time_format(Some("%H-%M".to_string()))
+            .with_decimal_notation(DecimalNotation::Scientific);
+
+        let cases: Vec<(ArrayRef, Vec<&str>)> = vec![
+            (
+                Arc::new(Date32Array::from(vec![Some(17890), None])),
+                vec!["25/12/2018", "NULL"],
+            ),
+            (
+                Arc::new(Date64Array::from(vec![1545696000001])),
+                vec!["2018-12-25 00:00:00"],
+            ),
+            (
+                Arc::new(TimestampSecondArray::from(vec![1545696000])),
+                vec!["2018-12-25 00:00:00"],
+            ),
+            (
+                Arc::new(
+                    TimestampSecondArray::from(vec![1545696000])
+                        .with_timezone("+01:00".to_string()),
+                ),
+                vec!["2018-12-25 01:00 +01:00"],
+            ),
+            (Arc::new(Time32SecondArray::from(vec![3723])), vec!["01-02"]),
+            (
+                Arc::new(
+                    Decimal128Array::from(vec![12345, -100, 12, 0])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+                vec!["1.2345e2", "-1e0", "1.2e-1", "0e0"],
+            ),
+            (
+                Arc::new(
+                    Decimal256Array::from(vec![i256::from_i128(-123)])
+                        .with_precision_and_scale(10, 0)
+                        .unwrap(),
+                ),
+                vec!["-1.23e2"],
+            ),
+            (
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                    Some(vec![Some(1), None]),
+                    None,
+                ])),
+                vec!["[1, null]", "NULL"],
+            ),
+        ];
+
+        for (array, expected) in cases {
+            let formatter = ArrayFormatter::new(&array, &options).unwrap();
+            let actual: Vec<_> = (0..array.len())
+                .map(|i| formatter.value(i).unwrap())
+                .collect();
+            assert_eq!(actual, expected, "{}", array.data_type());
+        }
+
+        // The defaults are those of array_value_to_string
+        let array = Arc::new(
+            Decimal128Array::from(vec![12345])
+                .with_precision_and_scale(10, 2)
+                .unwrap(),
+        ) as ArrayRef;
+        let options = FormatOptions::default();
+        let formatter = ArrayFormatter::new(&array, &options).unwrap();
+        assert_eq!(formatter.value(0).unwrap(), "123.45");
+
+        let array = Arc::new(Date32Array::from(vec![0])) as ArrayRef;
+        let options = FormatOptions::new().with_date_format(Some("%Q".to_string()));
+        let err = ArrayFormatter::new(&array, &options)
+            .unwrap()
+            .value(0)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Invalid date or time format string: \"%Q\""
+        );
+    }
+
+    #[test]
+    fn test_array_formatter_out_of_range() {
+        let options = FormatOptions::new().with_null("NULL");
+
+        let array = Arc::new(Date64Array::from(vec![i64::MAX, 0])) as ArrayRef;
+        let formatter = ArrayFormatter::new(&array, &options).unwrap();
+        assert_eq!(formatter.value(0).unwrap(), "NULL");
+        assert_eq!(formatter.value(1).unwrap(), "1970-01-01T00:00:00");
+
+        let array = Arc::new(
+            TimestampSecondArray::from(vec![i64::MAX])
+                .with_timezone("+01:00".to_string()),
+        ) as ArrayRef;
+        let formatter = ArrayFormatter::new(&array, &options).unwrap();
+        let err = formatter.value(0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Compute error: Unable to read value as datetime"
+        );
+
+        let array = Arc::new(
+            TimestampSecondArray::from(vec![0]).with_timezone("Unknown".to_string()),
+        ) as ArrayRef;
+        ArrayFormatter::new(&array, &options).unwrap_err();
+        assert_eq!(
+            array_value_to_string(&array, 0).unwrap(),
+            "1970-01-01T00:00:00 (Unknown Time Zone 'Unknown')"
+        );
+    }
 }
@@ -16,17 +16,18 @@
 // under the License.
 
 use arrow_array::builder::StringBuilder;
-use arrow_array::{ArrayRef, RecordBatch};
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_flight::decode::FlightRecordBatchStream;
 use arrow_flight::sql::{ActionCreatePreparedStatementResult, ProstMessageExt, SqlInfo};
 use arrow_flight::{
     Action, FlightData, FlightEndpoint, HandshakeRequest, HandshakeResponse, IpcMessage,
-    Location, SchemaAsIpc, Ticket,
+    Location, PutResult, SchemaAsIpc, Ticket,
 };
-use futures::{stream, Stream};
+use futures::{stream, Stream, TryStreamExt};
 use prost_types::Any;
 use std::fs;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tempfile::NamedTempFile;
 use tokio::net::{UnixListener, UnixStream};
 use tokio_stream::wrappers::UnixListenerStream;
@@ -41,12 +42,8 @@ use arrow_flight::{
     flight_service_server::FlightServiceServer,
     sql::{
         server::FlightSqlService, ActionClosePreparedStatementRequest,
-        ActionCreatePreparedStatementRequest, CommandGetCatalogs,
-        CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
-        CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo,
-        CommandGetTableTypes, CommandGetTables, CommandPreparedStatementQuery,
-        CommandPreparedStatementUpdate, CommandStatementQuery, CommandStatementUpdate,
-        TicketStatementQuery,
+        ActionCreatePreparedStatementRequest, CommandPreparedStatementQuery,
+        CommandPreparedStatementUpdate, CommandStatementUpdate,
     },
     FlightDescriptor, FlightInfo,
 };
@@ -59,17 +56,70 @@ macro_rules! status {
     };
 }
 
-#[derive(Clone)]
-pub struct FlightSqlServiceImpl {}
+const FAKE_TOKEN: &str = "uuid_token";
+const FAKE_UPDATE_RESULT: i64 = 1;
+
+#[derive(Clone, Default)]
+pub struct FlightSqlServiceImpl {
+    /// The names bound as parameters of the prepared statement
+    names: Arc<Mutex<Vec<String>>>,
+}
 
 impl FlightSqlServiceImpl {
-    fn fake_result() -> Result<RecordBatch, ArrowError> {
+    #[allow(clippy::result_large_err)]
+    fn check_token<T>(req: &Request<T>) -> Result<(), Status> {
+        let authorization = req
+            .metadata()
+            .get("authorization")
+            .ok_or_else(|| Status::unauthenticated("authorization field not present"))?
+            .to_str()
+            .map_err(|e| status!("authorization not parsable", e))?;
+        if authorization != format!("Bearer {}", FAKE_TOKEN) {
+            Err(Status::unauthenticated("invalid token"))?
+        }
+        Ok(())
+    }
+
+    /// Greets each bound name, or FlightSQL if there are none
+    fn fake_result(&self) -> Result<RecordBatch, ArrowError> {
         let schema = Schema::new(vec![Field::new("salutation", DataType::Utf8, false)]);
         let mut builder = StringBuilder::new();
-        builder.append_value("Hello, FlightSQL!");
+        let names = self.names.lock().unwrap();
+        if names.is_empty() {
+            builder.append_value("Hello, FlightSQL!");
+        }
+        for name in names.iter() {
+            builder.append_value(format!("Hello, {}!", name));
+        }
         let cols = vec![Arc::new(builder.finish()) as ArrayRef];
         RecordBatch::try_new(Arc::new(schema), cols)
     }
+
+    /// The schema of the parameters of the prepared statement
+    fn parameter_schema() -> Schema {
+        Schema::new(vec![Field::new("name", DataType::Utf8, false)])
+    }
+
+    /// Returns the names in the parameters uploaded by the client
+    async fn read_names(
+        request: Request<FlightRecordBatchStream>,
+    ) -> Result<Vec<String>, Status> {
+        let batches: Vec<RecordBatch> = request
+            .into_inner()
+            .try_collect()
+            .await
+            .map_err(|e| status!("Unable to read parameters", e))?;
+        let mut names = vec![];
+        for batch in batches {
+            let column = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| Status::invalid_argument("Expected string parameters"))?;
+            names.extend(column.iter().flatten().map(|name| name.to_string()));
+        }
+        Ok(names)
+    }
 }
 
 #[tonic::async_trait]
@@ -118,16 +168,24 @@ impl FlightSqlService for FlightSqlServiceImpl {
         };
         let result = Ok(result);
         let output = futures::stream::iter(vec![result]);
-        return Ok(Response::new(Box::pin(output)));
+        let mut resp: Response<Pin<Box<dyn Stream<Item = _> + Send>>> =
+            Response::new(Box::pin(output));
+        let bearer = format!("Bearer {}", FAKE_TOKEN)
+            .parse()
+            .map_err(|e| status!("Unable to create bearer token", e))?;
+        resp.metadata_mut().insert("authorization", bearer);
+        return Ok(resp);
     }
 
     async fn do_get_fallback(
         &self,
-        _request: Request<Ticket>,
+        request: Request<Ticket>,
         _message: prost_types::Any,
     ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        let batch =
-            Self::fake_result().map_err(|e| status!("Could not fake a result", e))?;
+        Self::check_token(&request)?;
+        let batch = self
+            .fake_result()
+            .map_err(|e| status!("Could not fake a result", e))?;
         let schema = (*batch.schema()).clone();
         let batches = vec![batch];
         let flight_data = batches_to_flight_data(schema, batches)
@@ -141,16 +199,6 @@ impl FlightSqlService for FlightSqlServiceImpl {
         Ok(resp)
     }
 
-    async fn get_flight_info_statement(
-        &self,
-        _query: CommandStatementQuery,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_statement not implemented",
-        ))
-    }
-
     async fn get_flight_info_prepared_statement(
         &self,
         cmd: CommandPreparedStatementQuery,
@@ -158,8 +206,9 @@ impl FlightSqlService for FlightSqlServiceImpl {
     ) -> Result<Response<FlightInfo>, Status> {
         let handle = String::from_utf8(cmd.prepared_statement_handle)
             .map_err(|e| status!("Unable to parse handle", e))?;
-        let batch =
-            Self::fake_result().map_err(|e| status!("Could not fake a result", e))?;
+        let batch = self
+            .fake_result()
+            .map_err(|e| status!("Could not fake a result", e))?;
         let schema = (*batch.schema()).clone();
         let num_rows = batch.num_rows();
         let num_bytes = batch.get_array_memory_size();
@@ -198,192 +247,7 @@ impl FlightSqlService for FlightSqlServiceImpl {
         Ok(resp)
     }
 
-    async fn get_flight_info_catalogs(
-        &self,
-        _query: CommandGetCatalogs,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_catalogs not implemented",
-        ))
-    }
-
-    async fn get_flight_info_schemas(
-        &self,
-        _query: CommandGetDbSchemas,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_schemas not implemented",
-        ))
-    }
-
-    async fn get_flight_info_tables(
-        &self,
-        _query: CommandGetTables,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_tables not implemented",
-        ))
-    }
-
-    async fn get_flight_info_table_types(
-        &self,
-        _query: CommandGetTableTypes,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_table_types not implemented",
-        ))
-    }
-
-    async fn get_flight_info_sql_info(
-        &self,
-        _query: CommandGetSqlInfo,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_sql_info not implemented",
-        ))
-    }
-
-    async fn get_flight_info_primary_keys(
-        &self,
-        _query: CommandGetPrimaryKeys,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_primary_keys not implemented",
-        ))
-    }
-
-    async fn get_flight_info_exported_keys(
-        &self,
-        _query: CommandGetExportedKeys,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_exported_keys not implemented",
-        ))
-    }
-
-    async fn get_flight_info_imported_keys(
-        &self,
-        _query: CommandGetImportedKeys,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_imported_keys not implemented",
-        ))
-    }
-
-    async fn get_flight_info_cross_reference(
-        &self,
-        _query: CommandGetCrossReference,
-        _request: Request<FlightDescriptor>,
-    ) -> Result<Response<FlightInfo>, Status> {
-        Err(Status::unimplemented(
-            "get_flight_info_imported_keys not implemented",
-        ))
-    }
-
     // do_get
-    async fn do_get_statement(
-        &self,
-        _ticket: TicketStatementQuery,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented("do_get_statement not implemented"))
-    }
-
-    async fn do_get_prepared_statement(
-        &self,
-        _query: CommandPreparedStatementQuery,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented(
-            "do_get_prepared_statement not implemented",
-        ))
-    }
-
-    async fn do_get_catalogs(
-        &self,
-        _query: CommandGetCatalogs,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented("do_get_catalogs not implemented"))
-    }
-
-    async fn do_get_schemas(
-        &self,
-        _query: CommandGetDbSchemas,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented("do_get_schemas not implemented"))
-    }
-
-    async fn do_get_tables(
-        &self,
-        _query: CommandGetTables,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented("do_get_tables not implemented"))
-    }
-
-    async fn do_get_table_types(
-        &self,
-        _query: CommandGetTableTypes,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented("do_get_table_types not implemented"))
-    }
-
-    async fn do_get_sql_info(
-        &self,
-        _query: CommandGetSqlInfo,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented("do_get_sql_info not implemented"))
-    }
-
-    async fn do_get_primary_keys(
-        &self,
-        _query: CommandGetPrimaryKeys,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented("do_get_primary_keys not implemented"))
-    }
-
-    async fn do_get_exported_keys(
-        &self,
-        _query: CommandGetExportedKeys,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented(
-            "do_get_exported_keys not implemented",
-        ))
-    }
-
-    async fn do_get_imported_keys(
-        &self,
-        _query: CommandGetImportedKeys,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented(
-            "do_get_imported_keys not implemented",
-        ))
-    }
-
-    async fn do_get_cross_reference(
-        &self,
-        _query: CommandGetCrossReference,
-        _request: Request<Ticket>,
-    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
-        Err(Status::unimplemented(
-            "do_get_cross_reference not implemented",
-        ))
-    }
 
     // do_put
     async fn do_put_statement_update(
@@ -391,29 +255,30 @@ impl FlightSqlService for FlightSqlServiceImpl {
         _ticket: CommandStatementUpdate,
         _request: Request<Streaming<FlightData>>,
     ) -> Result<i64, Status> {
-        Err(Status::unimplemented(
-            "do_put_statement_update not implemented",
-        ))
+        Ok(FAKE_UPDATE_RESULT)
     }
 
     async fn do_put_prepared_statement_query(
         &self,
         _query: CommandPreparedStatementQuery,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<FlightRecordBatchStream>,
     ) -> Result<Response<<Self as FlightService>::DoPutStream>, Status> {
-        Err(Status::unimplemented(
-            "do_put_prepared_statement_query not implemented",
-        ))
+        Self::check_token(&request)?;
+        *self.names.lock().unwrap() = Self::read_names(request).await?;
+        let output: Pin<Box<dyn Stream<Item = Result<PutResult, Status>> + Send>> =
+            Box::pin(stream::empty());
+        Ok(Response::new(output))
     }
 
     async fn do_put_prepared_statement_update(
         &self,
         _query: CommandPreparedStatementUpdate,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<FlightRecordBatchStream>,
     ) -> Result<i64, Status> {
-        Err(Status::unimplemented(
-            "do_put_prepared_statement_update not implemented",
-        ))
+        Self::check_token(&request)?;
+        // Executes the update once per set of parameters
+        let names = Self::read_names(request).await?;
+        Ok(names.len() as i64)
     }
 
     async fn do_action_create_prepared_statement(
@@ -422,17 +287,23 @@ impl FlightSqlService for FlightSqlServiceImpl {
         _request: Request<Action>,
     ) -> Result<ActionCreatePreparedStatementResult, Status> {
         let handle = "some_uuid";
-        let schema = Self::fake_result()
+        let schema = self
+            .fake_result()
             .map_err(|e| status!("Error getting result schema", e))?
             .schema();
         let message = SchemaAsIpc::new(&schema, &IpcWriteOptions::default())
             .try_into()
             .map_err(|e| status!("Unable to serialize schema", e))?;
         let IpcMessage(schema_bytes) = message;
+        let message =
+            SchemaAsIpc::new(&Self::parameter_schema(), &IpcWriteOptions::default())
+                .try_into()
+                .map_err(|e| status!("Unable to serialize schema", e))?;
+        let IpcMessage(parameter_schema_bytes) = message;
         let res = ActionCreatePreparedStatementResult {
             prepared_statement_handle: handle.as_bytes().to_vec(),
             dataset_schema: schema_bytes,
-            parameter_schema: vec![], // TODO: parameters
+            parameter_schema: parameter_schema_bytes,
         };
         Ok(res)
     }
@@ -442,7 +313,7 @@ impl FlightSqlService for FlightSqlServiceImpl {
         _query: ActionClosePreparedStatementRequest,
         _request: Request<Action>,
     ) {
-        unimplemented!("Implement do_action_close_prepared_statement")
+        self.names.lock().unwrap().clear();
     }
 
     async fn register_sql_info(&self, _id: i32, _result: &SqlInfo) {}
@@ -453,7 +324,7 @@ impl FlightSqlService for FlightSqlServiceImpl {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = "0.0.0.0:50051".parse()?;
 
-    let svc = FlightServiceServer::new(FlightSqlServiceImpl {});
+    let svc = FlightServiceServer::new(FlightSqlServiceImpl::default());
 
     println!("Listening on {:?}", addr);
 
@@ -484,24 +355,17 @@ impl ProstMessageExt for FetchResults {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use futures::TryStreamExt;
 
     use arrow::util::pretty::pretty_format_batches;
-    use arrow_flight::utils::flight_data_to_batches;
     use tower::service_fn;
 
-    async fn client_with_uds(path: String) -> FlightSqlServiceClient {
-        let connector = service_fn(move |_| UnixStream::connect(path.clone()));
-        let channel = Endpoint::try_from("https://example.com")
-            .unwrap()
-            .connect_with_connector(connector)
-            .await
-            .unwrap();
-        FlightSqlServiceClient::new(channel)
-    }
-
-    #[tokio::test]
-    async fn test_select_1() {
+    /// Serves [`FlightSqlServiceImpl`] on a Unix domain socket, and runs `f` with a
+    /// client connected to it
+    async fn run_client<F, Fut>(f: F)
+    where
+        F: FnOnce(FlightSqlServiceClient) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
         let file = NamedTempFile::new().unwrap();
         let path = file.into_temp_path().to_str().unwrap().to_string();
         let _ = fs::remove_file(path.clone());
@@ -510,21 +374,35 @@ mod tests {
         let stream = UnixListenerStream::new(uds);
 
         // We would just listen on TCP, but it seems impossible to know when tonic is ready to serve
-        let service = FlightSqlServiceImpl {};
+        let service = FlightSqlServiceImpl::default();
         let serve_future = Server::builder()
             .add_service(FlightServiceServer::new(service))
             .serve_with_incoming(stream);
 
         let request_future = async {
-            let mut client = client_with_uds(path).await;
+            let connector = service_fn(move |_| UnixStream::connect(path.clone()));
+            let channel = Endpoint::try_from("http://example.com")
+                .unwrap()
+                .connect_with_connector(connector)
+                .await
+                .unwrap();
+            f(FlightSqlServiceClient::new(channel)).await
+        };
+
+        tokio::select! {
+            _ = serve_future => panic!("server returned first"),
+            _ = request_future => println!("Client finished!"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_select_1() {
+        run_client(|mut client| async move {
             let token = client.handshake("admin", "password").await.unwrap();
             println!("Auth succeeded with token: {:?}", token);
             let mut stmt = client.prepare("select 1;".to_string()).await.unwrap();
             let flight_info = stmt.execute().await.unwrap();
-            let ticket = flight_info.endpoint[0].ticket.as_ref().unwrap().clone();
-            let flight_data = client.do_get(ticket).await.unwrap();
-            let flight_data: Vec<FlightData> = flight_data.try_collect().await.unwrap();
-            let batches = flight_data_to_batches(&flight_data).unwrap();
+            let batches = client.fetch(&flight_info).await.unwrap();
             let res = pretty_format_batches(batches.as_slice()).unwrap();
             let expected = r#"
 +-------------------+
@@ -535,11 +413,62 @@ mod tests {
                 .trim()
                 .to_string();
             assert_eq!(res.to_string(), expected);
-        };
+        })
+        .await
+    }
 
-        tokio::select! {
-            _ = serve_future => panic!("server returned first"),
-            _ = request_future => println!("Client finished!"),
-        }
+    #[tokio::test]
+    async fn test_execute_update() {
+        run_client(|mut client| async move {
+            client.handshake("admin", "password").await.unwrap();
+            let res = client
+                .execute_update("create table test(a int);".to_string())
+                .await
+                .unwrap();
+            assert_eq!(res, FAKE_UPDATE_RESULT);
+
+            // commands without an implementation report unimplemented
+            let err = client.get_catalogs().await.unwrap_err();
+            assert!(err.to_string().contains("Unimplemented"), "{}", err);
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statement_parameters() {
+        run_client(|mut client| async move {
+            client.handshake("admin", "password").await.unwrap();
+            let mut stmt = client
+                .prepare("select 'Hello, ' || ? || '!';".to_string())
+                .await
+                .unwrap();
+            let parameter_schema = stmt.parameter_schema().unwrap().clone();
+            assert_eq!(parameter_schema, FlightSqlServiceImpl::parameter_schema());
+
+            let names = Arc::new(StringArray::from(vec!["Alice", "Bob"])) as ArrayRef;
+            let parameters =
+                RecordBatch::try_new(Arc::new(parameter_schema), vec![names]).unwrap();
+            stmt.set_parameters(parameters).unwrap();
+
+            let flight_info = stmt.execute().await.unwrap();
+            let batches = client.fetch(&flight_info).await.unwrap();
+            let res = pretty_format_batches(batches.as_slice()).unwrap();
+            let expected = r#"
++---------------+
+| salutation    |
++---------------+
+| Hello, Alice! |
+| Hello, Bob!   |
++---------------+"#
+                .trim()
+                .to_string();
+            assert_eq!(res.to_string(), expected);
+
+            // The update is executed once per row of parameters
+            assert_eq!(stmt.execute_update().await.unwrap(), 2);
+
+            stmt.close().await.unwrap();
+        })
+        .await
     }
 }
@@ -125,7 +125,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let svc = FlightServiceServer::new(service);
 
-    Server::builder().add_service(svc).serve(addr).await?;
+    #[allow(unused_mut)]
+    let mut builder = Server::builder();
+
+    // With the `tls` feature, serves TLS if FLIGHT_TLS_CERT and FLIGHT_TLS_KEY
+    // name PEM files, and requires client certificates signed by the root
+    // certificate in FLIGHT_TLS_CLIENT_CA if set
+    #[cfg(feature = "tls")]
+    if let (Ok(cert), Ok(key)) = (
+        std::env::var("FLIGHT_TLS_CERT"),
+        std::env::var("FLIGHT_TLS_KEY"),
+    ) {
+        use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+        let identity = Identity::from_pem(std::fs::read(cert)?, std::fs::read(key)?);
+        let mut tls = ServerTlsConfig::new().identity(identity);
+        if let Ok(ca) = std::env::var("FLIGHT_TLS_CLIENT_CA") {
+            tls = tls.client_ca_root(Certificate::from_pem(std::fs::read(ca)?));
+        }
+        builder = builder.tls_config(tls)?;
+    }
+
+    builder.add_service(svc).serve(addr).await?;
 
     Ok(())
 }
@@ -0,0 +1,1136 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::decode::{extract_lazy_trailers, FlightDataDecoder, FlightRecordBatchStream};
+use crate::exchange::do_exchange_with_metadata;
+use crate::flight_service_client::FlightServiceClient;
+use crate::middleware::{
+    command_type, descriptor_command, CallTracker, FlightMethod, FlightMiddleware,
+};
+use crate::{
+    status_to_arrow_error, Action, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, Ticket,
+};
+use arrow_schema::{ArrowError, Schema};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use prost::Message;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::metadata::{AsciiMetadataKey, MetadataMap};
+#[cfg(feature = "tls")]
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Response, Status, Streaming};
+
+/// The header used to authenticate requests
+const AUTHORIZATION: &str = "authorization";
+
+/// A higher level client for [Apache Arrow Flight], wrapping the generated
+/// [`FlightServiceClient`] to decode responses into [`RecordBatch`]es and to
+/// attach the same request metadata, such as authentication, to every request
+///
+/// Servers commonly authenticate with a [`Self::handshake`] using basic
+/// credentials, returning a bearer token in the `authorization` header of the
+/// response, which is then used to authenticate all subsequent requests:
+///
+/// ```no_run
+/// # async fn run() -> Result<(), arrow_schema::ArrowError> {
+/// use arrow_flight::{FlightClient, Ticket};
+/// use futures::TryStreamExt;
+/// use tonic::transport::Channel;
+///
+/// let channel = Channel::from_static("http://localhost:50051")
+///     .connect()
+///     .await
+///     .map_err(|e| arrow_schema::ArrowError::ExternalError(Box::new(e)))?;
+/// let mut client = FlightClient::new(channel);
+///
+/// client.handshake_basic("admin", "password").await?;
+/// println!("authenticated with token {:?}", client.bearer_token());
+///
+/// let ticket = Ticket { ticket: b"my_query".to_vec() };
+/// let batches: Vec<_> = client.do_get(ticket).await?.try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Metadata can also be added to a single request, such as a trace id, with
+/// [`Self::with_request_metadata`]. The headers and trailers of the response are
+/// returned by [`Self::last_response_metadata`], or by the returned stream for
+/// [`Self::do_get`] and [`Self::do_exchange`]
+///
+/// Calls that fail with a transient error can be retried automatically, see
+/// [`Self::set_retry_policy`]
+///
+/// [Apache Arrow Flight]: https://arrow.apache.org/docs/format/Flight.html
+/// [`RecordBatch`]: arrow_array::RecordBatch
+#[derive(Debug, Clone)]
+pub struct FlightClient {
+    /// Metadata added to each request
+    metadata: MetadataMap,
+    /// The bearer token returned by the last handshake, if any
+    token: Option<String>,
+    /// The headers and trailers of the last response that was not a stream
+    response_metadata: Option<MetadataMap>,
+    /// The policy for retrying idempotent calls
+    retry_policy: RetryPolicy,
+    /// The hooks called around each call, if any
+    middleware: Option<Arc<dyn FlightMiddleware>>,
+    inner: FlightServiceClient<Channel>,
+}
+
+impl FlightClient {
+    /// Creates a new `FlightClient` that sends requests over `channel`
+    pub fn new(channel: Channel) -> Self {
+        Self::new_from_inner(FlightServiceClient::new(channel))
+    }
+
+    /// Creates a new `FlightClient` wrapping an existing [`FlightServiceClient`]
+    pub fn new_from_inner(inner: FlightServiceClient<Channel>) -> Self {
+        Self {
+            metadata: MetadataMap::new(),
+            token: None,
+            response_metadata: None,
+            retry_policy: RetryPolicy::default(),
+            middleware: None,
+            inner,
+        }
+    }
+
+    /// Returns the metadata added to each request
+    pub fn metadata(&self) -> &MetadataMap {
+        &self.metadata
+    }
+
+    /// Returns a mutable reference to the metadata added to each request
+    pub fn metadata_mut(&mut self) -> &mut MetadataMap {
+        &mut self.metadata
+    }
+
+    /// Returns a copy of this client that also adds `metadata` to each request,
+    /// replacing any existing values of the same keys
+    ///
+    /// As the copy shares the connection of this client, this is a cheap way to
+    /// add metadata to a single request:
+    ///
+    /// ```no_run
+    /// # async fn run(client: arrow_flight::FlightClient) -> Result<(), arrow_schema::ArrowError> {
+    /// use arrow_flight::Ticket;
+    /// use tonic::metadata::MetadataMap;
+    ///
+    /// let mut metadata = MetadataMap::new();
+    /// metadata.insert("x-trace-id", "1234".parse().unwrap());
+    ///
+    /// let ticket = Ticket { ticket: b"my_query".to_vec() };
+    /// let stream = client.with_request_metadata(metadata).do_get(ticket).await?;
+    /// println!("response headers: {:?}", stream.headers());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_request_metadata(&self, metadata: MetadataMap) -> Self {
+        let mut headers = self.metadata.clone().into_headers();
+        headers.extend(metadata.into_headers());
+
+        let mut client = self.clone();
+        client.metadata = MetadataMap::from_headers(headers);
+        client
+    }
+
+    /// Returns the headers, merged with any trailers, of the last successful response to
+    /// [`Self::handshake`], [`Self::get_flight_info`], [`Self::get_schema`] or
+    /// [`Self::do_action`]
+    ///
+    /// The metadata of the streams returned by [`Self::do_get`] and
+    /// [`Self::do_exchange`] is available from the stream itself, see
+    /// [`FlightDataDecoder::headers`] and [`FlightDataDecoder::trailers`]
+    pub fn last_response_metadata(&self) -> Option<&MetadataMap> {
+        self.response_metadata.as_ref()
+    }
+
+    /// Sets the [`RetryPolicy`] of the idempotent calls [`Self::get_flight_info`],
+    /// [`Self::get_schema`] and [`Self::do_get`], which are not retried by default,
+    /// and of [`Self::get_flight_data`], which re-fetches expired tickets
+    ///
+    /// The [`Channel`] reconnects to the server automatically, so a call that
+    /// failed as the connection was lost succeeds once the server is reachable
+    /// again
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Returns the [`RetryPolicy`] of idempotent calls
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Sets the [`FlightMiddleware`] called around each call, such as to log
+    /// calls or record their metrics
+    ///
+    /// A call with retries is reported once, and a call with a streaming
+    /// response finishes once the stream has ended or is dropped
+    pub fn set_middleware(&mut self, middleware: Arc<dyn FlightMiddleware>) {
+        self.middleware = Some(middleware);
+    }
+
+    /// Adds the header `key` with `value` to each request, replacing any
+    /// previous value of `key`
+    pub fn add_header(&mut self, key: &str, value: &str) -> Result<(), ArrowError> {
+        let key = AsciiMetadataKey::from_bytes(key.as_bytes()).map_err(|e| {
+            ArrowError::InvalidArgumentError(format!("Invalid header key {}: {}", key, e))
+        })?;
+        let value = value.parse().map_err(|e| {
+            ArrowError::InvalidArgumentError(format!(
+                "Invalid header value {}: {}",
+                value, e
+            ))
+        })?;
+        self.metadata.insert(key, value);
+        Ok(())
+    }
+
+    /// Authenticates each request with the bearer `token`
+    pub fn set_bearer_token(
+        &mut self,
+        token: impl Into<String>,
+    ) -> Result<(), ArrowError> {
+        let token = token.into();
+        self.add_header(AUTHORIZATION, &format!("Bearer {}", token))?;
+        self.token = Some(token);
+        Ok(())
+    }
+
+    /// Authenticates each request with the basic credentials `username` and `password`
+    pub fn set_basic_auth(
+        &mut self,
+        username: &str,
+        password: &str,
+    ) -> Result<(), ArrowError> {
+        let credentials = base64::encode(format!("{}:{}", username, password));
+        self.add_header(AUTHORIZATION, &format!("Basic {}", credentials))?;
+        self.token = None;
+        Ok(())
+    }
+
+    /// Returns the bearer token used to authenticate requests, either set with
+    /// [`Self::set_bearer_token`] or returned by the server in a handshake
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Returns a reference to the wrapped [`FlightServiceClient`]
+    pub fn inner(&self) -> &FlightServiceClient<Channel> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped [`FlightServiceClient`]
+    pub fn inner_mut(&mut self) -> &mut FlightServiceClient<Channel> {
+        &mut self.inner
+    }
+
+    /// Consumes this client, returning the wrapped [`FlightServiceClient`]
+    pub fn into_inner(self) -> FlightServiceClient<Channel> {
+        self.inner
+    }
+
+    /// Performs a `Handshake` with the server, sending `payload` and returning the
+    /// payload of the server's response
+    ///
+    /// If the server responds with a bearer token in the `authorization` header, it
+    /// is used to authenticate all subsequent requests, see [`Self::bearer_token`]
+    pub async fn handshake(&mut self, payload: Vec<u8>) -> Result<Vec<u8>, ArrowError> {
+        let request = HandshakeRequest {
+            protocol_version: 0,
+            payload,
+        };
+        let request = self.make_request(stream::once(async { request }));
+
+        let call = self.start_call(FlightMethod::Handshake, None, None);
+        let result = collect_streaming(self.inner.handshake(request).await).await;
+        if let Some(call) = call {
+            call.finish_collected(result.as_ref().map(|(_, m)| m.as_slice()));
+        }
+        let (metadata, mut responses) = result.map_err(status_to_arrow_error)?;
+
+        let token = match metadata.get(AUTHORIZATION) {
+            Some(value) => {
+                let value = value.to_str().map_err(|e| {
+                    ArrowError::ParseError(format!(
+                        "Invalid authorization header in handshake response: {}",
+                        e
+                    ))
+                })?;
+                match value.strip_prefix("Bearer ") {
+                    Some(token) => Some(token.to_string()),
+                    None => {
+                        return Err(ArrowError::ParseError(format!(
+                            "Expected a bearer token in handshake response, got {}",
+                            value
+                        )))
+                    }
+                }
+            }
+            None => None,
+        };
+
+        self.response_metadata = Some(metadata);
+        if responses.len() != 1 {
+            return Err(ArrowError::ParseError(format!(
+                "Expected a single handshake response, got {}",
+                responses.len()
+            )));
+        }
+
+        if let Some(token) = token {
+            self.set_bearer_token(token)?;
+        }
+        Ok(responses.pop().unwrap().payload)
+    }
+
+    /// Performs a [`Self::handshake`] authenticated with the basic credentials
+    /// `username` and `password`, which remain in use unless the server
+    /// responds with a bearer token
+    pub async fn handshake_basic(
+        &mut self,
+        username: &str,
+        password: &str,
+    ) -> Result<Vec<u8>, ArrowError> {
+        self.set_basic_auth(username, password)?;
+        self.handshake(vec![]).await
+    }
+
+    /// Requests the [`FlightInfo`] describing how to retrieve the data of `descriptor`
+    pub async fn get_flight_info(
+        &mut self,
+        descriptor: FlightDescriptor,
+    ) -> Result<FlightInfo, ArrowError> {
+        let command = descriptor_command(&descriptor);
+        let call = self.start_call(
+            FlightMethod::GetFlightInfo,
+            command,
+            Some(descriptor.encoded_len()),
+        );
+        let response = self
+            .retry_policy
+            .retry(|| {
+                let mut inner = self.inner.clone();
+                let request = self.make_request(descriptor.clone());
+                async move { inner.get_flight_info(request).await }
+            })
+            .await;
+        if let Some(call) = call {
+            call.finish_unary(&response);
+        }
+        let response = response.map_err(status_to_arrow_error)?;
+        self.response_metadata = Some(response.metadata().clone());
+        Ok(response.into_inner())
+    }
+
+    /// Requests the [`Schema`] of the data of `descriptor`
+    pub async fn get_schema(
+        &mut self,
+        descriptor: FlightDescriptor,
+    ) -> Result<Schema, ArrowError> {
+        let command = descriptor_command(&descriptor);
+        let call = self.start_call(
+            FlightMethod::GetSchema,
+            command,
+            Some(descriptor.encoded_len()),
+        );
+        let response = self
+            .retry_policy
+            .retry(|| {
+                let mut inner = self.inner.clone();
+                let request = self.make_request(descriptor.clone());
+                async move { inner.get_schema(request).await }
+            })
+            .await;
+        if let Some(call) = call {
+            call.finish_unary(&response);
+        }
+        let response = response.map_err(status_to_arrow_error)?;
+        self.response_metadata = Some(response.metadata().clone());
+        Schema::try_from(&response.into_inner())
+    }
+
+    /// Requests the data of `ticket`, returning a stream of the decoded
+    /// [`RecordBatch`](arrow_array::RecordBatch)es along with the headers and
+    /// trailers of the response
+    ///
+    /// With a [`RetryPolicy`], the request is sent again if it fails, or if the
+    /// stream fails before its first message. Errors after the first message are
+    /// returned by the stream
+    pub async fn do_get(
+        &mut self,
+        ticket: Ticket,
+    ) -> Result<FlightRecordBatchStream, ArrowError> {
+        let retry_policy = self.retry_policy.clone();
+        self.do_get_with_policy(ticket, &retry_policy)
+            .await
+            .map_err(status_to_arrow_error)
+    }
+
+    /// Retrieves the data of `descriptor`, requesting its [`FlightInfo`] with
+    /// [`Self::get_flight_info`] and then the data of each of its endpoints in
+    /// order with [`Self::do_get`], returning a stream per endpoint
+    ///
+    /// With a [`RetryPolicy`], a `DoGet` failing with a retryable error before
+    /// its first message is retried by requesting the [`FlightInfo`] again, as
+    /// the ticket of the endpoint may have expired, and then the data of the
+    /// endpoint with the new ticket. The data of all endpoints is requested from
+    /// the server of this client, their `location`s are not used
+    pub async fn get_flight_data(
+        &mut self,
+        descriptor: FlightDescriptor,
+    ) -> Result<Vec<FlightRecordBatchStream>, ArrowError> {
+        let retry_policy = self.retry_policy.clone();
+        let mut info = self.get_flight_info(descriptor.clone()).await?;
+        let mut streams = Vec::with_capacity(info.endpoint.len());
+        let mut attempt = 0;
+        while let Some(endpoint) = info.endpoint.get(streams.len()) {
+            let ticket = endpoint.ticket.clone().ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "Endpoint {} of FlightInfo has no ticket",
+                    streams.len()
+                ))
+            })?;
+
+            // Each ticket is only requested once, it is retried by re-fetching it
+            let result = self
+                .do_get_with_policy(ticket, &RetryPolicy::default())
+                .await;
+            match result {
+                Ok(stream) => streams.push(stream),
+                Err(status)
+                    if attempt < retry_policy.max_retries
+                        && retry_policy.is_retryable(&status) =>
+                {
+                    tokio::time::sleep(retry_policy.backoff(attempt)).await;
+                    attempt += 1;
+
+                    let endpoints = info.endpoint.len();
+                    info = self.get_flight_info(descriptor.clone()).await?;
+                    if info.endpoint.len() != endpoints {
+                        return Err(ArrowError::InvalidArgumentError(format!(
+                            "FlightInfo changed from {} to {} endpoints while retrying",
+                            endpoints,
+                            info.endpoint.len()
+                        )));
+                    }
+                }
+                Err(status) => return Err(status_to_arrow_error(status)),
+            }
+        }
+        Ok(streams)
+    }
+
+    /// Performs a [`Self::do_get`] retried according to `retry_policy`
+    async fn do_get_with_policy(
+        &mut self,
+        ticket: Ticket,
+        retry_policy: &RetryPolicy,
+    ) -> Result<FlightRecordBatchStream, Status> {
+        let command = command_type(&ticket.ticket);
+        let call =
+            self.start_call(FlightMethod::DoGet, command, Some(ticket.encoded_len()));
+        let result = retry_policy
+            .retry(|| {
+                let mut inner = self.inner.clone();
+                let request = self.make_request(ticket.clone());
+                async move {
+                    let response = inner.do_get(request).await?;
+                    let headers = response.metadata().clone();
+                    let (mut response, trailers) =
+                        extract_lazy_trailers(response.into_inner());
+
+                    let first = response.next().await.transpose()?;
+                    let response = stream::iter(first.map(Ok)).chain(response);
+                    Ok((headers, response, trailers))
+                }
+            })
+            .await;
+
+        let (headers, response, trailers) = match result {
+            Ok(result) => result,
+            Err(status) => {
+                if let Some(call) = call {
+                    call.finish(status.code(), 0, 0);
+                }
+                return Err(status);
+            }
+        };
+        let response = match call {
+            Some(call) => call.observe(response),
+            None => response.boxed(),
+        };
+        let decoder = FlightDataDecoder::new(response.map_err(status_to_arrow_error))
+            .with_headers(headers)
+            .with_trailers(trailers);
+        Ok(FlightRecordBatchStream::new(decoder))
+    }
+
+    /// Performs a `DoExchange` call identified by `descriptor`, see
+    /// [`crate::exchange::do_exchange`]
+    pub async fn do_exchange<S>(
+        &mut self,
+        descriptor: FlightDescriptor,
+        input: S,
+    ) -> Result<FlightDataDecoder, ArrowError>
+    where
+        S: Stream<Item = Result<FlightData, ArrowError>> + Send + 'static,
+    {
+        let command = descriptor_command(&descriptor);
+        let call = self.start_call(FlightMethod::DoExchange, command, None);
+        let metadata = self.metadata.clone();
+        do_exchange_with_metadata(&mut self.inner, metadata, descriptor, input, call)
+            .await
+    }
+
+    /// Performs the `action`, returning the bodies of the results
+    pub async fn do_action(
+        &mut self,
+        action: Action,
+    ) -> Result<Vec<Vec<u8>>, ArrowError> {
+        let command = Some(action.r#type.clone());
+        let call =
+            self.start_call(FlightMethod::DoAction, command, Some(action.encoded_len()));
+        let request = self.make_request(action);
+        let result = collect_streaming(self.inner.do_action(request).await).await;
+        if let Some(call) = call {
+            call.finish_collected(result.as_ref().map(|(_, m)| m.as_slice()));
+        }
+
+        let (metadata, results) = result.map_err(status_to_arrow_error)?;
+        self.response_metadata = Some(metadata);
+        Ok(results.into_iter().map(|result| result.body).collect())
+    }
+
+    /// Starts tracking a call of `method` with the [`FlightMiddleware`], if any,
+    /// where `request_bytes` is the size of the request if it is a single message
+    fn start_call(
+        &self,
+        method: FlightMethod,
+        command: Option<String>,
+        request_bytes: Option<usize>,
+    ) -> Option<CallTracker> {
+        self.middleware.as_ref().map(|middleware| {
+            CallTracker::start(
+                middleware.clone(),
+                method,
+                command,
+                self.metadata.clone(),
+                request_bytes,
+            )
+        })
+    }
+
+    /// Wraps `message` in a request with the metadata of this client
+    fn make_request<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        *request.metadata_mut() = self.metadata.clone();
+        request
+    }
+}
+
+/// Collects the messages of a streaming `response`, returning them along with its
+/// headers merged with its trailers
+async fn collect_streaming<T>(
+    response: Result<Response<Streaming<T>>, Status>,
+) -> Result<(MetadataMap, Vec<T>), Status> {
+    let response = response?;
+    let mut headers = response.metadata().clone().into_headers();
+    let mut response = response.into_inner();
+
+    let mut messages = vec![];
+    while let Some(message) = response.message().await? {
+        messages.push(message);
+    }
+    if let Some(trailers) = response.trailers().await? {
+        headers.extend(trailers.into_headers());
+    }
+    Ok((MetadataMap::from_headers(headers), messages))
+}
+
+/// The policy for retrying the idempotent calls of a [`FlightClient`] that fail
+/// with a transient error, waiting for an exponentially increasing backoff
+/// between attempts
+///
+/// By default, no calls are retried, and calls failing with [`Code::Unavailable`]
+/// are retried once retries are enabled with [`Self::new`]
+///
+/// ```
+/// use std::time::Duration;
+/// use arrow_flight::RetryPolicy;
+///
+/// // Waits 100ms, 200ms and then 400ms before each retry
+/// let policy = RetryPolicy::new(3)
+///     .with_initial_backoff(Duration::from_millis(100))
+///     .with_max_backoff(Duration::from_secs(1));
+/// assert_eq!(policy.backoff(2), Duration::from_millis(400));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The maximum number of times a call is retried
+    max_retries: usize,
+    /// The backoff before the first retry
+    initial_backoff: Duration,
+    /// The maximum backoff between retries
+    max_backoff: Duration,
+    /// The factor by which the backoff increases after each retry
+    multiplier: u32,
+    /// The status codes of the errors that are retried
+    retryable_codes: Vec<Code>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a new [`RetryPolicy`] retrying each call at most `max_retries` times
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2,
+            retryable_codes: vec![Code::Unavailable],
+        }
+    }
+
+    /// Sets the backoff before the first retry, defaults to 100ms
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum backoff between retries, defaults to 10s
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the factor by which the backoff increases after each retry, defaults to 2
+    pub fn with_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the status codes of the errors that are retried, defaults to
+    /// [`Code::Unavailable`]
+    pub fn with_retryable_codes(mut self, retryable_codes: Vec<Code>) -> Self {
+        self.retryable_codes = retryable_codes;
+        self
+    }
+
+    /// Returns the maximum number of times a call is retried
+    pub fn max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    /// Returns the backoff before the retry following `attempt` failed attempts,
+    /// starting from 0
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let factor = self
+            .multiplier
+            .checked_pow(attempt.try_into().unwrap_or(u32::MAX))
+            .unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    /// Returns true if a call that failed with `status` should be retried
+    pub fn is_retryable(&self, status: &Status) -> bool {
+        self.retryable_codes.contains(&status.code())
+    }
+
+    /// Calls `f` until it succeeds, fails with an error that is not retryable,
+    /// or has been retried [`Self::max_retries`] times
+    async fn retry<T, F, Fut>(&self, mut f: F) -> Result<T, Status>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Status>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Err(status)
+                    if attempt < self.max_retries && self.is_retryable(&status) =>
+                {
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Builds the [`Channel`] of a [`FlightClient`], and optionally configures TLS
+/// with the `tls` feature
+///
+/// ```no_run
+/// # #[cfg(feature = "tls")]
+/// # async fn run() -> Result<(), arrow_schema::ArrowError> {
+/// use arrow_flight::FlightChannelBuilder;
+///
+/// let ca = std::fs::read("ca.pem")?;
+/// let cert = std::fs::read("client.pem")?;
+/// let key = std::fs::read("client.key")?;
+///
+/// // Verifies the server with a custom root CA and authenticates the client
+/// // with a certificate (mTLS)
+/// let client = FlightChannelBuilder::new("https://flight.example.com:443")
+///     .with_ca_certificate(ca)
+///     .with_client_identity(cert, key)
+///     .with_domain_name("flight.example.com")
+///     .connect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlightChannelBuilder {
+    uri: String,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    /// PEM encoded root certificate used to verify the server
+    #[cfg(feature = "tls")]
+    ca_certificate: Option<Vec<u8>>,
+    /// PEM encoded certificate and private key of the client
+    #[cfg(feature = "tls")]
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+    /// The name used for SNI and to verify the server's certificate
+    #[cfg(feature = "tls")]
+    domain_name: Option<String>,
+}
+
+impl FlightChannelBuilder {
+    /// Creates a new builder connecting to `uri`, such as `http://localhost:50051`
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            connect_timeout: None,
+            timeout: None,
+            #[cfg(feature = "tls")]
+            ca_certificate: None,
+            #[cfg(feature = "tls")]
+            identity: None,
+            #[cfg(feature = "tls")]
+            domain_name: None,
+        }
+    }
+
+    /// Sets the timeout for establishing a connection
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for each request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Verifies the server's certificate with the PEM encoded root certificate
+    /// `pem`, instead of the default roots, and enables TLS
+    #[cfg(feature = "tls")]
+    pub fn with_ca_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_certificate = Some(pem.into());
+        self
+    }
+
+    /// Authenticates the client to the server with the PEM encoded certificate
+    /// `cert` and private key `key` (mutual TLS), and enables TLS
+    #[cfg(feature = "tls")]
+    pub fn with_client_identity(
+        mut self,
+        cert: impl Into<Vec<u8>>,
+        key: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.identity = Some((cert.into(), key.into()));
+        self
+    }
+
+    /// Sets the domain name sent with SNI and expected in the server's certificate,
+    /// which defaults to the host of the uri, and enables TLS
+    #[cfg(feature = "tls")]
+    pub fn with_domain_name(mut self, domain_name: impl Into<String>) -> Self {
+        self.domain_name = Some(domain_name.into());
+        self
+    }
+
+    /// Returns the configured [`Endpoint`], which may be further customized
+    pub fn endpoint(&self) -> Result<Endpoint, ArrowError> {
+        let mut endpoint = Endpoint::from_shared(self.uri.clone()).map_err(|e| {
+            ArrowError::InvalidArgumentError(format!(
+                "Invalid Flight uri {}: {}",
+                self.uri, e
+            ))
+        })?;
+        if let Some(timeout) = self.connect_timeout {
+            endpoint = endpoint.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            endpoint = endpoint.timeout(timeout);
+        }
+
+        #[cfg(feature = "tls")]
+        if let Some(tls) = self.tls_config() {
+            endpoint = endpoint
+                .tls_config(tls)
+                .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        }
+        Ok(endpoint)
+    }
+
+    /// Returns the TLS configuration if TLS is enabled, either by one of the TLS
+    /// options or by a `https` uri
+    #[cfg(feature = "tls")]
+    fn tls_config(&self) -> Option<ClientTlsConfig> {
+        let enabled = self.uri.starts_with("https://")
+            || self.ca_certificate.is_some()
+            || self.identity.is_some()
+            || self.domain_name.is_some();
+        if !enabled {
+            return None;
+        }
+
+        let mut tls = ClientTlsConfig::new();
+        if let Some(pem) = &self.ca_certificate {
+            tls = tls.ca_certificate(Certificate::from_pem(pem));
+        }
+        if let Some((cert, key)) = &self.identity {
+            tls = tls.identity(Identity::from_pem(cert, key));
+        }
+        if let Some(domain_name) = &self.domain_name {
+            tls = tls.domain_name(domain_name.clone());
+        }
+        Some(tls)
+    }
+
+    /// Connects to the server, returning the [`Channel`]
+    pub async fn connect_channel(&self) -> Result<Channel, ArrowError> {
+        self.endpoint()?
+            .connect()
+            .await
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))
+    }
+
+    /// Connects to the server, returning a [`FlightClient`]
+    pub async fn connect(&self) -> Result<FlightClient, ArrowError> {
+        Ok(FlightClient::new(self.connect_channel().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode::FlightDataEncoderBuilder;
+    use crate::test_util::{run_service, test_batch, TestFlightService, TOKEN, TRACE_ID};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_channel_builder() {
+        let endpoint = FlightChannelBuilder::new("http://localhost:50051")
+            .with_connect_timeout(Duration::from_secs(1))
+            .with_timeout(Duration::from_secs(2))
+            .endpoint()
+            .unwrap();
+        assert_eq!(endpoint.uri().to_string(), "http://localhost:50051/");
+
+        let err = FlightChannelBuilder::new("not a uri")
+            .endpoint()
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid Flight uri"), "{}", err);
+    }
+
+    /// Connects with `builder` and performs a handshake
+    #[cfg(feature = "tls")]
+    async fn tls_handshake(builder: FlightChannelBuilder) -> Result<(), ArrowError> {
+        let mut client = builder.connect().await?;
+        client.handshake_basic("admin", "password").await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn test_tls() {
+        use crate::test_util::{run_tls_service, CA_PEM, CLIENT_KEY, CLIENT_PEM};
+
+        run_tls_service(false, |uri| async move {
+            let mut client = FlightChannelBuilder::new(&uri)
+                .with_ca_certificate(CA_PEM)
+                .with_domain_name("localhost")
+                .connect()
+                .await
+                .unwrap();
+            client.handshake_basic("admin", "password").await.unwrap();
+            let descriptor = FlightDescriptor::new_path(vec!["a".to_string()]);
+            let info = client.get_flight_info(descriptor).await.unwrap();
+            assert_eq!(info.total_records, 3);
+
+            // The self-signed root is not trusted by default
+            let builder = FlightChannelBuilder::new(&uri).with_domain_name("localhost");
+            tls_handshake(builder).await.unwrap_err();
+
+            // The certificate is only valid for localhost
+            let builder = FlightChannelBuilder::new(&uri)
+                .with_ca_certificate(CA_PEM)
+                .with_domain_name("example.com");
+            tls_handshake(builder).await.unwrap_err();
+        })
+        .await;
+
+        run_tls_service(true, |uri| async move {
+            let builder = FlightChannelBuilder::new(&uri)
+                .with_ca_certificate(CA_PEM)
+                .with_domain_name("localhost");
+
+            // The server requires a client certificate (mTLS)
+            tls_handshake(builder.clone()).await.unwrap_err();
+
+            let builder = builder.with_client_identity(CLIENT_PEM, CLIENT_KEY);
+            tls_handshake(builder).await.unwrap();
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_auth() {
+        run_service(TestFlightService::default(), |channel| async move {
+            let mut client = FlightClient::new(channel);
+            let descriptor = FlightDescriptor::new_path(vec!["a".to_string()]);
+
+            // Unauthenticated requests are rejected
+            let err = client
+                .get_flight_info(descriptor.clone())
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("invalid token"), "{}", err);
+            let err = client.handshake_basic("admin", "wrong").await.unwrap_err();
+            assert!(err.to_string().contains("invalid credentials"), "{}", err);
+            assert_eq!(client.bearer_token(), None);
+
+            let payload = client.handshake_basic("admin", "password").await.unwrap();
+            assert_eq!(payload, b"welcome");
+            assert_eq!(client.bearer_token(), Some(TOKEN));
+
+            let info = client.get_flight_info(descriptor.clone()).await.unwrap();
+            assert_eq!(info.total_records, 3);
+
+            let schema = client.get_schema(descriptor.clone()).await.unwrap();
+            assert_eq!(&schema, test_batch().schema().as_ref());
+
+            let ticket = Ticket {
+                ticket: b"a".to_vec(),
+            };
+            let batches: Vec<_> = client
+                .do_get(ticket)
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(batches, vec![test_batch()]);
+
+            let action = Action {
+                r#type: "echo".to_string(),
+                body: b"body".to_vec(),
+            };
+            let results = client.do_action(action).await.unwrap();
+            assert_eq!(results, vec![b"body".to_vec()]);
+
+            let input = FlightDataEncoderBuilder::new()
+                .build(stream::iter(vec![Ok(test_batch())]));
+            let echo = FlightDescriptor::new_path(vec!["echo".to_string()]);
+            let decoder = client.do_exchange(echo, input).await.unwrap();
+            let batches: Vec<_> = FlightRecordBatchStream::new(decoder)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(batches, vec![test_batch()]);
+
+            // A token can also be set directly
+            let mut client = FlightClient::new_from_inner(client.into_inner());
+            assert!(client.get_flight_info(descriptor.clone()).await.is_err());
+            client.set_bearer_token(TOKEN).unwrap();
+            client.get_flight_info(descriptor).await.unwrap();
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_metadata() {
+        run_service(TestFlightService::default(), |channel| async move {
+            let mut client = FlightClient::new(channel);
+            client.set_bearer_token(TOKEN).unwrap();
+            let descriptor = FlightDescriptor::new_path(vec!["a".to_string()]);
+
+            let mut metadata = MetadataMap::new();
+            metadata.insert(TRACE_ID, "1".parse().unwrap());
+            let mut traced = client.with_request_metadata(metadata);
+
+            // The request metadata is added to the client's own metadata
+            assert_eq!(traced.metadata().len(), 2);
+            assert_eq!(client.metadata().len(), 1);
+
+            traced.get_flight_info(descriptor.clone()).await.unwrap();
+            let response = traced.last_response_metadata().unwrap();
+            assert_eq!(response.get(TRACE_ID).unwrap(), "1");
+
+            // Metadata is only added to requests of the returned client
+            client.get_flight_info(descriptor).await.unwrap();
+            let response = client.last_response_metadata().unwrap();
+            assert!(response.get(TRACE_ID).is_none());
+
+            let action = Action {
+                r#type: "echo".to_string(),
+                body: b"body".to_vec(),
+            };
+            traced.do_action(action).await.unwrap();
+            let response = traced.last_response_metadata().unwrap();
+            assert_eq!(response.get(TRACE_ID).unwrap(), "1");
+            assert_eq!(response.get("grpc-status").unwrap(), "0");
+
+            let ticket = Ticket {
+                ticket: b"a".to_vec(),
+            };
+            let mut stream = traced.do_get(ticket).await.unwrap();
+            assert_eq!(stream.headers().get(TRACE_ID).unwrap(), "1");
+            assert!(stream.trailers().is_none());
+
+            while stream.try_next().await.unwrap().is_some() {}
+            let trailers = stream.trailers().unwrap();
+            assert_eq!(trailers.get("grpc-status").unwrap(), "0");
+        })
+        .await
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new(10)
+            .with_initial_backoff(Duration::from_millis(10))
+            .with_max_backoff(Duration::from_millis(100))
+            .with_multiplier(3);
+        assert_eq!(policy.backoff(0), Duration::from_millis(10));
+        assert_eq!(policy.backoff(1), Duration::from_millis(30));
+        assert_eq!(policy.backoff(2), Duration::from_millis(90));
+        assert_eq!(policy.backoff(3), Duration::from_millis(100));
+        assert_eq!(policy.backoff(usize::MAX), Duration::from_millis(100));
+
+        assert!(policy.is_retryable(&Status::unavailable("")));
+        assert!(!policy.is_retryable(&Status::internal("")));
+        assert_eq!(RetryPolicy::default().max_retries(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let service = TestFlightService::default();
+        let unavailable = service.unavailable.clone();
+        run_service(service, |channel| async move {
+            let mut client = FlightClient::new(channel);
+            client.set_bearer_token(TOKEN).unwrap();
+            let descriptor = FlightDescriptor::new_path(vec!["a".to_string()]);
+            let ticket = Ticket {
+                ticket: b"a".to_vec(),
+            };
+
+            // Calls are not retried by default
+            unavailable.store(1, Ordering::SeqCst);
+            let err = client
+                .get_flight_info(descriptor.clone())
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("try again"), "{}", err);
+
+            let policy =
+                RetryPolicy::new(2).with_initial_backoff(Duration::from_millis(1));
+            client.set_retry_policy(policy);
+
+            unavailable.store(2, Ordering::SeqCst);
+            let info = client.get_flight_info(descriptor.clone()).await.unwrap();
+            assert_eq!(info.total_records, 3);
+
+            unavailable.store(3, Ordering::SeqCst);
+            let err = client.get_flight_info(descriptor).await.unwrap_err();
+            assert!(err.to_string().contains("try again"), "{}", err);
+            assert_eq!(unavailable.load(Ordering::SeqCst), 0);
+
+            // A stream failing before its first message is requested again
+            unavailable.store(2, Ordering::SeqCst);
+            let batches: Vec<_> = client
+                .do_get(ticket)
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(batches, vec![test_batch()]);
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_get_flight_data() {
+        let service = TestFlightService::default();
+        let expired_tickets = service.expired_tickets.clone();
+        run_service(service, |channel| async move {
+            let mut client = FlightClient::new(channel);
+            client.set_bearer_token(TOKEN).unwrap();
+            let descriptor = FlightDescriptor::new_path(vec!["a".to_string()]);
+
+            let streams = client.get_flight_data(descriptor.clone()).await.unwrap();
+            assert_eq!(streams.len(), 1);
+            let batches: Vec<Vec<_>> = futures::future::try_join_all(
+                streams.into_iter().map(|s| s.try_collect()),
+            )
+            .await
+            .unwrap();
+            assert_eq!(batches, vec![vec![test_batch()]]);
+
+            // An expired ticket is not re-fetched by default
+            expired_tickets.store(1, Ordering::SeqCst);
+            let err = client
+                .get_flight_data(descriptor.clone())
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("ticket expired"), "{}", err);
+
+            let policy =
+                RetryPolicy::new(2).with_initial_backoff(Duration::from_millis(1));
+            client.set_retry_policy(policy);
+
+            // Retrying the same ticket would fail, the ticket is fetched again
+            expired_tickets.store(2, Ordering::SeqCst);
+            let mut streams = client.get_flight_data(descriptor.clone()).await.unwrap();
+            let batches: Vec<_> = streams.remove(0).try_collect().await.unwrap();
+            assert_eq!(batches, vec![test_batch()]);
+            assert_eq!(expired_tickets.load(Ordering::SeqCst), 0);
+
+            expired_tickets.store(3, Ordering::SeqCst);
+            let err = client.get_flight_data(descriptor).await.unwrap_err();
+            assert!(err.to_string().contains("ticket expired"), "{}", err);
+        })
+        .await
+    }
+}
@@ -0,0 +1,478 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decoding of a stream of [`FlightData`] into a stream of [`RecordBatch`]es
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::FlightData;
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_buffer::Buffer;
+use arrow_ipc::convert::fb_to_schema;
+use arrow_ipc::{reader, root_as_message, MessageHeader};
+use arrow_schema::{ArrowError, SchemaRef};
+use futures::stream::BoxStream;
+use futures::{ready, Stream, StreamExt};
+use tonic::metadata::MetadataMap;
+use tonic::{Status, Streaming};
+
+/// A [`Stream`] of [`RecordBatch`]es decoded from a stream of [`FlightData`],
+/// such as the response to a `DoGet` request
+///
+/// Use [`FlightDataDecoder`] to also access the schema and `app_metadata` of
+/// each message
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+/// # use arrow_flight::decode::FlightRecordBatchStream;
+/// # use arrow_flight::encode::FlightDataEncoderBuilder;
+/// # use futures::{stream, TryStreamExt};
+/// # async fn run() -> Result<(), arrow_schema::ArrowError> {
+/// # let a = Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef;
+/// # let batch = RecordBatch::try_from_iter(vec![("a", a)])?;
+/// let flight_data = FlightDataEncoderBuilder::new().build(stream::iter(vec![Ok(batch)]));
+///
+/// let batches: Vec<RecordBatch> = FlightRecordBatchStream::new_from_flight_data(flight_data)
+///     .try_collect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FlightRecordBatchStream {
+    inner: FlightDataDecoder,
+}
+
+impl FlightRecordBatchStream {
+    /// Creates a new [`FlightRecordBatchStream`] from a [`FlightDataDecoder`]
+    pub fn new(inner: FlightDataDecoder) -> Self {
+        Self { inner }
+    }
+
+    /// Creates a new [`FlightRecordBatchStream`] decoding the [`FlightData`] of `inner`
+    ///
+    /// A `tonic::Streaming<FlightData>` can be adapted with
+    /// `map_err(|e| ArrowError::ExternalError(Box::new(e)))`
+    pub fn new_from_flight_data<S>(inner: S) -> Self
+    where
+        S: Stream<Item = Result<FlightData, ArrowError>> + Send + 'static,
+    {
+        Self::new(FlightDataDecoder::new(inner))
+    }
+
+    /// Returns the schema of the stream, if it has been received
+    pub fn schema(&self) -> Option<&SchemaRef> {
+        self.inner.schema()
+    }
+
+    /// Returns the headers of the response, see [`FlightDataDecoder::headers`]
+    pub fn headers(&self) -> &MetadataMap {
+        self.inner.headers()
+    }
+
+    /// Returns the trailers of the response, see [`FlightDataDecoder::trailers`]
+    pub fn trailers(&self) -> Option<MetadataMap> {
+        self.inner.trailers()
+    }
+
+    /// Consumes this stream, returning the wrapped [`FlightDataDecoder`]
+    pub fn into_inner(self) -> FlightDataDecoder {
+        self.inner
+    }
+}
+
+impl Stream for FlightRecordBatchStream {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            let decoded = match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(decoded)) => decoded,
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            };
+            if let DecodedPayload::RecordBatch(batch) = decoded.payload {
+                return Poll::Ready(Some(Ok(batch)));
+            }
+        }
+    }
+}
+
+/// A [`Stream`] of [`DecodedFlightData`] decoded from a stream of [`FlightData`]
+///
+/// Dictionary messages are consumed internally and used to decode the record
+/// batches that follow them. Any other message, including those without an IPC
+/// header, is returned along with its decoded [`DecodedPayload`]
+pub struct FlightDataDecoder {
+    /// The input stream
+    response: BoxStream<'static, Result<FlightData, ArrowError>>,
+    /// The schema and dictionaries received so far
+    state: Option<FlightStreamState>,
+    /// Whether the input has finished or an error has been returned
+    done: bool,
+    /// The headers of the response
+    headers: MetadataMap,
+    /// The trailers of the response, available once it has finished
+    trailers: Option<LazyTrailers>,
+}
+
+impl std::fmt::Debug for FlightDataDecoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlightDataDecoder")
+            .field("state", &self.state)
+            .field("done", &self.done)
+            .field("headers", &self.headers)
+            .field("trailers", &self.trailers)
+            .finish()
+    }
+}
+
+impl FlightDataDecoder {
+    /// Creates a new [`FlightDataDecoder`] decoding the [`FlightData`] of `response`
+    pub fn new<S>(response: S) -> Self
+    where
+        S: Stream<Item = Result<FlightData, ArrowError>> + Send + 'static,
+    {
+        Self {
+            response: response.boxed(),
+            state: None,
+            done: false,
+            headers: MetadataMap::new(),
+            trailers: None,
+        }
+    }
+
+    /// Sets the headers of the response, returned by [`Self::headers`]
+    pub fn with_headers(mut self, headers: MetadataMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets the trailers of the response, returned by [`Self::trailers`]
+    pub(crate) fn with_trailers(mut self, trailers: LazyTrailers) -> Self {
+        self.trailers = Some(trailers);
+        self
+    }
+
+    /// Returns the schema of the stream, if it has been received
+    pub fn schema(&self) -> Option<&SchemaRef> {
+        self.state.as_ref().map(|state| &state.schema)
+    }
+
+    /// Returns the headers of the gRPC response, which are empty if this
+    /// decoder was not created by a [`FlightClient`](crate::FlightClient)
+    pub fn headers(&self) -> &MetadataMap {
+        &self.headers
+    }
+
+    /// Returns the trailers of the gRPC response, which are only available
+    /// once the stream has been read to completion
+    pub fn trailers(&self) -> Option<MetadataMap> {
+        self.trailers.as_ref().and_then(|trailers| trailers.get())
+    }
+
+    /// Decodes `data`, returning `None` if it was a dictionary message
+    fn extract_message(
+        &mut self,
+        data: FlightData,
+    ) -> Result<Option<DecodedFlightData>, ArrowError> {
+        if data.data_header.is_empty() {
+            return Ok(Some(DecodedFlightData::new_none(data)));
+        }
+
+        let message = root_as_message(&data.data_header[..]).map_err(|err| {
+            ArrowError::ParseError(format!("Unable to get root as message: {:?}", err))
+        })?;
+
+        match message.header_type() {
+            MessageHeader::NONE => Ok(Some(DecodedFlightData::new_none(data))),
+            MessageHeader::Schema => {
+                let schema = message.header_as_schema().ok_or_else(|| {
+                    ArrowError::ParseError(
+                        "Unable to convert flight data header to a schema".to_string(),
+                    )
+                })?;
+                let schema = Arc::new(fb_to_schema(schema));
+
+                self.state = Some(FlightStreamState {
+                    schema: schema.clone(),
+                    dictionaries_by_id: HashMap::new(),
+                });
+                Ok(Some(DecodedFlightData::new_schema(data, schema)))
+            }
+            MessageHeader::DictionaryBatch => {
+                let state = self.state.as_mut().ok_or_else(|| {
+                    ArrowError::ParseError(
+                        "Received DictionaryBatch prior to Schema".to_string(),
+                    )
+                })?;
+                let dictionary =
+                    message.header_as_dictionary_batch().ok_or_else(|| {
+                        ArrowError::ParseError(
+                            "Unable to convert flight data header to a dictionary batch"
+                                .to_string(),
+                        )
+                    })?;
+                reader::read_dictionary(
+                    &Buffer::from(&data.data_body),
+                    dictionary,
+                    &state.schema,
+                    &mut state.dictionaries_by_id,
+                    &message.version(),
+                )?;
+                Ok(None)
+            }
+            MessageHeader::RecordBatch => {
+                let state = self.state.as_ref().ok_or_else(|| {
+                    ArrowError::ParseError(
+                        "Received RecordBatch prior to Schema".to_string(),
+                    )
+                })?;
+                let batch = message.header_as_record_batch().ok_or_else(|| {
+                    ArrowError::ParseError(
+                        "Unable to convert flight data header to a record batch"
+                            .to_string(),
+                    )
+                })?;
+                let batch = reader::read_record_batch(
+                    &Buffer::from(&data.data_body),
+                    batch,
+                    state.schema.clone(),
+                    &state.dictionaries_by_id,
+                    None,
+                    &message.version(),
+                )?;
+                Ok(Some(DecodedFlightData::new_record_batch(data, batch)))
+            }
+            other => Err(ArrowError::NotYetImplemented(format!(
+                "Unsupported flight data message header: {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+impl Stream for FlightDataDecoder {
+    type Item = Result<DecodedFlightData, ArrowError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            let data = match ready!(self.response.poll_next_unpin(cx)) {
+                Some(Ok(data)) => data,
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => {
+                    self.done = true;
+                    return Poll::Ready(None);
+                }
+            };
+
+            match self.extract_message(data) {
+                Ok(Some(decoded)) => return Poll::Ready(Some(Ok(decoded))),
+                Ok(None) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+/// The trailers of a gRPC response, which are received after its last message
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LazyTrailers(Arc<Mutex<Option<MetadataMap>>>);
+
+impl LazyTrailers {
+    fn get(&self) -> Option<MetadataMap> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Adapts the `response` of a streaming gRPC call, returning a stream of its
+/// messages and the [`LazyTrailers`] set once this stream has finished
+pub(crate) fn extract_lazy_trailers<T>(
+    response: Streaming<T>,
+) -> (BoxStream<'static, Result<T, Status>>, LazyTrailers)
+where
+    T: Send + 'static,
+{
+    let trailers = LazyTrailers::default();
+    let captured = trailers.clone();
+    let stream = futures::stream::unfold(Some(response), move |response| {
+        let captured = captured.clone();
+        async move {
+            let mut response = response?;
+            match response.message().await {
+                Ok(Some(message)) => Some((Ok(message), Some(response))),
+                Ok(None) => {
+                    if let Ok(Some(trailers)) = response.trailers().await {
+                        *captured.0.lock().unwrap() = Some(trailers);
+                    }
+                    None
+                }
+                Err(status) => Some((Err(status), None)),
+            }
+        }
+    });
+    (stream.boxed(), trailers)
+}
+
+/// The schema and dictionaries of a [`FlightDataDecoder`]
+#[derive(Debug)]
+struct FlightStreamState {
+    schema: SchemaRef,
+    dictionaries_by_id: HashMap<i64, ArrayRef>,
+}
+
+/// A [`FlightData`] along with its decoded [`DecodedPayload`]
+#[derive(Debug)]
+pub struct DecodedFlightData {
+    /// The original message, which may contain `app_metadata`
+    pub inner: FlightData,
+    /// The decoded contents of the message
+    pub payload: DecodedPayload,
+}
+
+impl DecodedFlightData {
+    fn new_none(inner: FlightData) -> Self {
+        Self {
+            inner,
+            payload: DecodedPayload::None,
+        }
+    }
+
+    fn new_schema(inner: FlightData, schema: SchemaRef) -> Self {
+        Self {
+            inner,
+            payload: DecodedPayload::Schema(schema),
+        }
+    }
+
+    fn new_record_batch(inner: FlightData, batch: RecordBatch) -> Self {
+        Self {
+            inner,
+            payload: DecodedPayload::RecordBatch(batch),
+        }
+    }
+
+    /// Returns the `app_metadata` of the message
+    pub fn app_metadata(&self) -> &[u8] {
+        &self.inner.app_metadata
+    }
+}
+
+/// The decoded contents of a [`FlightData`] message
+#[derive(Debug)]
+pub enum DecodedPayload {
+    /// A message without an IPC header, such as one carrying only `app_metadata`
+    None,
+    /// A schema, which applies to all subsequent record batches
+    Schema(SchemaRef),
+    /// A record batch
+    RecordBatch(RecordBatch),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode::FlightDataEncoderBuilder;
+    use arrow_array::types::Int8Type;
+    use arrow_array::{DictionaryArray, StringArray};
+    use futures::{stream, TryStreamExt};
+
+    #[tokio::test]
+    async fn test_decode() {
+        let dict: DictionaryArray<Int8Type> = vec!["x", "y", "x"].into_iter().collect();
+        let strings = StringArray::from(vec!["a", "b", "c"]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("dict", Arc::new(dict) as ArrayRef),
+            ("strings", Arc::new(strings) as ArrayRef),
+        ])
+        .unwrap();
+        let batches = vec![batch.clone(), batch.slice(1, 2)];
+
+        let encoder = FlightDataEncoderBuilder::new()
+            .with_metadata(b"meta".to_vec())
+            .build(stream::iter(batches.clone().into_iter().map(Ok)));
+        let metadata = FlightData {
+            app_metadata: b"trailer".to_vec(),
+            ..Default::default()
+        };
+        let input = encoder.chain(stream::iter(vec![Ok(metadata)]));
+
+        let decoded: Vec<_> = FlightDataDecoder::new(input).try_collect().await.unwrap();
+        assert_eq!(decoded.len(), 4);
+        assert!(
+            matches!(&decoded[0].payload, DecodedPayload::Schema(s) if *s == batch.schema())
+        );
+        assert_eq!(decoded[0].app_metadata(), b"meta");
+        assert!(
+            matches!(&decoded[1].payload, DecodedPayload::RecordBatch(b) if *b == batches[0])
+        );
+        assert!(
+            matches!(&decoded[2].payload, DecodedPayload::RecordBatch(b) if *b == batches[1])
+        );
+        assert!(matches!(decoded[3].payload, DecodedPayload::None));
+        assert_eq!(decoded[3].app_metadata(), b"trailer");
+
+        let encoder =
+            FlightDataEncoderBuilder::new().build(stream::iter(vec![Ok(batch.clone())]));
+        let mut stream = FlightRecordBatchStream::new_from_flight_data(encoder);
+        assert!(stream.schema().is_none());
+        let actual: Vec<_> = (&mut stream).try_collect().await.unwrap();
+        assert_eq!(actual, vec![batch.clone()]);
+        assert_eq!(stream.schema(), Some(&batch.schema()));
+    }
+
+    #[tokio::test]
+    async fn test_decode_without_schema() {
+        let batch = RecordBatch::try_from_iter(vec![(
+            "a",
+            Arc::new(StringArray::from(vec!["a"])) as ArrayRef,
+        )])
+        .unwrap();
+        let flight_data: Vec<_> = FlightDataEncoderBuilder::new()
+            .build(stream::iter(vec![Ok(batch)]))
+            .try_collect()
+            .await
+            .unwrap();
+
+        let input = stream::iter(flight_data.into_iter().skip(1).map(Ok));
+        let err = FlightRecordBatchStream::new_from_flight_data(input)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("prior to Schema"), "{}", err);
+    }
+}
@@ -0,0 +1,559 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encoding of a stream of [`RecordBatch`]es into a stream of [`FlightData`]
+
+use std::collections::{HashSet, VecDeque};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::{FlightData, SchemaAsIpc};
+use arrow_array::RecordBatch;
+use arrow_ipc::writer::{DictionaryTracker, IpcDataGenerator, IpcWriteOptions};
+use arrow_schema::{ArrowError, Field, Schema, SchemaRef};
+use futures::stream::BoxStream;
+use futures::{ready, Stream, StreamExt};
+
+/// Default target size for encoded [`FlightData`], gRPC servers such as tonic
+/// reject messages larger than 4MB by default
+pub const GRPC_TARGET_MAX_FLIGHT_SIZE_BYTES: usize = 2097152;
+
+/// Creates a [`FlightDataEncoder`], which converts a stream of [`RecordBatch`]es
+/// into a stream of [`FlightData`], emitting the schema and any dictionaries
+/// ahead of the batches that need them
+///
+/// Dictionaries are tracked for the whole stream: a dictionary is only sent again
+/// if its values change, as a delta if the new values extend those last sent,
+/// and otherwise as a replacement
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+/// # use arrow_flight::encode::FlightDataEncoderBuilder;
+/// # use futures::{stream, TryStreamExt};
+/// # async fn run() -> Result<(), arrow_schema::ArrowError> {
+/// let a = Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef;
+/// let batch = RecordBatch::try_from_iter(vec![("a", a)])?;
+///
+/// let flight_data: Vec<_> = FlightDataEncoderBuilder::new()
+///     .build(stream::iter(vec![Ok(batch)]))
+///     .try_collect()
+///     .await?;
+/// // The schema followed by the batch
+/// assert_eq!(flight_data.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FlightDataEncoderBuilder {
+    /// The target size of the encoded `FlightData` messages
+    max_flight_data_size: usize,
+    /// Options used to encode the IPC messages
+    options: IpcWriteOptions,
+    /// Metadata attached to the schema message
+    app_metadata: Vec<u8>,
+    /// The schema to send, if known ahead of the first batch
+    schema: Option<SchemaRef>,
+}
+
+impl Default for FlightDataEncoderBuilder {
+    fn default() -> Self {
+        Self {
+            max_flight_data_size: GRPC_TARGET_MAX_FLIGHT_SIZE_BYTES,
+            options: IpcWriteOptions::default(),
+            app_metadata: vec![],
+            schema: None,
+        }
+    }
+}
+
+impl FlightDataEncoderBuilder {
+    /// Creates a new [`FlightDataEncoderBuilder`] with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum encoded size of each [`FlightData`] containing a
+    /// [`RecordBatch`]. Defaults to [`GRPC_TARGET_MAX_FLIGHT_SIZE_BYTES`]
+    ///
+    /// Batches that encode larger than this size, after any compression, are
+    /// split into slices of rows sent in several messages, so that they are not
+    /// rejected by the transport. A single row larger than this size is still
+    /// sent in one message, as are dictionaries
+    pub fn with_max_flight_data_size(mut self, max_flight_data_size: usize) -> Self {
+        self.max_flight_data_size = max_flight_data_size;
+        self
+    }
+
+    /// Sets the [`IpcWriteOptions`] used to encode the messages
+    ///
+    /// Record batches and dictionaries are compressed if the options specify a
+    /// compression codec with [`IpcWriteOptions::try_with_compression`], which
+    /// requires the `ipc_compression` feature. [`FlightDataDecoder`] decompresses
+    /// such messages automatically
+    ///
+    /// [`FlightDataDecoder`]: crate::decode::FlightDataDecoder
+    pub fn with_options(mut self, options: IpcWriteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the `app_metadata` of the [`FlightData`] containing the schema
+    pub fn with_metadata(mut self, app_metadata: Vec<u8>) -> Self {
+        self.app_metadata = app_metadata;
+        self
+    }
+
+    /// Sets the schema to send, which is then sent even if the input contains
+    /// no [`RecordBatch`]es. Otherwise the schema of the first batch is sent
+    pub fn with_schema(mut self, schema: SchemaRef) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Returns a [`FlightDataEncoder`] that encodes the [`RecordBatch`]es of `input`
+    pub fn build<S>(self, input: S) -> FlightDataEncoder
+    where
+        S: Stream<Item = Result<RecordBatch, ArrowError>> + Send + 'static,
+    {
+        let mut encoder = FlightDataEncoder {
+            inner: input.boxed(),
+            schema: None,
+            max_flight_data_size: self.max_flight_data_size,
+            options: self.options,
+            app_metadata: Some(self.app_metadata),
+            generator: IpcDataGenerator::default(),
+            dictionary_tracker: DictionaryTracker::new(false),
+            queue: VecDeque::new(),
+            done: false,
+        };
+        if let Some(schema) = self.schema {
+            encoder.encode_schema(schema);
+        }
+        encoder
+    }
+}
+
+/// A [`Stream`] of [`FlightData`] encoded from a stream of [`RecordBatch`]es,
+/// created by [`FlightDataEncoderBuilder`]
+pub struct FlightDataEncoder {
+    /// The input stream
+    inner: BoxStream<'static, Result<RecordBatch, ArrowError>>,
+    /// The schema that has been sent, if any
+    schema: Option<SchemaRef>,
+    /// The target size of the encoded `FlightData` messages
+    max_flight_data_size: usize,
+    /// Options used to encode the IPC messages
+    options: IpcWriteOptions,
+    /// Metadata to attach to the schema message, taken when it is sent
+    app_metadata: Option<Vec<u8>>,
+    /// Encodes the IPC messages
+    generator: IpcDataGenerator,
+    /// Tracks the dictionaries that have been sent
+    dictionary_tracker: DictionaryTracker,
+    /// Messages that have been encoded but not yet returned
+    queue: VecDeque<FlightData>,
+    /// Whether the input or this stream has finished, possibly with an error
+    done: bool,
+}
+
+impl std::fmt::Debug for FlightDataEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlightDataEncoder")
+            .field("schema", &self.schema)
+            .field("max_flight_data_size", &self.max_flight_data_size)
+            .field("queue", &self.queue.len())
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl FlightDataEncoder {
+    /// Queues the schema message for `schema`
+    fn encode_schema(&mut self, schema: SchemaRef) {
+        let schema = Arc::new(prepare_schema_for_flight(&schema));
+        let mut schema_data: FlightData = SchemaAsIpc::new(&schema, &self.options).into();
+        schema_data.app_metadata = self.app_metadata.take().unwrap_or_default();
+        self.queue.push_back(schema_data);
+        self.schema = Some(schema);
+    }
+
+    /// Queues the messages for `batch`, splitting it up if it encodes larger than
+    /// `max_flight_data_size`, preceded by the schema if not yet sent
+    fn encode_batch(&mut self, batch: RecordBatch) -> Result<(), ArrowError> {
+        match &self.schema {
+            Some(schema) if schema.fields() != batch.schema().fields() => {
+                return Err(ArrowError::SchemaError(format!(
+                    "Cannot encode RecordBatch with schema {} in a stream with schema {}",
+                    batch.schema(),
+                    schema
+                )));
+            }
+            Some(_) => {}
+            None => self.encode_schema(batch.schema()),
+        }
+
+        // Use the dictionary ids of the schema that was sent
+        let schema = self.schema.clone().expect("schema sent above");
+        let batch = RecordBatch::try_new(schema, batch.columns().to_vec())?;
+
+        // Slices still to encode, in reverse order
+        let mut pending = vec![batch];
+        while let Some(batch) = pending.pop() {
+            let (dictionaries, encoded) = self.generator.encoded_batch(
+                &batch,
+                &mut self.dictionary_tracker,
+                &self.options,
+            )?;
+            // The dictionaries are marked as sent, and apply to all slices of `batch`
+            self.queue
+                .extend(dictionaries.into_iter().map(FlightData::from));
+
+            let size = encoded.ipc_message.len() + encoded.arrow_data.len();
+            if size > self.max_flight_data_size && batch.num_rows() > 1 {
+                let slices = split_batch(&batch, size, self.max_flight_data_size);
+                pending.extend(slices.into_iter().rev());
+                continue;
+            }
+            self.queue.push_back(encoded.into());
+        }
+        Ok(())
+    }
+}
+
+impl Stream for FlightDataEncoder {
+    type Item = Result<FlightData, ArrowError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(data) = self.queue.pop_front() {
+                return Poll::Ready(Some(Ok(data)));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    if let Err(e) = self.encode_batch(batch) {
+                        self.done = true;
+                        self.queue.clear();
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => self.done = true,
+            }
+        }
+    }
+}
+
+/// Returns `schema` with its top-level dictionary fields assigned distinct
+/// dictionary ids, keeping any ids that are already distinct
+///
+/// Fields created with [`Field::new`] all have the dictionary id 0, and so would
+/// otherwise overwrite each other's dictionaries on the receiving side. The ids
+/// of nested dictionaries are those of the data type of their array, and are
+/// left unchanged
+pub(crate) fn prepare_schema_for_flight(schema: &Schema) -> Schema {
+    let top_level = schema.fields();
+    let mut used: HashSet<i64> = schema
+        .all_fields()
+        .into_iter()
+        .filter(|f| !top_level.iter().any(|t| std::ptr::eq(t, *f)))
+        .filter_map(|f| f.dict_id())
+        .collect();
+    let mut next_id = 0;
+
+    let fields = top_level
+        .iter()
+        .map(|field| match field.dict_id() {
+            Some(dict_id) if used.insert(dict_id) => field.clone(),
+            Some(_) => {
+                while used.contains(&next_id) {
+                    next_id += 1;
+                }
+                used.insert(next_id);
+                Field::new_dict(
+                    field.name(),
+                    field.data_type().clone(),
+                    field.is_nullable(),
+                    next_id,
+                    field.dict_is_ordered().unwrap_or_default(),
+                )
+                .with_metadata(field.metadata().clone())
+            }
+            None => field.clone(),
+        })
+        .collect();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+/// Splits `batch`, which encodes to `size` bytes, into at least two slices of
+/// at least one row, each expected to encode to at most `max_flight_data_size`
+/// bytes if the rows are of similar size
+fn split_batch(
+    batch: &RecordBatch,
+    size: usize,
+    max_flight_data_size: usize,
+) -> Vec<RecordBatch> {
+    let num_rows = batch.num_rows();
+    let num_slices = (size + max_flight_data_size - 1) / max_flight_data_size.max(1);
+    let num_slices = num_slices.clamp(2, num_rows.max(2));
+
+    let rows_per_slice = (num_rows + num_slices - 1) / num_slices;
+    (0..num_rows)
+        .step_by(rows_per_slice)
+        .map(|offset| batch.slice(offset, rows_per_slice.min(num_rows - offset)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::flight_data_to_batches;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{ArrayRef, DictionaryArray, Int64Array, StringArray};
+    use futures::{stream, TryStreamExt};
+
+    fn make_batch(values: Vec<i64>, dict: Vec<&str>) -> RecordBatch {
+        let dict: DictionaryArray<Int32Type> = dict.into_iter().collect();
+        RecordBatch::try_from_iter(vec![
+            ("a", Arc::new(Int64Array::from(values)) as ArrayRef),
+            ("b", Arc::new(dict) as ArrayRef),
+        ])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_encode_dictionaries() {
+        let b1 = make_batch(vec![1, 2, 3], vec!["a", "b", "a"]);
+        let b2 = make_batch(vec![4, 5, 6], vec!["a", "b", "b"]);
+        let b3 = make_batch(vec![7, 8, 9], vec!["c", "c", "d"]);
+        let batches = vec![b1, b2, b3];
+
+        let flight_data: Vec<_> = FlightDataEncoderBuilder::new()
+            .build(stream::iter(batches.clone().into_iter().map(Ok)))
+            .try_collect()
+            .await
+            .unwrap();
+
+        // schema, dictionary, batch, batch, dictionary, batch
+        assert_eq!(flight_data.len(), 6);
+        let decoded = flight_data_to_batches(&flight_data).unwrap();
+        assert_eq!(decoded, batches);
+    }
+
+    #[tokio::test]
+    async fn test_encode_changing_dictionaries() {
+        fn make_batch(a: Vec<&str>, b: Vec<&str>) -> RecordBatch {
+            let a: DictionaryArray<Int32Type> = a.into_iter().collect();
+            let b: DictionaryArray<Int32Type> = b.into_iter().collect();
+            RecordBatch::try_from_iter(vec![
+                ("a", Arc::new(a) as ArrayRef),
+                ("b", Arc::new(b) as ArrayRef),
+            ])
+            .unwrap()
+        }
+
+        let batches = vec![
+            make_batch(vec!["a", "b", "a"], vec!["x", "x", "x"]),
+            // a is extended with a delta, b is unchanged
+            make_batch(vec!["a", "b", "c"], vec!["x", "x", "x"]),
+            // a is unchanged, b is replaced
+            make_batch(vec!["a", "b", "c"], vec!["y", "z", "y"]),
+        ];
+
+        let flight_data: Vec<_> = FlightDataEncoderBuilder::new()
+            .build(stream::iter(batches.clone().into_iter().map(Ok)))
+            .try_collect()
+            .await
+            .unwrap();
+
+        let dictionaries: Vec<_> = flight_data
+            .iter()
+            .filter_map(|d| {
+                let message = arrow_ipc::root_as_message(&d.data_header).unwrap();
+                message
+                    .header_as_dictionary_batch()
+                    .map(|d| (d.id(), d.isDelta()))
+            })
+            .collect();
+        assert_eq!(
+            dictionaries,
+            vec![(0, false), (1, false), (0, true), (1, false)]
+        );
+
+        let decoded = flight_data_to_batches(&flight_data).unwrap();
+        assert_eq!(decoded, batches);
+    }
+
+    #[tokio::test]
+    async fn test_encode_split() {
+        let batch = make_batch((0..1000).collect(), vec!["a"; 1000]);
+        let size = batch.columns()[0].get_array_memory_size();
+
+        let flight_data: Vec<_> = FlightDataEncoderBuilder::new()
+            .with_max_flight_data_size(size / 4)
+            .build(stream::iter(vec![Ok(batch.clone())]))
+            .try_collect()
+            .await
+            .unwrap();
+
+        let decoded = flight_data_to_batches(&flight_data).unwrap();
+        assert!(decoded.len() > 1);
+        assert!(decoded.iter().all(|b| b.num_rows() < 1000));
+        assert_eq!(
+            decoded.iter().map(|b| b.num_rows()).sum::<usize>(),
+            batch.num_rows()
+        );
+        let values: Vec<i64> = decoded
+            .iter()
+            .flat_map(|b| {
+                let a = b.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+                a.values().to_vec()
+            })
+            .collect();
+        assert_eq!(values, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_encode_split_variable_rows() {
+        // The last rows are much larger than the first, requiring further splits
+        let values: Vec<String> = (0..100)
+            .map(|i| {
+                if i < 90 {
+                    "a".to_string()
+                } else {
+                    "b".repeat(1000)
+                }
+            })
+            .collect();
+        let a = Arc::new(StringArray::from_iter_values(&values)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter(vec![("a", a)]).unwrap();
+
+        let max_flight_data_size = 2500;
+        let flight_data: Vec<_> = FlightDataEncoderBuilder::new()
+            .with_max_flight_data_size(max_flight_data_size)
+            .build(stream::iter(vec![Ok(batch.clone())]))
+            .try_collect()
+            .await
+            .unwrap();
+
+        // The schema and at least 4 batches
+        assert!(flight_data.len() > 4);
+        for data in &flight_data[1..] {
+            let size = data.data_header.len() + data.data_body.len();
+            assert!(size <= max_flight_data_size, "{}", size);
+        }
+
+        let decoded = flight_data_to_batches(&flight_data).unwrap();
+        let decoded: Vec<_> = decoded
+            .iter()
+            .flat_map(|b| {
+                let a = b.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+                a.iter().map(|v| v.unwrap().to_string()).collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(decoded, values);
+    }
+
+    #[cfg(feature = "ipc_compression")]
+    #[tokio::test]
+    async fn test_encode_compressed() {
+        use crate::decode::FlightRecordBatchStream;
+        use arrow_ipc::CompressionType;
+
+        let batch = make_batch(vec![7; 4096], vec!["a"; 4096]);
+        // The limit applies to the compressed size
+        let encode = |options: IpcWriteOptions| {
+            FlightDataEncoderBuilder::new()
+                .with_max_flight_data_size(16 * 1024)
+                .with_options(options)
+                .build(stream::iter(vec![Ok(batch.clone())]))
+                .try_collect::<Vec<_>>()
+        };
+        let body_len =
+            |data: &[FlightData]| data.iter().map(|d| d.data_body.len()).sum::<usize>();
+
+        let uncompressed = encode(IpcWriteOptions::default()).await.unwrap();
+        // The schema, the dictionary and several batches
+        assert!(uncompressed.len() > 3);
+        for compression in [CompressionType::LZ4_FRAME, CompressionType::ZSTD] {
+            let options = IpcWriteOptions::default()
+                .try_with_compression(Some(compression))
+                .unwrap();
+            let compressed = encode(options).await.unwrap();
+            assert!(body_len(&compressed) < body_len(&uncompressed) / 4);
+
+            let decoded: Vec<_> = FlightRecordBatchStream::new_from_flight_data(
+                stream::iter(compressed.into_iter().map(Ok)),
+            )
+            .try_collect()
+            .await
+            .unwrap();
+            assert_eq!(decoded, vec![batch.clone()]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encode_schema_only() {
+        let batch = make_batch(vec![], vec![]);
+        let flight_data: Vec<_> = FlightDataEncoderBuilder::new()
+            .with_schema(batch.schema())
+            .with_metadata(b"meta".to_vec())
+            .build(stream::empty())
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(flight_data.len(), 1);
+        assert_eq!(flight_data[0].app_metadata, b"meta".to_vec());
+        assert_eq!(
+            SchemaRef::new((&flight_data[0]).try_into().unwrap()),
+            batch.schema()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encode_schema_mismatch() {
+        let b1 = make_batch(vec![1], vec!["a"]);
+        let b2 = RecordBatch::try_from_iter(vec![(
+            "a",
+            Arc::new(Int64Array::from(vec![1])) as ArrayRef,
+        )])
+        .unwrap();
+
+        let err = FlightDataEncoderBuilder::new()
+            .build(stream::iter(vec![Ok(b1), Ok(b2)]))
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Cannot encode RecordBatch"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,277 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Helpers for the bidirectional `DoExchange` call
+//!
+//! A `DoExchange` request is a stream of [`FlightData`], the first of which carries
+//! the [`FlightDescriptor`] identifying the exchange, and is typically followed by a
+//! schema message and the encoded record batches, see
+//! [`FlightDataEncoderBuilder`](crate::encode::FlightDataEncoderBuilder). The
+//! response is decoded with a [`FlightDataDecoder`], which returns each message
+//! along with its `app_metadata`, in the order sent by the server.
+//!
+//! On the client, [`do_exchange`] attaches the descriptor and reports errors of the
+//! outgoing stream, which tonic would otherwise discard, on the response stream.
+//! On the server, [`exchange_request`] extracts the descriptor and decodes the
+//! incoming stream, and [`exchange_response`] adapts an outgoing stream to the
+//! type expected by [`FlightService`](crate::flight_service_server::FlightService).
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::decode::{extract_lazy_trailers, FlightDataDecoder};
+use crate::flight_service_client::FlightServiceClient;
+use crate::middleware::CallTracker;
+use crate::{status_to_arrow_error, FlightData, FlightDescriptor};
+use arrow_schema::ArrowError;
+use futures::stream::{self, BoxStream};
+use futures::{Stream, StreamExt, TryStreamExt};
+use tonic::metadata::MetadataMap;
+use tonic::transport::Channel;
+use tonic::{Request, Status, Streaming};
+
+/// Performs a `DoExchange` call identified by `descriptor`, sending the
+/// [`FlightData`] of `input` and returning a [`FlightDataDecoder`] of the response
+///
+/// The descriptor is attached to the first message of `input`, or sent on its own
+/// if `input` is empty. If `input` returns an error, no further messages are sent
+/// and the error is returned instead of any error the server responds with, or
+/// otherwise at the end of the response stream.
+pub async fn do_exchange<S>(
+    client: &mut FlightServiceClient<Channel>,
+    descriptor: FlightDescriptor,
+    input: S,
+) -> Result<FlightDataDecoder, ArrowError>
+where
+    S: Stream<Item = Result<FlightData, ArrowError>> + Send + 'static,
+{
+    do_exchange_with_metadata(client, MetadataMap::new(), descriptor, input, None).await
+}
+
+/// Performs a `DoExchange` call as [`do_exchange`], with the request `metadata`,
+/// tracked by `call` if any
+pub(crate) async fn do_exchange_with_metadata<S>(
+    client: &mut FlightServiceClient<Channel>,
+    metadata: MetadataMap,
+    descriptor: FlightDescriptor,
+    input: S,
+    call: Option<CallTracker>,
+) -> Result<FlightDataDecoder, ArrowError>
+where
+    S: Stream<Item = Result<FlightData, ArrowError>> + Send + 'static,
+{
+    let error = Arc::new(Mutex::new(None));
+    let mut request = Request::new(FallibleRequestStream {
+        inner: input.boxed(),
+        descriptor: Some(descriptor),
+        error: error.clone(),
+        done: false,
+    });
+    *request.metadata_mut() = metadata;
+
+    let response = match client.do_exchange(request).await {
+        Ok(response) => response,
+        Err(status) => {
+            if let Some(call) = call {
+                call.finish(status.code(), 0, 0);
+            }
+            return Err(
+                take_error(&error).unwrap_or_else(|| status_to_arrow_error(status))
+            );
+        }
+    };
+
+    let headers = response.metadata().clone();
+    let (response, trailers) = extract_lazy_trailers(response.into_inner());
+    let response = match call {
+        Some(call) => call.observe(response),
+        None => response,
+    };
+
+    // An error of the input takes precedence over the error it causes on the server
+    let response_error = error.clone();
+    let trailer = stream::once(async move { take_error(&error) })
+        .filter_map(|e| futures::future::ready(e.map(Err)));
+    let response = response
+        .map_err(move |status| {
+            take_error(&response_error).unwrap_or_else(|| status_to_arrow_error(status))
+        })
+        .chain(trailer);
+    Ok(FlightDataDecoder::new(response)
+        .with_headers(headers)
+        .with_trailers(trailers))
+}
+
+/// Extracts the [`FlightDescriptor`] of a `DoExchange` request received by a
+/// server, returning it along with a [`FlightDataDecoder`] of the request
+///
+/// The first message, which carries the descriptor, is also decoded, and so any
+/// schema, record batch or `app_metadata` it contains is returned by the decoder
+pub async fn exchange_request(
+    request: Request<Streaming<FlightData>>,
+) -> Result<(FlightDescriptor, FlightDataDecoder), Status> {
+    let mut request = request.into_inner();
+    let mut first = request
+        .message()
+        .await?
+        .ok_or_else(|| Status::invalid_argument("do_exchange: empty request stream"))?;
+    let descriptor = first.flight_descriptor.take().ok_or_else(|| {
+        Status::invalid_argument("do_exchange: missing flight descriptor")
+    })?;
+
+    let input = stream::once(async move { Ok(first) })
+        .chain(request.map_err(status_to_arrow_error));
+    Ok((descriptor, FlightDataDecoder::new(input)))
+}
+
+/// Converts a stream of [`FlightData`], such as a
+/// [`FlightDataEncoder`](crate::encode::FlightDataEncoder), into a stream
+/// suitable for the response of a `DoExchange` call
+pub fn exchange_response<S>(
+    output: S,
+) -> Pin<Box<dyn Stream<Item = Result<FlightData, Status>> + Send + 'static>>
+where
+    S: Stream<Item = Result<FlightData, ArrowError>> + Send + 'static,
+{
+    Box::pin(output.map_err(|e| Status::internal(e.to_string())))
+}
+
+fn take_error(error: &Mutex<Option<ArrowError>>) -> Option<ArrowError> {
+    error.lock().unwrap().take()
+}
+
+/// The outgoing stream of a `DoExchange` call, which attaches the descriptor to
+/// the first message and stores any error of the input for [`do_exchange`] to
+/// report, as a tonic request stream cannot return an error itself
+struct FallibleRequestStream {
+    inner: BoxStream<'static, Result<FlightData, ArrowError>>,
+    descriptor: Option<FlightDescriptor>,
+    error: Arc<Mutex<Option<ArrowError>>>,
+    done: bool,
+}
+
+impl Stream for FallibleRequestStream {
+    type Item = FlightData;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        match futures::ready!(self.inner.poll_next_unpin(cx)) {
+            Some(Ok(mut data)) => {
+                if let Some(descriptor) = self.descriptor.take() {
+                    data.flight_descriptor = Some(descriptor);
+                }
+                Poll::Ready(Some(data))
+            }
+            Some(Err(e)) => {
+                self.done = true;
+                *self.error.lock().unwrap() = Some(e);
+                Poll::Ready(None)
+            }
+            None => {
+                self.done = true;
+                Poll::Ready(self.descriptor.take().map(|descriptor| FlightData {
+                    flight_descriptor: Some(descriptor),
+                    ..Default::default()
+                }))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::DecodedPayload;
+    use crate::encode::FlightDataEncoderBuilder;
+    use crate::test_util::{run_service, TestFlightService};
+    use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+    use std::sync::Arc;
+
+    async fn run_exchange<F, Fut>(f: F)
+    where
+        F: FnOnce(FlightServiceClient<Channel>) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        run_service(TestFlightService::default(), |channel| {
+            f(FlightServiceClient::new(channel))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_do_exchange() {
+        run_exchange(|mut client| async move {
+            let a = Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef;
+            let batch = RecordBatch::try_from_iter(vec![("a", a)]).unwrap();
+            let input = FlightDataEncoderBuilder::new()
+                .build(stream::iter(vec![Ok(batch.clone()), Ok(batch.slice(0, 1))]));
+
+            let descriptor = FlightDescriptor::new_path(vec!["echo".to_string()]);
+            let response: Vec<_> = do_exchange(&mut client, descriptor, input)
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(response.len(), 4);
+            assert!(matches!(response[0].payload, DecodedPayload::Schema(_)));
+            assert!(
+                matches!(&response[1].payload, DecodedPayload::RecordBatch(b) if *b == batch)
+            );
+            assert!(matches!(&response[2].payload, DecodedPayload::RecordBatch(b) if b.num_rows() == 1));
+            assert!(matches!(response[3].payload, DecodedPayload::None));
+            assert_eq!(response[3].app_metadata(), b"4");
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_do_exchange_empty_and_error() {
+        run_exchange(|mut client| async move {
+            // The descriptor is sent even without any data
+            let descriptor = FlightDescriptor::new_path(vec!["echo".to_string()]);
+            let response: Vec<_> = do_exchange(&mut client, descriptor, stream::empty())
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(response.len(), 1);
+            assert_eq!(response[0].app_metadata(), b"0");
+
+            // An error of the input is returned in place of the server's response
+            let descriptor = FlightDescriptor::new_path(vec!["echo".to_string()]);
+            let input = stream::iter(vec![Err(ArrowError::ComputeError(
+                "input failed".to_string(),
+            ))]);
+            let err = match do_exchange(&mut client, descriptor, input).await {
+                Ok(response) => response.try_collect::<Vec<_>>().await.unwrap_err(),
+                Err(e) => e,
+            };
+            assert!(err.to_string().contains("input failed"), "{}", err);
+        })
+        .await
+    }
+}
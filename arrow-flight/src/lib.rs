@@ -68,6 +68,16 @@ pub use gen::Result;
 pub use gen::SchemaResult;
 pub use gen::Ticket;
 
+pub use client::{FlightChannelBuilder, FlightClient, RetryPolicy};
+
+mod client;
+pub mod decode;
+pub mod encode;
+pub mod exchange;
+pub mod middleware;
+pub mod sender;
+#[cfg(test)]
+mod test_util;
 pub mod utils;
 
 #[cfg(feature = "flight-sql-experimental")]
@@ -75,6 +85,11 @@ pub mod sql;
 
 use flight_descriptor::DescriptorType;
 
+/// Converts a `tonic::Status` into an [`ArrowError`], keeping it as the source
+pub(crate) fn status_to_arrow_error(status: tonic::Status) -> ArrowError {
+    ArrowError::ExternalError(Box::new(status))
+}
+
 /// SchemaAsIpc represents a pairing of a `Schema` with IpcWriteOptions
 pub struct SchemaAsIpc<'a> {
     pub pair: (&'a Schema, &'a IpcWriteOptions),
@@ -0,0 +1,593 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Hooks for logging, tracing and metrics around each Flight call
+//!
+//! A [`FlightMiddleware`] is notified when a call starts, with a [`FlightCall`]
+//! describing it, and when it finishes, with a [`FlightCallSummary`] of its
+//! duration, outcome and size. A call finishes once its response has been
+//! received completely, including all messages of a streaming response.
+//!
+//! On the server, wrap a [`FlightService`] in a [`MiddlewareService`]:
+//!
+//! ```
+//! # use std::sync::Arc;
+//! use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+//! use arrow_flight::middleware::{
+//!     FlightCall, FlightCallSummary, FlightMiddleware, MiddlewareService,
+//! };
+//!
+//! #[derive(Debug)]
+//! struct Logger;
+//!
+//! impl FlightMiddleware for Logger {
+//!     fn call_finished(&self, call: &FlightCall, summary: &FlightCallSummary) {
+//!         println!(
+//!             "{} {:?} returned {:?} with {} bytes in {:?}",
+//!             call.method, call.command, summary.code, summary.response_bytes,
+//!             summary.duration
+//!         );
+//!     }
+//! }
+//!
+//! fn serve<S: FlightService>(service: S) -> FlightServiceServer<MiddlewareService<S>> {
+//!     FlightServiceServer::new(MiddlewareService::new(service, Arc::new(Logger)))
+//! }
+//! ```
+//!
+//! On the client, set it with [`FlightClient::set_middleware`](crate::FlightClient::set_middleware).
+
+use std::fmt::{Debug, Display, Formatter};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::stream::BoxStream;
+use futures::{ready, Stream, StreamExt};
+use prost::Message;
+use tonic::metadata::MetadataMap;
+use tonic::{Code, Request, Response, Status, Streaming};
+
+use crate::flight_descriptor::DescriptorType;
+use crate::flight_service_server::FlightService;
+use crate::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
+};
+
+/// Hooks called around each Flight call. All methods do nothing by default
+pub trait FlightMiddleware: Debug + Send + Sync {
+    /// Called when `call` starts
+    fn call_started(&self, _call: &FlightCall) {}
+
+    /// Called when `call` finishes, successfully or not
+    fn call_finished(&self, _call: &FlightCall, _summary: &FlightCallSummary) {}
+}
+
+/// A method of the Flight service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlightMethod {
+    Handshake,
+    ListFlights,
+    GetFlightInfo,
+    GetSchema,
+    DoGet,
+    DoPut,
+    DoExchange,
+    DoAction,
+    ListActions,
+}
+
+impl FlightMethod {
+    /// Returns the name of the method, as in the gRPC service definition
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Handshake => "Handshake",
+            Self::ListFlights => "ListFlights",
+            Self::GetFlightInfo => "GetFlightInfo",
+            Self::GetSchema => "GetSchema",
+            Self::DoGet => "DoGet",
+            Self::DoPut => "DoPut",
+            Self::DoExchange => "DoExchange",
+            Self::DoAction => "DoAction",
+            Self::ListActions => "ListActions",
+        }
+    }
+}
+
+impl Display for FlightMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A Flight call, passed to the hooks of a [`FlightMiddleware`]
+#[derive(Debug, Clone)]
+pub struct FlightCall {
+    /// An id identifying this call among those of this process, which can be
+    /// used to correlate [`FlightMiddleware::call_started`] and
+    /// [`FlightMiddleware::call_finished`], such as to close a tracing span
+    pub id: u64,
+    /// The method of the call
+    pub method: FlightMethod,
+    /// A description of the command of the call, if known: the type url of a
+    /// command encoded as a protobuf `Any`, as used by Flight SQL, the path of a
+    /// path descriptor, or the type of an action
+    pub command: Option<String>,
+    /// The metadata of the request
+    pub metadata: MetadataMap,
+}
+
+/// The outcome of a Flight call, passed to [`FlightMiddleware::call_finished`]
+#[derive(Debug, Clone)]
+pub struct FlightCallSummary {
+    /// The time from the start of the call until it finished
+    pub duration: Duration,
+    /// The status code of the call, [`Code::Ok`] if it succeeded, and
+    /// [`Code::Cancelled`] if a streaming response was dropped before its end
+    pub code: Code,
+    /// The encoded size of the request, if it is a single message
+    pub request_bytes: Option<usize>,
+    /// The encoded size of the messages of the response
+    pub response_bytes: usize,
+    /// The number of messages of the response
+    pub response_messages: usize,
+}
+
+/// The next [`FlightCall::id`]
+static NEXT_CALL_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Tracks a call from its start until it finishes
+#[derive(Debug)]
+pub(crate) struct CallTracker {
+    middleware: Arc<dyn FlightMiddleware>,
+    call: FlightCall,
+    start: Instant,
+    request_bytes: Option<usize>,
+}
+
+impl CallTracker {
+    /// Starts a call of `method` with the request `metadata`, calling
+    /// [`FlightMiddleware::call_started`]
+    pub(crate) fn start(
+        middleware: Arc<dyn FlightMiddleware>,
+        method: FlightMethod,
+        command: Option<String>,
+        metadata: MetadataMap,
+        request_bytes: Option<usize>,
+    ) -> Self {
+        let call = FlightCall {
+            id: NEXT_CALL_ID.fetch_add(1, Ordering::Relaxed),
+            method,
+            command,
+            metadata,
+        };
+        middleware.call_started(&call);
+        Self {
+            middleware,
+            call,
+            start: Instant::now(),
+            request_bytes,
+        }
+    }
+
+    /// Finishes the call, calling [`FlightMiddleware::call_finished`]
+    pub(crate) fn finish(
+        self,
+        code: Code,
+        response_bytes: usize,
+        response_messages: usize,
+    ) {
+        let summary = FlightCallSummary {
+            duration: self.start.elapsed(),
+            code,
+            request_bytes: self.request_bytes,
+            response_bytes,
+            response_messages,
+        };
+        self.middleware.call_finished(&self.call, &summary);
+    }
+
+    /// Finishes the call with the `result` of a call with a single response message
+    pub(crate) fn finish_unary<T: Message>(self, result: &Result<Response<T>, Status>) {
+        match result {
+            Ok(response) => self.finish(Code::Ok, response.get_ref().encoded_len(), 1),
+            Err(status) => self.finish(status.code(), 0, 0),
+        }
+    }
+
+    /// Finishes the call with the result of a call with a streaming response
+    /// that has been collected into `messages`
+    pub(crate) fn finish_collected<T: Message>(self, messages: Result<&[T], &Status>) {
+        match messages {
+            Ok(messages) => {
+                let bytes = messages.iter().map(|m| m.encoded_len()).sum();
+                self.finish(Code::Ok, bytes, messages.len())
+            }
+            Err(status) => self.finish(status.code(), 0, 0),
+        }
+    }
+
+    /// Returns `response` observed until its end, when the call finishes
+    pub(crate) fn observe<T, S>(
+        self,
+        response: S,
+    ) -> BoxStream<'static, Result<T, Status>>
+    where
+        T: Message + 'static,
+        S: Stream<Item = Result<T, Status>> + Send + 'static,
+    {
+        ObservedStream {
+            inner: response.boxed(),
+            tracker: Some(self),
+            response_bytes: 0,
+            response_messages: 0,
+        }
+        .boxed()
+    }
+}
+
+/// A streaming response observed by a [`CallTracker`]
+struct ObservedStream<T> {
+    inner: BoxStream<'static, Result<T, Status>>,
+    /// The tracker of the call, until it has finished
+    tracker: Option<CallTracker>,
+    response_bytes: usize,
+    response_messages: usize,
+}
+
+impl<T> ObservedStream<T> {
+    fn finish(&mut self, code: Code) {
+        if let Some(tracker) = self.tracker.take() {
+            tracker.finish(code, self.response_bytes, self.response_messages);
+        }
+    }
+}
+
+impl<T: Message> Stream for ObservedStream<T> {
+    type Item = Result<T, Status>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.inner.poll_next_unpin(cx));
+        match &item {
+            Some(Ok(message)) => {
+                self.response_bytes += message.encoded_len();
+                self.response_messages += 1;
+            }
+            Some(Err(status)) => self.finish(status.code()),
+            None => self.finish(Code::Ok),
+        }
+        Poll::Ready(item)
+    }
+}
+
+impl<T> Drop for ObservedStream<T> {
+    fn drop(&mut self) {
+        self.finish(Code::Cancelled);
+    }
+}
+
+/// The subset of a protobuf `Any` needed to describe a command
+#[derive(Clone, PartialEq, Message)]
+struct AnyTypeUrl {
+    #[prost(string, tag = "1")]
+    type_url: String,
+}
+
+/// Returns the type url of `cmd` if it is a protobuf `Any`
+pub(crate) fn command_type(cmd: &[u8]) -> Option<String> {
+    let any = AnyTypeUrl::decode(cmd).ok()?;
+    any.type_url.contains('/').then_some(any.type_url)
+}
+
+/// Returns the description of the command of `descriptor`
+pub(crate) fn descriptor_command(descriptor: &FlightDescriptor) -> Option<String> {
+    match descriptor.r#type() {
+        DescriptorType::Cmd => command_type(&descriptor.cmd),
+        DescriptorType::Path => Some(descriptor.path.join("/")),
+        DescriptorType::Unknown => None,
+    }
+}
+
+/// A [`FlightService`] that calls a [`FlightMiddleware`] around each call of the
+/// wrapped service
+#[derive(Debug)]
+pub struct MiddlewareService<S> {
+    inner: S,
+    middleware: Arc<dyn FlightMiddleware>,
+}
+
+impl<S> MiddlewareService<S> {
+    /// Creates a new [`MiddlewareService`] calling `middleware` around the calls
+    /// of `inner`
+    pub fn new(inner: S, middleware: Arc<dyn FlightMiddleware>) -> Self {
+        Self { inner, middleware }
+    }
+
+    /// Returns a reference to the wrapped service
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Starts tracking a call of `method` with `request`
+    fn start<T>(
+        &self,
+        method: FlightMethod,
+        request: &Request<T>,
+        command: Option<String>,
+        request_bytes: Option<usize>,
+    ) -> CallTracker {
+        CallTracker::start(
+            self.middleware.clone(),
+            method,
+            command,
+            request.metadata().clone(),
+            request_bytes,
+        )
+    }
+}
+
+/// Returns the streaming `response` to a call tracked by `call`, observed
+/// until its end
+#[allow(clippy::result_large_err)]
+fn observe_response<T, S>(
+    call: CallTracker,
+    response: Result<Response<S>, Status>,
+) -> Result<Response<BoxStream<'static, Result<T, Status>>>, Status>
+where
+    T: Message + 'static,
+    S: Stream<Item = Result<T, Status>> + Send + 'static,
+{
+    match response {
+        Ok(response) => Ok(response.map(|stream| call.observe(stream))),
+        Err(status) => {
+            call.finish(status.code(), 0, 0);
+            Err(status)
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<S: FlightService> FlightService for MiddlewareService<S> {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<crate::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        let call = self.start(FlightMethod::Handshake, &request, None, None);
+        observe_response(call, self.inner.handshake(request).await)
+    }
+
+    async fn list_flights(
+        &self,
+        request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        let bytes = request.get_ref().encoded_len();
+        let call = self.start(FlightMethod::ListFlights, &request, None, Some(bytes));
+        observe_response(call, self.inner.list_flights(request).await)
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let command = descriptor_command(request.get_ref());
+        let bytes = request.get_ref().encoded_len();
+        let call =
+            self.start(FlightMethod::GetFlightInfo, &request, command, Some(bytes));
+        let response = self.inner.get_flight_info(request).await;
+        call.finish_unary(&response);
+        response
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let command = descriptor_command(request.get_ref());
+        let bytes = request.get_ref().encoded_len();
+        let call = self.start(FlightMethod::GetSchema, &request, command, Some(bytes));
+        let response = self.inner.get_schema(request).await;
+        call.finish_unary(&response);
+        response
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let command = command_type(&request.get_ref().ticket);
+        let bytes = request.get_ref().encoded_len();
+        let call = self.start(FlightMethod::DoGet, &request, command, Some(bytes));
+        observe_response(call, self.inner.do_get(request).await)
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let call = self.start(FlightMethod::DoPut, &request, None, None);
+        observe_response(call, self.inner.do_put(request).await)
+    }
+
+    async fn do_action(
+        &self,
+        request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        let command = Some(request.get_ref().r#type.clone());
+        let bytes = request.get_ref().encoded_len();
+        let call = self.start(FlightMethod::DoAction, &request, command, Some(bytes));
+        observe_response(call, self.inner.do_action(request).await)
+    }
+
+    async fn list_actions(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        let call = self.start(FlightMethod::ListActions, &request, None, Some(0));
+        observe_response(call, self.inner.list_actions(request).await)
+    }
+
+    async fn do_exchange(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        let call = self.start(FlightMethod::DoExchange, &request, None, None);
+        observe_response(call, self.inner.do_exchange(request).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{run_service, TestFlightService, TOKEN};
+    use crate::FlightClient;
+    use futures::TryStreamExt;
+    use std::sync::Mutex;
+
+    /// Records the calls it is notified of
+    #[derive(Debug, Default)]
+    struct Recorder {
+        started: Mutex<Vec<FlightCall>>,
+        finished: Mutex<Vec<(FlightCall, FlightCallSummary)>>,
+    }
+
+    impl Recorder {
+        fn take_finished(&self) -> Vec<(FlightCall, FlightCallSummary)> {
+            std::mem::take(&mut *self.finished.lock().unwrap())
+        }
+    }
+
+    impl FlightMiddleware for Recorder {
+        fn call_started(&self, call: &FlightCall) {
+            self.started.lock().unwrap().push(call.clone());
+        }
+
+        fn call_finished(&self, call: &FlightCall, summary: &FlightCallSummary) {
+            let finished = (call.clone(), summary.clone());
+            self.finished.lock().unwrap().push(finished);
+        }
+    }
+
+    #[test]
+    fn test_command_type() {
+        let any = AnyTypeUrl {
+            type_url: "type.googleapis.com/arrow.flight.protocol.sql.CommandGetCatalogs"
+                .to_string(),
+        };
+        assert_eq!(
+            command_type(&any.encode_to_vec()).unwrap(),
+            "type.googleapis.com/arrow.flight.protocol.sql.CommandGetCatalogs"
+        );
+        assert_eq!(command_type(b"select 1"), None);
+
+        let descriptor = FlightDescriptor::new_path(vec!["a".into(), "b".into()]);
+        assert_eq!(descriptor_command(&descriptor).unwrap(), "a/b");
+    }
+
+    #[tokio::test]
+    async fn test_middleware() {
+        let server_recorder = Arc::new(Recorder::default());
+        let service =
+            MiddlewareService::new(TestFlightService::default(), server_recorder.clone());
+        run_service(service, |channel| async move {
+            let client_recorder = Arc::new(Recorder::default());
+            let mut client = FlightClient::new(channel);
+            client.set_middleware(client_recorder.clone());
+            client.set_bearer_token(TOKEN).unwrap();
+            client.add_header("x-trace-id", "1").unwrap();
+
+            let descriptor = FlightDescriptor::new_path(vec!["a".into(), "b".into()]);
+            client.get_flight_info(descriptor).await.unwrap();
+
+            let ticket = Ticket {
+                ticket: b"a".to_vec(),
+            };
+            let batches: Vec<_> = client
+                .do_get(ticket.clone())
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(batches.len(), 1);
+
+            let action = Action {
+                r#type: "missing".to_string(),
+                body: vec![],
+            };
+            client.do_action(action).await.unwrap_err();
+
+            let client_calls = client_recorder.take_finished();
+            let server_calls = server_recorder.take_finished();
+            assert_eq!(client_recorder.started.lock().unwrap().len(), 3);
+            assert_eq!(server_recorder.started.lock().unwrap().len(), 3);
+
+            // Both sides observe the same calls
+            for calls in [&client_calls, &server_calls] {
+                let methods: Vec<_> = calls.iter().map(|(c, _)| c.method).collect();
+                assert_eq!(
+                    methods,
+                    vec![
+                        FlightMethod::GetFlightInfo,
+                        FlightMethod::DoGet,
+                        FlightMethod::DoAction
+                    ]
+                );
+                let codes: Vec<_> = calls.iter().map(|(_, s)| s.code).collect();
+                assert_eq!(codes, vec![Code::Ok, Code::Ok, Code::NotFound]);
+
+                let (call, summary) = &calls[0];
+                assert_eq!(call.command.as_deref(), Some("a/b"));
+                assert_eq!(call.metadata.get("x-trace-id").unwrap(), "1");
+                assert!(summary.request_bytes.unwrap() > 0);
+                assert_eq!(summary.response_messages, 1);
+
+                // The schema and the batch
+                let (_, summary) = &calls[1];
+                assert_eq!(summary.response_messages, 2);
+
+                let (call, _) = &calls[2];
+                assert_eq!(call.command.as_deref(), Some("missing"));
+            }
+            for i in 0..3 {
+                assert_eq!(
+                    client_calls[i].1.response_bytes,
+                    server_calls[i].1.response_bytes
+                );
+            }
+
+            // A stream dropped before its end is cancelled
+            drop(client.do_get(ticket).await.unwrap());
+            let client_calls = client_recorder.take_finished();
+            assert_eq!(client_calls[0].1.code, Code::Cancelled);
+        })
+        .await
+    }
+}
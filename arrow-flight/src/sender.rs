@@ -0,0 +1,307 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A bounded channel of [`RecordBatch`]es for producing the response of a
+//! streaming call, such as `DoGet`, from a separate task
+//!
+//! tonic only polls the response stream of a call once the previous message has
+//! been handed to the transport, and so a slow client slows down the consumption
+//! of a [`FlightDataReceiver`]. As the channel is bounded, [`FlightDataSender::send`]
+//! then waits for capacity, applying backpressure to the producer instead of
+//! buffering an unbounded number of batches in memory:
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+//! use arrow_flight::encode::FlightDataEncoderBuilder;
+//! use arrow_flight::sender::FlightDataSenderBuilder;
+//! use futures::TryStreamExt;
+//! use tonic::Status;
+//!
+//! # async fn run() -> Result<(), Status> {
+//! // At most 2 batches are buffered ahead of the client
+//! let (sender, receiver) = FlightDataSenderBuilder::new(2).build();
+//!
+//! tokio::spawn(async move {
+//!     for i in 0..100 {
+//!         let a = Arc::new(Int32Array::from(vec![i])) as ArrayRef;
+//!         let batch = RecordBatch::try_from_iter(vec![("a", a)]).unwrap();
+//!         // Returns an error once the client has gone away
+//!         if sender.send(batch).await.is_err() {
+//!             break;
+//!         }
+//!     }
+//! });
+//!
+//! let response = FlightDataEncoderBuilder::new()
+//!     .build(receiver)
+//!     .map_err(|e| Status::internal(e.to_string()));
+//! // Returned as the `DoGetStream` of a `FlightService`
+//! # let _ = response;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use arrow_array::RecordBatch;
+use arrow_schema::ArrowError;
+use futures::Stream;
+use tokio::sync::mpsc;
+
+/// Hooks called by a [`FlightDataSender`] and its [`FlightDataReceiver`], such as
+/// to record metrics of a call. All methods do nothing by default
+pub trait FlightDataSenderMetrics: Debug + Send + Sync {
+    /// Called when `batch` is queued by [`FlightDataSender::send`]
+    fn batch_sent(&self, _batch: &RecordBatch) {}
+
+    /// Called when [`FlightDataSender::send`] had to wait for `waited` until the
+    /// channel had capacity, as the receiver was consumed slower than produced
+    fn backpressure(&self, _waited: Duration) {}
+
+    /// Called when `batch` is returned by the [`FlightDataReceiver`], which is
+    /// when it is about to be encoded and sent to the client
+    fn batch_received(&self, _batch: &RecordBatch) {}
+}
+
+/// Creates a bounded channel of [`RecordBatch`]es, returning a
+/// [`FlightDataSender`] and [`FlightDataReceiver`]
+#[derive(Debug)]
+pub struct FlightDataSenderBuilder {
+    /// The maximum number of batches in the channel
+    capacity: usize,
+    /// The hooks called for the batches of the channel, if any
+    metrics: Option<Arc<dyn FlightDataSenderMetrics>>,
+}
+
+impl FlightDataSenderBuilder {
+    /// Creates a new [`FlightDataSenderBuilder`] for a channel buffering at most
+    /// `capacity` batches, which must be at least 1
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            metrics: None,
+        }
+    }
+
+    /// Sets the [`FlightDataSenderMetrics`] called for the batches of the channel
+    pub fn with_metrics(mut self, metrics: Arc<dyn FlightDataSenderMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the [`FlightDataSender`] and [`FlightDataReceiver`] of a new channel
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is 0
+    pub fn build(self) -> (FlightDataSender, FlightDataReceiver) {
+        let (tx, rx) = mpsc::channel(self.capacity);
+        let sender = FlightDataSender {
+            tx,
+            metrics: self.metrics.clone(),
+        };
+        let receiver = FlightDataReceiver {
+            rx,
+            metrics: self.metrics,
+        };
+        (sender, receiver)
+    }
+}
+
+/// The sending half of a channel created by [`FlightDataSenderBuilder`], which
+/// may be cloned to send from several tasks
+#[derive(Debug, Clone)]
+pub struct FlightDataSender {
+    tx: mpsc::Sender<Result<RecordBatch, ArrowError>>,
+    metrics: Option<Arc<dyn FlightDataSenderMetrics>>,
+}
+
+impl FlightDataSender {
+    /// Sends `batch`, waiting for capacity if the channel is full
+    ///
+    /// Returns an error if the [`FlightDataReceiver`] has been dropped, such as
+    /// when the client cancelled the call, in which case the producer should stop
+    pub async fn send(&self, batch: RecordBatch) -> Result<(), ArrowError> {
+        let permit = self.reserve().await?;
+        if let Some(metrics) = &self.metrics {
+            metrics.batch_sent(&batch);
+        }
+        permit.send(Ok(batch));
+        Ok(())
+    }
+
+    /// Sends `error`, which is returned by the [`FlightDataReceiver`] and ends
+    /// the response, waiting for capacity if the channel is full
+    pub async fn send_error(&self, error: ArrowError) -> Result<(), ArrowError> {
+        self.reserve().await?.send(Err(error));
+        Ok(())
+    }
+
+    /// Returns true if the [`FlightDataReceiver`] has been dropped
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Returns the number of batches that can be sent without waiting
+    pub fn capacity(&self) -> usize {
+        self.tx.capacity()
+    }
+
+    /// Reserves capacity for one message, reporting any wait to the metrics
+    async fn reserve(
+        &self,
+    ) -> Result<mpsc::Permit<'_, Result<RecordBatch, ArrowError>>, ArrowError> {
+        let permit = match self.tx.try_reserve() {
+            Ok(permit) => Ok(permit),
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                let start = Instant::now();
+                let permit = self.tx.reserve().await;
+                if let Some(metrics) = &self.metrics {
+                    metrics.backpressure(start.elapsed());
+                }
+                permit.map_err(|_| ())
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(()),
+        };
+        permit.map_err(|_| {
+            ArrowError::IoError(
+                "Cannot send to a FlightDataReceiver that has been dropped".to_string(),
+            )
+        })
+    }
+}
+
+/// The receiving half of a channel created by [`FlightDataSenderBuilder`], a
+/// [`Stream`] of [`RecordBatch`]es that ends once all [`FlightDataSender`]s have
+/// been dropped
+#[derive(Debug)]
+pub struct FlightDataReceiver {
+    rx: mpsc::Receiver<Result<RecordBatch, ArrowError>>,
+    metrics: Option<Arc<dyn FlightDataSenderMetrics>>,
+}
+
+impl Stream for FlightDataReceiver {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let item = self.rx.poll_recv(cx);
+        if let (Poll::Ready(Some(Ok(batch))), Some(metrics)) = (&item, &self.metrics) {
+            metrics.batch_received(batch);
+        }
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{ArrayRef, Int32Array};
+    use futures::{FutureExt, StreamExt, TryStreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn make_batch(value: i32) -> RecordBatch {
+        let a = Arc::new(Int32Array::from(vec![value])) as ArrayRef;
+        RecordBatch::try_from_iter(vec![("a", a)]).unwrap()
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingMetrics {
+        sent: AtomicUsize,
+        backpressure: AtomicUsize,
+        received: AtomicUsize,
+    }
+
+    impl FlightDataSenderMetrics for CountingMetrics {
+        fn batch_sent(&self, _batch: &RecordBatch) {
+            self.sent.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn backpressure(&self, _waited: Duration) {
+            self.backpressure.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn batch_received(&self, _batch: &RecordBatch) {
+            self.received.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backpressure() {
+        let metrics = Arc::new(CountingMetrics::default());
+        let (sender, mut receiver) = FlightDataSenderBuilder::new(1)
+            .with_metrics(metrics.clone())
+            .build();
+
+        sender.send(make_batch(1)).await.unwrap();
+        assert_eq!(sender.capacity(), 0);
+
+        // Waits until the receiver is polled
+        let mut blocked = Box::pin(sender.send(make_batch(2)));
+        assert!((&mut blocked).now_or_never().is_none());
+
+        assert_eq!(receiver.next().await.unwrap().unwrap(), make_batch(1));
+        blocked.await.unwrap();
+        drop(sender);
+
+        let rest: Vec<_> = receiver.try_collect().await.unwrap();
+        assert_eq!(rest, vec![make_batch(2)]);
+
+        assert_eq!(metrics.sent.load(Ordering::SeqCst), 2);
+        assert_eq!(metrics.backpressure.load(Ordering::SeqCst), 1);
+        assert_eq!(metrics.received.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_error() {
+        let (sender, receiver) = FlightDataSenderBuilder::new(2).build();
+        sender.send(make_batch(1)).await.unwrap();
+        sender
+            .send_error(ArrowError::ComputeError("failed".to_string()))
+            .await
+            .unwrap();
+        drop(sender);
+
+        let results: Vec<_> = receiver.collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        let err = results[1].as_ref().unwrap_err();
+        assert!(err.to_string().contains("failed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_receiver_dropped() {
+        let (sender, receiver) = FlightDataSenderBuilder::new(1).build();
+        sender.send(make_batch(1)).await.unwrap();
+
+        // Wakes a sender waiting for capacity
+        let mut blocked = Box::pin(sender.send(make_batch(2)));
+        assert!((&mut blocked).now_or_never().is_none());
+        drop(receiver);
+        let err = blocked.await.unwrap_err();
+        assert!(err.to_string().contains("dropped"), "{}", err);
+        assert!(sender.is_closed());
+        assert!(sender.send(make_batch(3)).await.is_err());
+    }
+}
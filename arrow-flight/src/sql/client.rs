@@ -19,6 +19,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::encode::FlightDataEncoderBuilder;
 use crate::flight_service_client::FlightServiceClient;
 use crate::sql::server::{CLOSE_PREPARED_STATEMENT, CREATE_PREPARED_STATEMENT};
 use crate::sql::{
@@ -26,9 +27,10 @@ use crate::sql::{
     ActionCreatePreparedStatementResult, CommandGetCatalogs, CommandGetCrossReference,
     CommandGetDbSchemas, CommandGetExportedKeys, CommandGetImportedKeys,
     CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes, CommandGetTables,
-    CommandPreparedStatementQuery, CommandStatementQuery, CommandStatementUpdate,
-    DoPutUpdateResult, ProstAnyExt, ProstMessageExt, SqlInfo,
+    CommandPreparedStatementQuery, CommandPreparedStatementUpdate, CommandStatementQuery,
+    CommandStatementUpdate, DoPutUpdateResult, ProstAnyExt, ProstMessageExt, SqlInfo,
 };
+use crate::utils::flight_data_to_batches;
 use crate::{
     Action, FlightData, FlightDescriptor, FlightInfo, HandshakeRequest,
     HandshakeResponse, IpcMessage, Ticket,
@@ -92,14 +94,21 @@ impl FlightSqlServiceClient {
             .map_err(|_| ArrowError::IoError("Unable to lock client".to_string()))
     }
 
+    /// Wraps `message` in a `tonic::Request`, attaching the bearer token
+    /// obtained by [`Self::handshake`] if any
+    fn request<T>(&self, message: T) -> Result<tonic::Request<T>, ArrowError> {
+        request_with_token(self.token.as_deref(), message)
+    }
+
     async fn get_flight_info_for_command<M: ProstMessageExt>(
         &mut self,
         cmd: M,
     ) -> Result<FlightInfo, ArrowError> {
         let descriptor = FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec());
+        let req = self.request(descriptor)?;
         let fi = self
             .mut_client()?
-            .get_flight_info(descriptor)
+            .get_flight_info(req)
             .await
             .map_err(status_to_arrow_error)?
             .into_inner();
@@ -163,12 +172,13 @@ impl FlightSqlServiceClient {
     pub async fn execute_update(&mut self, query: String) -> Result<i64, ArrowError> {
         let cmd = CommandStatementUpdate { query };
         let descriptor = FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec());
+        let req = self.request(stream::iter(vec![FlightData {
+            flight_descriptor: Some(descriptor),
+            ..Default::default()
+        }]))?;
         let mut result = self
             .mut_client()?
-            .do_put(stream::iter(vec![FlightData {
-                flight_descriptor: Some(descriptor),
-                ..Default::default()
-            }]))
+            .do_put(req)
             .await
             .map_err(status_to_arrow_error)?
             .into_inner();
@@ -177,9 +187,8 @@ impl FlightSqlServiceClient {
             .await
             .map_err(status_to_arrow_error)?
             .unwrap();
-        let any: prost_types::Any = prost::Message::decode(&*result.app_metadata)
+        let result = DoPutUpdateResult::decode(&*result.app_metadata)
             .map_err(decode_error_to_arrow_error)?;
-        let result: DoPutUpdateResult = any.unpack()?.unwrap();
         Ok(result.record_count)
     }
 
@@ -202,14 +211,58 @@ impl FlightSqlServiceClient {
         &mut self,
         ticket: Ticket,
     ) -> Result<Streaming<FlightData>, ArrowError> {
+        let req = self.request(ticket)?;
         Ok(self
             .mut_client()?
-            .do_get(ticket)
+            .do_get(req)
             .await
             .map_err(status_to_arrow_error)?
             .into_inner())
     }
 
+    /// Given a flight ticket, request to be sent the stream and decode it
+    /// into `RecordBatch`es, including any dictionaries it contains
+    pub async fn do_get_batches(
+        &mut self,
+        ticket: Ticket,
+    ) -> Result<Vec<RecordBatch>, ArrowError> {
+        let flight_data: Vec<FlightData> = self
+            .do_get(ticket)
+            .await?
+            .try_collect()
+            .await
+            .map_err(status_to_arrow_error)?;
+        flight_data_to_batches(&flight_data)
+    }
+
+    /// Fetch the results described by a `FlightInfo`, such as one returned by
+    /// [`Self::execute`] or [`Self::get_tables`], by calling `DoGet` for the
+    /// ticket of each of its endpoints in order
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), arrow_schema::ArrowError> {
+    /// use arrow_flight::sql::client::FlightSqlServiceClient;
+    ///
+    /// let mut client = FlightSqlServiceClient::new_with_endpoint("localhost", 50051).await?;
+    /// let info = client.execute("SELECT 1".to_string()).await?;
+    /// let batches = client.fetch(&info).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch(
+        &mut self,
+        info: &FlightInfo,
+    ) -> Result<Vec<RecordBatch>, ArrowError> {
+        let mut batches = vec![];
+        for endpoint in &info.endpoint {
+            let ticket = endpoint.ticket.clone().ok_or_else(|| {
+                ArrowError::IoError("FlightEndpoint is missing a ticket".to_string())
+            })?;
+            batches.extend(self.do_get_batches(ticket).await?);
+        }
+        Ok(batches)
+    }
+
     /// Request a list of tables.
     pub async fn get_tables(
         &mut self,
@@ -280,13 +333,7 @@ impl FlightSqlServiceClient {
             r#type: CREATE_PREPARED_STATEMENT.to_string(),
             body: cmd.as_any().encode_to_vec(),
         };
-        let mut req = tonic::Request::new(action);
-        if let Some(token) = &self.token {
-            let val = format!("Bearer {}", token).parse().map_err(|_| {
-                ArrowError::IoError("Statement already closed.".to_string())
-            })?;
-            req.metadata_mut().insert("authorization", val);
-        }
+        let req = self.request(action)?;
         let mut result = self
             .mut_client()?
             .do_action(req)
@@ -311,6 +358,7 @@ impl FlightSqlServiceClient {
         };
         Ok(PreparedStatement::new(
             self.flight_client.clone(),
+            self.token.clone(),
             prepared_result.prepared_statement_handle,
             dataset_schema,
             parameter_schema,
@@ -324,9 +372,13 @@ impl FlightSqlServiceClient {
 }
 
 /// A PreparedStatement
+///
+/// Parameters set with [`Self::set_parameters`] are uploaded to the server with
+/// a `DoPut` before the statement is executed
 #[derive(Debug, Clone)]
 pub struct PreparedStatement<T> {
     flight_client: Arc<Mutex<FlightServiceClient<T>>>,
+    token: Option<String>,
     parameter_binding: Option<RecordBatch>,
     handle: Vec<u8>,
     dataset_schema: Schema,
@@ -336,12 +388,14 @@ pub struct PreparedStatement<T> {
 impl PreparedStatement<Channel> {
     pub(crate) fn new(
         client: Arc<Mutex<FlightServiceClient<Channel>>>,
+        token: Option<String>,
         handle: Vec<u8>,
         dataset_schema: Schema,
         parameter_schema: Schema,
     ) -> Self {
         PreparedStatement {
             flight_client: client,
+            token,
             parameter_binding: None,
             handle,
             dataset_schema,
@@ -349,33 +403,52 @@ impl PreparedStatement<Channel> {
         }
     }
 
-    /// Executes the prepared statement query on the server.
+    /// Executes the prepared statement query on the server, binding any
+    /// parameters first.
     pub async fn execute(&mut self) -> Result<FlightInfo, ArrowError> {
         let cmd = CommandPreparedStatementQuery {
             prepared_statement_handle: self.handle.clone(),
         };
+        if self.parameter_binding.is_some() {
+            let flight_data =
+                self.encode_parameters(cmd.as_any().encode_to_vec()).await?;
+            let req = self.request(stream::iter(flight_data))?;
+            let mut result = self
+                .mut_client()?
+                .do_put(req)
+                .await
+                .map_err(status_to_arrow_error)?
+                .into_inner();
+            while result
+                .message()
+                .await
+                .map_err(status_to_arrow_error)?
+                .is_some()
+            {}
+        }
+
         let descriptor = FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec());
+        let req = self.request(descriptor)?;
         let result = self
             .mut_client()?
-            .get_flight_info(descriptor)
+            .get_flight_info(req)
             .await
             .map_err(status_to_arrow_error)?
             .into_inner();
         Ok(result)
     }
 
-    /// Executes the prepared statement update query on the server.
+    /// Executes the prepared statement update query on the server, with any
+    /// parameters.
     pub async fn execute_update(&mut self) -> Result<i64, ArrowError> {
-        let cmd = CommandPreparedStatementQuery {
+        let cmd = CommandPreparedStatementUpdate {
             prepared_statement_handle: self.handle.clone(),
         };
-        let descriptor = FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec());
+        let flight_data = self.encode_parameters(cmd.as_any().encode_to_vec()).await?;
+        let req = self.request(stream::iter(flight_data))?;
         let mut result = self
             .mut_client()?
-            .do_put(stream::iter(vec![FlightData {
-                flight_descriptor: Some(descriptor),
-                ..Default::default()
-            }]))
+            .do_put(req)
             .await
             .map_err(status_to_arrow_error)?
             .into_inner();
@@ -384,9 +457,8 @@ impl PreparedStatement<Channel> {
             .await
             .map_err(status_to_arrow_error)?
             .unwrap();
-        let any: prost_types::Any = Message::decode(&*result.app_metadata)
+        let result = DoPutUpdateResult::decode(&*result.app_metadata)
             .map_err(decode_error_to_arrow_error)?;
-        let result: DoPutUpdateResult = any.unpack()?.unwrap();
         Ok(result.record_count)
     }
 
@@ -400,7 +472,8 @@ impl PreparedStatement<Channel> {
         Ok(&self.dataset_schema)
     }
 
-    /// Set a RecordBatch that contains the parameters that will be bind.
+    /// Set a RecordBatch that contains the parameters that will be bind,
+    /// each row being one set of parameters.
     pub fn set_parameters(
         &mut self,
         parameter_binding: RecordBatch,
@@ -419,14 +492,39 @@ impl PreparedStatement<Channel> {
             r#type: CLOSE_PREPARED_STATEMENT.to_string(),
             body: cmd.as_any().encode_to_vec(),
         };
+        let req = self.request(action)?;
         let _ = self
             .mut_client()?
-            .do_action(action)
+            .do_action(req)
             .await
             .map_err(status_to_arrow_error)?;
         Ok(())
     }
 
+    /// Returns the [`FlightData`] of a `DoPut` of the parameters, the first of
+    /// which carries the descriptor with `cmd`. Without parameters, this is a
+    /// single message with only the descriptor
+    async fn encode_parameters(
+        &self,
+        cmd: Vec<u8>,
+    ) -> Result<Vec<FlightData>, ArrowError> {
+        let mut flight_data: Vec<FlightData> = match &self.parameter_binding {
+            Some(parameters) => {
+                FlightDataEncoderBuilder::new()
+                    .build(stream::iter(vec![Ok(parameters.clone())]))
+                    .try_collect()
+                    .await?
+            }
+            None => vec![FlightData::default()],
+        };
+        flight_data[0].flight_descriptor = Some(FlightDescriptor::new_cmd(cmd));
+        Ok(flight_data)
+    }
+
+    fn request<M>(&self, message: M) -> Result<tonic::Request<M>, ArrowError> {
+        request_with_token(self.token.as_deref(), message)
+    }
+
     fn mut_client(
         &mut self,
     ) -> Result<MutexGuard<FlightServiceClient<Channel>>, ArrowError> {
@@ -436,6 +534,21 @@ impl PreparedStatement<Channel> {
     }
 }
 
+/// Wraps `message` in a `tonic::Request`, authenticated with the bearer `token`
+fn request_with_token<T>(
+    token: Option<&str>,
+    message: T,
+) -> Result<tonic::Request<T>, ArrowError> {
+    let mut req = tonic::Request::new(message);
+    if let Some(token) = token {
+        let val = format!("Bearer {}", token).parse().map_err(|_| {
+            ArrowError::ParseError("Cannot parse bearer token header".to_string())
+        })?;
+        req.metadata_mut().insert("authorization", val);
+    }
+    Ok(req)
+}
+
 fn decode_error_to_arrow_error(err: prost::DecodeError) -> ArrowError {
     ArrowError::IoError(err.to_string())
 }
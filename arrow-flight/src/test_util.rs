@@ -0,0 +1,338 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A [`FlightService`] and server harness shared by the tests of this crate
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+use arrow_schema::ArrowError;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+#[cfg(feature = "tls")]
+use tokio::net::TcpListener;
+use tokio::net::{UnixListener, UnixStream};
+#[cfg(feature = "tls")]
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::wrappers::UnixListenerStream;
+#[cfg(feature = "tls")]
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+use tonic::transport::{Channel, Endpoint, Server};
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::encode::FlightDataEncoderBuilder;
+use crate::exchange::{exchange_request, exchange_response};
+use crate::flight_service_server::{FlightService, FlightServiceServer};
+use crate::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint,
+    FlightInfo, HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
+};
+
+const AUTHORIZATION: &str = "authorization";
+
+type BoxedStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// The bearer token returned by a handshake with [`TestFlightService`]
+pub(crate) const TOKEN: &str = "secret_token";
+
+/// A header echoed by [`TestFlightService`] in its responses
+pub(crate) const TRACE_ID: &str = "x-trace-id";
+
+/// The record batch returned by [`TestFlightService::do_get`]
+pub(crate) fn test_batch() -> RecordBatch {
+    let a = Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef;
+    RecordBatch::try_from_iter(vec![("a", a)]).unwrap()
+}
+
+/// The PEM encoded, self-signed root certificate of the test certificates
+#[cfg(feature = "tls")]
+pub(crate) const CA_PEM: &[u8] = include_bytes!("../examples/data/ca.pem");
+
+/// The PEM encoded certificate of a client, signed by [`CA_PEM`]
+#[cfg(feature = "tls")]
+pub(crate) const CLIENT_PEM: &[u8] = include_bytes!("../examples/data/client.pem");
+
+/// The PEM encoded private key of [`CLIENT_PEM`]
+#[cfg(feature = "tls")]
+pub(crate) const CLIENT_KEY: &[u8] = include_bytes!("../examples/data/client.key");
+
+/// Accepts the basic credentials admin:password in a handshake, returning
+/// the bearer token [`TOKEN`], which is required by all calls other than
+/// `do_exchange`
+///
+/// `do_exchange` echoes each record batch of a request with the descriptor path
+/// `echo`, followed by a message containing the number of rows received as
+/// `app_metadata`
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TestFlightService {
+    /// The number of calls to `get_flight_info` and `do_get` that remain
+    /// to fail as unavailable
+    pub(crate) unavailable: Arc<AtomicUsize>,
+    /// The number of tickets that remain to be returned by `get_flight_info`
+    /// already expired, failing as unavailable in `do_get`
+    pub(crate) expired_tickets: Arc<AtomicUsize>,
+}
+
+impl TestFlightService {
+    /// Returns true if the call should fail as unavailable
+    fn unavailable(&self) -> bool {
+        self.unavailable
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Returns the ticket of the next endpoint returned by `get_flight_info`
+    fn next_ticket(&self) -> Ticket {
+        let expired = self
+            .expired_tickets
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        let ticket = if expired { "expired" } else { "valid" };
+        Ticket {
+            ticket: ticket.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Returns `message` in a response with the [`TRACE_ID`] of `request`, if any
+fn echo_trace_id<T, U>(request: &Request<T>, message: U) -> Response<U> {
+    let mut response = Response::new(message);
+    if let Some(value) = request.metadata().get(TRACE_ID) {
+        response.metadata_mut().insert(TRACE_ID, value.clone());
+    }
+    response
+}
+
+#[allow(clippy::result_large_err)]
+fn check_token<T>(request: &Request<T>) -> Result<(), Status> {
+    match request.metadata().get(AUTHORIZATION) {
+        Some(value) if value == format!("Bearer {}", TOKEN).as_str() => Ok(()),
+        _ => Err(Status::unauthenticated("invalid token")),
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for TestFlightService {
+    type HandshakeStream = BoxedStream<HandshakeResponse>;
+    type ListFlightsStream = BoxedStream<FlightInfo>;
+    type DoGetStream = BoxedStream<FlightData>;
+    type DoPutStream = BoxedStream<PutResult>;
+    type DoActionStream = BoxedStream<crate::Result>;
+    type ListActionsStream = BoxedStream<ActionType>;
+    type DoExchangeStream = BoxedStream<FlightData>;
+
+    async fn handshake(
+        &self,
+        request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        let expected = format!("Basic {}", base64::encode("admin:password"));
+        match request.metadata().get(AUTHORIZATION) {
+            Some(value) if value == expected.as_str() => {}
+            _ => return Err(Status::unauthenticated("invalid credentials")),
+        }
+
+        let response = HandshakeResponse {
+            protocol_version: 0,
+            payload: b"welcome".to_vec(),
+        };
+        let output: Self::HandshakeStream = Box::pin(stream::iter(vec![Ok(response)]));
+        let mut response = Response::new(output);
+        let token = format!("Bearer {}", TOKEN).parse().unwrap();
+        response.metadata_mut().insert(AUTHORIZATION, token);
+        Ok(response)
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        check_token(&request)?;
+        if self.unavailable() {
+            return Err(Status::unavailable("try again"));
+        }
+        let endpoint = FlightEndpoint {
+            ticket: Some(self.next_ticket()),
+            location: vec![],
+        };
+        let info = FlightInfo {
+            endpoint: vec![endpoint],
+            total_records: 3,
+            ..Default::default()
+        };
+        Ok(echo_trace_id(&request, info))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        check_token(&request)?;
+        let options = arrow_ipc::writer::IpcWriteOptions::default();
+        let schema = crate::SchemaAsIpc::new(test_batch().schema().as_ref(), &options)
+            .try_into()
+            .map_err(|e: ArrowError| Status::internal(e.to_string()))?;
+        Ok(Response::new(schema))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        check_token(&request)?;
+        if request.get_ref().ticket == b"expired" {
+            return Err(Status::unavailable("ticket expired"));
+        }
+        if self.unavailable() {
+            // Fails in the stream, rather than the call
+            let output = stream::iter(vec![Err(Status::unavailable("try again"))]);
+            return Ok(Response::new(Box::pin(output)));
+        }
+        let output = FlightDataEncoderBuilder::new()
+            .build(stream::iter(vec![Ok(test_batch())]))
+            .map_err(|e| Status::internal(e.to_string()));
+        Ok(echo_trace_id(&request, Box::pin(output)))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn do_action(
+        &self,
+        request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        check_token(&request)?;
+        if request.get_ref().r#type != "echo" {
+            return Err(Status::not_found("no such action"));
+        }
+        let result = crate::Result {
+            body: request.get_ref().body.clone(),
+        };
+        let output: Self::DoActionStream = Box::pin(stream::iter(vec![Ok(result)]));
+        Ok(echo_trace_id(&request, output))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Err(Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn do_exchange(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        let (descriptor, decoder) = exchange_request(request).await?;
+        if descriptor.path != vec!["echo".to_string()] {
+            return Err(Status::invalid_argument(
+                "expected the descriptor path echo",
+            ));
+        }
+
+        let batches: Vec<RecordBatch> =
+            crate::decode::FlightRecordBatchStream::new(decoder)
+                .try_collect()
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+
+        let trailer = FlightData {
+            app_metadata: rows.to_string().into_bytes(),
+            ..Default::default()
+        };
+        let output = FlightDataEncoderBuilder::new()
+            .build(stream::iter(batches.into_iter().map(Ok)))
+            .chain(stream::iter(vec![Ok(trailer)]));
+        Ok(Response::new(exchange_response(output)))
+    }
+}
+
+/// Serves `service` on a Unix domain socket, and runs `f` with a [`Channel`]
+/// connected to it
+pub(crate) async fn run_service<S, F, Fut>(service: S, f: F)
+where
+    S: FlightService,
+    F: FnOnce(Channel) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("flight.sock");
+    let uds = UnixListener::bind(&path).unwrap();
+    let serve_future = Server::builder()
+        .add_service(FlightServiceServer::new(service))
+        .serve_with_incoming(UnixListenerStream::new(uds));
+
+    let request_future = async {
+        let connector = tower::service_fn(move |_| UnixStream::connect(path.clone()));
+        let channel = Endpoint::try_from("http://example.com")
+            .unwrap()
+            .connect_with_connector(connector)
+            .await
+            .unwrap();
+        f(channel).await
+    };
+
+    tokio::select! {
+        _ = serve_future => panic!("server returned first"),
+        _ = request_future => {},
+    }
+}
+
+/// Serves [`TestFlightService`] with TLS on a local port, using a certificate
+/// for `localhost` signed by [`CA_PEM`], and runs `f` with the uri of the server
+///
+/// If `client_auth` is true, clients must present a certificate signed by [`CA_PEM`]
+#[cfg(feature = "tls")]
+pub(crate) async fn run_tls_service<F, Fut>(client_auth: bool, f: F)
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let cert = include_bytes!("../examples/data/server.pem");
+    let key = include_bytes!("../examples/data/server.key");
+    let mut tls = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+    if client_auth {
+        tls = tls.client_ca_root(Certificate::from_pem(CA_PEM));
+    }
+
+    // Connections are queued by the bound listener until the server accepts them
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let uri = format!("https://{}", listener.local_addr().unwrap());
+    let serve_future = Server::builder()
+        .tls_config(tls)
+        .unwrap()
+        .add_service(FlightServiceServer::new(TestFlightService::default()))
+        .serve_with_incoming(TcpListenerStream::new(listener));
+
+    tokio::select! {
+        _ = serve_future => panic!("server returned first"),
+        _ = f(uri) => {},
+    }
+}
@@ -17,6 +17,7 @@
 
 //! Utilities to assist with reading and writing Arrow data as Flight messages
 
+use crate::encode::prepare_schema_for_flight;
 use crate::{FlightData, IpcMessage, SchemaAsIpc, SchemaResult};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -29,6 +30,10 @@ use arrow_schema::{ArrowError, Schema, SchemaRef};
 
 /// Convert a `RecordBatch` to a vector of `FlightData` representing the bytes of the dictionaries
 /// and a `FlightData` representing the bytes of the batch's values
+///
+/// All the dictionaries of the batch are returned, see
+/// [`FlightDataEncoderBuilder`](crate::encode::FlightDataEncoderBuilder) to only send
+/// dictionaries that have changed since the previous batch of a stream
 pub fn flight_data_from_arrow_batch(
     batch: &RecordBatch,
     options: &IpcWriteOptions,
@@ -47,6 +52,9 @@ pub fn flight_data_from_arrow_batch(
 }
 
 /// Convert a slice of wire protocol `FlightData`s into a vector of `RecordBatch`es
+///
+/// The first `FlightData` must contain the schema, and may be followed by any
+/// interleaving of dictionary and record batch messages
 pub fn flight_data_to_batches(
     flight_data: &[FlightData],
 ) -> Result<Vec<RecordBatch>, ArrowError> {
@@ -63,11 +71,25 @@ pub fn flight_data_to_batches(
     let schema = Arc::new(schema);
 
     let mut batches = vec![];
-    let dictionaries_by_id = HashMap::new();
+    let mut dictionaries_by_id = HashMap::new();
     for datum in flight_data[1..].iter() {
-        let batch =
-            flight_data_to_arrow_batch(datum, schema.clone(), &dictionaries_by_id)?;
-        batches.push(batch);
+        let message = root_as_message(&datum.data_header[..]).map_err(|err| {
+            ArrowError::ParseError(format!("Unable to get root as message: {:?}", err))
+        })?;
+        match message.header_as_dictionary_batch() {
+            Some(dictionary) => reader::read_dictionary(
+                &Buffer::from(&datum.data_body),
+                dictionary,
+                &schema,
+                &mut dictionaries_by_id,
+                &message.version(),
+            )?,
+            None => batches.push(flight_data_to_arrow_batch(
+                datum,
+                schema.clone(),
+                &dictionaries_by_id,
+            )?),
+        }
     }
     Ok(batches)
 }
@@ -141,23 +163,77 @@ pub fn ipc_message_from_arrow_schema(
 }
 
 /// Convert `RecordBatch`es to wire protocol `FlightData`s
+///
+/// Each batch is preceded by the dictionaries that have changed since the previous
+/// batch, either as a delta or a replacement
 pub fn batches_to_flight_data(
     schema: Schema,
     batches: Vec<RecordBatch>,
 ) -> Result<Vec<FlightData>, ArrowError> {
     let options = IpcWriteOptions::default();
+    let schema = Arc::new(prepare_schema_for_flight(&schema));
     let schema_flight_data: FlightData = SchemaAsIpc::new(&schema, &options).into();
-    let mut dictionaries = vec![];
-    let mut flight_data = vec![];
+    let data_gen = writer::IpcDataGenerator::default();
+    let mut dictionary_tracker = writer::DictionaryTracker::new(false);
+
+    let mut flight_data = vec![schema_flight_data];
     for batch in batches.iter() {
-        let (flight_dictionaries, flight_datum) =
-            flight_data_from_arrow_batch(batch, &options);
-        dictionaries.extend(flight_dictionaries);
-        flight_data.push(flight_datum);
+        let batch = RecordBatch::try_new(schema.clone(), batch.columns().to_vec())?;
+        let (encoded_dictionaries, encoded_batch) =
+            data_gen.encoded_batch(&batch, &mut dictionary_tracker, &options)?;
+        flight_data.extend(encoded_dictionaries.into_iter().map(Into::into));
+        flight_data.push(encoded_batch.into());
     }
-    let mut stream = vec![schema_flight_data];
-    stream.extend(dictionaries.into_iter());
-    stream.extend(flight_data.into_iter());
-    let flight_data: Vec<_> = stream.into_iter().collect();
     Ok(flight_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::types::Int8Type;
+    use arrow_array::{DictionaryArray, Int32Array};
+
+    #[test]
+    fn test_flight_data_to_batches_dictionary() {
+        let dict: DictionaryArray<Int8Type> =
+            vec!["a", "b", "a", "c"].into_iter().collect();
+        let ints = Int32Array::from(vec![1, 2, 3, 4]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("dict", Arc::new(dict) as ArrayRef),
+            ("ints", Arc::new(ints) as ArrayRef),
+        ])
+        .unwrap();
+
+        let flight_data =
+            batches_to_flight_data(batch.schema().as_ref().clone(), vec![batch.clone()])
+                .unwrap();
+        // schema, dictionary and record batch
+        assert_eq!(flight_data.len(), 3);
+
+        let decoded = flight_data_to_batches(&flight_data).unwrap();
+        assert_eq!(decoded, vec![batch]);
+    }
+
+    #[test]
+    fn test_batches_to_flight_data_changing_dictionaries() {
+        let make_batch = |values: Vec<&str>| {
+            let dict: DictionaryArray<Int8Type> = values.into_iter().collect();
+            RecordBatch::try_from_iter(vec![("dict", Arc::new(dict) as ArrayRef)])
+                .unwrap()
+        };
+        let batches = vec![
+            make_batch(vec!["a", "b"]),
+            make_batch(vec!["a", "b"]),
+            make_batch(vec!["a", "b", "c"]),
+            make_batch(vec!["d"]),
+        ];
+
+        let schema = batches[0].schema().as_ref().clone();
+        let flight_data = batches_to_flight_data(schema, batches.clone()).unwrap();
+        // schema, 4 batches and 3 dictionaries, as the second is unchanged
+        assert_eq!(flight_data.len(), 8);
+
+        let decoded = flight_data_to_batches(&flight_data).unwrap();
+        assert_eq!(decoded, batches);
+    }
+}
@@ -51,39 +51,14 @@ fn read_generated_files_014() {
     });
 }
 
-#[test]
-#[should_panic(expected = "Big Endian is not supported for Decimal!")]
-fn read_decimal_be_file_should_panic() {
-    let testdata = arrow_test_data();
-    let file = File::open(format!(
-        "{}/arrow-ipc-stream/integration/1.0.0-bigendian/generated_decimal.arrow_file",
-        testdata
-    ))
-    .unwrap();
-    FileReader::try_new(file, None).unwrap();
-}
-
-#[test]
-#[should_panic(
-    expected = "Last offset 687865856 of Utf8 is larger than values length 41"
-)]
-fn read_dictionary_be_not_implemented() {
-    // The offsets are not translated for big-endian files
-    // https://github.com/apache/arrow-rs/issues/859
-    let testdata = arrow_test_data();
-    let file = File::open(format!(
-        "{}/arrow-ipc-stream/integration/1.0.0-bigendian/generated_dictionary.arrow_file",
-        testdata
-    ))
-    .unwrap();
-    FileReader::try_new(file, None).unwrap();
-}
-
 #[test]
 fn read_generated_be_files_should_work() {
-    // complementary to the previous test
+    // big-endian files are byte swapped when read
     let testdata = arrow_test_data();
+    let version = "1.0.0-bigendian";
     let paths = vec![
+        "generated_decimal",
+        "generated_dictionary",
         "generated_interval",
         "generated_datetime",
         "generated_map",
@@ -96,12 +71,16 @@ fn read_generated_be_files_should_work() {
     ];
     paths.iter().for_each(|path| {
         let file = File::open(format!(
-            "{}/arrow-ipc-stream/integration/1.0.0-bigendian/{}.arrow_file",
-            testdata, path
+            "{}/arrow-ipc-stream/integration/{}/{}.arrow_file",
+            testdata, version, path
         ))
         .unwrap();
 
-        FileReader::try_new(file, None).unwrap();
+        let mut reader = FileReader::try_new(file, None).unwrap();
+
+        // read expected JSON output
+        let arrow_json = read_gzip_json(version, path);
+        assert!(arrow_json.equals_reader(&mut reader).unwrap());
     });
 }
 
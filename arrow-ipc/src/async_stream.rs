@@ -0,0 +1,440 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Async Arrow IPC Stream Reader and Writer
+//!
+//! [`AsyncStreamReader`] and [`AsyncStreamWriter`] read and write the Arrow IPC
+//! streaming format over tokio's [`AsyncRead`] and [`AsyncWrite`], such as a
+//! network socket, without blocking the calling task on IO.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+//! # use futures::TryStreamExt;
+//! use arrow_ipc::async_stream::{AsyncStreamReader, AsyncStreamWriter};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! # let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+//! # let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
+//! let (client, server) = tokio::io::duplex(1024);
+//!
+//! let schema = batch.schema();
+//! let write = async move {
+//!     let mut writer = AsyncStreamWriter::try_new(client, &schema).await?;
+//!     writer.write(&batch).await?;
+//!     writer.finish().await
+//! };
+//!
+//! let read = async move {
+//!     let reader = AsyncStreamReader::try_new(server, None).await?;
+//!     reader.into_stream().try_collect::<Vec<_>>().await
+//! };
+//!
+//! let (written, batches) = tokio::join!(write, read);
+//! written.unwrap();
+//! assert_eq!(batches.unwrap()[0].num_rows(), 3);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use arrow_array::RecordBatch;
+use arrow_buffer::MutableBuffer;
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::reader::{
+    parse_body_length, parse_metadata_length, StreamMessageDecoder, READ_CHUNK_SIZE,
+};
+use crate::writer::{
+    write_continuation, write_message, DictionaryTracker, IpcDataGenerator,
+    IpcWriteOptions,
+};
+use crate::CONTINUATION_MARKER;
+
+/// Async Arrow Stream reader, see the [module documentation](self) for an example
+pub struct AsyncStreamReader<R> {
+    /// The async reader
+    reader: R,
+
+    /// Decodes the messages read from the stream
+    decoder: StreamMessageDecoder,
+
+    /// An indicator of whether the stream is complete.
+    finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncStreamReader<R> {
+    /// Try to create a new stream reader
+    ///
+    /// The first message in the stream is the schema, the reader will fail if it does not
+    /// encounter a schema.
+    pub async fn try_new(
+        mut reader: R,
+        projection: Option<Vec<usize>>,
+    ) -> Result<Self, ArrowError> {
+        let meta_buffer = read_metadata(&mut reader).await?;
+        let decoder = StreamMessageDecoder::try_new(meta_buffer.as_deref(), projection)?;
+        Ok(Self {
+            reader,
+            decoder,
+            finished: false,
+        })
+    }
+
+    /// Return the schema of the stream
+    pub fn schema(&self) -> SchemaRef {
+        self.decoder.schema.clone()
+    }
+
+    /// Return the custom metadata of the message of the most recently read
+    /// record batch, empty if none has been read
+    pub fn batch_metadata(&self) -> &HashMap<String, String> {
+        &self.decoder.batch_metadata
+    }
+
+    /// Only decode the fields at the indices in `projection`, see
+    /// [projection](crate::reader#projection)
+    pub fn with_projection(mut self, projection: Vec<usize>) -> Result<Self, ArrowError> {
+        self.decoder.set_projection(projection)?;
+        Ok(self)
+    }
+
+    /// Skip validating the arrays read, see [validation](crate::reader#validation)
+    ///
+    /// # Safety
+    ///
+    /// If `skip_validation` is true, the IPC data must be known to contain valid arrays
+    pub unsafe fn with_skip_validation(mut self, skip_validation: bool) -> Self {
+        self.decoder.decode_options.skip_validation = skip_validation;
+        self
+    }
+
+    /// Check if the stream is finished
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Read the next [`RecordBatch`] from the stream, returning `None` once the
+    /// end of the stream is reached
+    pub async fn next(&mut self) -> Option<Result<RecordBatch, ArrowError>> {
+        self.maybe_next().await.transpose()
+    }
+
+    async fn maybe_next(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        // Dictionary batches precede the record batch that uses them
+        while !self.finished {
+            let meta_buffer = match read_metadata(&mut self.reader).await? {
+                Some(meta_buffer) => meta_buffer,
+                None => {
+                    self.finished = true;
+                    return Ok(None);
+                }
+            };
+            let message = match self.decoder.parse_message(&meta_buffer)? {
+                Some(message) => message,
+                None => return Ok(None),
+            };
+            let len = parse_body_length(&message)?;
+            let body = read_exact_chunked(&mut self.reader, len).await?;
+            if let Some(batch) = self.decoder.decode(message, &body.into())? {
+                return Ok(Some(batch));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<R: AsyncRead + Unpin + Send + 'static> AsyncStreamReader<R> {
+    /// Convert this reader into a [`Stream`](futures::Stream) of [`RecordBatch`]
+    pub fn into_stream(self) -> BoxStream<'static, Result<RecordBatch, ArrowError>> {
+        futures::stream::unfold(self, |mut reader| async move {
+            let next = reader.next().await?;
+            Some((next, reader))
+        })
+        .boxed()
+    }
+}
+
+/// Reads exactly `len` bytes from `reader`, growing the buffer as data is read
+/// rather than trusting `len` to size the allocation
+async fn read_exact_chunked<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    len: usize,
+) -> Result<MutableBuffer, ArrowError> {
+    let mut buf = MutableBuffer::new(len.min(READ_CHUNK_SIZE));
+    while buf.len() < len {
+        let start = buf.len();
+        buf.resize(len.min(start + READ_CHUNK_SIZE), 0);
+        reader.read_exact(&mut buf[start..]).await?;
+    }
+    Ok(buf)
+}
+
+/// Reads the metadata of the next message in the stream, returning `None` at
+/// the end of the stream, indicated by either a zero length or the end of the input
+async fn read_metadata<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<Vec<u8>>, ArrowError> {
+    // determine metadata length
+    let mut meta_size: [u8; 4] = [0; 4];
+    match reader.read_exact(&mut meta_size).await {
+        Ok(_) => {}
+        // Handle EOF without the "0xFFFFFFFF 0x00000000"
+        // valid according to:
+        // https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+
+    // If a continuation marker is encountered, skip over it and read
+    // the size from the next four bytes.
+    if meta_size == CONTINUATION_MARKER {
+        reader.read_exact(&mut meta_size).await?;
+    }
+    match parse_metadata_length(meta_size)? {
+        Some(len) => Ok(Some(
+            read_exact_chunked(reader, len).await?.as_slice().to_vec(),
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Async Arrow Stream writer, see the [module documentation](self) for an example
+///
+/// Each message is encoded in memory before being written to the underlying
+/// [`AsyncWrite`], which is flushed after each [`RecordBatch`].
+pub struct AsyncStreamWriter<W> {
+    /// The object to write to
+    writer: W,
+    /// IPC write options
+    write_options: IpcWriteOptions,
+    /// Whether the end of stream marker has been written, and the writer is finished
+    finished: bool,
+    /// Keeps track of dictionaries that have been written
+    dictionary_tracker: DictionaryTracker,
+    /// The encoded messages that have yet to be written
+    buffer: Vec<u8>,
+
+    data_gen: IpcDataGenerator,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncStreamWriter<W> {
+    /// Try create a new writer, with the schema written as part of the header
+    pub async fn try_new(writer: W, schema: &Schema) -> Result<Self, ArrowError> {
+        Self::try_new_with_options(writer, schema, IpcWriteOptions::default()).await
+    }
+
+    /// Try create a new writer with [`IpcWriteOptions`], with the schema written as
+    /// part of the header
+    pub async fn try_new_with_options(
+        writer: W,
+        schema: &Schema,
+        write_options: IpcWriteOptions,
+    ) -> Result<Self, ArrowError> {
+        let data_gen = IpcDataGenerator::default();
+        let mut buffer = vec![];
+        let encoded_message = data_gen.schema_to_bytes(schema, &write_options);
+        write_message(&mut buffer, encoded_message, &write_options)?;
+
+        let dictionary_tracker = DictionaryTracker::new(false);
+        let mut this = Self {
+            writer,
+            write_options,
+            finished: false,
+            dictionary_tracker,
+            buffer,
+            data_gen,
+        };
+        this.write_buffer().await?;
+        Ok(this)
+    }
+
+    /// Write a record batch to the stream
+    pub async fn write(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        self.write_with_metadata(batch, &HashMap::new()).await
+    }
+
+    /// Write a record batch to the stream, with the custom key/value `metadata`
+    /// attached to its message
+    pub async fn write_with_metadata(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(), ArrowError> {
+        if self.finished {
+            return Err(ArrowError::IoError(
+                "Cannot write record batch to stream writer as it is closed".to_string(),
+            ));
+        }
+
+        let (encoded_dictionaries, encoded_message) =
+            self.data_gen.encoded_batch_with_metadata(
+                batch,
+                metadata,
+                &mut self.dictionary_tracker,
+                &self.write_options,
+            )?;
+
+        for encoded_dictionary in encoded_dictionaries {
+            write_message(&mut self.buffer, encoded_dictionary, &self.write_options)?;
+        }
+        write_message(&mut self.buffer, encoded_message, &self.write_options)?;
+        self.write_buffer().await
+    }
+
+    /// Write continuation bytes, and mark the stream as done
+    pub async fn finish(&mut self) -> Result<(), ArrowError> {
+        if self.finished {
+            return Err(ArrowError::IoError(
+                "Cannot write footer to stream writer as it is closed".to_string(),
+            ));
+        }
+
+        write_continuation(&mut self.buffer, &self.write_options, 0)?;
+        self.write_buffer().await?;
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Finishes the stream if not already finished, and returns the underlying writer
+    pub async fn into_inner(mut self) -> Result<W, ArrowError> {
+        if !self.finished {
+            self.finish().await?;
+        }
+        Ok(self.writer)
+    }
+
+    /// Writes and flushes the encoded messages
+    async fn write_buffer(&mut self) -> Result<(), ArrowError> {
+        self.writer.write_all(&self.buffer).await?;
+        self.writer.flush().await?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::StreamReader;
+    use crate::test_util::sample_batches;
+    use crate::writer::StreamWriter;
+    use futures::TryStreamExt;
+    use std::io::Cursor;
+
+    #[tokio::test]
+    async fn test_async_write_sync_read() {
+        let batches = sample_batches(true);
+        let mut writer = AsyncStreamWriter::try_new(vec![], &batches[0].schema())
+            .await
+            .unwrap();
+        for batch in &batches {
+            writer.write(batch).await.unwrap();
+        }
+        let data = writer.into_inner().await.unwrap();
+
+        let mut expected = vec![];
+        {
+            let mut writer =
+                StreamWriter::try_new(&mut expected, &batches[0].schema()).unwrap();
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        assert_eq!(data, expected);
+
+        let reader = StreamReader::try_new(data.as_slice(), None).unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, batches);
+    }
+
+    #[tokio::test]
+    async fn test_async_read() {
+        let batches = sample_batches(true);
+        let mut data = vec![];
+        {
+            let mut writer =
+                StreamWriter::try_new(&mut data, &batches[0].schema()).unwrap();
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let mut reader = AsyncStreamReader::try_new(data.as_slice(), None)
+            .await
+            .unwrap();
+        assert_eq!(reader.schema(), batches[0].schema());
+        for batch in &batches {
+            assert_eq!(&reader.next().await.unwrap().unwrap(), batch);
+        }
+        assert!(reader.next().await.is_none());
+        assert!(reader.is_finished());
+
+        // A stream without the end of stream marker
+        let truncated = Cursor::new(data[..data.len() - 8].to_vec());
+        let reader = AsyncStreamReader::try_new(truncated, Some(vec![0, 2]))
+            .await
+            .unwrap();
+        let read: Vec<_> = reader.into_stream().try_collect().await.unwrap();
+        assert_eq!(read.len(), 3);
+        assert_eq!(read[2], batches[2].project(&[0, 2]).unwrap());
+
+        let err = AsyncStreamReader::try_new(&data[..0], None)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("empty stream"), "{}", err);
+
+        // A stream truncated within the body of the last record batch
+        let truncated = Cursor::new(data[..data.len() - 20].to_vec());
+        let reader = AsyncStreamReader::try_new(truncated, None).await.unwrap();
+        let err = reader
+            .into_stream()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ArrowError::IoError(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_async_read_hostile_lengths() {
+        // A metadata length far larger than the stream is not allocated up front
+        let mut data = CONTINUATION_MARKER.to_vec();
+        data.extend_from_slice(&i32::MAX.to_le_bytes());
+        data.extend_from_slice(&[0; 16]);
+        let err = AsyncStreamReader::try_new(data.as_slice(), None)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("early eof"), "{}", err);
+
+        data[4..8].copy_from_slice(&(-8_i32).to_le_bytes());
+        let err = AsyncStreamReader::try_new(data.as_slice(), None)
+            .await
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("Invalid message metadata length -8"));
+    }
+}
@@ -70,9 +70,10 @@ impl CompressionCodec {
             output.extend_from_slice(&uncompressed_data_len.to_le_bytes());
             self.compress(input, output)?;
 
-            let compression_len = output.len();
-            if compression_len > uncompressed_data_len {
-                // length of compressed data was larger than
+            let compression_len =
+                output.len() - original_output_len - LENGTH_OF_PREFIX_DATA as usize;
+            if compression_len >= uncompressed_data_len {
+                // length of compressed data was not smaller than
                 // uncompressed data, use the uncompressed data with
                 // length -1 to indicate that we don't compress the
                 // data
@@ -97,6 +98,12 @@ impl CompressionCodec {
     ) -> Result<Buffer, ArrowError> {
         // read the first 8 bytes to determine if the data is
         // compressed
+        if input.len() < LENGTH_OF_PREFIX_DATA as usize {
+            return Err(ArrowError::IoError(format!(
+                "Compressed buffer of {} bytes is too short to contain its uncompressed length",
+                input.len()
+            )));
+        }
         let decompressed_length = read_uncompressed_size(input);
         let buffer = if decompressed_length == 0 {
             // emtpy
@@ -104,12 +111,36 @@ impl CompressionCodec {
         } else if decompressed_length == LENGTH_NO_COMPRESSED_DATA {
             // no compression
             input.slice(LENGTH_OF_PREFIX_DATA as usize)
+        } else if decompressed_length < 0 {
+            return Err(ArrowError::IoError(format!(
+                "Invalid uncompressed length {} of compressed buffer",
+                decompressed_length
+            )));
         } else {
             // decompress data using the codec
-            let mut uncompressed_buffer =
-                Vec::with_capacity(decompressed_length as usize);
+            //
+            // The uncompressed length is read from the file, and so is not trusted
+            // to size the allocation, which instead grows as data is decompressed
+            let decompressed_length =
+                usize::try_from(decompressed_length).map_err(|_| {
+                    ArrowError::IoError(format!(
+                        "Uncompressed length {} of compressed buffer is too large",
+                        decompressed_length
+                    ))
+                })?;
             let input_data = &input[(LENGTH_OF_PREFIX_DATA as usize)..];
-            self.decompress(input_data, &mut uncompressed_buffer)?;
+            let capacity = decompressed_length.min(input_data.len());
+            let mut uncompressed_buffer = Vec::with_capacity(capacity);
+            // read at most one more byte than expected to detect longer data
+            let limit = decompressed_length.saturating_add(1);
+            self.decompress(input_data, &mut uncompressed_buffer, limit)?;
+            if uncompressed_buffer.len() != decompressed_length {
+                return Err(ArrowError::IoError(format!(
+                    "Expected {} bytes from decompressing buffer, got {}",
+                    decompressed_length,
+                    uncompressed_buffer.len()
+                )));
+            }
             Buffer::from(uncompressed_buffer)
         };
         Ok(buffer)
@@ -124,16 +155,17 @@ impl CompressionCodec {
         }
     }
 
-    /// Decompress the data in input buffer and write to output buffer
-    /// using the specified compression
+    /// Decompress the data in input buffer and write at most `limit` bytes
+    /// to output buffer using the specified compression
     fn decompress(
         &self,
         input: &[u8],
         output: &mut Vec<u8>,
+        limit: usize,
     ) -> Result<usize, ArrowError> {
         match self {
-            CompressionCodec::Lz4Frame => decompress_lz4(input, output),
-            CompressionCodec::Zstd => decompress_zstd(input, output),
+            CompressionCodec::Lz4Frame => decompress_lz4(input, output, limit),
+            CompressionCodec::Zstd => decompress_zstd(input, output, limit),
         }
     }
 }
@@ -156,14 +188,23 @@ fn compress_lz4(_input: &[u8], _output: &mut Vec<u8>) -> Result<(), ArrowError>
 }
 
 #[cfg(feature = "lz4")]
-fn decompress_lz4(input: &[u8], output: &mut Vec<u8>) -> Result<usize, ArrowError> {
+fn decompress_lz4(
+    input: &[u8],
+    output: &mut Vec<u8>,
+    limit: usize,
+) -> Result<usize, ArrowError> {
     use std::io::Read;
-    Ok(lz4::Decoder::new(input)?.read_to_end(output)?)
+    let decoder = lz4::Decoder::new(input)?;
+    Ok(decoder.take(limit as u64).read_to_end(output)?)
 }
 
 #[cfg(not(feature = "lz4"))]
 #[allow(clippy::ptr_arg)]
-fn decompress_lz4(_input: &[u8], _output: &mut Vec<u8>) -> Result<usize, ArrowError> {
+fn decompress_lz4(
+    _input: &[u8],
+    _output: &mut Vec<u8>,
+    _limit: usize,
+) -> Result<usize, ArrowError> {
     Err(ArrowError::InvalidArgumentError(
         "lz4 IPC decompression requires the lz4 feature".to_string(),
     ))
@@ -187,14 +228,23 @@ fn compress_zstd(_input: &[u8], _output: &mut Vec<u8>) -> Result<(), ArrowError>
 }
 
 #[cfg(feature = "zstd")]
-fn decompress_zstd(input: &[u8], output: &mut Vec<u8>) -> Result<usize, ArrowError> {
+fn decompress_zstd(
+    input: &[u8],
+    output: &mut Vec<u8>,
+    limit: usize,
+) -> Result<usize, ArrowError> {
     use std::io::Read;
-    Ok(zstd::Decoder::new(input)?.read_to_end(output)?)
+    let decoder = zstd::Decoder::new(input)?;
+    Ok(decoder.take(limit as u64).read_to_end(output)?)
 }
 
 #[cfg(not(feature = "zstd"))]
 #[allow(clippy::ptr_arg)]
-fn decompress_zstd(_input: &[u8], _output: &mut Vec<u8>) -> Result<usize, ArrowError> {
+fn decompress_zstd(
+    _input: &[u8],
+    _output: &mut Vec<u8>,
+    _limit: usize,
+) -> Result<usize, ArrowError> {
     Err(ArrowError::InvalidArgumentError(
         "zstd IPC decompression requires the zstd feature".to_string(),
     ))
@@ -214,6 +264,96 @@ fn read_uncompressed_size(buffer: &[u8]) -> i64 {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompress_uncompressed_prefix() {
+        // Buffers marked with a length of -1 are returned as is, for any codec
+        let mut input = (-1_i64).to_le_bytes().to_vec();
+        input.extend_from_slice(b"hello");
+        for codec in [CompressionCodec::Lz4Frame, CompressionCodec::Zstd] {
+            let output = codec.decompress_to_buffer(&Buffer::from(&input)).unwrap();
+            assert_eq!(output.as_slice(), b"hello");
+
+            let output = codec
+                .decompress_to_buffer(&Buffer::from(0_i64.to_le_bytes()))
+                .unwrap();
+            assert!(output.is_empty());
+
+            let err = codec
+                .decompress_to_buffer(&Buffer::from(&[1_u8, 2, 3]))
+                .unwrap_err();
+            assert!(err.to_string().contains("too short"), "{}", err);
+
+            let err = codec
+                .decompress_to_buffer(&Buffer::from((-2_i64).to_le_bytes()))
+                .unwrap_err();
+            assert!(err.to_string().contains("Invalid uncompressed length"));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_decompress_length_mismatch() {
+        let codec = CompressionCodec::Zstd;
+        let mut output = vec![];
+        codec.compress_to_vec(&[0; 1024], &mut output).unwrap();
+        // Corrupt the uncompressed length
+        output[..8].copy_from_slice(&1000_i64.to_le_bytes());
+        let err = codec
+            .decompress_to_buffer(&Buffer::from(&output))
+            .unwrap_err();
+        assert!(err.to_string().contains("Expected 1000 bytes"), "{}", err);
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_decompress_hostile_length() {
+        let codec = CompressionCodec::Zstd;
+        let mut output = vec![];
+        codec.compress_to_vec(&[0; 1024], &mut output).unwrap();
+        // A huge uncompressed length must not be used to size the allocation
+        output[..8].copy_from_slice(&i64::MAX.to_le_bytes());
+        let err = codec
+            .decompress_to_buffer(&Buffer::from(&output))
+            .unwrap_err();
+        assert!(err.to_string().contains("Expected"), "{}", err);
+
+        // Data longer than the uncompressed length is not fully decompressed
+        output[..8].copy_from_slice(&10_i64.to_le_bytes());
+        let err = codec
+            .decompress_to_buffer(&Buffer::from(&output))
+            .unwrap_err();
+        assert!(err.to_string().contains("Expected 10 bytes"), "{}", err);
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_compress_to_equal_length() {
+        let codec = CompressionCodec::Zstd;
+        // An input that compresses to exactly its own length
+        let input = (1..=255_u8)
+            .flat_map(|distinct| {
+                (0..64).map(move |zeros| {
+                    let mut input: Vec<u8> = (0..distinct).collect();
+                    input.resize(distinct as usize + zeros, 0);
+                    input
+                })
+            })
+            .find(|input| {
+                let mut compressed = vec![];
+                codec.compress(input, &mut compressed).unwrap();
+                compressed.len() == input.len()
+            })
+            .unwrap();
+
+        // Buffers that do not shrink when compressed are written uncompressed
+        let mut output = vec![];
+        codec.compress_to_vec(&input, &mut output).unwrap();
+        assert_eq!(output[..8], LENGTH_NO_COMPRESSED_DATA.to_le_bytes());
+        assert_eq!(&output[8..], input.as_slice());
+    }
+
     #[test]
     #[cfg(feature = "lz4")]
     fn test_lz4_compression() {
@@ -223,7 +363,7 @@ mod tests {
         codec.compress(input_bytes, &mut output_bytes).unwrap();
         let mut result_output_bytes: Vec<u8> = Vec::new();
         codec
-            .decompress(output_bytes.as_slice(), &mut result_output_bytes)
+            .decompress(output_bytes.as_slice(), &mut result_output_bytes, 1024)
             .unwrap();
         assert_eq!(input_bytes, result_output_bytes.as_slice());
     }
@@ -237,7 +377,7 @@ mod tests {
         codec.compress(input_bytes, &mut output_bytes).unwrap();
         let mut result_output_bytes: Vec<u8> = Vec::new();
         codec
-            .decompress(output_bytes.as_slice(), &mut result_output_bytes)
+            .decompress(output_bytes.as_slice(), &mut result_output_bytes, 1024)
             .unwrap();
         assert_eq!(input_bytes, result_output_bytes.as_slice());
     }
@@ -40,6 +40,15 @@ pub fn schema_to_fb(schema: &Schema) -> FlatBufferBuilder {
 pub fn schema_to_fb_offset<'a>(
     fbb: &mut FlatBufferBuilder<'a>,
     schema: &Schema,
+) -> WIPOffset<crate::Schema<'a>> {
+    schema_to_fb_offset_with_endianness(fbb, schema, crate::endian::native())
+}
+
+/// Serialize a schema, recording that its buffers are written with `endianness`
+pub(crate) fn schema_to_fb_offset_with_endianness<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    schema: &Schema,
+    endianness: crate::Endianness,
 ) -> WIPOffset<crate::Schema<'a>> {
     let fields = schema
         .fields()
@@ -48,26 +57,10 @@ pub fn schema_to_fb_offset<'a>(
         .collect::<Vec<_>>();
     let fb_field_list = fbb.create_vector(&fields);
 
-    let fb_metadata_list = if !schema.metadata().is_empty() {
-        let custom_metadata = schema
-            .metadata()
-            .iter()
-            .map(|(k, v)| {
-                let fb_key_name = fbb.create_string(k);
-                let fb_val_name = fbb.create_string(v);
-
-                let mut kv_builder = crate::KeyValueBuilder::new(fbb);
-                kv_builder.add_key(fb_key_name);
-                kv_builder.add_value(fb_val_name);
-                kv_builder.finish()
-            })
-            .collect::<Vec<_>>();
-        Some(fbb.create_vector(&custom_metadata))
-    } else {
-        None
-    };
+    let fb_metadata_list = metadata_to_fb(fbb, schema.metadata());
 
     let mut builder = crate::SchemaBuilder::new(fbb);
+    builder.add_endianness(endianness);
     builder.add_fields(fb_field_list);
     if let Some(fb_metadata_list) = fb_metadata_list {
         builder.add_custom_metadata(fb_metadata_list);
@@ -75,6 +68,43 @@ pub fn schema_to_fb_offset<'a>(
     builder.finish()
 }
 
+/// Serialize custom key/value metadata, sorted by key, returning `None` if empty
+pub(crate) fn metadata_to_fb<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    metadata: &HashMap<String, String>,
+) -> Option<WIPOffset<Vector<'a, ForwardsUOffset<crate::KeyValue<'a>>>>> {
+    if metadata.is_empty() {
+        return None;
+    }
+    let mut entries: Vec<_> = metadata.iter().collect();
+    entries.sort_unstable();
+
+    let kv_vec = entries
+        .into_iter()
+        .map(|(k, v)| {
+            let kv_args = crate::KeyValueArgs {
+                key: Some(fbb.create_string(k)),
+                value: Some(fbb.create_string(v)),
+            };
+            crate::KeyValue::create(fbb, &kv_args)
+        })
+        .collect::<Vec<_>>();
+    Some(fbb.create_vector(&kv_vec))
+}
+
+/// Deserialize custom key/value metadata, treating a missing value as empty
+pub(crate) fn metadata_from_fb(
+    list: Option<Vector<'_, ForwardsUOffset<crate::KeyValue<'_>>>>,
+) -> HashMap<String, String> {
+    list.into_iter()
+        .flatten()
+        .filter_map(|kv| {
+            let value = kv.value().unwrap_or_default();
+            kv.key().map(|k| (k.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
 /// Convert an IPC Field to Arrow Field
 impl<'a> From<crate::Field<'a>> for Field {
     fn from(field: crate::Field) -> Field {
@@ -94,16 +124,7 @@ impl<'a> From<crate::Field<'a>> for Field {
             )
         };
 
-        let mut metadata_map = HashMap::default();
-        if let Some(list) = field.custom_metadata() {
-            for kv in list {
-                if let (Some(k), Some(v)) = (kv.key(), kv.value()) {
-                    metadata_map.insert(k.to_string(), v.to_string());
-                }
-            }
-        }
-
-        arrow_field.with_metadata(metadata_map)
+        arrow_field.with_metadata(metadata_from_fb(field.custom_metadata()))
     }
 }
 
@@ -114,29 +135,10 @@ pub fn fb_to_schema(fb: crate::Schema) -> Schema {
     let len = c_fields.len();
     for i in 0..len {
         let c_field: crate::Field = c_fields.get(i);
-        match c_field.type_type() {
-            crate::Type::Decimal if fb.endianness() == crate::Endianness::Big => {
-                unimplemented!("Big Endian is not supported for Decimal!")
-            }
-            _ => (),
-        };
         fields.push(c_field.into());
     }
 
-    let mut metadata: HashMap<String, String> = HashMap::default();
-    if let Some(md_fields) = fb.custom_metadata() {
-        let len = md_fields.len();
-        for i in 0..len {
-            let kv = md_fields.get(i);
-            let k_str = kv.key();
-            let v_str = kv.value();
-            if let Some(k) = k_str {
-                if let Some(v) = v_str {
-                    metadata.insert(k.to_string(), v.to_string());
-                }
-            }
-        }
-    }
+    let metadata = metadata_from_fb(fb.custom_metadata());
     Schema::new_with_metadata(fields, metadata)
 }
 
@@ -429,19 +431,7 @@ pub(crate) fn build_field<'a>(
     field: &Field,
 ) -> WIPOffset<crate::Field<'a>> {
     // Optional custom metadata.
-    let mut fb_metadata = None;
-    if !field.metadata().is_empty() {
-        let mut kv_vec = vec![];
-        for (k, v) in field.metadata() {
-            let kv_args = crate::KeyValueArgs {
-                key: Some(fbb.create_string(k.as_str())),
-                value: Some(fbb.create_string(v.as_str())),
-            };
-            let kv_offset = crate::KeyValue::create(fbb, &kv_args);
-            kv_vec.push(kv_offset);
-        }
-        fb_metadata = Some(fbb.create_vector(&kv_vec));
-    };
+    let fb_metadata = metadata_to_fb(fbb, field.metadata());
 
     let fb_field_name = fbb.create_string(field.name().as_str());
     let field_type = get_fb_field_type(field.data_type(), fbb);
@@ -0,0 +1,116 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Byte swapping of IPC buffers written with a non-native endianness
+
+use arrow_buffer::{Buffer, MutableBuffer};
+use arrow_schema::{DataType, IntervalUnit, UnionMode};
+
+/// Returns the endianness of the target platform
+pub(crate) fn native() -> crate::Endianness {
+    if cfg!(target_endian = "big") {
+        crate::Endianness::Big
+    } else {
+        crate::Endianness::Little
+    }
+}
+
+/// Returns the widths in bytes of the fixed width values making up each
+/// element of the `index`th buffer of an [`arrow_data::ArrayData`] of `data_type`,
+/// excluding the validity buffer, or an empty slice if it needs no byte swapping
+pub(crate) fn buffer_widths(data_type: &DataType, index: usize) -> &'static [usize] {
+    match (data_type, index) {
+        (
+            DataType::Utf8 | DataType::Binary | DataType::List(_) | DataType::Map(_, _),
+            0,
+        ) => &[4],
+        (DataType::LargeUtf8 | DataType::LargeBinary | DataType::LargeList(_), 0) => &[8],
+        (DataType::Union(_, _, UnionMode::Dense), 1) => &[4],
+        (DataType::Dictionary(key_type, _), 0) => buffer_widths(key_type, 0),
+        (DataType::Int16 | DataType::UInt16 | DataType::Float16, 0) => &[2],
+        (
+            DataType::Int32
+            | DataType::UInt32
+            | DataType::Float32
+            | DataType::Date32
+            | DataType::Time32(_)
+            | DataType::Interval(IntervalUnit::YearMonth),
+            0,
+        ) => &[4],
+        (
+            DataType::Int64
+            | DataType::UInt64
+            | DataType::Float64
+            | DataType::Date64
+            | DataType::Time64(_)
+            | DataType::Timestamp(_, _)
+            | DataType::Duration(_),
+            0,
+        ) => &[8],
+        (DataType::Interval(IntervalUnit::DayTime), 0) => &[4, 4],
+        (DataType::Interval(IntervalUnit::MonthDayNano), 0) => &[4, 4, 8],
+        (DataType::Decimal128(_, _), 0) => &[16],
+        (DataType::Decimal256(_, _), 0) => &[32],
+        _ => &[],
+    }
+}
+
+/// Reverses the bytes of each value of `data`, consisting of repeated elements
+/// made up of values with the provided `widths`
+///
+/// Any trailing bytes not making up a complete element, such as padding, are
+/// copied unchanged
+pub(crate) fn byte_swap(data: &[u8], widths: &[usize]) -> Buffer {
+    let mut buffer = MutableBuffer::new(data.len());
+    buffer.extend_from_slice(data);
+
+    let stride: usize = widths.iter().sum();
+    if stride > 1 {
+        for element in buffer.as_slice_mut().chunks_exact_mut(stride) {
+            let mut start = 0;
+            for width in widths {
+                element[start..start + width].reverse();
+                start += width;
+            }
+        }
+    }
+    buffer.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_swap() {
+        let data = [1_u8, 2, 3, 4, 5, 6, 7, 8, 9];
+        assert_eq!(
+            byte_swap(&data, &[4]).as_slice(),
+            &[4, 3, 2, 1, 8, 7, 6, 5, 9]
+        );
+        assert_eq!(
+            byte_swap(&data, &[2, 1]).as_slice(),
+            &[2, 1, 3, 5, 4, 6, 8, 7, 9]
+        );
+        assert_eq!(byte_swap(&data, &[]).as_slice(), &data);
+
+        let values = [1_i32, -2, 300];
+        let big: Vec<u8> = values.iter().flat_map(|v| v.to_be_bytes()).collect();
+        let little: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(byte_swap(&big, &[4]).as_slice(), little.as_slice());
+    }
+}
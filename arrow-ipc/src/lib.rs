@@ -20,11 +20,18 @@
 // TODO: (vcq): Protobuf codegen is not generating Debug impls.
 #![allow(missing_debug_implementations)]
 
+#[cfg(feature = "tokio")]
+pub mod async_stream;
 pub mod convert;
+#[cfg(feature = "memmap2")]
+pub mod mmap;
 pub mod reader;
 pub mod writer;
 
 mod compression;
+mod endian;
+#[cfg(test)]
+mod test_util;
 
 #[allow(clippy::redundant_closure)]
 #[allow(clippy::needless_lifetimes)]
@@ -0,0 +1,522 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Zero-copy reading of Arrow IPC files
+//!
+//! [`MmapFileReader`] reads the record batches of an Arrow IPC file held in a
+//! single [`Buffer`], such as a memory-mapped file, creating arrays whose
+//! buffers are slices of it instead of copies.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+//! # use arrow_ipc::writer::FileWriter;
+//! use arrow_ipc::mmap::MmapFileReader;
+//!
+//! # let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+//! # let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
+//! let mut file = tempfile::tempfile().unwrap();
+//! # let mut writer = FileWriter::try_new(&mut file, &batch.schema()).unwrap();
+//! # writer.write(&batch).unwrap();
+//! # writer.finish().unwrap();
+//! # drop(writer);
+//! // SAFETY: the file is not modified while it is mapped
+//! let reader = unsafe { MmapFileReader::try_new(&file, None) }.unwrap();
+//! let batches: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+//! assert_eq!(batches, vec![batch]);
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::ops::Range;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_buffer::Buffer;
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use memmap2::Mmap;
+
+use crate::reader::{read_dictionary_impl, read_record_batch_impl, DecodeOptions};
+use crate::{ARROW_MAGIC, CONTINUATION_MARKER};
+
+/// Arrow File reader that creates arrays referencing the underlying [`Buffer`]
+/// directly, see the [module documentation](self) for an example.
+///
+/// As the arrays are not copied, the message metadata is verified, the offsets
+/// and lengths of all blocks and buffers are checked to lie within the file,
+/// and the resulting arrays are validated.
+pub struct MmapFileReader {
+    /// The contents of the file
+    data: Buffer,
+
+    /// The schema that is read from the file footer
+    schema: SchemaRef,
+
+    /// The blocks in the file
+    blocks: Vec<crate::Block>,
+
+    /// The index of the next block to read
+    current_block: usize,
+
+    /// Dictionaries by their id, read from the dictionary blocks
+    dictionaries_by_id: HashMap<i64, ArrayRef>,
+
+    /// Metadata version
+    metadata_version: crate::MetadataVersion,
+
+    /// Optional projection and projected schema
+    projection: Option<(Vec<usize>, Schema)>,
+
+    /// User-level custom metadata from the file footer
+    custom_metadata: HashMap<String, String>,
+
+    /// Custom metadata of the message of the most recently read record batch
+    batch_metadata: HashMap<String, String>,
+
+    /// Options controlling how record batches and dictionaries are decoded
+    decode_options: DecodeOptions,
+}
+
+impl MmapFileReader {
+    /// Try to create a new reader by memory-mapping `file`
+    ///
+    /// Returns errors if the file cannot be mapped, or does not meet the
+    /// Arrow Format header and footer requirements
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, including by other processes, while the
+    /// reader or any arrays read from it exist, see [`Mmap`]
+    pub unsafe fn try_new(
+        file: &File,
+        projection: Option<Vec<usize>>,
+    ) -> Result<Self, ArrowError> {
+        let mmap = Mmap::map(file)?;
+        let ptr = NonNull::new(mmap.as_ptr() as *mut u8).unwrap();
+        let data = Buffer::from_custom_allocation(ptr, mmap.len(), Arc::new(mmap));
+        Self::try_new_from_buffer(data, projection)
+    }
+
+    /// Try to create a new reader from `data` containing an entire Arrow IPC file
+    ///
+    /// Returns errors if the data does not meet the Arrow Format header and
+    /// footer requirements
+    pub fn try_new_from_buffer(
+        data: Buffer,
+        projection: Option<Vec<usize>>,
+    ) -> Result<Self, ArrowError> {
+        let len = data.len();
+        if len < 2 * ARROW_MAGIC.len() + 4 || data[..6] != ARROW_MAGIC {
+            return Err(ArrowError::IoError(
+                "Arrow file does not contain correct header".to_string(),
+            ));
+        }
+        if data[len - 6..] != ARROW_MAGIC {
+            return Err(ArrowError::IoError(
+                "Arrow file does not contain correct footer".to_string(),
+            ));
+        }
+
+        // read footer length
+        let footer_len = i32::from_le_bytes(data[len - 10..len - 6].try_into().unwrap());
+        let footer_start = usize::try_from(footer_len)
+            .ok()
+            .and_then(|footer_len| (len - 10).checked_sub(footer_len))
+            .filter(|start| *start >= ARROW_MAGIC.len())
+            .ok_or_else(|| {
+                ArrowError::IoError(format!("Invalid footer length {}", footer_len))
+            })?;
+
+        let footer =
+            crate::root_as_footer(&data[footer_start..len - 10]).map_err(|err| {
+                ArrowError::IoError(format!("Unable to get root as footer: {:?}", err))
+            })?;
+
+        let blocks = footer.recordBatches().ok_or_else(|| {
+            ArrowError::IoError(
+                "Unable to get record batches from IPC Footer".to_string(),
+            )
+        })?;
+
+        let ipc_schema = footer.schema().ok_or_else(|| {
+            ArrowError::IoError("Unable to get schema from IPC Footer".to_string())
+        })?;
+        let schema = crate::convert::fb_to_schema(ipc_schema);
+        let decode_options = DecodeOptions::new(ipc_schema);
+
+        let mut dictionaries_by_id = HashMap::new();
+        if let Some(dictionaries) = footer.dictionaries() {
+            for block in dictionaries {
+                let (message, body) = read_message(&data, block)?;
+                match message.header_type() {
+                    crate::MessageHeader::DictionaryBatch => {
+                        let batch = message.header_as_dictionary_batch().unwrap();
+                        read_dictionary_impl(
+                            &body,
+                            batch,
+                            &schema,
+                            &mut dictionaries_by_id,
+                            &message.version(),
+                            decode_options,
+                        )?;
+                    }
+                    t => {
+                        return Err(ArrowError::IoError(format!(
+                            "Expecting DictionaryBatch in dictionary blocks, found {:?}.",
+                            t
+                        )));
+                    }
+                }
+            }
+        }
+
+        let projection = match projection {
+            Some(projection_indices) => {
+                let schema = schema.project(&projection_indices)?;
+                Some((projection_indices, schema))
+            }
+            _ => None,
+        };
+
+        let blocks = blocks.iter().copied().collect();
+        let metadata_version = footer.version();
+        let custom_metadata = crate::convert::metadata_from_fb(footer.custom_metadata());
+        Ok(Self {
+            data,
+            schema: Arc::new(schema),
+            blocks,
+            current_block: 0,
+            dictionaries_by_id,
+            metadata_version,
+            projection,
+            custom_metadata,
+            batch_metadata: HashMap::new(),
+            decode_options,
+        })
+    }
+
+    /// Return the number of batches in the file
+    pub fn num_batches(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Return the schema of the file
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Return the user-level custom metadata of the file
+    pub fn custom_metadata(&self) -> &HashMap<String, String> {
+        &self.custom_metadata
+    }
+
+    /// Return the custom metadata of the message of the most recently read
+    /// record batch, empty if none has been read
+    pub fn batch_metadata(&self) -> &HashMap<String, String> {
+        &self.batch_metadata
+    }
+
+    /// Only decode the fields at the indices in `projection`, see
+    /// [projection](crate::reader#projection)
+    pub fn with_projection(mut self, projection: Vec<usize>) -> Result<Self, ArrowError> {
+        let schema = self.schema.project(&projection)?;
+        self.projection = Some((projection, schema));
+        Ok(self)
+    }
+
+    /// Skip validating the arrays read, see [validation](crate::reader#validation)
+    ///
+    /// # Safety
+    ///
+    /// If `skip_validation` is true, the IPC data must be known to contain valid arrays
+    pub unsafe fn with_skip_validation(mut self, skip_validation: bool) -> Self {
+        self.decode_options.skip_validation = skip_validation;
+        self
+    }
+
+    /// Read a specific record batch
+    ///
+    /// Sets the current block to the index, allowing random reads
+    pub fn set_index(&mut self, index: usize) -> Result<(), ArrowError> {
+        if index >= self.blocks.len() {
+            Err(ArrowError::IoError(format!(
+                "Cannot set batch to index {} from {} total batches",
+                index,
+                self.blocks.len()
+            )))
+        } else {
+            self.current_block = index;
+            Ok(())
+        }
+    }
+
+    /// Return the blocks of the record batches in the file, as listed in its footer
+    pub fn blocks(&self) -> &[crate::Block] {
+        &self.blocks
+    }
+
+    /// Read the record batch at `index`, without changing the position of the
+    /// reader when used as an [`Iterator`]
+    pub fn read_batch_at(&mut self, index: usize) -> Result<RecordBatch, ArrowError> {
+        if index >= self.blocks.len() {
+            return Err(ArrowError::IoError(format!(
+                "Cannot read batch at index {} from {} total batches",
+                index,
+                self.blocks.len()
+            )));
+        }
+        self.read_block(index)?.ok_or_else(|| {
+            ArrowError::IoError(format!(
+                "Block {} does not contain a record batch",
+                index
+            ))
+        })
+    }
+
+    /// Read the record batches at the indices in `range`, without changing the
+    /// position of the reader when used as an [`Iterator`]
+    pub fn read_range(
+        &mut self,
+        range: Range<usize>,
+    ) -> Result<Vec<RecordBatch>, ArrowError> {
+        if range.start > range.end || range.end > self.blocks.len() {
+            return Err(ArrowError::IoError(format!(
+                "Cannot read batches {:?} from {} total batches",
+                range,
+                self.blocks.len()
+            )));
+        }
+        range.map(|index| self.read_batch_at(index)).collect()
+    }
+
+    fn maybe_next(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        let index = self.current_block;
+        self.current_block += 1;
+        self.read_block(index)
+    }
+
+    /// Read the record batch in the block at `index`
+    fn read_block(&mut self, index: usize) -> Result<Option<RecordBatch>, ArrowError> {
+        let block = &self.blocks[index];
+
+        let (message, body) = read_message(&self.data, block)?;
+
+        // some old test data's footer metadata is not set, so we account for that
+        if self.metadata_version != crate::MetadataVersion::V1
+            && message.version() != self.metadata_version
+        {
+            return Err(ArrowError::IoError(
+                "Could not read IPC message as metadata versions mismatch".to_string(),
+            ));
+        }
+
+        match message.header_type() {
+            crate::MessageHeader::RecordBatch => {
+                let batch = message.header_as_record_batch().ok_or_else(|| {
+                    ArrowError::IoError(
+                        "Unable to read IPC message as record batch".to_string(),
+                    )
+                })?;
+                self.batch_metadata =
+                    crate::convert::metadata_from_fb(message.custom_metadata());
+                read_record_batch_impl(
+                    &body,
+                    batch,
+                    self.schema(),
+                    &self.dictionaries_by_id,
+                    self.projection.as_ref().map(|x| x.0.as_ref()),
+                    &message.version(),
+                    self.decode_options,
+                )
+                .map(Some)
+            }
+            crate::MessageHeader::NONE => Ok(None),
+            t => Err(ArrowError::IoError(format!(
+                "Reading types other than record batches not yet supported, unable to read {:?}",
+                t
+            ))),
+        }
+    }
+}
+
+/// Returns the message and body of `block` within `data`, checking that they
+/// lie within it
+fn read_message<'a>(
+    data: &'a Buffer,
+    block: &crate::Block,
+) -> Result<(crate::Message<'a>, Buffer), ArrowError> {
+    let out_of_bounds = || {
+        ArrowError::IoError(format!(
+            "Block at offset {} with metadata length {} and body length {} exceeds file of {} bytes",
+            block.offset(),
+            block.metaDataLength(),
+            block.bodyLength(),
+            data.len()
+        ))
+    };
+
+    let offset = usize::try_from(block.offset()).map_err(|_| out_of_bounds())?;
+    let meta_len =
+        usize::try_from(block.metaDataLength()).map_err(|_| out_of_bounds())?;
+    let body_len = usize::try_from(block.bodyLength()).map_err(|_| out_of_bounds())?;
+    let body_start = offset.checked_add(meta_len).ok_or_else(out_of_bounds)?;
+    let body_end = body_start.checked_add(body_len).ok_or_else(out_of_bounds)?;
+    if body_end > data.len() {
+        return Err(out_of_bounds());
+    }
+
+    // The metadata is prefixed by its length, which may itself be prefixed by
+    // the continuation marker
+    let mut meta = &data[offset..body_start];
+    if meta.starts_with(&CONTINUATION_MARKER) {
+        meta = &meta[4..];
+    }
+    let message_len = meta
+        .get(..4)
+        .map(|len| i32::from_le_bytes(len.try_into().unwrap()))
+        .and_then(|len| usize::try_from(len).ok())
+        .filter(|len| *len <= meta.len() - 4)
+        .ok_or_else(|| {
+            ArrowError::IoError(format!(
+                "Invalid message metadata in block at offset {}",
+                block.offset()
+            ))
+        })?;
+
+    let message = crate::root_as_message(&meta[4..4 + message_len]).map_err(|err| {
+        ArrowError::IoError(format!("Unable to get root as message: {:?}", err))
+    })?;
+    if message.bodyLength() > block.bodyLength() {
+        return Err(ArrowError::IoError(format!(
+            "Message body length {} exceeds block body length {}",
+            message.bodyLength(),
+            block.bodyLength()
+        )));
+    }
+
+    let body = data.slice_with_length(body_start, message.bodyLength() as usize);
+    Ok((message, body))
+}
+
+impl Iterator for MmapFileReader {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_block < self.blocks.len() {
+            self.maybe_next().transpose()
+        } else {
+            None
+        }
+    }
+}
+
+impl RecordBatchReader for MmapFileReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::FileReader;
+    use crate::test_util::sample_batches;
+    use crate::writer::FileWriter;
+    use arrow_array::Array;
+    use std::io::{Read, Seek};
+
+    fn write_file(batches: &[RecordBatch]) -> File {
+        let mut file = tempfile::tempfile().unwrap();
+        {
+            let mut writer =
+                FileWriter::try_new(&mut file, &batches[0].schema()).unwrap();
+            for batch in batches {
+                writer.write(batch).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        file.rewind().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_mmap_read() {
+        let batches = sample_batches(false);
+        let file = write_file(&batches);
+
+        let mut reader = unsafe { MmapFileReader::try_new(&file, None) }.unwrap();
+        assert_eq!(reader.num_batches(), 3);
+        assert_eq!(reader.schema(), batches[0].schema());
+
+        let read: Vec<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, batches);
+
+        // The arrays reference the mapped file
+        let range = reader.data.as_ptr() as usize
+            ..reader.data.as_ptr() as usize + reader.data.len();
+        let values = read[1].column(1).data().buffers()[1].as_ptr() as usize;
+        assert!(range.contains(&values));
+
+        reader.set_index(2).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap(), batches[2]);
+        assert!(reader.next().is_none());
+        reader.set_index(3).unwrap_err();
+        assert_eq!(reader.blocks().len(), 3);
+        assert_eq!(reader.read_batch_at(1).unwrap(), batches[1]);
+        assert_eq!(reader.read_range(0..2).unwrap(), &batches[..2]);
+        reader.read_range(1..4).unwrap_err();
+
+        let reader = unsafe { MmapFileReader::try_new(&file, Some(vec![2, 0])) }.unwrap();
+        let projected: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        let expected: Vec<_> = FileReader::try_new(&file, Some(vec![2, 0]))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(projected, expected);
+        assert_eq!(projected[0].num_columns(), 2);
+    }
+
+    #[test]
+    fn test_mmap_read_invalid() {
+        let batches = sample_batches(false);
+        let mut file = write_file(&batches);
+        let mut data = vec![];
+        file.read_to_end(&mut data).unwrap();
+
+        let err = MmapFileReader::try_new_from_buffer(Buffer::from(&data[..8]), None)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("correct header"), "{}", err);
+
+        let mut truncated = data.clone();
+        truncated.drain(100..200);
+        let err = MmapFileReader::try_new_from_buffer(Buffer::from(truncated), None)
+            .and_then(|reader| reader.collect::<Result<Vec<_>, _>>())
+            .unwrap_err();
+        assert!(matches!(err, ArrowError::IoError(_)), "{}", err);
+
+        // Corrupt the footer length
+        let mut corrupt = data.clone();
+        let len = corrupt.len();
+        corrupt[len - 10..len - 6].copy_from_slice(&i32::MAX.to_le_bytes());
+        let err = MmapFileReader::try_new_from_buffer(Buffer::from(corrupt), None)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("Invalid footer length"), "{}", err);
+    }
+}
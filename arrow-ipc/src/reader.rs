@@ -19,23 +19,63 @@
 //!
 //! The `FileReader` and `StreamReader` have similar interfaces,
 //! however the `FileReader` expects a reader that supports `Seek`ing
+//!
+//! The `StreamDecoder` decodes a stream from buffers pushed to it, rather
+//! than reading it from an `std::io::Read`
+//!
+//! # Projection
+//!
+//! The readers of this crate may be configured with a projection, the indices of
+//! the fields of the schema to decode, in which case the record batches returned
+//! contain only these columns in the order given.
+//!
+//! # Validation
+//!
+//! The arrays read are by default fully validated. Validation checks that offsets
+//! and dictionary keys are in bounds and that strings are valid UTF-8, so that
+//! untrusted input results in an error rather than undefined behavior.
+//!
+//! Readers may skip this validation, avoiding its cost for trusted data. This is
+//! `unsafe`, as the IPC data must then be known to contain valid arrays.
+//! Dictionaries read when a file reader is created are always validated.
 
 use arrow_buffer::i256;
 use std::collections::HashMap;
 use std::fmt;
 use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::ops::Range;
 use std::sync::Arc;
 
 use arrow_array::*;
 use arrow_buffer::{Buffer, MutableBuffer};
 use arrow_cast::cast;
-use arrow_data::ArrayData;
+use arrow_data::transform::MutableArrayData;
+use arrow_data::{ArrayData, ArrayDataBuilder};
 use arrow_schema::*;
 
 use crate::compression::CompressionCodec;
 use crate::CONTINUATION_MARKER;
 use DataType::*;
 
+/// Options controlling how the arrays of record batches and dictionaries are decoded
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DecodeOptions {
+    /// Byte swap the values of buffers written with non-native endianness
+    pub(crate) byte_swap: bool,
+    /// Skip validating the layout and values of the arrays decoded
+    pub(crate) skip_validation: bool,
+}
+
+impl DecodeOptions {
+    /// Create the [`DecodeOptions`] for data written with `schema`
+    pub(crate) fn new(schema: crate::Schema) -> Self {
+        Self {
+            byte_swap: schema.endianness() != crate::endian::native(),
+            skip_validation: false,
+        }
+    }
+}
+
 /// Read a buffer based on offset and length
 /// From <https://github.com/apache/arrow/blob/6a936c4ff5007045e86f65f1a6b6c3c955ad5103/format/Message.fbs#L58>
 /// Each constituent buffer is first compressed with the indicated
@@ -46,12 +86,32 @@ use DataType::*;
 /// follows is not compressed, which can be useful for cases where
 /// compression does not yield appreciable savings.
 fn read_buffer(
-    buf: &crate::Buffer,
+    buffers: flatbuffers::Vector<'_, crate::Buffer>,
+    index: usize,
     a_data: &Buffer,
     compression_codec: &Option<CompressionCodec>,
 ) -> Result<Buffer, ArrowError> {
-    let start_offset = buf.offset() as usize;
-    let buf_data = a_data.slice_with_length(start_offset, buf.length() as usize);
+    if index >= buffers.len() {
+        return Err(ArrowError::IoError(format!(
+            "Buffer {} not found in IPC RecordBatch with {} buffers",
+            index,
+            buffers.len()
+        )));
+    }
+    let buf = buffers.get(index);
+    let end = buf.offset().checked_add(buf.length());
+    if buf.offset() < 0
+        || buf.length() < 0
+        || end.map_or(true, |end| end as usize > a_data.len())
+    {
+        return Err(ArrowError::IoError(format!(
+            "Buffer at offset {} with length {} exceeds message body of {} bytes",
+            buf.offset(),
+            buf.length(),
+            a_data.len()
+        )));
+    }
+    let buf_data = a_data.slice_with_length(buf.offset() as usize, buf.length() as usize);
     // corner case: empty buffer
     match (buf_data.is_empty(), compression_codec) {
         (true, _) | (_, None) => Ok(buf_data),
@@ -59,6 +119,66 @@ fn read_buffer(
     }
 }
 
+/// Returns a copy of the first `len` bytes of `buffer`, a buffer of `field`
+fn slice_buffer(
+    buffer: &Buffer,
+    len: usize,
+    field: &Field,
+) -> Result<Buffer, ArrowError> {
+    match buffer.get(..len) {
+        Some(slice) => Ok(slice.into()),
+        None => Err(ArrowError::IoError(format!(
+            "Buffer of field {} has {} bytes, expected at least {}",
+            field.name(),
+            buffer.len(),
+            len
+        ))),
+    }
+}
+
+/// Returns the `index`th field node of an IPC RecordBatch
+fn get_node(
+    nodes: flatbuffers::Vector<'_, crate::FieldNode>,
+    index: usize,
+) -> Result<&crate::FieldNode, ArrowError> {
+    if index >= nodes.len() {
+        return Err(ArrowError::IoError(format!(
+            "Field node {} not found in IPC RecordBatch with {} field nodes",
+            index,
+            nodes.len()
+        )));
+    }
+    Ok(nodes.get(index))
+}
+
+/// Builds the [`ArrayData`] of `builder`, validating it unless
+/// [`DecodeOptions::skip_validation`] is set
+fn build_array(
+    builder: ArrayDataBuilder,
+    options: DecodeOptions,
+) -> Result<ArrayData, ArrowError> {
+    match options.skip_validation {
+        // Safety: the caller of `with_skip_validation` asserted the data is valid
+        true => Ok(unsafe { builder.build_unchecked() }),
+        false => builder.build(),
+    }
+}
+
+/// Byte swaps the values of `buffer`, the `index`th buffer of an array of `data_type`
+/// excluding the validity buffer, if `byte_swap` is true
+fn swap_buffer(
+    buffer: Buffer,
+    data_type: &DataType,
+    index: usize,
+    byte_swap: bool,
+) -> Buffer {
+    let widths = crate::endian::buffer_widths(data_type, index);
+    match byte_swap && !widths.is_empty() {
+        true => crate::endian::byte_swap(&buffer, widths),
+        false => buffer,
+    }
+}
+
 /// Coordinates reading arrays based on data types.
 ///
 /// Notes:
@@ -79,18 +199,25 @@ fn create_array(
     mut buffer_index: usize,
     compression_codec: &Option<CompressionCodec>,
     metadata: &crate::MetadataVersion,
+    options: DecodeOptions,
 ) -> Result<(ArrayRef, usize, usize), ArrowError> {
     let data_type = field.data_type();
     let array = match data_type {
         Utf8 | Binary | LargeBinary | LargeUtf8 => {
             let array = create_primitive_array(
-                nodes.get(node_index),
+                get_node(nodes, node_index)?,
                 data_type,
                 &[
-                    read_buffer(buffers.get(buffer_index), data, compression_codec)?,
-                    read_buffer(buffers.get(buffer_index + 1), data, compression_codec)?,
-                    read_buffer(buffers.get(buffer_index + 2), data, compression_codec)?,
+                    read_buffer(buffers, buffer_index, data, compression_codec)?,
+                    swap_buffer(
+                        read_buffer(buffers, buffer_index + 1, data, compression_codec)?,
+                        data_type,
+                        0,
+                        options.byte_swap,
+                    ),
+                    read_buffer(buffers, buffer_index + 2, data, compression_codec)?,
                 ],
+                options,
             )?;
             node_index += 1;
             buffer_index += 3;
@@ -98,22 +225,28 @@ fn create_array(
         }
         FixedSizeBinary(_) => {
             let array = create_primitive_array(
-                nodes.get(node_index),
+                get_node(nodes, node_index)?,
                 data_type,
                 &[
-                    read_buffer(buffers.get(buffer_index), data, compression_codec)?,
-                    read_buffer(buffers.get(buffer_index + 1), data, compression_codec)?,
+                    read_buffer(buffers, buffer_index, data, compression_codec)?,
+                    read_buffer(buffers, buffer_index + 1, data, compression_codec)?,
                 ],
+                options,
             )?;
             node_index += 1;
             buffer_index += 2;
             array
         }
         List(ref list_field) | LargeList(ref list_field) | Map(ref list_field, _) => {
-            let list_node = nodes.get(node_index);
+            let list_node = get_node(nodes, node_index)?;
             let list_buffers = [
-                read_buffer(buffers.get(buffer_index), data, compression_codec)?,
-                read_buffer(buffers.get(buffer_index + 1), data, compression_codec)?,
+                read_buffer(buffers, buffer_index, data, compression_codec)?,
+                swap_buffer(
+                    read_buffer(buffers, buffer_index + 1, data, compression_codec)?,
+                    data_type,
+                    0,
+                    options.byte_swap,
+                ),
             ];
             node_index += 1;
             buffer_index += 2;
@@ -127,19 +260,17 @@ fn create_array(
                 buffer_index,
                 compression_codec,
                 metadata,
+                options,
             )?;
             node_index = triple.1;
             buffer_index = triple.2;
 
-            create_list_array(list_node, data_type, &list_buffers, triple.0)?
+            create_list_array(list_node, data_type, &list_buffers, triple.0, options)?
         }
         FixedSizeList(ref list_field, _) => {
-            let list_node = nodes.get(node_index);
-            let list_buffers = [read_buffer(
-                buffers.get(buffer_index),
-                data,
-                compression_codec,
-            )?];
+            let list_node = get_node(nodes, node_index)?;
+            let list_buffers =
+                [read_buffer(buffers, buffer_index, data, compression_codec)?];
             node_index += 1;
             buffer_index += 1;
             let triple = create_array(
@@ -152,16 +283,17 @@ fn create_array(
                 buffer_index,
                 compression_codec,
                 metadata,
+                options,
             )?;
             node_index = triple.1;
             buffer_index = triple.2;
 
-            create_list_array(list_node, data_type, &list_buffers, triple.0)?
+            create_list_array(list_node, data_type, &list_buffers, triple.0, options)?
         }
         Struct(struct_fields) => {
-            let struct_node = nodes.get(node_index);
+            let struct_node = get_node(nodes, node_index)?;
             let null_buffer =
-                read_buffer(buffers.get(buffer_index), data, compression_codec)?;
+                read_buffer(buffers, buffer_index, data, compression_codec)?;
             node_index += 1;
             buffer_index += 1;
 
@@ -180,26 +312,30 @@ fn create_array(
                     buffer_index,
                     compression_codec,
                     metadata,
+                    options,
                 )?;
                 node_index = triple.1;
                 buffer_index = triple.2;
-                struct_arrays.push((struct_field.clone(), triple.0));
+                struct_arrays.push(triple.0);
             }
-            let null_count = struct_node.null_count() as usize;
-            let struct_array = if null_count > 0 {
-                // create struct array from fields, arrays and null data
-                StructArray::from((struct_arrays, null_buffer))
-            } else {
-                StructArray::from(struct_arrays)
-            };
-            Arc::new(struct_array)
+            let null_buffer = (struct_node.null_count() > 0).then_some(null_buffer);
+            let builder = ArrayData::builder(data_type.clone())
+                .len(struct_node.length() as usize)
+                .child_data(struct_arrays.into_iter().map(|a| a.into_data()).collect())
+                .null_bit_buffer(null_buffer);
+            make_array(build_array(builder, options)?)
         }
         // Create dictionary array from RecordBatch
         Dictionary(_, _) => {
-            let index_node = nodes.get(node_index);
+            let index_node = get_node(nodes, node_index)?;
             let index_buffers = [
-                read_buffer(buffers.get(buffer_index), data, compression_codec)?,
-                read_buffer(buffers.get(buffer_index + 1), data, compression_codec)?,
+                read_buffer(buffers, buffer_index, data, compression_codec)?,
+                swap_buffer(
+                    read_buffer(buffers, buffer_index + 1, data, compression_codec)?,
+                    data_type,
+                    0,
+                    options.byte_swap,
+                ),
             ];
 
             let dict_id = field.dict_id().ok_or_else(|| {
@@ -220,10 +356,11 @@ fn create_array(
                 data_type,
                 &index_buffers,
                 value_array.clone(),
+                options,
             )?
         }
         Union(fields, field_type_ids, mode) => {
-            let union_node = nodes.get(node_index);
+            let union_node = get_node(nodes, node_index)?;
             node_index += 1;
 
             let len = union_node.length() as usize;
@@ -231,22 +368,22 @@ fn create_array(
             // In V4, union types has validity bitmap
             // In V5 and later, union types have no validity bitmap
             if metadata < &crate::MetadataVersion::V5 {
-                read_buffer(buffers.get(buffer_index), data, compression_codec)?;
+                read_buffer(buffers, buffer_index, data, compression_codec)?;
                 buffer_index += 1;
             }
 
-            let type_ids: Buffer =
-                read_buffer(buffers.get(buffer_index), data, compression_codec)?[..len]
-                    .into();
+            let type_ids = read_buffer(buffers, buffer_index, data, compression_codec)?;
+            let type_ids = slice_buffer(&type_ids, len, field)?;
 
             buffer_index += 1;
 
             let value_offsets = match mode {
                 UnionMode::Dense => {
                     let buffer =
-                        read_buffer(buffers.get(buffer_index), data, compression_codec)?;
+                        read_buffer(buffers, buffer_index, data, compression_codec)?;
                     buffer_index += 1;
-                    Some(buffer[..len * 4].into())
+                    let buffer = slice_buffer(&buffer, len * 4, field)?;
+                    Some(swap_buffer(buffer, data_type, 1, options.byte_swap))
                 }
                 UnionMode::Sparse => None,
             };
@@ -264,6 +401,7 @@ fn create_array(
                     buffer_index,
                     compression_codec,
                     metadata,
+                    options,
                 )?;
 
                 node_index = triple.1;
@@ -272,12 +410,27 @@ fn create_array(
                 children.push((field.clone(), triple.0));
             }
 
-            let array =
-                UnionArray::try_new(field_type_ids, type_ids, value_offsets, children)?;
+            let array = match options.skip_validation {
+                // Safety: the caller of `with_skip_validation` asserted the data is valid
+                true => unsafe {
+                    UnionArray::new_unchecked(
+                        field_type_ids,
+                        type_ids,
+                        value_offsets,
+                        children,
+                    )
+                },
+                false => UnionArray::try_new(
+                    field_type_ids,
+                    type_ids,
+                    value_offsets,
+                    children,
+                )?,
+            };
             Arc::new(array)
         }
         Null => {
-            let node = nodes.get(node_index);
+            let node = get_node(nodes, node_index)?;
             let length = node.length();
             let null_count = node.null_count();
 
@@ -299,12 +452,18 @@ fn create_array(
         }
         _ => {
             let array = create_primitive_array(
-                nodes.get(node_index),
+                get_node(nodes, node_index)?,
                 data_type,
                 &[
-                    read_buffer(buffers.get(buffer_index), data, compression_codec)?,
-                    read_buffer(buffers.get(buffer_index + 1), data, compression_codec)?,
+                    read_buffer(buffers, buffer_index, data, compression_codec)?,
+                    swap_buffer(
+                        read_buffer(buffers, buffer_index + 1, data, compression_codec)?,
+                        data_type,
+                        0,
+                        options.byte_swap,
+                    ),
                 ],
+                options,
             )?;
             node_index += 1;
             buffer_index += 2;
@@ -397,17 +556,17 @@ fn create_primitive_array(
     field_node: &crate::FieldNode,
     data_type: &DataType,
     buffers: &[Buffer],
+    options: DecodeOptions,
 ) -> Result<ArrayRef, ArrowError> {
     let length = field_node.length() as usize;
     let null_buffer = (field_node.null_count() > 0).then_some(buffers[0].clone());
-    let array_data = match data_type {
+    let builder = match data_type {
         Utf8 | Binary | LargeBinary | LargeUtf8 => {
             // read 3 buffers: null buffer (optional), offsets buffer and data buffer
             ArrayData::builder(data_type.clone())
                 .len(length)
                 .buffers(buffers[1..3].to_vec())
                 .null_bit_buffer(null_buffer)
-                .build()?
         }
         FixedSizeBinary(_) => {
             // read 2 buffers: null buffer (optional) and data buffer
@@ -415,7 +574,6 @@ fn create_primitive_array(
                 .len(length)
                 .add_buffer(buffers[1].clone())
                 .null_bit_buffer(null_buffer)
-                .build()?
         }
         Int8
         | Int16
@@ -435,13 +593,12 @@ fn create_primitive_array(
                     .build()?;
                 let values = Arc::new(Int64Array::from(data)) as ArrayRef;
                 let casted = cast(&values, data_type)?;
-                casted.into_data()
+                casted.into_data().into_builder()
             } else {
                 ArrayData::builder(data_type.clone())
                     .len(length)
                     .add_buffer(buffers[1].clone())
                     .null_bit_buffer(null_buffer)
-                    .build()?
             }
         }
         Float32 => {
@@ -454,13 +611,12 @@ fn create_primitive_array(
                     .build()?;
                 let values = Arc::new(Float64Array::from(data)) as ArrayRef;
                 let casted = cast(&values, data_type)?;
-                casted.into_data()
+                casted.into_data().into_builder()
             } else {
                 ArrayData::builder(data_type.clone())
                     .len(length)
                     .add_buffer(buffers[1].clone())
                     .null_bit_buffer(null_buffer)
-                    .build()?
             }
         }
         Boolean
@@ -474,8 +630,7 @@ fn create_primitive_array(
         | Interval(IntervalUnit::DayTime) => ArrayData::builder(data_type.clone())
             .len(length)
             .add_buffer(buffers[1].clone())
-            .null_bit_buffer(null_buffer)
-            .build()?,
+            .null_bit_buffer(null_buffer),
         Interval(IntervalUnit::MonthDayNano) | Decimal128(_, _) => {
             let buffer = get_aligned_buffer::<i128>(&buffers[1], length);
 
@@ -484,7 +639,6 @@ fn create_primitive_array(
                 .len(length)
                 .add_buffer(buffer)
                 .null_bit_buffer(null_buffer)
-                .build()?
         }
         Decimal256(_, _) => {
             let buffer = get_aligned_buffer::<i256>(&buffers[1], length);
@@ -494,12 +648,11 @@ fn create_primitive_array(
                 .len(length)
                 .add_buffer(buffer)
                 .null_bit_buffer(null_buffer)
-                .build()?
         }
         t => unreachable!("Data type {:?} either unsupported or not primitive", t),
     };
 
-    Ok(make_array(array_data))
+    Ok(make_array(build_array(builder, options)?))
 }
 
 /// Checks if given `Buffer` is properly aligned with `T`.
@@ -527,6 +680,7 @@ fn create_list_array(
     data_type: &DataType,
     buffers: &[Buffer],
     child_array: ArrayRef,
+    options: DecodeOptions,
 ) -> Result<ArrayRef, ArrowError> {
     let null_buffer = (field_node.null_count() > 0).then_some(buffers[0].clone());
     let length = field_node.length() as usize;
@@ -545,7 +699,7 @@ fn create_list_array(
 
         _ => unreachable!("Cannot create list or map array from {:?}", data_type),
     };
-    Ok(make_array(builder.build()?))
+    Ok(make_array(build_array(builder, options)?))
 }
 
 /// Reads the correct number of buffers based on list type and null_count, and creates a
@@ -555,6 +709,7 @@ fn create_dictionary_array(
     data_type: &DataType,
     buffers: &[Buffer],
     value_array: ArrayRef,
+    options: DecodeOptions,
 ) -> Result<ArrayRef, ArrowError> {
     if let Dictionary(_, _) = *data_type {
         let null_buffer = (field_node.null_count() > 0).then_some(buffers[0].clone());
@@ -564,7 +719,7 @@ fn create_dictionary_array(
             .add_child_data(value_array.into_data())
             .null_bit_buffer(null_buffer);
 
-        Ok(make_array(builder.build()?))
+        Ok(make_array(build_array(builder, options)?))
     } else {
         unreachable!("Cannot create dictionary array from {:?}", data_type)
     }
@@ -578,6 +733,28 @@ pub fn read_record_batch(
     dictionaries_by_id: &HashMap<i64, ArrayRef>,
     projection: Option<&[usize]>,
     metadata: &crate::MetadataVersion,
+) -> Result<RecordBatch, ArrowError> {
+    read_record_batch_impl(
+        buf,
+        batch,
+        schema,
+        dictionaries_by_id,
+        projection,
+        metadata,
+        DecodeOptions::default(),
+    )
+}
+
+/// Creates a record batch as [`read_record_batch`], decoding its arrays with
+/// `decode_options`
+pub(crate) fn read_record_batch_impl(
+    buf: &Buffer,
+    batch: crate::RecordBatch,
+    schema: SchemaRef,
+    dictionaries_by_id: &HashMap<i64, ArrayRef>,
+    projection: Option<&[usize]>,
+    metadata: &crate::MetadataVersion,
+    decode_options: DecodeOptions,
 ) -> Result<RecordBatch, ArrowError> {
     let buffers = batch.buffers().ok_or_else(|| {
         ArrowError::IoError("Unable to get buffers from IPC RecordBatch".to_string())
@@ -586,6 +763,14 @@ pub fn read_record_batch(
         ArrowError::IoError("Unable to get field nodes from IPC RecordBatch".to_string())
     })?;
     let batch_compression = batch.compression();
+    if let Some(compression) = batch_compression {
+        if compression.method() != crate::BodyCompressionMethod::BUFFER {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "body compression method {:?} not supported",
+                compression.method()
+            )));
+        }
+    }
     let compression_codec: Option<CompressionCodec> = batch_compression
         .map(|batch_compression| batch_compression.codec().try_into())
         .transpose()?;
@@ -598,7 +783,10 @@ pub fn read_record_batch(
     let options = RecordBatchOptions::new().with_row_count(Some(batch.length() as usize));
 
     if let Some(projection) = projection {
-        // project fields
+        let projected_schema = Arc::new(schema.project(projection)?);
+
+        // project fields, which are decoded in the order of the schema
+        let mut columns = vec![None; schema.fields().len()];
         for (idx, field) in schema.fields().iter().enumerate() {
             // Create array for projected field
             if projection.contains(&idx) {
@@ -612,10 +800,11 @@ pub fn read_record_batch(
                     buffer_index,
                     &compression_codec,
                     metadata,
+                    decode_options,
                 )?;
                 node_index = triple.1;
                 buffer_index = triple.2;
-                arrays.push(triple.0);
+                columns[idx] = Some(triple.0);
             } else {
                 // Skip field.
                 // This must be called to advance `node_index` and `buffer_index`.
@@ -625,11 +814,9 @@ pub fn read_record_batch(
             }
         }
 
-        RecordBatch::try_new_with_options(
-            Arc::new(schema.project(projection)?),
-            arrays,
-            &options,
-        )
+        // return the columns in the order of the projection
+        arrays.extend(projection.iter().map(|idx| columns[*idx].clone().unwrap()));
+        RecordBatch::try_new_with_options(projected_schema, arrays, &options)
     } else {
         // keep track of index as lists require more than one node
         for field in schema.fields() {
@@ -643,6 +830,7 @@ pub fn read_record_batch(
                 buffer_index,
                 &compression_codec,
                 metadata,
+                decode_options,
             )?;
             node_index = triple.1;
             buffer_index = triple.2;
@@ -653,7 +841,10 @@ pub fn read_record_batch(
 }
 
 /// Read the dictionary from the buffer and provided metadata,
-/// updating the `dictionaries_by_id` with the resulting dictionary
+/// updating the `dictionaries_by_id` with the resulting dictionary.
+///
+/// The values of a delta dictionary batch are appended to those of the
+/// dictionary previously read with the same id.
 pub fn read_dictionary(
     buf: &Buffer,
     batch: crate::DictionaryBatch,
@@ -661,12 +852,26 @@ pub fn read_dictionary(
     dictionaries_by_id: &mut HashMap<i64, ArrayRef>,
     metadata: &crate::MetadataVersion,
 ) -> Result<(), ArrowError> {
-    if batch.isDelta() {
-        return Err(ArrowError::IoError(
-            "delta dictionary batches not supported".to_string(),
-        ));
-    }
+    read_dictionary_impl(
+        buf,
+        batch,
+        schema,
+        dictionaries_by_id,
+        metadata,
+        DecodeOptions::default(),
+    )
+}
 
+/// Reads a dictionary as [`read_dictionary`], decoding its values with
+/// `decode_options`
+pub(crate) fn read_dictionary_impl(
+    buf: &Buffer,
+    batch: crate::DictionaryBatch,
+    schema: &Schema,
+    dictionaries_by_id: &mut HashMap<i64, ArrayRef>,
+    metadata: &crate::MetadataVersion,
+    decode_options: DecodeOptions,
+) -> Result<(), ArrowError> {
     let id = batch.id();
     let fields_using_this_dictionary = schema.fields_with_dict_id(id);
     let first_field = fields_using_this_dictionary.first().ok_or_else(|| {
@@ -684,13 +889,14 @@ pub fn read_dictionary(
                 metadata: HashMap::new(),
             };
             // Read a single column
-            let record_batch = read_record_batch(
+            let record_batch = read_record_batch_impl(
                 buf,
                 batch.data().unwrap(),
                 Arc::new(schema),
                 dictionaries_by_id,
                 None,
                 metadata,
+                decode_options,
             )?;
             Some(record_batch.column(0).clone())
         }
@@ -700,10 +906,29 @@ pub fn read_dictionary(
         ArrowError::InvalidArgumentError("dictionary id not found in schema".to_string())
     })?;
 
+    let dictionary_values = match batch.isDelta() {
+        true => {
+            let existing = dictionaries_by_id.get(&id).ok_or_else(|| {
+                ArrowError::IoError(format!(
+                    "delta dictionary batch for id {} has no existing dictionary",
+                    id
+                ))
+            })?;
+            let (existing, delta) = (existing.data(), dictionary_values.data());
+            let capacity = existing.len() + delta.len();
+            let mut values =
+                MutableArrayData::new(vec![existing, delta], false, capacity);
+            values.extend(0, 0, existing.len());
+            values.extend(1, 0, delta.len());
+            make_array(values.freeze())
+        }
+        false => dictionary_values,
+    };
+
     // We don't currently record the isOrdered field. This could be general
     // attributes of arrays.
     // Add (possibly multiple) array refs to the dictionaries array.
-    dictionaries_by_id.insert(id, dictionary_values.clone());
+    dictionaries_by_id.insert(id, dictionary_values);
 
     Ok(())
 }
@@ -721,6 +946,9 @@ pub struct FileReader<R: Read + Seek> {
     /// A block indicates the regions in the file to read to get data
     blocks: Vec<crate::Block>,
 
+    /// The blocks of the dictionary batches in the file
+    dictionary_blocks: Vec<crate::Block>,
+
     /// A counter to keep track of the current block that should be read
     current_block: usize,
 
@@ -737,6 +965,15 @@ pub struct FileReader<R: Read + Seek> {
 
     /// Optional projection and projected_schema
     projection: Option<(Vec<usize>, Schema)>,
+
+    /// User-level custom metadata from the file footer
+    custom_metadata: HashMap<String, String>,
+
+    /// Custom metadata of the message of the most recently read record batch
+    batch_metadata: HashMap<String, String>,
+
+    /// Options controlling how record batches and dictionaries are decoded
+    decode_options: DecodeOptions,
 }
 
 impl<R: Read + Seek> fmt::Debug for FileReader<R> {
@@ -750,6 +987,9 @@ impl<R: Read + Seek> fmt::Debug for FileReader<R> {
             .field("dictionaries_by_id", &self.dictionaries_by_id)
             .field("metadata_version", &self.metadata_version)
             .field("projection", &self.projection)
+            .field("custom_metadata", &self.custom_metadata)
+            .field("batch_metadata", &self.batch_metadata)
+            .field("decode_options", &self.decode_options)
             .finish()
     }
 }
@@ -804,6 +1044,7 @@ impl<R: Read + Seek> FileReader<R> {
 
         let ipc_schema = footer.schema().unwrap();
         let schema = crate::convert::fb_to_schema(ipc_schema);
+        let decode_options = DecodeOptions::new(ipc_schema);
 
         // Create an array of optional dictionary value arrays, one per field.
         let mut dictionaries_by_id = HashMap::new();
@@ -840,12 +1081,13 @@ impl<R: Read + Seek> FileReader<R> {
                         ))?;
                         reader.read_exact(&mut buf)?;
 
-                        read_dictionary(
+                        read_dictionary_impl(
                             &buf.into(),
                             batch,
                             &schema,
                             &mut dictionaries_by_id,
                             &message.version(),
+                            decode_options,
                         )?;
                     }
                     t => {
@@ -869,11 +1111,18 @@ impl<R: Read + Seek> FileReader<R> {
             reader,
             schema: Arc::new(schema),
             blocks: blocks.iter().copied().collect(),
+            dictionary_blocks: footer
+                .dictionaries()
+                .map(|blocks| blocks.iter().copied().collect())
+                .unwrap_or_default(),
             current_block: 0,
             total_blocks,
             dictionaries_by_id,
             metadata_version: footer.version(),
             projection,
+            custom_metadata: crate::convert::metadata_from_fb(footer.custom_metadata()),
+            batch_metadata: HashMap::new(),
+            decode_options,
         })
     }
 
@@ -887,6 +1136,35 @@ impl<R: Read + Seek> FileReader<R> {
         self.schema.clone()
     }
 
+    /// Return the user-level custom metadata of the file
+    pub fn custom_metadata(&self) -> &HashMap<String, String> {
+        &self.custom_metadata
+    }
+
+    /// Return the custom metadata of the message of the most recently read
+    /// record batch, empty if none has been read
+    pub fn batch_metadata(&self) -> &HashMap<String, String> {
+        &self.batch_metadata
+    }
+
+    /// Only decode the fields at the indices in `projection`, see
+    /// [projection](crate::reader#projection)
+    pub fn with_projection(mut self, projection: Vec<usize>) -> Result<Self, ArrowError> {
+        let schema = self.schema.project(&projection)?;
+        self.projection = Some((projection, schema));
+        Ok(self)
+    }
+
+    /// Skip validating the arrays read, see [validation](crate::reader#validation)
+    ///
+    /// # Safety
+    ///
+    /// If `skip_validation` is true, the IPC data must be known to contain valid arrays
+    pub unsafe fn with_skip_validation(mut self, skip_validation: bool) -> Self {
+        self.decode_options.skip_validation = skip_validation;
+        self
+    }
+
     /// Read a specific record batch
     ///
     /// Sets the current block to the index, allowing random reads
@@ -902,9 +1180,72 @@ impl<R: Read + Seek> FileReader<R> {
         }
     }
 
+    /// Return the blocks of the record batches in the file, as listed in its footer
+    pub fn blocks(&self) -> &[crate::Block] {
+        &self.blocks
+    }
+
+    /// Return the blocks of the dictionary batches in the file, as listed in its footer
+    pub fn dictionary_blocks(&self) -> &[crate::Block] {
+        &self.dictionary_blocks
+    }
+
+    /// Return the dictionaries of the file, keyed by dictionary id
+    pub(crate) fn dictionaries_by_id(&self) -> &HashMap<i64, ArrayRef> {
+        &self.dictionaries_by_id
+    }
+
+    /// Return the metadata version of the file, as listed in its footer
+    pub(crate) fn metadata_version(&self) -> crate::MetadataVersion {
+        self.metadata_version
+    }
+
+    /// Return the options the file is decoded with
+    pub(crate) fn decode_options(&self) -> DecodeOptions {
+        self.decode_options
+    }
+
+    /// Read the record batch at `index`, without changing the position of the
+    /// reader when used as an [`Iterator`]
+    pub fn read_batch_at(&mut self, index: usize) -> Result<RecordBatch, ArrowError> {
+        if index >= self.total_blocks {
+            return Err(ArrowError::IoError(format!(
+                "Cannot read batch at index {} from {} total batches",
+                index, self.total_blocks
+            )));
+        }
+        self.read_block(index)?.ok_or_else(|| {
+            ArrowError::IoError(format!(
+                "Block {} does not contain a record batch",
+                index
+            ))
+        })
+    }
+
+    /// Read the record batches at the indices in `range`, without changing the
+    /// position of the reader when used as an [`Iterator`]
+    pub fn read_range(
+        &mut self,
+        range: Range<usize>,
+    ) -> Result<Vec<RecordBatch>, ArrowError> {
+        if range.start > range.end || range.end > self.total_blocks {
+            return Err(ArrowError::IoError(format!(
+                "Cannot read batches {:?} from {} total batches",
+                range, self.total_blocks
+            )));
+        }
+        range.map(|index| self.read_batch_at(index)).collect()
+    }
+
     fn maybe_next(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
-        let block = self.blocks[self.current_block];
+        let index = self.current_block;
         self.current_block += 1;
+        self.read_block(index)
+    }
+
+    /// Read the record batch in the block at `index`
+    fn read_block(&mut self, index: usize) -> Result<Option<RecordBatch>, ArrowError> {
+        let block = self.blocks[index];
 
         // read length
         self.reader.seek(SeekFrom::Start(block.offset() as u64))?;
@@ -941,6 +1282,8 @@ impl<R: Read + Seek> FileReader<R> {
                         "Unable to read IPC message as record batch".to_string(),
                     )
                 })?;
+                self.batch_metadata =
+                    crate::convert::metadata_from_fb(message.custom_metadata());
                 // read the block that makes up the record batch into a buffer
                 let mut buf = MutableBuffer::from_len_zeroed(message.bodyLength() as usize);
                 self.reader.seek(SeekFrom::Start(
@@ -948,14 +1291,14 @@ impl<R: Read + Seek> FileReader<R> {
                 ))?;
                 self.reader.read_exact(&mut buf)?;
 
-                read_record_batch(
+                read_record_batch_impl(
                     &buf.into(),
                     batch,
                     self.schema(),
                     &self.dictionaries_by_id,
                     self.projection.as_ref().map(|x| x.0.as_ref()),
-                    &message.version()
-
+                    &message.version(),
+                    self.decode_options,
                 ).map(Some)
             }
             crate::MessageHeader::NONE => {
@@ -987,36 +1330,237 @@ impl<R: Read + Seek> RecordBatchReader for FileReader<R> {
     }
 }
 
-/// Arrow Stream reader
-pub struct StreamReader<R: Read> {
-    /// Buffered stream reader
-    reader: BufReader<R>,
+/// The maximum number of bytes allocated ahead of reading a message
+///
+/// The lengths of messages are read from the stream, and so are not trusted to size
+/// allocations up front. Instead buffers are grown as the bytes are read
+pub(crate) const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Reads exactly `len` bytes from `reader`, growing the buffer as data is read
+fn read_exact_chunked<R: Read>(
+    reader: &mut R,
+    len: usize,
+) -> Result<MutableBuffer, ArrowError> {
+    let mut buf = MutableBuffer::new(len.min(READ_CHUNK_SIZE));
+    while buf.len() < len {
+        let start = buf.len();
+        buf.resize(len.min(start + READ_CHUNK_SIZE), 0);
+        reader.read_exact(&mut buf[start..])?;
+    }
+    Ok(buf)
+}
+
+/// Parses the metadata length read from a stream, returning `None` if it marks
+/// the end of the stream
+pub(crate) fn parse_metadata_length(
+    meta_size: [u8; 4],
+) -> Result<Option<usize>, ArrowError> {
+    match i32::from_le_bytes(meta_size) {
+        0 => Ok(None),
+        len => usize::try_from(len).map(Some).map_err(|_| {
+            ArrowError::IoError(format!("Invalid message metadata length {}", len))
+        }),
+    }
+}
+
+/// Parses the body length of `message`
+pub(crate) fn parse_body_length(message: &crate::Message) -> Result<usize, ArrowError> {
+    let len = message.bodyLength();
+    usize::try_from(len)
+        .map_err(|_| ArrowError::IoError(format!("Invalid message body length {}", len)))
+}
 
+/// Reads the metadata of the next message in the stream, returning `None` at
+/// the end of the stream, indicated by either a zero length or the end of the input
+fn read_metadata<R: Read>(reader: &mut R) -> Result<Option<Vec<u8>>, ArrowError> {
+    // determine metadata length
+    let mut meta_size: [u8; 4] = [0; 4];
+    match reader.read_exact(&mut meta_size) {
+        Ok(()) => {}
+        // Handle EOF without the "0xFFFFFFFF 0x00000000"
+        // valid according to:
+        // https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+
+    // If a continuation marker is encountered, skip over it and read
+    // the size from the next four bytes.
+    if meta_size == CONTINUATION_MARKER {
+        reader.read_exact(&mut meta_size)?;
+    }
+    match parse_metadata_length(meta_size)? {
+        Some(len) => Ok(Some(read_exact_chunked(reader, len)?.as_slice().to_vec())),
+        None => Ok(None),
+    }
+}
+
+/// Decodes the messages of the Arrow IPC streaming format, independent of how
+/// the bytes of the stream are read
+#[derive(Debug)]
+pub(crate) struct StreamMessageDecoder {
     /// The schema that is read from the stream's first message
-    schema: SchemaRef,
+    pub(crate) schema: SchemaRef,
 
     /// Optional dictionaries for each schema field.
     ///
     /// Dictionaries may be appended to in the streaming format.
-    dictionaries_by_id: HashMap<i64, ArrayRef>,
+    pub(crate) dictionaries_by_id: HashMap<i64, ArrayRef>,
+
+    /// Optional projection
+    pub(crate) projection: Option<(Vec<usize>, Schema)>,
+
+    /// Custom metadata of the message of the most recently read record batch
+    pub(crate) batch_metadata: HashMap<String, String>,
+
+    /// Options controlling how record batches and dictionaries are decoded
+    pub(crate) decode_options: DecodeOptions,
+}
+
+impl StreamMessageDecoder {
+    /// Create a decoder from the metadata of the stream's first message, which
+    /// must be a schema, or `None` if the stream is empty
+    pub(crate) fn try_new(
+        meta_buffer: Option<&[u8]>,
+        projection: Option<Vec<usize>>,
+    ) -> Result<Self, ArrowError> {
+        let meta_buffer = meta_buffer.ok_or_else(|| {
+            ArrowError::IoError("Unable to read schema from empty stream".to_string())
+        })?;
+        let message = crate::root_as_message(meta_buffer).map_err(|err| {
+            ArrowError::IoError(format!("Unable to get root as message: {:?}", err))
+        })?;
+        // message header is a Schema, so read it
+        let ipc_schema: crate::Schema = message.header_as_schema().ok_or_else(|| {
+            ArrowError::IoError("Unable to read IPC message as schema".to_string())
+        })?;
+        let schema = crate::convert::fb_to_schema(ipc_schema);
+        let decode_options = DecodeOptions::new(ipc_schema);
+
+        let projection = match projection {
+            Some(projection_indices) => {
+                let schema = schema.project(&projection_indices)?;
+                Some((projection_indices, schema))
+            }
+            _ => None,
+        };
+        Ok(Self {
+            schema: Arc::new(schema),
+            dictionaries_by_id: HashMap::new(),
+            projection,
+            batch_metadata: HashMap::new(),
+            decode_options,
+        })
+    }
+
+    /// See [`StreamReader::with_projection`]
+    pub(crate) fn set_projection(
+        &mut self,
+        projection: Vec<usize>,
+    ) -> Result<(), ArrowError> {
+        let schema = self.schema.project(&projection)?;
+        self.projection = Some((projection, schema));
+        Ok(())
+    }
+
+    /// Parses the metadata of a message following the schema
+    ///
+    /// Returns `None` if the message marks the end of the stream, otherwise
+    /// the message, whose body of [`parse_body_length`] bytes is then to be
+    /// passed to [`Self::decode`]
+    pub(crate) fn parse_message<'a>(
+        &self,
+        meta_buffer: &'a [u8],
+    ) -> Result<Option<crate::Message<'a>>, ArrowError> {
+        let message = crate::root_as_message(meta_buffer).map_err(|err| {
+            ArrowError::IoError(format!("Unable to get root as message: {:?}", err))
+        })?;
+
+        match message.header_type() {
+            crate::MessageHeader::Schema => Err(ArrowError::IoError(
+                "Not expecting a schema when messages are read".to_string(),
+            )),
+            crate::MessageHeader::RecordBatch
+            | crate::MessageHeader::DictionaryBatch => Ok(Some(message)),
+            crate::MessageHeader::NONE => Ok(None),
+            t => Err(ArrowError::IoError(format!(
+                "Reading types other than record batches not yet supported, unable to read {:?} ",
+                t
+            ))),
+        }
+    }
+
+    /// Decodes a `message` returned by [`Self::parse_message`] with its `body`,
+    /// returning `None` if it is a dictionary batch
+    pub(crate) fn decode(
+        &mut self,
+        message: crate::Message,
+        body: &Buffer,
+    ) -> Result<Option<RecordBatch>, ArrowError> {
+        match message.header_type() {
+            crate::MessageHeader::RecordBatch => {
+                let batch = message.header_as_record_batch().ok_or_else(|| {
+                    ArrowError::IoError(
+                        "Unable to read IPC message as record batch".to_string(),
+                    )
+                })?;
+                self.batch_metadata =
+                    crate::convert::metadata_from_fb(message.custom_metadata());
+                read_record_batch_impl(
+                    body,
+                    batch,
+                    self.schema.clone(),
+                    &self.dictionaries_by_id,
+                    self.projection.as_ref().map(|x| x.0.as_ref()),
+                    &message.version(),
+                    self.decode_options,
+                )
+                .map(Some)
+            }
+            _ => {
+                let batch = message.header_as_dictionary_batch().ok_or_else(|| {
+                    ArrowError::IoError(
+                        "Unable to read IPC message as dictionary batch".to_string(),
+                    )
+                })?;
+                read_dictionary_impl(
+                    body,
+                    batch,
+                    &self.schema,
+                    &mut self.dictionaries_by_id,
+                    &message.version(),
+                    self.decode_options,
+                )?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Arrow Stream reader
+pub struct StreamReader<R: Read> {
+    /// Buffered stream reader
+    reader: BufReader<R>,
+
+    /// Decodes the messages read from the stream
+    decoder: StreamMessageDecoder,
 
     /// An indicator of whether the stream is complete.
     ///
     /// This value is set to `true` the first time the reader's `next()` returns `None`.
     finished: bool,
-
-    /// Optional projection
-    projection: Option<(Vec<usize>, Schema)>,
 }
 
 impl<R: Read> fmt::Debug for StreamReader<R> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), fmt::Error> {
         f.debug_struct("StreamReader<R>")
             .field("reader", &"BufReader<..>")
-            .field("schema", &self.schema)
-            .field("dictionaries_by_id", &self.dictionaries_by_id)
+            .field("schema", &self.decoder.schema)
+            .field("dictionaries_by_id", &self.decoder.dictionaries_by_id)
             .field("finished", &self.finished)
-            .field("projection", &self.projection)
+            .field("projection", &self.decoder.projection)
+            .field("batch_metadata", &self.decoder.batch_metadata)
+            .field("decode_options", &self.decoder.decode_options)
             .finish()
     }
 }
@@ -1032,52 +1576,41 @@ impl<R: Read> StreamReader<R> {
         projection: Option<Vec<usize>>,
     ) -> Result<Self, ArrowError> {
         let mut reader = BufReader::new(reader);
-        // determine metadata length
-        let mut meta_size: [u8; 4] = [0; 4];
-        reader.read_exact(&mut meta_size)?;
-        let meta_len = {
-            // If a continuation marker is encountered, skip over it and read
-            // the size from the next four bytes.
-            if meta_size == CONTINUATION_MARKER {
-                reader.read_exact(&mut meta_size)?;
-            }
-            i32::from_le_bytes(meta_size)
-        };
-
-        let mut meta_buffer = vec![0; meta_len as usize];
-        reader.read_exact(&mut meta_buffer)?;
-
-        let message = crate::root_as_message(meta_buffer.as_slice()).map_err(|err| {
-            ArrowError::IoError(format!("Unable to get root as message: {:?}", err))
-        })?;
-        // message header is a Schema, so read it
-        let ipc_schema: crate::Schema = message.header_as_schema().ok_or_else(|| {
-            ArrowError::IoError("Unable to read IPC message as schema".to_string())
-        })?;
-        let schema = crate::convert::fb_to_schema(ipc_schema);
-
-        // Create an array of optional dictionary value arrays, one per field.
-        let dictionaries_by_id = HashMap::new();
-
-        let projection = match projection {
-            Some(projection_indices) => {
-                let schema = schema.project(&projection_indices)?;
-                Some((projection_indices, schema))
-            }
-            _ => None,
-        };
+        let meta_buffer = read_metadata(&mut reader)?;
+        let decoder = StreamMessageDecoder::try_new(meta_buffer.as_deref(), projection)?;
         Ok(Self {
             reader,
-            schema: Arc::new(schema),
+            decoder,
             finished: false,
-            dictionaries_by_id,
-            projection,
         })
     }
 
     /// Return the schema of the stream
     pub fn schema(&self) -> SchemaRef {
-        self.schema.clone()
+        self.decoder.schema.clone()
+    }
+
+    /// Return the custom metadata of the message of the most recently read
+    /// record batch, empty if none has been read
+    pub fn batch_metadata(&self) -> &HashMap<String, String> {
+        &self.decoder.batch_metadata
+    }
+
+    /// Only decode the fields at the indices in `projection`, see
+    /// [projection](crate::reader#projection)
+    pub fn with_projection(mut self, projection: Vec<usize>) -> Result<Self, ArrowError> {
+        self.decoder.set_projection(projection)?;
+        Ok(self)
+    }
+
+    /// Skip validating the arrays read, see [validation](crate::reader#validation)
+    ///
+    /// # Safety
+    ///
+    /// If `skip_validation` is true, the IPC data must be known to contain valid arrays
+    pub unsafe fn with_skip_validation(mut self, skip_validation: bool) -> Self {
+        self.decoder.decode_options.skip_validation = skip_validation;
+        self
     }
 
     /// Check if the stream is finished
@@ -1086,65 +1619,326 @@ impl<R: Read> StreamReader<R> {
     }
 
     fn maybe_next(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
-        if self.finished {
-            return Ok(None);
-        }
-        // determine metadata length
-        let mut meta_size: [u8; 4] = [0; 4];
-
-        match self.reader.read_exact(&mut meta_size) {
-            Ok(()) => (),
-            Err(e) => {
-                return if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                    // Handle EOF without the "0xFFFFFFFF 0x00000000"
-                    // valid according to:
-                    // https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format
+        // Dictionary batches precede the record batch that uses them
+        while !self.finished {
+            let meta_buffer = match read_metadata(&mut self.reader)? {
+                Some(meta_buffer) => meta_buffer,
+                None => {
+                    // the stream has ended, mark the reader as finished
                     self.finished = true;
-                    Ok(None)
-                } else {
-                    Err(ArrowError::from(e))
-                };
+                    return Ok(None);
+                }
+            };
+            let message = match self.decoder.parse_message(&meta_buffer)? {
+                Some(message) => message,
+                None => return Ok(None),
+            };
+            // read the block that makes up the message into a buffer
+            let body =
+                read_exact_chunked(&mut self.reader, parse_body_length(&message)?)?;
+            if let Some(batch) = self.decoder.decode(message, &body.into())? {
+                return Ok(Some(batch));
             }
         }
+        Ok(None)
+    }
+}
+
+impl<R: Read> Iterator for StreamReader<R> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.maybe_next().transpose()
+    }
+}
+
+impl<R: Read> RecordBatchReader for StreamReader<R> {
+    fn schema(&self) -> SchemaRef {
+        self.decoder.schema.clone()
+    }
+}
+
+/// The state of a [`StreamDecoder`]
+#[derive(Debug)]
+enum DecoderState {
+    /// Decoding the length of the next message's metadata, which may be
+    /// preceded by the continuation marker
+    Header {
+        buf: [u8; 4],
+        read: usize,
+        continuation: bool,
+    },
+    /// Decoding the metadata of a message
+    Message { size: usize },
+    /// Decoding the body of a message
+    Body { message: Buffer, size: usize },
+    /// Reached the end of the stream
+    Finished,
+}
+
+impl Default for DecoderState {
+    fn default() -> Self {
+        Self::Header {
+            buf: [0; 4],
+            read: 0,
+            continuation: false,
+        }
+    }
+}
+
+/// A push-based decoder of the Arrow IPC streaming format
+///
+/// Unlike [`StreamReader`], which reads from an [`std::io::Read`], the
+/// [`StreamDecoder`] is provided the stream in [`Buffer`]s of any size, and
+/// reassembles the messages split across them. Message bodies contained within a
+/// single [`Buffer`] are not copied.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+/// # use arrow_buffer::Buffer;
+/// # use arrow_ipc::writer::StreamWriter;
+/// use arrow_ipc::reader::StreamDecoder;
+///
+/// # let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+/// # let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
+/// # let mut writer = StreamWriter::try_new(vec![], &batch.schema()).unwrap();
+/// # writer.write(&batch).unwrap();
+/// # let data = writer.into_inner().unwrap();
+/// let mut decoder = StreamDecoder::new();
+/// let mut batches = vec![];
+/// for chunk in data.chunks(10) {
+///     let mut buffer = Buffer::from(chunk);
+///     while let Some(batch) = decoder.decode(&mut buffer).unwrap() {
+///         batches.push(batch);
+///     }
+/// }
+/// decoder.finish().unwrap();
+/// assert_eq!(batches, vec![batch]);
+/// ```
+#[derive(Debug)]
+pub struct StreamDecoder {
+    /// The schema decoded from the stream's first message
+    schema: Option<SchemaRef>,
+
+    /// Dictionaries by their id, decoded from dictionary batches
+    dictionaries_by_id: HashMap<i64, ArrayRef>,
+
+    /// The current state of the decoder
+    state: DecoderState,
+
+    /// The bytes of the current metadata or body, if split across buffers
+    partial: MutableBuffer,
+
+    /// Custom metadata of the message of the most recently read record batch
+    batch_metadata: HashMap<String, String>,
+
+    /// Options controlling how record batches and dictionaries are decoded
+    decode_options: DecodeOptions,
+}
+
+impl Default for StreamDecoder {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            dictionaries_by_id: HashMap::new(),
+            state: DecoderState::default(),
+            partial: MutableBuffer::new(0),
+            batch_metadata: HashMap::new(),
+            decode_options: DecodeOptions::default(),
+        }
+    }
+}
+
+impl StreamDecoder {
+    /// Create a new [`StreamDecoder`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip validating the arrays read, see [validation](crate::reader#validation)
+    ///
+    /// # Safety
+    ///
+    /// If `skip_validation` is true, the IPC data must be known to contain valid arrays
+    pub unsafe fn with_skip_validation(mut self, skip_validation: bool) -> Self {
+        self.decode_options.skip_validation = skip_validation;
+        self
+    }
+
+    /// Return the schema of the stream, if it has been decoded
+    pub fn schema(&self) -> Option<SchemaRef> {
+        self.schema.clone()
+    }
+
+    /// Return the custom metadata of the message of the most recently read
+    /// record batch, empty if none has been read
+    pub fn batch_metadata(&self) -> &HashMap<String, String> {
+        &self.batch_metadata
+    }
 
-        let meta_len = {
-            // If a continuation marker is encountered, skip over it and read
-            // the size from the next four bytes.
-            if meta_size == CONTINUATION_MARKER {
-                self.reader.read_exact(&mut meta_size)?;
+    /// Decode the next [`RecordBatch`] from `buffer`, advancing `buffer` past the
+    /// bytes consumed
+    ///
+    /// Returns `Ok(None)` if `buffer` is consumed without completing a
+    /// [`RecordBatch`], in which case this should be called again with the
+    /// following bytes of the stream. If `Ok(Some(_))` is returned, the remaining
+    /// bytes of `buffer` should be provided to this method again.
+    pub fn decode(
+        &mut self,
+        buffer: &mut Buffer,
+    ) -> Result<Option<RecordBatch>, ArrowError> {
+        while !buffer.is_empty() {
+            match &mut self.state {
+                DecoderState::Header {
+                    buf,
+                    read,
+                    continuation,
+                } => {
+                    let to_read = buffer.len().min(4 - *read);
+                    buf[*read..*read + to_read].copy_from_slice(&buffer[..to_read]);
+                    *buffer = buffer.slice(to_read);
+                    *read += to_read;
+                    if *read < 4 {
+                        continue;
+                    }
+
+                    // If a continuation marker is encountered, skip over it and read
+                    // the size from the next four bytes.
+                    if !*continuation && *buf == CONTINUATION_MARKER {
+                        *continuation = true;
+                        *read = 0;
+                        continue;
+                    }
+
+                    let size = i32::from_le_bytes(*buf);
+                    self.state = match size {
+                        0 => DecoderState::Finished,
+                        _ => DecoderState::Message {
+                            size: usize::try_from(size).map_err(|_| {
+                                ArrowError::IoError(format!(
+                                    "Invalid message metadata length {}",
+                                    size
+                                ))
+                            })?,
+                        },
+                    };
+                }
+                DecoderState::Message { size } => {
+                    let size = *size;
+                    if let Some(message) = self.read_partial(buffer, size) {
+                        let body_size = crate::root_as_message(&message)
+                            .map_err(|err| {
+                                ArrowError::IoError(format!(
+                                    "Unable to get root as message: {:?}",
+                                    err
+                                ))
+                            })?
+                            .bodyLength();
+                        let size = usize::try_from(body_size).map_err(|_| {
+                            ArrowError::IoError(format!(
+                                "Invalid message body length {}",
+                                body_size
+                            ))
+                        })?;
+                        self.state = DecoderState::Body { message, size };
+                        if size == 0 {
+                            if let Some(batch) = self.read_body(Buffer::from(&[]))? {
+                                return Ok(Some(batch));
+                            }
+                        }
+                    }
+                }
+                DecoderState::Body { size, .. } => {
+                    let size = *size;
+                    if let Some(body) = self.read_partial(buffer, size) {
+                        if let Some(batch) = self.read_body(body)? {
+                            return Ok(Some(batch));
+                        }
+                    }
+                }
+                DecoderState::Finished => {
+                    return Err(ArrowError::IoError(
+                        "Unexpected data after the end of the stream".to_string(),
+                    ))
+                }
             }
-            i32::from_le_bytes(meta_size)
-        };
+        }
+        Ok(None)
+    }
 
-        if meta_len == 0 {
-            // the stream has ended, mark the reader as finished
-            self.finished = true;
-            return Ok(None);
+    /// Signal the end of the stream, returning an error if it ended part way
+    /// through a message
+    pub fn finish(&mut self) -> Result<(), ArrowError> {
+        match &self.state {
+            DecoderState::Finished | DecoderState::Header { read: 0, .. } => Ok(()),
+            _ => Err(ArrowError::IoError(
+                "Unexpected end of stream part way through a message".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the next `size` bytes, taking them from `buffer` without copying if
+    /// no bytes have already been read, or `None` if `buffer` does not contain the
+    /// remaining bytes
+    fn read_partial(&mut self, buffer: &mut Buffer, size: usize) -> Option<Buffer> {
+        if self.partial.is_empty() && buffer.len() >= size {
+            let data = buffer.slice_with_length(0, size);
+            *buffer = buffer.slice(size);
+            return Some(data);
         }
 
-        let mut meta_buffer = vec![0; meta_len as usize];
-        self.reader.read_exact(&mut meta_buffer)?;
+        let to_read = buffer.len().min(size - self.partial.len());
+        self.partial.extend_from_slice(&buffer[..to_read]);
+        *buffer = buffer.slice(to_read);
+        (self.partial.len() == size)
+            .then(|| std::mem::replace(&mut self.partial, MutableBuffer::new(0)).into())
+    }
 
-        let vecs = &meta_buffer.to_vec();
-        let message = crate::root_as_message(vecs).map_err(|err| {
+    /// Decodes the message of the current [`DecoderState::Body`] with its `body`,
+    /// and resets the state to read the next message
+    fn read_body(&mut self, body: Buffer) -> Result<Option<RecordBatch>, ArrowError> {
+        let message = match std::mem::take(&mut self.state) {
+            DecoderState::Body { message, .. } => message,
+            _ => unreachable!("read_body called outside of DecoderState::Body"),
+        };
+        let message = crate::root_as_message(&message).map_err(|err| {
             ArrowError::IoError(format!("Unable to get root as message: {:?}", err))
         })?;
 
         match message.header_type() {
-            crate::MessageHeader::Schema => Err(ArrowError::IoError(
-                "Not expecting a schema when messages are read".to_string(),
-            )),
+            crate::MessageHeader::Schema => {
+                if self.schema.is_some() {
+                    return Err(ArrowError::IoError(
+                        "Not expecting a schema when messages are read".to_string(),
+                    ));
+                }
+                let ipc_schema = message.header_as_schema().ok_or_else(|| {
+                    ArrowError::IoError("Unable to read IPC message as schema".to_string())
+                })?;
+                self.schema = Some(Arc::new(crate::convert::fb_to_schema(ipc_schema)));
+                self.decode_options.byte_swap =
+                    ipc_schema.endianness() != crate::endian::native();
+                Ok(None)
+            }
             crate::MessageHeader::RecordBatch => {
                 let batch = message.header_as_record_batch().ok_or_else(|| {
                     ArrowError::IoError(
                         "Unable to read IPC message as record batch".to_string(),
                     )
                 })?;
-                // read the block that makes up the record batch into a buffer
-                let mut buf = MutableBuffer::from_len_zeroed(message.bodyLength() as usize);
-                self.reader.read_exact(&mut buf)?;
-
-                read_record_batch(&buf.into(), batch, self.schema(), &self.dictionaries_by_id, self.projection.as_ref().map(|x| x.0.as_ref()), &message.version()).map(Some)
+                self.batch_metadata =
+                    crate::convert::metadata_from_fb(message.custom_metadata());
+                read_record_batch_impl(
+                    &body,
+                    batch,
+                    self.expect_schema()?,
+                    &self.dictionaries_by_id,
+                    None,
+                    &message.version(),
+                    self.decode_options,
+                )
+                .map(Some)
             }
             crate::MessageHeader::DictionaryBatch => {
                 let batch = message.header_as_dictionary_batch().ok_or_else(|| {
@@ -1152,38 +1946,30 @@ impl<R: Read> StreamReader<R> {
                         "Unable to read IPC message as dictionary batch".to_string(),
                     )
                 })?;
-                // read the block that makes up the dictionary batch into a buffer
-                let mut buf = MutableBuffer::from_len_zeroed(message.bodyLength() as usize);
-                self.reader.read_exact(&mut buf)?;
-
-                read_dictionary(
-                    &buf.into(), batch, &self.schema, &mut self.dictionaries_by_id, &message.version()
+                read_dictionary_impl(
+                    &body,
+                    batch,
+                    self.expect_schema()?.as_ref(),
+                    &mut self.dictionaries_by_id,
+                    &message.version(),
+                    self.decode_options,
                 )?;
-
-                // read the next message until we encounter a RecordBatch
-                self.maybe_next()
-            }
-            crate::MessageHeader::NONE => {
                 Ok(None)
             }
-            t => Err(ArrowError::IoError(
-                format!("Reading types other than record batches not yet supported, unable to read {:?} ", t)
-            )),
+            crate::MessageHeader::NONE => Ok(None),
+            t => Err(ArrowError::IoError(format!(
+                "Reading types other than record batches not yet supported, unable to read {:?}",
+                t
+            ))),
         }
     }
-}
-
-impl<R: Read> Iterator for StreamReader<R> {
-    type Item = Result<RecordBatch, ArrowError>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.maybe_next().transpose()
-    }
-}
 
-impl<R: Read> RecordBatchReader for StreamReader<R> {
-    fn schema(&self) -> SchemaRef {
-        self.schema.clone()
+    fn expect_schema(&self) -> Result<SchemaRef, ArrowError> {
+        self.schema.clone().ok_or_else(|| {
+            ArrowError::IoError(
+                "Expected the stream to begin with a schema message".to_string(),
+            )
+        })
     }
 }
 
@@ -1358,6 +2144,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_projection() {
+        let schema = create_test_projection_schema();
+        let batch = create_test_projection_batch_data(&schema);
+        let projection = vec![11, 2, 7, 0];
+        let expected = batch.project(&projection).unwrap();
+
+        let mut file = vec![];
+        {
+            let mut writer =
+                crate::writer::FileWriter::try_new(&mut file, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+        let reader = FileReader::try_new(std::io::Cursor::new(file.clone()), None)
+            .unwrap()
+            .with_projection(projection.clone())
+            .unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, vec![expected.clone()]);
+
+        let mut stream = vec![];
+        {
+            let mut writer =
+                crate::writer::StreamWriter::try_new(&mut stream, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+        let reader = StreamReader::try_new(stream.as_slice(), None)
+            .unwrap()
+            .with_projection(projection)
+            .unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, vec![expected]);
+
+        let err = StreamReader::try_new(stream.as_slice(), None)
+            .unwrap()
+            .with_projection(vec![13])
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("project index 13 out of bounds"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_read_batch_at() {
+        let batches: Vec<_> = (0..5)
+            .map(|i| {
+                let array: ArrayRef = Arc::new(Int32Array::from(vec![i; i as usize + 1]));
+                RecordBatch::try_from_iter(vec![("a", array)]).unwrap()
+            })
+            .collect();
+
+        let mut file = vec![];
+        {
+            let mut writer =
+                crate::writer::FileWriter::try_new(&mut file, &batches[0].schema())
+                    .unwrap();
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let mut reader = FileReader::try_new(std::io::Cursor::new(file), None).unwrap();
+        assert_eq!(reader.blocks().len(), 5);
+        assert!(reader
+            .blocks()
+            .windows(2)
+            .all(|w| w[0].offset() < w[1].offset()));
+
+        assert_eq!(reader.read_batch_at(3).unwrap(), batches[3]);
+        assert_eq!(reader.read_range(1..4).unwrap(), &batches[1..4]);
+        assert_eq!(reader.read_range(2..2).unwrap(), vec![]);
+
+        // The position of the iterator is unaffected
+        assert_eq!(reader.next().unwrap().unwrap(), batches[0]);
+        assert_eq!(reader.read_batch_at(4).unwrap(), batches[4]);
+        assert_eq!(reader.next().unwrap().unwrap(), batches[1]);
+
+        let err = reader.read_batch_at(5).unwrap_err();
+        assert!(err.to_string().contains("index 5 from 5"), "{}", err);
+        reader.read_range(3..6).unwrap_err();
+    }
+
     #[test]
     fn test_arrow_single_float_row() {
         let schema = Schema::new(vec![
@@ -1435,6 +2308,148 @@ mod tests {
         reader.next().unwrap().unwrap()
     }
 
+    #[test]
+    fn test_stream_reader_hostile_lengths() {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
+        let mut writer =
+            crate::writer::StreamWriter::try_new(vec![], &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        let data = writer.into_inner().unwrap();
+
+        // A metadata length far larger than the stream is not allocated up front
+        let mut hostile = CONTINUATION_MARKER.to_vec();
+        hostile.extend_from_slice(&i32::MAX.to_le_bytes());
+        hostile.extend_from_slice(&data[8..]);
+        let err = StreamReader::try_new(hostile.as_slice(), None).unwrap_err();
+        assert!(
+            err.to_string().contains("failed to fill whole buffer"),
+            "{}",
+            err
+        );
+
+        hostile[4..8].copy_from_slice(&(-8_i32).to_le_bytes());
+        let err = StreamReader::try_new(hostile.as_slice(), None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid message metadata length -8"));
+
+        // A stream truncated within the body of a record batch
+        let truncated = &data[..data.len() - 20];
+        let mut reader = StreamReader::try_new(truncated, None).unwrap();
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(
+            err.to_string().contains("failed to fill whole buffer"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_skip_validation() {
+        let array: ArrayRef = Arc::new(StringArray::from(vec!["hello", "world"]));
+        let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
+        let mut writer =
+            crate::writer::StreamWriter::try_new(vec![], &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        let mut data = writer.into_inner().unwrap();
+
+        // Replace the first byte of "hello" with invalid UTF-8
+        let idx = data.windows(5).position(|w| w == b"hello").unwrap();
+        data[idx] = 0xFF;
+
+        let mut reader = StreamReader::try_new(data.as_slice(), None).unwrap();
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("Invalid UTF8"), "{}", err);
+
+        let reader = StreamReader::try_new(data.as_slice(), None).unwrap();
+        let mut reader = unsafe { reader.with_skip_validation(true) };
+        assert_eq!(reader.next().unwrap().unwrap().num_rows(), 2);
+    }
+
+    #[test]
+    fn test_read_out_of_bounds_buffer() {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
+        let (_, encoded) = crate::writer::IpcDataGenerator::default()
+            .encoded_batch(
+                &batch,
+                &mut crate::writer::DictionaryTracker::new(false),
+                &Default::default(),
+            )
+            .unwrap();
+        let message = crate::root_as_message(&encoded.ipc_message).unwrap();
+        let ipc_batch = message.header_as_record_batch().unwrap();
+
+        // Truncate the body so that the values buffer exceeds it
+        let body = Buffer::from(&encoded.arrow_data[..4]);
+        let err = read_record_batch(
+            &body,
+            ipc_batch,
+            batch.schema(),
+            &HashMap::new(),
+            None,
+            &message.version(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("exceeds message body"), "{}", err);
+    }
+
+    #[test]
+    fn test_stream_decoder() {
+        let schema = create_test_projection_schema();
+        let batch = create_test_projection_batch_data(&schema);
+        let batches = vec![batch.clone(), batch.slice(1, 1), batch];
+
+        let options = [
+            crate::writer::IpcWriteOptions::default(),
+            crate::writer::IpcWriteOptions::try_new(8, true, crate::MetadataVersion::V4)
+                .unwrap(),
+        ];
+        for options in options {
+            let mut writer = crate::writer::StreamWriter::try_new_with_options(
+                vec![],
+                &schema,
+                options,
+            )
+            .unwrap();
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            let data = writer.into_inner().unwrap();
+            let expected: Vec<_> = StreamReader::try_new(data.as_slice(), None)
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(expected.len(), 3);
+
+            for chunk_size in [1, 3, 7, 64, 1024, data.len()] {
+                let mut decoder = StreamDecoder::new();
+                let mut decoded = vec![];
+                for chunk in data.chunks(chunk_size) {
+                    let mut buffer = Buffer::from(chunk);
+                    while let Some(batch) = decoder.decode(&mut buffer).unwrap() {
+                        decoded.push(batch);
+                    }
+                    assert!(buffer.is_empty());
+                }
+                decoder.finish().unwrap();
+                assert_eq!(decoder.schema().unwrap().as_ref(), &schema);
+                assert_eq!(decoded, expected, "chunk_size {}", chunk_size);
+
+                // Data following the end of stream marker is an error
+                decoder.decode(&mut Buffer::from(&[0])).unwrap_err();
+            }
+
+            // A stream ending part way through a message is an error
+            let mut decoder = StreamDecoder::new();
+            let mut buffer = Buffer::from(&data[..data.len() - 20]);
+            while decoder.decode(&mut buffer).unwrap().is_some() {}
+            let err = decoder.finish().unwrap_err();
+            assert!(err.to_string().contains("part way through"), "{}", err);
+        }
+    }
+
     fn roundtrip_ipc_stream(rb: &RecordBatch) -> RecordBatch {
         let mut buf = Vec::new();
         let mut writer =
@@ -0,0 +1,52 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Utilities shared by the tests of this crate
+
+use std::sync::Arc;
+
+use arrow_array::types::Int32Type;
+use arrow_array::{ArrayRef, DictionaryArray, Int64Array, RecordBatch, StringArray};
+
+/// Returns three record batches with integer, string and dictionary columns
+///
+/// If `replace_dictionaries` is true, the values of the dictionary column differ
+/// between batches, otherwise all batches share the same dictionary
+pub(crate) fn sample_batches(replace_dictionaries: bool) -> Vec<RecordBatch> {
+    (0..3)
+        .map(|i| {
+            let ints: ArrayRef = Arc::new(Int64Array::from_iter(
+                (0..100).map(|x| (x % 7 != 0).then_some(x * i)),
+            ));
+            let strings: ArrayRef = Arc::new(StringArray::from_iter_values(
+                (0..100).map(|x| format!("value {}", x + i)),
+            ));
+            let values = match replace_dictionaries {
+                true => 2 + i as usize,
+                false => 3,
+            };
+            let dict: DictionaryArray<Int32Type> =
+                (0..100).map(|x| ["a", "b", "c", "d"][x % values]).collect();
+            RecordBatch::try_from_iter(vec![
+                ("ints", ints),
+                ("strings", strings),
+                ("dict", Arc::new(dict) as ArrayRef),
+            ])
+            .unwrap()
+        })
+        .collect()
+}
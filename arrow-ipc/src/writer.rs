@@ -22,7 +22,7 @@
 
 use std::cmp::min;
 use std::collections::HashMap;
-use std::io::{BufWriter, Write};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
 
 use flatbuffers::FlatBufferBuilder;
 
@@ -31,6 +31,7 @@ use arrow_array::cast::*;
 use arrow_array::*;
 use arrow_buffer::bit_util;
 use arrow_buffer::{Buffer, MutableBuffer};
+use arrow_data::transform::MutableArrayData;
 use arrow_data::{layout, ArrayData, BufferSpec};
 use arrow_schema::*;
 
@@ -40,9 +41,11 @@ use crate::CONTINUATION_MARKER;
 /// IPC write options used to control the behaviour of the writer
 #[derive(Debug, Clone)]
 pub struct IpcWriteOptions {
-    /// Write padding after memory buffers to this multiple of bytes.
+    /// Write padding after the metadata of each message to this multiple of bytes.
     /// Generally 8 or 64, defaults to 64
     alignment: usize,
+    /// Write padding after memory buffers to this multiple of bytes, defaults to 8
+    buffer_alignment: usize,
     /// The legacy format is for releases before 0.15.0, and uses metadata V4
     write_legacy_ipc_format: bool,
     /// The metadata version to write. The Rust IPC writer supports V4+
@@ -57,13 +60,30 @@ pub struct IpcWriteOptions {
     /// Compression, if desired. Will result in a runtime error
     /// if the corresponding feature is not enabled
     batch_compression_type: Option<crate::CompressionType>,
+    /// The endianness to write buffers with, defaults to that of the target platform
+    endianness: crate::Endianness,
+    /// How to write a dictionary whose values differ from those last written
+    dictionary_handling: DictionaryHandling,
 }
 
 impl IpcWriteOptions {
     /// Configures compression when writing IPC files.
     ///
+    /// Each buffer of a record batch or dictionary body is compressed
+    /// separately, and prefixed with its uncompressed length. Buffers that
+    /// do not shrink when compressed are written uncompressed, prefixed with
+    /// a length of `-1`, as described by the IPC specification.
+    ///
     /// Will result in a runtime error if the corresponding feature
     /// is not enabled
+    ///
+    /// ```
+    /// # use arrow_ipc::writer::IpcWriteOptions;
+    /// # use arrow_ipc::CompressionType;
+    /// let options = IpcWriteOptions::default()
+    ///     .try_with_compression(Some(CompressionType::ZSTD))
+    ///     .unwrap();
+    /// ```
     pub fn try_with_compression(
         mut self,
         batch_compression_type: Option<crate::CompressionType>,
@@ -98,9 +118,12 @@ impl IpcWriteOptions {
             )),
             crate::MetadataVersion::V4 => Ok(Self {
                 alignment,
+                buffer_alignment: 8,
                 write_legacy_ipc_format,
                 metadata_version,
                 batch_compression_type: None,
+                endianness: crate::endian::native(),
+                dictionary_handling: DictionaryHandling::Delta,
             }),
             crate::MetadataVersion::V5 => {
                 if write_legacy_ipc_format {
@@ -111,9 +134,12 @@ impl IpcWriteOptions {
                 } else {
                     Ok(Self {
                         alignment,
+                        buffer_alignment: 8,
                         write_legacy_ipc_format,
                         metadata_version,
                         batch_compression_type: None,
+                        endianness: crate::endian::native(),
+                        dictionary_handling: DictionaryHandling::Delta,
                     })
                 }
             }
@@ -123,15 +149,142 @@ impl IpcWriteOptions {
             ))),
         }
     }
+
+    /// Configures the alignment in bytes of the metadata and buffers of each
+    /// message, which must be a power of two of at least 8
+    ///
+    /// By default the metadata is aligned to 64 bytes and buffers to 8 bytes.
+    /// Consumers that memory-map IPC files may require buffers aligned to 64 bytes
+    pub fn try_with_alignment(mut self, alignment: usize) -> Result<Self, ArrowError> {
+        if alignment < 8 || !alignment.is_power_of_two() {
+            return Err(ArrowError::InvalidArgumentError(
+                "Alignment should be a power of two greater than or equal to 8"
+                    .to_string(),
+            ));
+        }
+        self.alignment = alignment;
+        self.buffer_alignment = alignment;
+        Ok(self)
+    }
+
+    /// Configures the metadata version to write, which must be V4 or V5
+    ///
+    /// Returns an error if the legacy IPC format or compression are configured and
+    /// not supported by `metadata_version`
+    pub fn try_with_metadata_version(
+        mut self,
+        metadata_version: crate::MetadataVersion,
+    ) -> Result<Self, ArrowError> {
+        Self::try_new(
+            self.alignment,
+            self.write_legacy_ipc_format,
+            metadata_version,
+        )?;
+        self.metadata_version = metadata_version;
+        let compression = self.batch_compression_type;
+        self.try_with_compression(compression)
+    }
+
+    /// Configures writing the legacy IPC format of releases before 0.15.0, in which
+    /// messages are not prefixed by the continuation marker
+    ///
+    /// Returns an error if enabled without metadata version V4
+    ///
+    /// ```
+    /// # use arrow_ipc::writer::IpcWriteOptions;
+    /// # use arrow_ipc::MetadataVersion;
+    /// let options = IpcWriteOptions::default()
+    ///     .try_with_alignment(8)
+    ///     .unwrap()
+    ///     .try_with_metadata_version(MetadataVersion::V4)
+    ///     .unwrap()
+    ///     .try_with_legacy_ipc_format(true)
+    ///     .unwrap();
+    /// assert_eq!(options.alignment(), 8);
+    /// assert!(options.write_legacy_ipc_format());
+    /// ```
+    pub fn try_with_legacy_ipc_format(
+        mut self,
+        write_legacy_ipc_format: bool,
+    ) -> Result<Self, ArrowError> {
+        Self::try_new(
+            self.alignment,
+            write_legacy_ipc_format,
+            self.metadata_version,
+        )?;
+        self.write_legacy_ipc_format = write_legacy_ipc_format;
+        Ok(self)
+    }
+
+    /// Configures the endianness to write buffers with, defaults to that of the
+    /// target platform
+    ///
+    /// Buffers are byte swapped when written with an endianness other than that of the
+    /// target platform, allowing files to be produced for big-endian consumers
+    pub fn with_endianness(mut self, endianness: crate::Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Configures how a dictionary whose values differ from those last written for
+    /// the same field is written, defaults to [`DictionaryHandling::Delta`]
+    ///
+    /// As the IPC file format does not support replacing dictionaries, the
+    /// [`FileWriter`] always errors if a dictionary would be resent in full
+    pub fn with_dictionary_handling(
+        mut self,
+        dictionary_handling: DictionaryHandling,
+    ) -> Self {
+        self.dictionary_handling = dictionary_handling;
+        self
+    }
+
+    /// Returns the alignment in bytes of the metadata of each message
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Returns the alignment in bytes of the buffers of each message
+    pub fn buffer_alignment(&self) -> usize {
+        self.buffer_alignment
+    }
+
+    /// Returns true if the legacy IPC format is written
+    pub fn write_legacy_ipc_format(&self) -> bool {
+        self.write_legacy_ipc_format
+    }
+
+    /// Returns the metadata version written
+    pub fn metadata_version(&self) -> crate::MetadataVersion {
+        self.metadata_version
+    }
+
+    /// Returns the compression configured, if any
+    pub fn batch_compression_type(&self) -> Option<crate::CompressionType> {
+        self.batch_compression_type
+    }
+
+    /// Returns the endianness buffers are written with
+    pub fn endianness(&self) -> crate::Endianness {
+        self.endianness
+    }
+
+    /// Returns how a dictionary whose values differ from those last written is written
+    pub fn dictionary_handling(&self) -> DictionaryHandling {
+        self.dictionary_handling
+    }
 }
 
 impl Default for IpcWriteOptions {
     fn default() -> Self {
         Self {
             alignment: 64,
+            buffer_alignment: 8,
             write_legacy_ipc_format: false,
             metadata_version: crate::MetadataVersion::V5,
             batch_compression_type: None,
+            endianness: crate::endian::native(),
+            dictionary_handling: DictionaryHandling::Delta,
         }
     }
 }
@@ -147,7 +300,11 @@ impl IpcDataGenerator {
     ) -> EncodedData {
         let mut fbb = FlatBufferBuilder::new();
         let schema = {
-            let fb = crate::convert::schema_to_fb_offset(&mut fbb, schema);
+            let fb = crate::convert::schema_to_fb_offset_with_endianness(
+                &mut fbb,
+                schema,
+                write_options.endianness,
+            );
             fb.as_union_value()
         };
 
@@ -295,14 +452,25 @@ impl IpcDataGenerator {
                     write_options,
                 )?;
 
-                let emit = dictionary_tracker.insert(dict_id, column)?;
-
-                if emit {
-                    encoded_dictionaries.push(self.dictionary_batch_to_bytes(
-                        dict_id,
-                        dict_values,
-                        write_options,
-                    )?);
+                let handling = write_options.dictionary_handling;
+                match dictionary_tracker.insert_column(dict_id, column, handling)? {
+                    DictionaryUpdate::None => {}
+                    DictionaryUpdate::New | DictionaryUpdate::Replaced => {
+                        encoded_dictionaries.push(self.dictionary_batch_to_bytes(
+                            dict_id,
+                            dict_values,
+                            false,
+                            write_options,
+                        )?);
+                    }
+                    DictionaryUpdate::Delta(delta) => {
+                        encoded_dictionaries.push(self.dictionary_batch_to_bytes(
+                            dict_id,
+                            &delta,
+                            true,
+                            write_options,
+                        )?);
+                    }
                 }
             }
             _ => self._encode_dictionaries(
@@ -321,6 +489,23 @@ impl IpcDataGenerator {
         batch: &RecordBatch,
         dictionary_tracker: &mut DictionaryTracker,
         write_options: &IpcWriteOptions,
+    ) -> Result<(Vec<EncodedData>, EncodedData), ArrowError> {
+        self.encoded_batch_with_metadata(
+            batch,
+            &HashMap::new(),
+            dictionary_tracker,
+            write_options,
+        )
+    }
+
+    /// Encodes `batch` as [`Self::encoded_batch`], with the custom key/value
+    /// `metadata` attached to the message of the record batch
+    pub fn encoded_batch_with_metadata(
+        &self,
+        batch: &RecordBatch,
+        metadata: &HashMap<String, String>,
+        dictionary_tracker: &mut DictionaryTracker,
+        write_options: &IpcWriteOptions,
     ) -> Result<(Vec<EncodedData>, EncodedData), ArrowError> {
         let schema = batch.schema();
         let mut encoded_dictionaries = Vec::with_capacity(schema.all_fields().len());
@@ -336,7 +521,8 @@ impl IpcDataGenerator {
             )?;
         }
 
-        let encoded_message = self.record_batch_to_bytes(batch, write_options)?;
+        let encoded_message =
+            self.record_batch_to_bytes(batch, metadata, write_options)?;
         Ok((encoded_dictionaries, encoded_message))
     }
 
@@ -345,6 +531,7 @@ impl IpcDataGenerator {
     fn record_batch_to_bytes(
         &self,
         batch: &RecordBatch,
+        metadata: &HashMap<String, String>,
         write_options: &IpcWriteOptions,
     ) -> Result<EncodedData, ArrowError> {
         let mut fbb = FlatBufferBuilder::new();
@@ -383,7 +570,7 @@ impl IpcDataGenerator {
         }
         // pad the tail of body data
         let len = arrow_data.len();
-        let pad_len = pad_to_8(len as u32);
+        let pad_len = pad_to_alignment(write_options.buffer_alignment, len);
         arrow_data.extend_from_slice(&vec![0u8; pad_len][..]);
 
         // write data
@@ -400,12 +587,16 @@ impl IpcDataGenerator {
             let b = batch_builder.finish();
             b.as_union_value()
         };
+        let custom_metadata = crate::convert::metadata_to_fb(&mut fbb, metadata);
         // create an crate::Message
         let mut message = crate::MessageBuilder::new(&mut fbb);
         message.add_version(write_options.metadata_version);
         message.add_header_type(crate::MessageHeader::RecordBatch);
         message.add_bodyLength(arrow_data.len() as i64);
         message.add_header(root);
+        if let Some(custom_metadata) = custom_metadata {
+            message.add_custom_metadata(custom_metadata);
+        }
         let root = message.finish();
         fbb.finish(root, None);
         let finished_data = fbb.finished_data();
@@ -417,11 +608,13 @@ impl IpcDataGenerator {
     }
 
     /// Write dictionary values into two sets of bytes, one for the header (crate::Message) and the
-    /// other for the data
+    /// other for the data. If `is_delta` is true, the values are appended to those of the
+    /// dictionary previously written with the same id
     fn dictionary_batch_to_bytes(
         &self,
         dict_id: i64,
         array_data: &ArrayData,
+        is_delta: bool,
         write_options: &IpcWriteOptions,
     ) -> Result<EncodedData, ArrowError> {
         let mut fbb = FlatBufferBuilder::new();
@@ -458,7 +651,7 @@ impl IpcDataGenerator {
 
         // pad the tail of body data
         let len = arrow_data.len();
-        let pad_len = pad_to_8(len as u32);
+        let pad_len = pad_to_alignment(write_options.buffer_alignment, len);
         arrow_data.extend_from_slice(&vec![0u8; pad_len][..]);
 
         // write data
@@ -480,6 +673,7 @@ impl IpcDataGenerator {
             let mut batch_builder = crate::DictionaryBatchBuilder::new(&mut fbb);
             batch_builder.add_id(dict_id);
             batch_builder.add_data(root);
+            batch_builder.add_isDelta(is_delta);
             batch_builder.finish().as_union_value()
         };
 
@@ -502,11 +696,39 @@ impl IpcDataGenerator {
     }
 }
 
+/// How a writer handles a dictionary whose values differ from those last written
+/// for the same dictionary id, see [`IpcWriteOptions::with_dictionary_handling`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryHandling {
+    /// Return an error
+    Error,
+    /// Write the new dictionary in full, replacing the previous one
+    Resend,
+    /// Write only the new values as a delta if the values last written are a prefix
+    /// of the new values, otherwise write the new dictionary in full
+    Delta,
+}
+
+/// The update to a dictionary determined by [`DictionaryTracker::insert_column`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DictionaryUpdate {
+    /// The dictionary values are the same as those last written, and need not be written again
+    None,
+    /// No dictionary with this id has been written before
+    New,
+    /// The dictionary values differ from those last written, and must be written in full
+    Replaced,
+    /// The values last written are a prefix of the new dictionary values, and so only
+    /// the contained values, which follow them, need to be written as a delta
+    Delta(ArrayData),
+}
+
 /// Keeps track of dictionaries that have been written, to avoid emitting the same dictionary
-/// multiple times. Can optionally error if an update to an existing dictionary is attempted, which
+/// multiple times, and to emit only the new values of a dictionary that has grown. Can
+/// optionally error if a replacement of an existing dictionary is attempted, which
 /// isn't allowed in the `FileWriter`.
 pub struct DictionaryTracker {
-    written: HashMap<i64, ArrayRef>,
+    written: HashMap<i64, ArrayData>,
     error_on_replacement: bool,
 }
 
@@ -527,31 +749,77 @@ impl DictionaryTracker {
     /// * If the tracker has not been configured to error on replacement or this dictionary
     ///   has never been seen before, return `Ok(true)` to indicate that the dictionary was just
     ///   inserted.
+    ///
+    /// A dictionary whose values extend those already written is not a replacement, see
+    /// [`Self::insert_column`] to determine the values that need to be written for it.
     pub fn insert(
         &mut self,
         dict_id: i64,
         column: &ArrayRef,
     ) -> Result<bool, ArrowError> {
+        let update = self.insert_column(dict_id, column, DictionaryHandling::Resend)?;
+        Ok(update != DictionaryUpdate::None)
+    }
+
+    /// Keep track of the dictionary with the given ID and values, returning the
+    /// [`DictionaryUpdate`] that needs to be written for it.
+    ///
+    /// If this ID has been written already with different values, a delta is only returned
+    /// with [`DictionaryHandling::Delta`]. An error is returned with
+    /// [`DictionaryHandling::Error`], or if this tracker is configured to error on
+    /// replacement and the dictionary would be written in full.
+    pub fn insert_column(
+        &mut self,
+        dict_id: i64,
+        column: &ArrayRef,
+        handling: DictionaryHandling,
+    ) -> Result<DictionaryUpdate, ArrowError> {
         let dict_data = column.data();
         let dict_values = &dict_data.child_data()[0];
 
-        // If a dictionary with this id was already emitted, check if it was the same.
-        if let Some(last) = self.written.get(&dict_id) {
-            if last.data().child_data()[0] == *dict_values {
-                // Same dictionary values => no need to emit it again
-                return Ok(false);
-            } else if self.error_on_replacement {
-                return Err(ArrowError::InvalidArgumentError(
-                    "Dictionary replacement detected when writing IPC file format. \
-                     Arrow IPC files only support a single dictionary for a given field \
-                     across all batches."
-                        .to_string(),
-                ));
+        // If a dictionary with this id was already emitted, check if it was the same,
+        // or if it has only had values appended to it
+        let update = match self.written.get(&dict_id) {
+            None => DictionaryUpdate::New,
+            Some(last_values) => {
+                if last_values == dict_values {
+                    // Same dictionary values => no need to emit it again
+                    return Ok(DictionaryUpdate::None);
+                }
+
+                let last_len = last_values.len();
+                if handling == DictionaryHandling::Delta
+                    && last_len < dict_values.len()
+                    && dict_values.slice(0, last_len) == *last_values
+                {
+                    let mut delta = MutableArrayData::new(
+                        vec![dict_values],
+                        false,
+                        dict_values.len(),
+                    );
+                    delta.extend(0, last_len, dict_values.len());
+                    DictionaryUpdate::Delta(delta.freeze())
+                } else if handling == DictionaryHandling::Error {
+                    return Err(ArrowError::InvalidArgumentError(format!(
+                        "Dictionary with id {} changed between batches, which is not \
+                         permitted by DictionaryHandling::Error",
+                        dict_id
+                    )));
+                } else if self.error_on_replacement {
+                    return Err(ArrowError::InvalidArgumentError(
+                        "Dictionary replacement detected when writing IPC file format. \
+                         Arrow IPC files only support a single dictionary for a given field \
+                         across all batches, which may only be extended with new values."
+                            .to_string(),
+                    ));
+                } else {
+                    DictionaryUpdate::Replaced
+                }
             }
-        }
+        };
 
-        self.written.insert(dict_id, column.clone());
-        Ok(true)
+        self.written.insert(dict_id, dict_values.clone());
+        Ok(update)
     }
 }
 
@@ -572,6 +840,8 @@ pub struct FileWriter<W: Write> {
     finished: bool,
     /// Keeps track of dictionaries that have been written
     dictionary_tracker: DictionaryTracker,
+    /// User-level custom metadata written as part of the IPC footer
+    custom_metadata: HashMap<String, String>,
 
     data_gen: IpcDataGenerator,
 }
@@ -598,7 +868,9 @@ impl<W: Write> FileWriter<W> {
         writer.write_all(&[0, 0])?;
         // write the schema, set the written bytes to the schema + header
         let encoded_message = data_gen.schema_to_bytes(schema, &write_options);
-        let (meta, data) = write_message(&mut writer, encoded_message, &write_options)?;
+        let (meta, data) =
+            write_message_at(&mut writer, encoded_message, &write_options, header_size)?;
+        let dictionary_tracker = DictionaryTracker::new(true);
         Ok(Self {
             writer,
             write_options,
@@ -607,24 +879,62 @@ impl<W: Write> FileWriter<W> {
             dictionary_blocks: vec![],
             record_blocks: vec![],
             finished: false,
-            dictionary_tracker: DictionaryTracker::new(true),
+            dictionary_tracker,
+            custom_metadata: HashMap::new(),
             data_gen,
         })
     }
 
+    /// Adds a key/value pair to the custom metadata of the file, written in the
+    /// footer by [`Self::finish`]
+    pub fn write_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.custom_metadata.insert(key.into(), value.into());
+    }
+
     /// Write a record batch to the file
     pub fn write(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        self.write_with_metadata(batch, &HashMap::new())
+    }
+
+    /// Write a record batch to the file, with the custom key/value `metadata`
+    /// attached to its message
+    pub fn write_with_metadata(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(), ArrowError> {
         if self.finished {
             return Err(ArrowError::IoError(
                 "Cannot write record batch to file writer as it is closed".to_string(),
             ));
         }
 
-        let (encoded_dictionaries, encoded_message) = self.data_gen.encoded_batch(
-            batch,
-            &mut self.dictionary_tracker,
-            &self.write_options,
-        )?;
+        let schema = batch.schema();
+        if schema.fields().len() != self.schema.fields().len()
+            || schema
+                .fields()
+                .iter()
+                .zip(self.schema.fields())
+                .any(|(a, b)| {
+                    a.name() != b.name()
+                        || a.data_type() != b.data_type()
+                        || a.is_nullable() != b.is_nullable()
+                })
+        {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot write record batch to file writer as its schema does not \
+                 match the schema of the file"
+                    .to_string(),
+            ));
+        }
+
+        let (encoded_dictionaries, encoded_message) =
+            self.data_gen.encoded_batch_with_metadata(
+                batch,
+                metadata,
+                &mut self.dictionary_tracker,
+                &self.write_options,
+            )?;
 
         for encoded_dictionary in encoded_dictionaries {
             let (meta, data) =
@@ -663,7 +973,13 @@ impl<W: Write> FileWriter<W> {
         let mut fbb = FlatBufferBuilder::new();
         let dictionaries = fbb.create_vector(&self.dictionary_blocks);
         let record_batches = fbb.create_vector(&self.record_blocks);
-        let schema = crate::convert::schema_to_fb_offset(&mut fbb, &self.schema);
+        let schema = crate::convert::schema_to_fb_offset_with_endianness(
+            &mut fbb,
+            &self.schema,
+            self.write_options.endianness,
+        );
+        let custom_metadata =
+            crate::convert::metadata_to_fb(&mut fbb, &self.custom_metadata);
 
         let root = {
             let mut footer_builder = crate::FooterBuilder::new(&mut fbb);
@@ -671,10 +987,14 @@ impl<W: Write> FileWriter<W> {
             footer_builder.add_schema(schema);
             footer_builder.add_dictionaries(dictionaries);
             footer_builder.add_recordBatches(record_batches);
+            if let Some(custom_metadata) = custom_metadata {
+                footer_builder.add_custom_metadata(custom_metadata);
+            }
             footer_builder.finish()
         };
         fbb.finish(root, None);
         let footer_data = fbb.finished_data();
+
         self.writer.write_all(footer_data)?;
         self.writer
             .write_all(&(footer_data.len() as i32).to_le_bytes())?;
@@ -698,6 +1018,147 @@ impl<W: Write> FileWriter<W> {
     }
 }
 
+/// A writer of an existing file that can be truncated, allowing
+/// [`FileWriter::try_append`] to remove the footer of the file
+pub trait Truncate {
+    /// Truncates the underlying data to `len` bytes
+    fn truncate(&mut self, len: u64) -> std::io::Result<()>;
+}
+
+impl Truncate for std::fs::File {
+    fn truncate(&mut self, len: u64) -> std::io::Result<()> {
+        self.set_len(len)
+    }
+}
+
+impl Truncate for std::io::Cursor<Vec<u8>> {
+    fn truncate(&mut self, len: u64) -> std::io::Result<()> {
+        let len = usize::try_from(len)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        self.get_mut().truncate(len);
+        Ok(())
+    }
+}
+
+impl<T: Truncate + ?Sized> Truncate for &mut T {
+    fn truncate(&mut self, len: u64) -> std::io::Result<()> {
+        (**self).truncate(len)
+    }
+}
+
+impl<W: Read + Write + Seek + Truncate> FileWriter<W> {
+    /// Try create a writer appending record batches to the existing Arrow file
+    /// in `writer`, see [`Self::try_append_with_options`]
+    pub fn try_append(writer: W) -> Result<Self, ArrowError> {
+        Self::try_append_with_options(writer, IpcWriteOptions::default())
+    }
+
+    /// Try create a writer appending record batches to the existing Arrow file
+    /// in `writer`, with IpcWriteOptions
+    ///
+    /// The schema, dictionaries, blocks and custom metadata of the file are read
+    /// from its footer, which is then truncated from the file. Record batches written
+    /// must have the same schema as the file, and its dictionaries may only be
+    /// extended with new values. A new footer is written by [`Self::finish`].
+    ///
+    /// Returns an error if the file was written with a different metadata version
+    /// or endianness than `write_options`
+    pub fn try_append_with_options(
+        mut writer: W,
+        write_options: IpcWriteOptions,
+    ) -> Result<Self, ArrowError> {
+        let file_len = writer.seek(SeekFrom::End(0))? as usize;
+        writer.seek(SeekFrom::Start(0))?;
+        let reader = crate::reader::FileReader::try_new(&mut writer, None)?;
+
+        if reader.metadata_version() != write_options.metadata_version {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Cannot append to file with metadata version {:?} using metadata version {:?}",
+                reader.metadata_version(),
+                write_options.metadata_version
+            )));
+        }
+        let byte_swap = write_options.endianness != crate::endian::native();
+        if reader.decode_options().byte_swap != byte_swap {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot append to file with a different endianness".to_string(),
+            ));
+        }
+
+        let schema = reader.schema().as_ref().clone();
+        let dictionary_blocks = reader.dictionary_blocks().to_vec();
+        let record_blocks = reader.blocks().to_vec();
+        let custom_metadata = reader.custom_metadata().clone();
+
+        let mut dictionary_tracker = DictionaryTracker::new(true);
+        for (dict_id, values) in reader.dictionaries_by_id() {
+            dictionary_tracker
+                .written
+                .insert(*dict_id, values.data().clone());
+        }
+        drop(reader);
+
+        // resume writing after the last message of the file, which is the
+        // schema if no batches have been written
+        let header_size = super::ARROW_MAGIC.len() + 2;
+        let mut prefix = [0; 4];
+        writer.seek(SeekFrom::Start(header_size as u64))?;
+        writer.read_exact(&mut prefix)?;
+        let mut schema_end = header_size + 4;
+        if prefix == CONTINUATION_MARKER {
+            writer.read_exact(&mut prefix)?;
+            schema_end += 4;
+        }
+        let schema_len = i32::from_le_bytes(prefix);
+        schema_end = usize::try_from(schema_len)
+            .ok()
+            .and_then(|len| schema_end.checked_add(len))
+            .filter(|end| *end <= file_len)
+            .ok_or_else(|| {
+                ArrowError::IoError(format!(
+                    "Invalid schema message length {} in file of {} bytes",
+                    schema_len, file_len
+                ))
+            })?;
+
+        let mut block_offsets = schema_end;
+        for block in dictionary_blocks.iter().chain(&record_blocks) {
+            let end = usize::try_from(block.offset())
+                .ok()
+                .zip(usize::try_from(block.metaDataLength()).ok())
+                .zip(usize::try_from(block.bodyLength()).ok())
+                .and_then(|((offset, meta), body)| {
+                    offset.checked_add(meta)?.checked_add(body)
+                })
+                .filter(|end| *end <= file_len)
+                .ok_or_else(|| {
+                    ArrowError::IoError(format!(
+                        "Invalid block at offset {} in file of {} bytes",
+                        block.offset(),
+                        file_len
+                    ))
+                })?;
+            block_offsets = block_offsets.max(end);
+        }
+        // remove the end of stream marker and footer, to be written by finish
+        writer.truncate(block_offsets as u64)?;
+        writer.seek(SeekFrom::Start(block_offsets as u64))?;
+
+        Ok(Self {
+            writer: BufWriter::new(writer),
+            write_options,
+            schema,
+            block_offsets,
+            dictionary_blocks,
+            record_blocks,
+            finished: false,
+            dictionary_tracker,
+            custom_metadata,
+            data_gen: IpcDataGenerator::default(),
+        })
+    }
+}
+
 pub struct StreamWriter<W: Write> {
     /// The object to write to
     writer: BufWriter<W>,
@@ -728,27 +1189,41 @@ impl<W: Write> StreamWriter<W> {
         // write the schema, set the written bytes to the schema
         let encoded_message = data_gen.schema_to_bytes(schema, &write_options);
         write_message(&mut writer, encoded_message, &write_options)?;
+        let dictionary_tracker = DictionaryTracker::new(false);
         Ok(Self {
             writer,
             write_options,
             finished: false,
-            dictionary_tracker: DictionaryTracker::new(false),
+            dictionary_tracker,
             data_gen,
         })
     }
 
     /// Write a record batch to the stream
     pub fn write(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        self.write_with_metadata(batch, &HashMap::new())
+    }
+
+    /// Write a record batch to the stream, with the custom key/value `metadata`
+    /// attached to its message
+    pub fn write_with_metadata(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(), ArrowError> {
         if self.finished {
             return Err(ArrowError::IoError(
                 "Cannot write record batch to stream writer as it is closed".to_string(),
             ));
         }
 
-        let (encoded_dictionaries, encoded_message) = self
-            .data_gen
-            .encoded_batch(batch, &mut self.dictionary_tracker, &self.write_options)
-            .expect("StreamWriter is configured to not error on dictionary replacement");
+        let (encoded_dictionaries, encoded_message) =
+            self.data_gen.encoded_batch_with_metadata(
+                batch,
+                metadata,
+                &mut self.dictionary_tracker,
+                &self.write_options,
+            )?;
 
         for encoded_dictionary in encoded_dictionaries {
             write_message(&mut self.writer, encoded_dictionary, &self.write_options)?;
@@ -829,9 +1304,20 @@ pub struct EncodedData {
 }
 /// Write a message's IPC data and buffers, returning metadata and buffer data lengths written
 pub fn write_message<W: Write>(
+    writer: W,
+    encoded: EncodedData,
+    write_options: &IpcWriteOptions,
+) -> Result<(usize, usize), ArrowError> {
+    write_message_at(writer, encoded, write_options, 0)
+}
+
+/// Write a message as [`write_message`], starting at `offset` bytes from an
+/// aligned position, padding its metadata such that its body is aligned
+fn write_message_at<W: Write>(
     mut writer: W,
     encoded: EncodedData,
     write_options: &IpcWriteOptions,
+    offset: usize,
 ) -> Result<(usize, usize), ArrowError> {
     let arrow_data_len = encoded.arrow_data.len();
     if arrow_data_len % 8 != 0 {
@@ -840,6 +1326,8 @@ pub fn write_message<W: Write>(
         ));
     }
 
+    // only the offset from a position to which buffers are aligned is significant
+    let offset = offset % write_options.buffer_alignment;
     let a = write_options.alignment - 1;
     let buffer = encoded.ipc_message;
     let flatbuf_size = buffer.len();
@@ -848,7 +1336,7 @@ pub fn write_message<W: Write>(
     } else {
         8
     };
-    let aligned_size = (flatbuf_size + prefix_size + a) & !a;
+    let aligned_size = ((offset + flatbuf_size + prefix_size + a) & !a) - offset;
     let padding_bytes = aligned_size - flatbuf_size - prefix_size;
 
     write_continuation(
@@ -891,7 +1379,7 @@ fn write_body_buffers<W: Write>(mut writer: W, data: &[u8]) -> Result<usize, Arr
 
 /// Write a record batch to the writer, writing the message size before the message
 /// if the record batch is being written to a stream
-fn write_continuation<W: Write>(
+pub(crate) fn write_continuation<W: Write>(
     mut writer: W,
     write_options: &IpcWriteOptions,
     total_len: i32,
@@ -1086,6 +1574,8 @@ fn write_array_data(
             arrow_data,
             offset,
             compression_codec,
+            write_options.buffer_alignment,
+            &[],
         )?;
     }
 
@@ -1125,6 +1615,8 @@ fn write_array_data(
                 arrow_data,
                 offset,
                 compression_codec,
+                write_options.buffer_alignment,
+                byte_widths(data_type, 0, write_options),
             )?;
 
             let total_bytes = get_binary_buffer_len(array_data);
@@ -1138,15 +1630,19 @@ fn write_array_data(
                 arrow_data,
                 offset,
                 compression_codec,
+                write_options.buffer_alignment,
+                &[],
             )?;
         } else {
-            for buffer in array_data.buffers() {
+            for (i, buffer) in array_data.buffers().iter().enumerate() {
                 offset = write_buffer(
                     buffer.as_slice(),
                     buffers,
                     arrow_data,
                     offset,
                     compression_codec,
+                    write_options.buffer_alignment,
+                    byte_widths(data_type, i, write_options),
                 )?;
             }
         }
@@ -1177,6 +1673,8 @@ fn write_array_data(
                 arrow_data,
                 offset,
                 compression_codec,
+                write_options.buffer_alignment,
+                byte_widths(data_type, 0, write_options),
             )?;
         } else {
             offset = write_buffer(
@@ -1185,12 +1683,21 @@ fn write_array_data(
                 arrow_data,
                 offset,
                 compression_codec,
+                write_options.buffer_alignment,
+                byte_widths(data_type, 0, write_options),
             )?;
         }
     } else {
-        for buffer in array_data.buffers() {
-            offset =
-                write_buffer(buffer, buffers, arrow_data, offset, compression_codec)?;
+        for (i, buffer) in array_data.buffers().iter().enumerate() {
+            offset = write_buffer(
+                buffer,
+                buffers,
+                arrow_data,
+                offset,
+                compression_codec,
+                write_options.buffer_alignment,
+                byte_widths(data_type, i, write_options),
+            )?;
         }
     }
 
@@ -1215,6 +1722,20 @@ fn write_array_data(
     Ok(offset)
 }
 
+/// Returns the widths of the values of the `index`th buffer of an array of `data_type`,
+/// excluding the validity buffer, to byte swap when writing with `write_options`,
+/// or an empty slice if the buffer is written unchanged
+fn byte_widths(
+    data_type: &DataType,
+    index: usize,
+    write_options: &IpcWriteOptions,
+) -> &'static [usize] {
+    match write_options.endianness == crate::endian::native() {
+        true => &[],
+        false => crate::endian::buffer_widths(data_type, index),
+    }
+}
+
 /// Write a buffer into `arrow_data`, a vector of bytes, and adds its
 /// [`crate::Buffer`] to `buffers`. Returns the new offset in `arrow_data`
 ///
@@ -1233,7 +1754,18 @@ fn write_buffer(
     arrow_data: &mut Vec<u8>,         // output stream
     offset: i64,                      // current output stream offset
     compression_codec: Option<CompressionCodec>,
+    alignment: usize,
+    widths: &[usize], // widths of the values to byte swap, if any
 ) -> Result<i64, ArrowError> {
+    let swapped;
+    let buffer = match widths.is_empty() {
+        true => buffer,
+        false => {
+            swapped = crate::endian::byte_swap(buffer, widths);
+            swapped.as_slice()
+        }
+    };
+
     let len: i64 = match compression_codec {
         Some(compressor) => compressor.compress_to_vec(buffer, arrow_data)?,
         None => {
@@ -1251,8 +1783,8 @@ fn write_buffer(
 
     // make new index entry
     buffers.push(crate::Buffer::new(offset, len));
-    // padding and make offset 8 bytes aligned
-    let pad_len = pad_to_8(len as u32) as i64;
+    // padding and make offset aligned
+    let pad_len = pad_to_alignment(alignment, len as usize) as i64;
     arrow_data.extend_from_slice(&vec![0u8; pad_len as usize][..]);
 
     Ok(offset + len + pad_len)
@@ -1264,6 +1796,14 @@ fn pad_to_8(len: u32) -> usize {
     (((len + 7) & !7) - len) as usize
 }
 
+/// Return the number of bytes needed to pad `len` to a multiple of `alignment`,
+/// which must be a power of two
+#[inline]
+fn pad_to_alignment(alignment: usize, len: usize) -> usize {
+    let a = alignment - 1;
+    ((len + a) & !a) - len
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1274,7 +1814,7 @@ mod tests {
     use crate::MetadataVersion;
 
     use crate::reader::*;
-    use arrow_array::builder::UnionBuilder;
+    use arrow_array::builder::{Int16Builder, ListBuilder, UnionBuilder};
     use arrow_array::types::*;
     use arrow_schema::DataType;
 
@@ -1331,6 +1871,82 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "lz4", feature = "zstd"))]
+    fn test_write_compressed_buffers() {
+        // Pseudo-random values that do not compress, followed by values that do
+        let mut state = 0x2545F4914F6CDD1D_u64;
+        let incompressible: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 56) as u8
+            })
+            .collect();
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::UInt8, false),
+            Field::new("b", DataType::UInt8, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(UInt8Array::from(incompressible.clone())),
+                Arc::new(UInt8Array::from(vec![0_u8; 4096])),
+            ],
+        )
+        .unwrap();
+
+        for compression in [
+            crate::CompressionType::LZ4_FRAME,
+            crate::CompressionType::ZSTD,
+        ] {
+            let options = IpcWriteOptions::default()
+                .try_with_compression(Some(compression))
+                .unwrap();
+            let (_, encoded) = IpcDataGenerator::default()
+                .encoded_batch(&batch, &mut DictionaryTracker::new(false), &options)
+                .unwrap();
+
+            let message = crate::root_as_message(&encoded.ipc_message).unwrap();
+            let record_batch = message.header_as_record_batch().unwrap();
+            let compression_info = record_batch.compression().unwrap();
+            assert_eq!(compression_info.codec(), compression);
+
+            // validity and values buffers of each column
+            let buffers = record_batch.buffers().unwrap();
+            assert_eq!(buffers.len(), 4);
+
+            // The incompressible values are written uncompressed with a length of -1
+            let a = buffers.get(1);
+            assert_eq!(a.length(), 8 + 4096);
+            let data = &encoded.arrow_data[a.offset() as usize..];
+            assert_eq!(i64::from_le_bytes(data[..8].try_into().unwrap()), -1);
+            assert_eq!(&data[8..8 + 4096], incompressible.as_slice());
+
+            // The compressible values are compressed, prefixed by their length
+            let b = buffers.get(3);
+            assert!(b.length() < 512, "{}", b.length());
+            let data = &encoded.arrow_data[b.offset() as usize..];
+            assert_eq!(i64::from_le_bytes(data[..8].try_into().unwrap()), 4096);
+
+            // Roundtrip through the stream reader
+            let mut stream = vec![];
+            {
+                let mut writer = StreamWriter::try_new_with_options(
+                    &mut stream,
+                    &batch.schema(),
+                    options,
+                )
+                .unwrap();
+                writer.write(&batch).unwrap();
+                writer.finish().unwrap();
+            }
+            let mut reader = StreamReader::try_new(stream.as_slice(), None).unwrap();
+            assert_eq!(reader.next().unwrap().unwrap(), batch);
+        }
+    }
+
     #[test]
     #[cfg(feature = "lz4")]
     fn test_write_file_with_lz4_compression() {
@@ -1609,6 +2225,552 @@ mod tests {
         assert!(dict_tracker.written.contains_key(&2));
     }
 
+    fn delta_dictionary_batches() -> Vec<RecordBatch> {
+        let schema = Arc::new(Schema::new(vec![Field::new_dict(
+            "dict",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            true,
+            1,
+            false,
+        )]));
+        let batch = |keys: Vec<Option<i32>>, values: Vec<&str>| {
+            let keys = Int32Array::from(keys);
+            let values = StringArray::from(values);
+            let dict = DictionaryArray::try_new(&keys, &values).unwrap();
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(dict)]).unwrap()
+        };
+        vec![
+            batch(vec![Some(0), Some(1), None], vec!["a", "b"]),
+            batch(vec![Some(1), Some(0)], vec!["a", "b"]),
+            batch(vec![Some(2), None, Some(0)], vec!["a", "b", "c"]),
+            batch(vec![Some(3), Some(1)], vec!["a", "b", "c", "d"]),
+        ]
+    }
+
+    #[test]
+    fn test_dictionary_tracker_delta() {
+        let batches = delta_dictionary_batches();
+        let gen = IpcDataGenerator {};
+        let mut dict_tracker = DictionaryTracker::new(true);
+
+        let deltas: Vec<_> = batches
+            .iter()
+            .map(|batch| {
+                let (dictionaries, _) = gen
+                    .encoded_batch(batch, &mut dict_tracker, &Default::default())
+                    .unwrap();
+                dictionaries
+                    .iter()
+                    .map(|d| {
+                        let message = crate::root_as_message(&d.ipc_message).unwrap();
+                        let dictionary = message.header_as_dictionary_batch().unwrap();
+                        (dictionary.isDelta(), dictionary.data().unwrap().length())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![vec![(false, 2)], vec![], vec![(true, 1)], vec![(true, 1)]]
+        );
+
+        // Replacing the dictionary is an error if configured
+        let replaced = &delta_dictionary_batches()[0];
+        let dict = replaced.column(0).clone();
+        let values = StringArray::from(vec!["x", "y"]);
+        let replaced = DictionaryArray::<Int32Type>::try_new(
+            as_dictionary_array::<Int32Type>(&dict).keys(),
+            &values,
+        )
+        .unwrap();
+        let replaced = Arc::new(replaced) as ArrayRef;
+        let err = dict_tracker
+            .insert_column(1, &replaced, DictionaryHandling::Delta)
+            .unwrap_err();
+        assert!(err.to_string().contains("Dictionary replacement detected"));
+
+        let mut dict_tracker = DictionaryTracker::new(false);
+        assert_eq!(
+            dict_tracker
+                .insert_column(1, &dict, DictionaryHandling::Delta)
+                .unwrap(),
+            DictionaryUpdate::New
+        );
+        assert_eq!(
+            dict_tracker
+                .insert_column(1, &replaced, DictionaryHandling::Delta)
+                .unwrap(),
+            DictionaryUpdate::Replaced
+        );
+    }
+
+    #[test]
+    fn test_write_delta_dictionaries() {
+        let batches = delta_dictionary_batches();
+        let schema = batches[0].schema();
+
+        let mut stream = vec![];
+        {
+            let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        let reader = StreamReader::try_new(std::io::Cursor::new(stream), None).unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, batches);
+
+        let mut file = tempfile::tempfile().unwrap();
+        {
+            let mut writer = FileWriter::try_new(&mut file, &schema).unwrap();
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        file.rewind().unwrap();
+        let reader = FileReader::try_new(file, None).unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+
+        // The file reader reads every dictionary before the first batch, and
+        // so the batches share the final dictionary
+        assert_eq!(read.len(), batches.len());
+        for (read, batch) in read.iter().zip(&batches) {
+            let read = as_dictionary_array::<Int32Type>(read.column(0));
+            let expected = as_dictionary_array::<Int32Type>(batch.column(0));
+            assert_eq!(read.keys(), expected.keys());
+            assert_eq!(read.values().len(), 4);
+        }
+    }
+
+    #[test]
+    fn test_write_dictionary_handling() {
+        let batches = delta_dictionary_batches();
+        let schema = batches[0].schema();
+        let keys = Int32Array::from(vec![Some(1), Some(0)]);
+        let values = StringArray::from(vec!["x", "y"]);
+        let replaced = DictionaryArray::<Int32Type>::try_new(&keys, &values).unwrap();
+        let replaced =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(replaced)]).unwrap();
+        let mut expected = batches.clone();
+        expected.push(replaced);
+
+        let write_stream = |handling| {
+            let options = IpcWriteOptions::default().with_dictionary_handling(handling);
+            assert_eq!(options.dictionary_handling(), handling);
+            let mut writer =
+                StreamWriter::try_new_with_options(vec![], &schema, options).unwrap();
+            for batch in &expected {
+                writer.write(batch)?;
+            }
+            writer.into_inner()
+        };
+
+        for handling in [DictionaryHandling::Delta, DictionaryHandling::Resend] {
+            let stream = write_stream(handling).unwrap();
+            let reader = StreamReader::try_new(stream.as_slice(), None).unwrap();
+            let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+            assert_eq!(read, expected);
+        }
+        let err = write_stream(DictionaryHandling::Error).unwrap_err();
+        assert!(
+            err.to_string().contains("changed between batches"),
+            "{}",
+            err
+        );
+
+        // Dictionaries are always written in full with DictionaryHandling::Resend
+        let gen = IpcDataGenerator {};
+        let mut dict_tracker = DictionaryTracker::new(false);
+        let options = IpcWriteOptions::default()
+            .with_dictionary_handling(DictionaryHandling::Resend);
+        let deltas: Vec<_> = expected
+            .iter()
+            .flat_map(|batch| {
+                let (dictionaries, _) = gen
+                    .encoded_batch(batch, &mut dict_tracker, &options)
+                    .unwrap();
+                dictionaries.into_iter().map(|d| {
+                    let message = crate::root_as_message(&d.ipc_message).unwrap();
+                    let dictionary = message.header_as_dictionary_batch().unwrap();
+                    (dictionary.isDelta(), dictionary.data().unwrap().length())
+                })
+            })
+            .collect();
+        assert_eq!(deltas, vec![(false, 2), (false, 3), (false, 4), (false, 2)]);
+
+        // A dictionary resent in full is a replacement in the file format
+        let options = IpcWriteOptions::default()
+            .with_dictionary_handling(DictionaryHandling::Resend);
+        let mut writer =
+            FileWriter::try_new_with_options(vec![], &schema, options).unwrap();
+        writer.write(&batches[0]).unwrap();
+        writer.write(&batches[1]).unwrap();
+        let err = writer.write(&batches[2]).unwrap_err();
+        assert!(err.to_string().contains("replacement"), "{}", err);
+    }
+
+    #[test]
+    fn test_file_append() {
+        let batches = delta_dictionary_batches();
+        let schema = batches[0].schema();
+
+        let mut writer = FileWriter::try_new(vec![], &schema).unwrap();
+        writer.write_metadata("key", "value");
+        writer.write(&batches[0]).unwrap();
+        writer.write(&batches[1]).unwrap();
+        let file = std::io::Cursor::new(writer.into_inner().unwrap());
+
+        let mut writer = FileWriter::try_append(file).unwrap();
+        writer.write(&batches[2]).unwrap();
+        writer.write(&batches[3]).unwrap();
+        let file = writer.into_inner().unwrap().into_inner();
+
+        let reader = FileReader::try_new(std::io::Cursor::new(file), None).unwrap();
+        assert_eq!(reader.num_batches(), 4);
+        assert_eq!(reader.dictionary_blocks().len(), 3);
+        assert_eq!(reader.custom_metadata().get("key").unwrap(), "value");
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, batches);
+
+        // Appending to a file without batches resumes after the schema
+        let writer = FileWriter::try_new(vec![], &schema).unwrap();
+        let file = std::io::Cursor::new(writer.into_inner().unwrap());
+        let mut writer = FileWriter::try_append(file).unwrap();
+        writer.write(&batches[0]).unwrap();
+        let file = writer.into_inner().unwrap().into_inner();
+        let reader = FileReader::try_new(std::io::Cursor::new(file), None).unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, batches[..1]);
+
+        // Batches must match the schema of the file
+        let file = std::io::Cursor::new(
+            FileWriter::try_new(vec![], &schema)
+                .unwrap()
+                .into_inner()
+                .unwrap(),
+        );
+        let mut writer = FileWriter::try_append(file).unwrap();
+        let other = RecordBatch::try_from_iter(vec![(
+            "dict",
+            Arc::new(Int32Array::from(vec![1])) as ArrayRef,
+        )])
+        .unwrap();
+        let err = writer.write(&other).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{}", err);
+
+        let renamed = Schema::new(vec![schema.field(0).clone().with_name("other")]);
+        let renamed =
+            RecordBatch::try_new(Arc::new(renamed), batches[0].columns().to_vec())
+                .unwrap();
+        let err = writer.write(&renamed).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{}", err);
+
+        let options = IpcWriteOptions::try_new(8, false, MetadataVersion::V4).unwrap();
+        let err =
+            FileWriter::try_append_with_options(writer.into_inner().unwrap(), options)
+                .err()
+                .unwrap();
+        assert!(err.to_string().contains("metadata version"), "{}", err);
+
+        // The previous footer is truncated, rather than padded over
+        let mut writer = FileWriter::try_new(vec![], &schema).unwrap();
+        writer.write(&batches[0]).unwrap();
+        let original = writer.into_inner().unwrap();
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&original).unwrap();
+        FileWriter::try_append(&mut file).unwrap().finish().unwrap();
+        let mut appended = vec![];
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.read_to_end(&mut appended).unwrap();
+        assert_eq!(appended, original);
+
+        // The length of the schema message is validated against the file
+        let mut corrupt = original.clone();
+        corrupt[12..16].copy_from_slice(&(-8_i32).to_le_bytes());
+        let err = FileWriter::try_append(std::io::Cursor::new(corrupt))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("Invalid schema message length -8"));
+    }
+
+    #[test]
+    fn test_custom_metadata_roundtrip() {
+        let metadata = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        let item = Field::new("item", DataType::Int32, true)
+            .with_metadata(metadata(&[("item", "1"), ("empty", "")]));
+        let child = Field::new("child", DataType::List(Box::new(item)), true)
+            .with_metadata(metadata(&[("child", "2")]));
+        let field = Field::new("struct", DataType::Struct(vec![child.clone()]), true)
+            .with_metadata(metadata(&[("struct", "3")]));
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![field],
+            metadata(&[("schema", "4"), ("b", "5"), ("a", "6")]),
+        ));
+
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), None]),
+            None,
+        ]);
+        let list = make_array(
+            list.into_data()
+                .into_builder()
+                .data_type(child.data_type().clone())
+                .build()
+                .unwrap(),
+        );
+        let array = StructArray::from(vec![(child, list)]);
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+
+        let batch_metadata = vec![
+            metadata(&[("batch", "0")]),
+            metadata(&[]),
+            metadata(&[("batch", "2"), ("extra", "x")]),
+        ];
+
+        let mut file = vec![];
+        {
+            let mut writer = FileWriter::try_new(&mut file, &schema).unwrap();
+            writer.write_metadata("file", "1");
+            writer.write_metadata(String::from("file"), "2");
+            for metadata in &batch_metadata {
+                writer.write_with_metadata(&batch, metadata).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        let mut reader = FileReader::try_new(std::io::Cursor::new(file), None).unwrap();
+        assert_eq!(reader.schema(), schema);
+        assert_eq!(reader.custom_metadata(), &metadata(&[("file", "2")]));
+        assert!(reader.batch_metadata().is_empty());
+        for metadata in &batch_metadata {
+            let read = reader.next().unwrap().unwrap();
+            assert_eq!(read, batch);
+            assert_eq!(read.schema(), schema);
+            assert_eq!(reader.batch_metadata(), metadata);
+        }
+
+        let mut stream = vec![];
+        {
+            let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+            for metadata in &batch_metadata {
+                writer.write_with_metadata(&batch, metadata).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        let mut reader = StreamReader::try_new(stream.as_slice(), None).unwrap();
+        assert_eq!(reader.schema(), schema);
+        for metadata in &batch_metadata {
+            assert_eq!(reader.next().unwrap().unwrap(), batch);
+            assert_eq!(reader.batch_metadata(), metadata);
+        }
+
+        let mut decoder = StreamDecoder::new();
+        let mut buffer = Buffer::from(stream);
+        for metadata in &batch_metadata {
+            assert_eq!(decoder.decode(&mut buffer).unwrap().unwrap(), batch);
+            assert_eq!(decoder.batch_metadata(), metadata);
+        }
+    }
+
+    #[test]
+    fn test_write_alignment() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int8, true),
+            Field::new("b", DataType::Utf8, false),
+            Field::new("c", DataType::Float64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int8Array::from(vec![Some(1), None, Some(3)])),
+                Arc::new(StringArray::from(vec!["a", "bb", "ccc"])),
+                Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0])),
+            ],
+        )
+        .unwrap();
+
+        for alignment in [8, 16, 64] {
+            let options = IpcWriteOptions::default()
+                .try_with_alignment(alignment)
+                .unwrap();
+            assert_eq!(options.alignment(), alignment);
+            assert_eq!(options.buffer_alignment(), alignment);
+
+            let mut file = vec![];
+            {
+                let mut writer =
+                    FileWriter::try_new_with_options(&mut file, &schema, options)
+                        .unwrap();
+                writer.write(&batch).unwrap();
+                writer.write(&batch).unwrap();
+                writer.finish().unwrap();
+            }
+
+            let mut reader =
+                FileReader::try_new(std::io::Cursor::new(file.clone()), None).unwrap();
+            for block in reader.blocks() {
+                let body_start =
+                    (block.offset() + block.metaDataLength() as i64) as usize;
+                assert_eq!(body_start % alignment, 0);
+                assert_eq!(block.bodyLength() as usize % alignment, 0);
+
+                let mut meta = &file[block.offset() as usize..body_start];
+                if meta.starts_with(&CONTINUATION_MARKER) {
+                    meta = &meta[4..];
+                }
+                let message = crate::root_as_message(&meta[4..]).unwrap();
+                let buffers =
+                    message.header_as_record_batch().unwrap().buffers().unwrap();
+                for buffer in buffers {
+                    assert_eq!(buffer.offset() as usize % alignment, 0);
+                }
+            }
+            assert_eq!(reader.next().unwrap().unwrap(), batch);
+        }
+
+        // Buffers are aligned to 8 bytes unless configured
+        let options = IpcWriteOptions::default();
+        assert_eq!((options.alignment(), options.buffer_alignment()), (64, 8));
+
+        let err = IpcWriteOptions::default()
+            .try_with_alignment(24)
+            .unwrap_err();
+        assert!(err.to_string().contains("power of two"), "{}", err);
+        IpcWriteOptions::default()
+            .try_with_alignment(4)
+            .unwrap_err();
+        IpcWriteOptions::default()
+            .try_with_legacy_ipc_format(true)
+            .unwrap_err();
+
+        let options = IpcWriteOptions::default()
+            .try_with_metadata_version(MetadataVersion::V4)
+            .unwrap()
+            .try_with_legacy_ipc_format(true)
+            .unwrap();
+        assert_eq!(options.metadata_version(), MetadataVersion::V4);
+        assert!(options.write_legacy_ipc_format());
+        options
+            .try_with_metadata_version(MetadataVersion::V5)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_write_big_endian() {
+        let dictionary: DictionaryArray<Int16Type> =
+            vec![Some("a"), None, Some("b"), Some("a")]
+                .into_iter()
+                .collect();
+        let mut list = ListBuilder::new(Int16Builder::new());
+        list.values().append_slice(&[1, -2]);
+        list.append(true);
+        list.append(false);
+        list.values().append_slice(&[300]);
+        list.append(true);
+        list.append(true);
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "i32",
+                Arc::new(Int32Array::from(vec![
+                    Some(0x01020304),
+                    None,
+                    Some(-1),
+                    Some(7),
+                ])) as ArrayRef,
+            ),
+            (
+                "f64",
+                Arc::new(Float64Array::from(vec![1.5, -2.0, f64::MAX, 0.0])),
+            ),
+            (
+                "utf8",
+                Arc::new(StringArray::from(vec!["a", "bb", "", "dddd"])),
+            ),
+            (
+                "large_binary",
+                Arc::new(LargeBinaryArray::from(vec![
+                    b"a".as_ref(),
+                    b"",
+                    b"ccc",
+                    b"dd",
+                ])),
+            ),
+            ("list", Arc::new(list.finish())),
+            ("dictionary", Arc::new(dictionary)),
+            (
+                "decimal",
+                Arc::new(
+                    Decimal128Array::from(vec![1, -1, i128::MAX, 0])
+                        .with_precision_and_scale(38, 2)
+                        .unwrap(),
+                ),
+            ),
+            (
+                "interval",
+                Arc::new(IntervalMonthDayNanoArray::from(vec![
+                    IntervalMonthDayNanoType::make_value(1, 2, 3),
+                    IntervalMonthDayNanoType::make_value(-1, 0, i64::MAX),
+                    IntervalMonthDayNanoType::make_value(0, -5, 0),
+                    IntervalMonthDayNanoType::make_value(12, 31, -1),
+                ])),
+            ),
+        ])
+        .unwrap();
+
+        let write = |endianness| {
+            let options = IpcWriteOptions::default().with_endianness(endianness);
+            assert_eq!(options.endianness(), endianness);
+            let mut file = vec![];
+            let mut writer = FileWriter::try_new_with_options(
+                &mut file,
+                &batch.schema(),
+                options.clone(),
+            )
+            .unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+            drop(writer);
+
+            let mut stream = vec![];
+            let mut writer =
+                StreamWriter::try_new_with_options(&mut stream, &batch.schema(), options)
+                    .unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+            drop(writer);
+            (file, stream)
+        };
+
+        let contains = |data: &[u8], bytes: &[u8]| data.windows(4).any(|w| w == bytes);
+        let (little_file, _) = write(crate::Endianness::Little);
+        let (big_file, big_stream) = write(crate::Endianness::Big);
+        assert!(contains(&little_file, &[4, 3, 2, 1]));
+        assert!(!contains(&little_file, &[1, 2, 3, 4]));
+        assert!(contains(&big_file, &[1, 2, 3, 4]));
+        assert!(!contains(&big_file, &[4, 3, 2, 1]));
+
+        let reader = FileReader::try_new(std::io::Cursor::new(big_file), None).unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, vec![batch.clone()]);
+
+        let reader = StreamReader::try_new(big_stream.as_slice(), None).unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, vec![batch.clone()]);
+
+        let mut decoder = StreamDecoder::new();
+        let mut buffer = Buffer::from(big_stream);
+        let read = decoder.decode(&mut buffer).unwrap().unwrap();
+        assert_eq!(read, batch);
+    }
+
     fn write_union_file(options: IpcWriteOptions) {
         let schema = Schema::new(vec![Field::new(
             "union",
@@ -0,0 +1,927 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encoders that write the values of arrow arrays directly as JSON text,
+//! without first converting them to [`serde_json::Value`], or convert them
+//! directly to [`serde_json::Value`] without writing them as JSON text
+
+use std::fmt::Write;
+
+use arrow_array::timezone::Tz;
+use arrow_array::types::*;
+use arrow_array::*;
+use arrow_schema::{ArrowError, DataType, TimeUnit};
+use chrono::{SecondsFormat, TimeZone, Timelike};
+use serde_json::ser::Formatter;
+use serde_json::{map::Map as JsonMap, Value};
+
+use crate::writer::{EncoderOptions, TemporalFormat};
+use crate::{BinaryEncoding, JsonSerializable};
+
+/// Writes the values of an array as JSON text, using the [`Formatter`] `F` to
+/// write any structural tokens such as brackets and separators
+pub(crate) trait Encoder<F: Formatter> {
+    /// Returns true if the value at `idx` is null, in which case it is omitted
+    /// from objects and written as `null` within lists
+    fn is_null(&self, idx: usize) -> bool;
+
+    /// Writes the value at `idx`, which must not be null, to `out`
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError>;
+
+    /// Returns the value at `idx`, which must not be null, as a [`Value`]
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError>;
+}
+
+type BoxEncoder<F> = Box<dyn Encoder<F>>;
+
+/// Writes `value` to `out` as JSON text, scalar values are formatted identically
+/// regardless of the [`Formatter`] in use
+fn write_json<T: serde::Serialize + ?Sized>(
+    out: &mut Vec<u8>,
+    value: &T,
+) -> Result<(), ArrowError> {
+    serde_json::to_writer(out, value).map_err(|e| ArrowError::JsonError(e.to_string()))
+}
+
+/// Writes the JSON `null` literal to `out`
+fn write_null<F: Formatter>(f: &mut F, out: &mut Vec<u8>) -> Result<(), ArrowError> {
+    f.write_null(out)?;
+    Ok(())
+}
+
+/// Returns an [`Encoder`] for the values of `array`
+pub(crate) fn make_encoder<F: Formatter + 'static>(
+    array: &dyn Array,
+    options: &EncoderOptions,
+) -> Result<BoxEncoder<F>, ArrowError> {
+    let data = array.data().clone();
+    let encoder: BoxEncoder<F> = match array.data_type() {
+        DataType::Null => Box::new(NullEncoder),
+        DataType::Boolean => Box::new(BooleanEncoder(BooleanArray::from(data))),
+        DataType::Int8 => Box::new(PrimitiveEncoder::<Int8Type>::new(data)),
+        DataType::Int16 => Box::new(PrimitiveEncoder::<Int16Type>::new(data)),
+        DataType::Int32 => Box::new(PrimitiveEncoder::<Int32Type>::new(data)),
+        DataType::Int64 => Box::new(PrimitiveEncoder::<Int64Type>::new(data)),
+        DataType::UInt8 => Box::new(PrimitiveEncoder::<UInt8Type>::new(data)),
+        DataType::UInt16 => Box::new(PrimitiveEncoder::<UInt16Type>::new(data)),
+        DataType::UInt32 => Box::new(PrimitiveEncoder::<UInt32Type>::new(data)),
+        DataType::UInt64 => Box::new(PrimitiveEncoder::<UInt64Type>::new(data)),
+        DataType::Float16 => Box::new(PrimitiveEncoder::<Float16Type>::new(data)),
+        DataType::Float32 => Box::new(PrimitiveEncoder::<Float32Type>::new(data)),
+        DataType::Float64 => Box::new(PrimitiveEncoder::<Float64Type>::new(data)),
+        DataType::Decimal128(_, _) => {
+            Box::new(DecimalEncoder::<Decimal128Type>(data.into()))
+        }
+        DataType::Decimal256(_, _) => {
+            Box::new(DecimalEncoder::<Decimal256Type>(data.into()))
+        }
+        DataType::Utf8 => Box::new(StringEncoder::<i32>(data.into())),
+        DataType::LargeUtf8 => Box::new(StringEncoder::<i64>(data.into())),
+        DataType::Binary => Box::new(BinaryEncoder {
+            array: BinaryArray::from(data),
+            encoding: options.binary_encoding,
+        }),
+        DataType::LargeBinary => Box::new(BinaryEncoder {
+            array: LargeBinaryArray::from(data),
+            encoding: options.binary_encoding,
+        }),
+        DataType::FixedSizeBinary(_) => Box::new(BinaryEncoder {
+            array: FixedSizeBinaryArray::from(data),
+            encoding: options.binary_encoding,
+        }),
+        DataType::Date32
+        | DataType::Date64
+        | DataType::Timestamp(_, _)
+        | DataType::Time32(_)
+        | DataType::Time64(_)
+        | DataType::Duration(_) => {
+            Box::new(TemporalEncoder(make_temporal(array, options)?))
+        }
+        DataType::List(_) => {
+            let array = ListArray::from(data);
+            let values = make_encoder(array.values().as_ref(), options)?;
+            Box::new(ListEncoder { array, values })
+        }
+        DataType::LargeList(_) => {
+            let array = LargeListArray::from(data);
+            let values = make_encoder(array.values().as_ref(), options)?;
+            Box::new(ListEncoder { array, values })
+        }
+        DataType::FixedSizeList(_, _) => {
+            let array = FixedSizeListArray::from(data);
+            let values = make_encoder(array.values().as_ref(), options)?;
+            Box::new(FixedSizeListEncoder { array, values })
+        }
+        DataType::Struct(_) => {
+            let array = StructArray::from(data);
+            let fields = array
+                .column_names()
+                .into_iter()
+                .zip(array.columns())
+                .map(|(name, column)| {
+                    Ok((name.to_string(), make_encoder(column.as_ref(), options)?))
+                })
+                .collect::<Result<_, ArrowError>>()?;
+            Box::new(StructEncoder { fields })
+        }
+        DataType::Map(_, _) => {
+            let array = MapArray::from(data);
+            let keys = make_encoder(array.keys().as_ref(), options)?;
+            let values = make_encoder(array.values().as_ref(), options)?;
+            Box::new(MapEncoder {
+                array,
+                keys,
+                values,
+                entries: options.map_entries,
+                key_buffer: vec![],
+            })
+        }
+        DataType::Union(fields, _, _) => {
+            let array = UnionArray::from(data);
+            let children = (0..fields.len())
+                .map(|i| make_encoder(array.child(i as i8).as_ref(), options))
+                .collect::<Result<_, ArrowError>>()?;
+            Box::new(UnionEncoder { array, children })
+        }
+        DataType::Dictionary(_, value_type) => {
+            let hydrated = arrow_cast::cast::cast(&make_array(data), value_type)?;
+            make_encoder(hydrated.as_ref(), options)?
+        }
+        t => {
+            return Err(ArrowError::JsonError(format!(
+                "data type {:?} not supported in json writer",
+                t
+            )))
+        }
+    };
+    Ok(encoder)
+}
+
+/// Encodes the rows of a [`RecordBatch`] as JSON objects
+pub(crate) fn make_row_encoder<F: Formatter + 'static>(
+    batch: &RecordBatch,
+    options: &EncoderOptions,
+) -> Result<StructEncoder<F>, ArrowError> {
+    let schema = batch.schema();
+    let fields = schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| {
+            Ok((
+                field.name().clone(),
+                make_encoder(column.as_ref(), options)?,
+            ))
+        })
+        .collect::<Result<_, ArrowError>>()?;
+    Ok(StructEncoder { fields })
+}
+
+struct NullEncoder;
+
+impl<F: Formatter> Encoder<F> for NullEncoder {
+    fn is_null(&self, _idx: usize) -> bool {
+        true
+    }
+
+    fn encode(
+        &mut self,
+        _: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        write_null(f, out)
+    }
+
+    fn to_value(&self, _: usize) -> Result<Value, ArrowError> {
+        Ok(Value::Null)
+    }
+}
+
+struct BooleanEncoder(BooleanArray);
+
+impl<F: Formatter> Encoder<F> for BooleanEncoder {
+    fn is_null(&self, idx: usize) -> bool {
+        self.0.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        f.write_bool(out, self.0.value(idx))?;
+        Ok(())
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        Ok(self.0.value(idx).into())
+    }
+}
+
+struct PrimitiveEncoder<T: ArrowPrimitiveType>(PrimitiveArray<T>);
+
+impl<T: ArrowPrimitiveType> PrimitiveEncoder<T> {
+    fn new(data: arrow_data::ArrayData) -> Self {
+        Self(data.into())
+    }
+}
+
+impl<T, F> Encoder<F> for PrimitiveEncoder<T>
+where
+    T: ArrowPrimitiveType,
+    T::Native: JsonSerializable,
+    F: Formatter,
+{
+    fn is_null(&self, idx: usize) -> bool {
+        self.0.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        // Values that cannot be represented in JSON, such as NaN, are written as null
+        match self.0.value(idx).into_json_value() {
+            Some(v) => write_json(out, &v),
+            None => write_null(f, out),
+        }
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        Ok(self.0.value(idx).into_json_value().unwrap_or(Value::Null))
+    }
+}
+
+struct DecimalEncoder<T: DecimalType>(PrimitiveArray<T>);
+
+impl<T: DecimalType, F: Formatter> Encoder<F> for DecimalEncoder<T> {
+    fn is_null(&self, idx: usize) -> bool {
+        self.0.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        _: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        write_json(out, &self.0.value_as_string(idx))
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        Ok(self.0.value_as_string(idx).into())
+    }
+}
+
+struct StringEncoder<O: OffsetSizeTrait>(GenericStringArray<O>);
+
+impl<O: OffsetSizeTrait, F: Formatter> Encoder<F> for StringEncoder<O> {
+    fn is_null(&self, idx: usize) -> bool {
+        self.0.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        _: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        write_json(out, self.0.value(idx))
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        Ok(self.0.value(idx).into())
+    }
+}
+
+struct BinaryEncoder<A> {
+    array: A,
+    encoding: BinaryEncoding,
+}
+
+impl<A, F> Encoder<F> for BinaryEncoder<A>
+where
+    A: Array,
+    for<'a> &'a A: ArrayAccessor<Item = &'a [u8]>,
+    F: Formatter,
+{
+    fn is_null(&self, idx: usize) -> bool {
+        self.array.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        _: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        let encoded = self.encoding.encode((&self.array).value(idx))?;
+        write_json(out, &encoded)
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        Ok(self.encoding.encode((&self.array).value(idx))?.into())
+    }
+}
+
+/// Converts the values of a temporal array to JSON according to a [`TemporalFormat`]
+pub(crate) trait TemporalToJson {
+    /// Returns true if the value at `idx` is null or cannot be represented as a
+    /// date or time
+    fn is_null(&self, idx: usize) -> bool;
+
+    /// Returns the value at `idx` as JSON, or `None` if it is null or cannot be
+    /// represented as a date or time
+    fn to_json(&self, idx: usize) -> Result<Option<Value>, ArrowError>;
+}
+
+/// Returns a [`TemporalToJson`] for a date, time, timestamp or duration `array`
+pub(crate) fn make_temporal(
+    array: &dyn Array,
+    options: &EncoderOptions,
+) -> Result<Box<dyn TemporalToJson>, ArrowError> {
+    use TemporalKind::*;
+
+    fn boxed<T>(
+        array: &dyn Array,
+        kind: TemporalKind,
+        options: &EncoderOptions,
+    ) -> Result<Box<dyn TemporalToJson>, ArrowError>
+    where
+        T: ArrowTemporalType + 'static,
+        i64: From<T::Native>,
+    {
+        let tz = match array.data_type() {
+            DataType::Timestamp(_, Some(tz)) => Some(tz.parse::<Tz>()?),
+            _ => None,
+        };
+        Ok(Box::new(TemporalArray::<T> {
+            array: array.data().clone().into(),
+            kind,
+            format: options.temporal_format.clone(),
+            tz,
+        }))
+    }
+
+    match array.data_type() {
+        DataType::Date32 => boxed::<Date32Type>(array, Date, options),
+        DataType::Date64 => boxed::<Date64Type>(array, Date, options),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            boxed::<TimestampSecondType>(array, Timestamp, options)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            boxed::<TimestampMillisecondType>(array, Timestamp, options)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            boxed::<TimestampMicrosecondType>(array, Timestamp, options)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            boxed::<TimestampNanosecondType>(array, Timestamp, options)
+        }
+        DataType::Time32(TimeUnit::Second) => {
+            boxed::<Time32SecondType>(array, Time, options)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            boxed::<Time32MillisecondType>(array, Time, options)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            boxed::<Time64MicrosecondType>(array, Time, options)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            boxed::<Time64NanosecondType>(array, Time, options)
+        }
+        DataType::Duration(TimeUnit::Second) => {
+            boxed::<DurationSecondType>(array, Duration, options)
+        }
+        DataType::Duration(TimeUnit::Millisecond) => {
+            boxed::<DurationMillisecondType>(array, Duration, options)
+        }
+        DataType::Duration(TimeUnit::Microsecond) => {
+            boxed::<DurationMicrosecondType>(array, Duration, options)
+        }
+        DataType::Duration(TimeUnit::Nanosecond) => {
+            boxed::<DurationNanosecondType>(array, Duration, options)
+        }
+        t => Err(ArrowError::JsonError(format!(
+            "data type {:?} is not a temporal type",
+            t
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TemporalKind {
+    Date,
+    Time,
+    Timestamp,
+    Duration,
+}
+
+struct TemporalArray<T: ArrowPrimitiveType> {
+    array: PrimitiveArray<T>,
+    kind: TemporalKind,
+    format: TemporalFormat,
+    /// The timezone of a timestamp array
+    tz: Option<Tz>,
+}
+
+/// Formats `item` with a custom format string, returning an error if the format
+/// string is not valid for `item`, e.g. if it references the time of a date
+fn format_custom(item: impl std::fmt::Display) -> Result<String, ArrowError> {
+    let mut s = String::new();
+    write!(s, "{}", item).map_err(|_| {
+        ArrowError::JsonError("invalid format string for temporal value".to_string())
+    })?;
+    Ok(s)
+}
+
+impl<T> TemporalToJson for TemporalArray<T>
+where
+    T: ArrowTemporalType,
+    i64: From<T::Native>,
+{
+    fn is_null(&self, idx: usize) -> bool {
+        self.array.is_null(idx)
+            || match self.kind {
+                TemporalKind::Date | TemporalKind::Timestamp => {
+                    self.array.value_as_datetime(idx).is_none()
+                }
+                TemporalKind::Time => self.array.value_as_time(idx).is_none(),
+                TemporalKind::Duration => self.array.value_as_duration(idx).is_none(),
+            }
+    }
+
+    fn to_json(&self, idx: usize) -> Result<Option<Value>, ArrowError> {
+        if self.array.is_null(idx) {
+            return Ok(None);
+        }
+
+        let value = match self.kind {
+            TemporalKind::Date => {
+                let datetime = match self.array.value_as_datetime(idx) {
+                    Some(v) => v,
+                    None => return Ok(None),
+                };
+                match &self.format {
+                    TemporalFormat::Display | TemporalFormat::Rfc3339 => {
+                        datetime.date().to_string().into()
+                    }
+                    TemporalFormat::EpochMillis => datetime.timestamp_millis().into(),
+                    TemporalFormat::Custom(f) => {
+                        format_custom(datetime.format(f))?.into()
+                    }
+                }
+            }
+            TemporalKind::Time => {
+                let time = match self.array.value_as_time(idx) {
+                    Some(v) => v,
+                    None => return Ok(None),
+                };
+                match &self.format {
+                    TemporalFormat::Display | TemporalFormat::Rfc3339 => {
+                        time.to_string().into()
+                    }
+                    TemporalFormat::EpochMillis => {
+                        let millis = time.num_seconds_from_midnight() as i64 * 1_000
+                            + time.nanosecond() as i64 / 1_000_000;
+                        millis.into()
+                    }
+                    TemporalFormat::Custom(f) => format_custom(time.format(f))?.into(),
+                }
+            }
+            TemporalKind::Timestamp => {
+                let naive = match self.array.value_as_datetime(idx) {
+                    Some(v) => v,
+                    None => return Ok(None),
+                };
+                let datetime = self.tz.map(|tz| tz.from_utc_datetime(&naive));
+                match (&self.format, datetime) {
+                    (TemporalFormat::Display, _) => naive.to_string().into(),
+                    (TemporalFormat::Rfc3339, Some(datetime)) => {
+                        datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true).into()
+                    }
+                    (TemporalFormat::Rfc3339, None) => {
+                        naive.format("%Y-%m-%dT%H:%M:%S%.f").to_string().into()
+                    }
+                    (TemporalFormat::EpochMillis, _) => naive.timestamp_millis().into(),
+                    (TemporalFormat::Custom(f), Some(datetime)) => {
+                        format_custom(datetime.format(f))?.into()
+                    }
+                    (TemporalFormat::Custom(f), None) => {
+                        format_custom(naive.format(f))?.into()
+                    }
+                }
+            }
+            TemporalKind::Duration => {
+                let duration = match self.array.value_as_duration(idx) {
+                    Some(v) => v,
+                    None => return Ok(None),
+                };
+                match &self.format {
+                    TemporalFormat::EpochMillis => duration.num_milliseconds().into(),
+                    _ => duration.to_string().into(),
+                }
+            }
+        };
+        Ok(Some(value))
+    }
+}
+
+struct TemporalEncoder(Box<dyn TemporalToJson>);
+
+impl<F: Formatter> Encoder<F> for TemporalEncoder {
+    fn is_null(&self, idx: usize) -> bool {
+        // Values that cannot be represented as a date or time are also omitted
+        self.0.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        match self.0.to_json(idx)? {
+            Some(v) => write_json(out, &v),
+            None => write_null(f, out),
+        }
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        Ok(self.0.to_json(idx)?.unwrap_or(Value::Null))
+    }
+}
+
+/// Writes the values of `encoder` within `range` as a JSON array
+fn encode_list<F: Formatter>(
+    encoder: &mut BoxEncoder<F>,
+    range: std::ops::Range<usize>,
+    f: &mut F,
+    out: &mut Vec<u8>,
+) -> Result<(), ArrowError> {
+    f.begin_array(out)?;
+    for (i, idx) in range.enumerate() {
+        f.begin_array_value(out, i == 0)?;
+        match encoder.is_null(idx) {
+            true => write_null(f, out)?,
+            false => encoder.encode(idx, f, out)?,
+        }
+        f.end_array_value(out)?;
+    }
+    f.end_array(out)?;
+    Ok(())
+}
+
+/// Returns the values of `encoder` within `range` as a JSON array
+fn list_to_value<F: Formatter>(
+    encoder: &BoxEncoder<F>,
+    range: std::ops::Range<usize>,
+) -> Result<Value, ArrowError> {
+    range
+        .map(|idx| match encoder.is_null(idx) {
+            true => Ok(Value::Null),
+            false => encoder.to_value(idx),
+        })
+        .collect()
+}
+
+struct ListEncoder<O: OffsetSizeTrait, F: Formatter> {
+    array: GenericListArray<O>,
+    values: BoxEncoder<F>,
+}
+
+impl<O: OffsetSizeTrait, F: Formatter> Encoder<F> for ListEncoder<O, F> {
+    fn is_null(&self, idx: usize) -> bool {
+        self.array.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        let offsets = self.array.value_offsets();
+        let range = offsets[idx].as_usize()..offsets[idx + 1].as_usize();
+        encode_list(&mut self.values, range, f, out)
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        let offsets = self.array.value_offsets();
+        let range = offsets[idx].as_usize()..offsets[idx + 1].as_usize();
+        list_to_value(&self.values, range)
+    }
+}
+
+struct FixedSizeListEncoder<F: Formatter> {
+    array: FixedSizeListArray,
+    values: BoxEncoder<F>,
+}
+
+impl<F: Formatter> Encoder<F> for FixedSizeListEncoder<F> {
+    fn is_null(&self, idx: usize) -> bool {
+        self.array.is_null(idx)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        let start = self.array.value_offset(idx) as usize;
+        let end = start + self.array.value_length() as usize;
+        encode_list(&mut self.values, start..end, f, out)
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        let start = self.array.value_offset(idx) as usize;
+        let end = start + self.array.value_length() as usize;
+        list_to_value(&self.values, start..end)
+    }
+}
+
+/// Encodes structs, and the rows of a [`RecordBatch`], as JSON objects
+/// omitting any null fields
+///
+/// Null structs are written as objects of their fields, in the same way as
+/// the rows of a [`RecordBatch`]
+pub(crate) struct StructEncoder<F: Formatter> {
+    fields: Vec<(String, BoxEncoder<F>)>,
+}
+
+impl<F: Formatter> StructEncoder<F> {
+    /// Returns the value at `idx` as a JSON object, omitting any null fields
+    pub(crate) fn to_object(
+        &self,
+        idx: usize,
+    ) -> Result<JsonMap<String, Value>, ArrowError> {
+        let mut object = JsonMap::new();
+        for (name, encoder) in self.fields.iter() {
+            if !encoder.is_null(idx) {
+                object.insert(name.clone(), encoder.to_value(idx)?);
+            }
+        }
+        Ok(object)
+    }
+}
+
+impl<F: Formatter> Encoder<F> for StructEncoder<F> {
+    fn is_null(&self, _idx: usize) -> bool {
+        false
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        f.begin_object(out)?;
+        let mut first = true;
+        for (name, encoder) in self.fields.iter_mut() {
+            if encoder.is_null(idx) {
+                continue;
+            }
+            f.begin_object_key(out, first)?;
+            write_json(out, name.as_str())?;
+            f.end_object_key(out)?;
+            f.begin_object_value(out)?;
+            encoder.encode(idx, f, out)?;
+            f.end_object_value(out)?;
+            first = false;
+        }
+        f.end_object(out)?;
+        Ok(())
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        Ok(Value::Object(self.to_object(idx)?))
+    }
+}
+
+struct MapEncoder<F: Formatter> {
+    array: MapArray,
+    keys: BoxEncoder<F>,
+    values: BoxEncoder<F>,
+    /// Write maps as lists of key-value entries instead of JSON objects
+    entries: bool,
+    /// Scratch space used to encode keys that are not strings
+    key_buffer: Vec<u8>,
+}
+
+impl<F: Formatter> MapEncoder<F> {
+    fn value_to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        match self.values.is_null(idx) {
+            true => Ok(Value::Null),
+            false => self.values.to_value(idx),
+        }
+    }
+
+    fn encode_value(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        match self.values.is_null(idx) {
+            true => write_null(f, out),
+            false => self.values.encode(idx, f, out),
+        }
+    }
+
+    fn encode_entries(
+        &mut self,
+        range: std::ops::Range<usize>,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        f.begin_array(out)?;
+        for (i, idx) in range.enumerate() {
+            f.begin_array_value(out, i == 0)?;
+            f.begin_object(out)?;
+
+            f.begin_object_key(out, true)?;
+            write_json(out, "key")?;
+            f.end_object_key(out)?;
+            f.begin_object_value(out)?;
+            match self.keys.is_null(idx) {
+                true => write_null(f, out)?,
+                false => self.keys.encode(idx, f, out)?,
+            }
+            f.end_object_value(out)?;
+
+            f.begin_object_key(out, false)?;
+            write_json(out, "value")?;
+            f.end_object_key(out)?;
+            f.begin_object_value(out)?;
+            self.encode_value(idx, f, out)?;
+            f.end_object_value(out)?;
+
+            f.end_object(out)?;
+            f.end_array_value(out)?;
+        }
+        f.end_array(out)?;
+        Ok(())
+    }
+
+    fn encode_object(
+        &mut self,
+        range: std::ops::Range<usize>,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        f.begin_object(out)?;
+        for (i, idx) in range.enumerate() {
+            if self.keys.is_null(idx) {
+                return Err(ArrowError::JsonError(
+                    "keys in a map should be non-null".to_string(),
+                ));
+            }
+
+            // Keys that are not strings are written as their JSON text
+            self.key_buffer.clear();
+            self.keys.encode(idx, f, &mut self.key_buffer)?;
+
+            f.begin_object_key(out, i == 0)?;
+            match self.key_buffer.first() {
+                Some(b'"') => out.extend_from_slice(&self.key_buffer),
+                _ => {
+                    let key = std::str::from_utf8(&self.key_buffer)
+                        .map_err(|e| ArrowError::JsonError(e.to_string()))?;
+                    write_json(out, key)?
+                }
+            }
+            f.end_object_key(out)?;
+
+            f.begin_object_value(out)?;
+            self.encode_value(idx, f, out)?;
+            f.end_object_value(out)?;
+        }
+        f.end_object(out)?;
+        Ok(())
+    }
+}
+
+impl<F: Formatter> Encoder<F> for MapEncoder<F> {
+    fn is_null(&self, _idx: usize) -> bool {
+        // Null maps are written explicitly as null
+        false
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        if self.array.is_null(idx) {
+            return write_null(f, out);
+        }
+
+        let offsets = self.array.value_offsets();
+        let range = offsets[idx] as usize..offsets[idx + 1] as usize;
+        match self.entries {
+            true => self.encode_entries(range, f, out),
+            false => self.encode_object(range, f, out),
+        }
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        if self.array.is_null(idx) {
+            return Ok(Value::Null);
+        }
+
+        let offsets = self.array.value_offsets();
+        let range = offsets[idx] as usize..offsets[idx + 1] as usize;
+        match self.entries {
+            true => range
+                .map(|idx| {
+                    let key = match self.keys.is_null(idx) {
+                        true => Value::Null,
+                        false => self.keys.to_value(idx)?,
+                    };
+                    let mut entry = JsonMap::new();
+                    entry.insert("key".to_string(), key);
+                    entry.insert("value".to_string(), self.value_to_value(idx)?);
+                    Ok(Value::Object(entry))
+                })
+                .collect(),
+            false => {
+                let mut object = JsonMap::new();
+                for idx in range {
+                    if self.keys.is_null(idx) {
+                        return Err(ArrowError::JsonError(
+                            "keys in a map should be non-null".to_string(),
+                        ));
+                    }
+                    // Keys that are not strings are written as their JSON text
+                    let key = match self.keys.to_value(idx)? {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    object.insert(key, self.value_to_value(idx)?);
+                }
+                Ok(Value::Object(object))
+            }
+        }
+    }
+}
+
+struct UnionEncoder<F: Formatter> {
+    array: UnionArray,
+    children: Vec<BoxEncoder<F>>,
+}
+
+impl<F: Formatter> UnionEncoder<F> {
+    /// Returns the child and index within that child of the value at `idx`
+    fn child(&self, idx: usize) -> (usize, usize) {
+        let type_id = self.array.type_id(idx) as usize;
+        (type_id, self.array.value_offset(idx) as usize)
+    }
+}
+
+impl<F: Formatter> Encoder<F> for UnionEncoder<F> {
+    fn is_null(&self, idx: usize) -> bool {
+        let (child, offset) = self.child(idx);
+        self.children[child].is_null(offset)
+    }
+
+    fn encode(
+        &mut self,
+        idx: usize,
+        f: &mut F,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ArrowError> {
+        let (child, offset) = self.child(idx);
+        self.children[child].encode(offset, f, out)
+    }
+
+    fn to_value(&self, idx: usize) -> Result<Value, ArrowError> {
+        let (child, offset) = self.child(idx);
+        self.children[child].to_value(offset)
+    }
+}
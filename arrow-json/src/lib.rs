@@ -17,20 +17,96 @@
 
 //! Transfer data between the Arrow memory format and JSON
 //! line-delimited records. See the module level documentation for the
-//! [`reader`] and [`writer`] for usage examples.
+//! [`reader`] and [`writer`] for usage examples, and [`path`] for
+//! extracting values from columns of JSON documents.
+//!
+//! With the `object_store` feature, `store` reads newline-delimited JSON from
+//! an object store in parallel.
 
 #![deny(rustdoc::broken_intra_doc_links)]
 #![warn(missing_docs)]
 
+mod encoder;
+pub mod path;
 pub mod reader;
+#[cfg(feature = "object_store")]
+pub mod store;
 pub mod writer;
 
 pub use self::reader::Reader;
 pub use self::reader::ReaderBuilder;
-pub use self::writer::{ArrayWriter, LineDelimitedWriter, Writer};
+pub use self::writer::{ArrayWriter, LineDelimitedWriter, PrettyArrayWriter, Writer};
+use arrow_schema::ArrowError;
 use half::f16;
 use serde_json::{Number, Value};
 
+/// Specifies how `Binary` and `FixedSizeBinary` values are represented as JSON strings
+///
+/// Defaults to [`BinaryEncoding::Utf8`] for both reading and writing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinaryEncoding {
+    /// The bytes are the UTF-8 encoded contents of the string
+    #[default]
+    Utf8,
+    /// The bytes are encoded using standard, padded base64
+    Base64,
+    /// The bytes are encoded as lowercase hexadecimal, two characters per byte
+    Hex,
+}
+
+impl BinaryEncoding {
+    /// Encode `bytes` as a string
+    pub(crate) fn encode(&self, bytes: &[u8]) -> Result<String, ArrowError> {
+        match self {
+            Self::Utf8 => {
+                std::str::from_utf8(bytes)
+                    .map(ToString::to_string)
+                    .map_err(|e| {
+                        ArrowError::JsonError(format!(
+                            "Binary value is not valid UTF-8: {}",
+                            e
+                        ))
+                    })
+            }
+            Self::Base64 => Ok(base64::encode(bytes)),
+            Self::Hex => {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+                let mut out = String::with_capacity(bytes.len() * 2);
+                for b in bytes {
+                    out.push(HEX[(b >> 4) as usize] as char);
+                    out.push(HEX[(b & 0xF) as usize] as char);
+                }
+                Ok(out)
+            }
+        }
+    }
+
+    /// Decode a string produced by [`Self::encode`] back into bytes
+    pub(crate) fn decode(&self, s: &str) -> Result<Vec<u8>, ArrowError> {
+        match self {
+            Self::Utf8 => Ok(s.as_bytes().to_vec()),
+            Self::Base64 => base64::decode(s).map_err(|e| {
+                ArrowError::JsonError(format!("Invalid base64 value '{}': {}", s, e))
+            }),
+            Self::Hex => {
+                let invalid =
+                    || ArrowError::JsonError(format!("Invalid hex value '{}'", s));
+                if s.len() % 2 != 0 {
+                    return Err(invalid());
+                }
+                s.as_bytes()
+                    .chunks(2)
+                    .map(|pair| {
+                        let hi = (pair[0] as char).to_digit(16).ok_or_else(invalid)?;
+                        let lo = (pair[1] as char).to_digit(16).ok_or_else(invalid)?;
+                        Ok((hi * 16 + lo) as u8)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
 /// Trait declaring any type that is serializable to JSON. This includes all primitive types (bool, i32, etc.).
 pub trait JsonSerializable: 'static {
     /// Converts self into json value if its possible
@@ -94,6 +170,23 @@ mod tests {
         Value::{Bool, Number as VNumber, String as VString},
     };
 
+    #[test]
+    fn test_binary_encoding_roundtrip() {
+        let bytes = [0_u8, 1, 127, 128, 254, 255];
+        for encoding in [BinaryEncoding::Base64, BinaryEncoding::Hex] {
+            let encoded = encoding.encode(&bytes).unwrap();
+            assert_eq!(encoding.decode(&encoded).unwrap(), bytes);
+        }
+        assert_eq!(BinaryEncoding::Hex.encode(&bytes).unwrap(), "00017f80feff");
+        assert_eq!(BinaryEncoding::Base64.encode(b"foo").unwrap(), "Zm9v");
+        assert_eq!(BinaryEncoding::Utf8.encode(b"foo").unwrap(), "foo");
+
+        BinaryEncoding::Utf8.encode(&bytes).unwrap_err();
+        BinaryEncoding::Hex.decode("abc").unwrap_err();
+        BinaryEncoding::Hex.decode("zz").unwrap_err();
+        BinaryEncoding::Base64.decode("!!").unwrap_err();
+    }
+
     #[test]
     fn test_arrow_native_type_to_json() {
         assert_eq!(Some(Bool(true)), true.into_json_value());
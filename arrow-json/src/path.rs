@@ -0,0 +1,273 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! # JSON Path Extraction
+//!
+//! Kernels to extract values from string columns containing JSON documents,
+//! without decoding the documents into nested arrays.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow_array::{ArrayRef, StringArray};
+//! # use arrow_array::cast::as_string_array;
+//! use arrow_json::path::get_json_object;
+//!
+//! let array: ArrayRef = Arc::new(StringArray::from(vec![
+//!     Some(r#"{"a": {"b": [1, 2]}}"#),
+//!     Some(r#"{"a": {"b": "foo"}}"#),
+//!     Some(r#"{"a": null}"#),
+//!     None,
+//! ]));
+//!
+//! let result = get_json_object(&array, "$.a.b").unwrap();
+//! assert_eq!(
+//!     as_string_array(&result).iter().collect::<Vec<_>>(),
+//!     vec![Some("[1,2]"), Some("foo"), None, None]
+//! );
+//! ```
+//!
+//! The supported path syntax is a subset of [JSONPath], consisting of the root
+//! `$` followed by any number of:
+//!
+//! * `.name` - select the field `name` of an object
+//! * `['name']` or `["name"]` - select the field `name` of an object, where `name`
+//!   may contain any characters other than the enclosing quote
+//! * `[index]` - select the element at the zero-based `index` of an array
+//!
+//! [JSONPath]: https://goessner.net/articles/JsonPath/
+
+use std::sync::Arc;
+
+use arrow_array::cast::{as_largestring_array, as_string_array};
+use arrow_array::{Array, ArrayRef, GenericStringArray, OffsetSizeTrait};
+use arrow_cast::cast::{cast_with_options, CastOptions};
+use arrow_schema::{ArrowError, DataType};
+use serde_json::Value;
+
+/// A single step of a parsed JSON path
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathElement {
+    Field(String),
+    Index(usize),
+}
+
+/// Parse a JSON path expression, see the module documentation for the syntax
+fn parse_path(path: &str) -> Result<Vec<PathElement>, ArrowError> {
+    let invalid = |reason: &str| {
+        ArrowError::InvalidArgumentError(format!(
+            "Invalid JSON path '{}': {}",
+            path, reason
+        ))
+    };
+
+    let mut rest = path
+        .strip_prefix('$')
+        .ok_or_else(|| invalid("must start with '$'"))?;
+
+    let mut elements = vec![];
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix('.') {
+            let end = r.find(['.', '[']).unwrap_or(r.len());
+            if end == 0 {
+                return Err(invalid("empty field name"));
+            }
+            elements.push(PathElement::Field(r[..end].to_string()));
+            rest = &r[end..];
+        } else if let Some(r) = rest.strip_prefix('[') {
+            let (element, r) = match r.chars().next() {
+                Some(quote @ ('\'' | '"')) => {
+                    let r = &r[1..];
+                    let end =
+                        r.find(quote).ok_or_else(|| invalid("unterminated quote"))?;
+                    (PathElement::Field(r[..end].to_string()), &r[end + 1..])
+                }
+                _ => {
+                    let end = r.find(']').unwrap_or(r.len());
+                    let index = r[..end]
+                        .parse()
+                        .map_err(|_| invalid("expected array index"))?;
+                    (PathElement::Index(index), &r[end..])
+                }
+            };
+            rest = r.strip_prefix(']').ok_or_else(|| invalid("expected ']'"))?;
+            elements.push(element);
+        } else {
+            return Err(invalid("expected '.' or '['"));
+        }
+    }
+    Ok(elements)
+}
+
+/// Returns the value at `path` within the JSON document `json`, formatted as a string
+fn extract(json: &str, path: &[PathElement]) -> Option<String> {
+    let document: Value = serde_json::from_str(json).ok()?;
+    let value = path
+        .iter()
+        .try_fold(&document, |value, element| match element {
+            PathElement::Field(name) => value.as_object()?.get(name),
+            PathElement::Index(index) => value.as_array()?.get(*index),
+        })?;
+
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        v => Some(v.to_string()),
+    }
+}
+
+fn get_json_object_impl<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    path: &[PathElement],
+) -> GenericStringArray<O> {
+    array
+        .iter()
+        .map(|json| json.and_then(|json| extract(json, path)))
+        .collect()
+}
+
+/// Extracts the value at the JSON `path` from each JSON document in `array`.
+///
+/// Strings are returned without their enclosing quotes, and objects, arrays and
+/// other scalars are returned as their JSON text. The result is null where the
+/// input is null or not valid JSON, or where `path` does not exist or is `null`.
+///
+/// `array` must be a `Utf8` or `LargeUtf8` array, and the result has the same type.
+/// See the [module documentation](self) for the supported path syntax.
+pub fn get_json_object(array: &dyn Array, path: &str) -> Result<ArrayRef, ArrowError> {
+    let path = parse_path(path)?;
+    match array.data_type() {
+        DataType::Utf8 => Ok(Arc::new(get_json_object_impl(
+            as_string_array(array),
+            &path,
+        ))),
+        DataType::LargeUtf8 => Ok(Arc::new(get_json_object_impl(
+            as_largestring_array(array),
+            &path,
+        ))),
+        t => Err(ArrowError::InvalidArgumentError(format!(
+            "get_json_object does not support type {:?}",
+            t
+        ))),
+    }
+}
+
+/// Extracts the value at the JSON `path` from each JSON document in `array`, as
+/// [`get_json_object`], and casts the result to `data_type`.
+///
+/// Values that cannot be cast to `data_type` are returned as null.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{ArrayRef, Int64Array, StringArray};
+/// # use arrow_schema::DataType;
+/// use arrow_json::path::get_json_object_as;
+///
+/// let array: ArrayRef = Arc::new(StringArray::from(vec![
+///     r#"{"items": [{"id": 3}]}"#,
+///     r#"{"items": [{"id": "x"}]}"#,
+///     r#"{"items": []}"#,
+/// ]));
+///
+/// let ids = get_json_object_as(&array, "$.items[0].id", &DataType::Int64).unwrap();
+/// assert_eq!(
+///     ids.as_any().downcast_ref::<Int64Array>().unwrap(),
+///     &Int64Array::from(vec![Some(3), None, None])
+/// );
+/// ```
+pub fn get_json_object_as(
+    array: &dyn Array,
+    path: &str,
+    data_type: &DataType,
+) -> Result<ArrayRef, ArrowError> {
+    let values = get_json_object(array, path)?;
+    cast_with_options(&values, data_type, &CastOptions { safe: true })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::cast::as_boolean_array;
+    use arrow_array::{LargeStringArray, StringArray};
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("$").unwrap(), vec![]);
+        assert_eq!(
+            parse_path("$.a.b[0]['c.d'][\"e\"]").unwrap(),
+            vec![
+                PathElement::Field("a".to_string()),
+                PathElement::Field("b".to_string()),
+                PathElement::Index(0),
+                PathElement::Field("c.d".to_string()),
+                PathElement::Field("e".to_string()),
+            ]
+        );
+
+        for path in [
+            "", "a", "$.", "$..a", "$[", "$[a]", "$[-1]", "$['a]", "$['a'", "$a",
+        ] {
+            let err = parse_path(path).unwrap_err().to_string();
+            assert!(err.contains("Invalid JSON path"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_get_json_object() {
+        let array: ArrayRef = Arc::new(StringArray::from(vec![
+            Some(r#"{"a": [{"b": 1}, {"b": "two"}], "c": true}"#),
+            Some(r#"{"a": [{"b": 2.5}], "c": false}"#),
+            Some(r#"{"a": {"0": 1}}"#),
+            Some("not json"),
+            None,
+        ]));
+
+        let result = get_json_object(&array, "$.a[1].b").unwrap();
+        assert_eq!(
+            as_string_array(&result).iter().collect::<Vec<_>>(),
+            vec![Some("two"), None, None, None, None]
+        );
+
+        let result = get_json_object(&array, "$.a[0]").unwrap();
+        assert_eq!(
+            as_string_array(&result).iter().collect::<Vec<_>>(),
+            vec![Some(r#"{"b":1}"#), Some(r#"{"b":2.5}"#), None, None, None]
+        );
+
+        let result = get_json_object(&array, "$['a']['0']").unwrap();
+        assert_eq!(
+            as_string_array(&result).iter().collect::<Vec<_>>(),
+            vec![None, None, Some("1"), None, None]
+        );
+
+        let result = get_json_object_as(&array, "$.c", &DataType::Boolean).unwrap();
+        assert_eq!(
+            as_boolean_array(&result).iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false), None, None, None]
+        );
+    }
+
+    #[test]
+    fn test_get_json_object_large_utf8() {
+        let array: ArrayRef = Arc::new(LargeStringArray::from(vec![r#"{"a": "b"}"#]));
+        let result = get_json_object(&array, "$.a").unwrap();
+        assert_eq!(result.data_type(), &DataType::LargeUtf8);
+        assert_eq!(as_largestring_array(&result).value(0), "b".to_string());
+
+        let array: ArrayRef = Arc::new(arrow_array::Int32Array::from(vec![1]));
+        get_json_object(&array, "$.a").unwrap_err();
+    }
+}
@@ -17,9 +17,9 @@
 
 //! # JSON Reader
 //!
-//! This JSON reader allows JSON line-delimited files to be read into the Arrow memory
-//! model. Records are loaded in batches and are then converted from row-based data to
-//! columnar data.
+//! This JSON reader allows JSON line-delimited files, or files containing a single
+//! top-level JSON array of objects, to be read into the Arrow memory model. Records are
+//! loaded in batches and are then converted from row-based data to columnar data.
 //!
 //! Example:
 //!
@@ -47,61 +47,86 @@
 //! ```
 
 use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::str::FromStr;
 use std::sync::Arc;
 
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone};
 use indexmap::map::IndexMap as HashMap;
 use indexmap::set::IndexSet as HashSet;
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use serde_json::{map::Map as JsonMap, Value};
 
 use arrow_array::builder::*;
+use arrow_array::timezone::Tz;
 use arrow_array::types::*;
 use arrow_array::*;
+use arrow_buffer::ArrowNativeType;
 use arrow_buffer::{bit_util, Buffer, MutableBuffer};
-use arrow_cast::parse::Parser;
+use arrow_cast::parse::{string_to_timestamp_nanos, Parser};
 use arrow_data::{ArrayData, ArrayDataBuilder};
 use arrow_schema::*;
 
+use crate::BinaryEncoding;
+
 #[derive(Debug, Clone)]
 enum InferredType {
     Scalar(HashSet<DataType>),
     Array(Box<InferredType>),
     Object(HashMap<String, InferredType>),
+    /// Values with conflicting or too deeply nested structure, read as JSON text
+    Json,
     Any,
 }
 
 impl InferredType {
-    fn merge(&mut self, other: InferredType) -> Result<(), ArrowError> {
+    /// Merge `other` into `self`, promoting to a common type.
+    ///
+    /// Values that cannot be represented by a common type are promoted to
+    /// [`InferredType::Json`] if `coerce_conflicts` is true, otherwise an error
+    /// is returned
+    fn merge(
+        &mut self,
+        other: InferredType,
+        coerce_conflicts: bool,
+    ) -> Result<(), ArrowError> {
         match (self, other) {
             (InferredType::Array(s), InferredType::Array(o)) => {
-                s.merge(*o)?;
+                s.merge(*o, coerce_conflicts)?;
             }
             (InferredType::Scalar(self_hs), InferredType::Scalar(other_hs)) => {
-                other_hs.into_iter().for_each(|v| {
-                    self_hs.insert(v);
-                });
+                self_hs.extend(other_hs);
             }
             (InferredType::Object(self_map), InferredType::Object(other_map)) => {
                 for (k, v) in other_map {
-                    self_map.entry(k).or_insert(InferredType::Any).merge(v)?;
+                    self_map
+                        .entry(k)
+                        .or_insert(InferredType::Any)
+                        .merge(v, coerce_conflicts)?;
                 }
             }
             (s @ InferredType::Any, v) => {
                 *s = v;
             }
-            (_, InferredType::Any) => {}
+            (_, InferredType::Any) | (InferredType::Json, _) => {}
+            (s, InferredType::Json) => {
+                *s = InferredType::Json;
+            }
             // convert a scalar type to a single-item scalar array type.
             (
                 InferredType::Array(self_inner_type),
                 other_scalar @ InferredType::Scalar(_),
             ) => {
-                self_inner_type.merge(other_scalar)?;
+                self_inner_type.merge(other_scalar, coerce_conflicts)?;
             }
             (s @ InferredType::Scalar(_), InferredType::Array(mut other_inner_type)) => {
-                other_inner_type.merge(s.clone())?;
+                other_inner_type.merge(s.clone(), coerce_conflicts)?;
                 *s = InferredType::Array(other_inner_type);
             }
             // incompatible types
+            (s, _) if coerce_conflicts => {
+                *s = InferredType::Json;
+            }
             (s, o) => {
                 return Err(ArrowError::JsonError(format!(
                     "Incompatible type found during schema inference: {:?} v.s. {:?}",
@@ -114,6 +139,54 @@ impl InferredType {
     }
 }
 
+/// Options for JSON schema inference
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InferenceOptions {
+    /// Infer `Map<Utf8, T>` for nested objects whose values share a common type
+    infer_maps: bool,
+    /// Maximum nesting depth of objects and arrays below the top-level record
+    max_depth: Option<usize>,
+    /// Infer `Utf8` for fields with conflicting types instead of returning an error
+    coerce_conflicts: bool,
+}
+
+impl InferenceOptions {
+    /// Creates a new `InferenceOptions`
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Infer nested objects as `Map<Utf8, T>` when all of their values share a
+    /// common type `T`, instead of as a `Struct` with a field per key.
+    ///
+    /// This is useful for objects keyed by data, such as identifiers or timestamps
+    pub fn with_infer_maps(mut self, infer_maps: bool) -> Self {
+        self.infer_maps = infer_maps;
+        self
+    }
+
+    /// Set the maximum nesting depth of inferred types
+    ///
+    /// Objects and arrays nested more than `max_depth` levels below the top-level
+    /// record are inferred as `Utf8`, to be read as their JSON text with
+    /// [`DecoderOptions::with_json_text`]. A `max_depth` of `0` therefore infers
+    /// a flat schema
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Infer `Utf8` for fields whose values have conflicting types, such as an
+    /// object and a number, instead of returning an error. Defaults to `false`
+    ///
+    /// Such fields should be read with [`DecoderOptions::with_json_text`], so that
+    /// values which are not strings are read as their JSON text
+    pub fn with_coerce_conflicts(mut self, coerce_conflicts: bool) -> Self {
+        self.coerce_conflicts = coerce_conflicts;
+        self
+    }
+}
+
 /// Coerce data type during inference
 ///
 /// * `Int64` and `Float64` should be `Float64`
@@ -146,34 +219,89 @@ fn coerce_data_type(dt: Vec<&DataType>) -> DataType {
     })
 }
 
-fn generate_datatype(t: &InferredType) -> Result<DataType, ArrowError> {
+fn generate_datatype(
+    t: &InferredType,
+    options: &InferenceOptions,
+) -> Result<DataType, ArrowError> {
     Ok(match t {
         InferredType::Scalar(hs) => coerce_data_type(hs.iter().collect()),
-        InferredType::Object(spec) => DataType::Struct(generate_fields(spec)?),
+        InferredType::Object(spec) => match generate_map_type(spec, options)? {
+            Some(map_type) => map_type,
+            None => DataType::Struct(generate_fields(spec, options)?),
+        },
         InferredType::Array(ele_type) => DataType::List(Box::new(Field::new(
             "item",
-            generate_datatype(ele_type)?,
+            generate_datatype(ele_type, options)?,
             true,
         ))),
+        InferredType::Json => DataType::Utf8,
         InferredType::Any => DataType::Null,
     })
 }
 
+/// Returns a `Map<Utf8, T>` if map inference is enabled, and the values of `spec`
+/// can be promoted to a common type `T` without falling back to `Utf8`
+fn generate_map_type(
+    spec: &HashMap<String, InferredType>,
+    options: &InferenceOptions,
+) -> Result<Option<DataType>, ArrowError> {
+    if !options.infer_maps || spec.is_empty() {
+        return Ok(None);
+    }
+
+    let mut value_type = InferredType::Any;
+    for t in spec.values() {
+        value_type.merge(t.clone(), true)?;
+    }
+    let homogeneous = match &value_type {
+        InferredType::Json => false,
+        InferredType::Scalar(hs) => {
+            hs.len() == 1
+                || hs
+                    .iter()
+                    .all(|t| matches!(t, DataType::Int64 | DataType::Float64))
+        }
+        _ => true,
+    };
+    if !homogeneous {
+        return Ok(None);
+    }
+
+    let entries = Field::new(
+        "entries",
+        DataType::Struct(vec![
+            Field::new("keys", DataType::Utf8, false),
+            Field::new("values", generate_datatype(&value_type, options)?, true),
+        ]),
+        false,
+    );
+    Ok(Some(DataType::Map(Box::new(entries), false)))
+}
+
 fn generate_fields(
     spec: &HashMap<String, InferredType>,
+    options: &InferenceOptions,
 ) -> Result<Vec<Field>, ArrowError> {
     spec.iter()
-        .map(|(k, types)| Ok(Field::new(k, generate_datatype(types)?, true)))
+        .map(|(k, types)| Ok(Field::new(k, generate_datatype(types, options)?, true)))
         .collect()
 }
 
 /// Generate schema from JSON field names and inferred data types
-fn generate_schema(spec: HashMap<String, InferredType>) -> Result<Schema, ArrowError> {
-    Ok(Schema::new(generate_fields(&spec)?))
+fn generate_schema(
+    spec: HashMap<String, InferredType>,
+    options: &InferenceOptions,
+) -> Result<Schema, ArrowError> {
+    Ok(Schema::new(generate_fields(&spec, options)?))
 }
 
 /// JSON file reader that produces a serde_json::Value iterator from a Read trait
 ///
+/// The input may either be line-delimited JSON, with one value per line, or a single
+/// top-level JSON array, in which case each element of the array is yielded in turn.
+/// The format is detected from the first non-whitespace character of the input, and
+/// array elements are parsed one at a time without buffering the whole document.
+///
 /// # Example
 ///
 /// ```
@@ -195,6 +323,20 @@ pub struct ValueIter<'a, R: Read> {
     record_count: usize,
     // reuse line buffer to avoid allocation on each record
     line_buf: String,
+    state: ValueIterState,
+}
+
+/// The layout of the JSON input being read by a [`ValueIter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueIterState {
+    /// No input has been read yet
+    Unknown,
+    /// Newline-delimited JSON values
+    LineDelimited,
+    /// Elements of a top-level JSON array, `first` is true until an element is read
+    Array { first: bool },
+    /// The closing bracket of a top-level array has been read, or decoding failed
+    Finished,
 }
 
 impl<'a, R: Read> ValueIter<'a, R> {
@@ -205,20 +347,33 @@ impl<'a, R: Read> ValueIter<'a, R> {
             max_read_records,
             record_count: 0,
             line_buf: String::new(),
+            state: ValueIterState::Unknown,
         }
     }
-}
 
-impl<'a, R: Read> Iterator for ValueIter<'a, R> {
-    type Item = Result<Value, ArrowError>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(max) = self.max_read_records {
-            if self.record_count >= max {
-                return None;
+    /// Skips any leading whitespace, returning the next byte without consuming it
+    fn peek_non_whitespace(&mut self) -> std::io::Result<Option<u8>> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(idx) => {
+                    let b = buf[idx];
+                    self.reader.consume(idx);
+                    return Ok(Some(b));
+                }
+                None => {
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
             }
         }
+    }
 
+    /// Reads the next non-empty line of line-delimited JSON
+    fn next_line(&mut self) -> Option<Result<Value, ArrowError>> {
         loop {
             self.line_buf.truncate(0);
             match self.reader.read_line(&mut self.line_buf) {
@@ -247,6 +402,99 @@ impl<'a, R: Read> Iterator for ValueIter<'a, R> {
             }
         }
     }
+
+    /// Reads the next element of a top-level JSON array
+    fn next_array_element(&mut self, first: bool) -> Result<Option<Value>, ArrowError> {
+        let read_err =
+            |e| ArrowError::JsonError(format!("Failed to read JSON record: {}", e));
+
+        match self.peek_non_whitespace().map_err(read_err)? {
+            Some(b']') => {
+                self.reader.consume(1);
+                return match self.peek_non_whitespace().map_err(read_err)? {
+                    None => Ok(None),
+                    Some(b) => Err(ArrowError::JsonError(format!(
+                        "Unexpected character '{}' after end of JSON array",
+                        b as char
+                    ))),
+                };
+            }
+            Some(b',') if !first => {
+                self.reader.consume(1);
+            }
+            Some(_) if first => {}
+            Some(b) => {
+                return Err(ArrowError::JsonError(format!(
+                    "Expected ',' or ']' in JSON array, found '{}'",
+                    b as char
+                )))
+            }
+            None => {
+                return Err(ArrowError::JsonError(
+                    "Unexpected end of input in JSON array".to_string(),
+                ))
+            }
+        }
+
+        let mut stream =
+            serde_json::Deserializer::from_reader(&mut *self.reader).into_iter::<Value>();
+        match stream.next() {
+            Some(value) => value
+                .map(Some)
+                .map_err(|e| ArrowError::JsonError(format!("Not valid JSON: {}", e))),
+            None => Err(ArrowError::JsonError(
+                "Unexpected end of input in JSON array".to_string(),
+            )),
+        }
+    }
+}
+
+impl<'a, R: Read> Iterator for ValueIter<'a, R> {
+    type Item = Result<Value, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(max) = self.max_read_records {
+            if self.record_count >= max {
+                return None;
+            }
+        }
+
+        if self.state == ValueIterState::Unknown {
+            self.state = match self.peek_non_whitespace() {
+                Ok(Some(b'[')) => {
+                    self.reader.consume(1);
+                    ValueIterState::Array { first: true }
+                }
+                Ok(_) => ValueIterState::LineDelimited,
+                Err(e) => {
+                    return Some(Err(ArrowError::JsonError(format!(
+                        "Failed to read JSON record: {}",
+                        e
+                    ))));
+                }
+            };
+        }
+
+        match self.state {
+            ValueIterState::Array { first } => match self.next_array_element(first) {
+                Ok(Some(value)) => {
+                    self.state = ValueIterState::Array { first: false };
+                    self.record_count += 1;
+                    Some(Ok(value))
+                }
+                Ok(None) => {
+                    self.state = ValueIterState::Finished;
+                    None
+                }
+                Err(e) => {
+                    self.state = ValueIterState::Finished;
+                    Some(Err(e))
+                }
+            },
+            ValueIterState::Finished => None,
+            _ => self.next_line(),
+        }
+    }
 }
 
 /// Infer the fields of a JSON file by reading the first n records of the file, with
@@ -310,210 +558,102 @@ pub fn infer_json_schema<R: Read>(
     reader: &mut BufReader<R>,
     max_read_records: Option<usize>,
 ) -> Result<Schema, ArrowError> {
-    infer_json_schema_from_iterator(ValueIter::new(reader, max_read_records))
+    infer_json_schema_with_options(reader, max_read_records, &InferenceOptions::new())
 }
 
-fn set_object_scalar_field_type(
-    field_types: &mut HashMap<String, InferredType>,
-    key: &str,
-    ftype: DataType,
-) -> Result<(), ArrowError> {
-    if !field_types.contains_key(key) {
-        field_types.insert(key.to_string(), InferredType::Scalar(HashSet::new()));
-    }
-
-    match field_types.get_mut(key).unwrap() {
-        InferredType::Scalar(hs) => {
-            hs.insert(ftype);
-            Ok(())
-        }
-        // in case of column contains both scalar type and scalar array type, we convert type of
-        // this column to scalar array.
-        scalar_array @ InferredType::Array(_) => {
-            let mut hs = HashSet::new();
-            hs.insert(ftype);
-            scalar_array.merge(InferredType::Scalar(hs))?;
-            Ok(())
-        }
-        t => Err(ArrowError::JsonError(format!(
-            "Expected scalar or scalar array JSON type, found: {:?}",
-            t,
-        ))),
-    }
+/// Infer the fields of a JSON file as [`infer_json_schema`] does, using the
+/// provided [`InferenceOptions`]
+///
+/// # Examples
+/// ```
+/// use std::io::{BufReader, Cursor};
+/// use arrow_json::reader::{infer_json_schema_with_options, InferenceOptions};
+/// use arrow_schema::{DataType, Field};
+///
+/// let json = r#"{"tags": {"a": 1, "b": 2}, "nested": {"x": {"y": 1}, "z": true}}"#;
+/// let mut reader = BufReader::new(Cursor::new(json));
+/// let options = InferenceOptions::new().with_infer_maps(true).with_max_depth(1);
+/// let schema = infer_json_schema_with_options(&mut reader, None, &options).unwrap();
+///
+/// // "tags" has values of a single type, and is inferred as Map<Utf8, Int64>
+/// let tags = schema.field_with_name("tags").unwrap();
+/// assert!(matches!(tags.data_type(), DataType::Map(_, _)));
+///
+/// // "nested.x" is nested too deeply, and is inferred as Utf8
+/// assert_eq!(
+///     schema.field_with_name("nested").unwrap().data_type(),
+///     &DataType::Struct(vec![
+///         Field::new("x", DataType::Utf8, true),
+///         Field::new("z", DataType::Boolean, true),
+///     ])
+/// );
+/// ```
+pub fn infer_json_schema_with_options<R: Read>(
+    reader: &mut BufReader<R>,
+    max_read_records: Option<usize>,
+    options: &InferenceOptions,
+) -> Result<Schema, ArrowError> {
+    infer_json_schema_from_iterator_with_options(
+        ValueIter::new(reader, max_read_records),
+        options,
+    )
 }
 
-fn infer_scalar_array_type(array: &[Value]) -> Result<InferredType, ArrowError> {
-    let mut hs = HashSet::new();
-
-    for v in array {
-        match v {
-            Value::Null => {}
-            Value::Number(n) => {
-                if n.is_i64() {
-                    hs.insert(DataType::Int64);
-                } else {
-                    hs.insert(DataType::Float64);
-                }
-            }
-            Value::Bool(_) => {
-                hs.insert(DataType::Boolean);
-            }
-            Value::String(_) => {
-                hs.insert(DataType::Utf8);
-            }
-            Value::Array(_) | Value::Object(_) => {
-                return Err(ArrowError::JsonError(format!(
-                    "Expected scalar value for scalar array, got: {:?}",
-                    v
-                )));
-            }
+/// Infer the type of a JSON value found `depth` levels below the top-level record
+fn infer_value_type(
+    value: &Value,
+    depth: usize,
+    options: &InferenceOptions,
+) -> Result<InferredType, ArrowError> {
+    let scalar = |t: DataType| InferredType::Scalar(std::iter::once(t).collect());
+    Ok(match value {
+        Value::Null => InferredType::Any,
+        Value::Bool(_) => scalar(DataType::Boolean),
+        Value::Number(n) => match n.is_i64() {
+            true => scalar(DataType::Int64),
+            false => scalar(DataType::Float64),
+        },
+        Value::String(_) => scalar(DataType::Utf8),
+        Value::Array(_) | Value::Object(_)
+            if options.max_depth.map(|max| depth > max).unwrap_or(false) =>
+        {
+            InferredType::Json
         }
-    }
-
-    Ok(InferredType::Scalar(hs))
-}
-
-fn infer_nested_array_type(array: &[Value]) -> Result<InferredType, ArrowError> {
-    let mut inner_ele_type = InferredType::Any;
-
-    for v in array {
-        match v {
-            Value::Array(inner_array) => {
-                inner_ele_type.merge(infer_array_element_type(inner_array)?)?;
-            }
-            x => {
-                return Err(ArrowError::JsonError(format!(
-                    "Got non array element in nested array: {:?}",
-                    x
-                )));
+        Value::Array(array) => {
+            let mut ele_type = InferredType::Any;
+            for v in array {
+                ele_type.merge(
+                    infer_value_type(v, depth + 1, options)?,
+                    options.coerce_conflicts,
+                )?;
             }
+            InferredType::Array(Box::new(ele_type))
         }
-    }
-
-    Ok(InferredType::Array(Box::new(inner_ele_type)))
-}
-
-fn infer_struct_array_type(array: &[Value]) -> Result<InferredType, ArrowError> {
-    let mut field_types = HashMap::new();
-
-    for v in array {
-        match v {
-            Value::Object(map) => {
-                collect_field_types_from_object(&mut field_types, map)?;
-            }
-            _ => {
-                return Err(ArrowError::JsonError(format!(
-                    "Expected struct value for struct array, got: {:?}",
-                    v
-                )));
-            }
+        Value::Object(map) => {
+            let mut field_types = HashMap::new();
+            collect_field_types_from_object(&mut field_types, map, depth + 1, options)?;
+            InferredType::Object(field_types)
         }
-    }
-
-    Ok(InferredType::Object(field_types))
-}
-
-fn infer_array_element_type(array: &[Value]) -> Result<InferredType, ArrowError> {
-    match array.iter().take(1).next() {
-        None => Ok(InferredType::Any), // empty array, return any type that can be updated later
-        Some(a) => match a {
-            Value::Array(_) => infer_nested_array_type(array),
-            Value::Object(_) => infer_struct_array_type(array),
-            _ => infer_scalar_array_type(array),
-        },
-    }
+    })
 }
 
 fn collect_field_types_from_object(
     field_types: &mut HashMap<String, InferredType>,
     map: &JsonMap<String, Value>,
+    depth: usize,
+    options: &InferenceOptions,
 ) -> Result<(), ArrowError> {
     for (k, v) in map {
-        match v {
-            Value::Array(array) => {
-                let ele_type = infer_array_element_type(array)?;
-
-                if !field_types.contains_key(k) {
-                    match ele_type {
-                        InferredType::Scalar(_) => {
-                            field_types.insert(
-                                k.to_string(),
-                                InferredType::Array(Box::new(InferredType::Scalar(
-                                    HashSet::new(),
-                                ))),
-                            );
-                        }
-                        InferredType::Object(_) => {
-                            field_types.insert(
-                                k.to_string(),
-                                InferredType::Array(Box::new(InferredType::Object(
-                                    HashMap::new(),
-                                ))),
-                            );
-                        }
-                        InferredType::Any | InferredType::Array(_) => {
-                            // set inner type to any for nested array as well
-                            // so it can be updated properly from subsequent type merges
-                            field_types.insert(
-                                k.to_string(),
-                                InferredType::Array(Box::new(InferredType::Any)),
-                            );
-                        }
-                    }
-                }
-
-                match field_types.get_mut(k).unwrap() {
-                    InferredType::Array(inner_type) => {
-                        inner_type.merge(ele_type)?;
-                    }
-                    // in case of column contains both scalar type and scalar array type, we
-                    // convert type of this column to scalar array.
-                    field_type @ InferredType::Scalar(_) => {
-                        field_type.merge(ele_type)?;
-                        *field_type = InferredType::Array(Box::new(field_type.clone()));
-                    }
-                    t => {
-                        return Err(ArrowError::JsonError(format!(
-                            "Expected array json type, found: {:?}",
-                            t,
-                        )));
-                    }
-                }
-            }
-            Value::Bool(_) => {
-                set_object_scalar_field_type(field_types, k, DataType::Boolean)?;
-            }
-            Value::Null => {
-                // do nothing, we treat json as nullable by default when
-                // inferring
-            }
-            Value::Number(n) => {
-                if n.is_f64() {
-                    set_object_scalar_field_type(field_types, k, DataType::Float64)?;
-                } else {
-                    // default to i64
-                    set_object_scalar_field_type(field_types, k, DataType::Int64)?;
-                }
-            }
-            Value::String(_) => {
-                set_object_scalar_field_type(field_types, k, DataType::Utf8)?;
-            }
-            Value::Object(inner_map) => {
-                if !field_types.contains_key(k) {
-                    field_types
-                        .insert(k.to_string(), InferredType::Object(HashMap::new()));
-                }
-                match field_types.get_mut(k).unwrap() {
-                    InferredType::Object(inner_field_types) => {
-                        collect_field_types_from_object(inner_field_types, inner_map)?;
-                    }
-                    t => {
-                        return Err(ArrowError::JsonError(format!(
-                            "Expected object json type, found: {:?}",
-                            t,
-                        )));
-                    }
-                }
+        // we treat json as nullable by default when inferring, so nulls
+        // do not contribute a type
+        if v.is_null() {
+            continue;
+        }
+        let value_type = infer_value_type(v, depth, options)?;
+        match field_types.get_mut(k) {
+            Some(field_type) => field_type.merge(value_type, options.coerce_conflicts)?,
+            None => {
+                field_types.insert(k.to_string(), value_type);
             }
         }
     }
@@ -526,7 +666,10 @@ fn collect_field_types_from_object(
 /// The following type coercion logic is implemented:
 /// * `Int64` and `Float64` are converted to `Float64`
 /// * Lists and scalars are coerced to a list of a compatible scalar
-/// * All other cases are coerced to `Utf8` (String)
+/// * Objects are merged into a struct containing the fields of both
+/// * All other scalar types are coerced to `Utf8` (String)
+/// * Conflicts between objects, lists and scalars return an error, unless
+///   [`InferenceOptions::with_coerce_conflicts`] is set
 ///
 /// Note that the above coercion logic is different from what Spark has, where it would default to
 /// String type in case of List and Scalar values appeared in the same field.
@@ -535,6 +678,20 @@ fn collect_field_types_from_object(
 /// interpreted as Strings. We should match Spark's behavior once we added more JSON parsing
 /// kernels in the future.
 pub fn infer_json_schema_from_iterator<I>(value_iter: I) -> Result<Schema, ArrowError>
+where
+    I: Iterator<Item = Result<Value, ArrowError>>,
+{
+    infer_json_schema_from_iterator_with_options(value_iter, &InferenceOptions::new())
+}
+
+/// Infer the fields of a JSON file by reading all items from the JSON Value Iterator,
+/// using the provided [`InferenceOptions`]
+///
+/// See [`infer_json_schema_from_iterator`] for the type coercion rules
+pub fn infer_json_schema_from_iterator_with_options<I>(
+    value_iter: I,
+    options: &InferenceOptions,
+) -> Result<Schema, ArrowError>
 where
     I: Iterator<Item = Result<Value, ArrowError>>,
 {
@@ -543,7 +700,7 @@ where
     for record in value_iter {
         match record? {
             Value::Object(map) => {
-                collect_field_types_from_object(&mut field_types, &map)?;
+                collect_field_types_from_object(&mut field_types, &map, 1, options)?;
             }
             value => {
                 return Err(ArrowError::JsonError(format!(
@@ -554,7 +711,57 @@ where
         };
     }
 
-    generate_schema(field_types)
+    generate_schema(field_types, options)
+}
+
+/// Deserializes the rows of [`RecordBatch`]es into `T`.
+///
+/// Each row is converted directly from the arrays of the batch to a
+/// [`serde_json::Value`] object, as by
+/// [`record_batches_to_json_rows`](crate::writer::record_batches_to_json_rows),
+/// which is then deserialized into `T`. Rows are never written as JSON text.
+/// Null values are omitted from the object, and so should be deserialized into
+/// `Option` fields.
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{Int32Array, RecordBatch, StringArray};
+/// # use arrow_schema::{DataType, Field, Schema};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Row {
+///     a: i32,
+///     b: Option<String>,
+/// }
+///
+/// let schema = Schema::new(vec![
+///     Field::new("a", DataType::Int32, false),
+///     Field::new("b", DataType::Utf8, true),
+/// ]);
+/// let a = Int32Array::from(vec![1, 2]);
+/// let b = StringArray::from(vec![Some("foo"), None]);
+/// let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)])
+///     .unwrap();
+///
+/// let rows: Vec<Row> = arrow_json::reader::deserialize_record_batches(&[batch]).unwrap();
+/// assert_eq!(
+///     rows,
+///     vec![Row { a: 1, b: Some("foo".to_string()) }, Row { a: 2, b: None }]
+/// );
+/// ```
+pub fn deserialize_record_batches<T: DeserializeOwned>(
+    batches: &[RecordBatch],
+) -> Result<Vec<T>, ArrowError> {
+    crate::writer::record_batches_to_json_rows(batches)?
+        .into_iter()
+        .map(|row| {
+            serde_json::from_value(Value::Object(row)).map_err(|e| {
+                ArrowError::JsonError(format!("Failed to deserialize row: {}", e))
+            })
+        })
+        .collect()
 }
 
 /// JSON values to Arrow record batch decoder.
@@ -601,6 +808,15 @@ pub struct DecoderOptions {
     projection: Option<Vec<String>>,
     /// optional HashMap of column name to its format string
     format_strings: Option<HashMap<String, String>>,
+    /// How `Binary` and `FixedSizeBinary` values are encoded, defaults to UTF-8 strings
+    binary_encoding: BinaryEncoding,
+    /// Optional format string for timestamp columns without an entry in `format_strings`
+    timestamp_format: Option<String>,
+    /// Optional timezone in which to interpret timestamps without an offset,
+    /// defaults to UTC
+    timezone: Option<String>,
+    /// Read values which are not strings into `Utf8` columns as their JSON text
+    json_text: bool,
 }
 
 impl Default for DecoderOptions {
@@ -609,6 +825,10 @@ impl Default for DecoderOptions {
             batch_size: 1024,
             projection: None,
             format_strings: None,
+            binary_encoding: BinaryEncoding::default(),
+            timestamp_format: None,
+            timezone: None,
+            json_text: false,
         }
     }
 }
@@ -639,6 +859,41 @@ impl DecoderOptions {
         self.format_strings = Some(format_strings);
         self
     }
+
+    /// Set how `Binary` and `FixedSizeBinary` values are encoded as JSON strings
+    pub fn with_binary_encoding(mut self, binary_encoding: BinaryEncoding) -> Self {
+        self.binary_encoding = binary_encoding;
+        self
+    }
+
+    /// Set the `chrono` format string used to parse timestamp strings, e.g.
+    /// `%d/%m/%Y %H:%M`. Entries in [`Self::with_format_strings`] take precedence.
+    ///
+    /// Strings that do not match the format are parsed as RFC3339 or one of its
+    /// variants, as are all strings if no format is set
+    pub fn with_timestamp_format(mut self, timestamp_format: String) -> Self {
+        self.timestamp_format = Some(timestamp_format);
+        self
+    }
+
+    /// Set the timezone in which to interpret timestamp strings without an offset,
+    /// such as `+02:00`, or `Europe/Paris` if the `chrono-tz` feature of
+    /// `arrow-array` is enabled. Defaults to UTC.
+    ///
+    /// Timestamps are always stored relative to the UNIX epoch in UTC, this does
+    /// not change the timezone of the schema
+    pub fn with_timezone(mut self, timezone: String) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    /// Set whether numbers, booleans, objects and arrays are read into `Utf8`
+    /// columns as their JSON text, e.g. `{"a":1}`. Defaults to `false`, in which
+    /// case such values are read as null
+    pub fn with_json_text(mut self, json_text: bool) -> Self {
+        self.json_text = json_text;
+        self
+    }
 }
 
 impl Decoder {
@@ -703,8 +958,11 @@ impl Decoder {
             return Ok(None);
         }
 
-        let rows = &rows[..];
+        self.build_batch(&rows).map(Some)
+    }
 
+    /// Builds a [`RecordBatch`] from `rows` of [`Value::Object`]
+    pub(crate) fn build_batch(&self, rows: &[Value]) -> Result<RecordBatch, ArrowError> {
         let arrays =
             self.build_struct_array(rows, self.schema.fields(), &self.options.projection);
 
@@ -729,7 +987,6 @@ impl Decoder {
                     .with_match_field_names(true)
                     .with_row_count(Some(rows.len())),
             )
-            .map(Some)
         })
     }
 
@@ -984,6 +1241,46 @@ impl Decoder {
         Ok(Arc::new(builder.finish()))
     }
 
+    fn build_timestamp_array<T: ArrowTimestampType<Native = i64>>(
+        &self,
+        rows: &[Value],
+        col_name: &str,
+    ) -> Result<ArrayRef, ArrowError> {
+        let format = self
+            .options
+            .format_strings
+            .as_ref()
+            .and_then(|fmts| fmts.get(col_name))
+            .or(self.options.timestamp_format.as_ref())
+            .map(|format| format.as_str());
+        let tz = self
+            .options
+            .timezone
+            .as_deref()
+            .map(Tz::from_str)
+            .transpose()?;
+        let divisor = match T::get_time_unit() {
+            TimeUnit::Second => 1_000_000_000,
+            TimeUnit::Millisecond => 1_000_000,
+            TimeUnit::Microsecond => 1_000,
+            TimeUnit::Nanosecond => 1,
+        };
+
+        Ok(Arc::new(
+            rows.iter()
+                .map(|row| {
+                    row.get(col_name).and_then(|value| match value {
+                        Value::String(s) => parse_timestamp_nanos(s, format, tz.as_ref())
+                            .map(|nanos| nanos / divisor),
+                        v if v.is_i64() => v.as_i64(),
+                        v if v.is_u64() => v.as_u64().and_then(num::cast::cast),
+                        v => v.as_f64().and_then(num::cast::cast),
+                    })
+                })
+                .collect::<PrimitiveArray<T>>(),
+        ))
+    }
+
     fn build_primitive_array<T: ArrowPrimitiveType + Parser>(
         &self,
         rows: &[Value],
@@ -1022,6 +1319,69 @@ impl Decoder {
         ))
     }
 
+    fn build_binary_array(
+        &self,
+        rows: &[Value],
+        col_name: &str,
+    ) -> Result<ArrayRef, ArrowError> {
+        let encoding = self.options.binary_encoding;
+        let values = rows
+            .iter()
+            .map(|row| {
+                row.get(col_name)
+                    .and_then(|value| value.as_str())
+                    .map(|s| encoding.decode(s))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+        Ok(Arc::new(BinaryArray::from_iter(values)))
+    }
+
+    fn build_fixed_size_binary_array(
+        &self,
+        rows: &[Value],
+        col_name: &str,
+        size: i32,
+    ) -> Result<ArrayRef, ArrowError> {
+        let encoding = self.options.binary_encoding;
+        let mut builder = FixedSizeBinaryBuilder::with_capacity(rows.len(), size);
+        for row in rows {
+            match row.get(col_name).and_then(|value| value.as_str()) {
+                Some(s) => builder.append_value(encoding.decode(s)?)?,
+                None => builder.append_null(),
+            }
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+
+    /// Reads decimal values from JSON numbers or strings, truncating any digits
+    /// beyond `scale`
+    fn build_decimal_array<T>(
+        &self,
+        rows: &[Value],
+        col_name: &str,
+        precision: u8,
+        scale: i8,
+    ) -> Result<ArrayRef, ArrowError>
+    where
+        T: DecimalType,
+        T::Native: ArrowNativeTypeOp,
+    {
+        let values = rows
+            .iter()
+            .map(|row| match row.get(col_name) {
+                Some(Value::String(s)) => {
+                    parse_decimal::<T>(s, precision, scale).map(Some)
+                }
+                Some(Value::Number(n)) => {
+                    parse_decimal::<T>(&n.to_string(), precision, scale).map(Some)
+                }
+                _ => Ok(None),
+            })
+            .collect::<Result<PrimitiveArray<T>, ArrowError>>()?;
+        Ok(Arc::new(values.with_precision_and_scale(precision, scale)?))
+    }
+
     /// Build a nested GenericListArray from a list of unnested `Value`s
     fn build_nested_list_array<OffsetSize: OffsetSizeTrait>(
         &self,
@@ -1241,25 +1601,24 @@ impl Decoder {
                     DataType::UInt8 => {
                         self.build_primitive_array::<UInt8Type>(rows, field.name())
                     }
-                    // TODO: this is incomplete
                     DataType::Timestamp(unit, _) => match unit {
                         TimeUnit::Second => self
-                            .build_primitive_array::<TimestampSecondType>(
+                            .build_timestamp_array::<TimestampSecondType>(
                                 rows,
                                 field.name(),
                             ),
                         TimeUnit::Microsecond => self
-                            .build_primitive_array::<TimestampMicrosecondType>(
+                            .build_timestamp_array::<TimestampMicrosecondType>(
                                 rows,
                                 field.name(),
                             ),
                         TimeUnit::Millisecond => self
-                            .build_primitive_array::<TimestampMillisecondType>(
+                            .build_timestamp_array::<TimestampMillisecondType>(
                                 rows,
                                 field.name(),
                             ),
                         TimeUnit::Nanosecond => self
-                            .build_primitive_array::<TimestampNanosecondType>(
+                            .build_timestamp_array::<TimestampNanosecondType>(
                                 rows,
                                 field.name(),
                             ),
@@ -1302,22 +1661,41 @@ impl Decoder {
                             t
                         ))),
                     },
-                    DataType::Utf8 => Ok(Arc::new(
+                    DataType::Utf8 if self.options.json_text => Ok(Arc::new(
                         rows.iter()
                             .map(|row| {
                                 let maybe_value = row.get(field.name());
-                                maybe_value.and_then(|value| value.as_str())
+                                maybe_value.and_then(json_value_as_string)
                             })
                             .collect::<StringArray>(),
-                    ) as ArrayRef),
-                    DataType::Binary => Ok(Arc::new(
+                    )
+                        as ArrayRef),
+                    DataType::Utf8 => Ok(Arc::new(
                         rows.iter()
                             .map(|row| {
                                 let maybe_value = row.get(field.name());
                                 maybe_value.and_then(|value| value.as_str())
                             })
-                            .collect::<BinaryArray>(),
+                            .collect::<StringArray>(),
                     ) as ArrayRef),
+                    DataType::Binary => self.build_binary_array(rows, field.name()),
+                    DataType::FixedSizeBinary(size) => {
+                        self.build_fixed_size_binary_array(rows, field.name(), *size)
+                    }
+                    DataType::Decimal128(precision, scale) => self
+                        .build_decimal_array::<Decimal128Type>(
+                            rows,
+                            field.name(),
+                            *precision,
+                            *scale,
+                        ),
+                    DataType::Decimal256(precision, scale) => self
+                        .build_decimal_array::<Decimal256Type>(
+                            rows,
+                            field.name(),
+                            *precision,
+                            *scale,
+                        ),
                     DataType::List(ref list_field) => {
                         match list_field.data_type() {
                             DataType::Dictionary(ref key_ty, _) => {
@@ -1550,6 +1928,133 @@ impl Decoder {
     }
 }
 
+/// Returns the nanoseconds since the UNIX epoch of `naive` in `tz`, or UTC if `None`
+fn localize_timestamp(naive: NaiveDateTime, tz: Option<&Tz>) -> Option<i64> {
+    match tz {
+        Some(tz) => Some(tz.from_local_datetime(&naive).earliest()?.timestamp_nanos()),
+        None => Some(naive.timestamp_nanos()),
+    }
+}
+
+/// Parses a timestamp string into nanoseconds since the UNIX epoch, first with
+/// `format` if provided, and then with the formats supported by
+/// [`string_to_timestamp_nanos`]. Timestamps without an offset are interpreted
+/// in `tz`, or UTC if `None`
+fn parse_timestamp_nanos(s: &str, format: Option<&str>, tz: Option<&Tz>) -> Option<i64> {
+    if let Some(format) = format {
+        if let Ok(ts) = DateTime::parse_from_str(s, format) {
+            return Some(ts.timestamp_nanos());
+        }
+        if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+            return localize_timestamp(naive, tz);
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(s, format) {
+            return localize_timestamp(date.and_hms_opt(0, 0, 0)?, tz);
+        }
+    }
+
+    // Timestamps without an offset are interpreted in the timezone, timestamps
+    // with an offset fail to parse with these formats and are handled below
+    if let Some(tz) = tz {
+        const NAIVE_FORMATS: [&str; 4] = [
+            "%Y-%m-%dT%H:%M:%S%.f",
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%d %H:%M:%S%.f",
+            "%Y-%m-%d %H:%M:%S",
+        ];
+        for format in NAIVE_FORMATS {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+                return localize_timestamp(naive, Some(tz));
+            }
+        }
+    }
+
+    string_to_timestamp_nanos(s).ok()
+}
+
+/// Parses a decimal string, optionally with a fractional part and exponent, into
+/// the integer representation of a decimal with the given `precision` and `scale`
+fn parse_decimal<T>(s: &str, precision: u8, scale: i8) -> Result<T::Native, ArrowError>
+where
+    T: DecimalType,
+    T::Native: ArrowNativeTypeOp,
+{
+    let invalid = || {
+        ArrowError::JsonError(format!(
+            "Cannot parse '{}' as {}({}, {})",
+            s,
+            T::PREFIX,
+            precision,
+            scale
+        ))
+    };
+
+    let (negative, unsigned) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(idx) => (
+            &unsigned[..idx],
+            unsigned[idx + 1..].parse::<i32>().map_err(|_| invalid())?,
+        ),
+        None => (unsigned, 0),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(invalid());
+    }
+
+    let digits = int_part.bytes().chain(frac_part.bytes());
+    if !digits.clone().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    // the number of digits to append (if positive) or truncate (if negative),
+    // computed as an i64 as it cannot then overflow for any i32 exponent
+    let shift = scale as i64 + exponent as i64 - frac_part.len() as i64;
+    let len = int_part.len() + frac_part.len();
+    let keep = match shift {
+        s if s < 0 => {
+            len.saturating_sub(usize::try_from(s.unsigned_abs()).unwrap_or(usize::MAX))
+        }
+        _ => len,
+    };
+
+    let overflow = |_| {
+        ArrowError::JsonError(format!(
+            "Overflow parsing '{}' as {}({}, {})",
+            s,
+            T::PREFIX,
+            precision,
+            scale
+        ))
+    };
+    let ten = T::Native::from_usize(10).unwrap();
+    let mut value = T::Native::default();
+    for b in digits.take(keep) {
+        let digit = T::Native::from_usize((b - b'0') as usize).unwrap();
+        value = value
+            .mul_checked(ten)
+            .and_then(|v| v.add_checked(digit))
+            .map_err(overflow)?;
+    }
+    if shift > 0 {
+        // shift is at most i8::MAX + i32::MAX, and so fits in a u32
+        value = ten
+            .pow_checked(shift as u32)
+            .and_then(|m| value.mul_checked(m))
+            .map_err(overflow)?;
+    }
+    if negative {
+        value = value.neg_wrapping();
+    }
+
+    T::validate_decimal_precision(value, precision)?;
+    Ok(value)
+}
+
 /// Reads a JSON value as a string, regardless of its type.
 /// This is useful if the expected datatype is a string, in which case we preserve
 /// all the values regardless of they type.
@@ -1607,11 +2112,16 @@ fn flatten_json_string_values(values: &[Value]) -> Vec<Option<String>> {
         .collect::<Vec<Option<_>>>()
 }
 /// JSON file reader
+///
+/// Reads either line-delimited JSON or a single top-level JSON array of objects,
+/// see [`ValueIter`] for details
 #[derive(Debug)]
 pub struct Reader<R: Read> {
     reader: BufReader<R>,
     /// JSON value decoder
     decoder: Decoder,
+    /// Layout of the input, preserved across batches
+    state: ValueIterState,
 }
 
 impl<R: Read> Reader<R> {
@@ -1634,6 +2144,7 @@ impl<R: Read> Reader<R> {
         Self {
             reader,
             decoder: Decoder::new(schema, options),
+            state: ValueIterState::Unknown,
         }
     }
 
@@ -1646,8 +2157,11 @@ impl<R: Read> Reader<R> {
     /// Read the next batch of records
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
-        self.decoder
-            .next_batch(&mut ValueIter::new(&mut self.reader, None))
+        let mut values = ValueIter::new(&mut self.reader, None);
+        values.state = self.state;
+        let batch = self.decoder.next_batch(&mut values);
+        self.state = values.state;
+        batch
     }
 }
 
@@ -1665,6 +2179,8 @@ pub struct ReaderBuilder {
     max_records: Option<usize>,
     /// Options for json decoder
     options: DecoderOptions,
+    /// Options for schema inference
+    inference_options: InferenceOptions,
 }
 
 impl ReaderBuilder {
@@ -1706,6 +2222,12 @@ impl ReaderBuilder {
         self
     }
 
+    /// Set the options used when inferring the schema
+    pub fn with_inference_options(mut self, inference_options: InferenceOptions) -> Self {
+        self.inference_options = inference_options;
+        self
+    }
+
     /// Set the batch size (number of records to load at one time)
     pub fn with_batch_size(mut self, batch_size: usize) -> Self {
         self.options = self.options.with_batch_size(batch_size);
@@ -1727,6 +2249,33 @@ impl ReaderBuilder {
         self
     }
 
+    /// Set how `Binary` and `FixedSizeBinary` values are encoded as JSON strings
+    pub fn with_binary_encoding(mut self, binary_encoding: BinaryEncoding) -> Self {
+        self.options = self.options.with_binary_encoding(binary_encoding);
+        self
+    }
+
+    /// Set whether values which are not strings are read into `Utf8` columns as
+    /// their JSON text, see [`DecoderOptions::with_json_text`]
+    pub fn with_json_text(mut self, json_text: bool) -> Self {
+        self.options = self.options.with_json_text(json_text);
+        self
+    }
+
+    /// Set the format string used to parse timestamp strings, see
+    /// [`DecoderOptions::with_timestamp_format`]
+    pub fn with_timestamp_format(mut self, timestamp_format: String) -> Self {
+        self.options = self.options.with_timestamp_format(timestamp_format);
+        self
+    }
+
+    /// Set the timezone in which to interpret timestamp strings without an offset,
+    /// see [`DecoderOptions::with_timezone`]
+    pub fn with_timezone(mut self, timezone: String) -> Self {
+        self.options = self.options.with_timezone(timezone);
+        self
+    }
+
     /// Create a new `Reader` from the `ReaderBuilder`
     pub fn build<R>(self, source: R) -> Result<Reader<R>, ArrowError>
     where
@@ -1737,10 +2286,16 @@ impl ReaderBuilder {
         // check if schema should be inferred
         let schema = match self.schema {
             Some(schema) => schema,
-            None => Arc::new(infer_json_schema_from_seekable(
-                &mut buf_reader,
-                self.max_records,
-            )?),
+            None => {
+                let schema = infer_json_schema_with_options(
+                    &mut buf_reader,
+                    self.max_records,
+                    &self.inference_options,
+                );
+                // return the reader seek back to the start
+                buf_reader.seek(SeekFrom::Start(0))?;
+                Arc::new(schema?)
+            }
         };
 
         Ok(Reader::from_buf_reader(buf_reader, schema, self.options))
@@ -1758,6 +2313,7 @@ impl<R: Read> Iterator for Reader<R> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow_array::cast::{as_map_array, as_primitive_array, as_string_array};
     use arrow_buffer::ToByteSlice;
     use arrow_schema::DataType::{Dictionary, List};
     use flate2::read::GzDecoder;
@@ -2642,6 +3198,17 @@ mod tests {
 
     #[test]
     fn test_row_type_validation() {
+        let builder = ReaderBuilder::new().infer_schema(None).with_batch_size(64);
+        let json_content = "
+        \"world\"
+        [1, \"hello\"]";
+        let re = builder.build(Cursor::new(json_content));
+        assert_eq!(
+            re.err().unwrap().to_string(),
+            r#"Json error: Expected JSON record to be an object, found String("world")"#,
+        );
+
+        // a leading array is read as a top-level array of records
         let builder = ReaderBuilder::new().infer_schema(None).with_batch_size(64);
         let json_content = "
         [1, \"hello\"]
@@ -2649,7 +3216,7 @@ mod tests {
         let re = builder.build(Cursor::new(json_content));
         assert_eq!(
             re.err().unwrap().to_string(),
-            r#"Json error: Expected JSON record to be an object, found Array [Number(1), String("hello")]"#,
+            r#"Json error: Expected JSON record to be an object, found Number(1)"#,
         );
     }
 
@@ -3022,6 +3589,283 @@ mod tests {
         assert_eq!(inferred_schema, schema);
     }
 
+    #[test]
+    fn test_json_infer_schema_maps() {
+        let values = vec![
+            serde_json::json!({"m": {"a": 1, "b": 2}, "s": {"a": 1, "b": "x"}}),
+            serde_json::json!({"m": {"c": 1.5}, "s": {"a": 2}}),
+        ];
+
+        // without map inference, objects are inferred as structs
+        let inferred_schema =
+            infer_json_schema_from_iterator(values.clone().into_iter().map(Ok)).unwrap();
+        assert_eq!(
+            inferred_schema.field(0).data_type(),
+            &DataType::Struct(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Int64, true),
+                Field::new("c", DataType::Float64, true),
+            ])
+        );
+
+        let options = InferenceOptions::new().with_infer_maps(true);
+        let inferred_schema = infer_json_schema_from_iterator_with_options(
+            values.into_iter().map(Ok),
+            &options,
+        )
+        .unwrap();
+        let expected = Schema::new(vec![
+            Field::new(
+                "m",
+                DataType::Map(
+                    Box::new(Field::new(
+                        "entries",
+                        DataType::Struct(vec![
+                            Field::new("keys", DataType::Utf8, false),
+                            Field::new("values", DataType::Float64, true),
+                        ]),
+                        false,
+                    )),
+                    false,
+                ),
+                true,
+            ),
+            Field::new(
+                "s",
+                DataType::Struct(vec![
+                    Field::new("a", DataType::Int64, true),
+                    Field::new("b", DataType::Utf8, true),
+                ]),
+                true,
+            ),
+        ]);
+        assert_eq!(inferred_schema, expected);
+
+        let json = r#"
+            {"m": {"a": 1, "b": 2}}
+            {"m": {"c": 1.5}}
+            {"m": null}
+        "#;
+        let mut reader = ReaderBuilder::new()
+            .infer_schema(None)
+            .with_inference_options(options)
+            .build(Cursor::new(json))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        let map = as_map_array(batch.column(0));
+        assert_eq!(map.value_offsets(), &[0, 2, 3, 3]);
+        assert!(map.is_null(2));
+        let values = map.values();
+        let values = as_primitive_array::<Float64Type>(&values);
+        assert_eq!(values.values(), &[1.0, 2.0, 1.5]);
+    }
+
+    #[test]
+    fn test_json_infer_schema_max_depth() {
+        let values = vec![serde_json::json!({
+            "a": 1,
+            "b": {"c": {"d": 1}, "e": [1, 2]},
+            "f": [[1], [2]],
+        })];
+
+        let options = InferenceOptions::new().with_max_depth(0);
+        let inferred_schema = infer_json_schema_from_iterator_with_options(
+            values.clone().into_iter().map(Ok),
+            &options,
+        )
+        .unwrap();
+        let expected = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("f", DataType::Utf8, true),
+        ]);
+        assert_eq!(inferred_schema, expected);
+
+        let options = InferenceOptions::new().with_max_depth(1);
+        let inferred_schema = infer_json_schema_from_iterator_with_options(
+            values.into_iter().map(Ok),
+            &options,
+        )
+        .unwrap();
+        let expected = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new(
+                "b",
+                DataType::Struct(vec![
+                    Field::new("c", DataType::Utf8, true),
+                    Field::new("e", DataType::Utf8, true),
+                ]),
+                true,
+            ),
+            Field::new(
+                "f",
+                DataType::List(Box::new(Field::new("item", DataType::Utf8, true))),
+                true,
+            ),
+        ]);
+        assert_eq!(inferred_schema, expected);
+
+        let json = r#"{"a": 1, "b": {"c": {"d": 1}, "e": [1, 2]}}"#;
+        let mut reader = ReaderBuilder::new()
+            .infer_schema(None)
+            .with_inference_options(InferenceOptions::new().with_max_depth(0))
+            .with_json_text(true)
+            .build(Cursor::new(json))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        let b = as_string_array(batch.column(1));
+        assert_eq!(b.value(0), r#"{"c":{"d":1},"e":[1,2]}"#);
+    }
+
+    #[test]
+    fn test_json_infer_schema_max_depth_order() {
+        let rows = vec![
+            serde_json::json!({"a": 1, "b": [1]}),
+            serde_json::json!({"a": {"c": 1}, "b": {"d": 2}}),
+        ];
+        let expected = Schema::new(vec![
+            Field::new("a", DataType::Utf8, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+
+        // values beyond the maximum depth merge with other values in either order
+        let options = InferenceOptions::new().with_max_depth(0);
+        for rows in [rows.clone(), rows.into_iter().rev().collect()] {
+            let inferred_schema = infer_json_schema_from_iterator_with_options(
+                rows.into_iter().map(Ok),
+                &options,
+            )
+            .unwrap();
+            assert_eq!(inferred_schema, expected);
+        }
+    }
+
+    #[test]
+    fn test_json_infer_schema_promotion() {
+        let json = r#"
+            {"a": 1, "b": {"c": 1}, "c": true}
+            {"a": 2.5, "b": 4, "c": 1}
+            {"a": [3], "b": [1, 2], "c": "foo"}
+        "#;
+
+        // conflicting types are an error by default
+        let err = ReaderBuilder::new()
+            .infer_schema(None)
+            .build(Cursor::new(json))
+            .unwrap_err();
+        assert!(err.to_string().contains("Incompatible type"), "{}", err);
+
+        let mut reader = ReaderBuilder::new()
+            .infer_schema(None)
+            .with_inference_options(InferenceOptions::new().with_coerce_conflicts(true))
+            .with_json_text(true)
+            .build(Cursor::new(json))
+            .unwrap();
+        let expected = Schema::new(vec![
+            Field::new(
+                "a",
+                DataType::List(Box::new(Field::new("item", DataType::Float64, true))),
+                true,
+            ),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Utf8, true),
+        ]);
+        assert_eq!(reader.schema().as_ref(), &expected);
+
+        let batch = reader.next().unwrap().unwrap();
+        let b = as_string_array(batch.column(1));
+        assert_eq!(
+            b.iter().collect::<Vec<_>>(),
+            vec![Some(r#"{"c":1}"#), Some("4"), Some("[1,2]")]
+        );
+        let c = as_string_array(batch.column(2));
+        assert_eq!(
+            c.iter().collect::<Vec<_>>(),
+            vec![Some("true"), Some("1"), Some("foo")]
+        );
+
+        // scalars of different types are coerced to Utf8 as before, and by default
+        // only string values are read
+        let json = r#"
+            {"c": true}
+            {"c": "foo"}
+        "#;
+        let mut reader = ReaderBuilder::new()
+            .infer_schema(None)
+            .build(Cursor::new(json))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        let c = as_string_array(batch.column(0));
+        assert_eq!(c.iter().collect::<Vec<_>>(), vec![None, Some("foo")]);
+    }
+
+    #[test]
+    fn test_timestamp_format_and_timezone() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Timestamp(TimeUnit::Second, None), true),
+            Field::new("b", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+        ]));
+        let json = r#"
+        {"a": "31/01/2022 10:00", "b": "2022-01-31T10:00:00.5"}
+        {"a": "2022-01-31T10:00:00Z", "b": "2022-01-31 10:00:00+01:00"}
+        {"a": "2022-01-31", "b": 1000}
+        {"a": "not a timestamp"}
+        "#;
+        let read = |options: DecoderOptions| {
+            let mut reader = Reader::new(Cursor::new(json), schema.clone(), options);
+            let batch = reader.next().unwrap().unwrap();
+            let a = as_primitive_array::<TimestampSecondType>(batch.column(0));
+            let b = as_primitive_array::<TimestampMillisecondType>(batch.column(1));
+            (a.iter().collect::<Vec<_>>(), b.iter().collect::<Vec<_>>())
+        };
+
+        // 2022-01-31T10:00:00Z
+        let ts = 1643623200;
+
+        let (a, b) = read(DecoderOptions::new());
+        assert_eq!(a, vec![None, Some(ts), None, None]);
+        assert_eq!(
+            b,
+            vec![
+                Some(ts * 1000 + 500),
+                Some(ts * 1000 - 3600000),
+                Some(1000),
+                None
+            ]
+        );
+
+        let options = DecoderOptions::new()
+            .with_timestamp_format("%d/%m/%Y %H:%M".to_string())
+            .with_timezone("+02:00".to_string());
+        let (a, b) = read(options);
+        let hours = |h: i64| h * 3600;
+        assert_eq!(a, vec![Some(ts - hours(2)), Some(ts), None, None]);
+        assert_eq!(
+            b,
+            vec![
+                Some((ts - hours(2)) * 1000 + 500),
+                Some(ts * 1000 - 3600000),
+                Some(1000),
+                None
+            ]
+        );
+
+        // Per-column format strings take precedence
+        let options = DecoderOptions::new()
+            .with_timestamp_format("%d/%m/%Y %H:%M".to_string())
+            .with_format_strings(
+                [("a".to_string(), "%Y-%m-%d".to_string())]
+                    .into_iter()
+                    .collect(),
+            );
+        let (a, _) = read(options);
+        assert_eq!(a, vec![None, Some(ts), Some(ts - 36000), None]);
+
+        let options = DecoderOptions::new().with_timezone("foo".to_string());
+        let mut reader = Reader::new(Cursor::new(json), schema.clone(), options);
+        reader.next().unwrap_err();
+    }
+
     #[test]
     fn test_timestamp_from_json_seconds() {
         let schema = Schema::new(vec![Field::new(
@@ -3316,6 +4160,160 @@ mod tests {
         assert_eq!(batch.num_rows(), 2);
     }
 
+    #[test]
+    fn test_json_read_decimals() {
+        let json = r#"
+            {"a": 1.5, "b": "-123456789012345678901234567890.123"}
+            {"a": "0.00123", "b": 1e3}
+            {"a": null}
+            {"a": -12, "b": "+4.5E-2"}
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Decimal128(10, 3), true),
+            Field::new("b", DataType::Decimal256(40, 2), true),
+        ]));
+        let mut reader = Reader::new(Cursor::new(json), schema, DecoderOptions::new());
+        let batch = reader.next().unwrap().unwrap();
+
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(a.precision(), 10);
+        assert_eq!(a.scale(), 3);
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![Some(1500), Some(1), None, Some(-12000)]
+        );
+
+        let b = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Decimal256Array>()
+            .unwrap();
+        let b: Vec<_> = (0..b.len())
+            .map(|i| b.is_valid(i).then(|| b.value_as_string(i)))
+            .collect();
+        assert_eq!(
+            b,
+            vec![
+                Some("-123456789012345678901234567890.12".to_string()),
+                Some("1000.00".to_string()),
+                None,
+                Some("0.04".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_read_invalid_decimals() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            DataType::Decimal128(5, 2),
+            true,
+        )]));
+        for json in [
+            r#"{"a": "1.2.3"}"#,
+            r#"{"a": "abc"}"#,
+            r#"{"a": "."}"#,
+            r#"{"a": 1234.5}"#,
+            r#"{"a": "1e40"}"#,
+            r#"{"a": "1e2147483647"}"#,
+            r#"{"a": "-1.5e2147483647"}"#,
+        ] {
+            let mut reader =
+                Reader::new(Cursor::new(json), schema.clone(), DecoderOptions::new());
+            reader.next().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_json_read_decimals_large_exponents() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            DataType::Decimal128(5, 2),
+            true,
+        )]));
+        let json = r#"
+            {"a": "1e-2147483648"}
+            {"a": "-1.5e-2147483648"}
+            {"a": "123e-2147483647"}
+        "#;
+        let mut reader = Reader::new(Cursor::new(json), schema, DecoderOptions::new());
+        let batch = reader.next().unwrap().unwrap();
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![Some(0), Some(0), Some(0)]
+        );
+
+        let err = parse_decimal::<Decimal128Type>("1e2147483647", 5, 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Json error: Overflow parsing '1e2147483647' as Decimal128(5, 2)"
+        );
+    }
+
+    #[test]
+    fn test_json_read_encoded_binary() {
+        let json = r#"
+            {"a": "Zm9v", "b": "0102"}
+            {"a": null, "b": "ff00"}
+            {"a": "", "b": null}
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Binary, true),
+            Field::new("b", DataType::FixedSizeBinary(2), true),
+        ]));
+
+        // "0102" is valid base64 but does not decode to 2 bytes
+        let options = DecoderOptions::new().with_binary_encoding(BinaryEncoding::Base64);
+        let mut reader = Reader::new(Cursor::new(json), schema.clone(), options);
+        reader.next().unwrap_err();
+
+        let schema = Arc::new(Schema::new(vec![schema.field(0).clone()]));
+        let options = DecoderOptions::new().with_binary_encoding(BinaryEncoding::Base64);
+        let mut reader = Reader::new(Cursor::new(json), schema, options);
+        let batch = reader.next().unwrap().unwrap();
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![Some(b"foo".as_ref()), None, Some(b"".as_ref())]
+        );
+
+        let json = r#"
+            {"b": "0102"}
+            {"b": "FF00"}
+            {"b": null}
+        "#;
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "b",
+            DataType::FixedSizeBinary(2),
+            true,
+        )]));
+        let options = DecoderOptions::new().with_binary_encoding(BinaryEncoding::Hex);
+        let mut reader = Reader::new(Cursor::new(json), schema, options);
+        let batch = reader.next().unwrap().unwrap();
+        let b = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        assert_eq!(
+            b.iter().collect::<Vec<_>>(),
+            vec![Some([1_u8, 2].as_ref()), Some([255_u8, 0].as_ref()), None]
+        );
+    }
+
     #[test]
     fn test_json_iterator() {
         let builder = ReaderBuilder::new().infer_schema(None).with_batch_size(5);
@@ -3347,6 +4345,79 @@ mod tests {
         assert_eq!(100000000000011, sum_a);
     }
 
+    #[test]
+    fn test_json_top_level_array() {
+        let json = r#"
+            [
+                {"a": 1, "b": "foo"},
+                {"a": 2, "b": null} ,
+                {"a": null, "b": "bar"}
+            ]
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let options = DecoderOptions::new().with_batch_size(2);
+        let mut reader = Reader::new(Cursor::new(json), schema, options);
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(a, &Int64Array::from(vec![Some(1), Some(2)]));
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 1);
+        let b = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(b, &StringArray::from(vec![Some("bar")]));
+
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_json_top_level_array_infer_schema() {
+        let json = r#"[{"a": 1, "b": [1.5]}, {"a": 2, "c": true}]"#;
+        let mut reader = ReaderBuilder::new()
+            .infer_schema(None)
+            .build(Cursor::new(json))
+            .unwrap();
+        let schema = reader.schema();
+        assert_eq!(schema.fields().len(), 3);
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(2).data_type(), &DataType::Boolean);
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_json_top_level_array_invalid() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        for json in [
+            r#"[{"a": 1} {"a": 2}]"#,
+            r#"[{"a": 1},"#,
+            r#"[{"a": 1}] {}"#,
+        ] {
+            let mut reader =
+                Reader::new(Cursor::new(json), schema.clone(), DecoderOptions::new());
+            let err = reader.next().unwrap_err();
+            assert!(matches!(err, ArrowError::JsonError(_)), "{}", err);
+        }
+
+        let mut reader =
+            Reader::new(Cursor::new("[]"), schema.clone(), DecoderOptions::new());
+        assert!(reader.next().unwrap().is_none());
+    }
+
     #[test]
     fn test_options_clone() {
         // ensure options have appropriate derivation
@@ -0,0 +1,323 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! # Parallel JSON ingestion from an [`ObjectStore`]
+//!
+//! [`ObjectStoreReader`] reads newline-delimited JSON objects from an
+//! [`ObjectStore`], splitting each object into byte ranges on line boundaries,
+//! and decoding the ranges concurrently into [`RecordBatch`]es.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow_schema::{DataType, Field, Schema};
+//! # use futures::TryStreamExt;
+//! # use object_store::{memory::InMemory, path::Path, ObjectStore};
+//! use arrow_json::store::ObjectStoreReader;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let store = Arc::new(InMemory::new());
+//! let data = "{\"a\": 1}\n{\"a\": 2}\n{\"a\": 3}\n";
+//! store.put(&Path::from("data/1.json"), data.into()).await.unwrap();
+//!
+//! let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+//! let reader = ObjectStoreReader::new(store, schema)
+//!     .with_chunk_size(8 * 1024 * 1024)
+//!     .with_max_in_flight(4);
+//!
+//! let batches: Vec<_> = reader
+//!     .read_prefix(&Path::from("data"))
+//!     .await
+//!     .unwrap()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//! assert_eq!(batches[0].num_rows(), 3);
+//! # }
+//! ```
+
+use std::io::Cursor;
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+use arrow_schema::{ArrowError, SchemaRef};
+use bytes::Bytes;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore};
+
+use crate::reader::{DecoderOptions, Reader};
+
+/// The number of bytes fetched at a time when completing the last line of a range
+const READ_AHEAD: usize = 64 * 1024;
+
+/// Reads newline-delimited JSON objects from an [`ObjectStore`] into
+/// [`RecordBatch`]es, fetching and decoding multiple byte ranges concurrently.
+/// See the [module documentation](self) for an example.
+///
+/// Each object is split into ranges of [`Self::with_chunk_size`] bytes, a range
+/// being decoded from the first line starting within it up to the end of the
+/// last line starting within it. At most [`Self::with_max_in_flight`] ranges are
+/// fetched or decoded at once, and the resulting batches are returned in the
+/// order of the objects and the ranges within them.
+///
+/// Decoding is performed with [`tokio::task::spawn_blocking`], and so must be
+/// run within a tokio runtime.
+#[derive(Debug, Clone)]
+pub struct ObjectStoreReader {
+    store: Arc<dyn ObjectStore>,
+    schema: SchemaRef,
+    options: DecoderOptions,
+    chunk_size: usize,
+    max_in_flight: usize,
+}
+
+impl ObjectStoreReader {
+    /// Create a new [`ObjectStoreReader`] decoding objects in `store` with `schema`
+    pub fn new(store: Arc<dyn ObjectStore>, schema: SchemaRef) -> Self {
+        Self {
+            store,
+            schema,
+            options: DecoderOptions::default(),
+            chunk_size: 8 * 1024 * 1024,
+            max_in_flight: 4,
+        }
+    }
+
+    /// Set the [`DecoderOptions`] used to decode each range, note that batches
+    /// do not span ranges and so may contain fewer rows than the batch size
+    pub fn with_decoder_options(mut self, options: DecoderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the number of bytes in each range, defaults to 8 MiB
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Set the maximum number of ranges to fetch or decode at once, defaults to 4
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Read all objects under `prefix`, ordered by their location
+    pub async fn read_prefix(
+        &self,
+        prefix: &Path,
+    ) -> Result<BoxStream<'static, Result<RecordBatch, ArrowError>>, ArrowError> {
+        let mut objects: Vec<ObjectMeta> = self
+            .store
+            .list(Some(prefix))
+            .await
+            .map_err(external)?
+            .try_collect()
+            .await
+            .map_err(external)?;
+        objects.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+        Ok(self.read_objects(objects))
+    }
+
+    /// Read `objects`, in the order provided
+    pub fn read_objects(
+        &self,
+        objects: Vec<ObjectMeta>,
+    ) -> BoxStream<'static, Result<RecordBatch, ArrowError>> {
+        let chunk_size = self.chunk_size;
+        let ranges = objects.into_iter().flat_map(move |object| {
+            let size = object.size;
+            (0..size)
+                .step_by(chunk_size)
+                .map(move |start| (object.clone(), start..size.min(start + chunk_size)))
+        });
+
+        let store = Arc::clone(&self.store);
+        let schema = Arc::clone(&self.schema);
+        let options = self.options.clone();
+        stream::iter(ranges)
+            .map(move |(object, range)| {
+                let store = Arc::clone(&store);
+                let schema = Arc::clone(&schema);
+                let options = options.clone();
+                async move {
+                    let data = fetch_lines(store.as_ref(), &object, range).await?;
+                    tokio::task::spawn_blocking(move || {
+                        Reader::new(Cursor::new(data), schema, options).collect()
+                    })
+                    .await
+                    .map_err(external)?
+                }
+            })
+            .buffered(self.max_in_flight)
+            .map_ok(|batches: Vec<RecordBatch>| stream::iter(batches).map(Ok))
+            .try_flatten()
+            .boxed()
+    }
+}
+
+fn external(e: impl std::error::Error + Send + Sync + 'static) -> ArrowError {
+    ArrowError::ExternalError(Box::new(e))
+}
+
+/// Fetches the lines of `object` that start within `range`
+async fn fetch_lines(
+    store: &dyn ObjectStore,
+    object: &ObjectMeta,
+    range: Range<usize>,
+) -> Result<Bytes, ArrowError> {
+    // Fetch the preceding byte to determine if a line starts at the range
+    let fetch_start = range.start.saturating_sub(1);
+    let data = store
+        .get_range(&object.location, fetch_start..range.end)
+        .await
+        .map_err(external)?;
+
+    let start = match range.start {
+        0 => 0,
+        _ => match data.iter().position(|b| *b == b'\n') {
+            Some(idx) => idx + 1,
+            None => return Ok(Bytes::new()),
+        },
+    };
+
+    if data.len() == start || data.last() == Some(&b'\n') || range.end == object.size {
+        return Ok(data.slice(start..));
+    }
+
+    // Complete the last line, which continues beyond the range
+    let mut lines = data[start..].to_vec();
+    let mut offset = range.end;
+    while offset < object.size {
+        let end = object.size.min(offset + READ_AHEAD);
+        let next = store
+            .get_range(&object.location, offset..end)
+            .await
+            .map_err(external)?;
+        match next.iter().position(|b| *b == b'\n') {
+            Some(idx) => {
+                lines.extend_from_slice(&next[..=idx]);
+                break;
+            }
+            None => lines.extend_from_slice(&next),
+        }
+        offset = end;
+    }
+    Ok(lines.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::cast::as_primitive_array;
+    use arrow_array::types::Int64Type;
+    use arrow_schema::{DataType, Field, Schema};
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn test_read_chunks() {
+        let store = Arc::new(InMemory::new());
+        let lines: Vec<_> = (0..100).map(|i| format!("{{\"a\": {}}}\n", i)).collect();
+        let data = lines.concat();
+        store
+            .put(&Path::from("prefix/b.json"), data.clone().into())
+            .await
+            .unwrap();
+        store
+            .put(&Path::from("prefix/a.json"), data.into())
+            .await
+            .unwrap();
+        store
+            .put(&Path::from("prefix/c.json"), Bytes::new())
+            .await
+            .unwrap();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let expected: Vec<_> = (0..100).chain(0..100).map(Some).collect();
+
+        for chunk_size in [1, 7, 10, 11, 100, 1000, 10000] {
+            let reader = ObjectStoreReader::new(store.clone(), schema.clone())
+                .with_chunk_size(chunk_size)
+                .with_max_in_flight(3)
+                .with_decoder_options(DecoderOptions::new().with_batch_size(16));
+            let batches: Vec<_> = reader
+                .read_prefix(&Path::from("prefix"))
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert!(batches.iter().all(|b| b.num_rows() <= 16));
+            let values: Vec<_> = batches
+                .iter()
+                .flat_map(|b| as_primitive_array::<Int64Type>(b.column(0)).iter())
+                .collect();
+            assert_eq!(values, expected, "chunk_size {}", chunk_size);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_without_trailing_newline() {
+        let store = Arc::new(InMemory::new());
+        let location = Path::from("data.json");
+        store
+            .put(
+                &location,
+                "{\"a\": 1}\n\n{\"a\": 22}\r\n{\"a\": 333}".into(),
+            )
+            .await
+            .unwrap();
+        let object = store.head(&location).await.unwrap();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        for chunk_size in [1, 4, 9, 10, 64] {
+            let reader = ObjectStoreReader::new(store.clone(), schema.clone())
+                .with_chunk_size(chunk_size);
+            let batches: Vec<_> = reader
+                .read_objects(vec![object.clone()])
+                .try_collect()
+                .await
+                .unwrap();
+            let values: Vec<_> = batches
+                .iter()
+                .flat_map(|b| as_primitive_array::<Int64Type>(b.column(0)).iter())
+                .collect();
+            assert_eq!(values, vec![Some(1), Some(22), Some(333)]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_invalid() {
+        let store = Arc::new(InMemory::new());
+        let location = Path::from("data.json");
+        store.put(&location, "{\"a\": 1}\n{".into()).await.unwrap();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let reader = ObjectStoreReader::new(store, schema);
+        let result: Result<Vec<_>, _> = reader
+            .read_prefix(&Path::from(""))
+            .await
+            .unwrap()
+            .try_collect()
+            .await;
+        result.unwrap_err();
+    }
+}
@@ -92,481 +92,193 @@
 //! let buf = writer.into_inner();
 //! assert_eq!(r#"[{"a":1},{"a":2},{"a":3}]"#, String::from_utf8(buf).unwrap())
 //! ```
+//!
+//! To pretty print the array over multiple indented lines, use
+//! [`PrettyArrayWriter`].
+//!
+//! Rows are encoded directly from the arrays of each [`RecordBatch`], and
+//! buffered in memory before being written to the output, see [`Writer`].
 
-use std::iter;
 use std::{fmt::Debug, io::Write};
 
+use serde::Serialize;
 use serde_json::map::Map as JsonMap;
+use serde_json::ser::{CompactFormatter, Formatter, PrettyFormatter};
 use serde_json::Value;
 
-use crate::JsonSerializable;
-use arrow_array::cast::*;
-use arrow_array::types::*;
+use crate::encoder::{make_encoder, make_row_encoder, Encoder};
+use crate::reader::Decoder;
+use crate::BinaryEncoding;
 use arrow_array::*;
 use arrow_schema::*;
 
-fn primitive_array_to_json<T>(array: &ArrayRef) -> Result<Vec<Value>, ArrowError>
-where
-    T: ArrowPrimitiveType,
-    T::Native: JsonSerializable,
-{
-    Ok(as_primitive_array::<T>(array)
-        .iter()
-        .map(|maybe_value| match maybe_value {
-            Some(v) => v.into_json_value().unwrap_or(Value::Null),
-            None => Value::Null,
-        })
-        .collect())
+/// Specifies how date, time, timestamp and duration values are written as JSON
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TemporalFormat {
+    /// The default formatting of `chrono`, e.g. `2022-01-31 12:34:56.789` for
+    /// timestamps, ignoring any timezone
+    #[default]
+    Display,
+    /// RFC3339, e.g. `2022-01-31T12:34:56.789+01:00` for timestamps. Timestamps
+    /// without a timezone are written without an offset
+    Rfc3339,
+    /// Numbers of milliseconds since the UNIX epoch for dates and timestamps, since
+    /// midnight for times, and the length of durations
+    EpochMillis,
+    /// A custom `chrono` format string, e.g. `%d/%m/%Y %H:%M`, see
+    /// [`chrono::format::strftime`]. Durations are written as with [`Self::Display`]
+    Custom(String),
 }
 
-fn struct_array_to_jsonmap_array(
-    array: &StructArray,
-    row_count: usize,
-) -> Result<Vec<JsonMap<String, Value>>, ArrowError> {
-    let inner_col_names = array.column_names();
-
-    let mut inner_objs = iter::repeat(JsonMap::new())
-        .take(row_count)
-        .collect::<Vec<JsonMap<String, Value>>>();
+/// Options for JSON encoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncoderOptions {
+    /// How `Binary` and `FixedSizeBinary` values are encoded, defaults to UTF-8 strings
+    pub(crate) binary_encoding: BinaryEncoding,
+    /// Write maps as lists of key-value entries instead of JSON objects
+    pub(crate) map_entries: bool,
+    /// How temporal values are written, defaults to [`TemporalFormat::Display`]
+    pub(crate) temporal_format: TemporalFormat,
+}
 
-    for (j, struct_col) in array.columns().iter().enumerate() {
-        set_column_for_json_rows(
-            &mut inner_objs,
-            row_count,
-            struct_col,
-            inner_col_names[j],
-        )?
+impl Default for EncoderOptions {
+    fn default() -> Self {
+        Self {
+            binary_encoding: BinaryEncoding::default(),
+            map_entries: false,
+            temporal_format: TemporalFormat::Display,
+        }
     }
-    Ok(inner_objs)
 }
 
-/// Converts an arrow [`ArrayRef`] into a `Vec` of Serde JSON [`serde_json::Value`]'s
-pub fn array_to_json_array(array: &ArrayRef) -> Result<Vec<Value>, ArrowError> {
-    match array.data_type() {
-        DataType::Null => Ok(iter::repeat(Value::Null).take(array.len()).collect()),
-        DataType::Boolean => Ok(as_boolean_array(array)
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => v.into(),
-                None => Value::Null,
-            })
-            .collect()),
+impl EncoderOptions {
+    /// Creates a new `EncoderOptions`
+    pub fn new() -> Self {
+        Default::default()
+    }
 
-        DataType::Utf8 => Ok(as_string_array(array)
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => v.into(),
-                None => Value::Null,
-            })
-            .collect()),
-        DataType::LargeUtf8 => Ok(as_largestring_array(array)
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => v.into(),
-                None => Value::Null,
-            })
-            .collect()),
-        DataType::Int8 => primitive_array_to_json::<Int8Type>(array),
-        DataType::Int16 => primitive_array_to_json::<Int16Type>(array),
-        DataType::Int32 => primitive_array_to_json::<Int32Type>(array),
-        DataType::Int64 => primitive_array_to_json::<Int64Type>(array),
-        DataType::UInt8 => primitive_array_to_json::<UInt8Type>(array),
-        DataType::UInt16 => primitive_array_to_json::<UInt16Type>(array),
-        DataType::UInt32 => primitive_array_to_json::<UInt32Type>(array),
-        DataType::UInt64 => primitive_array_to_json::<UInt64Type>(array),
-        DataType::Float32 => primitive_array_to_json::<Float32Type>(array),
-        DataType::Float64 => primitive_array_to_json::<Float64Type>(array),
-        DataType::List(_) => as_list_array(array)
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => Ok(Value::Array(array_to_json_array(&v)?)),
-                None => Ok(Value::Null),
-            })
-            .collect(),
-        DataType::LargeList(_) => as_large_list_array(array)
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => Ok(Value::Array(array_to_json_array(&v)?)),
-                None => Ok(Value::Null),
-            })
-            .collect(),
-        DataType::Struct(_) => {
-            let jsonmaps =
-                struct_array_to_jsonmap_array(as_struct_array(array), array.len())?;
-            Ok(jsonmaps.into_iter().map(Value::Object).collect())
-        }
-        t => Err(ArrowError::JsonError(format!(
-            "data type {:?} not supported",
-            t
-        ))),
+    /// Set how `Binary` and `FixedSizeBinary` values are encoded as JSON strings
+    pub fn with_binary_encoding(mut self, binary_encoding: BinaryEncoding) -> Self {
+        self.binary_encoding = binary_encoding;
+        self
     }
-}
 
-macro_rules! set_column_by_array_type {
-    ($cast_fn:ident, $col_name:ident, $rows:ident, $array:ident, $row_count:ident) => {
-        let arr = $cast_fn($array);
-        $rows.iter_mut().zip(arr.iter()).take($row_count).for_each(
-            |(row, maybe_value)| {
-                if let Some(v) = maybe_value {
-                    row.insert($col_name.to_string(), v.into());
-                }
-            },
-        );
-    };
-}
+    /// Set whether maps are written as lists of key-value entries.
+    ///
+    /// By default maps are written as JSON objects, e.g. `{"a": 1, "b": 2}`, with
+    /// any keys that are not strings written as their JSON text. When enabled, maps
+    /// are instead written as lists of entries, e.g.
+    /// `[{"key": "a", "value": 1}, {"key": "b", "value": 2}]`, which preserves the
+    /// type of the keys, along with any duplicates
+    pub fn with_map_entries(mut self, map_entries: bool) -> Self {
+        self.map_entries = map_entries;
+        self
+    }
 
-macro_rules! set_temporal_column_by_array_type {
-    ($array_type:ident, $col_name:ident, $rows:ident, $array:ident, $row_count:ident, $cast_fn:ident) => {
-        let arr = $array.as_any().downcast_ref::<$array_type>().unwrap();
-
-        $rows
-            .iter_mut()
-            .enumerate()
-            .take($row_count)
-            .for_each(|(i, row)| {
-                if !arr.is_null(i) {
-                    if let Some(v) = arr.$cast_fn(i) {
-                        row.insert($col_name.to_string(), v.to_string().into());
-                    }
-                }
-            });
-    };
+    /// Set how date, time, timestamp and duration values are written
+    pub fn with_temporal_format(mut self, temporal_format: TemporalFormat) -> Self {
+        self.temporal_format = temporal_format;
+        self
+    }
 }
 
-fn set_column_by_primitive_type<T>(
-    rows: &mut [JsonMap<String, Value>],
-    row_count: usize,
-    array: &ArrayRef,
-    col_name: &str,
-) where
-    T: ArrowPrimitiveType,
-    T::Native: JsonSerializable,
-{
-    let primitive_arr = as_primitive_array::<T>(array);
-
-    rows.iter_mut()
-        .zip(primitive_arr.iter())
-        .take(row_count)
-        .for_each(|(row, maybe_value)| {
-            // when value is null, we simply skip setting the key
-            if let Some(j) = maybe_value.and_then(|v| v.into_json_value()) {
-                row.insert(col_name.to_string(), j);
-            }
-        });
+/// Converts an arrow [`ArrayRef`] into a `Vec` of Serde JSON [`serde_json::Value`]'s
+pub fn array_to_json_array(array: &ArrayRef) -> Result<Vec<Value>, ArrowError> {
+    array_to_json_array_with_options(array, &EncoderOptions::default())
 }
 
-fn set_column_for_json_rows(
-    rows: &mut [JsonMap<String, Value>],
-    row_count: usize,
+/// Converts an arrow [`ArrayRef`] into a `Vec` of Serde JSON [`serde_json::Value`]'s
+/// using the provided [`EncoderOptions`]
+pub fn array_to_json_array_with_options(
     array: &ArrayRef,
-    col_name: &str,
-) -> Result<(), ArrowError> {
-    match array.data_type() {
-        DataType::Int8 => {
-            set_column_by_primitive_type::<Int8Type>(rows, row_count, array, col_name);
-        }
-        DataType::Int16 => {
-            set_column_by_primitive_type::<Int16Type>(rows, row_count, array, col_name);
-        }
-        DataType::Int32 => {
-            set_column_by_primitive_type::<Int32Type>(rows, row_count, array, col_name);
-        }
-        DataType::Int64 => {
-            set_column_by_primitive_type::<Int64Type>(rows, row_count, array, col_name);
-        }
-        DataType::UInt8 => {
-            set_column_by_primitive_type::<UInt8Type>(rows, row_count, array, col_name);
-        }
-        DataType::UInt16 => {
-            set_column_by_primitive_type::<UInt16Type>(rows, row_count, array, col_name);
-        }
-        DataType::UInt32 => {
-            set_column_by_primitive_type::<UInt32Type>(rows, row_count, array, col_name);
-        }
-        DataType::UInt64 => {
-            set_column_by_primitive_type::<UInt64Type>(rows, row_count, array, col_name);
-        }
-        DataType::Float32 => {
-            set_column_by_primitive_type::<Float32Type>(rows, row_count, array, col_name);
-        }
-        DataType::Float64 => {
-            set_column_by_primitive_type::<Float64Type>(rows, row_count, array, col_name);
-        }
-        DataType::Null => {
-            // when value is null, we simply skip setting the key
-        }
-        DataType::Boolean => {
-            set_column_by_array_type!(as_boolean_array, col_name, rows, array, row_count);
-        }
-        DataType::Utf8 => {
-            set_column_by_array_type!(as_string_array, col_name, rows, array, row_count);
-        }
-        DataType::LargeUtf8 => {
-            set_column_by_array_type!(
-                as_largestring_array,
-                col_name,
-                rows,
-                array,
-                row_count
-            );
-        }
-        DataType::Date32 => {
-            set_temporal_column_by_array_type!(
-                Date32Array,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_date
-            );
-        }
-        DataType::Date64 => {
-            set_temporal_column_by_array_type!(
-                Date64Array,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_date
-            );
-        }
-        DataType::Timestamp(TimeUnit::Second, _) => {
-            set_temporal_column_by_array_type!(
-                TimestampSecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_datetime
-            );
-        }
-        DataType::Timestamp(TimeUnit::Millisecond, _) => {
-            set_temporal_column_by_array_type!(
-                TimestampMillisecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_datetime
-            );
-        }
-        DataType::Timestamp(TimeUnit::Microsecond, _) => {
-            set_temporal_column_by_array_type!(
-                TimestampMicrosecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_datetime
-            );
-        }
-        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
-            set_temporal_column_by_array_type!(
-                TimestampNanosecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_datetime
-            );
-        }
-        DataType::Time32(TimeUnit::Second) => {
-            set_temporal_column_by_array_type!(
-                Time32SecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_time
-            );
-        }
-        DataType::Time32(TimeUnit::Millisecond) => {
-            set_temporal_column_by_array_type!(
-                Time32MillisecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_time
-            );
-        }
-        DataType::Time64(TimeUnit::Microsecond) => {
-            set_temporal_column_by_array_type!(
-                Time64MicrosecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_time
-            );
-        }
-        DataType::Time64(TimeUnit::Nanosecond) => {
-            set_temporal_column_by_array_type!(
-                Time64NanosecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_time
-            );
-        }
-        DataType::Duration(TimeUnit::Second) => {
-            set_temporal_column_by_array_type!(
-                DurationSecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_duration
-            );
-        }
-        DataType::Duration(TimeUnit::Millisecond) => {
-            set_temporal_column_by_array_type!(
-                DurationMillisecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_duration
-            );
-        }
-        DataType::Duration(TimeUnit::Microsecond) => {
-            set_temporal_column_by_array_type!(
-                DurationMicrosecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_duration
-            );
-        }
-        DataType::Duration(TimeUnit::Nanosecond) => {
-            set_temporal_column_by_array_type!(
-                DurationNanosecondArray,
-                col_name,
-                rows,
-                array,
-                row_count,
-                value_as_duration
-            );
-        }
-        DataType::Struct(_) => {
-            let inner_objs =
-                struct_array_to_jsonmap_array(as_struct_array(array), row_count)?;
-            rows.iter_mut()
-                .take(row_count)
-                .zip(inner_objs.into_iter())
-                .for_each(|(row, obj)| {
-                    row.insert(col_name.to_string(), Value::Object(obj));
-                });
-        }
-        DataType::List(_) => {
-            let listarr = as_list_array(array);
-            rows.iter_mut()
-                .zip(listarr.iter())
-                .take(row_count)
-                .try_for_each(|(row, maybe_value)| -> Result<(), ArrowError> {
-                    if let Some(v) = maybe_value {
-                        row.insert(
-                            col_name.to_string(),
-                            Value::Array(array_to_json_array(&v)?),
-                        );
-                    }
-                    Ok(())
-                })?;
-        }
-        DataType::LargeList(_) => {
-            let listarr = as_large_list_array(array);
-            rows.iter_mut()
-                .zip(listarr.iter())
-                .take(row_count)
-                .try_for_each(|(row, maybe_value)| -> Result<(), ArrowError> {
-                    if let Some(v) = maybe_value {
-                        let val = array_to_json_array(&v)?;
-                        row.insert(col_name.to_string(), Value::Array(val));
-                    }
-                    Ok(())
-                })?;
-        }
-        DataType::Dictionary(_, value_type) => {
-            let slice = array.slice(0, row_count);
-            let hydrated = arrow_cast::cast::cast(&slice, value_type)
-                .expect("cannot cast dictionary to underlying values");
-            set_column_for_json_rows(rows, row_count, &hydrated, col_name)?;
-        }
-        DataType::Map(_, _) => {
-            let maparr = as_map_array(array);
-
-            let keys = maparr.keys();
-            let values = maparr.values();
-
-            // Keys have to be strings to convert to json.
-            if !matches!(keys.data_type(), DataType::Utf8) {
-                return Err(ArrowError::JsonError(format!(
-                    "data type {:?} not supported in nested map for json writer",
-                    keys.data_type()
-                )));
-            }
-
-            let keys = as_string_array(&keys);
-            let values = array_to_json_array(&values)?;
-
-            let mut kv = keys.iter().zip(values.into_iter());
-
-            for (i, row) in rows.iter_mut().take(row_count).enumerate() {
-                if maparr.is_null(i) {
-                    row.insert(col_name.to_string(), serde_json::Value::Null);
-                    continue;
-                }
-
-                let len = maparr.value_length(i) as usize;
-                let mut obj = serde_json::Map::new();
-
-                for (_, (k, v)) in (0..len).zip(&mut kv) {
-                    obj.insert(
-                        k.expect("keys in a map should be non-null").to_string(),
-                        v,
-                    );
-                }
-
-                row.insert(col_name.to_string(), serde_json::Value::Object(obj));
-            }
-        }
-        _ => {
-            return Err(ArrowError::JsonError(format!(
-                "data type {:?} not supported in nested map for json writer",
-                array.data_type()
-            )))
-        }
-    }
-    Ok(())
+    options: &EncoderOptions,
+) -> Result<Vec<Value>, ArrowError> {
+    let encoder = make_encoder::<CompactFormatter>(array.as_ref(), options)?;
+    (0..array.len())
+        .map(|idx| match encoder.is_null(idx) {
+            true => Ok(Value::Null),
+            false => encoder.to_value(idx),
+        })
+        .collect()
 }
 
 /// Converts an arrow [`RecordBatch`] into a `Vec` of Serde JSON
-/// [`JsonMap`]s (objects)
+/// [`JsonMap`]s (objects), without writing them as JSON text
 pub fn record_batches_to_json_rows(
     batches: &[RecordBatch],
 ) -> Result<Vec<JsonMap<String, Value>>, ArrowError> {
-    let mut rows: Vec<JsonMap<String, Value>> = iter::repeat(JsonMap::new())
-        .take(batches.iter().map(|b| b.num_rows()).sum())
-        .collect();
+    record_batches_to_json_rows_with_options(batches, &EncoderOptions::default())
+}
 
-    if !rows.is_empty() {
-        let schema = batches[0].schema();
-        let mut base = 0;
-        for batch in batches {
-            let row_count = batch.num_rows();
-            for (j, col) in batch.columns().iter().enumerate() {
-                let col_name = schema.field(j).name();
-                set_column_for_json_rows(&mut rows[base..], row_count, col, col_name)?
-            }
-            base += row_count;
+/// Converts an arrow [`RecordBatch`] into a `Vec` of Serde JSON
+/// [`JsonMap`]s (objects) using the provided [`EncoderOptions`]
+pub fn record_batches_to_json_rows_with_options(
+    batches: &[RecordBatch],
+    options: &EncoderOptions,
+) -> Result<Vec<JsonMap<String, Value>>, ArrowError> {
+    let mut rows = Vec::with_capacity(batches.iter().map(|b| b.num_rows()).sum());
+    for batch in batches {
+        let encoder = make_row_encoder::<CompactFormatter>(batch, options)?;
+        for idx in 0..batch.num_rows() {
+            rows.push(encoder.to_object(idx)?);
         }
     }
-
     Ok(rows)
 }
 
+impl Decoder {
+    /// Serialize `rows` into a [`RecordBatch`] with this decoder's schema.
+    ///
+    /// Each row is converted directly to a [`serde_json::Value`], without
+    /// formatting it as JSON text, and must serialize to a JSON object. All rows
+    /// are written to a single [`RecordBatch`], regardless of the batch size.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use arrow_array::cast::as_primitive_array;
+    /// use arrow_array::types::Int32Type;
+    /// use arrow_json::reader::{Decoder, DecoderOptions};
+    /// use arrow_schema::{DataType, Field, Schema};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: Option<i32>,
+    /// }
+    ///
+    /// let schema = Schema::new(vec![
+    ///     Field::new("x", DataType::Int32, false),
+    ///     Field::new("y", DataType::Int32, true),
+    /// ]);
+    /// let decoder = Decoder::new(Arc::new(schema), DecoderOptions::new());
+    ///
+    /// let rows = vec![Point { x: 1, y: Some(2) }, Point { x: 3, y: None }];
+    /// let batch = decoder.serialize(&rows).unwrap();
+    ///
+    /// let y = as_primitive_array::<Int32Type>(batch.column(1));
+    /// assert_eq!(y.iter().collect::<Vec<_>>(), vec![Some(2), None]);
+    /// ```
+    pub fn serialize<S: Serialize>(&self, rows: &[S]) -> Result<RecordBatch, ArrowError> {
+        let rows = rows
+            .iter()
+            .map(|row| match serde_json::to_value(row) {
+                Ok(v @ Value::Object(_)) => Ok(v),
+                Ok(v) => Err(ArrowError::JsonError(format!(
+                    "Row needs to be of type object, got: {:?}",
+                    v
+                ))),
+                Err(e) => Err(ArrowError::JsonError(format!(
+                    "Failed to serialize row: {}",
+                    e
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.build_batch(&rows)
+    }
+}
+
 /// This trait defines how to format a sequence of JSON objects to a
 /// byte stream.
 pub trait JsonFormat: Debug + Default {
@@ -596,6 +308,13 @@ pub trait JsonFormat: Debug + Default {
     fn end_stream<W: Write>(&self, _writer: &mut W) -> Result<(), ArrowError> {
         Ok(())
     }
+
+    #[inline]
+    /// returns true if each row should be pretty printed over multiple
+    /// indented lines, nested one level within the stream
+    fn pretty(&self) -> bool {
+        false
+    }
 }
 
 /// Produces JSON output with one record per line. For example
@@ -646,16 +365,78 @@ impl JsonFormat for JsonArray {
     }
 }
 
+/// Produces JSON output as a single pretty printed JSON array. For example
+///
+/// ```json
+/// [
+///   {
+///     "foo": 1
+///   },
+///   {
+///     "bar": 1
+///   }
+/// ]
+/// ```
+#[derive(Debug, Default)]
+pub struct PrettyJsonArray {}
+
+impl JsonFormat for PrettyJsonArray {
+    fn start_stream<W: Write>(&self, writer: &mut W) -> Result<(), ArrowError> {
+        writer.write_all(b"[")?;
+        Ok(())
+    }
+
+    fn start_row<W: Write>(
+        &self,
+        writer: &mut W,
+        is_first_row: bool,
+    ) -> Result<(), ArrowError> {
+        if !is_first_row {
+            writer.write_all(b",")?;
+        }
+        writer.write_all(b"\n  ")?;
+        Ok(())
+    }
+
+    fn end_stream<W: Write>(&self, writer: &mut W) -> Result<(), ArrowError> {
+        writer.write_all(b"\n]")?;
+        Ok(())
+    }
+
+    fn pretty(&self) -> bool {
+        true
+    }
+}
+
 /// A JSON writer which serializes [`RecordBatch`]es to newline delimited JSON objects
 pub type LineDelimitedWriter<W> = Writer<W, LineDelimited>;
 
 /// A JSON writer which serializes [`RecordBatch`]es to JSON arrays
 pub type ArrayWriter<W> = Writer<W, JsonArray>;
 
+/// A JSON writer which serializes [`RecordBatch`]es to pretty printed JSON arrays
+pub type PrettyArrayWriter<W> = Writer<W, PrettyJsonArray>;
+
+/// The default capacity of the internal buffer of [`Writer`]
+const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Returns a [`PrettyFormatter`] for rows nested one level within the stream
+fn pretty_row_formatter() -> PrettyFormatter<'static> {
+    let mut formatter = PrettyFormatter::new();
+    // Writing to a sink cannot fail, this only increases the indentation
+    let _ = formatter.begin_array(&mut std::io::sink());
+    formatter
+}
+
 /// A JSON writer which serializes [`RecordBatch`]es to a stream of
 /// `u8` encoded JSON objects. See the module level documentation for
 /// detailed usage and examples. The specific format of the stream is
 /// controlled by the [`JsonFormat`] type parameter.
+///
+/// Rows are encoded directly from the arrays of each [`RecordBatch`] into an
+/// internal buffer, which is written to the underlying writer whenever it
+/// exceeds its capacity, see [`Writer::with_buffer_size`], and at the end of
+/// each call to write rows. No bytes therefore remain buffered between calls.
 #[derive(Debug)]
 pub struct Writer<W, F>
 where
@@ -665,6 +446,12 @@ where
     /// Underlying writer to use to write bytes
     writer: W,
 
+    /// Encoded bytes not yet written to `writer`
+    buffer: Vec<u8>,
+
+    /// The number of buffered bytes at which they are written to `writer`
+    buffer_size: usize,
+
     /// Has the writer output any records yet?
     started: bool,
 
@@ -673,6 +460,9 @@ where
 
     /// Determines how the byte stream is formatted
     format: F,
+
+    /// Determines how values are encoded
+    options: EncoderOptions,
 }
 
 impl<W, F> Writer<W, F>
@@ -682,56 +472,117 @@ where
 {
     /// Construct a new writer
     pub fn new(writer: W) -> Self {
+        Self::new_with_options(writer, EncoderOptions::default())
+    }
+
+    /// Construct a new writer with the provided [`EncoderOptions`]
+    pub fn new_with_options(writer: W, options: EncoderOptions) -> Self {
         Self {
             writer,
+            buffer: Vec::with_capacity(DEFAULT_BUFFER_SIZE),
+            buffer_size: DEFAULT_BUFFER_SIZE,
             started: false,
             finished: false,
             format: F::default(),
+            options,
         }
     }
 
-    /// Write a single JSON row to the output writer
-    pub fn write_row(&mut self, row: &Value) -> Result<(), ArrowError> {
+    /// Set the number of bytes to buffer before writing them to the underlying
+    /// writer, defaults to 8 KiB. A size of `0` writes each row as it is encoded
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    fn start_row(&mut self) -> Result<(), ArrowError> {
         let is_first_row = !self.started;
         if !self.started {
-            self.format.start_stream(&mut self.writer)?;
+            self.format.start_stream(&mut self.buffer)?;
             self.started = true;
         }
+        self.format.start_row(&mut self.buffer, is_first_row)
+    }
 
-        self.format.start_row(&mut self.writer, is_first_row)?;
-        self.writer.write_all(
-            &serde_json::to_vec(row)
-                .map_err(|error| ArrowError::JsonError(error.to_string()))?,
-        )?;
-        self.format.end_row(&mut self.writer)?;
+    fn end_row(&mut self) -> Result<(), ArrowError> {
+        self.format.end_row(&mut self.buffer)?;
+        if self.buffer.len() >= self.buffer_size {
+            self.write_buffer()?;
+        }
         Ok(())
     }
 
-    /// Convert the `RecordBatch` into JSON rows, and write them to the output
-    pub fn write(&mut self, batch: RecordBatch) -> Result<(), ArrowError> {
-        for row in record_batches_to_json_rows(&[batch])? {
-            self.write_row(&Value::Object(row))?;
+    /// Write any buffered bytes to the underlying writer
+    fn write_buffer(&mut self) -> Result<(), ArrowError> {
+        let result = self.writer.write_all(&self.buffer);
+        self.buffer.clear();
+        Ok(result?)
+    }
+
+    /// Write a single JSON row to the output writer
+    pub fn write_row(&mut self, row: &Value) -> Result<(), ArrowError> {
+        self.start_row()?;
+        let result = match self.format.pretty() {
+            true => row.serialize(&mut serde_json::Serializer::with_formatter(
+                &mut self.buffer,
+                pretty_row_formatter(),
+            )),
+            false => serde_json::to_writer(&mut self.buffer, row),
+        };
+        result.map_err(|error| ArrowError::JsonError(error.to_string()))?;
+        self.end_row()?;
+        self.write_buffer()
+    }
+
+    /// Encode the rows of `batch` with formatters created by `formatter`
+    fn write_rows<Fmt: Formatter + 'static>(
+        &mut self,
+        batch: &RecordBatch,
+        formatter: fn() -> Fmt,
+    ) -> Result<(), ArrowError> {
+        let mut encoder = make_row_encoder::<Fmt>(batch, &self.options)?;
+        for idx in 0..batch.num_rows() {
+            self.start_row()?;
+            encoder.encode(idx, &mut formatter(), &mut self.buffer)?;
+            self.end_row()?;
         }
         Ok(())
     }
 
+    /// Convert the `RecordBatch` into JSON rows, and write them to the output
+    pub fn write(&mut self, batch: RecordBatch) -> Result<(), ArrowError> {
+        self.write_batches(&[batch])
+    }
+
     /// Convert the [`RecordBatch`] into JSON rows, and write them to the output
     pub fn write_batches(&mut self, batches: &[RecordBatch]) -> Result<(), ArrowError> {
-        for row in record_batches_to_json_rows(batches)? {
-            self.write_row(&Value::Object(row))?;
+        for batch in batches {
+            match self.format.pretty() {
+                true => self.write_rows(batch, pretty_row_formatter)?,
+                false => self.write_rows(batch, || CompactFormatter)?,
+            }
         }
+        self.write_buffer()
+    }
+
+    /// Write any buffered bytes to the underlying writer, and flush it
+    pub fn flush(&mut self) -> Result<(), ArrowError> {
+        self.write_buffer()?;
+        self.writer.flush()?;
         Ok(())
     }
 
     /// Finishes the output stream. This function must be called after
     /// all record batches have been produced. (e.g. producing the final `']'` if writing
     /// arrays.
+    ///
+    /// Any buffered bytes are then written and the underlying writer flushed
     pub fn finish(&mut self) -> Result<(), ArrowError> {
         if self.started && !self.finished {
-            self.format.end_stream(&mut self.writer)?;
+            self.format.end_stream(&mut self.buffer)?;
             self.finished = true;
         }
-        Ok(())
+        self.flush()
     }
 
     /// Unwraps this `Writer<W>`, returning the underlying writer
@@ -746,7 +597,9 @@ mod tests {
     use std::sync::Arc;
 
     use crate::reader::*;
-    use arrow_buffer::{Buffer, ToByteSlice};
+    use arrow_array::builder::*;
+    use arrow_array::types::*;
+    use arrow_buffer::{i256, Buffer, ToByteSlice};
     use arrow_data::ArrayData;
     use serde_json::json;
 
@@ -767,6 +620,25 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    /// Asserts that the rows of `batches` converted to [`Value`]s, which does not
+    /// write them as JSON text, match the rows written by a [`LineDelimitedWriter`]
+    fn assert_rows_match_writer(batches: &[RecordBatch], options: EncoderOptions) {
+        let rows = record_batches_to_json_rows_with_options(batches, &options).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new_with_options(&mut buf, options);
+            writer.write_batches(batches).unwrap();
+        }
+        let written: Vec<JsonMap<String, Value>> = buf
+            .split(|b| *b == b'\n')
+            .filter(|s| !s.is_empty())
+            .map(|s| serde_json::from_slice(s).unwrap())
+            .collect();
+
+        assert_eq!(rows, written);
+    }
+
     #[test]
     fn write_simple_rows() {
         let schema = Schema::new(vec![
@@ -931,6 +803,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_temporal_formats() {
+        // 2022-01-31T10:00:00.250Z
+        let ts_millis = 1643623200250;
+        let timestamps = TimestampMillisecondArray::from(vec![Some(ts_millis), None]);
+        let with_tz = timestamps.clone().with_timezone("+01:00".to_string());
+        let dates = Date32Array::from(vec![Some(19023), None]);
+        let times = Time32MillisecondArray::from(vec![Some(3_723_500), None]);
+        let durations = DurationSecondArray::from(vec![Some(90), None]);
+
+        let schema = Schema::new(vec![
+            Field::new("ts", timestamps.data_type().clone(), true),
+            Field::new("tz", with_tz.data_type().clone(), true),
+            Field::new("date", dates.data_type().clone(), true),
+            Field::new("time", times.data_type().clone(), true),
+            Field::new("duration", durations.data_type().clone(), true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(timestamps),
+                Arc::new(with_tz),
+                Arc::new(dates),
+                Arc::new(times),
+                Arc::new(durations),
+            ],
+        )
+        .unwrap();
+
+        let write_batch = |batch: &RecordBatch, format: TemporalFormat| {
+            let options = EncoderOptions::new().with_temporal_format(format.clone());
+            let mut buf = Vec::new();
+            {
+                let mut writer = LineDelimitedWriter::new_with_options(&mut buf, options);
+                writer.write(batch.clone())?;
+            }
+
+            // The Value based conversion is consistent with the writer
+            let options = EncoderOptions::new().with_temporal_format(format);
+            let rows = record_batches_to_json_rows_with_options(
+                std::slice::from_ref(batch),
+                &options,
+            )?;
+            let expected: Vec<u8> = rows
+                .into_iter()
+                .flat_map(|row| format!("{}\n", Value::Object(row)).into_bytes())
+                .collect();
+            assert_json_eq(&buf, std::str::from_utf8(&expected).unwrap());
+            Ok::<_, ArrowError>(buf)
+        };
+        let write = |format: TemporalFormat| write_batch(&batch, format).unwrap();
+
+        assert_json_eq(
+            &write(TemporalFormat::Display),
+            r#"{"ts":"2022-01-31 10:00:00.250","tz":"2022-01-31 10:00:00.250","date":"2022-01-31","time":"01:02:03.500","duration":"PT90S"}
+{}
+"#,
+        );
+
+        assert_json_eq(
+            &write(TemporalFormat::Rfc3339),
+            r#"{"ts":"2022-01-31T10:00:00.250","tz":"2022-01-31T11:00:00.250+01:00","date":"2022-01-31","time":"01:02:03.500","duration":"PT90S"}
+{}
+"#,
+        );
+
+        assert_json_eq(
+            &write(TemporalFormat::EpochMillis),
+            r#"{"ts":1643623200250,"tz":1643623200250,"date":1643587200000,"time":3723500,"duration":90000}
+{}
+"#,
+        );
+
+        // Times cannot be formatted with a date
+        let format = TemporalFormat::Custom("%d/%m/%Y %H:%M".to_string());
+        write_batch(&batch, format.clone()).unwrap_err();
+
+        let batch = batch.project(&[0, 1, 2, 4]).unwrap();
+        assert_json_eq(
+            &write_batch(&batch, format).unwrap(),
+            r#"{"ts":"31/01/2022 10:00","tz":"31/01/2022 11:00","date":"31/01/2022 00:00","duration":"PT90S"}
+{}
+"#,
+        );
+
+        let format = TemporalFormat::Custom("%H:%M:%S%.3f %z".to_string());
+        write_batch(&batch, format.clone()).unwrap_err();
+        assert_json_eq(
+            &write_batch(&batch.project(&[1]).unwrap(), format).unwrap(),
+            r#"{"tz":"11:00:00.250 +0100"}
+{}
+"#,
+        );
+    }
+
     #[test]
     fn write_dates() {
         let ts_string = "2018-11-13T17:11:10.011375885995";
@@ -1310,7 +1277,7 @@ mod tests {
         let mut buf = Vec::new();
         {
             let mut writer = LineDelimitedWriter::new(&mut buf);
-            writer.write_batches(&[batch]).unwrap();
+            writer.write_batches(std::slice::from_ref(&batch)).unwrap();
         }
 
         let result = String::from_utf8(buf).unwrap();
@@ -1325,6 +1292,200 @@ mod tests {
             }
             assert_eq!(serde_json::from_str::<Value>(r).unwrap(), expected_json,);
         }
+
+        assert_rows_match_writer(&[batch], EncoderOptions::default());
+    }
+
+    #[test]
+    fn write_decimals() {
+        let schema = Schema::new(vec![
+            Field::new("c1", DataType::Decimal128(10, 2), true),
+            Field::new("c2", DataType::Decimal256(40, -2), true),
+        ]);
+
+        let a = Decimal128Array::from(vec![Some(12345), None, Some(-5)])
+            .with_precision_and_scale(10, 2)
+            .unwrap();
+        let b = Decimal256Array::from(vec![
+            Some(i256::from_i128(7)),
+            Some(i256::from_i128(-3)),
+            None,
+        ])
+        .with_precision_and_scale(40, -2)
+        .unwrap();
+
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)])
+                .unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer.write_batches(&[batch]).unwrap();
+        }
+
+        assert_json_eq(
+            &buf,
+            r#"{"c1":"123.45","c2":"700"}
+{"c2":"-300"}
+{"c1":"-0.05"}
+"#,
+        );
+    }
+
+    #[test]
+    fn write_binary() {
+        let schema = Schema::new(vec![
+            Field::new("c1", DataType::Binary, true),
+            Field::new("c2", DataType::FixedSizeBinary(2), true),
+        ]);
+
+        let a = BinaryArray::from(vec![Some(b"foo".as_ref()), None, Some(&[0xFF, 0])]);
+        let b = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            vec![Some(vec![1_u8, 2]), Some(vec![3, 4]), None].into_iter(),
+            2,
+        )
+        .unwrap();
+
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)])
+                .unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let options =
+                EncoderOptions::new().with_binary_encoding(BinaryEncoding::Base64);
+            let mut writer = LineDelimitedWriter::new_with_options(&mut buf, options);
+            writer.write(batch.clone()).unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"c1":"Zm9v","c2":"AQI="}
+{"c2":"AwQ="}
+{"c1":"/wA="}
+"#,
+        );
+
+        let mut buf = Vec::new();
+        {
+            let options = EncoderOptions::new().with_binary_encoding(BinaryEncoding::Hex);
+            let mut writer = LineDelimitedWriter::new_with_options(&mut buf, options);
+            writer.write(batch.clone()).unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"c1":"666f6f","c2":"0102"}
+{"c2":"0304"}
+{"c1":"ff00"}
+"#,
+        );
+
+        // binary values which are not valid UTF-8 cannot be written as strings
+        let options = EncoderOptions::new().with_binary_encoding(BinaryEncoding::Utf8);
+        let mut writer = LineDelimitedWriter::new_with_options(Vec::new(), options);
+        writer.write_batches(&[batch]).unwrap_err();
+    }
+
+    #[test]
+    fn json_binary_decimal_roundtrip() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("c1", DataType::Binary, true),
+            Field::new("c2", DataType::Decimal128(20, 3), true),
+        ]));
+
+        let a = BinaryArray::from(vec![Some(&[0_u8, 159, 146, 150][..]), None]);
+        let b = Decimal128Array::from(vec![None, Some(-1234567)])
+            .with_precision_and_scale(20, 3)
+            .unwrap();
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(a), Arc::new(b)]).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let options =
+                EncoderOptions::new().with_binary_encoding(BinaryEncoding::Base64);
+            let mut writer = LineDelimitedWriter::new_with_options(&mut buf, options);
+            writer.write(batch.clone()).unwrap();
+        }
+
+        let options = DecoderOptions::new().with_binary_encoding(BinaryEncoding::Base64);
+        let mut reader = Reader::new(buf.as_slice(), schema, options);
+        let read = reader.next().unwrap().unwrap();
+        assert_eq!(read, batch);
+    }
+
+    #[test]
+    fn json_binary_default_roundtrip() {
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("c1", DataType::Binary, true)]));
+
+        let a = BinaryArray::from(vec![Some("₁₂₃".as_bytes()), None, Some(b"foo")]);
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(a)]).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer.write(batch.clone()).unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"c1":"₁₂₃"}
+{}
+{"c1":"foo"}
+"#,
+        );
+
+        let mut reader = Reader::new(buf.as_slice(), schema, DecoderOptions::new());
+        let read = reader.next().unwrap().unwrap();
+        assert_eq!(read, batch);
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Inner {
+            c: bool,
+        }
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Row {
+            a: Option<i64>,
+            b: Vec<f64>,
+            s: String,
+            inner: Inner,
+        }
+
+        let rows = vec![
+            Row {
+                a: Some(1),
+                b: vec![1.5, 2.5],
+                s: "foo".to_string(),
+                inner: Inner { c: true },
+            },
+            Row {
+                a: None,
+                b: vec![],
+                s: "bar".to_string(),
+                inner: Inner { c: false },
+            },
+        ];
+
+        let values: Vec<_> = rows
+            .iter()
+            .map(|r| Ok(serde_json::to_value(r).unwrap()))
+            .collect();
+        let schema = infer_json_schema_from_iterator(values.into_iter()).unwrap();
+        let decoder = Decoder::new(Arc::new(schema), DecoderOptions::new());
+        let batch = decoder.serialize(&rows).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 4);
+
+        let read: Vec<Row> = deserialize_record_batches(&[batch]).unwrap();
+        assert_eq!(read, rows);
+
+        // rows must serialize to objects
+        let err = decoder.serialize(&[1, 2]).unwrap_err();
+        assert!(err.to_string().contains("Row needs to be of type object"));
     }
 
     #[test]
@@ -1485,6 +1646,367 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_fixed_size_list() {
+        let list = FixedSizeListArray::from_iter_primitive::<Int32Type, _, _>(
+            vec![
+                Some(vec![Some(1), Some(2)]),
+                None,
+                Some(vec![None, Some(4)]),
+            ],
+            2,
+        );
+        let schema = Schema::new(vec![Field::new("l", list.data_type().clone(), true)]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(list)]).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer.write_batches(&[batch]).unwrap();
+        }
+
+        assert_json_eq(
+            &buf,
+            r#"{"l":[1,2]}
+{}
+{"l":[null,4]}
+"#,
+        );
+    }
+
+    #[test]
+    fn write_map_with_non_string_keys() {
+        let mut builder =
+            MapBuilder::new(None, Int32Builder::new(), StringBuilder::new());
+        builder.keys().append_value(1);
+        builder.values().append_value("a");
+        builder.keys().append_value(2);
+        builder.values().append_null();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.keys().append_value(3);
+        builder.values().append_value("c");
+        builder.append(true).unwrap();
+        let map = builder.finish();
+
+        let schema = Schema::new(vec![Field::new("m", map.data_type().clone(), true)]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(map)]).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer.write(batch.clone()).unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"m":{"1":"a","2":null}}
+{"m":null}
+{"m":{"3":"c"}}
+"#,
+        );
+
+        let mut buf = Vec::new();
+        {
+            let options = EncoderOptions::new().with_map_entries(true);
+            let mut writer = LineDelimitedWriter::new_with_options(&mut buf, options);
+            writer.write(batch.slice(1, 2)).unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"m":null}
+{"m":[{"key":3,"value":"c"}]}
+"#,
+        );
+
+        assert_rows_match_writer(std::slice::from_ref(&batch), EncoderOptions::default());
+        let options = EncoderOptions::new().with_map_entries(true);
+        assert_rows_match_writer(&[batch], options);
+    }
+
+    #[test]
+    fn write_list_of_maps() {
+        let mut builder = ListBuilder::new(MapBuilder::new(
+            None,
+            StringBuilder::new(),
+            Int32Builder::new(),
+        ));
+        builder.values().keys().append_value("a");
+        builder.values().values().append_value(1);
+        builder.values().append(true).unwrap();
+        builder.values().append(true).unwrap();
+        builder.append(true);
+        builder.append(false);
+        let list = builder.finish();
+
+        let schema = Schema::new(vec![Field::new("l", list.data_type().clone(), true)]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(list)]).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer.write_batches(&[batch]).unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"l":[{"a":1},{}]}
+{}
+"#,
+        );
+    }
+
+    #[test]
+    fn write_nested_nulls() {
+        let mut map = MapBuilder::new(None, StringBuilder::new(), Int32Builder::new());
+        map.keys().append_value("a");
+        map.values().append_value(1);
+        map.append(true).unwrap();
+        map.append(false).unwrap();
+        let map = Arc::new(map.finish()) as ArrayRef;
+
+        let list = FixedSizeListArray::from_iter_primitive::<Int32Type, _, _>(
+            vec![Some(vec![Some(1), None]), None],
+            2,
+        );
+        let list = Arc::new(list) as ArrayRef;
+
+        // a list containing each of the above values
+        let wrap = |values: &ArrayRef| {
+            let data = ArrayData::builder(DataType::List(Box::new(Field::new(
+                "item",
+                values.data_type().clone(),
+                true,
+            ))))
+            .len(1)
+            .add_buffer(Buffer::from_slice_ref([0_i32, 2]))
+            .add_child_data(values.data().clone())
+            .build()
+            .unwrap();
+            Arc::new(ListArray::from(data)) as ArrayRef
+        };
+
+        let batch = RecordBatch::try_from_iter([("m", map), ("l", list)]).unwrap();
+        let nested = RecordBatch::try_from_iter([
+            ("m", wrap(batch.column(0))),
+            ("l", wrap(batch.column(1))),
+        ])
+        .unwrap();
+
+        // null values are omitted from objects, except for maps which are written as
+        // null, and written as null within lists
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer
+                .write_batches(&[batch.clone(), nested.clone()])
+                .unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"m":{"a":1},"l":[1,null]}
+{"m":null}
+{"m":[{"a":1},null],"l":[[1,null],null]}
+"#,
+        );
+
+        assert_rows_match_writer(&[batch, nested], EncoderOptions::default());
+    }
+
+    #[test]
+    fn write_unions() {
+        for mut builder in [UnionBuilder::new_dense(), UnionBuilder::new_sparse()] {
+            builder.append::<Int32Type>("a", 1).unwrap();
+            builder.append::<Float64Type>("b", 3.5).unwrap();
+            builder.append_null::<Int32Type>("a").unwrap();
+            builder.append::<Int32Type>("a", 4).unwrap();
+            let union = builder.build().unwrap();
+
+            let schema =
+                Schema::new(vec![Field::new("u", union.data_type().clone(), true)]);
+            let batch =
+                RecordBatch::try_new(Arc::new(schema), vec![Arc::new(union)]).unwrap();
+
+            let mut buf = Vec::new();
+            {
+                let mut writer = LineDelimitedWriter::new(&mut buf);
+                writer.write_batches(std::slice::from_ref(&batch)).unwrap();
+            }
+            assert_json_eq(
+                &buf,
+                r#"{"u":1}
+{"u":3.5}
+{}
+{"u":4}
+"#,
+            );
+            assert_rows_match_writer(&[batch], EncoderOptions::default());
+        }
+    }
+
+    #[test]
+    fn write_pretty_array() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new(
+                "b",
+                DataType::List(Box::new(Field::new("item", DataType::Utf8, true))),
+                true,
+            ),
+        ]);
+        let a = Int32Array::from(vec![Some(1), None]);
+        let mut b = ListBuilder::new(StringBuilder::new());
+        b.values().append_value("x");
+        b.values().append_null();
+        b.append(true);
+        b.append(true);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(a), Arc::new(b.finish())],
+        )
+        .unwrap();
+
+        let mut writer = PrettyArrayWriter::new(vec![]);
+        writer.write(batch).unwrap();
+        writer
+            .write_row(&json!({"a": 2, "c": {"d": true}}))
+            .unwrap();
+        writer.finish().unwrap();
+
+        let expected = r#"[
+  {
+    "a": 1,
+    "b": [
+      "x",
+      null
+    ]
+  },
+  {
+    "b": []
+  },
+  {
+    "a": 2,
+    "c": {
+      "d": true
+    }
+  }
+]"#;
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), expected);
+    }
+
+    /// A writer that records the size of each call to `write`
+    #[derive(Debug, Default)]
+    struct RecordingWriter {
+        data: Vec<u8>,
+        writes: Vec<usize>,
+        flushes: usize,
+    }
+
+    impl Write for RecordingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.data.extend_from_slice(buf);
+            self.writes.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_buffer_size() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let a = Int32Array::from(vec![1, 2, 3, 4]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a)]).unwrap();
+
+        // Rows are buffered until the end of each call
+        let mut writer = LineDelimitedWriter::new(RecordingWriter::default());
+        writer
+            .write_batches(&[batch.clone(), batch.clone()])
+            .unwrap();
+        assert_eq!(writer.writer.writes, vec![64]);
+        writer.flush().unwrap();
+        let inner = writer.into_inner();
+        assert_eq!(inner.writes, vec![64]);
+        assert_eq!(inner.flushes, 1);
+
+        // Each row is 8 bytes, so every second row exceeds the buffer size
+        let mut writer =
+            LineDelimitedWriter::new(RecordingWriter::default()).with_buffer_size(10);
+        writer.write(batch.clone()).unwrap();
+        assert_eq!(writer.writer.writes, vec![16, 16]);
+        writer.write_row(&json!({"a": 5})).unwrap();
+        let inner = writer.into_inner();
+        assert_eq!(inner.writes, vec![16, 16, 8]);
+        assert_eq!(inner.flushes, 0);
+        assert_json_eq(
+            &inner.data,
+            "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n{\"a\":4}\n{\"a\":5}\n",
+        );
+
+        // Errors writing the buffer are returned
+        let mut writer = ArrayWriter::new(FailingWriter);
+        writer.write(batch).unwrap_err();
+    }
+
+    /// A writer that fails every write
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "failed"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_nested_temporal_and_dictionary() {
+        let dates = Date32Array::from(vec![Some(0), None, Some(1)]);
+        let list = ListArray::from(
+            ArrayData::builder(DataType::List(Box::new(Field::new(
+                "item",
+                DataType::Date32,
+                true,
+            ))))
+            .len(2)
+            .add_buffer(Buffer::from_slice_ref([0i32, 2, 3]))
+            .add_child_data(dates.into_data())
+            .build()
+            .unwrap(),
+        );
+        let dict: DictionaryArray<Int32Type> =
+            vec![Some("a"), None].into_iter().collect();
+        let structs = StructArray::from(vec![(
+            Field::new("d", dict.data_type().clone(), true),
+            Arc::new(dict) as ArrayRef,
+        )]);
+
+        let schema = Schema::new(vec![
+            Field::new("l", list.data_type().clone(), true),
+            Field::new("s", structs.data_type().clone(), true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(list), Arc::new(structs)],
+        )
+        .unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer.write(batch).unwrap();
+        }
+        assert_json_eq(
+            &buf,
+            r#"{"l":["1970-01-01",null],"s":{"d":"a"}}
+{"l":["1970-01-02"],"s":{}}
+"#,
+        );
+    }
+
     #[test]
     fn test_write_single_batch() {
         let test_file = "test/data/basic.json";
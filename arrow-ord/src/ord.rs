@@ -72,8 +72,8 @@ fn compare_string<T>(left: &dyn Array, right: &dyn Array) -> DynComparator
 where
     T: OffsetSizeTrait,
 {
-    let left: StringArray = StringArray::from(left.data().clone());
-    let right: StringArray = StringArray::from(right.data().clone());
+    let left: GenericStringArray<T> = GenericStringArray::from(left.data().clone());
+    let right: GenericStringArray<T> = GenericStringArray::from(right.data().clone());
 
     Box::new(move |i, j| left.value(i).cmp(right.value(j)))
 }
@@ -356,6 +356,15 @@ pub mod tests {
         assert_eq!(Ordering::Less, (cmp)(0, 0));
     }
 
+    #[test]
+    fn test_large_string() {
+        let array = LargeStringArray::from(vec!["b", "a"]);
+
+        let cmp = build_compare(&array, &array).unwrap();
+
+        assert_eq!(Ordering::Greater, (cmp)(0, 1));
+    }
+
     #[test]
     fn test_f64() {
         let array = Float64Array::from(vec![1.0, 2.0]);
@@ -29,8 +29,8 @@ use arrow::compute::kernels;
 use arrow::datatypes::{DataType, Field, Schema};
 use arrow::error::ArrowError;
 use arrow::ffi_stream::ArrowArrayStreamReader;
-use arrow::pyarrow::{PyArrowConvert, PyArrowException, PyArrowType};
-use arrow::record_batch::RecordBatch;
+use arrow::pyarrow::{IntoPyArrow, PyArrowConvert, PyArrowException, PyArrowType};
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
 
 fn to_py_err(err: ArrowError) -> PyErr {
     PyArrowException::new_err(err.to_string())
@@ -138,6 +138,15 @@ fn round_trip_record_batch_reader(
     Ok(obj)
 }
 
+#[pyfunction]
+fn round_trip_boxed_record_batch_reader(
+    obj: PyArrowType<ArrowArrayStreamReader>,
+    py: Python,
+) -> PyResult<PyObject> {
+    let reader: Box<dyn RecordBatchReader> = Box::new(obj.0);
+    reader.into_pyarrow(py)
+}
+
 #[pymodule]
 fn arrow_pyarrow_integration_testing(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(double))?;
@@ -151,5 +160,6 @@ fn arrow_pyarrow_integration_testing(_py: Python, m: &PyModule) -> PyResult<()>
     m.add_wrapped(wrap_pyfunction!(round_trip_array))?;
     m.add_wrapped(wrap_pyfunction!(round_trip_record_batch))?;
     m.add_wrapped(wrap_pyfunction!(round_trip_record_batch_reader))?;
+    m.add_wrapped(wrap_pyfunction!(round_trip_boxed_record_batch_reader))?;
     Ok(())
 }
@@ -87,10 +87,6 @@ _supported_pyarrow_types = [
     ),
     pa.dictionary(pa.int8(), pa.string()),
     pa.map_(pa.string(), pa.int32()),
-]
-
-_unsupported_pyarrow_types = [
-    pa.decimal256(76, 38),
     pa.union(
         [pa.field("a", pa.binary(10)), pa.field("b", pa.string())],
         mode=pa.lib.UnionMode_DENSE,
@@ -113,6 +109,10 @@ _unsupported_pyarrow_types = [
     ),
 ]
 
+_unsupported_pyarrow_types = [
+    pa.decimal256(76, 38),
+]
+
 
 @pytest.mark.parametrize("pyarrow_type", _supported_pyarrow_types, ids=str)
 def test_type_roundtrip(pyarrow_type):
@@ -146,6 +146,18 @@ def test_schema_roundtrip():
     assert schema == pyarrow_schema
 
 
+def test_schema_metadata_roundtrip():
+    field = pa.field(
+        "uuid",
+        pa.binary(16),
+        metadata={b"ARROW:extension:name": b"arrow.uuid", b"empty": b""},
+    )
+    pyarrow_schema = pa.schema([field, pa.field("b", pa.int32())], metadata={b"key": b"value"})
+    schema = rust.round_trip_schema(pyarrow_schema)
+    assert schema.equals(pyarrow_schema, check_metadata=True)
+    assert rust.round_trip_field(field).equals(field, check_metadata=True)
+
+
 def test_primitive_python():
     """
     Python -> Rust -> Python
@@ -352,6 +364,32 @@ def test_dictionary_python():
     del a
     del b
 
+def test_sliced_dictionary_python():
+    """
+    Python -> Rust -> Python
+    """
+    a = pa.array(["a", None, "b", "c", "a"], type=pa.dictionary(pa.int32(), pa.string()))
+    a = a.slice(1, 3)
+    b = rust.round_trip_array(a)
+    assert a == b
+    assert b.to_pylist() == [None, "b", "c"]
+    del a
+    del b
+
+def test_nested_dictionary_python():
+    """
+    Python -> Rust -> Python
+    """
+    values = pa.array([[1, 2], None, [3]], type=pa.list_(pa.int64()))
+    a = pa.DictionaryArray.from_arrays(pa.array([2, None, 0, 1], type=pa.int16()), values)
+    b = rust.round_trip_array(a)
+    assert a == b
+    c = rust.round_trip_record_batch(pa.record_batch([a], names=["dict"]))
+    assert c.column(0) == a
+    del a
+    del b
+    del c
+
 def test_record_batch_reader():
     """
     Python -> Rust -> Python
@@ -367,3 +405,68 @@ def test_record_batch_reader():
     assert b.schema == schema
     got_batches = list(b)
     assert got_batches == batches
+
+def test_boxed_record_batch_reader():
+    """
+    Python -> Rust -> Python, through a boxed Rust RecordBatchReader
+    """
+    schema = pa.schema([('ints', pa.list_(pa.int32()))])
+    batches = [
+        pa.record_batch([[[1], [2, 42]]], schema),
+        pa.record_batch([[None, [], [5, 6]]], schema),
+    ]
+    a = pa.RecordBatchReader.from_batches(schema, batches)
+    b = rust.round_trip_boxed_record_batch_reader(a)
+
+    assert b.schema == schema
+    assert list(b) == batches
+
+def test_boxed_record_batch_reader_error():
+    """
+    Errors of an exported Rust RecordBatchReader are raised in Python
+    """
+    schema = pa.schema([('ints', pa.int32())])
+
+    def iter_batches():
+        yield pa.record_batch([[1, 2]], schema)
+        raise ValueError("test error")
+
+    a = pa.RecordBatchReader.from_batches(schema, iter_batches())
+    b = rust.round_trip_boxed_record_batch_reader(a)
+
+    assert b.read_next_batch() == pa.record_batch([[1, 2]], schema)
+    with pytest.raises(pa.ArrowInvalid, match="test error"):
+        b.read_next_batch()
+
+class CapsuleOnly:
+    """
+    Exposes only the Arrow PyCapsule Interface of a pyarrow object
+    """
+    def __init__(self, obj):
+        self.obj = obj
+
+    def __getattr__(self, name):
+        if name in ("__arrow_c_schema__", "__arrow_c_array__", "__arrow_c_stream__"):
+            return getattr(self.obj, name)
+        raise AttributeError(name)
+
+def test_pycapsule_interface():
+    if not hasattr(pa.Schema, "__arrow_c_schema__"):
+        pytest.skip("pyarrow does not implement the Arrow PyCapsule Interface")
+
+    schema = pa.schema([("ints", pa.int32()), ("strs", pa.string())], metadata={b"key": b"value"})
+    assert rust.round_trip_schema(CapsuleOnly(schema)) == schema
+    assert rust.round_trip_type(CapsuleOnly(pa.int32())) == pa.int32()
+
+    a = pa.array([1, None, 3], type=pa.int32())
+    assert rust.round_trip_array(CapsuleOnly(a)) == a
+
+    batch = pa.record_batch([a, pa.array(["a", "b", None])], schema=schema)
+    assert rust.round_trip_record_batch(CapsuleOnly(batch)) == batch
+    sliced = batch.slice(1, 2)
+    assert rust.round_trip_record_batch(CapsuleOnly(sliced)) == sliced
+
+    reader = pa.RecordBatchReader.from_batches(schema, [batch, batch])
+    b = rust.round_trip_record_batch_reader(CapsuleOnly(reader))
+    assert b.schema == schema
+    assert list(b) == [batch, batch]
@@ -81,7 +81,10 @@ pub unsafe fn export_array_into_raw(
 
 #[cfg(test)]
 mod tests {
-    use crate::array::{DictionaryArray, FixedSizeListArray, Int32Array, StringArray};
+    use crate::array::{
+        DictionaryArray, FixedSizeListArray, Int32Array, LargeStringArray, StringArray,
+        UnionBuilder,
+    };
     use crate::buffer::Buffer;
     use crate::error::Result;
     use crate::util::bit_util;
@@ -90,7 +93,7 @@ mod tests {
             Array, ArrayData, BooleanArray, FixedSizeBinaryArray, Int64Array,
             StructArray, UInt32Array, UInt64Array,
         },
-        datatypes::{DataType, Field},
+        datatypes::{DataType, Field, Float64Type, Int32Type},
         ffi::ArrowArray,
     };
     use std::convert::TryFrom;
@@ -301,4 +304,29 @@ mod tests {
         let data = array.data();
         test_round_trip(data)
     }
+
+    #[test]
+    fn test_union() -> Result<()> {
+        let mut builder = UnionBuilder::new_dense();
+        builder.append::<Int32Type>("a", 1).unwrap();
+        builder.append_null::<Float64Type>("b").unwrap();
+        builder.append::<Float64Type>("b", 3.5).unwrap();
+        builder.append::<Int32Type>("a", 4).unwrap();
+        test_round_trip(builder.build().unwrap().data())?;
+
+        let mut builder = UnionBuilder::new_sparse();
+        builder.append::<Int32Type>("a", 1).unwrap();
+        builder.append::<Float64Type>("b", 3.5).unwrap();
+        builder.append_null::<Int32Type>("a").unwrap();
+        let array = builder.build().unwrap();
+        test_round_trip(array.data())?;
+        test_round_trip(&array.data().slice(1, 2))
+    }
+
+    #[test]
+    fn test_large_string() -> Result<()> {
+        let array = LargeStringArray::from(vec![Some("a"), None, Some("bcd")]);
+        test_round_trip(array.data())?;
+        test_round_trip(&array.data().slice(1, 2))
+    }
 }
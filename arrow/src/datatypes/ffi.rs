@@ -19,7 +19,7 @@ use std::convert::TryFrom;
 
 use crate::datatypes::DataType::Map;
 use crate::{
-    datatypes::{DataType, Field, Schema, TimeUnit},
+    datatypes::{DataType, Field, Schema, TimeUnit, UnionMode},
     error::{ArrowError, Result},
     ffi::{FFI_ArrowSchema, Flags},
 };
@@ -94,6 +94,35 @@ impl TryFrom<&FFI_ArrowSchema> for DataType {
                         })?;
                         DataType::FixedSizeList(Box::new(Field::try_from(c_child)?), parsed_num_elems)
                     },
+                    // Union types in format "+ud:type_id,type_id,..." and "+us:type_id,type_id,..."
+                    ["+ud", type_ids] | ["+us", type_ids] => {
+                        let mode = match other.as_bytes()[2] {
+                            b'd' => UnionMode::Dense,
+                            _ => UnionMode::Sparse,
+                        };
+                        let type_ids = type_ids
+                            .split(',')
+                            .filter(|type_id| !type_id.is_empty())
+                            .map(|type_id| type_id.parse::<i8>())
+                            .collect::<std::result::Result<Vec<_>, _>>()
+                            .map_err(|_| {
+                                ArrowError::CDataInterface(
+                                    "The Union type requires integer type ids".to_string(),
+                                )
+                            })?;
+                        let fields = c_schema
+                            .children()
+                            .map(Field::try_from)
+                            .collect::<Result<Vec<_>>>()?;
+                        if fields.len() != type_ids.len() {
+                            return Err(ArrowError::CDataInterface(format!(
+                                "The Union type has {} type ids but {} children",
+                                type_ids.len(),
+                                fields.len()
+                            )));
+                        }
+                        DataType::Union(fields, type_ids, mode)
+                    }
                     // Decimal types in format "d:precision,scale" or "d:precision,scale,bitWidth"
                     ["d", extra] => {
                         match extra.splitn(3, ',').collect::<Vec<&str>>().as_slice() {
@@ -176,7 +205,8 @@ impl TryFrom<&FFI_ArrowSchema> for Field {
 
     fn try_from(c_schema: &FFI_ArrowSchema) -> Result<Self> {
         let dtype = DataType::try_from(c_schema)?;
-        let field = Field::new(c_schema.name(), dtype, c_schema.nullable());
+        let field = Field::new(c_schema.name(), dtype, c_schema.nullable())
+            .with_metadata(c_schema.metadata()?);
         Ok(field)
     }
 }
@@ -188,7 +218,7 @@ impl TryFrom<&FFI_ArrowSchema> for Schema {
         // interpret it as a struct type then extract its fields
         let dtype = DataType::try_from(c_schema)?;
         if let DataType::Struct(fields) = dtype {
-            Ok(Schema::new(fields))
+            Ok(Schema::new_with_metadata(fields, c_schema.metadata()?))
         } else {
             Err(ArrowError::CDataInterface(
                 "Unable to interpret C data struct as a Schema".to_string(),
@@ -211,7 +241,7 @@ impl TryFrom<&DataType> for FFI_ArrowSchema {
             | DataType::Map(child, _) => {
                 vec![FFI_ArrowSchema::try_from(child.as_ref())?]
             }
-            DataType::Struct(fields) => fields
+            DataType::Struct(fields) | DataType::Union(fields, _, _) => fields
                 .iter()
                 .map(FFI_ArrowSchema::try_from)
                 .collect::<Result<Vec<_>>>()?,
@@ -278,6 +308,14 @@ fn get_format_string(dtype: &DataType) -> Result<String> {
         DataType::LargeList(_) => Ok("+L".to_string()),
         DataType::Struct(_) => Ok("+s".to_string()),
         DataType::Map(_, _) => Ok("+m".to_string()),
+        DataType::Union(_, type_ids, mode) => {
+            let mode = match mode {
+                UnionMode::Dense => "d",
+                UnionMode::Sparse => "s",
+            };
+            let type_ids: Vec<_> = type_ids.iter().map(|id| id.to_string()).collect();
+            Ok(format!("+u{}:{}", mode, type_ids.join(",")))
+        }
         DataType::Dictionary(key_data_type, _) => get_format_string(key_data_type),
         other => Err(ArrowError::CDataInterface(format!(
             "The datatype \"{:?}\" is still not supported in Rust implementation",
@@ -302,7 +340,8 @@ impl TryFrom<&Field> for FFI_ArrowSchema {
 
         FFI_ArrowSchema::try_from(field.data_type())?
             .with_name(field.name())?
-            .with_flags(flags)
+            .with_flags(flags)?
+            .with_metadata(field.metadata())
     }
 }
 
@@ -311,7 +350,8 @@ impl TryFrom<&Schema> for FFI_ArrowSchema {
 
     fn try_from(schema: &Schema) -> Result<Self> {
         let dtype = DataType::Struct(schema.fields().clone());
-        let c_schema = FFI_ArrowSchema::try_from(&dtype)?;
+        let c_schema =
+            FFI_ArrowSchema::try_from(&dtype)?.with_metadata(&schema.metadata)?;
         Ok(c_schema)
     }
 }
@@ -345,6 +385,7 @@ mod tests {
     use super::*;
     use crate::datatypes::{DataType, Field, TimeUnit};
     use crate::error::Result;
+    use std::collections::HashMap;
     use std::convert::TryFrom;
 
     fn round_trip_type(dtype: DataType) -> Result<()> {
@@ -391,6 +432,24 @@ mod tests {
             DataType::Utf8,
             true,
         )]))?;
+        round_trip_type(DataType::LargeUtf8)?;
+        round_trip_type(DataType::LargeBinary)?;
+        round_trip_type(DataType::LargeList(Box::new(Field::new(
+            "a",
+            DataType::Int16,
+            true,
+        ))))?;
+        for mode in [UnionMode::Dense, UnionMode::Sparse] {
+            round_trip_type(DataType::Union(
+                vec![
+                    Field::new("a", DataType::Int32, true),
+                    Field::new("b", DataType::Utf8, true),
+                ],
+                vec![3, 7],
+                mode.clone(),
+            ))?;
+            round_trip_type(DataType::Union(vec![], vec![], mode))?;
+        }
         Ok(())
     }
 
@@ -457,4 +516,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_metadata() -> Result<()> {
+        let metadata: HashMap<String, String> = [
+            ("ARROW:extension:name", "uuid"),
+            ("ARROW:extension:metadata", ""),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let field =
+            Field::new("a", DataType::FixedSizeBinary(16), true).with_metadata(metadata);
+        round_trip_field(field.clone())?;
+
+        let schema = Schema::new_with_metadata(
+            vec![field, Field::new("b", DataType::Utf8, false)],
+            [("key".to_string(), "value".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        round_trip_schema(schema.clone())?;
+
+        // the metadata is encoded as its number of entries, followed by the
+        // length and bytes of each key and value
+        let c_schema = FFI_ArrowSchema::try_from(&schema)?;
+        let mut expected = 1_i32.to_ne_bytes().to_vec();
+        expected.extend_from_slice(&3_i32.to_ne_bytes());
+        expected.extend_from_slice(b"key");
+        expected.extend_from_slice(&5_i32.to_ne_bytes());
+        expected.extend_from_slice(b"value");
+        let encoded = unsafe {
+            std::slice::from_raw_parts(c_schema.metadata as *const u8, expected.len())
+        };
+        assert_eq!(encoded, expected.as_slice());
+
+        // empty metadata is exported as null
+        assert!(c_schema.child(1).metadata.is_null());
+        assert!(c_schema.child(1).metadata()?.is_empty());
+        Ok(())
+    }
 }
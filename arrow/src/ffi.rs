@@ -24,6 +24,28 @@
 //! The second interface maps native Rust types to the Rust-specific implementation of Arrow such as `format` to `Datatype`,
 //! `Buffer`, etc. This is handled by `ArrowArray`.
 //!
+//! The safe [`to_ffi`] and [`from_ffi`] export and import [`ArrayData`] as a pair
+//! of [`FFI_ArrowArray`] and [`FFI_ArrowSchema`]:
+//!
+//! ```rust
+//! # use arrow::array::{Array, Int32Array, make_array};
+//! # use arrow::error::Result;
+//! # use arrow::ffi::{from_ffi, to_ffi};
+//! # fn main() -> Result<()> {
+//! let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+//!
+//! // export it, handing the structs to a consumer
+//! let (ffi_array, ffi_schema) = to_ffi(array.data())?;
+//!
+//! // import it, with the structs as received from a producer
+//! let data = from_ffi(ffi_array, &ffi_schema)?;
+//! assert_eq!(make_array(data).as_ref(), &array as &dyn Array);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Lower level functions exchange raw pointers to the structs:
+//!
 //! ```rust
 //! # use std::sync::Arc;
 //! # use arrow::array::{Int32Array, Array, ArrayData, export_array_into_raw, make_array, make_array_from_raw};
@@ -110,6 +132,7 @@ To export an array, create an `ArrowArray` using [ArrowArray::try_new].
 */
 
 use std::{
+    collections::HashMap,
     convert::TryFrom,
     ffi::CStr,
     ffi::CString,
@@ -124,7 +147,7 @@ use bitflags::bitflags;
 
 use crate::array::{layout, ArrayData};
 use crate::buffer::{Buffer, MutableBuffer};
-use crate::datatypes::DataType;
+use crate::datatypes::{DataType, UnionMode};
 use crate::error::{ArrowError, Result};
 use crate::util::bit_util;
 
@@ -156,6 +179,7 @@ pub struct FFI_ArrowSchema {
 struct SchemaPrivateData {
     children: Box<[*mut FFI_ArrowSchema]>,
     dictionary: *mut FFI_ArrowSchema,
+    metadata: Option<Vec<u8>>,
 }
 
 // callback used to drop [FFI_ArrowSchema] when it is exported.
@@ -212,6 +236,7 @@ impl FFI_ArrowSchema {
         let mut private_data = Box::new(SchemaPrivateData {
             children: children_ptr,
             dictionary: dictionary_ptr,
+            metadata: None,
         });
 
         // intentionally set from private_data (see https://github.com/apache/arrow-rs/issues/580)
@@ -234,6 +259,52 @@ impl FFI_ArrowSchema {
         Ok(self)
     }
 
+    /// Sets the key/value `metadata` of this schema, encoded as described in
+    /// <https://arrow.apache.org/docs/format/CDataInterface.html#c.ArrowSchema.metadata>
+    ///
+    /// Empty metadata is exported as a null pointer
+    pub fn with_metadata(mut self, metadata: &HashMap<String, String>) -> Result<Self> {
+        if self.private_data.is_null() {
+            return Err(ArrowError::CDataInterface(
+                "Cannot set the metadata of a schema not created by Rust".to_string(),
+            ));
+        }
+
+        let encoded = if metadata.is_empty() {
+            None
+        } else {
+            let len = |len: usize| {
+                i32::try_from(len).map(i32::to_ne_bytes).map_err(|_| {
+                    ArrowError::CDataInterface(
+                        "Schema metadata is too large to be exported".to_string(),
+                    )
+                })
+            };
+
+            // sort the entries, so that the encoding is deterministic
+            let mut entries: Vec<_> = metadata.iter().collect();
+            entries.sort_unstable();
+
+            let mut encoded = len(entries.len())?.to_vec();
+            for (key, value) in entries {
+                encoded.extend_from_slice(&len(key.len())?);
+                encoded.extend_from_slice(key.as_bytes());
+                encoded.extend_from_slice(&len(value.len())?);
+                encoded.extend_from_slice(value.as_bytes());
+            }
+            Some(encoded)
+        };
+
+        // safe because `private_data` is only ever set by `try_new`
+        let private_data = unsafe { &mut *(self.private_data as *mut SchemaPrivateData) };
+        self.metadata = encoded
+            .as_ref()
+            .map(|encoded| encoded.as_ptr() as *const c_char)
+            .unwrap_or(std::ptr::null());
+        private_data.metadata = encoded;
+        Ok(self)
+    }
+
     pub fn empty() -> Self {
         Self {
             format: std::ptr::null_mut(),
@@ -270,6 +341,54 @@ impl FFI_ArrowSchema {
         Flags::from_bits(self.flags)
     }
 
+    /// returns the key/value metadata of this schema, which is empty if it has none.
+    pub fn metadata(&self) -> Result<HashMap<String, String>> {
+        if self.metadata.is_null() {
+            return Ok(HashMap::new());
+        }
+
+        let mut ptr = self.metadata as *const u8;
+        // safe because the metadata starts with its i32 number of entries, followed
+        // by each key and value prefixed by their i32 length, as required by the
+        // C Data Interface
+        let mut read_i32 = || unsafe {
+            let value = ptr::read_unaligned(ptr as *const i32);
+            ptr = ptr.add(size_of::<i32>());
+            value
+        };
+        let num_entries = read_i32();
+        if num_entries < 0 {
+            return Err(ArrowError::CDataInterface(format!(
+                "The schema metadata has a negative number of entries: {}",
+                num_entries
+            )));
+        }
+
+        let mut read_str = || -> Result<String> {
+            let len = unsafe { ptr::read_unaligned(ptr as *const i32) };
+            if len < 0 {
+                return Err(ArrowError::CDataInterface(format!(
+                    "The schema metadata has a key or value of negative length: {}",
+                    len
+                )));
+            }
+            let bytes = unsafe {
+                let data = ptr.add(size_of::<i32>());
+                ptr = data.add(len as usize);
+                std::slice::from_raw_parts(data, len as usize)
+            };
+            String::from_utf8(bytes.to_vec()).map_err(|_| {
+                ArrowError::CDataInterface(
+                    "The external API has non-utf8 schema metadata".to_string(),
+                )
+            })
+        };
+
+        (0..num_entries)
+            .map(|_| Ok((read_str()?, read_str()?)))
+            .collect()
+    }
+
     pub fn child(&self, index: usize) -> &Self {
         assert!(index < self.n_children as usize);
         unsafe { self.children.add(index).as_ref().unwrap().as_ref().unwrap() }
@@ -310,6 +429,22 @@ impl Drop for FFI_ArrowSchema {
 #[allow(clippy::manual_bits)]
 fn bit_width(data_type: &DataType, i: usize) -> Result<usize> {
     Ok(match (data_type, i) {
+        // unions have no null buffer, their first buffer holds the i8 type ids
+        // and dense unions have a second buffer of i32 offsets
+        (DataType::Union(_, _, _), 0) => size_of::<i8>() * 8,
+        (DataType::Union(_, _, UnionMode::Dense), 1) => size_of::<i32>() * 8,
+        (DataType::Union(_, _, UnionMode::Sparse), _) => {
+            return Err(ArrowError::CDataInterface(format!(
+                "The datatype \"{:?}\" expects 1 buffer, but requested {}. Please verify that the C data interface is correctly implemented.",
+                data_type, i
+            )))
+        }
+        (DataType::Union(_, _, UnionMode::Dense), _) => {
+            return Err(ArrowError::CDataInterface(format!(
+                "The datatype \"{:?}\" expects 2 buffers, but requested {}. Please verify that the C data interface is correctly implemented.",
+                data_type, i
+            )))
+        }
         // the null buffer is bit sized
         (_, 0) => 1,
         // primitive types first buffer's size is given by the native types
@@ -632,13 +767,12 @@ pub trait ArrowArrayRef {
             None
         };
 
-        let mut child_data: Vec<ArrayData> = (0..self.array().n_children as usize)
+        let mut child_data = (0..self.array().n_children as usize)
             .map(|i| {
                 let child = self.child(i);
                 child.to_data()
             })
-            .map(|d| d.unwrap())
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         if let Some(d) = self.dictionary() {
             // For dictionary type there should only be a single child, so we don't need to worry if
@@ -693,6 +827,9 @@ pub trait ArrowArrayRef {
     // Rust implementation uses fixed-sized buffers, which require knowledge of their `len`.
     // for variable-sized buffers, such as the second buffer of a stringArray, we need
     // to fetch offset buffer's len to build the second buffer.
+    //
+    // Buffers start at the beginning of the array, so the length of a sliced array
+    // includes the `offset` values preceding it.
     fn buffer_len(&self, i: usize) -> Result<usize> {
         // Special handling for dictionary type as we only care about the key type in the case.
         let t = self.data_type()?;
@@ -701,6 +838,8 @@ pub trait ArrowArrayRef {
             dt => dt,
         };
 
+        let length = self.array().offset() + self.array().len();
+
         // Inner type is not important for buffer length.
         Ok(match (&data_type, i) {
             (DataType::Utf8, 1)
@@ -713,7 +852,7 @@ pub trait ArrowArrayRef {
                 // the len of the offset buffer (buffer 1) equals length + 1
                 let bits = bit_width(data_type, i)?;
                 debug_assert_eq!(bits % 8, 0);
-                (self.array().length as usize + 1) * (bits / 8)
+                (length + 1) * (bits / 8)
             }
             (DataType::Utf8, 2) | (DataType::Binary, 2) => {
                 // the len of the data buffer (buffer 2) equals the last value of the offset buffer (buffer 1)
@@ -742,7 +881,7 @@ pub trait ArrowArrayRef {
             // buffer len of primitive types
             _ => {
                 let bits = bit_width(data_type, i)?;
-                bit_util::ceil(self.array().length as usize * bits, 8)
+                bit_util::ceil(length * bits, 8)
             }
         })
     }
@@ -752,7 +891,7 @@ pub trait ArrowArrayRef {
     /// The C Data interface's null buffer is part of the array of buffers.
     fn null_bit_buffer(&self) -> Option<Buffer> {
         // similar to `self.buffer_len(0)`, but without `Result`.
-        let buffer_len = bit_util::ceil(self.array().length as usize, 8);
+        let buffer_len = bit_util::ceil(self.array().offset() + self.array().len(), 8);
 
         unsafe { create_buffer(self.owner().clone(), self.array(), 0, buffer_len) }
     }
@@ -911,6 +1050,156 @@ impl ArrowArray {
     }
 }
 
+/// Exports `data` to the C Data Interface, returning the [`FFI_ArrowArray`] and
+/// [`FFI_ArrowSchema`] to hand to a consumer
+///
+/// The buffers of `data` are kept alive until the consumer calls the `release`
+/// callback of the [`FFI_ArrowArray`], or it is dropped
+pub fn to_ffi(data: &ArrayData) -> Result<(FFI_ArrowArray, FFI_ArrowSchema)> {
+    let schema = FFI_ArrowSchema::try_from(data.data_type())?;
+    let array = FFI_ArrowArray::new(data);
+    Ok((array, schema))
+}
+
+/// Imports [`ArrayData`] from the C Data Interface, taking ownership of `array`
+///
+/// The buffers of the returned data keep `array` alive, and call its `release`
+/// callback once they are all dropped. `schema` is only borrowed, and remains
+/// owned by the caller.
+///
+/// The imported data is fully validated, so that a producer not respecting the
+/// Arrow specification results in an error rather than undefined behavior.
+/// [`FFI_ArrowArray`] and [`FFI_ArrowSchema`] can only be populated by a producer
+/// through `unsafe` code, which is responsible for them following the C Data Interface.
+///
+/// # Error
+/// Errors if `array` or `schema` have been released, or the data is invalid
+pub fn from_ffi(array: FFI_ArrowArray, schema: &FFI_ArrowSchema) -> Result<ArrayData> {
+    if array.release.is_none() {
+        return Err(ArrowError::CDataInterface(
+            "Cannot import an array that has been released".to_string(),
+        ));
+    }
+    if schema.release.is_none() || schema.format.is_null() {
+        return Err(ArrowError::CDataInterface(
+            "Cannot import an array with a schema that has been released".to_string(),
+        ));
+    }
+
+    validate_children(&array, schema)?;
+    validate_header(&array, schema)?;
+
+    let owner = Arc::new(array);
+    let data = ArrowArrayChild::from_raw(&owner, schema, owner.clone()).to_data()?;
+    data.validate_full()?;
+    Ok(data)
+}
+
+/// Validates the lengths, buffer and child counts of `array`, and recursively of
+/// its children and dictionary, against `schema` before any buffer is dereferenced
+fn validate_header(array: &FFI_ArrowArray, schema: &FFI_ArrowSchema) -> Result<()> {
+    let data_type = DataType::try_from(schema)?;
+
+    let end = array.offset.checked_add(array.length);
+    if array.length < 0
+        || array.offset < 0
+        || end.is_none()
+        || array.null_count < -1
+        || array.null_count > array.length
+    {
+        return Err(ArrowError::CDataInterface(format!(
+            "Invalid length {}, offset {} or null count {} of array of type {:?}",
+            array.length, array.offset, array.null_count, data_type
+        )));
+    }
+
+    let data_layout = layout(&data_type);
+    let n_buffers =
+        data_layout.buffers.len() + usize::from(data_layout.can_contain_null_mask);
+    if array.n_buffers != n_buffers as i64 {
+        return Err(ArrowError::CDataInterface(format!(
+            "Expected {} buffers for array of type {:?}, got {}",
+            n_buffers, data_type, array.n_buffers
+        )));
+    }
+    if n_buffers > 0 && array.buffers.is_null() {
+        return Err(ArrowError::CDataInterface(format!(
+            "The buffers of array of type {:?} are null",
+            data_type
+        )));
+    }
+
+    let expected_children = match &data_type {
+        DataType::List(_)
+        | DataType::LargeList(_)
+        | DataType::FixedSizeList(_, _)
+        | DataType::Map(_, _) => 1,
+        DataType::Struct(fields) | DataType::Union(fields, _, _) => fields.len(),
+        _ => 0,
+    };
+    if array.n_children != expected_children as i64 {
+        return Err(ArrowError::CDataInterface(format!(
+            "Expected {} children for array of type {:?}, got {}",
+            expected_children, data_type, array.n_children
+        )));
+    }
+    for index in 0..array.n_children as usize {
+        // Safety: the children were validated by `validate_children`
+        let (child, child_schema) =
+            unsafe { (*array.children.add(index), *schema.children.add(index)) };
+        validate_header(unsafe { &*child }, unsafe { &*child_schema })?;
+    }
+
+    match array.dictionary.is_null() {
+        true => Ok(()),
+        false => validate_header(unsafe { &*array.dictionary }, unsafe {
+            &*schema.dictionary
+        }),
+    }
+}
+
+/// Validates the child counts and the child and dictionary pointers of `array` and
+/// `schema`, and recursively of their children and dictionaries
+///
+/// These are read when determining the [`DataType`] of `schema`, and so must be
+/// validated before [`validate_header`]
+fn validate_children(array: &FFI_ArrowArray, schema: &FFI_ArrowSchema) -> Result<()> {
+    if array.n_children < 0 || array.n_children != schema.n_children {
+        return Err(ArrowError::CDataInterface(format!(
+            "Invalid number of children {} of array, expected {} from schema",
+            array.n_children, schema.n_children
+        )));
+    }
+    if array.n_children > 0 && (array.children.is_null() || schema.children.is_null()) {
+        return Err(ArrowError::CDataInterface(
+            "The children of array are null".to_string(),
+        ));
+    }
+    for index in 0..array.n_children as usize {
+        // Safety: the FFI structs contain n_children pointers to their children
+        let (child, child_schema) =
+            unsafe { (*array.children.add(index), *schema.children.add(index)) };
+        if child.is_null() || child_schema.is_null() {
+            return Err(ArrowError::CDataInterface(format!(
+                "Child {} of array is null",
+                index
+            )));
+        }
+        validate_children(unsafe { &*child }, unsafe { &*child_schema })?;
+    }
+
+    match (array.dictionary.is_null(), schema.dictionary.is_null()) {
+        (true, true) => Ok(()),
+        (false, false) => validate_children(unsafe { &*array.dictionary }, unsafe {
+            &*schema.dictionary
+        }),
+        _ => Err(ArrowError::CDataInterface(
+            "Dictionary should both be set or not set in FFI_ArrowArray and FFI_ArrowSchema"
+                .to_string(),
+        )),
+    }
+}
+
 impl<'a> ArrowArrayChild<'a> {
     fn from_raw(
         array: &'a FFI_ArrowArray,
@@ -932,8 +1221,8 @@ mod tests {
         export_array_into_raw, make_array, Array, ArrayData, BooleanArray,
         Decimal128Array, DictionaryArray, DurationSecondArray, FixedSizeBinaryArray,
         FixedSizeListArray, GenericBinaryArray, GenericListArray, GenericStringArray,
-        Int32Array, MapArray, NullArray, OffsetSizeTrait, Time32MillisecondArray,
-        TimestampMillisecondArray, UInt32Array,
+        Int32Array, Int8Array, MapArray, NullArray, OffsetSizeTrait, StringArray,
+        Time32MillisecondArray, TimestampMillisecondArray, UInt32Array,
     };
     use crate::compute::kernels;
     use crate::datatypes::{Field, Int8Type};
@@ -1384,6 +1673,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_sliced_dictionary() -> Result<()> {
+        // create a sliced array natively, exported with a non-zero offset
+        let values = vec![Some("a"), None, Some("bb"), Some("a"), Some("ccc"), None];
+        let dict_array: DictionaryArray<Int8Type> = values.into_iter().collect();
+        let sliced = dict_array.slice(1, 4);
+
+        // export it
+        let array = ArrowArray::try_from(sliced.data().clone())?;
+        assert_eq!(array.array.offset(), 1);
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        data.validate_full()?;
+        let array = make_array(data);
+
+        // verify
+        let actual = array
+            .as_any()
+            .downcast_ref::<DictionaryArray<Int8Type>>()
+            .unwrap();
+        assert_eq!(
+            actual.keys(),
+            &Int8Array::from(vec![None, Some(1), Some(0), Some(2)])
+        );
+        let values = actual.values().as_any().downcast_ref::<StringArray>();
+        assert_eq!(values.unwrap(), &StringArray::from(vec!["a", "bb", "ccc"]));
+
+        // (drop/release)
+        Ok(())
+    }
+
     #[test]
     fn test_export_array_into_raw() -> Result<()> {
         let array = make_array(Int32Array::from(vec![1, 2, 3]).into_data());
@@ -1500,4 +1821,99 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_to_from_ffi() -> Result<()> {
+        let array = StringArray::from(vec![Some("a"), None, Some("bcd"), Some("")]);
+        let sliced = array.slice(1, 3);
+
+        let (ffi_array, ffi_schema) = to_ffi(sliced.data())?;
+        let data = from_ffi(ffi_array, &ffi_schema)?;
+        assert_eq!(&data, sliced.data());
+
+        // released structs are rejected
+        let err = from_ffi(FFI_ArrowArray::empty(), &ffi_schema).unwrap_err();
+        assert!(err.to_string().contains("released"), "{}", err);
+        let (ffi_array, _) = to_ffi(sliced.data())?;
+        let err = from_ffi(ffi_array, &FFI_ArrowSchema::empty()).unwrap_err();
+        assert!(err.to_string().contains("released"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_ffi_validates() -> Result<()> {
+        // dictionary keys out of bounds of the values
+        let values = StringArray::from(vec!["a", "b"]);
+        let data = unsafe {
+            ArrayData::new_unchecked(
+                DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+                2,
+                None,
+                None,
+                0,
+                vec![Buffer::from_slice_ref([0_i8, 5])],
+                vec![values.into_data()],
+            )
+        };
+
+        let (ffi_array, ffi_schema) = to_ffi(&data)?;
+        let err = from_ffi(ffi_array, &ffi_schema).unwrap_err();
+        assert!(err.to_string().contains("out of bounds"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_ffi_validates_header() -> Result<()> {
+        let array = StringArray::from(vec![Some("a"), None, Some("bcd")]);
+
+        let (mut ffi_array, ffi_schema) = to_ffi(array.data())?;
+        ffi_array.length = -1;
+        let err = from_ffi(ffi_array, &ffi_schema).unwrap_err();
+        assert!(err.to_string().contains("Invalid length -1"), "{}", err);
+
+        let (mut ffi_array, ffi_schema) = to_ffi(array.data())?;
+        ffi_array.offset = i64::MAX;
+        let err = from_ffi(ffi_array, &ffi_schema).unwrap_err();
+        assert!(err.to_string().contains("Invalid length"), "{}", err);
+
+        let (mut ffi_array, ffi_schema) = to_ffi(array.data())?;
+        ffi_array.n_buffers = 2;
+        let err = from_ffi(ffi_array, &ffi_schema).unwrap_err();
+        assert!(err.to_string().contains("Expected 3 buffers"), "{}", err);
+
+        let list =
+            GenericListArray::<i32>::from_iter_primitive::<Int8Type, _, _>(vec![Some(
+                vec![Some(1)],
+            )]);
+        let (mut ffi_array, ffi_schema) = to_ffi(list.data())?;
+        ffi_array.n_children = 2;
+        let err = from_ffi(ffi_array, &ffi_schema).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid number of children 2"),
+            "{}",
+            err
+        );
+
+        // negative child counts are rejected before any child is dereferenced
+        let (mut ffi_array, mut ffi_schema) = to_ffi(list.data())?;
+        ffi_array.n_children = -1;
+        ffi_schema.n_children = -1;
+        let err = from_ffi(ffi_array, &ffi_schema).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid number of children -1"),
+            "{}",
+            err
+        );
+
+        // the child count must match the data type
+        let (list_array, list_schema) = to_ffi(list.data())?;
+        let (mut ffi_array, mut ffi_schema) = to_ffi(Int32Array::from(vec![1]).data())?;
+        ffi_array.n_children = 1;
+        ffi_array.children = list_array.children;
+        ffi_schema.n_children = 1;
+        ffi_schema.children = list_schema.children;
+        let err = from_ffi(ffi_array, &ffi_schema).unwrap_err();
+        assert!(err.to_string().contains("Expected 0 children"), "{}", err);
+        Ok(())
+    }
 }
@@ -0,0 +1,257 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Contains declarations to bind to the [C Device Data Interface](https://arrow.apache.org/docs/format/CDeviceDataInterface.html).
+//!
+//! An [`FFI_ArrowDeviceArray`] is an [`FFI_ArrowArray`] whose buffers are resident on the
+//! device identified by its [`DeviceType`] and device id, such as a CUDA GPU. Its buffers
+//! are never read by arrow-rs, so a device array can be received from one library and
+//! handed to another without copying its buffers to the host.
+//!
+//! Only arrays whose buffers are accessible from the host, such as [`DeviceType::CPU`],
+//! can be imported as [`ArrayData`]:
+//!
+//! ```rust
+//! # use arrow::array::{Array, Int32Array};
+//! # use arrow::error::Result;
+//! # use arrow::ffi::FFI_ArrowSchema;
+//! # use arrow::ffi_device::{from_ffi_device, DeviceType, FFI_ArrowDeviceArray};
+//! # use std::convert::TryFrom;
+//! # fn main() -> Result<()> {
+//! let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+//!
+//! // export it
+//! let device_array = FFI_ArrowDeviceArray::from_cpu(array.data());
+//! let schema = FFI_ArrowSchema::try_from(array.data_type())?;
+//! assert_eq!(device_array.device_type(), DeviceType::CPU);
+//!
+//! // import it
+//! let data = from_ffi_device(device_array, &schema)?;
+//! assert_eq!(&data, array.data());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::os::raw::c_void;
+
+use crate::array::ArrayData;
+use crate::error::{ArrowError, Result};
+use crate::ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema};
+
+/// The type of device an [`FFI_ArrowDeviceArray`] is resident on
+///
+/// This is an open set of values, so that device types defined by newer
+/// versions of the specification can be received
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceType(pub i32);
+
+impl DeviceType {
+    /// CPU device, same as using [`FFI_ArrowArray`] directly
+    pub const CPU: Self = Self(1);
+    /// CUDA GPU device
+    pub const CUDA: Self = Self(2);
+    /// Pinned CUDA CPU memory allocated by `cudaMallocHost`
+    pub const CUDA_HOST: Self = Self(3);
+    /// OpenCL device
+    pub const OPENCL: Self = Self(4);
+    /// Vulkan buffer for next-gen graphics
+    pub const VULKAN: Self = Self(7);
+    /// Metal for Apple GPU
+    pub const METAL: Self = Self(8);
+    /// Verilog simulator buffer
+    pub const VPI: Self = Self(9);
+    /// ROCm GPUs for AMD GPUs
+    pub const ROCM: Self = Self(10);
+    /// Pinned ROCm CPU memory allocated by `hipMallocHost`
+    pub const ROCM_HOST: Self = Self(11);
+    /// Reserved for extension devices
+    pub const EXT_DEV: Self = Self(12);
+    /// CUDA managed/unified memory allocated by `cudaMallocManaged`
+    pub const CUDA_MANAGED: Self = Self(13);
+    /// Unified shared memory allocated on an Intel oneAPI non-partitioned device
+    pub const ONEAPI: Self = Self(14);
+    /// GPU support for next-gen WebGPU standard
+    pub const WEBGPU: Self = Self(15);
+    /// Qualcomm Hexagon DSP
+    pub const HEXAGON: Self = Self(16);
+
+    /// Returns whether the memory of this device type can be read by the host
+    pub fn is_host_accessible(&self) -> bool {
+        matches!(
+            *self,
+            Self::CPU | Self::CUDA_HOST | Self::ROCM_HOST | Self::CUDA_MANAGED
+        )
+    }
+}
+
+/// ABI-compatible struct for `ArrowDeviceArray` from C Device Data Interface
+/// See <https://arrow.apache.org/docs/format/CDeviceDataInterface.html#structure-definitions>
+///
+/// Dropping it calls the `release` callback of its [`FFI_ArrowArray`], which releases
+/// the device array as a whole
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFI_ArrowDeviceArray {
+    pub(crate) array: FFI_ArrowArray,
+    pub(crate) device_id: i64,
+    pub(crate) device_type: DeviceType,
+    pub(crate) sync_event: *mut c_void,
+    pub(crate) reserved: [i64; 3],
+}
+
+impl FFI_ArrowDeviceArray {
+    /// Creates a new [`FFI_ArrowDeviceArray`] from an `array` whose buffers are resident
+    /// on the device identified by `device_type` and `device_id`
+    ///
+    /// `sync_event` points to an event of the device, such as a `cudaEvent_t*`, that a
+    /// consumer must wait on before accessing the buffers, or is null if the buffers
+    /// are already safe to access. The event must remain valid until the array is released.
+    ///
+    /// # Safety
+    ///
+    /// The buffers of `array` must be resident on the given device, and `sync_event`
+    /// must be null or a valid event for its device type
+    pub unsafe fn new(
+        array: FFI_ArrowArray,
+        device_type: DeviceType,
+        device_id: i64,
+        sync_event: *mut c_void,
+    ) -> Self {
+        Self {
+            array,
+            device_id,
+            device_type,
+            sync_event,
+            reserved: [0; 3],
+        }
+    }
+
+    /// Creates a new [`FFI_ArrowDeviceArray`] exporting `data` on the CPU
+    pub fn from_cpu(data: &ArrayData) -> Self {
+        // safe as `data` is resident on the CPU, which needs no sync event
+        unsafe {
+            Self::new(
+                FFI_ArrowArray::new(data),
+                DeviceType::CPU,
+                -1,
+                std::ptr::null_mut(),
+            )
+        }
+    }
+
+    /// creates an empty [`FFI_ArrowDeviceArray`], which can be used to import data into
+    pub fn empty() -> Self {
+        Self {
+            array: FFI_ArrowArray::empty(),
+            device_id: -1,
+            device_type: DeviceType::CPU,
+            sync_event: std::ptr::null_mut(),
+            reserved: [0; 3],
+        }
+    }
+
+    /// the array whose buffers are resident on the device
+    pub fn array(&self) -> &FFI_ArrowArray {
+        &self.array
+    }
+
+    /// the type of device the buffers are resident on
+    pub fn device_type(&self) -> DeviceType {
+        self.device_type
+    }
+
+    /// the id of the device the buffers are resident on, -1 for the CPU
+    pub fn device_id(&self) -> i64 {
+        self.device_id
+    }
+
+    /// the event to wait on before accessing the buffers, null if none is needed
+    pub fn sync_event(&self) -> *mut c_void {
+        self.sync_event
+    }
+
+    /// Returns the [`FFI_ArrowArray`] of this device array, which takes ownership
+    /// of its buffers
+    pub fn into_array(self) -> FFI_ArrowArray {
+        self.array
+    }
+}
+
+/// Imports [`ArrayData`] from the C Device Data Interface, see [`from_ffi`]
+///
+/// The caller must have waited on the [`FFI_ArrowDeviceArray::sync_event`], if any,
+/// before calling this function.
+///
+/// # Error
+/// Errors if the buffers of `array` are not accessible from the host, or the
+/// data is invalid
+pub fn from_ffi_device(
+    array: FFI_ArrowDeviceArray,
+    schema: &FFI_ArrowSchema,
+) -> Result<ArrayData> {
+    if !array.device_type.is_host_accessible() {
+        return Err(ArrowError::CDataInterface(format!(
+            "Cannot import an array resident on device type {} into host memory",
+            array.device_type.0
+        )));
+    }
+    from_ffi(array.into_array(), schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::{Array, StringArray};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_layout() {
+        // the structs are passed by pointer, so must match the C definition
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(std::mem::size_of::<FFI_ArrowArray>(), 80);
+            assert_eq!(std::mem::size_of::<FFI_ArrowDeviceArray>(), 128);
+        }
+    }
+
+    #[test]
+    fn test_device_round_trip() -> Result<()> {
+        let array = StringArray::from(vec![Some("a"), None, Some("bcd")]);
+        let schema = FFI_ArrowSchema::try_from(array.data_type())?;
+
+        let device_array = FFI_ArrowDeviceArray::from_cpu(array.data());
+        assert_eq!(device_array.device_id(), -1);
+        assert!(device_array.sync_event().is_null());
+        let data = from_ffi_device(device_array, &schema)?;
+        assert_eq!(&data, array.data());
+
+        // buffers on a device can be exchanged, but not imported
+        let device_array = unsafe {
+            FFI_ArrowDeviceArray::new(
+                FFI_ArrowArray::new(array.data()),
+                DeviceType::CUDA,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(device_array.device_type(), DeviceType::CUDA);
+        assert_eq!(device_array.array().len(), 3);
+        let err = from_ffi_device(device_array, &schema).unwrap_err();
+        assert!(err.to_string().contains("device type 2"), "{}", err);
+        Ok(())
+    }
+}
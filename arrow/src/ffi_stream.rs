@@ -24,45 +24,42 @@
 //! The second interface is used to import `FFI_ArrowArrayStream` as Rust implementation `RecordBatch` reader.
 //! This is handled by `ArrowArrayStreamReader`.
 //!
-//! ```ignore
+//! ```no_run
 //! # use std::fs::File;
-//! # use std::sync::Arc;
 //! # use arrow::error::Result;
 //! # use arrow::ffi_stream::{export_reader_into_raw, ArrowArrayStreamReader, FFI_ArrowArrayStream};
 //! # use arrow::ipc::reader::FileReader;
 //! # use arrow::record_batch::RecordBatchReader;
 //! # fn main() -> Result<()> {
 //! // create an record batch reader natively
-//! let file = File::open("arrow_file").unwrap();
-//! let reader = Box::new(FileReader::try_new(file).unwrap());
+//! let file = File::open("arrow_file")?;
+//! let reader = Box::new(FileReader::try_new(file, None)?);
 //!
 //! // export it
 //! let stream = Box::new(FFI_ArrowArrayStream::empty());
-//! let stream_ptr = Box::into_raw(stream) as *mut FFI_ArrowArrayStream;
+//! let stream_ptr = Box::into_raw(stream);
 //! unsafe { export_reader_into_raw(reader, stream_ptr) };
 //!
 //! // consumed and used by something else...
 //!
 //! // import it
-//! let stream_reader = unsafe { ArrowArrayStreamReader::from_raw(stream_ptr).unwrap() };
+//! let stream_reader = unsafe { ArrowArrayStreamReader::from_raw(stream_ptr)? };
 //! let imported_schema = stream_reader.schema();
 //!
 //! let mut produced_batches = vec![];
 //! for batch in stream_reader {
-//!      produced_batches.push(batch.unwrap());
+//!      produced_batches.push(batch?);
 //! }
 //!
 //! // (drop/release)
-//! unsafe {
-//!   Box::from_raw(stream_ptr);
-//! }
+//! drop(unsafe { Box::from_raw(stream_ptr) });
 //! Ok(())
 //! }
 //! ```
 
 use std::{
     convert::TryFrom,
-    ffi::CString,
+    ffi::{CStr, CString},
     os::raw::{c_char, c_int, c_void},
     sync::Arc,
 };
@@ -123,7 +120,9 @@ unsafe extern "C" fn release_stream(stream: *mut FFI_ArrowArrayStream) {
 
 struct StreamPrivateData {
     batch_reader: Box<dyn RecordBatchReader>,
-    last_error: String,
+    /// The error of the last failed operation, kept alive until the next
+    /// operation as the C Stream Interface requires of `get_last_error`
+    last_error: Option<CString>,
 }
 
 // The callback used to get array schema
@@ -145,8 +144,10 @@ unsafe extern "C" fn get_next(
 // The callback used to get the error from last operation on the `FFI_ArrowArrayStream`
 unsafe extern "C" fn get_last_error(stream: *mut FFI_ArrowArrayStream) -> *const c_char {
     let mut ffi_stream = ExportedArrayStream { stream };
-    let last_error = ffi_stream.get_last_error();
-    CString::new(last_error.as_str()).unwrap().into_raw()
+    match ffi_stream.get_last_error() {
+        Some(err) => err.as_ptr(),
+        None => std::ptr::null(),
+    }
 }
 
 impl Drop for FFI_ArrowArrayStream {
@@ -163,7 +164,7 @@ impl FFI_ArrowArrayStream {
     pub fn new(batch_reader: Box<dyn RecordBatchReader>) -> Self {
         let private_data = Box::new(StreamPrivateData {
             batch_reader,
-            last_error: String::new(),
+            last_error: None,
         });
 
         Self {
@@ -198,6 +199,7 @@ impl ExportedArrayStream {
 
     pub fn get_schema(&mut self, out: *mut FFI_ArrowSchema) -> i32 {
         let mut private_data = self.get_private_data();
+        private_data.last_error = None;
         let reader = &private_data.batch_reader;
 
         let schema = FFI_ArrowSchema::try_from(reader.schema().as_ref());
@@ -209,7 +211,7 @@ impl ExportedArrayStream {
                 0
             },
             Err(ref err) => {
-                private_data.last_error = err.to_string();
+                private_data.last_error = Some(error_to_c_string(err));
                 get_error_code(err)
             }
         }
@@ -217,6 +219,7 @@ impl ExportedArrayStream {
 
     pub fn get_next(&mut self, out: *mut FFI_ArrowArray) -> i32 {
         let mut private_data = self.get_private_data();
+        private_data.last_error = None;
         let reader = &mut private_data.batch_reader;
 
         let ret_code = match reader.next() {
@@ -239,7 +242,7 @@ impl ExportedArrayStream {
                     }
                 } else {
                     let err = &next_batch.unwrap_err();
-                    private_data.last_error = err.to_string();
+                    private_data.last_error = Some(error_to_c_string(err));
                     get_error_code(err)
                 }
             }
@@ -248,11 +251,17 @@ impl ExportedArrayStream {
         ret_code
     }
 
-    pub fn get_last_error(&mut self) -> &String {
-        &self.get_private_data().last_error
+    pub fn get_last_error(&mut self) -> Option<&CString> {
+        self.get_private_data().last_error.as_ref()
     }
 }
 
+/// Converts `err` to a [`CString`], dropping any interior nul bytes
+fn error_to_c_string(err: &ArrowError) -> CString {
+    let message = err.to_string().replace('\0', "");
+    CString::new(message).unwrap()
+}
+
 fn get_error_code(err: &ArrowError) -> i32 {
     match err {
         ArrowError::NotYetImplemented(_) => ENOSYS,
@@ -275,24 +284,42 @@ pub struct ArrowArrayStreamReader {
 /// Gets schema from a raw pointer of `FFI_ArrowArrayStream`. This is used when constructing
 /// `ArrowArrayStreamReader` to cache schema.
 fn get_stream_schema(stream_ptr: *mut FFI_ArrowArrayStream) -> Result<SchemaRef> {
+    let get_schema = unsafe { (*stream_ptr).get_schema }.ok_or_else(|| {
+        ArrowError::CDataInterface("input stream has no get_schema callback".to_string())
+    })?;
+
     let empty_schema = Arc::new(FFI_ArrowSchema::empty());
     let schema_ptr = Arc::into_raw(empty_schema) as *mut FFI_ArrowSchema;
 
-    let ret_code = unsafe { (*stream_ptr).get_schema.unwrap()(stream_ptr, schema_ptr) };
+    let ret_code = unsafe { get_schema(stream_ptr, schema_ptr) };
 
     let ffi_schema = unsafe { Arc::from_raw(schema_ptr) };
 
     if ret_code == 0 {
-        let schema = Schema::try_from(ffi_schema.as_ref()).unwrap();
+        let schema = Schema::try_from(ffi_schema.as_ref())?;
         Ok(Arc::new(schema))
     } else {
+        let last_error = unsafe { stream_last_error(stream_ptr) };
         Err(ArrowError::CDataInterface(format!(
-            "Cannot get schema from input stream. Error code: {:?}",
-            ret_code
+            "Cannot get schema from input stream. Error code: {:?}{}",
+            ret_code,
+            last_error.map(|e| format!(": {}", e)).unwrap_or_default()
         )))
     }
 }
 
+/// Gets the error of the last failed operation on the stream at `stream_ptr`, if any
+///
+/// The message is copied, as it is owned by the producer of the stream
+unsafe fn stream_last_error(stream_ptr: *mut FFI_ArrowArrayStream) -> Option<String> {
+    let get_last_error = (*stream_ptr).get_last_error?;
+    let c_str = get_last_error(stream_ptr);
+    if c_str.is_null() {
+        return None;
+    }
+    Some(CStr::from_ptr(c_str).to_string_lossy().into_owned())
+}
+
 impl ArrowArrayStreamReader {
     /// Creates a new `ArrowArrayStreamReader` from a `FFI_ArrowArrayStream`.
     /// This is used to import from the C Stream Interface.
@@ -331,20 +358,8 @@ impl ArrowArrayStreamReader {
 
     /// Get the last error from `ArrowArrayStreamReader`
     fn get_stream_last_error(&self) -> Option<String> {
-        self.stream.get_last_error?;
-
         let stream_ptr = Arc::as_ptr(&self.stream) as *mut FFI_ArrowArrayStream;
-
-        let error_str = unsafe {
-            let c_str = self.stream.get_last_error.unwrap()(stream_ptr) as *mut c_char;
-            CString::from_raw(c_str).into_string()
-        };
-
-        if let Err(err) = error_str {
-            Some(err.to_string())
-        } else {
-            Some(error_str.unwrap())
-        }
+        unsafe { stream_last_error(stream_ptr) }
     }
 }
 
@@ -354,10 +369,19 @@ impl Iterator for ArrowArrayStreamReader {
     fn next(&mut self) -> Option<Self::Item> {
         let stream_ptr = Arc::as_ptr(&self.stream) as *mut FFI_ArrowArrayStream;
 
+        let get_next = match self.stream.get_next {
+            Some(get_next) => get_next,
+            None => {
+                return Some(Err(ArrowError::CDataInterface(
+                    "input stream has no get_next callback".to_string(),
+                )))
+            }
+        };
+
         let empty_array = Arc::new(FFI_ArrowArray::empty());
         let array_ptr = Arc::into_raw(empty_array) as *mut FFI_ArrowArray;
 
-        let ret_code = unsafe { self.stream.get_next.unwrap()(stream_ptr, array_ptr) };
+        let ret_code = unsafe { get_next(stream_ptr, array_ptr) };
 
         if ret_code == 0 {
             let ffi_array = unsafe { Arc::from_raw(array_ptr) };
@@ -366,24 +390,27 @@ impl Iterator for ArrowArrayStreamReader {
             ffi_array.release?;
 
             let schema_ref = self.schema();
-            let schema = FFI_ArrowSchema::try_from(schema_ref.as_ref()).ok()?;
-
-            let data = ArrowArray {
-                array: ffi_array,
-                schema: Arc::new(schema),
-            }
-            .to_data()
-            .ok()?;
-
-            let record_batch = RecordBatch::from(&StructArray::from(data));
+            let result = FFI_ArrowSchema::try_from(schema_ref.as_ref())
+                .and_then(|schema| {
+                    ArrowArray {
+                        array: ffi_array,
+                        schema: Arc::new(schema),
+                    }
+                    .to_data()
+                })
+                .map(|data| RecordBatch::from(&StructArray::from(data)));
 
-            Some(Ok(record_batch))
+            Some(result)
         } else {
             unsafe { Arc::from_raw(array_ptr) };
 
-            let last_error = self.get_stream_last_error();
-            let err = ArrowError::CDataInterface(last_error.unwrap());
-            Some(Err(err))
+            let last_error = self.get_stream_last_error().unwrap_or_else(|| {
+                format!(
+                    "Cannot get next batch from input stream. Error code: {}",
+                    ret_code
+                )
+            });
+            Some(Err(ArrowError::CDataInterface(last_error)))
         }
     }
 }
@@ -413,7 +440,7 @@ mod tests {
     use super::*;
 
     use crate::array::Int32Array;
-    use crate::datatypes::{Field, Schema};
+    use crate::datatypes::{DataType, Field, Schema};
 
     struct TestRecordBatchReader {
         schema: SchemaRef,
@@ -550,4 +577,46 @@ mod tests {
 
         _test_round_trip_import(vec![array.clone(), array.clone(), array])
     }
+
+    #[test]
+    fn test_stream_error() {
+        let array: Arc<dyn Array> = Arc::new(Int32Array::from(vec![1, 2]));
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![array]).unwrap();
+        let new_stream = || {
+            let batches = vec![
+                Ok(batch.clone()),
+                Err(ArrowError::IoError("failed\0 to read".to_string())),
+            ];
+            let iter = Box::new(batches.into_iter());
+            FFI_ArrowArrayStream::new(TestRecordBatchReader::new(schema.clone(), iter))
+        };
+
+        // Errors are exposed through get_last_error until the next operation
+        let mut stream = new_stream();
+        let stream_ptr = &mut stream as *mut FFI_ArrowArrayStream;
+        assert!(unsafe { stream_last_error(stream_ptr) }.is_none());
+
+        let mut array = FFI_ArrowArray::empty();
+        assert_eq!(unsafe { get_next(stream_ptr, &mut array) }, 0);
+        assert!(unsafe { stream_last_error(stream_ptr) }.is_none());
+        drop(array);
+
+        let mut array = FFI_ArrowArray::empty();
+        assert_eq!(unsafe { get_next(stream_ptr, &mut array) }, EIO);
+        assert_eq!(
+            unsafe { stream_last_error(stream_ptr) }.unwrap(),
+            "Io error: failed to read"
+        );
+
+        // Errors are propagated by the imported reader
+        let stream_reader = ArrowArrayStreamReader::try_new(new_stream()).unwrap();
+        let read: Vec<_> = stream_reader.collect();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].as_ref().unwrap(), &batch);
+        assert_eq!(
+            read[1].as_ref().unwrap_err().to_string(),
+            "C Data interface error: Io error: failed to read"
+        );
+    }
 }
@@ -316,6 +316,8 @@ pub mod datatypes;
 pub mod error;
 #[cfg(feature = "ffi")]
 pub mod ffi;
+#[cfg(feature = "ffi_device")]
+pub mod ffi_device;
 #[cfg(feature = "ffi")]
 pub mod ffi_stream;
 #[cfg(feature = "ipc")]
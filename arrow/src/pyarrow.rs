@@ -17,24 +17,31 @@
 
 //! This module demonstrates a minimal usage of Rust's C data interface to pass
 //! arrays from and to Python.
+//!
+//! Python objects implementing the [Arrow PyCapsule Interface], such as those of
+//! pyarrow 14 and later, are imported through the capsules they export. Objects of
+//! older versions of pyarrow are imported through their private `_export_to_c` method.
+//!
+//! [Arrow PyCapsule Interface]: https://arrow.apache.org/docs/format/CDataInterface/PyCapsuleInterface.html
 
 use std::convert::{From, TryFrom};
+use std::ffi::CStr;
 use std::sync::Arc;
 
 use pyo3::ffi::Py_uintptr_t;
 use pyo3::import_exception;
 use pyo3::prelude::*;
-use pyo3::types::{PyList, PyTuple};
+use pyo3::types::{PyCapsule, PyList, PyTuple};
 
 use crate::array::{make_array, Array, ArrayData};
 use crate::datatypes::{DataType, Field, Schema};
 use crate::error::ArrowError;
 use crate::ffi;
-use crate::ffi::FFI_ArrowSchema;
+use crate::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
 use crate::ffi_stream::{
     export_reader_into_raw, ArrowArrayStreamReader, FFI_ArrowArrayStream,
 };
-use crate::record_batch::RecordBatch;
+use crate::record_batch::{RecordBatch, RecordBatchOptions, RecordBatchReader};
 
 import_exception!(pyarrow, ArrowException);
 pub type PyArrowException = ArrowException;
@@ -48,11 +55,72 @@ pub trait PyArrowConvert: Sized {
     fn to_pyarrow(&self, py: Python) -> PyResult<PyObject>;
 }
 
+/// Conversion of a Rust value consumed by its conversion to a PyArrow object
+pub trait IntoPyArrow {
+    fn into_pyarrow(self, py: Python) -> PyResult<PyObject>;
+}
+
+/// Checks that `capsule` has the `expected` name of the Arrow PyCapsule Interface
+fn validate_capsule_name(capsule: &PyCapsule, expected: &str) -> PyResult<()> {
+    let name = capsule.name()?.map(CStr::to_bytes);
+    if name != Some(expected.as_bytes()) {
+        return Err(PyArrowException::new_err(format!(
+            "Expected PyCapsule named {}, got {:?}",
+            expected,
+            name.map(String::from_utf8_lossy)
+        )));
+    }
+    Ok(())
+}
+
+/// Moves the struct out of `capsule`, leaving it released so that the destructor
+/// of the capsule does not release it
+///
+/// # Safety
+///
+/// `capsule` must point to a valid `T`
+unsafe fn take_from_capsule<T>(capsule: &PyCapsule, empty: T) -> T {
+    std::ptr::replace(capsule.pointer() as *mut T, empty)
+}
+
+/// Imports the [`FFI_ArrowSchema`] of `value`, through `__arrow_c_schema__` if it
+/// implements the Arrow PyCapsule Interface, or else pyarrow's `_export_to_c`
+fn import_schema(value: &PyAny) -> PyResult<FFI_ArrowSchema> {
+    if value.hasattr("__arrow_c_schema__")? {
+        let capsule: &PyCapsule = value.call_method0("__arrow_c_schema__")?.downcast()?;
+        validate_capsule_name(capsule, "arrow_schema")?;
+        return Ok(unsafe { take_from_capsule(capsule, FFI_ArrowSchema::empty()) });
+    }
+
+    let c_schema = FFI_ArrowSchema::empty();
+    let c_schema_ptr = &c_schema as *const FFI_ArrowSchema;
+    value.call_method1("_export_to_c", (c_schema_ptr as Py_uintptr_t,))?;
+    Ok(c_schema)
+}
+
+/// Imports the [`FFI_ArrowArray`] and [`FFI_ArrowSchema`] of `value` through
+/// `__arrow_c_array__`, if it implements the Arrow PyCapsule Interface
+fn import_array_capsules(
+    value: &PyAny,
+) -> PyResult<Option<(FFI_ArrowArray, FFI_ArrowSchema)>> {
+    if !value.hasattr("__arrow_c_array__")? {
+        return Ok(None);
+    }
+
+    let capsules: &PyTuple = value.call_method0("__arrow_c_array__")?.downcast()?;
+    let schema_capsule: &PyCapsule = capsules.get_item(0)?.downcast()?;
+    let array_capsule: &PyCapsule = capsules.get_item(1)?.downcast()?;
+    validate_capsule_name(schema_capsule, "arrow_schema")?;
+    validate_capsule_name(array_capsule, "arrow_array")?;
+
+    let schema = unsafe { take_from_capsule(schema_capsule, FFI_ArrowSchema::empty()) };
+    let array = unsafe { take_from_capsule(array_capsule, FFI_ArrowArray::empty()) };
+    Ok(Some((array, schema)))
+}
+
 impl PyArrowConvert for DataType {
     fn from_pyarrow(value: &PyAny) -> PyResult<Self> {
-        let c_schema = FFI_ArrowSchema::empty();
-        let c_schema_ptr = &c_schema as *const FFI_ArrowSchema;
-        value.call_method1("_export_to_c", (c_schema_ptr as Py_uintptr_t,))?;
+        let c_schema = import_schema(value)?;
         let dtype = DataType::try_from(&c_schema).map_err(to_py_err)?;
         Ok(dtype)
     }
@@ -70,9 +138,7 @@ impl PyArrowConvert for DataType {
 
 impl PyArrowConvert for Field {
     fn from_pyarrow(value: &PyAny) -> PyResult<Self> {
-        let c_schema = FFI_ArrowSchema::empty();
-        let c_schema_ptr = &c_schema as *const FFI_ArrowSchema;
-        value.call_method1("_export_to_c", (c_schema_ptr as Py_uintptr_t,))?;
+        let c_schema = import_schema(value)?;
         let field = Field::try_from(&c_schema).map_err(to_py_err)?;
         Ok(field)
     }
@@ -90,9 +156,7 @@ impl PyArrowConvert for Field {
 
 impl PyArrowConvert for Schema {
     fn from_pyarrow(value: &PyAny) -> PyResult<Self> {
-        let c_schema = FFI_ArrowSchema::empty();
-        let c_schema_ptr = &c_schema as *const FFI_ArrowSchema;
-        value.call_method1("_export_to_c", (c_schema_ptr as Py_uintptr_t,))?;
+        let c_schema = import_schema(value)?;
         let schema = Schema::try_from(&c_schema).map_err(to_py_err)?;
         Ok(schema)
     }
@@ -110,6 +174,10 @@ impl PyArrowConvert for Schema {
 
 impl PyArrowConvert for ArrayData {
     fn from_pyarrow(value: &PyAny) -> PyResult<Self> {
+        if let Some((array, schema)) = import_array_capsules(value)? {
+            return ffi::from_ffi(array, &schema).map_err(to_py_err);
+        }
+
         // prepare a pointer to receive the Array struct
         let (array_pointer, schema_pointer) =
             ffi::ArrowArray::into_raw(unsafe { ffi::ArrowArray::empty() });
@@ -168,6 +236,25 @@ impl<T: PyArrowConvert> PyArrowConvert for Vec<T> {
 
 impl PyArrowConvert for RecordBatch {
     fn from_pyarrow(value: &PyAny) -> PyResult<Self> {
+        if let Some((array, schema)) = import_array_capsules(value)? {
+            // a record batch is exported as a struct array, whose fields are the columns
+            let batch_schema = Arc::new(Schema::try_from(&schema).map_err(to_py_err)?);
+            let data = ffi::from_ffi(array, &schema).map_err(to_py_err)?;
+            if data.null_count() != 0 {
+                return Err(PyArrowException::new_err(
+                    "Cannot import a struct array with nulls as a RecordBatch",
+                ));
+            }
+            let columns = data
+                .child_data()
+                .iter()
+                .map(|child| make_array(child.slice(data.offset(), data.len())))
+                .collect();
+            let options = RecordBatchOptions::new().with_row_count(Some(data.len()));
+            return RecordBatch::try_new_with_options(batch_schema, columns, &options)
+                .map_err(to_py_err);
+        }
+
         // TODO(kszucs): implement the FFI conversions in arrow-rs for RecordBatches
         let schema = value.getattr("schema")?;
         let schema = Arc::new(Schema::from_pyarrow(schema)?);
@@ -204,6 +291,15 @@ impl PyArrowConvert for RecordBatch {
 
 impl PyArrowConvert for ArrowArrayStreamReader {
     fn from_pyarrow(value: &PyAny) -> PyResult<Self> {
+        if value.hasattr("__arrow_c_stream__")? {
+            let capsule: &PyCapsule =
+                value.call_method0("__arrow_c_stream__")?.downcast()?;
+            validate_capsule_name(capsule, "arrow_array_stream")?;
+            let stream =
+                unsafe { take_from_capsule(capsule, FFI_ArrowArrayStream::empty()) };
+            return ArrowArrayStreamReader::try_new(stream).map_err(to_py_err);
+        }
+
         // prepare a pointer to receive the stream struct
         let stream = Box::new(FFI_ArrowArrayStream::empty());
         let stream_ptr = Box::into_raw(stream) as *mut FFI_ArrowArrayStream;
@@ -225,16 +321,32 @@ impl PyArrowConvert for ArrowArrayStreamReader {
     }
 
     fn to_pyarrow(&self, py: Python) -> PyResult<PyObject> {
+        let reader: Box<dyn RecordBatchReader> = Box::new(self.clone());
+        reader.into_pyarrow(py)
+    }
+}
+
+/// Exports the reader as a `pyarrow.RecordBatchReader`, whose batches and errors
+/// are read from the reader through the C Stream Interface
+impl IntoPyArrow for Box<dyn RecordBatchReader> {
+    fn into_pyarrow(self, py: Python) -> PyResult<PyObject> {
         let stream = Box::new(FFI_ArrowArrayStream::empty());
         let stream_ptr = Box::into_raw(stream) as *mut FFI_ArrowArrayStream;
 
-        unsafe { export_reader_into_raw(Box::new(self.clone()), stream_ptr) };
+        unsafe { export_reader_into_raw(self, stream_ptr) };
 
         let module = py.import("pyarrow")?;
         let class = module.getattr("RecordBatchReader")?;
         let args = PyTuple::new(py, &[stream_ptr as Py_uintptr_t]);
-        let reader = class.call_method1("_import_from_c", args)?;
-        Ok(PyObject::from(reader))
+        let reader = class.call_method1("_import_from_c", args);
+
+        // the stream is moved out of the pointer by a successful import, otherwise
+        // dropping it releases the reader
+        unsafe {
+            drop(Box::from_raw(stream_ptr));
+        }
+
+        Ok(PyObject::from(reader?))
     }
 }
 
@@ -30,33 +30,70 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 /// Computes the dictionary mapping for the given dictionary values
+///
+/// Only the values referenced by a key, as indicated by `used`, are interned, the
+/// mapping for the remaining values is `None`
 pub fn compute_dictionary_mapping(
     interner: &mut OrderPreservingInterner,
     values: &ArrayRef,
+    used: &[bool],
 ) -> Vec<Option<Interned>> {
     downcast_primitive_array! {
         values => interner
-            .intern(values.iter().map(|x| x.map(|x| x.encode()))),
+            .intern(referenced(values.iter().map(|x| x.map(|x| x.encode())), used)),
+        DataType::Null => interner.intern((0..values.len()).map(|_| None::<&[u8]>)),
+        DataType::Boolean => {
+            let iter = as_boolean_array(values).iter().map(|x| x.map(|x| x.encode()));
+            interner.intern(referenced(iter, used))
+        }
         DataType::Binary => {
-            let iter = as_generic_binary_array::<i64>(values).iter();
-            interner.intern(iter)
+            let iter = as_generic_binary_array::<i32>(values).iter();
+            interner.intern(referenced(iter, used))
         }
         DataType::LargeBinary => {
             let iter = as_generic_binary_array::<i64>(values).iter();
-            interner.intern(iter)
+            interner.intern(referenced(iter, used))
         }
         DataType::Utf8 => {
             let iter = as_string_array(values).iter().map(|x| x.map(|x| x.as_bytes()));
-            interner.intern(iter)
+            interner.intern(referenced(iter, used))
         }
         DataType::LargeUtf8 => {
             let iter = as_largestring_array(values).iter().map(|x| x.map(|x| x.as_bytes()));
-            interner.intern(iter)
+            interner.intern(referenced(iter, used))
+        }
+        DataType::FixedSizeBinary(_) => {
+            let values = values
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            interner.intern(referenced(values.iter(), used))
         }
         _ => unreachable!(),
     }
 }
 
+/// Returns `None` for the values of `iter` not marked as `used`
+fn referenced<'a, I, V>(iter: I, used: &'a [bool]) -> impl Iterator<Item = Option<V>> + 'a
+where
+    I: Iterator<Item = Option<V>> + 'a,
+{
+    iter.zip(used).map(|(v, used)| v.filter(|_| *used))
+}
+
+/// Returns a mask of the values of `array` referenced by a non-null key
+pub fn referenced_values<K: ArrowDictionaryKeyType>(
+    array: &DictionaryArray<K>,
+) -> Vec<bool> {
+    let mut used = vec![false; array.values().len()];
+    array
+        .keys()
+        .iter()
+        .flatten()
+        .for_each(|k| used[k.as_usize()] = true);
+    used
+}
+
 /// Dictionary types are encoded as
 ///
 /// - single `0_u8` if null
@@ -177,6 +214,7 @@ pub unsafe fn decode_dictionary<K: ArrowDictionaryKeyType>(
         DataType::LargeUtf8 => decode_string::<i64>(&values),
         DataType::Binary => decode_binary::<i32>(&values),
         DataType::LargeBinary => decode_binary::<i64>(&values),
+        DataType::FixedSizeBinary(size) => decode_fixed_size_binary(&values, *size),
         _ => unreachable!(),
     };
 
@@ -207,6 +245,15 @@ fn decode_binary<O: OffsetSizeTrait>(values: &[&[u8]]) -> ArrayData {
     builder.finish().into_data()
 }
 
+/// Decodes a fixed size binary array of `size` bytes from dictionary values
+fn decode_fixed_size_binary(values: &[&[u8]], size: i32) -> ArrayData {
+    let mut builder = FixedSizeBinaryBuilder::with_capacity(values.len(), size);
+    for v in values {
+        builder.append_value(v).unwrap()
+    }
+    builder.finish().into_data()
+}
+
 /// Decodes a string array from dictionary values
 ///
 /// # Safety
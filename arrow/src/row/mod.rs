@@ -137,7 +137,7 @@ use crate::compute::SortOptions;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 use crate::row::dictionary::{
-    compute_dictionary_mapping, decode_dictionary, encode_dictionary,
+    compute_dictionary_mapping, decode_dictionary, encode_dictionary, referenced_values,
 };
 use crate::row::fixed::{decode_bool, decode_fixed_size_binary, decode_primitive};
 use crate::row::interner::OrderPreservingInterner;
@@ -489,12 +489,12 @@ impl Codec {
         match self {
             Codec::Stateless => Ok(Encoder::Stateless),
             Codec::Dictionary(interner) => {
-                let values = downcast_dictionary_array! {
-                    array => array.values(),
+                let (values, used) = downcast_dictionary_array! {
+                    array => (array.values(), referenced_values(array)),
                     _ => unreachable!()
                 };
 
-                let mapping = compute_dictionary_mapping(interner, values)
+                let mapping = compute_dictionary_mapping(interner, values, &used)
                     .into_iter()
                     .map(|maybe_interned| {
                         maybe_interned.map(|interned| interner.normalized_key(interned))
@@ -600,7 +600,7 @@ impl RowConverter {
     fn supports_datatype(d: &DataType) -> bool {
         match d {
             _ if !d.is_nested() => true,
-            DataType::List(f) | DataType::LargeList(f) | DataType::Map(f, _) => {
+            DataType::List(f) | DataType::LargeList(f) => {
                 Self::supports_datatype(f.data_type())
             }
             DataType::Struct(f) => {
@@ -1271,6 +1271,8 @@ mod tests {
         Int16Array, Int32Array, OffsetSizeTrait, PrimitiveArray,
         PrimitiveDictionaryBuilder, StringArray,
     };
+    use crate::compute::take;
+    use crate::record_batch::RecordBatch;
     use crate::util::display::array_value_to_string;
 
     use super::*;
@@ -1742,6 +1744,112 @@ mod tests {
         assert!(rows.row(3) < rows.row(0));
     }
 
+    #[test]
+    fn test_dictionary_unused_values() {
+        let values: Vec<_> = (0..2000).map(|x| format!("{:04}", x)).collect();
+        let values = StringArray::from_iter_values(&values);
+        let keys = Int32Array::from(vec![1999, 3, 1999]);
+        let dict = DictionaryArray::<Int32Type>::try_new(&keys, &values).unwrap();
+        let dict = Arc::new(dict) as ArrayRef;
+
+        let values = StringArray::from(vec!["0003", "1999"]);
+        let keys = Int32Array::from(vec![1, 0, 1]);
+        let small = DictionaryArray::<Int32Type>::try_new(&keys, &values).unwrap();
+        let small = Arc::new(small) as ArrayRef;
+
+        let fields = vec![SortField::new(dict.data_type().clone())];
+        let mut converter = RowConverter::new(fields.clone()).unwrap();
+        let rows = converter
+            .convert_columns(std::slice::from_ref(&dict))
+            .unwrap();
+
+        // Unreferenced values should not be interned, nor lengthen the normalized keys
+        let mut small_converter = RowConverter::new(fields).unwrap();
+        let small_rows = small_converter.convert_columns(&[small]).unwrap();
+        assert_eq!(converter.size(), small_converter.size());
+        for (a, b) in rows.iter().zip(small_rows.iter()) {
+            assert_eq!(a.as_ref(), b.as_ref());
+        }
+
+        assert!(rows.row(1) < rows.row(0));
+        assert_eq!(rows.row(0), rows.row(2));
+
+        let back = converter.convert_rows(&rows).unwrap();
+        let back = crate::compute::cast(&back[0], &DataType::Utf8).unwrap();
+        let expected = crate::compute::cast(&dict, &DataType::Utf8).unwrap();
+        assert_eq!(&back, &expected);
+    }
+
+    #[test]
+    fn test_dictionary_value_types() {
+        let mut fixed = FixedSizeBinaryBuilder::new(2);
+        fixed.append_value([2, 1]).unwrap();
+        fixed.append_value([0, 7]).unwrap();
+        fixed.append_value([2, 0]).unwrap();
+
+        let values: Vec<ArrayRef> = vec![
+            Arc::new(BinaryArray::from_iter_values([
+                b"bb".as_slice(),
+                b"a",
+                b"b",
+            ])),
+            Arc::new(BooleanArray::from(vec![true, false, true])),
+            Arc::new(fixed.finish()),
+        ];
+        for values in values {
+            let data_type = DataType::Dictionary(
+                Box::new(DataType::Int32),
+                Box::new(values.data_type().clone()),
+            );
+            let keys = Int32Array::from_iter([Some(0), Some(1), None, Some(2), Some(1)]);
+            let data = keys
+                .into_data()
+                .into_builder()
+                .data_type(data_type.clone())
+                .child_data(vec![values.into_data()])
+                .build()
+                .unwrap();
+            let array = Arc::new(DictionaryArray::<Int32Type>::from(data)) as ArrayRef;
+
+            let mut converter =
+                RowConverter::new(vec![SortField::new(data_type.clone())]).unwrap();
+            let rows = converter.convert_columns(&[Arc::clone(&array)]).unwrap();
+            assert!(rows.row(2) < rows.row(1));
+            assert!(rows.row(1) < rows.row(3));
+            assert!(rows.row(3) <= rows.row(0));
+            assert_eq!(rows.row(1), rows.row(4));
+
+            let back = converter.convert_rows(&rows).unwrap();
+            assert_eq!(back[0].data_type(), &data_type);
+            let back = crate::compute::cast(&back[0], values_type(&data_type)).unwrap();
+            let expected = crate::compute::cast(&array, values_type(&data_type)).unwrap();
+            assert_eq!(&back, &expected);
+        }
+    }
+
+    fn values_type(data_type: &DataType) -> &DataType {
+        match data_type {
+            DataType::Dictionary(_, v) => v.as_ref(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_unsupported_map() {
+        let entries = Field::new(
+            "entries",
+            DataType::Struct(vec![
+                Field::new("keys", DataType::Utf8, false),
+                Field::new("values", DataType::Int32, true),
+            ]),
+            false,
+        );
+        let field = SortField::new(DataType::Map(Box::new(entries), false));
+        assert!(!RowConverter::supports_fields(std::slice::from_ref(&field)));
+        let err = RowConverter::new(vec![field]).unwrap_err();
+        assert!(err.to_string().contains("not yet implemented"), "{}", err);
+    }
+
     #[test]
     #[should_panic(expected = "Invalid UTF-8 sequence")]
     fn test_invalid_utf8() {
@@ -1770,6 +1878,62 @@ mod tests {
         let _ = converter.convert_rows(&rows);
     }
 
+    #[test]
+    fn test_convert_owned_rows() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("int", DataType::Int32, true),
+            Field::new("str", DataType::Utf8, true),
+            Field::new(
+                "dict",
+                DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+                true,
+            ),
+            Field::new("null", DataType::Null, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(3), None, Some(-1)])),
+                Arc::new(StringArray::from(vec![Some("a"), Some("b"), None])),
+                Arc::new(DictionaryArray::<Int8Type>::from_iter([
+                    Some("x"),
+                    None,
+                    Some("y"),
+                ])),
+                Arc::new(NullArray::new(3)),
+            ],
+        )
+        .unwrap();
+
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|f| SortField::new(f.data_type().clone()))
+            .collect();
+        let mut converter = RowConverter::new(fields).unwrap();
+
+        // Rows may be copied out, e.g. to be shuffled, without retaining the arrays
+        let owned: Vec<Vec<u8>> = converter
+            .convert_columns(batch.columns())
+            .unwrap()
+            .iter()
+            .map(|row| row.as_ref().to_vec())
+            .collect();
+
+        let parser = converter.parser();
+        let rows = owned.iter().rev().map(|bytes| parser.parse(bytes));
+        let columns = converter.convert_rows(rows).unwrap();
+        let back = RecordBatch::try_new(schema.clone(), columns).unwrap();
+        let indices = UInt32Array::from(vec![2, 1, 0]);
+        for (a, b) in back.columns().iter().zip(batch.columns()) {
+            assert_eq!(a, &take(b.as_ref(), &indices, None).unwrap());
+        }
+
+        let columns = converter.convert_rows(std::iter::empty()).unwrap();
+        let empty = RecordBatch::try_new(schema, columns).unwrap();
+        assert_eq!(empty.num_rows(), 0);
+    }
+
     fn test_single_list<O: OffsetSizeTrait>() {
         let mut builder = GenericListBuilder::<O, _>::new(Int32Builder::new());
         builder.values().append_value(32);
@@ -1994,6 +2158,13 @@ mod tests {
             .collect()
     }
 
+    fn generate_boolean_array(len: usize, valid_percent: f64) -> BooleanArray {
+        let mut rng = thread_rng();
+        (0..len)
+            .map(|_| rng.gen_bool(valid_percent).then(|| rng.gen_bool(0.5)))
+            .collect()
+    }
+
     fn generate_strings<O: OffsetSizeTrait>(
         len: usize,
         valid_percent: f64,
@@ -2069,7 +2240,7 @@ mod tests {
 
     fn generate_column(len: usize) -> ArrayRef {
         let mut rng = thread_rng();
-        match rng.gen_range(0..10) {
+        match rng.gen_range(0..13) {
             0 => Arc::new(generate_primitive_array::<Int32Type>(len, 0.8)),
             1 => Arc::new(generate_primitive_array::<UInt32Type>(len, 0.8)),
             2 => Arc::new(generate_primitive_array::<Int64Type>(len, 0.8)),
@@ -2093,6 +2264,14 @@ mod tests {
                 0.8,
             )),
             9 => Arc::new(generate_fixed_size_binary(len, 0.8)),
+            10 => Arc::new(generate_boolean_array(len, 0.8)),
+            11 => Arc::new(generate_strings::<i64>(len, 0.8)),
+            12 => Arc::new(generate_dictionary::<Int16Type>(
+                // Cannot test dictionaries containing null values because of #2687
+                Arc::new(generate_strings::<i32>(rng.gen_range(1..len), 1.0)),
+                len,
+                0.8,
+            )),
             _ => unreachable!(),
         }
     }
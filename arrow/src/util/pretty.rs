@@ -26,9 +26,81 @@ use crate::error::Result;
 
 use super::display::array_value_to_string;
 
+/// The string used to elide rows and values by [`PrettyFormatOptions`]
+const ELLIPSIS: &str = "...";
+
+/// Options for formatting record batches and columns as tables
+///
+/// By default all rows are printed in full, and null values are printed as empty cells
+///
+/// ```
+/// # use arrow::util::pretty::PrettyFormatOptions;
+/// // Print at most 10 rows, the first 5 and last 5, with values of at most 20 characters
+/// let options = PrettyFormatOptions::new()
+///     .with_max_rows(Some(10))
+///     .with_max_width(Some(20))
+///     .with_null("NULL");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrettyFormatOptions {
+    max_rows: Option<usize>,
+    max_width: Option<usize>,
+    null: String,
+}
+
+impl PrettyFormatOptions {
+    /// Create a new [`PrettyFormatOptions`] with the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of rows to print, if there are more the first half
+    /// and last half of this number are printed, separated by a row of `...`
+    pub fn with_max_rows(mut self, max_rows: Option<usize>) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Sets the maximum number of characters of a value, longer values are
+    /// truncated to this width, ending with `...`
+    pub fn with_max_width(mut self, max_width: Option<usize>) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Sets the string to print for null values
+    pub fn with_null(mut self, null: impl Into<String>) -> Self {
+        self.null = null.into();
+        self
+    }
+
+    /// Returns the maximum number of rows to print
+    pub fn max_rows(&self) -> Option<usize> {
+        self.max_rows
+    }
+
+    /// Returns the maximum number of characters of a value
+    pub fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Returns the string printed for null values
+    pub fn null(&self) -> &str {
+        &self.null
+    }
+}
+
 ///! Create a visual representation of record batches
 pub fn pretty_format_batches(results: &[RecordBatch]) -> Result<impl Display> {
-    create_table(results)
+    create_table(results, &PrettyFormatOptions::default())
+}
+
+/// Create a visual representation of record batches with the provided options
+pub fn pretty_format_batches_with_options(
+    results: &[RecordBatch],
+    options: &PrettyFormatOptions,
+) -> Result<impl Display> {
+    create_table(results, options)
 }
 
 ///! Create a visual representation of columns
@@ -36,23 +108,47 @@ pub fn pretty_format_columns(
     col_name: &str,
     results: &[ArrayRef],
 ) -> Result<impl Display> {
-    create_column(col_name, results)
+    create_column(col_name, results, &PrettyFormatOptions::default())
+}
+
+/// Create a visual representation of columns with the provided options
+pub fn pretty_format_columns_with_options(
+    col_name: &str,
+    results: &[ArrayRef],
+    options: &PrettyFormatOptions,
+) -> Result<impl Display> {
+    create_column(col_name, results, options)
 }
 
 ///! Prints a visual representation of record batches to stdout
 pub fn print_batches(results: &[RecordBatch]) -> Result<()> {
-    println!("{}", create_table(results)?);
+    println!(
+        "{}",
+        create_table(results, &PrettyFormatOptions::default())?
+    );
+    Ok(())
+}
+
+/// Prints a visual representation of record batches to stdout with the provided options
+pub fn print_batches_with_options(
+    results: &[RecordBatch],
+    options: &PrettyFormatOptions,
+) -> Result<()> {
+    println!("{}", create_table(results, options)?);
     Ok(())
 }
 
 ///! Prints a visual representation of a list of column to stdout
 pub fn print_columns(col_name: &str, results: &[ArrayRef]) -> Result<()> {
-    println!("{}", create_column(col_name, results)?);
+    println!(
+        "{}",
+        create_column(col_name, results, &PrettyFormatOptions::default())?
+    );
     Ok(())
 }
 
 ///! Convert a series of record batches into a table
-fn create_table(results: &[RecordBatch]) -> Result<Table> {
+fn create_table(results: &[RecordBatch], options: &PrettyFormatOptions) -> Result<Table> {
     let mut table = Table::new();
     table.load_preset("||--+-++|    ++++++");
 
@@ -68,21 +164,27 @@ fn create_table(results: &[RecordBatch]) -> Result<Table> {
     }
     table.set_header(header);
 
-    for batch in results {
-        for row in 0..batch.num_rows() {
-            let mut cells = Vec::new();
-            for col in 0..batch.num_columns() {
-                let column = batch.column(col);
-                cells.push(Cell::new(&array_value_to_string(column, row)?));
-            }
-            table.add_row(cells);
-        }
+    let lens: Vec<_> = results.iter().map(|batch| batch.num_rows()).collect();
+    for row in visible_rows(&lens, options) {
+        let cells = match row {
+            Some((batch, row)) => results[batch]
+                .columns()
+                .iter()
+                .map(|column| create_cell(column, row, options))
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![Cell::new(ELLIPSIS); schema.fields().len()],
+        };
+        table.add_row(cells);
     }
 
     Ok(table)
 }
 
-fn create_column(field: &str, columns: &[ArrayRef]) -> Result<Table> {
+fn create_column(
+    field: &str,
+    columns: &[ArrayRef],
+    options: &PrettyFormatOptions,
+) -> Result<Table> {
     let mut table = Table::new();
     table.load_preset("||--+-++|    ++++++");
 
@@ -93,16 +195,74 @@ fn create_column(field: &str, columns: &[ArrayRef]) -> Result<Table> {
     let header = vec![Cell::new(field)];
     table.set_header(header);
 
-    for col in columns {
-        for row in 0..col.len() {
-            let cells = vec![Cell::new(&array_value_to_string(col, row)?)];
-            table.add_row(cells);
-        }
+    let lens: Vec<_> = columns.iter().map(|col| col.len()).collect();
+    for row in visible_rows(&lens, options) {
+        let cells = match row {
+            Some((col, row)) => vec![create_cell(&columns[col], row, options)?],
+            None => vec![Cell::new(ELLIPSIS)],
+        };
+        table.add_row(cells);
     }
 
     Ok(table)
 }
 
+/// Returns the `(chunk, row)` of the rows to print of chunks of length `lens`, with
+/// `None` in place of the rows elided by [`PrettyFormatOptions::max_rows`]
+fn visible_rows(
+    lens: &[usize],
+    options: &PrettyFormatOptions,
+) -> Vec<Option<(usize, usize)>> {
+    let total: usize = lens.iter().sum();
+    let (head, tail_start) = match options.max_rows {
+        Some(max_rows) if total > max_rows => {
+            let head = (max_rows + 1) / 2;
+            (head, total - (max_rows - head))
+        }
+        _ => (total, total),
+    };
+
+    let mut rows = Vec::with_capacity(head + total - tail_start + 1);
+    let mut start = 0;
+    for (chunk, len) in lens.iter().enumerate() {
+        let end = start + len;
+        let head_end = head.clamp(start, end) - start;
+        rows.extend((0..head_end).map(|row| Some((chunk, row))));
+        if head < tail_start && (start..end).contains(&head) {
+            rows.push(None);
+        }
+        let tail_begin = tail_start.clamp(start, end) - start;
+        rows.extend((tail_begin.max(head_end)..*len).map(|row| Some((chunk, row))));
+        start = end;
+    }
+    rows
+}
+
+fn create_cell(
+    array: &ArrayRef,
+    row: usize,
+    options: &PrettyFormatOptions,
+) -> Result<Cell> {
+    let value = match array.is_null(row) {
+        true => options.null.clone(),
+        false => array_value_to_string(array, row)?,
+    };
+    Ok(Cell::new(truncate(value, options.max_width)))
+}
+
+/// Truncates `value` to at most `max_width` characters, ending with [`ELLIPSIS`]
+fn truncate(value: String, max_width: Option<usize>) -> String {
+    match max_width {
+        Some(max_width) if value.chars().count() > max_width => {
+            let keep = max_width.saturating_sub(ELLIPSIS.len());
+            let mut truncated: String = value.chars().take(keep).collect();
+            truncated.push_str(&ELLIPSIS[..max_width - keep]);
+            truncated
+        }
+        _ => value,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -234,6 +394,78 @@ mod tests {
         assert_eq!(expected, actual, "Actual result:\n{:#?}", table);
     }
 
+    #[test]
+    fn test_pretty_format_options() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let batches = (0..3)
+            .map(|i| {
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int32Array::from(vec![Some(i * 2), None])),
+                        Arc::new(StringArray::from(vec![Some("abcdefgh"), Some("abc")])),
+                    ],
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let options = PrettyFormatOptions::new()
+            .with_max_rows(Some(3))
+            .with_max_width(Some(6))
+            .with_null("NULL");
+        let table = pretty_format_batches_with_options(&batches, &options)?.to_string();
+
+        let expected = vec![
+            "+------+--------+",
+            "| a    | b      |",
+            "+------+--------+",
+            "| 0    | abc... |",
+            "| NULL | abc    |",
+            "| ...  | ...    |",
+            "| NULL | abc    |",
+            "+------+--------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+
+        // Rows are not elided if there are no more than max_rows
+        let options = options.with_max_rows(Some(6)).with_max_width(Some(2));
+        let table = pretty_format_batches_with_options(&batches, &options)?.to_string();
+
+        let expected = vec![
+            "+----+----+",
+            "| a  | b  |",
+            "+----+----+",
+            "| 0  | .. |",
+            "| .. | .. |",
+            "| 2  | .. |",
+            "| .. | .. |",
+            "| 4  | .. |",
+            "| .. | .. |",
+            "+----+----+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+
+        let columns: Vec<ArrayRef> =
+            batches.iter().map(|b| b.column(0).clone()).collect();
+        let options = PrettyFormatOptions::new().with_max_rows(Some(4));
+        let table =
+            pretty_format_columns_with_options("a", &columns, &options)?.to_string();
+
+        let expected = vec![
+            "+-----+", "| a   |", "+-----+", "| 0   |", "|     |", "| ... |", "| 4   |",
+            "|     |", "+-----+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+
+        Ok(())
+    }
+
     #[test]
     fn test_pretty_format_dictionary() -> Result<()> {
         // define a schema.
@@ -559,12 +791,12 @@ mod tests {
     #[test]
     fn test_pretty_format_date_64() {
         let expected = vec![
-            "+------------+",
-            "| f          |",
-            "+------------+",
-            "| 2005-03-18 |",
-            "|            |",
-            "+------------+",
+            "+---------------------+",
+            "| f                   |",
+            "+---------------------+",
+            "| 2005-03-18T01:58:20 |",
+            "|                     |",
+            "+---------------------+",
         ];
         check_datetime!(Date64Array, 1111111100000, expected);
     }
@@ -751,13 +983,13 @@ mod tests {
 
         let table = pretty_format_batches(&[batch])?.to_string();
         let expected = vec![
-            r#"+-------------------------------------+----+"#,
-            r#"| c1                                  | c2 |"#,
-            r#"+-------------------------------------+----+"#,
-            r#"| {"c11": 1, "c12": {"c121": "e"}}    | a  |"#,
-            r#"| {"c11": null, "c12": {"c121": "f"}} | b  |"#,
-            r#"| {"c11": 5, "c12": {"c121": "g"}}    | c  |"#,
-            r#"+-------------------------------------+----+"#,
+            r#"+-----------------------------+----+"#,
+            r#"| c1                          | c2 |"#,
+            r#"+-----------------------------+----+"#,
+            r#"| {c11: 1, c12: {c121: e}}    | a  |"#,
+            r#"| {c11: null, c12: {c121: f}} | b  |"#,
+            r#"| {c11: 5, c12: {c121: g}}    | c  |"#,
+            r#"+-----------------------------+----+"#,
         ];
 
         let actual: Vec<&str> = table.lines().collect();
@@ -798,8 +1030,8 @@ mod tests {
             "+------------+",
             "| {a=1}      |",
             "| {b=3.2234} |",
-            "| {b=}       |",
-            "| {a=}       |",
+            "| {b=null}   |",
+            "| {a=null}   |",
             "+------------+",
         ];
 
@@ -839,8 +1071,8 @@ mod tests {
             "+------------+",
             "| {a=1}      |",
             "| {b=3.2234} |",
-            "| {b=}       |",
-            "| {a=}       |",
+            "| {b=null}   |",
+            "| {a=null}   |",
             "+------------+",
         ];
 
@@ -903,9 +1135,9 @@ mod tests {
             "+-----------------------------+",
             "| {European Union={b=1}}      |",
             "| {European Union={c=3.2234}} |",
-            "| {a=}                        |",
+            "| {a=null}                    |",
             "| {a=1234}                    |",
-            "| {European Union={c=}}       |",
+            "| {European Union={c=null}}   |",
             "+-----------------------------+",
         ];
         assert_eq!(expected, actual);
@@ -22,10 +22,12 @@ use arrow_schema::DataType;
 use crate::arrow::array_reader::empty_array::make_empty_array_reader;
 use crate::arrow::array_reader::fixed_len_byte_array::make_fixed_len_byte_array_reader;
 use crate::arrow::array_reader::{
-    make_byte_array_dictionary_reader, make_byte_array_reader, ArrayReader,
+    make_byte_array_dictionary_reader, make_byte_array_reader,
+    make_primitive_dictionary_reader, ArrayReader, FixedSizeListArrayReader,
     ListArrayReader, MapArrayReader, NullArrayReader, PrimitiveArrayReader,
     RowGroupCollection, StructArrayReader,
 };
+use crate::arrow::arrow_reader::Int96Overflow;
 use crate::arrow::schema::{ParquetField, ParquetFieldType};
 use crate::arrow::ProjectionMask;
 use crate::basic::Type as PhysicalType;
@@ -40,9 +42,12 @@ pub fn build_array_reader(
     field: Option<&ParquetField>,
     mask: &ProjectionMask,
     row_groups: &dyn RowGroupCollection,
+    int96_overflow: Int96Overflow,
 ) -> Result<Box<dyn ArrayReader>> {
     let reader = field
-        .and_then(|field| build_reader(field, mask, row_groups).transpose())
+        .and_then(|field| {
+            build_reader(field, mask, row_groups, int96_overflow).transpose()
+        })
         .transpose()?
         .unwrap_or_else(|| make_empty_array_reader(row_groups.num_rows()));
 
@@ -53,16 +58,28 @@ fn build_reader(
     field: &ParquetField,
     mask: &ProjectionMask,
     row_groups: &dyn RowGroupCollection,
+    int96_overflow: Int96Overflow,
 ) -> Result<Option<Box<dyn ArrayReader>>> {
     match field.field_type {
         ParquetFieldType::Primitive { .. } => {
-            build_primitive_reader(field, mask, row_groups)
+            build_primitive_reader(field, mask, row_groups, int96_overflow)
         }
         ParquetFieldType::Group { .. } => match &field.arrow_type {
-            DataType::Map(_, _) => build_map_reader(field, mask, row_groups),
-            DataType::Struct(_) => build_struct_reader(field, mask, row_groups),
-            DataType::List(_) => build_list_reader(field, mask, false, row_groups),
-            DataType::LargeList(_) => build_list_reader(field, mask, true, row_groups),
+            DataType::Map(_, _) => {
+                build_map_reader(field, mask, row_groups, int96_overflow)
+            }
+            DataType::Struct(_) => {
+                build_struct_reader(field, mask, row_groups, int96_overflow)
+            }
+            DataType::List(_) => {
+                build_list_reader(field, mask, false, row_groups, int96_overflow)
+            }
+            DataType::LargeList(_) => {
+                build_list_reader(field, mask, true, row_groups, int96_overflow)
+            }
+            DataType::FixedSizeList(_, _) => {
+                build_fixed_size_list_reader(field, mask, row_groups, int96_overflow)
+            }
             d => unimplemented!("reading group type {} not implemented", d),
         },
     }
@@ -73,12 +90,13 @@ fn build_map_reader(
     field: &ParquetField,
     mask: &ProjectionMask,
     row_groups: &dyn RowGroupCollection,
+    int96_overflow: Int96Overflow,
 ) -> Result<Option<Box<dyn ArrayReader>>> {
     let children = field.children().unwrap();
     assert_eq!(children.len(), 2);
 
-    let key_reader = build_reader(&children[0], mask, row_groups)?;
-    let value_reader = build_reader(&children[1], mask, row_groups)?;
+    let key_reader = build_reader(&children[0], mask, row_groups, int96_overflow)?;
+    let value_reader = build_reader(&children[1], mask, row_groups, int96_overflow)?;
 
     match (key_reader, value_reader) {
         (Some(key_reader), Some(value_reader)) => {
@@ -125,11 +143,12 @@ fn build_list_reader(
     mask: &ProjectionMask,
     is_large: bool,
     row_groups: &dyn RowGroupCollection,
+    int96_overflow: Int96Overflow,
 ) -> Result<Option<Box<dyn ArrayReader>>> {
     let children = field.children().unwrap();
     assert_eq!(children.len(), 1);
 
-    let reader = match build_reader(&children[0], mask, row_groups)? {
+    let reader = match build_reader(&children[0], mask, row_groups, int96_overflow)? {
         Some(item_reader) => {
             let item_type = item_reader.get_data_type().clone();
             let data_type = match &field.arrow_type {
@@ -165,11 +184,50 @@ fn build_list_reader(
     Ok(reader)
 }
 
+/// Build array reader for fixed-size list type.
+fn build_fixed_size_list_reader(
+    field: &ParquetField,
+    mask: &ProjectionMask,
+    row_groups: &dyn RowGroupCollection,
+    int96_overflow: Int96Overflow,
+) -> Result<Option<Box<dyn ArrayReader>>> {
+    let children = field.children().unwrap();
+    assert_eq!(children.len(), 1);
+
+    let reader = match build_reader(&children[0], mask, row_groups, int96_overflow)? {
+        Some(item_reader) => {
+            let item_type = item_reader.get_data_type().clone();
+            let reader = match &field.arrow_type {
+                DataType::FixedSizeList(f, size) => {
+                    let data_type = DataType::FixedSizeList(
+                        Box::new(f.clone().with_data_type(item_type)),
+                        *size,
+                    );
+
+                    Box::new(FixedSizeListArrayReader::new(
+                        item_reader,
+                        *size as usize,
+                        data_type,
+                        field.def_level,
+                        field.rep_level,
+                        field.nullable,
+                    )) as _
+                }
+                _ => unreachable!(),
+            };
+            Some(reader)
+        }
+        None => None,
+    };
+    Ok(reader)
+}
+
 /// Creates primitive array reader for each primitive type.
 fn build_primitive_reader(
     field: &ParquetField,
     mask: &ProjectionMask,
     row_groups: &dyn RowGroupCollection,
+    int96_overflow: Int96Overflow,
 ) -> Result<Option<Box<dyn ArrayReader>>> {
     let (col_idx, primitive_type) = match &field.field_type {
         ParquetFieldType::Primitive {
@@ -205,6 +263,14 @@ fn build_primitive_reader(
     let arrow_type = Some(field.arrow_type.clone());
 
     let reader = match physical_type {
+        PhysicalType::INT32
+        | PhysicalType::INT64
+        | PhysicalType::FLOAT
+        | PhysicalType::DOUBLE
+            if matches!(arrow_type, Some(DataType::Dictionary(_, _))) =>
+        {
+            make_primitive_dictionary_reader(page_iterator, column_desc, arrow_type)?
+        }
         PhysicalType::BOOLEAN => Box::new(PrimitiveArrayReader::<BoolType>::new(
             page_iterator,
             column_desc,
@@ -229,11 +295,14 @@ fn build_primitive_reader(
             column_desc,
             arrow_type,
         )?) as _,
-        PhysicalType::INT96 => Box::new(PrimitiveArrayReader::<Int96Type>::new(
-            page_iterator,
-            column_desc,
-            arrow_type,
-        )?) as _,
+        PhysicalType::INT96 => Box::new(
+            PrimitiveArrayReader::<Int96Type>::new(
+                page_iterator,
+                column_desc,
+                arrow_type,
+            )?
+            .with_int96_overflow(int96_overflow),
+        ) as _,
         PhysicalType::FLOAT => Box::new(PrimitiveArrayReader::<FloatType>::new(
             page_iterator,
             column_desc,
@@ -261,6 +330,7 @@ fn build_struct_reader(
     field: &ParquetField,
     mask: &ProjectionMask,
     row_groups: &dyn RowGroupCollection,
+    int96_overflow: Int96Overflow,
 ) -> Result<Option<Box<dyn ArrayReader>>> {
     let arrow_fields = match &field.arrow_type {
         DataType::Struct(children) => children,
@@ -273,7 +343,7 @@ fn build_struct_reader(
     let mut projected_fields = Vec::with_capacity(children.len());
 
     for (arrow, parquet) in arrow_fields.iter().zip(children) {
-        if let Some(reader) = build_reader(parquet, mask, row_groups)? {
+        if let Some(reader) = build_reader(parquet, mask, row_groups, int96_overflow)? {
             let child_type = reader.get_data_type().clone();
             projected_fields.push(arrow.clone().with_data_type(child_type));
             readers.push(reader);
@@ -314,11 +384,13 @@ mod tests {
             file_metadata.schema_descr(),
             ProjectionMask::all(),
             file_metadata.key_value_metadata(),
+            None,
         )
         .unwrap();
 
         let array_reader =
-            build_array_reader(fields.as_ref(), &mask, &file_reader).unwrap();
+            build_array_reader(fields.as_ref(), &mask, &file_reader, Default::default())
+                .unwrap();
 
         // Create arrow types
         let arrow_type = DataType::Struct(vec![Field::new(
@@ -26,16 +26,14 @@ use arrow_schema::DataType as ArrowType;
 
 use crate::arrow::array_reader::byte_array::{ByteArrayDecoder, ByteArrayDecoderPlain};
 use crate::arrow::array_reader::{read_records, skip_records, ArrayReader};
-use crate::arrow::buffer::{
-    dictionary_buffer::DictionaryBuffer, offset_buffer::OffsetBuffer,
-};
-use crate::arrow::record_reader::buffer::{BufferQueue, ScalarValue};
+use crate::arrow::buffer::dictionary_buffer::{DictionaryBuffer, MaybeDictionaryDecoder};
+use crate::arrow::buffer::offset_buffer::OffsetBuffer;
+use crate::arrow::record_reader::buffer::ScalarValue;
 use crate::arrow::record_reader::GenericRecordReader;
 use crate::arrow::schema::parquet_to_arrow_field;
 use crate::basic::{ConvertedType, Encoding};
 use crate::column::page::PageIterator;
 use crate::column::reader::decoder::ColumnValueDecoder;
-use crate::encodings::rle::RleDecoder;
 use crate::errors::{ParquetError, Result};
 use crate::schema::types::ColumnDescPtr;
 use crate::util::bit_util::FromBytes;
@@ -196,26 +194,13 @@ where
     }
 }
 
-/// If the data is dictionary encoded decode the key data directly, so that the dictionary
-/// encoding can be preserved. Otherwise fallback to decoding using [`ByteArrayDecoder`]
-/// and compute a fresh dictionary in [`ByteArrayDictionaryReader::next_batch`]
-enum MaybeDictionaryDecoder {
-    Dict {
-        decoder: RleDecoder,
-        /// This is a maximum as the null count is not always known, e.g. value data from
-        /// a v1 data page
-        max_remaining_values: usize,
-    },
-    Fallback(ByteArrayDecoder),
-}
-
 /// A [`ColumnValueDecoder`] for dictionary encoded variable length byte arrays
 struct DictionaryDecoder<K, V> {
     /// The current dictionary
     dict: Option<ArrayRef>,
 
     /// Dictionary decoder
-    decoder: Option<MaybeDictionaryDecoder>,
+    decoder: Option<MaybeDictionaryDecoder<ByteArrayDecoder>>,
 
     validate_utf8: bool,
 
@@ -290,102 +275,43 @@ where
         num_levels: usize,
         num_values: Option<usize>,
     ) -> Result<()> {
-        let decoder = match encoding {
-            Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY => {
-                let bit_width = data[0];
-                let mut decoder = RleDecoder::new(bit_width);
-                decoder.set_data(data.start_from(1));
-                MaybeDictionaryDecoder::Dict {
-                    decoder,
-                    max_remaining_values: num_values.unwrap_or(num_levels),
-                }
-            }
-            _ => MaybeDictionaryDecoder::Fallback(ByteArrayDecoder::new(
-                encoding,
-                data,
-                num_levels,
-                num_values,
-                self.validate_utf8,
-            )?),
-        };
+        let validate_utf8 = self.validate_utf8;
+        let decoder = MaybeDictionaryDecoder::new(
+            encoding,
+            data,
+            num_levels,
+            num_values,
+            |encoding, data, num_levels, num_values| {
+                ByteArrayDecoder::new(
+                    encoding,
+                    data,
+                    num_levels,
+                    num_values,
+                    validate_utf8,
+                )
+            },
+        )?;
 
         self.decoder = Some(decoder);
         Ok(())
     }
 
     fn read(&mut self, out: &mut Self::Slice, range: Range<usize>) -> Result<usize> {
-        match self.decoder.as_mut().expect("decoder set") {
-            MaybeDictionaryDecoder::Fallback(decoder) => {
-                decoder.read(out.spill_values()?, range.end - range.start, None)
-            }
-            MaybeDictionaryDecoder::Dict {
-                decoder,
-                max_remaining_values,
-            } => {
-                let len = (range.end - range.start).min(*max_remaining_values);
-
-                let dict = self
-                    .dict
-                    .as_ref()
-                    .ok_or_else(|| general_err!("missing dictionary page for column"))?;
-
-                assert_eq!(dict.data_type(), &self.value_type);
-
-                if dict.is_empty() {
-                    return Ok(0); // All data must be NULL
-                }
-
-                match out.as_keys(dict) {
-                    Some(keys) => {
-                        // Happy path - can just copy keys
-                        // Keys will be validated on conversion to arrow
-                        let keys_slice = keys.spare_capacity_mut(range.start + len);
-                        let len = decoder.get_batch(&mut keys_slice[range.start..])?;
-                        *max_remaining_values -= len;
-                        Ok(len)
-                    }
-                    None => {
-                        // Sad path - need to recompute dictionary
-                        //
-                        // This either means we crossed into a new column chunk whilst
-                        // reading this batch, or encountered non-dictionary encoded data
-                        let values = out.spill_values()?;
-                        let mut keys = vec![K::default(); len];
-                        let len = decoder.get_batch(&mut keys)?;
-
-                        assert_eq!(dict.data_type(), &self.value_type);
-
-                        let dict_buffers = dict.data().buffers();
-                        let dict_offsets = dict_buffers[0].typed_data::<V>();
-                        let dict_values = dict_buffers[1].as_slice();
-
-                        values.extend_from_dictionary(
-                            &keys[..len],
-                            dict_offsets,
-                            dict_values,
-                        )?;
-                        *max_remaining_values -= len;
-                        Ok(len)
-                    }
-                }
-            }
+        if let Some(dict) = &self.dict {
+            assert_eq!(dict.data_type(), &self.value_type);
         }
+
+        let decoder = self.decoder.as_mut().expect("decoder set");
+        decoder.read(out, range, self.dict.as_ref(), |decoder, values, len| {
+            decoder.read(values, len, None)
+        })
     }
 
     fn skip_values(&mut self, num_values: usize) -> Result<usize> {
-        match self.decoder.as_mut().expect("decoder set") {
-            MaybeDictionaryDecoder::Fallback(decoder) => {
-                decoder.skip::<V>(num_values, None)
-            }
-            MaybeDictionaryDecoder::Dict {
-                decoder,
-                max_remaining_values,
-            } => {
-                let num_values = num_values.min(*max_remaining_values);
-                *max_remaining_values -= num_values;
-                decoder.skip(num_values)
-            }
-        }
+        let decoder = self.decoder.as_mut().expect("decoder set");
+        decoder.skip(num_values, |decoder, num_values| {
+            decoder.skip::<V>(num_values, None)
+        })
     }
 }
 
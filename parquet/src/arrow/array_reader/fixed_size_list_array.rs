@@ -0,0 +1,335 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::arrow::array_reader::ArrayReader;
+use crate::errors::ParquetError;
+use crate::errors::Result;
+use arrow_array::{
+    builder::BooleanBufferBuilder, new_empty_array, Array, ArrayRef, FixedSizeListArray,
+};
+use arrow_data::{transform::MutableArrayData, ArrayData};
+use arrow_schema::DataType as ArrowType;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Implementation of fixed-size list array reader.
+pub struct FixedSizeListArrayReader {
+    item_reader: Box<dyn ArrayReader>,
+    /// The number of child items in each row of the list array
+    fixed_size: usize,
+    data_type: ArrowType,
+    /// The definition level at which this list is not null
+    def_level: i16,
+    /// The repetition level that corresponds to a new value in this array
+    rep_level: i16,
+    /// If the list is nullable
+    nullable: bool,
+}
+
+impl FixedSizeListArrayReader {
+    /// Construct fixed-size list array reader.
+    pub fn new(
+        item_reader: Box<dyn ArrayReader>,
+        fixed_size: usize,
+        data_type: ArrowType,
+        def_level: i16,
+        rep_level: i16,
+        nullable: bool,
+    ) -> Self {
+        Self {
+            item_reader,
+            fixed_size,
+            data_type,
+            def_level,
+            rep_level,
+            nullable,
+        }
+    }
+}
+
+impl ArrayReader for FixedSizeListArrayReader {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_data_type(&self) -> &ArrowType {
+        &self.data_type
+    }
+
+    fn read_records(&mut self, batch_size: usize) -> Result<usize> {
+        let size = self.item_reader.read_records(batch_size)?;
+        Ok(size)
+    }
+
+    fn consume_batch(&mut self) -> Result<ArrayRef> {
+        let next_batch_array = self.item_reader.consume_batch()?;
+        if next_batch_array.is_empty() {
+            return Ok(new_empty_array(&self.data_type));
+        }
+
+        let def_levels = self
+            .item_reader
+            .get_def_levels()
+            .ok_or_else(|| general_err!("item_reader def levels are None"))?;
+        let rep_levels = self
+            .item_reader
+            .get_rep_levels()
+            .ok_or_else(|| general_err!("item_reader rep levels are None"))?;
+
+        if !rep_levels.is_empty() && rep_levels[0] != 0 {
+            // This implies either the source data was invalid, or the leaf column
+            // reader did not correctly delimit semantic records
+            return Err(general_err!("first repetition level of batch must be 0"));
+        }
+
+        // As with ListArrayReader, the child data contains a padding value for each
+        // null or empty list. Unlike a ListArray, a null FixedSizeListArray slot must
+        // still occupy `fixed_size` child values, so the padding is replaced with
+        // `fixed_size` nulls
+
+        // The validity mask of the computed FixedSizeListArray if nullable
+        let mut validity = self
+            .nullable
+            .then(|| BooleanBufferBuilder::new(next_batch_array.len()));
+
+        // The number of rows in the computed FixedSizeListArray
+        let mut row_count = 0;
+
+        // The offset into the child data of the current level being considered
+        let mut child_idx = 0;
+
+        // The number of child values in the current valid row
+        let mut row_len: Option<usize> = None;
+
+        // Identifies the start of a run of values to copy from the source child data
+        let mut filter_start = None;
+
+        // If any null or empty rows were encountered
+        let mut padded = false;
+
+        // Builder used to construct the child data, replacing padding with nulls
+        let mut child_data_builder = MutableArrayData::new(
+            vec![next_batch_array.data()],
+            true,
+            next_batch_array.len(),
+        );
+
+        let check_row = |row_len: Option<usize>| match row_len {
+            Some(len) if len != self.fixed_size => Err(general_err!(
+                "Encountered misaligned row with length {} (expected length {})",
+                len,
+                self.fixed_size
+            )),
+            _ => Ok(()),
+        };
+
+        def_levels.iter().zip(rep_levels).try_for_each(|(d, r)| {
+            match r.cmp(&self.rep_level) {
+                Ordering::Greater => {
+                    // Repetition level greater than current => already handled by inner array
+                    if *d < self.def_level {
+                        return Err(general_err!(
+                            "Encountered repetition level too large for definition level"
+                        ));
+                    }
+                }
+                Ordering::Equal => {
+                    // New value in the current row
+                    match row_len.as_mut() {
+                        Some(len) => *len += 1,
+                        None => {
+                            return Err(general_err!(
+                                "Encountered repeated value in null or empty row"
+                            ))
+                        }
+                    }
+                    child_idx += 1;
+                }
+                Ordering::Less => {
+                    // Start of a new row
+                    check_row(row_len.take())?;
+                    row_count += 1;
+
+                    if *d >= self.def_level {
+                        // Fully defined value
+                        filter_start.get_or_insert(child_idx);
+                        row_len = Some(1);
+
+                        if let Some(validity) = validity.as_mut() {
+                            validity.append(true)
+                        }
+                    } else {
+                        // Flush the current run of child values if any
+                        if let Some(start) = filter_start.take() {
+                            child_data_builder.extend(0, start, child_idx);
+                        }
+
+                        if *d + 1 == self.def_level {
+                            // An empty list is only valid for a zero-sized list
+                            if self.fixed_size != 0 {
+                                return Err(general_err!(
+                                    "Encountered empty row in FixedSizeList of size {}",
+                                    self.fixed_size
+                                ));
+                            }
+                        } else {
+                            child_data_builder.extend_nulls(self.fixed_size);
+                        }
+
+                        if let Some(validity) = validity.as_mut() {
+                            // Valid if empty list
+                            validity.append(*d + 1 == self.def_level)
+                        }
+
+                        padded = true;
+                    }
+                    child_idx += 1;
+                }
+            }
+            Ok(())
+        })?;
+
+        check_row(row_len)?;
+
+        let child_data = if !padded {
+            // No padding values - can reuse original array
+            next_batch_array.data().clone()
+        } else {
+            if let Some(start) = filter_start.take() {
+                child_data_builder.extend(0, start, child_idx);
+            }
+
+            child_data_builder.freeze()
+        };
+
+        if child_data.len() != row_count * self.fixed_size {
+            return Err(general_err!(
+                "Failed to reconstruct FixedSizeList from level data"
+            ));
+        }
+
+        let mut data_builder = ArrayData::builder(self.get_data_type().clone())
+            .len(row_count)
+            .add_child_data(child_data);
+
+        if let Some(mut builder) = validity {
+            assert_eq!(builder.len(), row_count);
+            data_builder = data_builder.null_bit_buffer(Some(builder.finish()))
+        }
+
+        let list_data = unsafe { data_builder.build_unchecked() };
+
+        let result_array = FixedSizeListArray::from(list_data);
+        Ok(Arc::new(result_array))
+    }
+
+    fn skip_records(&mut self, num_records: usize) -> Result<usize> {
+        self.item_reader.skip_records(num_records)
+    }
+
+    fn get_def_levels(&self) -> Option<&[i16]> {
+        self.item_reader.get_def_levels()
+    }
+
+    fn get_rep_levels(&self) -> Option<&[i16]> {
+        self.item_reader.get_rep_levels()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::array_reader::test_util::InMemoryArrayReader;
+    use arrow::datatypes::Field;
+    use arrow_array::builder::{FixedSizeListBuilder, Int32Builder};
+    use arrow_array::Int32Array;
+
+    fn list_type(size: i32) -> ArrowType {
+        let field = Field::new("item", ArrowType::Int32, true);
+        ArrowType::FixedSizeList(Box::new(field), size)
+    }
+
+    #[test]
+    fn test_nullable_list() {
+        // [[1, null], null, [3, 4]]
+        let mut builder = FixedSizeListBuilder::new(Int32Builder::new(), 2);
+        builder.values().append_value(1);
+        builder.values().append_null();
+        builder.append(true);
+        builder.values().append_nulls(2);
+        builder.append(false);
+        builder.values().append_slice(&[3, 4]);
+        builder.append(true);
+        let expected = builder.finish();
+
+        let array = Arc::new(Int32Array::from(vec![
+            Some(1),
+            None,
+            None,
+            Some(3),
+            Some(4),
+        ]));
+        let item_array_reader = InMemoryArrayReader::new(
+            ArrowType::Int32,
+            array,
+            Some(vec![3, 2, 0, 3, 3]),
+            Some(vec![0, 1, 0, 0, 1]),
+        );
+
+        let mut list_array_reader = FixedSizeListArrayReader::new(
+            Box::new(item_array_reader),
+            2,
+            list_type(2),
+            2,
+            1,
+            true,
+        );
+
+        let actual = list_array_reader.next_batch(1024).unwrap();
+        let actual = actual
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+
+        assert_eq!(&expected, actual);
+    }
+
+    #[test]
+    fn test_misaligned_list() {
+        // [[1, 2, 3]] cannot be read as a FixedSizeList of size 2
+        let array = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let item_array_reader = InMemoryArrayReader::new(
+            ArrowType::Int32,
+            array,
+            Some(vec![3, 3, 3]),
+            Some(vec![0, 1, 1]),
+        );
+
+        let mut list_array_reader = FixedSizeListArrayReader::new(
+            Box::new(item_array_reader),
+            2,
+            list_type(2),
+            2,
+            1,
+            true,
+        );
+
+        let err = list_array_reader.next_batch(1024).unwrap_err().to_string();
+        assert!(err.contains("misaligned row with length 3"), "{}", err);
+    }
+}
@@ -571,11 +571,13 @@ mod tests {
             schema,
             ProjectionMask::all(),
             file_metadata.key_value_metadata(),
+            None,
         )
         .unwrap();
 
         let mut array_reader =
-            build_array_reader(fields.as_ref(), &mask, &file_reader).unwrap();
+            build_array_reader(fields.as_ref(), &mask, &file_reader, Default::default())
+                .unwrap();
 
         let batch = array_reader.next_batch(100).unwrap();
         assert_eq!(batch.data_type(), array_reader.get_data_type());
@@ -35,10 +35,12 @@ mod byte_array;
 mod byte_array_dictionary;
 mod empty_array;
 mod fixed_len_byte_array;
+mod fixed_size_list_array;
 mod list_array;
 mod map_array;
 mod null_array;
 mod primitive_array;
+mod primitive_dictionary;
 mod struct_array;
 
 #[cfg(test)]
@@ -48,10 +50,12 @@ pub use builder::build_array_reader;
 pub use byte_array::make_byte_array_reader;
 pub use byte_array_dictionary::make_byte_array_dictionary_reader;
 pub use fixed_len_byte_array::make_fixed_len_byte_array_reader;
+pub use fixed_size_list_array::FixedSizeListArrayReader;
 pub use list_array::ListArrayReader;
 pub use map_array::MapArrayReader;
 pub use null_array::NullArrayReader;
 pub use primitive_array::PrimitiveArrayReader;
+pub use primitive_dictionary::make_primitive_dictionary_reader;
 pub use struct_array::StructArrayReader;
 
 /// Array reader reads parquet data into arrow array.
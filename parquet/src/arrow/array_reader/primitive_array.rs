@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::arrow::array_reader::{read_records, skip_records, ArrayReader};
+use crate::arrow::arrow_reader::Int96Overflow;
 use crate::arrow::record_reader::buffer::ScalarValue;
 use crate::arrow::record_reader::RecordReader;
 use crate::arrow::schema::parquet_to_arrow_field;
@@ -25,11 +26,11 @@ use crate::data_type::{DataType, Int96};
 use crate::errors::{ParquetError, Result};
 use crate::schema::types::ColumnDescPtr;
 use arrow_array::{
-    builder::{BooleanBufferBuilder, TimestampNanosecondBufferBuilder},
-    ArrayRef, BooleanArray, Decimal128Array, Float32Array, Float64Array, Int32Array,
-    Int64Array, TimestampNanosecondArray, UInt32Array, UInt64Array,
+    builder::{BooleanBufferBuilder, BufferBuilder},
+    make_array, ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Float32Array,
+    Float64Array, Int32Array, Int64Array, UInt32Array, UInt64Array,
 };
-use arrow_buffer::Buffer;
+use arrow_buffer::{bit_util, i256, Buffer};
 use arrow_data::ArrayDataBuilder;
 use arrow_schema::{DataType as ArrowType, TimeUnit};
 use std::any::Any;
@@ -47,6 +48,7 @@ where
     def_levels_buffer: Option<Buffer>,
     rep_levels_buffer: Option<Buffer>,
     record_reader: RecordReader<T>,
+    int96_overflow: Int96Overflow,
 }
 
 impl<T> PrimitiveArrayReader<T>
@@ -76,8 +78,17 @@ where
             def_levels_buffer: None,
             rep_levels_buffer: None,
             record_reader,
+            int96_overflow: Int96Overflow::default(),
         })
     }
+
+    /// Set how INT96 timestamps that cannot be represented by the arrow type are handled
+    pub fn with_int96_overflow(self, int96_overflow: Int96Overflow) -> Self {
+        Self {
+            int96_overflow,
+            ..self
+        }
+    }
 }
 
 /// Implementation of primitive array reader.
@@ -126,8 +137,10 @@ where
             PhysicalType::FLOAT => ArrowType::Float32,
             PhysicalType::DOUBLE => ArrowType::Float64,
             PhysicalType::INT96 => match target_type {
-                ArrowType::Timestamp(TimeUnit::Nanosecond, _) => target_type.clone(),
-                _ => unreachable!("INT96 must be timestamp nanosecond"),
+                ArrowType::Timestamp(_, _) | ArrowType::Decimal128(_, _) => {
+                    target_type.clone()
+                }
+                _ => unreachable!("INT96 must be timestamp or decimal"),
             },
             PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
                 unreachable!(
@@ -140,6 +153,7 @@ where
         // The physical types are then cast to Arrow types if necessary
 
         let record_data = self.record_reader.consume_record_data();
+        let null_buffer = self.record_reader.consume_bitmap_buffer();
         let record_data = match T::get_physical_type() {
             PhysicalType::BOOLEAN => {
                 let mut boolean_buffer = BooleanBufferBuilder::new(record_data.len());
@@ -155,12 +169,35 @@ where
                     unsafe { record_data.as_slice().align_to::<Int96>() };
                 assert!(prefix.is_empty() && suffix.is_empty());
 
-                let mut builder = TimestampNanosecondBufferBuilder::new(slice.len());
-                for v in slice {
-                    builder.append(v.to_nanos())
+                match target_type {
+                    ArrowType::Decimal128(_, _) => {
+                        let mut builder = BufferBuilder::<i128>::new(slice.len());
+                        for v in slice {
+                            let (seconds, nanos) = v.to_seconds_and_nanos();
+                            builder
+                                .append(seconds as i128 * 1_000_000_000 + nanos as i128)
+                        }
+                        builder.finish()
+                    }
+                    ArrowType::Timestamp(unit, _) => {
+                        let valid = |idx: usize| match &null_buffer {
+                            Some(nulls) => bit_util::get_bit(nulls.as_slice(), idx),
+                            None => true,
+                        };
+
+                        let mut builder = BufferBuilder::<i64>::new(slice.len());
+                        for (idx, v) in slice.iter().enumerate() {
+                            // Null slots are padded with zero, which is out of range
+                            let overflow = match valid(idx) {
+                                true => self.int96_overflow,
+                                false => Int96Overflow::Wrap,
+                            };
+                            builder.append(int96_to_timestamp(v, unit, overflow)?)
+                        }
+                        builder.finish()
+                    }
+                    _ => unreachable!(),
                 }
-
-                builder.finish()
             }
             _ => record_data,
         };
@@ -168,7 +205,7 @@ where
         let array_data = ArrayDataBuilder::new(arrow_data_type)
             .len(self.record_reader.num_values())
             .add_buffer(record_data)
-            .null_bit_buffer(self.record_reader.consume_bitmap_buffer());
+            .null_bit_buffer(null_buffer);
 
         let array_data = unsafe { array_data.build_unchecked() };
         let array: ArrayRef = match T::get_physical_type() {
@@ -185,7 +222,7 @@ where
             },
             PhysicalType::FLOAT => Arc::new(Float32Array::from(array_data)),
             PhysicalType::DOUBLE => Arc::new(Float64Array::from(array_data)),
-            PhysicalType::INT96 => Arc::new(TimestampNanosecondArray::from(array_data)),
+            PhysicalType::INT96 => make_array(array_data),
             PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
                 unreachable!(
                     "PrimitiveArrayReaders don't support complex physical types"
@@ -193,52 +230,7 @@ where
             }
         };
 
-        // cast to Arrow type
-        // We make a strong assumption here that the casts should be infallible.
-        // If the cast fails because of incompatible datatypes, then there might
-        // be a bigger problem with how Arrow schemas are converted to Parquet.
-        //
-        // As there is not always a 1:1 mapping between Arrow and Parquet, there
-        // are datatypes which we must convert explicitly.
-        // These are:
-        // - date64: we should cast int32 to date32, then date32 to date64.
-        // - decimal: cast in32 to decimal, int64 to decimal
-        let array = match target_type {
-            ArrowType::Date64 => {
-                // this is cheap as it internally reinterprets the data
-                let a = arrow_cast::cast(&array, &ArrowType::Date32)?;
-                arrow_cast::cast(&a, target_type)?
-            }
-            ArrowType::Decimal128(p, s) => {
-                let array = match array.data_type() {
-                    ArrowType::Int32 => array
-                        .as_any()
-                        .downcast_ref::<Int32Array>()
-                        .unwrap()
-                        .iter()
-                        .map(|v| v.map(|v| v as i128))
-                        .collect::<Decimal128Array>(),
-
-                    ArrowType::Int64 => array
-                        .as_any()
-                        .downcast_ref::<Int64Array>()
-                        .unwrap()
-                        .iter()
-                        .map(|v| v.map(|v| v as i128))
-                        .collect::<Decimal128Array>(),
-                    _ => {
-                        return Err(arrow_err!(
-                            "Cannot convert {:?} to decimal",
-                            array.data_type()
-                        ));
-                    }
-                }
-                .with_precision_and_scale(*p, *s)?;
-
-                Arc::new(array) as ArrayRef
-            }
-            _ => arrow_cast::cast(&array, target_type)?,
-        };
+        let array = coerce_array(array, target_type)?;
 
         // save definition and repetition buffers
         self.def_levels_buffer = self.record_reader.consume_def_levels();
@@ -260,6 +252,124 @@ where
     }
 }
 
+/// Casts `array`, as decoded from the parquet physical type, to `target_type`
+pub(crate) fn coerce_array(array: ArrayRef, target_type: &ArrowType) -> Result<ArrayRef> {
+    // We make a strong assumption here that the casts should be infallible.
+    // If the cast fails because of incompatible datatypes, then there might
+    // be a bigger problem with how Arrow schemas are converted to Parquet.
+    //
+    // As there is not always a 1:1 mapping between Arrow and Parquet, there
+    // are datatypes which we must convert explicitly.
+    // These are:
+    // - date64: we should cast int32 to date32, then date32 to date64.
+    // - decimal: cast in32 to decimal, int64 to decimal
+    if array.data_type() == target_type {
+        return Ok(array);
+    }
+
+    match target_type {
+        ArrowType::Date64 => {
+            // this is cheap as it internally reinterprets the data
+            let a = arrow_cast::cast(&array, &ArrowType::Date32)?;
+            Ok(arrow_cast::cast(&a, target_type)?)
+        }
+        ArrowType::Decimal128(p, s) => {
+            let array = match array.data_type() {
+                ArrowType::Int32 => array
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .iter()
+                    .map(|v| v.map(|v| v as i128))
+                    .collect::<Decimal128Array>(),
+
+                ArrowType::Int64 => array
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .iter()
+                    .map(|v| v.map(|v| v as i128))
+                    .collect::<Decimal128Array>(),
+                _ => {
+                    return Err(arrow_err!(
+                        "Cannot convert {:?} to decimal",
+                        array.data_type()
+                    ));
+                }
+            }
+            .with_precision_and_scale(*p, *s)?;
+
+            Ok(Arc::new(array) as ArrayRef)
+        }
+        ArrowType::Decimal256(p, s) => {
+            let array = match array.data_type() {
+                ArrowType::Int32 => array
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .iter()
+                    .map(|v| v.map(|v| i256::from_i128(v as i128)))
+                    .collect::<Decimal256Array>(),
+
+                ArrowType::Int64 => array
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .iter()
+                    .map(|v| v.map(|v| i256::from_i128(v as i128)))
+                    .collect::<Decimal256Array>(),
+                _ => {
+                    return Err(arrow_err!(
+                        "Cannot convert {:?} to decimal",
+                        array.data_type()
+                    ));
+                }
+            }
+            .with_precision_and_scale(*p, *s)?;
+
+            Ok(Arc::new(array) as ArrayRef)
+        }
+        _ => Ok(arrow_cast::cast(&array, target_type)?),
+    }
+}
+
+/// Converts an [`Int96`] to a timestamp in `unit`, handling values that cannot be
+/// represented as specified by `overflow`
+fn int96_to_timestamp(
+    v: &Int96,
+    unit: &TimeUnit,
+    overflow: Int96Overflow,
+) -> Result<i64> {
+    let (seconds, nanos) = v.to_seconds_and_nanos();
+    let (multiplier, divisor) = match unit {
+        TimeUnit::Second => (1, 1_000_000_000),
+        TimeUnit::Millisecond => (1_000, 1_000_000),
+        TimeUnit::Microsecond => (1_000_000, 1_000),
+        TimeUnit::Nanosecond => (1_000_000_000, 1),
+    };
+
+    let checked = seconds
+        .checked_mul(multiplier)
+        .and_then(|v| v.checked_add(nanos / divisor));
+
+    match (checked, overflow) {
+        (Some(v), _) => Ok(v),
+        (None, Int96Overflow::Wrap) => Ok(seconds
+            .wrapping_mul(multiplier)
+            .wrapping_add(nanos / divisor)),
+        (None, Int96Overflow::Saturate) => match seconds < 0 {
+            true => Ok(i64::MIN),
+            false => Ok(i64::MAX),
+        },
+        (None, Int96Overflow::Error) => Err(arrow_err!(
+            "INT96 timestamp of {} seconds and {} nanoseconds overflows {:?} timestamp",
+            seconds,
+            nanos,
+            unit
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
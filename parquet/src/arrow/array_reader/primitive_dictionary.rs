@@ -0,0 +1,559 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow_array::{make_array, Array, ArrayRef};
+use arrow_buffer::{ArrowNativeType, Buffer};
+use arrow_data::ArrayDataBuilder;
+use arrow_schema::DataType as ArrowType;
+
+use crate::arrow::array_reader::primitive_array::coerce_array;
+use crate::arrow::array_reader::{read_records, skip_records, ArrayReader};
+use crate::arrow::buffer::dictionary_buffer::{
+    keys_into_array, GenericDictionaryBuffer, MaybeDictionaryDecoder,
+};
+use crate::arrow::record_reader::buffer::{BufferQueue, ScalarBuffer, ScalarValue};
+use crate::arrow::record_reader::GenericRecordReader;
+use crate::arrow::schema::parquet_to_arrow_field;
+use crate::basic::{Encoding, Type as PhysicalType};
+use crate::column::page::PageIterator;
+use crate::column::reader::decoder::{ColumnValueDecoder, ColumnValueDecoderImpl};
+use crate::data_type::{DataType, DoubleType, FloatType, Int32Type, Int64Type};
+use crate::encodings::decoding::{Decoder, PlainDecoder};
+use crate::errors::{ParquetError, Result};
+use crate::schema::types::ColumnDescPtr;
+use crate::util::bit_util::FromBytes;
+use crate::util::memory::ByteBufferPtr;
+
+/// A macro to reduce verbosity of [`make_primitive_dictionary_reader`]
+macro_rules! make_reader {
+    (
+        ($pages:expr, $column_desc:expr, $data_type:expr, $value:ty) => match $k:ident {
+            $($key_arrow:pat => $key_type:ty,)+
+        }
+    ) => {
+        match $k {
+            $(
+                $key_arrow => {
+                    let reader = GenericRecordReader::new($column_desc);
+                    Ok(Box::new(PrimitiveDictionaryReader::<$key_type, $value>::new(
+                        $pages, $data_type, reader,
+                    )))
+                }
+            )+
+            _ => Err(general_err!(
+                "unsupported data type for primitive dictionary reader - {}",
+                $data_type
+            )),
+        }
+    }
+}
+
+/// Returns an [`ArrayReader`] that decodes the provided INT32, INT64, FLOAT or DOUBLE
+/// column into a [`DictionaryArray`](arrow_array::DictionaryArray)
+///
+/// This will attempt to preserve any dictionary encoding present in the parquet data,
+/// using the dictionary page as the values of the returned array
+///
+/// As with [`make_byte_array_dictionary_reader`](super::make_byte_array_dictionary_reader),
+/// it will be unable to preserve the dictionary encoding if:
+///
+/// * A single read spans across multiple column chunks
+/// * A column chunk contains non-dictionary encoded pages
+///
+/// In which case a new dictionary will be computed from the decoded values
+pub fn make_primitive_dictionary_reader(
+    pages: Box<dyn PageIterator>,
+    column_desc: ColumnDescPtr,
+    arrow_type: Option<ArrowType>,
+) -> Result<Box<dyn ArrayReader>> {
+    // Check if Arrow type is specified, else create it from Parquet type
+    let data_type = match arrow_type {
+        Some(t) => t,
+        None => parquet_to_arrow_field(column_desc.as_ref())?
+            .data_type()
+            .clone(),
+    };
+
+    let key_type = match &data_type {
+        ArrowType::Dictionary(key_type, _) => key_type.as_ref().clone(),
+        _ => {
+            return Err(general_err!(
+                "invalid non-dictionary data type for primitive dictionary reader - {}",
+                data_type
+            ))
+        }
+    };
+
+    match column_desc.physical_type() {
+        PhysicalType::INT32 => {
+            make_key_reader::<Int32Type>(pages, column_desc, data_type, &key_type)
+        }
+        PhysicalType::INT64 => {
+            make_key_reader::<Int64Type>(pages, column_desc, data_type, &key_type)
+        }
+        PhysicalType::FLOAT => {
+            make_key_reader::<FloatType>(pages, column_desc, data_type, &key_type)
+        }
+        PhysicalType::DOUBLE => {
+            make_key_reader::<DoubleType>(pages, column_desc, data_type, &key_type)
+        }
+        p => Err(general_err!(
+            "unsupported physical type for primitive dictionary reader - {}",
+            p
+        )),
+    }
+}
+
+fn make_key_reader<T>(
+    pages: Box<dyn PageIterator>,
+    column_desc: ColumnDescPtr,
+    data_type: ArrowType,
+    key_type: &ArrowType,
+) -> Result<Box<dyn ArrayReader>>
+where
+    T: DataType,
+    T::T: ScalarValue + ArrowNativeType,
+{
+    make_reader! {
+        (pages, column_desc, data_type, T) => match key_type {
+            ArrowType::UInt8 => u8,
+            ArrowType::Int8 => i8,
+            ArrowType::UInt16 => u16,
+            ArrowType::Int16 => i16,
+            ArrowType::UInt32 => u32,
+            ArrowType::Int32 => i32,
+            ArrowType::UInt64 => u64,
+            ArrowType::Int64 => i64,
+        }
+    }
+}
+
+/// Returns the [`ArrowType`] of the values decoded from the parquet physical type `T`
+fn physical_arrow_type<T: DataType>() -> ArrowType {
+    match T::get_physical_type() {
+        PhysicalType::INT32 => ArrowType::Int32,
+        PhysicalType::INT64 => ArrowType::Int64,
+        PhysicalType::FLOAT => ArrowType::Float32,
+        PhysicalType::DOUBLE => ArrowType::Float64,
+        _ => unreachable!("unsupported physical type for primitive dictionary"),
+    }
+}
+
+/// Converts `values`, as decoded from the parquet physical type, to `value_type`
+fn coerce_values(values: ArrayRef, value_type: &ArrowType) -> Result<ArrayRef> {
+    match (values.data_type(), value_type) {
+        (ArrowType::Int32, ArrowType::UInt32) | (ArrowType::Int64, ArrowType::UInt64) => {
+            // Reinterpret as unsigned, consistent with PrimitiveArrayReader
+            let builder = values
+                .into_data()
+                .into_builder()
+                .data_type(value_type.clone());
+            Ok(make_array(unsafe { builder.build_unchecked() }))
+        }
+        _ => coerce_array(values, value_type),
+    }
+}
+
+/// An [`ArrayReader`] for dictionary encoded primitive columns
+///
+/// Will attempt to preserve any dictionary encoding present in the parquet data
+struct PrimitiveDictionaryReader<K: ScalarValue, T: DataType>
+where
+    T::T: ScalarValue,
+{
+    data_type: ArrowType,
+    pages: Box<dyn PageIterator>,
+    def_levels_buffer: Option<Buffer>,
+    rep_levels_buffer: Option<Buffer>,
+    /// The most recent dictionary decoded from parquet, and its conversion to the
+    /// value type of `data_type`
+    dictionary: Option<(ArrayRef, ArrayRef)>,
+    record_reader:
+        GenericRecordReader<PrimitiveDictionaryBuffer<K, T::T>, DictionaryDecoder<K, T>>,
+}
+
+impl<K, T> PrimitiveDictionaryReader<K, T>
+where
+    K: FromBytes + ScalarValue + Ord + ArrowNativeType,
+    T: DataType,
+    T::T: ScalarValue + ArrowNativeType,
+{
+    fn new(
+        pages: Box<dyn PageIterator>,
+        data_type: ArrowType,
+        record_reader: GenericRecordReader<
+            PrimitiveDictionaryBuffer<K, T::T>,
+            DictionaryDecoder<K, T>,
+        >,
+    ) -> Self {
+        Self {
+            data_type,
+            pages,
+            def_levels_buffer: None,
+            rep_levels_buffer: None,
+            dictionary: None,
+            record_reader,
+        }
+    }
+
+    /// Returns the value type of the [`ArrowType::Dictionary`] being read
+    fn value_type(&self) -> &ArrowType {
+        match &self.data_type {
+            ArrowType::Dictionary(_, v) => v.as_ref(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Converts the decoded dictionary `values` to the dictionary value type,
+    /// reusing the previous conversion if the dictionary has not changed
+    fn convert_dictionary(&mut self, values: ArrayRef) -> Result<ArrayRef> {
+        if let Some((decoded, converted)) = &self.dictionary {
+            if Arc::ptr_eq(decoded, &values) {
+                return Ok(converted.clone());
+            }
+        }
+
+        let converted = coerce_values(values.clone(), self.value_type())?;
+        self.dictionary = Some((values, converted.clone()));
+        Ok(converted)
+    }
+}
+
+impl<K, T> ArrayReader for PrimitiveDictionaryReader<K, T>
+where
+    K: FromBytes + ScalarValue + Ord + ArrowNativeType,
+    T: DataType,
+    T::T: ScalarValue + ArrowNativeType,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_data_type(&self) -> &ArrowType {
+        &self.data_type
+    }
+
+    fn read_records(&mut self, batch_size: usize) -> Result<usize> {
+        read_records(&mut self.record_reader, self.pages.as_mut(), batch_size)
+    }
+
+    fn consume_batch(&mut self) -> Result<ArrayRef> {
+        let buffer = self.record_reader.consume_record_data();
+        let null_buffer = self.record_reader.consume_bitmap_buffer();
+
+        let array = match buffer {
+            PrimitiveDictionaryBuffer::Dict { keys, values } => {
+                let values = self.convert_dictionary(values)?;
+                keys_into_array(keys, values, null_buffer, &self.data_type)?
+            }
+            PrimitiveDictionaryBuffer::Values { values } => {
+                let builder = ArrayDataBuilder::new(physical_arrow_type::<T>())
+                    .len(values.len())
+                    .add_buffer(values.into())
+                    .null_bit_buffer(null_buffer);
+
+                let values = make_array(unsafe { builder.build_unchecked() });
+                let values = coerce_values(values, self.value_type())?;
+
+                // This will compute a new dictionary
+                arrow_cast::cast(&values, &self.data_type)?
+            }
+        };
+
+        self.def_levels_buffer = self.record_reader.consume_def_levels();
+        self.rep_levels_buffer = self.record_reader.consume_rep_levels();
+        self.record_reader.reset();
+
+        Ok(array)
+    }
+
+    fn skip_records(&mut self, num_records: usize) -> Result<usize> {
+        skip_records(&mut self.record_reader, self.pages.as_mut(), num_records)
+    }
+
+    fn get_def_levels(&self) -> Option<&[i16]> {
+        self.def_levels_buffer.as_ref().map(|buf| buf.typed_data())
+    }
+
+    fn get_rep_levels(&self) -> Option<&[i16]> {
+        self.rep_levels_buffer.as_ref().map(|buf| buf.typed_data())
+    }
+}
+
+/// An array of primitive values that are potentially dictionary encoded
+type PrimitiveDictionaryBuffer<K, V> = GenericDictionaryBuffer<K, ScalarBuffer<V>>;
+
+/// A [`ColumnValueDecoder`] for dictionary encoded primitive columns
+struct DictionaryDecoder<K, T: DataType> {
+    /// The current dictionary
+    dict: Option<ArrayRef>,
+
+    /// Decoder for the current page
+    decoder: Option<MaybeDictionaryDecoder<ColumnValueDecoderImpl<T>>>,
+
+    descr: ColumnDescPtr,
+
+    phantom: PhantomData<K>,
+}
+
+/// Appends up to `len` values produced by `decode` to the end of `out`
+fn append_values<S: ScalarValue>(
+    out: &mut ScalarBuffer<S>,
+    len: usize,
+    decode: impl FnOnce(&mut [S]) -> Result<usize>,
+) -> Result<usize> {
+    let start = out.len();
+    let read = decode(out.spare_capacity_mut(len))?;
+    out.set_len(start + read);
+    Ok(read)
+}
+
+impl<K, T> ColumnValueDecoder for DictionaryDecoder<K, T>
+where
+    K: FromBytes + ScalarValue + Ord + ArrowNativeType,
+    T: DataType,
+    T::T: ScalarValue + ArrowNativeType,
+{
+    type Slice = PrimitiveDictionaryBuffer<K, T::T>;
+
+    fn new(col: &ColumnDescPtr) -> Self {
+        Self {
+            dict: None,
+            decoder: None,
+            descr: col.clone(),
+            phantom: Default::default(),
+        }
+    }
+
+    fn set_dict(
+        &mut self,
+        buf: ByteBufferPtr,
+        num_values: u32,
+        encoding: Encoding,
+        _is_sorted: bool,
+    ) -> Result<()> {
+        if !matches!(
+            encoding,
+            Encoding::PLAIN | Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY
+        ) {
+            return Err(nyi_err!(
+                "Invalid/Unsupported encoding type for dictionary: {}",
+                encoding
+            ));
+        }
+
+        if K::from_usize(num_values as usize).is_none() {
+            return Err(general_err!("dictionary too large for index type"));
+        }
+
+        let len = num_values as usize;
+        let mut values = ScalarBuffer::<T::T>::new();
+        let mut decoder = PlainDecoder::<T>::new(self.descr.type_length());
+        decoder.set_data(buf, len)?;
+        let read = append_values(&mut values, len, |out| decoder.get(out))?;
+        if read != len {
+            return Err(eof_err!(
+                "Expected to read {} dictionary values, read {}",
+                len,
+                read
+            ));
+        }
+
+        let builder = ArrayDataBuilder::new(physical_arrow_type::<T>())
+            .len(len)
+            .add_buffer(values.into());
+
+        self.dict = Some(make_array(unsafe { builder.build_unchecked() }));
+        Ok(())
+    }
+
+    fn set_data(
+        &mut self,
+        encoding: Encoding,
+        data: ByteBufferPtr,
+        num_levels: usize,
+        num_values: Option<usize>,
+    ) -> Result<()> {
+        let descr = &self.descr;
+        let decoder = MaybeDictionaryDecoder::new(
+            encoding,
+            data,
+            num_levels,
+            num_values,
+            |encoding, data, num_levels, num_values| {
+                let mut decoder = ColumnValueDecoderImpl::new(descr);
+                decoder.set_data(encoding, data, num_levels, num_values)?;
+                Ok(decoder)
+            },
+        )?;
+
+        self.decoder = Some(decoder);
+        Ok(())
+    }
+
+    fn read(&mut self, out: &mut Self::Slice, range: Range<usize>) -> Result<usize> {
+        let decoder = self.decoder.as_mut().expect("decoder set");
+        decoder.read(out, range, self.dict.as_ref(), |decoder, values, len| {
+            append_values(values, len, |out| decoder.read(out, 0..len))
+        })
+    }
+
+    fn skip_values(&mut self, num_values: usize) -> Result<usize> {
+        let decoder = self.decoder.as_mut().expect("decoder set");
+        decoder.skip(num_values, |decoder, num_values| {
+            decoder.skip_values(num_values)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_buffer::Buffer;
+
+    use crate::arrow::record_reader::buffer::ValuesBuffer;
+    use crate::basic::Type as PhysicalType;
+    use crate::encodings::encoding::{get_encoder, DictEncoder, Encoder};
+    use crate::schema::types::{ColumnDescriptor, ColumnPath, Type};
+
+    use super::*;
+
+    fn int32_column() -> ColumnDescPtr {
+        let t = Type::primitive_type_builder("col", PhysicalType::INT32)
+            .build()
+            .unwrap();
+
+        Arc::new(ColumnDescriptor::new(
+            Arc::new(t),
+            1,
+            0,
+            ColumnPath::new(vec![]),
+        ))
+    }
+
+    fn encode_dictionary(data: &[i32]) -> (ByteBufferPtr, ByteBufferPtr) {
+        let mut dict_encoder = DictEncoder::<Int32Type>::new(int32_column());
+        dict_encoder.put(data).unwrap();
+        let encoded_rle = dict_encoder.flush_buffer().unwrap();
+        let encoded_dictionary = dict_encoder.write_dict().unwrap();
+        (encoded_dictionary, encoded_rle)
+    }
+
+    fn spilled(output: &mut PrimitiveDictionaryBuffer<i32, i32>) -> Vec<i32> {
+        output.spill_values().unwrap().as_slice().to_vec()
+    }
+
+    #[test]
+    fn test_dictionary_preservation() {
+        let data = [10, 20, 10, 20, 30, 20, 30];
+        let (dict, encoded) = encode_dictionary(&data);
+
+        let column_desc = int32_column();
+        let mut decoder = DictionaryDecoder::<i32, Int32Type>::new(&column_desc);
+        decoder
+            .set_dict(dict, 3, Encoding::RLE_DICTIONARY, false)
+            .unwrap();
+        decoder
+            .set_data(Encoding::RLE_DICTIONARY, encoded, 9, Some(data.len()))
+            .unwrap();
+
+        let mut output = PrimitiveDictionaryBuffer::<i32, i32>::default();
+        assert_eq!(decoder.read(&mut output, 0..3).unwrap(), 3);
+
+        let valid = [true, false, true, true];
+        let valid_buffer = Buffer::from_iter(valid.iter().cloned());
+        output.pad_nulls(0, 3, valid.len(), valid_buffer.as_slice());
+
+        assert_eq!(decoder.read(&mut output, 0..4).unwrap(), 4);
+
+        match &output {
+            PrimitiveDictionaryBuffer::Dict { keys, values } => {
+                assert_eq!(values.len(), 3);
+                assert_eq!(keys.len(), 8);
+                assert_eq!(&keys.as_slice()[4..], &[1, 2, 1, 2]);
+            }
+            _ => panic!("expected dictionary encoded output"),
+        }
+
+        // Null slot retains an arbitrary, but valid, dictionary key
+        let values = spilled(&mut output);
+        assert_eq!(values[0], 10);
+        assert_eq!(&values[2..], &[20, 10, 20, 30, 20, 30]);
+    }
+
+    #[test]
+    fn test_dictionary_fallback() {
+        let column_desc = int32_column();
+        let mut decoder = DictionaryDecoder::<i32, Int32Type>::new(&column_desc);
+
+        let (dict, encoded) = encode_dictionary(&[1, 2, 1]);
+        decoder
+            .set_dict(dict, 2, Encoding::RLE_DICTIONARY, false)
+            .unwrap();
+        decoder
+            .set_data(Encoding::RLE_DICTIONARY, encoded, 3, Some(3))
+            .unwrap();
+
+        let mut output = PrimitiveDictionaryBuffer::<i32, i32>::default();
+        assert_eq!(decoder.read(&mut output, 0..3).unwrap(), 3);
+        assert!(matches!(output, PrimitiveDictionaryBuffer::Dict { .. }));
+
+        // A PLAIN encoded page forces the dictionary to be spilled
+        let mut encoder = get_encoder::<Int32Type>(Encoding::PLAIN).unwrap();
+        encoder.put(&[4, 5]).unwrap();
+        let plain = encoder.flush_buffer().unwrap();
+        decoder
+            .set_data(Encoding::PLAIN, plain, 2, Some(2))
+            .unwrap();
+        assert_eq!(decoder.read(&mut output, 0..2).unwrap(), 2);
+        assert!(matches!(output, PrimitiveDictionaryBuffer::Values { .. }));
+
+        // A new dictionary mid-batch cannot be preserved
+        let (dict, encoded) = encode_dictionary(&[7, 8]);
+        decoder
+            .set_dict(dict, 2, Encoding::RLE_DICTIONARY, false)
+            .unwrap();
+        decoder
+            .set_data(Encoding::RLE_DICTIONARY, encoded, 2, Some(2))
+            .unwrap();
+        assert_eq!(decoder.read(&mut output, 0..2).unwrap(), 2);
+
+        assert_eq!(spilled(&mut output), vec![1, 2, 1, 4, 5, 7, 8]);
+    }
+
+    #[test]
+    fn test_dictionary_too_large() {
+        let data: Vec<_> = (0..200).collect();
+        let (dict, _) = encode_dictionary(&data);
+
+        let column_desc = int32_column();
+        let mut decoder = DictionaryDecoder::<i8, Int32Type>::new(&column_desc);
+        let err = decoder
+            .set_dict(dict, 200, Encoding::RLE_DICTIONARY, false)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("dictionary too large for index type"),
+            "{}",
+            err
+        );
+    }
+}
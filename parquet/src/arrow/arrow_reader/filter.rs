@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::arrow::ProjectionMask;
+use crate::file::page_index::index::Index;
 use arrow_array::{BooleanArray, RecordBatch};
 use arrow_schema::ArrowError;
 
@@ -94,7 +95,49 @@ where
 /// leaves 99% of the rows, it may be better to not filter the data from parquet and
 /// apply the filter after the RecordBatch has been fully decoded.
 ///
-/// [`RowSelection`]: [super::selection::RowSelection]
+/// For example, to only decode the `name` column of the rows where `id > 1`:
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow::compute::kernels::comparison::gt_scalar;
+/// # use arrow_array::{cast::as_primitive_array, ArrayRef, Int32Array, StringArray};
+/// # use arrow_array::{types::Int32Type, RecordBatch};
+/// # use bytes::Bytes;
+/// # use parquet::arrow::arrow_reader::{
+/// #     ArrowPredicateFn, ParquetRecordBatchReaderBuilder, RowFilter,
+/// # };
+/// # use parquet::arrow::{ArrowWriter, ProjectionMask};
+/// #
+/// # let id = Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef;
+/// # let name = Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef;
+/// # let batch = RecordBatch::try_from_iter([("id", id), ("name", name)]).unwrap();
+/// # let mut file = Vec::new();
+/// # let mut writer = ArrowWriter::try_new(&mut file, batch.schema(), None).unwrap();
+/// # writer.write(&batch).unwrap();
+/// # writer.close().unwrap();
+/// # let file = Bytes::from(file);
+/// #
+/// let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+///
+/// // The predicate is only passed the `id` column
+/// let id = ProjectionMask::roots(builder.parquet_schema(), [0]);
+/// let predicate = ArrowPredicateFn::new(id, |batch: RecordBatch| {
+///     gt_scalar(as_primitive_array::<Int32Type>(batch.column(0)), 1)
+/// });
+///
+/// let name = ProjectionMask::roots(builder.parquet_schema(), [1]);
+/// let mut reader = builder
+///     .with_projection(name)
+///     .with_row_filter(RowFilter::new(vec![Box::new(predicate)]))
+///     .build()
+///     .unwrap();
+///
+/// let batch = reader.next().unwrap().unwrap();
+/// let expected = StringArray::from(vec!["b", "c"]);
+/// assert_eq!(batch.column(0).as_ref(), &expected);
+/// ```
+///
+/// [`RowSelection`]: super::RowSelection
 pub struct RowFilter {
     /// A list of [`ArrowPredicate`]
     pub(crate) predicates: Vec<Box<dyn ArrowPredicate>>,
@@ -106,3 +149,35 @@ impl RowFilter {
         Self { predicates }
     }
 }
+
+type PagePredicate = Box<dyn FnMut(&Index) -> Vec<bool> + Send>;
+
+/// A [`PageFilter`] uses the page index of a column to skip the pages whose
+/// statistics show they contain no rows of interest, avoiding their IO and decode
+///
+/// The predicate is called with the [`Index`] of the column for each row group
+/// to be read, and returns for each of its pages whether it may contain rows of
+/// interest. Row groups without a column index for the column are read in full.
+///
+/// Unlike a [`RowFilter`], this only skips whole pages, and so rows that do not
+/// satisfy the predicate may still be returned
+pub struct PageFilter {
+    /// The leaf column whose page index is passed to `predicate`
+    pub(crate) column: usize,
+    /// Returns whether each page of the column index may contain rows of interest
+    pub(crate) predicate: PagePredicate,
+}
+
+impl PageFilter {
+    /// Create a new [`PageFilter`] for the leaf column `column`, where `predicate`
+    /// returns `true` for each page that may contain rows of interest
+    pub fn new<F>(column: usize, predicate: F) -> Self
+    where
+        F: FnMut(&Index) -> Vec<bool> + Send + 'static,
+    {
+        Self {
+            column,
+            predicate: Box::new(predicate),
+        }
+    }
+}
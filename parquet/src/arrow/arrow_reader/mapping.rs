@@ -0,0 +1,230 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::arrow::ProjectionMask;
+use crate::errors::{ParquetError, Result};
+use crate::schema::types::SchemaDescriptor;
+use arrow_array::{new_null_array, RecordBatch, RecordBatchOptions};
+use arrow_cast::cast::{can_cast_types, cast};
+use arrow_schema::{ArrowError, DataType, Schema, SchemaRef};
+
+/// Maps the [`RecordBatch`] decoded from a parquet file to a target [`Schema`]
+/// provided with [`ArrowReaderBuilder::with_target_schema`]
+///
+/// [`ArrowReaderBuilder::with_target_schema`]: super::ArrowReaderBuilder::with_target_schema
+#[derive(Debug, Clone)]
+pub(crate) struct SchemaMapping {
+    schema: SchemaRef,
+    /// For each field of `schema`, the index of the corresponding column
+    /// of the decoded [`RecordBatch`], or `None` if it should be null
+    columns: Vec<Option<usize>>,
+}
+
+impl SchemaMapping {
+    /// Returns the subset of `projection` containing the root columns of `parquet_schema`
+    /// whose name matches a field of `target`
+    pub(crate) fn projection(
+        parquet_schema: &SchemaDescriptor,
+        target: &Schema,
+        projection: &ProjectionMask,
+    ) -> ProjectionMask {
+        let names = target.fields().iter().map(|f| f.name().as_str());
+        let mut mask = ProjectionMask::columns(parquet_schema, names);
+        mask.intersect(projection);
+        mask
+    }
+
+    /// Create a [`SchemaMapping`] from the `decoded` schema of the parquet reader to `target`
+    pub(crate) fn try_new(target: SchemaRef, decoded: &Schema) -> Result<Self> {
+        let columns = target
+            .fields()
+            .iter()
+            .map(|field| match decoded.index_of(field.name()) {
+                Ok(idx) => {
+                    let from = decoded.field(idx).data_type();
+                    let to = field.data_type();
+                    match from == to || can_widen(from, to) {
+                        true => Ok(Some(idx)),
+                        false => Err(arrow_err!(
+                            "Cannot read column \"{}\" of type {} as {}",
+                            field.name(),
+                            from,
+                            to
+                        )),
+                    }
+                }
+                Err(_) if field.is_nullable() => Ok(None),
+                Err(_) => Err(arrow_err!(
+                    "Non-nullable field \"{}\" not found in parquet file",
+                    field.name()
+                )),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            schema: target,
+            columns,
+        })
+    }
+
+    /// Returns the target [`SchemaRef`]
+    pub(crate) fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Converts a decoded [`RecordBatch`] to the target schema
+    pub(crate) fn map_batch(
+        &self,
+        batch: RecordBatch,
+    ) -> Result<RecordBatch, ArrowError> {
+        let num_rows = batch.num_rows();
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .zip(&self.columns)
+            .map(|(field, idx)| match idx {
+                Some(idx) => {
+                    let column = batch.column(*idx);
+                    match column.data_type() == field.data_type() {
+                        true => Ok(column.clone()),
+                        false => cast(column, field.data_type()),
+                    }
+                }
+                None => Ok(new_null_array(field.data_type(), num_rows)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let options = RecordBatchOptions::new().with_row_count(Some(num_rows));
+        RecordBatch::try_new_with_options(self.schema.clone(), columns, &options)
+    }
+}
+
+/// Returns true if values of type `from` can be losslessly converted to `to`
+fn can_widen(from: &DataType, to: &DataType) -> bool {
+    use DataType::*;
+    let widens = match (from, to) {
+        (Int8, Int16 | Int32 | Int64)
+        | (Int16, Int32 | Int64)
+        | (Int32, Int64)
+        | (UInt8, UInt16 | UInt32 | UInt64 | Int16 | Int32 | Int64)
+        | (UInt16, UInt32 | UInt64 | Int32 | Int64)
+        | (UInt32, UInt64 | Int64)
+        | (Float32, Float64)
+        | (Date32, Date64)
+        | (Utf8, LargeUtf8)
+        | (Binary, LargeBinary) => true,
+        (Decimal128(p1, s1), Decimal128(p2, s2) | Decimal256(p2, s2))
+        | (Decimal256(p1, s1), Decimal256(p2, s2)) => {
+            // Must not lose any integer or fractional digits
+            s2 >= s1 && *p2 as i16 - *s2 as i16 >= *p1 as i16 - *s1 as i16
+        }
+        (Dictionary(_, value), _) => value.as_ref() == to || can_widen(value, to),
+        _ => false,
+    };
+    widens && can_cast_types(from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Array, ArrayRef, Int32Array, StringArray};
+    use arrow_schema::Field;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_can_widen() {
+        assert!(can_widen(&DataType::Int32, &DataType::Int64));
+        assert!(can_widen(&DataType::UInt16, &DataType::Int32));
+        assert!(can_widen(&DataType::Float32, &DataType::Float64));
+        assert!(can_widen(&DataType::Utf8, &DataType::LargeUtf8));
+        assert!(can_widen(
+            &DataType::Decimal128(10, 2),
+            &DataType::Decimal128(12, 4)
+        ));
+        assert!(can_widen(
+            &DataType::Decimal128(10, 2),
+            &DataType::Decimal256(40, 2)
+        ));
+        assert!(can_widen(
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            &DataType::Utf8
+        ));
+
+        assert!(!can_widen(&DataType::Int64, &DataType::Int32));
+        assert!(!can_widen(&DataType::Int32, &DataType::UInt32));
+        assert!(!can_widen(&DataType::Utf8, &DataType::Binary));
+        assert!(!can_widen(
+            &DataType::Decimal128(10, 2),
+            &DataType::Decimal128(10, 4)
+        ));
+    }
+
+    #[test]
+    fn test_map_batch() {
+        let decoded = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let target = Arc::new(Schema::new(vec![
+            Field::new("b", DataType::LargeUtf8, true),
+            Field::new("c", DataType::Float64, true),
+            Field::new("a", DataType::Int64, true),
+        ]));
+
+        let mapping = SchemaMapping::try_new(target.clone(), &decoded).unwrap();
+
+        let batch = RecordBatch::try_new(
+            Arc::new(decoded),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+                Arc::new(StringArray::from(vec!["x", "y", "z"])),
+            ],
+        )
+        .unwrap();
+
+        let mapped = mapping.map_batch(batch).unwrap();
+        assert_eq!(mapped.schema(), target);
+        assert_eq!(mapped.num_rows(), 3);
+        assert_eq!(mapped.column(1).null_count(), 3);
+
+        let a = cast(mapped.column(2), &DataType::Int32).unwrap();
+        assert_eq!(
+            a.as_ref(),
+            &Int32Array::from(vec![Some(1), None, Some(3)]) as &dyn Array
+        );
+    }
+
+    #[test]
+    fn test_invalid_mapping() {
+        let decoded = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+
+        let target = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let err = SchemaMapping::try_new(Arc::new(target), &decoded).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow: Cannot read column \"a\" of type Int64 as Int32"
+        );
+
+        let target = Schema::new(vec![Field::new("b", DataType::Int64, false)]);
+        let err = SchemaMapping::try_new(Arc::new(target), &decoded).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow: Non-nullable field \"b\" not found in parquet file"
+        );
+    }
+}
@@ -18,11 +18,12 @@
 //! Contains reader which reads parquet data into arrow [`RecordBatch`]
 
 use std::collections::VecDeque;
+use std::ops::Range;
 use std::sync::Arc;
 
 use arrow_array::{Array, StructArray};
 use arrow_array::{RecordBatch, RecordBatchReader};
-use arrow_schema::{ArrowError, DataType as ArrowType, Schema, SchemaRef};
+use arrow_schema::{ArrowError, DataType as ArrowType, Schema, SchemaRef, TimeUnit};
 use arrow_select::filter::prep_null_mask_filter;
 
 use crate::arrow::array_reader::{
@@ -31,17 +32,27 @@ use crate::arrow::array_reader::{
 use crate::arrow::schema::{parquet_to_array_schema_and_fields, parquet_to_arrow_schema};
 use crate::arrow::schema::{parquet_to_arrow_schema_by_columns, ParquetField};
 use crate::arrow::ProjectionMask;
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::FileDecryptionProperties;
 use crate::errors::{ParquetError, Result};
 use crate::file::metadata::{KeyValue, ParquetMetaData};
+use crate::file::page_index::index::Index;
+#[cfg(doc)]
+use crate::file::properties::ReaderPropertiesBuilder;
+use crate::file::properties::{ReaderProperties, ReaderPropertiesPtr};
 use crate::file::reader::{ChunkReader, FileReader, SerializedFileReader};
-use crate::file::serialized_reader::ReadOptionsBuilder;
+use crate::file::serialized_reader::{read_metadata, ReadOptions, ReadOptionsBuilder};
 use crate::schema::types::SchemaDescriptor;
 
 mod filter;
+mod mapping;
 mod selection;
+mod statistics;
 
-pub use filter::{ArrowPredicate, ArrowPredicateFn, RowFilter};
+pub use filter::{ArrowPredicate, ArrowPredicateFn, PageFilter, RowFilter};
+pub(crate) use mapping::SchemaMapping;
 pub use selection::{RowSelection, RowSelector};
+pub use statistics::StatisticsConverter;
 
 /// A generic builder for constructing sync or async arrow parquet readers. This is not intended
 /// to be used directly, instead you should use the specialization for the type of reader
@@ -60,6 +71,10 @@ pub struct ArrowReaderBuilder<T> {
 
     pub(crate) fields: Option<ParquetField>,
 
+    pub(crate) int96_overflow: Int96Overflow,
+
+    pub(crate) max_page_size: Option<usize>,
+
     pub(crate) batch_size: usize,
 
     pub(crate) row_groups: Option<Vec<usize>>,
@@ -68,37 +83,33 @@ pub struct ArrowReaderBuilder<T> {
 
     pub(crate) filter: Option<RowFilter>,
 
+    pub(crate) page_filter: Option<PageFilter>,
+
     pub(crate) selection: Option<RowSelection>,
-}
 
-impl<T> ArrowReaderBuilder<T> {
-    pub(crate) fn new_builder(
-        input: T,
-        metadata: Arc<ParquetMetaData>,
-        options: ArrowReaderOptions,
-    ) -> Result<Self> {
-        let kv_metadata = match options.skip_arrow_metadata {
-            true => None,
-            false => metadata.file_metadata().key_value_metadata(),
-        };
+    pub(crate) row_range: Option<Range<usize>>,
 
-        let (schema, fields) = parquet_to_array_schema_and_fields(
-            metadata.file_metadata().schema_descr(),
-            ProjectionMask::all(),
-            kv_metadata,
-        )?;
+    pub(crate) target_schema: Option<SchemaRef>,
+}
 
-        Ok(Self {
+impl<T> ArrowReaderBuilder<T> {
+    pub(crate) fn new_builder(input: T, metadata: ArrowReaderMetadata) -> Self {
+        Self {
             input,
-            metadata,
-            schema: Arc::new(schema),
-            fields,
+            metadata: metadata.metadata,
+            schema: metadata.schema,
+            fields: metadata.fields,
+            int96_overflow: metadata.int96_overflow,
+            max_page_size: metadata.max_page_size,
             batch_size: 1024,
             row_groups: None,
             projection: ProjectionMask::all(),
             filter: None,
+            page_filter: None,
             selection: None,
-        })
+            row_range: None,
+            target_schema: None,
+        }
     }
 
     /// Returns a reference to the [`ParquetMetaData`] for this parquet file
@@ -116,6 +127,32 @@ impl<T> ArrowReaderBuilder<T> {
         &self.schema
     }
 
+    /// Returns the number of rows in the row group `row_group`, or `None` if there
+    /// is no such row group
+    pub fn row_group_num_rows(&self, row_group: usize) -> Option<usize> {
+        let row_groups = self.metadata.row_groups();
+        row_groups.get(row_group).map(|rg| rg.num_rows() as usize)
+    }
+
+    /// Returns the total number of rows in the row groups to read, that is those
+    /// provided to [`Self::with_row_groups`], or all row groups if not set
+    ///
+    /// This does not take into account any [`RowSelection`], row range or filter
+    pub fn num_rows(&self) -> usize {
+        match &self.row_groups {
+            Some(row_groups) => row_groups
+                .iter()
+                .filter_map(|idx| self.row_group_num_rows(*idx))
+                .sum(),
+            None => self
+                .metadata
+                .row_groups()
+                .iter()
+                .map(|rg| rg.num_rows() as usize)
+                .sum(),
+        }
+    }
+
     /// Set the size of [`RecordBatch`] to produce. Defaults to 1024
     /// If the batch_size more than the file row count, use the file row count.
     pub fn with_batch_size(self, batch_size: usize) -> Self {
@@ -158,6 +195,24 @@ impl<T> ArrowReaderBuilder<T> {
         }
     }
 
+    /// Only read the rows in `range`, a range of the rows of the row groups to read,
+    /// in the order they are read, see [`Self::num_rows`]
+    ///
+    /// This is translated into a selection of the row groups containing these rows,
+    /// and a [`RowSelection`] skipping the other rows of these row groups. Any
+    /// [`RowSelection`] provided by [`Self::with_row_selection`] should cover the row
+    /// groups to read prior to this, and is intersected with `range`
+    ///
+    /// An empty or reversed `range`, such as `10..5`, selects no rows
+    ///
+    /// Row filters are applied after the row range
+    pub fn with_row_range(self, range: Range<usize>) -> Self {
+        Self {
+            row_range: Some(range),
+            ..self
+        }
+    }
+
     /// Provide a [`RowFilter`] to skip decoding rows
     ///
     /// Row filters are applied after row group selection and row selection
@@ -167,6 +222,137 @@ impl<T> ArrowReaderBuilder<T> {
             ..self
         }
     }
+
+    /// Provide a [`PageFilter`] to skip reading pages based on the page index
+    ///
+    /// This requires the page index to have been loaded with
+    /// [`ArrowReaderOptions::with_page_index`], otherwise no pages are skipped.
+    ///
+    /// The pages selected are intersected with any [`RowSelection`], and page
+    /// filters are applied before any [`RowFilter`]
+    pub fn with_page_filter(self, filter: PageFilter) -> Self {
+        Self {
+            page_filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Provide a target [`Schema`] for the [`RecordBatch`] produced by the reader,
+    /// allowing files whose schema has evolved to be read with a consistent schema
+    ///
+    /// Fields of `schema` are matched to the root columns of the file by name:
+    ///
+    /// * Columns are returned in the order of `schema`, columns of the file not
+    ///   present in `schema` are not read
+    /// * Nullable fields not present in the file are filled with nulls
+    /// * Columns may be widened to a compatible type, e.g. `Int32` to `Int64`,
+    ///   or `Utf8` to `LargeUtf8`
+    ///
+    /// Any [`ProjectionMask`] is applied in addition to this, with fields whose
+    /// columns it excludes treated as not present in the file
+    ///
+    /// The reader will return an error if a non-nullable field is not present in
+    /// the file, or a column cannot be converted to the type of its field
+    pub fn with_target_schema(self, schema: SchemaRef) -> Self {
+        Self {
+            target_schema: Some(schema),
+            ..self
+        }
+    }
+
+    /// Returns the [`ProjectionMask`] of the columns to decode, taking into account
+    /// any target schema provided with [`Self::with_target_schema`]
+    pub(crate) fn build_projection(&self) -> ProjectionMask {
+        match &self.target_schema {
+            Some(target) => {
+                SchemaMapping::projection(self.parquet_schema(), target, &self.projection)
+            }
+            None => self.projection.clone(),
+        }
+    }
+
+    /// Returns the row groups of `row_groups` to read, and the [`RowSelection`] to
+    /// read from them, combining any [`RowSelection`] and row range with the pages
+    /// selected by any [`PageFilter`]
+    pub(crate) fn build_selection(
+        &mut self,
+        row_groups: Vec<usize>,
+    ) -> Result<(Vec<usize>, Option<RowSelection>)> {
+        let mut selection = self.selection.take();
+        if let Some(filter) = self.page_filter.as_mut() {
+            let pages = evaluate_page_filter(&self.metadata, &row_groups, filter)?;
+            selection = Some(match selection {
+                Some(selection) => selection.intersection(&pages),
+                None => pages,
+            });
+        }
+
+        match self.row_range.take() {
+            Some(range) => Ok(select_row_range(
+                &self.metadata,
+                row_groups,
+                range,
+                selection,
+            )),
+            None => Ok((row_groups, selection)),
+        }
+    }
+}
+
+/// Returns the row groups of `row_groups` containing the rows in `range`, and the
+/// [`RowSelection`] of these rows intersected with `selection`, if any
+fn select_row_range(
+    metadata: &ParquetMetaData,
+    row_groups: Vec<usize>,
+    range: Range<usize>,
+    mut selection: Option<RowSelection>,
+) -> (Vec<usize>, Option<RowSelection>) {
+    let mut selected = Vec::with_capacity(row_groups.len());
+    let mut selectors = vec![];
+    let mut start = 0;
+
+    for idx in row_groups {
+        let num_rows = metadata.row_group(idx).num_rows() as usize;
+        let end = start + num_rows;
+
+        // The selection of this row group, skipping any rows it doesn't cover
+        let row_group_selection = selection.as_mut().map(|s| {
+            let selection = s.split_off(num_rows);
+            let uncovered = num_rows - selection.row_count();
+            non_empty_selection(
+                selection
+                    .iter()
+                    .cloned()
+                    .chain([RowSelector::skip(uncovered)]),
+            )
+        });
+
+        if range.start < range.end && end > range.start && start < range.end {
+            let first = range.start.saturating_sub(start);
+            let last = range.end.min(end) - start;
+            let range_selection = non_empty_selection([
+                RowSelector::skip(first),
+                RowSelector::select(last - first),
+                RowSelector::skip(num_rows - last),
+            ]);
+
+            let row_group_selection = match row_group_selection {
+                Some(s) => s.intersection(&range_selection),
+                None => range_selection,
+            };
+            selectors.extend(row_group_selection.iter().cloned());
+            selected.push(idx);
+        }
+        start = end;
+    }
+
+    (selected, Some(RowSelection::from(selectors)))
+}
+
+/// Returns a [`RowSelection`] of the non-empty `selectors`
+fn non_empty_selection(selectors: impl IntoIterator<Item = RowSelector>) -> RowSelection {
+    let selectors: Vec<_> = selectors.into_iter().filter(|s| s.row_count != 0).collect();
+    selectors.into()
 }
 
 /// Arrow reader api.
@@ -214,6 +400,11 @@ pub trait ArrowReader {
 pub struct ArrowReaderOptions {
     skip_arrow_metadata: bool,
     pub(crate) page_index: bool,
+    int96_coercion: Option<Int96Coercion>,
+    int96_overflow: Int96Overflow,
+    max_page_size: Option<usize>,
+    #[cfg(feature = "encryption")]
+    pub(crate) file_decryption_properties: Option<FileDecryptionProperties>,
 }
 
 impl ArrowReaderOptions {
@@ -242,6 +433,191 @@ impl ArrowReaderOptions {
     pub fn with_page_index(self, page_index: bool) -> Self {
         Self { page_index, ..self }
     }
+
+    /// Set the arrow type that parquet INT96 timestamps are decoded to, defaults to
+    /// [`Int96Coercion::Timestamp`] with [`TimeUnit::Nanosecond`]
+    ///
+    /// INT96 timestamps can represent dates far outside the range of a nanosecond
+    /// timestamp, see [`Self::with_int96_overflow`] for how such values are handled
+    pub fn with_int96_coercion(self, coercion: Int96Coercion) -> Self {
+        Self {
+            int96_coercion: Some(coercion),
+            ..self
+        }
+    }
+
+    /// Set how INT96 timestamps that cannot be represented by the arrow type
+    /// selected with [`Self::with_int96_coercion`] are handled, defaults to
+    /// [`Int96Overflow::Wrap`]
+    pub fn with_int96_overflow(self, overflow: Int96Overflow) -> Self {
+        Self {
+            int96_overflow: overflow,
+            ..self
+        }
+    }
+
+    /// Set the maximum size in bytes of a page that will be read, returning an error
+    /// for larger pages instead of buffering them, see
+    /// [`ReaderPropertiesBuilder::set_max_page_size`]
+    pub fn with_max_page_size(self, max_page_size: usize) -> Self {
+        Self {
+            max_page_size: Some(max_page_size),
+            ..self
+        }
+    }
+
+    /// Provide the [`FileDecryptionProperties`] used to read an encrypted file
+    ///
+    /// This is only used by [`ParquetRecordBatchReaderBuilder`], for the async reader
+    /// the metadata of an encrypted file can be decoded with
+    /// [`decode_encrypted_metadata`](crate::file::footer::decode_encrypted_metadata)
+    /// by [`AsyncFileReader::get_metadata`](crate::arrow::async_reader::AsyncFileReader::get_metadata)
+    #[cfg(feature = "encryption")]
+    pub fn with_file_decryption_properties(
+        self,
+        file_decryption_properties: FileDecryptionProperties,
+    ) -> Self {
+        Self {
+            file_decryption_properties: Some(file_decryption_properties),
+            ..self
+        }
+    }
+
+    /// Returns the [`ReadOptions`] used to read the [`ParquetMetaData`] of a file
+    fn read_options(&self) -> ReadOptions {
+        let mut read_options = ReadOptionsBuilder::new();
+        if self.page_index {
+            read_options = read_options.with_page_index();
+        }
+        #[cfg(feature = "encryption")]
+        if let Some(properties) = &self.file_decryption_properties {
+            read_options =
+                read_options.with_file_decryption_properties(properties.clone());
+        }
+        read_options.build()
+    }
+}
+
+/// The arrow type parquet INT96 timestamps are decoded to, see
+/// [`ArrowReaderOptions::with_int96_coercion`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Int96Coercion {
+    /// Decode to a `Timestamp` with the provided [`TimeUnit`] and no timezone
+    Timestamp(TimeUnit),
+    /// Decode to a `Decimal128(38, 9)` of the seconds since the epoch, which can
+    /// represent every INT96 timestamp without loss
+    Decimal,
+}
+
+/// Returns the [`ReaderProperties`] used to read the pages of a file, with their size
+/// limited to `max_page_size` if set
+pub(crate) fn reader_properties(max_page_size: Option<usize>) -> ReaderPropertiesPtr {
+    let mut props = ReaderProperties::builder();
+    if let Some(max_page_size) = max_page_size {
+        props = props.set_max_page_size(max_page_size);
+    }
+    Arc::new(props.build())
+}
+
+impl Int96Coercion {
+    fn data_type(&self) -> ArrowType {
+        match self {
+            Self::Timestamp(unit) => ArrowType::Timestamp(unit.clone(), None),
+            Self::Decimal => ArrowType::Decimal128(38, 9),
+        }
+    }
+}
+
+/// How INT96 timestamps that cannot be represented by the arrow type they are
+/// decoded to are handled, see [`ArrowReaderOptions::with_int96_overflow`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Int96Overflow {
+    /// Wrap around on overflow
+    #[default]
+    Wrap,
+    /// Saturate to the minimum or maximum representable timestamp
+    Saturate,
+    /// Return an error
+    Error,
+}
+
+/// The [`ParquetMetaData`] of a parquet file, along with the arrow schema derived from it
+///
+/// This can be loaded once with [`ArrowReaderMetadata::load`], or
+/// [`ArrowReaderMetadata::load_async`] for the async reader, and then cheaply cloned
+/// to construct multiple readers of the same file without decoding its footer again,
+/// see [`ParquetRecordBatchReaderBuilder::new_with_metadata`]
+///
+/// [`ArrowReaderMetadata::load_async`]: crate::arrow::arrow_reader::ArrowReaderMetadata::load_async
+#[derive(Debug, Clone)]
+pub struct ArrowReaderMetadata {
+    pub(crate) metadata: Arc<ParquetMetaData>,
+
+    pub(crate) schema: SchemaRef,
+
+    pub(crate) fields: Option<ParquetField>,
+
+    pub(crate) int96_overflow: Int96Overflow,
+
+    pub(crate) max_page_size: Option<usize>,
+}
+
+impl ArrowReaderMetadata {
+    /// Loads [`ArrowReaderMetadata`] from the provided [`ChunkReader`]
+    ///
+    /// If `options` has page index enabled, this will also load the page index
+    pub fn load<T: ChunkReader>(reader: &T, options: ArrowReaderOptions) -> Result<Self> {
+        let metadata = read_metadata(reader, &mut options.read_options())?;
+        Self::try_new(Arc::new(metadata), options)
+    }
+
+    /// Creates [`ArrowReaderMetadata`] from already loaded [`ParquetMetaData`],
+    /// for example metadata cached in a catalog
+    ///
+    /// This will not load the page index, regardless of `options`
+    pub fn try_new(
+        metadata: Arc<ParquetMetaData>,
+        options: ArrowReaderOptions,
+    ) -> Result<Self> {
+        let kv_metadata = match options.skip_arrow_metadata {
+            true => None,
+            false => metadata.file_metadata().key_value_metadata(),
+        };
+
+        let int96_type = options
+            .int96_coercion
+            .as_ref()
+            .map(Int96Coercion::data_type);
+        let (schema, fields) = parquet_to_array_schema_and_fields(
+            metadata.file_metadata().schema_descr(),
+            ProjectionMask::all(),
+            kv_metadata,
+            int96_type.as_ref(),
+        )?;
+
+        Ok(Self {
+            metadata,
+            schema: Arc::new(schema),
+            fields,
+            int96_overflow: options.int96_overflow,
+            max_page_size: options.max_page_size,
+        })
+    }
+
+    /// Returns a reference to the [`ParquetMetaData`] for this parquet file
+    pub fn metadata(&self) -> &Arc<ParquetMetaData> {
+        &self.metadata
+    }
+
+    /// Returns the parquet [`SchemaDescriptor`] for this parquet file
+    pub fn parquet_schema(&self) -> &SchemaDescriptor {
+        self.metadata.file_metadata().schema_descr()
+    }
+
+    /// Returns the arrow [`SchemaRef`] for this parquet file
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
 }
 
 /// An `ArrowReader` that can be used to synchronously read parquet data as [`RecordBatch`]
@@ -289,11 +665,13 @@ impl ArrowReader for ParquetFileArrowReader {
             self.parquet_schema(),
             mask,
             self.get_kv_metadata(),
+            None,
         )?;
         let array_reader = build_array_reader(
             field.as_ref(),
             &ProjectionMask::all(),
             &self.file_reader,
+            Int96Overflow::default(),
         )?;
 
         // Try to avoid allocate large buffer
@@ -398,27 +776,76 @@ impl<T: ChunkReader + 'static> ArrowReaderBuilder<SyncReader<T>> {
 
     /// Create a new [`ParquetRecordBatchReaderBuilder`] with [`ArrowReaderOptions`]
     pub fn try_new_with_options(reader: T, options: ArrowReaderOptions) -> Result<Self> {
-        let reader = match options.page_index {
-            true => {
-                let read_options = ReadOptionsBuilder::new().with_page_index().build();
-                SerializedFileReader::new_with_options(reader, read_options)?
-            }
-            false => SerializedFileReader::new(reader)?,
-        };
+        let metadata = ArrowReaderMetadata::load(&reader, options)?;
+        Ok(Self::new_with_metadata(reader, metadata))
+    }
 
-        let metadata = Arc::clone(reader.metadata_ref());
-        Self::new_builder(SyncReader(reader), metadata, options)
+    /// Create a [`ParquetRecordBatchReaderBuilder`] from the provided [`ArrowReaderMetadata`]
+    ///
+    /// This allows loading the metadata once and using it to create multiple builders,
+    /// potentially with different settings, without decoding the footer again
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use bytes::Bytes;
+    /// # use arrow_array::{Int32Array, RecordBatch};
+    /// # use parquet::arrow::ArrowWriter;
+    /// # use parquet::arrow::arrow_reader::{
+    /// #     ArrowReaderMetadata, ParquetRecordBatchReaderBuilder,
+    /// # };
+    /// # let batch = RecordBatch::try_from_iter([
+    /// #     ("col", Arc::new(Int32Array::from(vec![1, 2, 3])) as _),
+    /// # ]).unwrap();
+    /// # let mut buffer = Vec::new();
+    /// # let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), None).unwrap();
+    /// # writer.write(&batch).unwrap();
+    /// # writer.close().unwrap();
+    /// let file = Bytes::from(buffer);
+    /// let metadata = ArrowReaderMetadata::load(&file, Default::default()).unwrap();
+    ///
+    /// let mut a = ParquetRecordBatchReaderBuilder::new_with_metadata(file.clone(), metadata.clone())
+    ///     .build()
+    ///     .unwrap();
+    /// let mut b = ParquetRecordBatchReaderBuilder::new_with_metadata(file, metadata)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(a.next().unwrap().unwrap(), b.next().unwrap().unwrap());
+    /// ```
+    pub fn new_with_metadata(input: T, metadata: ArrowReaderMetadata) -> Self {
+        let reader = SerializedFileReader::new_with_metadata(
+            input,
+            Arc::clone(&metadata.metadata),
+            reader_properties(metadata.max_page_size),
+        );
+        Self::new_builder(SyncReader(reader), metadata)
     }
 
     /// Build a [`ParquetRecordBatchReader`]
     ///
     /// Note: this will eagerly evaluate any `RowFilter` before returning
-    pub fn build(self) -> Result<ParquetRecordBatchReader> {
+    pub fn build(mut self) -> Result<ParquetRecordBatchReader> {
+        let num_row_groups = self.metadata.num_row_groups();
+        let projection = self.build_projection();
+        let row_groups = match self.row_groups.take() {
+            Some(row_groups) => {
+                if let Some(col) = row_groups.iter().find(|x| **x >= num_row_groups) {
+                    return Err(general_err!(
+                        "row group {} out of bounds 0..{}",
+                        col,
+                        num_row_groups
+                    ));
+                }
+                row_groups
+            }
+            None => (0..num_row_groups).collect(),
+        };
+        let (row_groups, mut selection) = self.build_selection(row_groups)?;
+
         let reader =
-            FileReaderRowGroupCollection::new(Arc::new(self.input.0), self.row_groups);
+            FileReaderRowGroupCollection::new(Arc::new(self.input.0), Some(row_groups));
 
         let mut filter = self.filter;
-        let mut selection = self.selection;
 
         // Try to avoid allocate large buffer
         let batch_size = self
@@ -434,6 +861,7 @@ impl<T: ChunkReader + 'static> ArrowReaderBuilder<SyncReader<T>> {
                     self.fields.as_ref(),
                     predicate.projection(),
                     &reader,
+                    self.int96_overflow,
                 )?;
 
                 selection = Some(evaluate_predicate(
@@ -445,19 +873,23 @@ impl<T: ChunkReader + 'static> ArrowReaderBuilder<SyncReader<T>> {
             }
         }
 
-        let array_reader =
-            build_array_reader(self.fields.as_ref(), &self.projection, &reader)?;
+        let array_reader = build_array_reader(
+            self.fields.as_ref(),
+            &projection,
+            &reader,
+            self.int96_overflow,
+        )?;
 
         // If selection is empty, truncate
         if !selects_any(selection.as_ref()) {
             selection = Some(RowSelection::from(vec![]));
         }
 
-        Ok(ParquetRecordBatchReader::new(
-            batch_size,
-            array_reader,
-            selection,
-        ))
+        let reader = ParquetRecordBatchReader::new(batch_size, array_reader, selection);
+        match self.target_schema {
+            Some(schema) => reader.with_target_schema(schema),
+            None => Ok(reader),
+        }
     }
 }
 
@@ -468,6 +900,7 @@ pub struct ParquetRecordBatchReader {
     array_reader: Box<dyn ArrayReader>,
     schema: SchemaRef,
     selection: Option<VecDeque<RowSelector>>,
+    mapping: Option<SchemaMapping>,
 }
 
 impl Iterator for ParquetRecordBatchReader {
@@ -540,7 +973,13 @@ impl Iterator for ParquetRecordBatchReader {
 
                 match struct_array {
                     Err(err) => Some(Err(err)),
-                    Ok(e) => (e.len() > 0).then(|| Ok(RecordBatch::from(e))),
+                    Ok(e) => (e.len() > 0).then(|| {
+                        let batch = RecordBatch::from(e);
+                        match &self.mapping {
+                            Some(mapping) => mapping.map_batch(batch),
+                            None => Ok(batch),
+                        }
+                    }),
                 }
             }
         }
@@ -584,8 +1023,21 @@ impl ParquetRecordBatchReader {
             array_reader,
             schema: Arc::new(schema),
             selection: selection.map(|s| s.trim().into()),
+            mapping: None,
         }
     }
+
+    /// Convert the [`RecordBatch`] produced by this reader to `schema`
+    ///
+    /// See [`ArrowReaderBuilder::with_target_schema`]
+    pub(crate) fn with_target_schema(self, schema: SchemaRef) -> Result<Self> {
+        let mapping = SchemaMapping::try_new(schema, &self.schema)?;
+        Ok(Self {
+            schema: mapping.schema().clone(),
+            mapping: Some(mapping),
+            ..self
+        })
+    }
 }
 
 /// Returns `true` if `selection` is `None` or selects some rows
@@ -622,6 +1074,65 @@ pub(crate) fn evaluate_predicate(
     })
 }
 
+/// Evaluates a [`PageFilter`] against the page index of `row_groups`, returning
+/// a [`RowSelection`] of the rows of the pages it selects
+pub(crate) fn evaluate_page_filter(
+    metadata: &ParquetMetaData,
+    row_groups: &[usize],
+    filter: &mut PageFilter,
+) -> Result<RowSelection> {
+    let num_columns = metadata.file_metadata().schema_descr().num_columns();
+    if filter.column >= num_columns {
+        return Err(general_err!(
+            "page filter column {} out of bounds 0..{}",
+            filter.column,
+            num_columns
+        ));
+    }
+
+    let mut selectors: Vec<RowSelector> = vec![];
+    let mut push = |row_count: usize, skip: bool| match selectors.last_mut() {
+        Some(last) if last.skip == skip => last.row_count += row_count,
+        _ => selectors.push(RowSelector { row_count, skip }),
+    };
+
+    for &row_group in row_groups {
+        let num_rows = metadata.row_group(row_group).num_rows() as usize;
+        let pages = metadata
+            .page_indexes()
+            .zip(metadata.offset_indexes())
+            .and_then(|(indexes, locations)| {
+                let index = indexes.get(row_group)?.get(filter.column)?;
+                let locations = locations.get(row_group)?.get(filter.column)?;
+                Some((index, locations))
+            })
+            .filter(|(index, _)| !matches!(index, Index::NONE));
+
+        let (index, locations) = match pages {
+            Some(pages) => pages,
+            None => {
+                push(num_rows, false);
+                continue;
+            }
+        };
+
+        let keep = (filter.predicate)(index);
+        if keep.len() != locations.len() {
+            return Err(general_err!(
+                "page filter returned {} values for {} pages",
+                keep.len(),
+                locations.len()
+            ));
+        }
+
+        let pages = RowSelection::from_page_locations(locations, num_rows, &keep)?;
+        for selector in pages.iter() {
+            push(selector.row_count, selector.skip);
+        }
+    }
+    Ok(selectors.into())
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::min;
@@ -637,14 +1148,16 @@ mod tests {
     use tempfile::tempfile;
 
     use arrow_array::builder::*;
+    use arrow_array::cast::as_list_array;
     use arrow_array::*;
     use arrow_array::{RecordBatch, RecordBatchReader};
     use arrow_buffer::Buffer;
     use arrow_data::ArrayDataBuilder;
-    use arrow_schema::{DataType as ArrowDataType, Field, Schema};
+    use arrow_schema::{DataType as ArrowDataType, Field, Schema, TimeUnit};
 
     use crate::arrow::arrow_reader::{
-        ArrowPredicateFn, ArrowReaderOptions, ParquetRecordBatchReader,
+        evaluate_page_filter, ArrowPredicateFn, ArrowReaderMetadata, ArrowReaderOptions,
+        Int96Coercion, Int96Overflow, PageFilter, ParquetRecordBatchReader,
         ParquetRecordBatchReaderBuilder, RowFilter, RowSelection, RowSelector,
     };
     use crate::arrow::schema::add_encoded_arrow_schema_to_metadata;
@@ -652,10 +1165,14 @@ mod tests {
     use crate::basic::{ConvertedType, Encoding, Repetition, Type as PhysicalType};
     use crate::data_type::{
         BoolType, ByteArray, ByteArrayType, DataType, FixedLenByteArray,
-        FixedLenByteArrayType, Int32Type, Int64Type, Int96Type,
+        FixedLenByteArrayType, Int32Type, Int64Type, Int96, Int96Type,
     };
     use crate::errors::Result;
+    use crate::file::metadata::ParquetMetaData;
+    use crate::file::page_index::index::Index;
     use crate::file::properties::{EnabledStatistics, WriterProperties, WriterVersion};
+    use crate::file::reader::FileReader;
+    use crate::file::serialized_reader::SerializedFileReader;
     use crate::file::writer::SerializedFileWriter;
     use crate::schema::parser::parse_message_type;
     use crate::schema::types::{Type, TypePtr};
@@ -1839,6 +2356,217 @@ mod tests {
         assert_eq!(batch.column(0).data().null_count(), 2);
     }
 
+    #[test]
+    fn test_int96_coercion() {
+        const EPOCH: u32 = 2_440_588;
+
+        let int96 = |day: u32, nanos: u64| {
+            let mut v = Int96::new();
+            v.set_data(nanos as u32, (nanos >> 32) as u32, day);
+            v
+        };
+
+        let values = [
+            int96(EPOCH, 1_500_000_000),
+            // Approximately the year 3000, which overflows a nanosecond timestamp
+            int96(EPOCH + 376_000, 0),
+            // Approximately the year 1420, which overflows a nanosecond timestamp
+            int96(EPOCH - 200_000, 0),
+        ];
+
+        let schema = Arc::new(
+            parse_message_type("message schema { OPTIONAL INT96 ts; }").unwrap(),
+        );
+        let mut buffer = Vec::new();
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(&mut buffer, schema, props).unwrap();
+        let mut row_group_writer = writer.next_row_group().unwrap();
+        let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+        column_writer
+            .typed::<Int96Type>()
+            .write_batch(&values, Some(&[1, 0, 1, 1]), None)
+            .unwrap();
+        column_writer.close().unwrap();
+        row_group_writer.close().unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        let read = |options: ArrowReaderOptions| {
+            ParquetRecordBatchReaderBuilder::try_new_with_options(data.clone(), options)
+                .unwrap()
+                .build()
+                .unwrap()
+                .next()
+                .unwrap()
+                .map(|batch| batch.column(0).clone())
+        };
+
+        // Defaults to wrapping nanoseconds
+        let array = read(ArrowReaderOptions::new()).unwrap();
+        let expected = TimestampNanosecondArray::from(vec![
+            Some(1_500_000_000),
+            None,
+            Some(values[1].to_nanos()),
+            Some(values[2].to_nanos()),
+        ]);
+        assert_eq!(array.as_ref(), &expected as &dyn Array);
+
+        let options =
+            ArrowReaderOptions::new().with_int96_overflow(Int96Overflow::Saturate);
+        let array = read(options).unwrap();
+        let expected = TimestampNanosecondArray::from(vec![
+            Some(1_500_000_000),
+            None,
+            Some(i64::MAX),
+            Some(i64::MIN),
+        ]);
+        assert_eq!(array.as_ref(), &expected as &dyn Array);
+
+        let options = ArrowReaderOptions::new().with_int96_overflow(Int96Overflow::Error);
+        let err = read(options).unwrap_err().to_string();
+        assert!(err.contains("overflows Nanosecond timestamp"), "{}", err);
+
+        let options = ArrowReaderOptions::new()
+            .with_int96_coercion(Int96Coercion::Timestamp(TimeUnit::Microsecond))
+            .with_int96_overflow(Int96Overflow::Error);
+        let array = read(options).unwrap();
+        let expected = TimestampMicrosecondArray::from(vec![
+            Some(1_500_000),
+            None,
+            Some(376_000 * 86_400 * 1_000_000),
+            Some(-200_000 * 86_400 * 1_000_000),
+        ]);
+        assert_eq!(array.as_ref(), &expected as &dyn Array);
+
+        let options =
+            ArrowReaderOptions::new().with_int96_coercion(Int96Coercion::Decimal);
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new_with_options(data.clone(), options)
+                .unwrap();
+        assert_eq!(
+            builder.schema().field(0).data_type(),
+            &ArrowDataType::Decimal128(38, 9)
+        );
+        let batch = builder.build().unwrap().next().unwrap().unwrap();
+        let expected = Decimal128Array::from(vec![
+            Some(1_500_000_000),
+            None,
+            Some(376_000 * 86_400 * 1_000_000_000),
+            Some(-200_000 * 86_400 * 1_000_000_000),
+        ])
+        .with_precision_and_scale(38, 9)
+        .unwrap();
+        assert_eq!(batch.column(0).as_ref(), &expected as &dyn Array);
+    }
+
+    #[test]
+    fn test_read_legacy_nested_lists() {
+        // Lists and maps nested within 2-level lists, as written by some legacy writers
+        let message_type = "message legacy {
+          OPTIONAL GROUP a (LIST) {
+            REPEATED GROUP array (LIST) {
+              REPEATED INT32 array;
+            }
+          }
+          OPTIONAL GROUP b (LIST) {
+            REPEATED GROUP map (MAP) {
+              REPEATED GROUP key_value {
+                REQUIRED INT32 key;
+                OPTIONAL INT32 value;
+              }
+            }
+          }
+        }";
+
+        let schema = Arc::new(parse_message_type(message_type).unwrap());
+        let mut buf = Vec::with_capacity(1024);
+        {
+            let writer_props = Arc::new(WriterProperties::builder().build());
+            let mut writer =
+                SerializedFileWriter::new(&mut buf, schema, writer_props).unwrap();
+            let mut row_group_writer = writer.next_row_group().unwrap();
+
+            // [[1, 2], [3]], null, [], [[]]
+            let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer
+                .typed::<Int32Type>()
+                .write_batch(
+                    &[1, 2, 3],
+                    Some(&[3, 3, 3, 0, 1, 2]),
+                    Some(&[0, 2, 1, 0, 0, 0]),
+                )
+                .unwrap();
+            column_writer.close().unwrap();
+
+            // [{1: 10}, {}], null, [], [{2: null}]
+            let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer
+                .typed::<Int32Type>()
+                .write_batch(&[1, 2], Some(&[3, 2, 0, 1, 3]), Some(&[0, 1, 0, 0, 0]))
+                .unwrap();
+            column_writer.close().unwrap();
+            let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer
+                .typed::<Int32Type>()
+                .write_batch(&[10], Some(&[4, 2, 0, 1, 3]), Some(&[0, 1, 0, 0, 0]))
+                .unwrap();
+            column_writer.close().unwrap();
+
+            row_group_writer.close().unwrap();
+            writer.close().unwrap();
+        }
+
+        let batch = ParquetRecordBatchReader::try_new(Bytes::from(buf), 1024)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.num_rows(), 4);
+
+        // The repeated LIST group is read as a nested list, not a struct
+        let a = as_list_array(batch.column(0));
+        assert_eq!(
+            a.data_type(),
+            &ArrowDataType::List(Box::new(Field::new(
+                "array",
+                ArrowDataType::List(Box::new(Field::new(
+                    "array",
+                    ArrowDataType::Int32,
+                    false
+                ))),
+                false
+            )))
+        );
+        assert!(a.is_null(1));
+        assert_eq!(a.value_length(2), 0);
+        let values = a.value(0);
+        let values = as_list_array(&values);
+        assert_eq!(
+            values.value(0).as_ref(),
+            &Int32Array::from_iter_values([1, 2])
+        );
+        assert_eq!(values.value(1).as_ref(), &Int32Array::from_iter_values([3]));
+        let values = a.value(3);
+        let values = as_list_array(&values);
+        assert_eq!(values.len(), 1);
+        assert_eq!(values.value_length(0), 0);
+
+        // The repeated MAP group is read as a list of maps
+        let b = as_list_array(batch.column(1));
+        assert!(matches!(b.value_type(), ArrowDataType::Map(_, false)));
+        assert!(b.is_null(1));
+        assert_eq!(b.value_length(2), 0);
+        assert_eq!(b.value_offsets(), &[0, 2, 2, 2, 3]);
+        let maps = b.values();
+        let maps = maps.as_any().downcast_ref::<MapArray>().unwrap();
+        assert_eq!(maps.value_offsets(), &[0, 1, 1, 2]);
+        assert_eq!(maps.keys().as_ref(), &Int32Array::from_iter_values([1, 2]));
+        assert_eq!(
+            maps.values().as_ref(),
+            &Int32Array::from(vec![Some(10), None])
+        );
+    }
+
     #[test]
     fn test_invalid_utf8() {
         // a parquet file with 1 column with invalid utf8
@@ -1965,6 +2693,239 @@ mod tests {
         assert_eq!(get_dict(&batches[4]), get_dict(&batches[5]));
     }
 
+    #[test]
+    fn test_primitive_dictionary_preservation() {
+        let mut fields = vec![Arc::new(
+            Type::primitive_type_builder("leaf", PhysicalType::INT64)
+                .with_repetition(Repetition::OPTIONAL)
+                .build()
+                .unwrap(),
+        )];
+
+        let schema = Arc::new(
+            Type::group_type_builder("test_schema")
+                .with_fields(&mut fields)
+                .build()
+                .unwrap(),
+        );
+
+        let dict_type = ArrowDataType::Dictionary(
+            Box::new(ArrowDataType::Int16),
+            Box::new(ArrowDataType::Int64),
+        );
+
+        let arrow_field = Field::new("leaf", dict_type.clone(), true);
+
+        let mut file = tempfile::tempfile().unwrap();
+
+        let values = vec![vec![10, 1, 2, 4], vec![3, 1, 2]];
+
+        let def_levels = vec![
+            vec![1, 0, 0, 1, 0, 0, 1, 1],
+            vec![0, 0, 1, 1, 0, 0, 1, 0, 0],
+        ];
+
+        let opts = TestOptions {
+            encoding: Encoding::RLE_DICTIONARY,
+            ..Default::default()
+        };
+
+        generate_single_column_file_with_data::<Int64Type>(
+            &values,
+            Some(&def_levels),
+            file.try_clone().unwrap(), // Cannot use &mut File (#1163)
+            schema,
+            Some(arrow_field),
+            &opts,
+        )
+        .unwrap();
+
+        file.rewind().unwrap();
+
+        let record_reader = ParquetRecordBatchReader::try_new(file, 3).unwrap();
+
+        let batches = record_reader
+            .collect::<Result<Vec<RecordBatch>, _>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 6);
+        assert!(batches
+            .iter()
+            .all(|x| x.column(0).data_type() == &dict_type));
+
+        let get_dict =
+            |batch: &RecordBatch| batch.column(0).data().child_data()[0].clone();
+
+        // First and second batch in same row group -> same dictionary
+        assert_eq!(get_dict(&batches[0]), get_dict(&batches[1]));
+        // Third batch spans row group -> computed dictionary
+        assert_ne!(get_dict(&batches[1]), get_dict(&batches[2]));
+        assert_ne!(get_dict(&batches[2]), get_dict(&batches[3]));
+        // Fourth, fifth and sixth from same row group -> same dictionary
+        assert_eq!(get_dict(&batches[3]), get_dict(&batches[4]));
+        assert_eq!(get_dict(&batches[4]), get_dict(&batches[5]));
+
+        let decoded: Vec<_> = batches
+            .iter()
+            .flat_map(|batch| {
+                let array =
+                    arrow_cast::cast(batch.column(0), &ArrowDataType::Int64).unwrap();
+                let array = array.as_any().downcast_ref::<Int64Array>().unwrap();
+                array.iter().collect::<Vec<_>>()
+            })
+            .collect();
+
+        let expected = vec![
+            Some(10),
+            None,
+            None,
+            Some(1),
+            None,
+            None,
+            Some(2),
+            Some(4),
+            None,
+            None,
+            Some(3),
+            Some(1),
+            None,
+            None,
+            Some(2),
+            None,
+            None,
+        ];
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_reader_metadata_reuse() {
+        let a = Arc::new(Int32Array::from_iter_values(0..1000)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..1000).map(|x| x.to_string()),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+        let props = WriterProperties::builder()
+            .set_data_pagesize_limit(256)
+            .set_write_batch_size(100)
+            .build();
+
+        let mut buffer = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        let metadata = ArrowReaderMetadata::load(&data, Default::default()).unwrap();
+        assert_eq!(metadata.schema(), &batch.schema());
+        assert_eq!(metadata.parquet_schema().num_columns(), 2);
+        assert!(metadata.metadata().page_indexes().is_none());
+
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        let with_index = ArrowReaderMetadata::load(&data, options).unwrap();
+        assert_eq!(with_index.metadata().page_indexes().unwrap()[0].len(), 2);
+
+        for metadata in [metadata.clone(), with_index] {
+            let mask = ProjectionMask::leaves(metadata.parquet_schema(), [1]);
+            let reader = ParquetRecordBatchReaderBuilder::new_with_metadata(
+                data.clone(),
+                metadata,
+            )
+            .with_projection(mask)
+            .with_batch_size(1000)
+            .build()
+            .unwrap();
+            let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+            assert_eq!(batches.len(), 1);
+            assert_eq!(batches[0].column(0), batch.column(1));
+        }
+
+        // Metadata from an external source, e.g. a catalog, can also be used
+        let metadata = ArrowReaderMetadata::try_new(
+            Arc::clone(metadata.metadata()),
+            ArrowReaderOptions::new().with_skip_arrow_metadata(true),
+        )
+        .unwrap();
+        let batches = ParquetRecordBatchReaderBuilder::new_with_metadata(data, metadata)
+            .with_batch_size(1000)
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches, vec![batch]);
+    }
+
+    #[test]
+    fn test_target_schema() {
+        let a = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef;
+        let b = Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef;
+        let c = Arc::new(Float32Array::from(vec![1., 2., 3.])) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b), ("c", c)]).unwrap();
+
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        // Reorder columns, skip "b", widen types and add a missing column "d"
+        let target = Arc::new(Schema::new(vec![
+            Field::new("c", ArrowDataType::Float64, false),
+            Field::new("d", ArrowDataType::Utf8, true),
+            Field::new("a", ArrowDataType::Int64, true),
+        ]));
+        let reader = ParquetRecordBatchReaderBuilder::try_new(data.clone())
+            .unwrap()
+            .with_target_schema(target.clone())
+            .build()
+            .unwrap();
+        assert_eq!(reader.schema(), target);
+
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+        let expected = RecordBatch::try_new(
+            target.clone(),
+            vec![
+                Arc::new(Float64Array::from(vec![1., 2., 3.])),
+                Arc::new(StringArray::from(vec![None::<&str>; 3])),
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])),
+            ],
+        )
+        .unwrap();
+        assert_eq!(batches[0], expected);
+
+        // A projection excluding "a" results in nulls
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data.clone()).unwrap();
+        let mask = ProjectionMask::roots(builder.parquet_schema(), [1, 2]);
+        let batches = builder
+            .with_projection(mask)
+            .with_target_schema(target)
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches[0].column(0).as_ref(), expected.column(0).as_ref());
+        assert_eq!(batches[0].column(2).null_count(), 3);
+
+        // Narrowing a column is an error
+        let target = Arc::new(Schema::new(vec![Field::new(
+            "c",
+            ArrowDataType::Float16,
+            false,
+        )]));
+        let result = ParquetRecordBatchReaderBuilder::try_new(data)
+            .unwrap()
+            .with_target_schema(target)
+            .build();
+        match result {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Arrow: Cannot read column \"c\" of type Float32 as Float16"
+            ),
+            Ok(_) => panic!("expected error"),
+        }
+    }
+
     #[test]
     fn test_read_null_list() {
         let testdata = arrow::util::test_util::parquet_test_data();
@@ -2553,4 +3514,289 @@ mod tests {
 
         assert_eq!(&written.slice(0, 8), &read[0]);
     }
+
+    #[test]
+    fn test_page_filter() {
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_data_page_row_count_limit(10)
+            .set_write_batch_size(10)
+            .build();
+        let mut buffer = Vec::with_capacity(1024);
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        // Selects the pages that may contain `a >= 75`
+        let filter = || {
+            PageFilter::new(0, |index| match index {
+                Index::INT32(index) => index
+                    .indexes
+                    .iter()
+                    .map(|page| page.max().map(|max| *max >= 75).unwrap_or(false))
+                    .collect(),
+                _ => unreachable!(),
+            })
+        };
+        let read = |builder: ParquetRecordBatchReaderBuilder<Bytes>| {
+            let batches = builder
+                .build()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            arrow_select::concat::concat_batches(&batch.schema(), &batches).unwrap()
+        };
+
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        let builder = ParquetRecordBatchReaderBuilder::try_new_with_options(
+            data.clone(),
+            options.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            read(builder.with_page_filter(filter())),
+            batch.slice(70, 30)
+        );
+
+        // Intersected with the row selection
+        let builder = ParquetRecordBatchReaderBuilder::try_new_with_options(
+            data.clone(),
+            options.clone(),
+        )
+        .unwrap()
+        .with_row_selection(RowSelection::from(vec![
+            RowSelector::select(75),
+            RowSelector::skip(25),
+        ]))
+        .with_page_filter(filter());
+        assert_eq!(read(builder), batch.slice(70, 5));
+
+        // Without the page index no pages are skipped
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data.clone()).unwrap();
+        assert_eq!(read(builder.with_page_filter(filter())), batch);
+
+        // Nor if the file has no page index, which is read as an empty offset
+        // index of each row group
+        let reader = SerializedFileReader::new(data.clone()).unwrap();
+        let metadata = reader.metadata();
+        let metadata = ParquetMetaData::new_with_page_index(
+            metadata.file_metadata().clone(),
+            metadata.row_groups().to_vec(),
+            Some(vec![vec![Index::NONE; 2]]),
+            Some(vec![vec![]]),
+        );
+        let selection = evaluate_page_filter(&metadata, &[0], &mut filter()).unwrap();
+        assert_eq!(
+            selection,
+            RowSelection::from(vec![RowSelector::select(100)])
+        );
+
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new_with_options(data, options)
+                .unwrap()
+                .with_page_filter(PageFilter::new(0, |_| vec![true]));
+        let err = match builder.build() {
+            Err(e) => e.to_string(),
+            Ok(_) => panic!("expected error"),
+        };
+        assert!(
+            err.contains("page filter returned 1 values for 10 pages"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_row_range() {
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(30)
+            .build();
+        let mut buffer = Vec::with_capacity(1024);
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data.clone()).unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 4);
+        assert_eq!(builder.row_group_num_rows(0), Some(30));
+        assert_eq!(builder.row_group_num_rows(3), Some(10));
+        assert_eq!(builder.row_group_num_rows(4), None);
+        assert_eq!(builder.num_rows(), 100);
+        assert_eq!(builder.with_row_groups(vec![1, 3]).num_rows(), 40);
+
+        let read = |builder: ParquetRecordBatchReaderBuilder<Bytes>| {
+            let batches = builder
+                .build()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            arrow_select::concat::concat_batches(&batch.schema(), &batches).unwrap()
+        };
+        let builder = || ParquetRecordBatchReaderBuilder::try_new(data.clone()).unwrap();
+
+        // Spanning row groups
+        assert_eq!(read(builder().with_row_range(25..65)), batch.slice(25, 40));
+        // Within a single row group
+        assert_eq!(read(builder().with_row_range(62..68)), batch.slice(62, 6));
+        // Exactly a row group
+        assert_eq!(read(builder().with_row_range(30..60)), batch.slice(30, 30));
+        // Beyond the end of the file
+        assert_eq!(read(builder().with_row_range(95..200)), batch.slice(95, 5));
+        assert_eq!(read(builder().with_row_range(100..200)).num_rows(), 0);
+        // Empty and reversed ranges
+        assert_eq!(read(builder().with_row_range(40..40)).num_rows(), 0);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = builder().with_row_range(40..25);
+        assert_eq!(read(reversed).num_rows(), 0);
+
+        // Relative to the row groups read
+        let expected = arrow_select::concat::concat_batches(
+            &batch.schema(),
+            &[batch.slice(35, 25), batch.slice(90, 5)],
+        )
+        .unwrap();
+        let row_groups = builder().with_row_groups(vec![1, 3]).with_row_range(5..35);
+        assert_eq!(read(row_groups), expected);
+
+        // Intersected with the row selection
+        let selection = builder()
+            .with_row_selection(RowSelection::from(vec![
+                RowSelector::skip(40),
+                RowSelector::select(10),
+                RowSelector::skip(10),
+                RowSelector::select(40),
+            ]))
+            .with_row_range(20..70);
+        let expected = arrow_select::concat::concat_batches(
+            &batch.schema(),
+            &[batch.slice(40, 10), batch.slice(60, 10)],
+        )
+        .unwrap();
+        assert_eq!(read(selection), expected);
+    }
+
+    #[test]
+    fn test_max_page_size() {
+        let a = Arc::new(Int64Array::from_iter_values(0..1000)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_dictionary_enabled(false)
+            .build();
+        let mut buffer = Vec::with_capacity(1024);
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        let read = |max_page_size: usize| {
+            let options = ArrowReaderOptions::new().with_max_page_size(max_page_size);
+            ParquetRecordBatchReaderBuilder::try_new_with_options(data.clone(), options)
+                .unwrap()
+                .build()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let batches = read(8000).unwrap();
+        assert_eq!(batches, vec![batch]);
+
+        let err = read(7999).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet argument error: Parquet error: Page size of 8000 bytes exceeds the maximum page size of 7999 bytes"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "encryption")]
+    fn test_read_encrypted() {
+        use crate::encryption::decrypt::tests::encrypt_file;
+        use crate::encryption::decrypt::FileDecryptionProperties;
+        use crate::schema::types::ColumnPath;
+
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i % 7)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(50)
+            .set_data_page_row_count_limit(10)
+            .set_write_batch_size(10)
+            .build();
+        let mut buffer = Vec::with_capacity(1024);
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let footer_key = b"0123456789012345".as_slice();
+        let column_key = b"1234567890123450".as_slice();
+        let properties = |column_key: &[u8]| {
+            FileDecryptionProperties::builder(footer_key.to_vec())
+                .with_column_key(ColumnPath::from("a"), column_key.to_vec())
+                .build()
+                .unwrap()
+        };
+        let read = |data: &Bytes, properties: FileDecryptionProperties| {
+            let options =
+                ArrowReaderOptions::new().with_file_decryption_properties(properties);
+            let batches = ParquetRecordBatchReaderBuilder::try_new_with_options(
+                data.clone(),
+                options,
+            )?
+            .with_row_selection(RowSelection::from(vec![
+                RowSelector::skip(25),
+                RowSelector::select(75),
+            ]))
+            .build()?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok::<_, crate::errors::ParquetError>(
+                arrow_select::concat::concat_batches(&batch.schema(), &batches).unwrap(),
+            )
+        };
+
+        for (encrypted_footer, ctr) in
+            [(true, false), (true, true), (false, false), (false, true)]
+        {
+            let data = Bytes::from(encrypt_file(
+                &buffer,
+                footer_key,
+                &[("a", column_key)],
+                encrypted_footer,
+                ctr,
+            ));
+
+            let read_batch = read(&data, properties(column_key)).unwrap();
+            assert_eq!(read_batch, batch.slice(25, 75));
+
+            let err = read(&data, properties(footer_key)).unwrap_err().to_string();
+            assert!(err.contains("Failed to decrypt"), "{}", err);
+
+            let err = match ParquetRecordBatchReaderBuilder::try_new(data) {
+                Ok(builder) => builder.build().unwrap().next().unwrap().unwrap_err(),
+                Err(e) => e.into(),
+            };
+            let expected = match encrypted_footer {
+                true => "Parquet file has an encrypted footer",
+                false => "Column a is encrypted",
+            };
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
+    }
 }
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::errors::{ParquetError, Result};
+use crate::format::PageLocation;
 use arrow_array::{Array, BooleanArray};
 use arrow_select::filter::SlicesIterator;
 use std::cmp::Ordering;
@@ -141,6 +143,61 @@ impl RowSelection {
         Self { selectors }
     }
 
+    /// Creates a [`RowSelection`] from the [`PageLocation`] of the data pages of a
+    /// column chunk containing `total_rows` rows, selecting the rows of the pages
+    /// for which `keep` is `true`
+    ///
+    /// This can be used to derive a [`RowSelection`] from the page index of a column,
+    /// for example by evaluating a predicate against the statistics of each page
+    ///
+    /// Returns an error if `keep` does not contain a value for each page, or if the
+    /// `first_row_index` of the pages are not increasing within `0..=total_rows`
+    pub fn from_page_locations(
+        locations: &[PageLocation],
+        total_rows: usize,
+        keep: &[bool],
+    ) -> Result<Self> {
+        if locations.len() != keep.len() {
+            return Err(general_err!(
+                "expected a value for each of {} pages, got {}",
+                locations.len(),
+                keep.len()
+            ));
+        }
+
+        let mut first_rows = Vec::with_capacity(locations.len());
+        for location in locations {
+            let first_row = usize::try_from(location.first_row_index)
+                .ok()
+                .filter(|first_row| *first_row <= total_rows)
+                .ok_or_else(|| {
+                    general_err!(
+                        "page first row index {} out of bounds 0..={}",
+                        location.first_row_index,
+                        total_rows
+                    )
+                })?;
+            if first_rows.last().map_or(false, |last| first_row < *last) {
+                return Err(general_err!(
+                    "page first row index {} is not increasing",
+                    first_row
+                ));
+            }
+            first_rows.push(first_row);
+        }
+
+        let ranges = first_rows
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| keep[*idx])
+            .map(|(idx, first_row)| {
+                let end = first_rows.get(idx + 1).copied().unwrap_or(total_rows);
+                *first_row..end
+            });
+
+        Ok(Self::from_consecutive_ranges(ranges, total_rows))
+    }
+
     /// Creates a [`RowSelection`] from a slice of uncombined `RowSelector`:
     /// Like [skip(5),skip(5),read(10)].
     /// After combine will return [skip(10),read(10)]
@@ -177,7 +234,7 @@ impl RowSelection {
     #[cfg(any(test, feature = "async"))]
     pub(crate) fn scan_ranges(
         &self,
-        page_locations: &[crate::format::PageLocation],
+        page_locations: &[PageLocation],
     ) -> Vec<Range<usize>> {
         let mut ranges = vec![];
         let mut row_offset = 0;
@@ -358,11 +415,38 @@ impl RowSelection {
         }
     }
 
+    /// Compute the union of two [`RowSelection`]
+    /// For example:
+    /// self:      NNYYYYNNYYNYN
+    /// other:     NYNNNNNNN
+    ///
+    /// returned:  NYYYYYNNYYNYN
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_selectors_and_combine(&union_row_selections(
+            &self.selectors,
+            &other.selectors,
+        ))
+    }
+
     /// Returns `true` if this [`RowSelection`] selects any rows
     pub fn selects_any(&self) -> bool {
         self.selectors.iter().any(|x| !x.skip)
     }
 
+    /// Returns the number of rows, selected or skipped, covered by this [`RowSelection`]
+    pub fn row_count(&self) -> usize {
+        self.selectors.iter().map(|x| x.row_count).sum()
+    }
+
+    /// Returns the number of rows selected by this [`RowSelection`]
+    pub fn selected_row_count(&self) -> usize {
+        self.selectors
+            .iter()
+            .filter(|x| !x.skip)
+            .map(|x| x.row_count)
+            .sum()
+    }
+
     /// Trims this [`RowSelection`] removing any trailing skips
     pub(crate) fn trim(mut self) -> Self {
         while self.selectors.last().map(|x| x.skip).unwrap_or(false) {
@@ -456,6 +540,37 @@ fn intersect_row_selections(
     res
 }
 
+/// Combine two lists of `RowSelection` return the union of them
+/// For example:
+/// self:      NNYYYYNNYYNYN
+/// other:     NYNNNNNNN
+///
+/// returned:  NYYYYYNNYYNYN
+fn union_row_selections(left: &[RowSelector], right: &[RowSelector]) -> Vec<RowSelector> {
+    let mut res = Vec::with_capacity(left.len() + right.len());
+    let mut l_iter = left.iter().copied().peekable();
+    let mut r_iter = right.iter().copied().peekable();
+
+    while let (Some(a), Some(b)) = (l_iter.peek_mut(), r_iter.peek_mut()) {
+        if a.row_count == 0 {
+            l_iter.next().unwrap();
+            continue;
+        }
+        if b.row_count == 0 {
+            r_iter.next().unwrap();
+            continue;
+        }
+        // Rows are only skipped if skipped by both
+        let row_count = a.row_count.min(b.row_count);
+        add_selector(a.skip && b.skip, row_count, &mut res);
+        a.row_count -= row_count;
+        b.row_count -= row_count;
+    }
+
+    res.extend(l_iter.chain(r_iter).filter(|x| x.row_count != 0));
+    res
+}
+
 fn add_selector(skip: bool, sum_row: usize, combined_result: &mut Vec<RowSelector>) {
     let selector = if skip {
         RowSelector::skip(sum_row)
@@ -468,7 +583,6 @@ fn add_selector(skip: bool, sum_row: usize, combined_result: &mut Vec<RowSelecto
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::format::PageLocation;
     use rand::{thread_rng, Rng};
 
     #[test]
@@ -824,6 +938,154 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_union() {
+        let a = RowSelection::from(vec![
+            RowSelector::skip(2),
+            RowSelector::select(4),
+            RowSelector::skip(2),
+            RowSelector::select(2),
+            RowSelector::skip(1),
+            RowSelector::select(1),
+            RowSelector::skip(1),
+        ]);
+        let b = RowSelection::from(vec![
+            RowSelector::skip(1),
+            RowSelector::select(1),
+            RowSelector::skip(11),
+        ]);
+
+        let expected = vec![
+            RowSelector::skip(1),
+            RowSelector::select(5),
+            RowSelector::skip(2),
+            RowSelector::select(2),
+            RowSelector::skip(1),
+            RowSelector::select(1),
+            RowSelector::skip(1),
+        ];
+        assert_eq!(a.union(&b).selectors, expected);
+        assert_eq!(b.union(&a).selectors, expected);
+        assert_eq!(a.union(&b).row_count(), 13);
+        assert_eq!(a.union(&b).selected_row_count(), 8);
+
+        // The remainder of the longer selection is preserved
+        let c = RowSelection::from(vec![RowSelector::skip(3)]);
+        assert_eq!(
+            a.union(&c).selectors,
+            RowSelection::from(Vec::from(a.clone())).selectors
+        );
+        assert_eq!(a.union(&RowSelection::default()), a);
+    }
+
+    #[test]
+    fn test_union_fuzz() {
+        let mut rand = thread_rng();
+        for _ in 0..100 {
+            let len = rand.gen_range(10..100);
+            let a_bools: Vec<_> = (0..len).map(|_| rand.gen_bool(0.2)).collect();
+            let b_bools: Vec<_> = (0..len).map(|_| rand.gen_bool(0.2)).collect();
+
+            let a = RowSelection::from_filters(&[BooleanArray::from(a_bools.clone())]);
+            let b = RowSelection::from_filters(&[BooleanArray::from(b_bools.clone())]);
+
+            let expected_bools: Vec<_> = a_bools
+                .iter()
+                .zip(&b_bools)
+                .map(|(a, b)| *a || *b)
+                .collect();
+            let expected =
+                RowSelection::from_filters(&[BooleanArray::from(expected_bools)]);
+
+            assert_eq!(a.union(&b), expected);
+            assert_eq!(a.union(&b).row_count(), len);
+        }
+    }
+
+    #[test]
+    fn test_from_page_locations() {
+        let locations: Vec<_> = [0, 10, 15, 30]
+            .into_iter()
+            .map(|first_row_index| PageLocation {
+                offset: first_row_index * 8,
+                compressed_page_size: 8,
+                first_row_index,
+            })
+            .collect();
+
+        let selection = RowSelection::from_page_locations(
+            &locations,
+            40,
+            &[false, true, true, false],
+        )
+        .unwrap();
+        assert_eq!(
+            selection.selectors,
+            vec![
+                RowSelector::skip(10),
+                RowSelector::select(20),
+                RowSelector::skip(10)
+            ]
+        );
+        assert_eq!(selection.row_count(), 40);
+        assert_eq!(selection.selected_row_count(), 20);
+
+        let selection = RowSelection::from_page_locations(
+            &locations,
+            40,
+            &[true, false, false, true],
+        )
+        .unwrap();
+        assert_eq!(
+            selection.selectors,
+            vec![
+                RowSelector::select(10),
+                RowSelector::skip(20),
+                RowSelector::select(10)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_page_locations_invalid() {
+        let location = |first_row_index| PageLocation {
+            offset: first_row_index * 8,
+            compressed_page_size: 8,
+            first_row_index,
+        };
+        let err = |locations: &[PageLocation], keep: &[bool]| {
+            RowSelection::from_page_locations(locations, 10, keep)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let locations = vec![location(0)];
+        assert_eq!(
+            err(&locations, &[true, false]),
+            "Parquet error: expected a value for each of 1 pages, got 2"
+        );
+
+        // Unsorted
+        let locations = vec![location(0), location(6), location(4)];
+        assert_eq!(
+            err(&locations, &[true, true, false]),
+            "Parquet error: page first row index 4 is not increasing"
+        );
+
+        // Beyond the rows of the column chunk
+        let locations = vec![location(0), location(12)];
+        assert_eq!(
+            err(&locations, &[true, true]),
+            "Parquet error: page first row index 12 out of bounds 0..=10"
+        );
+
+        let locations = vec![location(-1)];
+        assert_eq!(
+            err(&locations, &[true]),
+            "Parquet error: page first row index -1 out of bounds 0..=10"
+        );
+    }
+
     #[test]
     fn test_iter() {
         // use the iter() API to show it does what is expected and
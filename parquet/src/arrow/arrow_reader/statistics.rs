@@ -0,0 +1,667 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Contains [`StatisticsConverter`] for converting the statistics of parquet
+//! column chunks to arrow arrays
+
+use std::sync::Arc;
+
+use arrow_array::types::{
+    Time32MillisecondType, Time32SecondType, Time64MicrosecondType, Time64NanosecondType,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType,
+};
+use arrow_array::{
+    new_null_array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Date64Array,
+    Decimal128Array, Decimal256Array, FixedSizeBinaryArray, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, Int8Array, LargeBinaryArray, LargeStringArray,
+    PrimitiveArray, StringArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+};
+use arrow_buffer::i256;
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+
+use crate::arrow::buffer::bit_util::sign_extend_be;
+use crate::basic::{ColumnOrder, SortOrder};
+use crate::data_type::private::ParquetValueType;
+use crate::data_type::AsBytes;
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::RowGroupMetaData;
+use crate::file::statistics::{Statistics, ValueStatistics};
+use crate::schema::types::{ColumnDescPtr, SchemaDescriptor};
+
+/// Converts the statistics of the column chunks of a column to arrow arrays, with
+/// an element for each row group, of the type of the column in an arrow schema.
+///
+/// Statistics are stored in parquet as values of the physical type of a column,
+/// which must be interpreted according to its logical type. This reinterprets the
+/// min and max of each column chunk as the arrow reader would its values, e.g. as
+/// timestamps with the timezone of the arrow column, or as decimals sign-extended
+/// from the bytes of a `FIXED_LEN_BYTE_ARRAY`.
+///
+/// Elements are null for column chunks without statistics, for min and max
+/// statistics that are not ordered by the sort order of the column, such as
+/// deprecated min and max of unsigned columns, and for arrow types, e.g. nested
+/// types, for which statistics are not supported. The statistics of dictionary
+/// columns are converted to arrays of their value type.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::fs::File;
+/// # use parquet::arrow::arrow_reader::{ParquetRecordBatchReaderBuilder, StatisticsConverter};
+/// let file = File::open("data.parquet").unwrap();
+/// let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+///
+/// let converter = StatisticsConverter::try_new(
+///     "timestamp",
+///     builder.schema(),
+///     builder.parquet_schema(),
+/// )
+/// .unwrap();
+///
+/// let row_groups = builder.metadata().row_groups();
+/// let mins = converter.row_group_mins(row_groups).unwrap();
+/// let maxes = converter.row_group_maxes(row_groups).unwrap();
+/// let null_counts = converter.row_group_null_counts(row_groups).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct StatisticsConverter<'a> {
+    /// The parquet column of the arrow column, if it is a primitive column
+    parquet_column: Option<ColumnDescPtr>,
+    /// The arrow column
+    arrow_field: &'a Field,
+}
+
+impl<'a> StatisticsConverter<'a> {
+    /// Creates a new [`StatisticsConverter`] for the top-level column named
+    /// `column_name` of `arrow_schema`, read from `parquet_schema`
+    pub fn try_new(
+        column_name: &str,
+        arrow_schema: &'a Schema,
+        parquet_schema: &SchemaDescriptor,
+    ) -> Result<Self> {
+        let arrow_field = arrow_schema
+            .fields()
+            .iter()
+            .find(|f| f.name() == column_name)
+            .ok_or_else(|| {
+                arrow_err!("Column {} not found in arrow schema", column_name)
+            })?;
+
+        let parquet_column = parquet_schema
+            .columns()
+            .iter()
+            .find(|c| c.path().parts() == [column_name])
+            .cloned();
+
+        Ok(Self {
+            parquet_column,
+            arrow_field,
+        })
+    }
+
+    /// Returns the arrow column whose statistics are converted
+    pub fn arrow_field(&self) -> &Field {
+        self.arrow_field
+    }
+
+    /// Returns the min of the column in each of `metadatas`
+    pub fn row_group_mins<'b, I>(&self, metadatas: I) -> Result<ArrayRef>
+    where
+        I: IntoIterator<Item = &'b RowGroupMetaData>,
+    {
+        self.convert(metadatas, true)
+    }
+
+    /// Returns the max of the column in each of `metadatas`
+    pub fn row_group_maxes<'b, I>(&self, metadatas: I) -> Result<ArrayRef>
+    where
+        I: IntoIterator<Item = &'b RowGroupMetaData>,
+    {
+        self.convert(metadatas, false)
+    }
+
+    /// Returns the null count of the column in each of `metadatas`
+    pub fn row_group_null_counts<'b, I>(&self, metadatas: I) -> Result<UInt64Array>
+    where
+        I: IntoIterator<Item = &'b RowGroupMetaData>,
+    {
+        let statistics = self.statistics(metadatas)?;
+        Ok(statistics
+            .iter()
+            .map(|s| s.map(|s| s.null_count()))
+            .collect())
+    }
+
+    /// Returns the statistics of the column in each of `metadatas`
+    fn statistics<'b, I>(&self, metadatas: I) -> Result<Vec<Option<&'b Statistics>>>
+    where
+        I: IntoIterator<Item = &'b RowGroupMetaData>,
+    {
+        let metadatas = metadatas.into_iter();
+        let column = match &self.parquet_column {
+            Some(column) => column,
+            None => return Ok(metadatas.map(|_| None).collect()),
+        };
+        metadatas
+            .map(|metadata| {
+                let chunk = metadata
+                    .columns()
+                    .iter()
+                    .find(|c| c.column_path() == column.path())
+                    .ok_or_else(|| {
+                        general_err!("Column {} not found in row group", column.path())
+                    })?;
+                Ok(chunk.statistics())
+            })
+            .collect()
+    }
+
+    /// Returns the min, if `min`, or max of the column in each of `metadatas`
+    fn convert<'b, I>(&self, metadatas: I, min: bool) -> Result<ArrayRef>
+    where
+        I: IntoIterator<Item = &'b RowGroupMetaData>,
+    {
+        let statistics = self.statistics(metadatas)?;
+        let column = match &self.parquet_column {
+            Some(column) => column,
+            None => {
+                return Ok(new_null_array(
+                    self.arrow_field.data_type(),
+                    statistics.len(),
+                ))
+            }
+        };
+
+        let sort_order = ColumnOrder::get_sort_order(
+            column.logical_type(),
+            column.converted_type(),
+            column.physical_type(),
+        );
+        let statistics: Vec<_> = statistics
+            .into_iter()
+            .map(|s| s.filter(|s| is_ordered(s, sort_order)))
+            .collect();
+
+        convert(self.arrow_field.data_type(), &statistics, min)
+    }
+}
+
+/// Returns `true` if the min and max of `statistics` are set and ordered by
+/// `sort_order`
+fn is_ordered(statistics: &Statistics, sort_order: SortOrder) -> bool {
+    match sort_order {
+        _ if !statistics.has_min_max_set() => false,
+        SortOrder::UNDEFINED => false,
+        // Deprecated min and max are ordered as signed values
+        SortOrder::UNSIGNED => !statistics.is_min_max_deprecated(),
+        SortOrder::SIGNED => true,
+    }
+}
+
+/// Returns the min, if `min`, or max of `statistics`
+fn pick<T: ParquetValueType>(statistics: &ValueStatistics<T>, min: bool) -> &T {
+    match min {
+        true => statistics.min(),
+        false => statistics.max(),
+    }
+}
+
+/// Converts the min, if `min`, or max of each of `statistics` to an array of
+/// `data_type`
+fn convert(
+    data_type: &DataType,
+    statistics: &[Option<&Statistics>],
+    min: bool,
+) -> Result<ArrayRef> {
+    // Maps the min or max of each of `statistics` of the given variant with `$f`,
+    // returning an `Option` of the converted value
+    macro_rules! values {
+        ($($variant:ident)|+, $f:expr) => {
+            statistics.iter().map(|s| match s {
+                $(Some(Statistics::$variant(s)) => $f(pick(s, min)),)+
+                _ => None,
+            })
+        };
+    }
+
+    fn as_bytes<T: AsBytes>(v: &T) -> Option<&[u8]> {
+        Some(v.as_bytes())
+    }
+
+    fn as_str<T: AsBytes>(v: &T) -> Option<&str> {
+        std::str::from_utf8(v.as_bytes()).ok()
+    }
+
+    // Decodes a big-endian two's complement integer of 1 to `N` bytes
+    fn be_bytes<const N: usize>(b: &[u8]) -> Option<[u8; N]> {
+        (!b.is_empty() && b.len() <= N).then(|| sign_extend_be(b))
+    }
+
+    let array: ArrayRef = match data_type {
+        DataType::Boolean => Arc::new(BooleanArray::from_iter(values!(
+            Boolean,
+            |v: &bool| Some(*v)
+        ))),
+        DataType::Int8 => Arc::new(Int8Array::from_iter(values!(Int32, |v: &i32| Some(
+            *v as i8
+        )))),
+        DataType::Int16 => Arc::new(Int16Array::from_iter(values!(Int32, |v: &i32| {
+            Some(*v as i16)
+        }))),
+        DataType::Int32 => {
+            Arc::new(Int32Array::from_iter(values!(Int32, |v: &i32| Some(*v))))
+        }
+        DataType::Int64 => {
+            Arc::new(Int64Array::from_iter(values!(Int64, |v: &i64| Some(*v))))
+        }
+        DataType::UInt8 => {
+            Arc::new(UInt8Array::from_iter(values!(Int32, |v: &i32| Some(
+                *v as u8
+            ))))
+        }
+        DataType::UInt16 => Arc::new(UInt16Array::from_iter(values!(
+            Int32,
+            |v: &i32| Some(*v as u16)
+        ))),
+        DataType::UInt32 => Arc::new(UInt32Array::from_iter(values!(
+            Int32,
+            |v: &i32| Some(*v as u32)
+        ))),
+        DataType::UInt64 => Arc::new(UInt64Array::from_iter(values!(
+            Int64,
+            |v: &i64| Some(*v as u64)
+        ))),
+        DataType::Float32 => {
+            Arc::new(Float32Array::from_iter(values!(Float, |v: &f32| Some(*v))))
+        }
+        DataType::Float64 => {
+            Arc::new(Float64Array::from_iter(values!(Double, |v: &f64| Some(*v))))
+        }
+        DataType::Date32 => {
+            Arc::new(Date32Array::from_iter(values!(Int32, |v: &i32| Some(*v))))
+        }
+        DataType::Date64 => {
+            // Date64 is written as days in a DATE column, but may be read from
+            // milliseconds in an INT64 column
+            Arc::new(Date64Array::from_iter(statistics.iter().map(|s| match s {
+                Some(Statistics::Int32(s)) => Some(*pick(s, min) as i64 * 86_400_000),
+                Some(Statistics::Int64(s)) => Some(*pick(s, min)),
+                _ => None,
+            })))
+        }
+        DataType::Time32(TimeUnit::Second) => {
+            Arc::new(PrimitiveArray::<Time32SecondType>::from_iter(values!(
+                Int32,
+                |v: &i32| Some(*v)
+            )))
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            Arc::new(PrimitiveArray::<Time32MillisecondType>::from_iter(values!(
+                Int32,
+                |v: &i32| Some(*v)
+            )))
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            Arc::new(PrimitiveArray::<Time64MicrosecondType>::from_iter(values!(
+                Int64,
+                |v: &i64| Some(*v)
+            )))
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            Arc::new(PrimitiveArray::<Time64NanosecondType>::from_iter(values!(
+                Int64,
+                |v: &i64| Some(*v)
+            )))
+        }
+        DataType::Timestamp(unit, tz) => {
+            let values = values!(Int64, |v: &i64| Some(*v));
+            match unit {
+                TimeUnit::Second => Arc::new(
+                    PrimitiveArray::<TimestampSecondType>::from_iter(values)
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Millisecond => Arc::new(
+                    PrimitiveArray::<TimestampMillisecondType>::from_iter(values)
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Microsecond => Arc::new(
+                    PrimitiveArray::<TimestampMicrosecondType>::from_iter(values)
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Nanosecond => Arc::new(
+                    PrimitiveArray::<TimestampNanosecondType>::from_iter(values)
+                        .with_timezone_opt(tz.clone()),
+                ),
+            }
+        }
+        DataType::Decimal128(precision, scale) => {
+            let values = statistics.iter().map(|s| match s {
+                Some(Statistics::Int32(s)) => Some(*pick(s, min) as i128),
+                Some(Statistics::Int64(s)) => Some(*pick(s, min) as i128),
+                Some(Statistics::ByteArray(s)) => {
+                    be_bytes(pick(s, min).data()).map(i128::from_be_bytes)
+                }
+                Some(Statistics::FixedLenByteArray(s)) => {
+                    be_bytes(pick(s, min).data()).map(i128::from_be_bytes)
+                }
+                _ => None,
+            });
+            Arc::new(
+                Decimal128Array::from_iter(values)
+                    .with_precision_and_scale(*precision, *scale)?,
+            )
+        }
+        DataType::Decimal256(precision, scale) => {
+            let values = statistics.iter().map(|s| match s {
+                Some(Statistics::Int32(s)) => {
+                    Some(i256::from_i128(*pick(s, min) as i128))
+                }
+                Some(Statistics::Int64(s)) => {
+                    Some(i256::from_i128(*pick(s, min) as i128))
+                }
+                Some(Statistics::ByteArray(s)) => {
+                    be_bytes(pick(s, min).data()).map(i256::from_be_bytes)
+                }
+                Some(Statistics::FixedLenByteArray(s)) => {
+                    be_bytes(pick(s, min).data()).map(i256::from_be_bytes)
+                }
+                _ => None,
+            });
+            Arc::new(
+                Decimal256Array::from_iter(values)
+                    .with_precision_and_scale(*precision, *scale)?,
+            )
+        }
+        DataType::Utf8 => Arc::new(StringArray::from_iter(values!(
+            ByteArray | FixedLenByteArray,
+            as_str
+        ))),
+        DataType::LargeUtf8 => Arc::new(LargeStringArray::from_iter(values!(
+            ByteArray | FixedLenByteArray,
+            as_str
+        ))),
+        DataType::Binary => Arc::new(BinaryArray::from_iter(values!(
+            ByteArray | FixedLenByteArray,
+            as_bytes
+        ))),
+        DataType::LargeBinary => Arc::new(LargeBinaryArray::from_iter(values!(
+            ByteArray | FixedLenByteArray,
+            as_bytes
+        ))),
+        DataType::FixedSizeBinary(size) => {
+            let values = values!(ByteArray | FixedLenByteArray, as_bytes)
+                .map(|v| v.filter(|v| v.len() == *size as usize));
+            Arc::new(FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                values, *size,
+            )?)
+        }
+        DataType::Dictionary(_, value_type) => {
+            return convert(value_type, statistics, min)
+        }
+        _ => new_null_array(data_type, statistics.len()),
+    };
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use crate::arrow::ArrowWriter;
+    use crate::data_type::ByteArray;
+    use crate::file::properties::WriterProperties;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{Array, DictionaryArray, RecordBatch, TimestampMillisecondArray};
+    use bytes::Bytes;
+
+    #[test]
+    fn test_statistics_converter() {
+        let tz = Some("+01:00".to_string());
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "i8",
+                Arc::new(Int8Array::from(vec![Some(1), Some(-3), None, Some(4)]))
+                    as ArrayRef,
+            ),
+            (
+                "u32",
+                Arc::new(UInt32Array::from(vec![Some(1), Some(u32::MAX), None, None])),
+            ),
+            (
+                "ts",
+                Arc::new(
+                    TimestampMillisecondArray::from(vec![
+                        Some(3),
+                        Some(1),
+                        Some(7),
+                        None,
+                    ])
+                    .with_timezone_opt(tz.clone()),
+                ),
+            ),
+            (
+                "decimal",
+                Arc::new(
+                    Decimal128Array::from(vec![Some(-12345), Some(3), None, Some(678)])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+            ),
+            (
+                "string",
+                Arc::new(StringArray::from(vec![
+                    Some("b"),
+                    Some("a"),
+                    Some("z"),
+                    None,
+                ])),
+            ),
+            (
+                "uuid",
+                Arc::new(
+                    FixedSizeBinaryArray::try_from_iter(
+                        [[1_u8; 16], [0; 16], [9; 16], [8; 16]].into_iter(),
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                "dict",
+                Arc::new(
+                    vec![Some("x"), None, Some("y"), None]
+                        .into_iter()
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ),
+            ),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(2)
+            .build();
+        let mut buf = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf)).unwrap();
+        let row_groups = builder.metadata().row_groups();
+        assert_eq!(row_groups.len(), 2);
+
+        let converter = |name| {
+            StatisticsConverter::try_new(name, builder.schema(), builder.parquet_schema())
+                .unwrap()
+        };
+        let check = |name, mins: ArrayRef, maxes: ArrayRef, null_counts: Vec<u64>| {
+            let converter = converter(name);
+            assert_eq!(&converter.row_group_mins(row_groups).unwrap(), &mins);
+            assert_eq!(&converter.row_group_maxes(row_groups).unwrap(), &maxes);
+            assert_eq!(
+                converter.row_group_null_counts(row_groups).unwrap(),
+                UInt64Array::from(null_counts)
+            );
+        };
+
+        check(
+            "i8",
+            Arc::new(Int8Array::from(vec![-3, 4])),
+            Arc::new(Int8Array::from(vec![1, 4])),
+            vec![0, 1],
+        );
+        check(
+            "u32",
+            Arc::new(UInt32Array::from(vec![Some(1), None])),
+            Arc::new(UInt32Array::from(vec![Some(u32::MAX), None])),
+            vec![0, 2],
+        );
+        check(
+            "ts",
+            Arc::new(
+                TimestampMillisecondArray::from(vec![1, 7]).with_timezone_opt(tz.clone()),
+            ),
+            Arc::new(TimestampMillisecondArray::from(vec![3, 7]).with_timezone_opt(tz)),
+            vec![0, 1],
+        );
+        check(
+            "decimal",
+            Arc::new(
+                Decimal128Array::from(vec![-12345, 678])
+                    .with_precision_and_scale(10, 2)
+                    .unwrap(),
+            ),
+            Arc::new(
+                Decimal128Array::from(vec![3, 678])
+                    .with_precision_and_scale(10, 2)
+                    .unwrap(),
+            ),
+            vec![0, 1],
+        );
+        check(
+            "string",
+            Arc::new(StringArray::from(vec!["a", "z"])),
+            Arc::new(StringArray::from(vec!["b", "z"])),
+            vec![0, 1],
+        );
+        check(
+            "uuid",
+            Arc::new(
+                FixedSizeBinaryArray::try_from_iter([[0_u8; 16], [8; 16]].into_iter())
+                    .unwrap(),
+            ),
+            Arc::new(
+                FixedSizeBinaryArray::try_from_iter([[1_u8; 16], [9; 16]].into_iter())
+                    .unwrap(),
+            ),
+            vec![0, 0],
+        );
+        check(
+            "dict",
+            Arc::new(StringArray::from(vec!["x", "y"])),
+            Arc::new(StringArray::from(vec!["x", "y"])),
+            vec![1, 1],
+        );
+
+        let err = StatisticsConverter::try_new(
+            "missing",
+            builder.schema(),
+            builder.parquet_schema(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow: Column missing not found in arrow schema"
+        );
+    }
+
+    #[test]
+    fn test_statistics_converter_nested() {
+        let batch = RecordBatch::try_from_iter(vec![(
+            "list",
+            Arc::new(
+                arrow_array::ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                    Some(vec![Some(1), Some(2)]),
+                    None,
+                ]),
+            ) as ArrayRef,
+        )])
+        .unwrap();
+
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf)).unwrap();
+        let row_groups = builder.metadata().row_groups();
+        let converter = StatisticsConverter::try_new(
+            "list",
+            builder.schema(),
+            builder.parquet_schema(),
+        )
+        .unwrap();
+
+        let mins = converter.row_group_mins(row_groups).unwrap();
+        assert_eq!(mins.data_type(), converter.arrow_field().data_type());
+        assert_eq!(mins.null_count(), 1);
+        assert_eq!(
+            converter.row_group_null_counts(row_groups).unwrap(),
+            UInt64Array::from(vec![None])
+        );
+    }
+
+    #[test]
+    fn test_convert_decimal_invalid_bytes() {
+        let empty = Statistics::byte_array(
+            Some(ByteArray::from(vec![])),
+            Some(ByteArray::from(vec![])),
+            None,
+            0,
+            false,
+        );
+        let too_long = Statistics::fixed_len_byte_array(
+            Some(ByteArray::from(vec![1; 33]).into()),
+            Some(ByteArray::from(vec![1; 33]).into()),
+            None,
+            0,
+            false,
+        );
+        let valid = Statistics::byte_array(
+            Some(ByteArray::from(vec![255])),
+            Some(ByteArray::from(vec![1, 0])),
+            None,
+            0,
+            false,
+        );
+        let statistics = [Some(&empty), Some(&too_long), Some(&valid)];
+
+        let expected = Decimal128Array::from(vec![None, None, Some(-1)])
+            .with_precision_and_scale(10, 2)
+            .unwrap();
+        let mins = convert(&DataType::Decimal128(10, 2), &statistics, true).unwrap();
+        assert_eq!(mins.as_ref(), &expected as &dyn Array);
+
+        let expected =
+            Decimal256Array::from(vec![None, None, Some(i256::from_i128(256))])
+                .with_precision_and_scale(40, 2)
+                .unwrap();
+        let maxes = convert(&DataType::Decimal256(40, 2), &statistics, false).unwrap();
+        assert_eq!(maxes.as_ref(), &expected as &dyn Array);
+    }
+}
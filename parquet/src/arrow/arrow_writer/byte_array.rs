@@ -114,7 +114,7 @@ impl<'a> ByteArrayWriter<'a> {
         })
     }
 
-    pub fn write(&mut self, array: &ArrayRef, levels: LevelInfo) -> Result<()> {
+    pub fn write(&mut self, array: &ArrayRef, levels: &LevelInfo) -> Result<()> {
         self.writer.write_batch_internal(
             array,
             Some(levels.non_null_indices()),
@@ -288,16 +288,19 @@ impl FallbackEncoder {
                 let mut out = Vec::with_capacity(lengths.len() + buffer.len());
                 out.extend_from_slice(lengths.data());
                 out.extend_from_slice(buffer);
+                buffer.clear();
                 (out, Encoding::DELTA_LENGTH_BYTE_ARRAY)
             }
             FallbackEncoderImpl::Delta {
                 buffer,
+                last_value,
                 prefix_lengths,
                 suffix_lengths,
-                ..
             } => {
                 let prefix_lengths = prefix_lengths.flush_buffer()?;
                 let suffix_lengths = suffix_lengths.flush_buffer()?;
+                // The prefix of the first value of a page is always empty
+                last_value.clear();
 
                 let mut out = Vec::with_capacity(
                     prefix_lengths.len() + suffix_lengths.len() + buffer.len(),
@@ -305,6 +308,7 @@ impl FallbackEncoder {
                 out.extend_from_slice(prefix_lengths.data());
                 out.extend_from_slice(suffix_lengths.data());
                 out.extend_from_slice(buffer);
+                buffer.clear();
                 (out, Encoding::DELTA_BYTE_ARRAY)
             }
         };
@@ -424,7 +428,7 @@ impl DictEncoder {
     }
 }
 
-struct ByteArrayEncoder {
+pub(super) struct ByteArrayEncoder {
     fallback: FallbackEncoder,
     dict_encoder: Option<DictEncoder>,
     min_value: Option<ByteArray>,
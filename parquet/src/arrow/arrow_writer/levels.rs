@@ -42,7 +42,8 @@
 
 use crate::errors::{ParquetError, Result};
 use arrow_array::{
-    make_array, Array, ArrayRef, GenericListArray, MapArray, OffsetSizeTrait, StructArray,
+    make_array, Array, ArrayRef, FixedSizeListArray, GenericListArray, MapArray,
+    OffsetSizeTrait, StructArray,
 };
 use arrow_data::ArrayData;
 use arrow_schema::{DataType, Field};
@@ -89,6 +90,7 @@ fn is_leaf(data_type: &DataType) -> bool {
             | DataType::Binary
             | DataType::LargeBinary
             | DataType::Decimal128(_, _)
+            | DataType::Decimal256(_, _)
             | DataType::FixedSizeBinary(_)
     )
 }
@@ -145,6 +147,7 @@ impl LevelInfoBuilder {
             }
             DataType::List(child)
             | DataType::LargeList(child)
+            | DataType::FixedSizeList(child, _)
             | DataType::Map(child, _) => {
                 let def_level = match field.is_nullable() {
                     true => parent_ctx.def_level + 2,
@@ -191,7 +194,8 @@ impl LevelInfoBuilder {
                     .as_any()
                     .downcast_ref::<GenericListArray<i32>>()
                     .unwrap();
-                self.write_list(array.value_offsets(), array.data(), range)
+                let offsets = &array.value_offsets()[range.start..range.end + 1];
+                self.write_list(offsets, array.data(), range)
             }
             DataType::LargeList(_) => {
                 let array = array
@@ -199,21 +203,36 @@ impl LevelInfoBuilder {
                     .downcast_ref::<GenericListArray<i64>>()
                     .unwrap();
 
-                self.write_list(array.value_offsets(), array.data(), range)
+                let offsets = &array.value_offsets()[range.start..range.end + 1];
+                self.write_list(offsets, array.data(), range)
+            }
+            DataType::FixedSizeList(_, size) => {
+                let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+                // A FixedSizeListArray has implicit offsets, compute them so that
+                // it can be treated as any other list
+                let size = *size as usize;
+                let offset = array.offset();
+                let offsets: Vec<i64> = (range.start..range.end + 1)
+                    .map(|idx| ((offset + idx) * size) as i64)
+                    .collect();
+                self.write_list(&offsets, array.data(), range)
             }
             DataType::Map(_, _) => {
                 let array = array.as_any().downcast_ref::<MapArray>().unwrap();
                 // A Map is just as ListArray<i32> with a StructArray child, we therefore
                 // treat it as such to avoid code duplication
-                self.write_list(array.value_offsets(), array.data(), range)
+                let offsets = &array.value_offsets()[range.start..range.end + 1];
+                self.write_list(offsets, array.data(), range)
             }
             _ => unreachable!(),
         }
     }
 
-    /// Write `range` elements from ListArray `array`
+    /// Write `range` elements from ListArray `array`, where `offsets` contains the
+    /// `range.len() + 1` offsets delimiting these elements
     ///
-    /// Note: MapArrays are `ListArray<i32>` under the hood and so are dispatched to this method
+    /// Note: MapArrays are `ListArray<i32>` under the hood and so are dispatched to this method,
+    /// FixedSizeListArrays are dispatched with their implicit offsets
     fn write_list<O: OffsetSizeTrait>(
         &mut self,
         offsets: &[O],
@@ -225,7 +244,6 @@ impl LevelInfoBuilder {
             _ => unreachable!(),
         };
 
-        let offsets = &offsets[range.start..range.end + 1];
         let child_array = make_array(list_data.child_data()[0].clone());
 
         let write_non_null_slice =
@@ -1360,8 +1378,8 @@ mod tests {
             r#""#.to_string(),
             r#""#.to_string(),
             r#"[]"#.to_string(),
-            r#"[{"list": [3, ], "integers": null}]"#.to_string(),
-            r#"[, {"list": null, "integers": 5}]"#.to_string(),
+            r#"[{list: [3, null], integers: null}]"#.to_string(),
+            r#"[null, {list: null, integers: 5}]"#.to_string(),
             r#"[]"#.to_string(),
         ];
 
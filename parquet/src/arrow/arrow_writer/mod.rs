@@ -21,20 +21,30 @@ use std::collections::VecDeque;
 use std::io::Write;
 use std::sync::Arc;
 
-use arrow_array::{Array, ArrayRef, RecordBatch};
-use arrow_schema::{DataType as ArrowDataType, IntervalUnit, SchemaRef};
+use arrow_array::{Array, ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_data::{layout, ArrayData, BufferSpec};
+use arrow_schema::{
+    DataType as ArrowDataType, Field, IntervalUnit, Schema, SchemaRef, TimeUnit,
+};
+use bytes::Bytes;
 
 use super::schema::{
     add_encoded_arrow_schema_to_metadata, arrow_to_parquet_schema,
     decimal_length_from_precision,
 };
 
-use crate::arrow::arrow_writer::byte_array::ByteArrayWriter;
-use crate::column::writer::{ColumnWriter, ColumnWriterImpl};
+use crate::arrow::arrow_writer::byte_array::{ByteArrayEncoder, ByteArrayWriter};
+use crate::basic::{LogicalType, TimeUnit as ParquetTimeUnit};
+use crate::column::page::{CompressedPage, PageWriteSpec, PageWriter};
+use crate::column::writer::{
+    get_column_writer, ColumnCloseResult, ColumnWriter, ColumnWriterImpl,
+    GenericColumnWriter,
+};
 use crate::errors::{ParquetError, Result};
-use crate::file::metadata::RowGroupMetaDataPtr;
-use crate::file::properties::WriterProperties;
-use crate::file::writer::SerializedRowGroupWriter;
+use crate::file::metadata::{ColumnChunkMetaData, KeyValue, RowGroupMetaDataPtr};
+use crate::file::properties::{TimestampUnit, WriterProperties, WriterPropertiesPtr};
+use crate::file::writer::{SerializedPageWriter, SerializedRowGroupWriter, TrackedWrite};
+use crate::schema::types::{ColumnDescPtr, SchemaDescriptor};
 use crate::{data_type::*, file::writer::SerializedFileWriter};
 use levels::{calculate_array_levels, LevelInfo};
 
@@ -48,6 +58,10 @@ mod levels;
 /// flushed on close, leading the final row group in the output file to potentially
 /// contain fewer than `max_row_group_size` rows
 ///
+/// If [`WriterProperties::max_row_group_bytes`] is set, row groups will additionally be
+/// flushed once the [`in_progress_size`](Self::in_progress_size) reaches this limit,
+/// allowing callers to bound the memory used by the buffered data
+///
 /// ```
 /// # use std::sync::Arc;
 /// # use bytes::Bytes;
@@ -85,6 +99,9 @@ pub struct ArrowWriter<W: Write> {
 
     /// The length of arrays to write to each row group
     max_row_group_size: usize,
+
+    /// The estimated size of the buffered data at which to flush a row group
+    max_row_group_bytes: Option<usize>,
 }
 
 impl<W: Write> ArrowWriter<W> {
@@ -93,17 +110,25 @@ impl<W: Write> ArrowWriter<W> {
     /// The writer will fail if:
     ///  * a `SerializedFileWriter` cannot be created from the ParquetWriter
     ///  * the Arrow schema contains unsupported datatypes such as Unions
+    ///
+    /// If [`WriterProperties::coerce_timestamps`] is set, the parquet schema and the
+    /// embedded arrow schema use the coerced timestamp unit
     pub fn try_new(
         writer: W,
         arrow_schema: SchemaRef,
         props: Option<WriterProperties>,
     ) -> Result<Self> {
-        let schema = arrow_to_parquet_schema(&arrow_schema)?;
-        // add serialized arrow schema
         let mut props = props.unwrap_or_else(|| WriterProperties::builder().build());
-        add_encoded_arrow_schema_to_metadata(&arrow_schema, &mut props);
+        let file_schema = match props.coerce_timestamps() {
+            Some(unit) => coerce_schema(&arrow_schema, unit),
+            None => arrow_schema.as_ref().clone(),
+        };
+        let schema = arrow_to_parquet_schema(&file_schema)?;
+        // add serialized arrow schema
+        add_encoded_arrow_schema_to_metadata(&file_schema, &mut props);
 
         let max_row_group_size = props.max_row_group_size();
+        let max_row_group_bytes = props.max_row_group_bytes();
 
         let file_writer =
             SerializedFileWriter::new(writer, schema.root_schema_ptr(), Arc::new(props))?;
@@ -114,6 +139,7 @@ impl<W: Write> ArrowWriter<W> {
             buffered_rows: 0,
             arrow_schema,
             max_row_group_size,
+            max_row_group_bytes,
         })
     }
 
@@ -122,11 +148,52 @@ impl<W: Write> ArrowWriter<W> {
         self.writer.flushed_row_groups()
     }
 
+    /// Returns the number of rows buffered for the in progress row group
+    pub fn in_progress_rows(&self) -> usize {
+        self.buffered_rows
+    }
+
+    /// Returns the estimated size in bytes of the rows buffered for the in progress
+    /// row group
+    ///
+    /// As data is only encoded when a row group is flushed, this is an estimate of the
+    /// size of the buffered rows prior to encoding and compression, and only accounts
+    /// for the rows referenced by any sliced arrays
+    pub fn in_progress_size(&self) -> usize {
+        self.buffer
+            .iter()
+            .flatten()
+            .map(|array| estimated_slice_size(array.data()))
+            .sum()
+    }
+
+    /// Returns the estimated memory in bytes retained by the buffered arrays
+    ///
+    /// This may exceed [`in_progress_size`](Self::in_progress_size), as a buffered array
+    /// that is a slice of a larger array retains the buffers of that array
+    pub fn memory_size(&self) -> usize {
+        self.buffer
+            .iter()
+            .flatten()
+            .map(|array| array.get_array_memory_size())
+            .sum()
+    }
+
+    /// Adds a [`KeyValue`] to the metadata written to the file footer, in addition
+    /// to any [`WriterProperties::key_value_metadata`] and the embedded arrow schema
+    ///
+    /// This may be called at any point before [`close`](Self::close), e.g. to attach
+    /// metadata computed from the data written, such as checksums or row counts.
+    pub fn append_key_value_metadata(&mut self, kv_metadata: KeyValue) {
+        self.writer.append_key_value_metadata(kv_metadata)
+    }
+
     /// Enqueues the provided `RecordBatch` to be written
     ///
     /// If following this there are more than `max_row_group_size` rows buffered,
     /// this will flush out one or more row groups with `max_row_group_size` rows,
-    /// and drop any fully written `RecordBatch`
+    /// and drop any fully written `RecordBatch`. Similarly, if `max_row_group_bytes`
+    /// is set, row groups will be flushed until less than this many bytes are buffered
     pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
         // validate batch schema against writer's supplied schema
         if self.arrow_schema != batch.schema() {
@@ -145,12 +212,51 @@ impl<W: Write> ArrowWriter<W> {
         Ok(())
     }
 
-    /// Flushes buffered data until there are less than `max_row_group_size` rows buffered
+    /// Writes all the `RecordBatch` of `reader`, returning the number of rows written
+    ///
+    /// As for [`write`](Self::write), the batches are sliced or coalesced into row groups
+    /// of `max_row_group_size` rows, regardless of their size. Any remaining rows are
+    /// left buffered, to be written along with subsequent batches or on
+    /// [`close`](Self::close).
+    pub fn write_reader<R: RecordBatchReader>(&mut self, reader: R) -> Result<usize> {
+        if self.arrow_schema != reader.schema() {
+            return Err(ParquetError::ArrowError(
+                "Record batch reader schema does not match writer schema".to_string(),
+            ));
+        }
+
+        let mut num_rows = 0;
+        for batch in reader {
+            let batch = batch?;
+            self.write(&batch)?;
+            num_rows += batch.num_rows();
+        }
+        Ok(num_rows)
+    }
+
+    /// Flushes buffered data until there are less than `max_row_group_size` rows,
+    /// and less than `max_row_group_bytes` bytes, buffered
     fn flush_completed(&mut self) -> Result<()> {
-        while self.buffered_rows >= self.max_row_group_size {
-            self.flush_rows(self.max_row_group_size)?;
+        loop {
+            let mut num_rows = (self.buffered_rows >= self.max_row_group_size)
+                .then_some(self.max_row_group_size);
+
+            if let Some(max_bytes) = self.max_row_group_bytes {
+                let size = self.in_progress_size();
+                if size >= max_bytes && self.buffered_rows != 0 {
+                    // Assume rows are of a uniform size to determine how many to flush
+                    let rows =
+                        self.buffered_rows as u128 * max_bytes as u128 / size as u128;
+                    let rows = (rows as usize).clamp(1, self.buffered_rows);
+                    num_rows = Some(num_rows.map_or(rows, |n| n.min(rows)));
+                }
+            }
+
+            match num_rows {
+                Some(num_rows) => self.flush_rows(num_rows)?,
+                None => return Ok(()),
+            }
         }
-        Ok(())
     }
 
     /// Flushes all buffered rows into a new row group
@@ -201,17 +307,20 @@ impl<W: Write> ArrowWriter<W> {
                 }
             }
 
-            let mut levels = arrays
-                .iter()
-                .map(|array| {
-                    let mut levels = calculate_array_levels(array, field)?;
-                    // Reverse levels as we pop() them when writing arrays
-                    levels.reverse();
-                    Ok(levels)
-                })
-                .collect::<Result<Vec<_>>>()?;
+            // Group the leaves of the arrays by leaf column
+            let mut leaves: Vec<Vec<ArrowLeafColumn>> = Vec::new();
+            for array in &arrays {
+                for (idx, leaf) in compute_leaves(field, array)?.into_iter().enumerate() {
+                    match leaves.get_mut(idx) {
+                        Some(column) => column.push(leaf),
+                        None => leaves.push(vec![leaf]),
+                    }
+                }
+            }
 
-            write_leaves(&mut row_group_writer, &arrays, &mut levels)?;
+            for column in &leaves {
+                write_leaf_column(&mut row_group_writer, column)?;
+            }
         }
 
         row_group_writer.close()?;
@@ -233,139 +342,534 @@ impl<W: Write> ArrowWriter<W> {
     }
 }
 
-fn write_leaves<W: Write>(
-    row_group_writer: &mut SerializedRowGroupWriter<'_, W>,
-    arrays: &[ArrayRef],
-    levels: &mut [Vec<LevelInfo>],
+/// A single leaf column of an arrow array, along with its definition and repetition
+/// levels, as returned by [`compute_leaves`]
+#[derive(Debug, Clone)]
+pub struct ArrowLeafColumn {
+    array: ArrayRef,
+    levels: LevelInfo,
+}
+
+/// Computes the [`ArrowLeafColumn`] of each leaf column of `array`, a column of type
+/// `field`, in the order of the leaf columns of its parquet schema
+pub fn compute_leaves(field: &Field, array: &ArrayRef) -> Result<Vec<ArrowLeafColumn>> {
+    let levels = calculate_array_levels(array, field)?;
+    let mut arrays = Vec::with_capacity(levels.len());
+    collect_leaves(array, &mut arrays)?;
+    if arrays.len() != levels.len() {
+        return Err(general_err!(
+            "Expected {} leaf columns for field {}, got {}",
+            levels.len(),
+            field.name(),
+            arrays.len()
+        ));
+    }
+
+    Ok(arrays
+        .into_iter()
+        .zip(levels)
+        .map(|(array, levels)| ArrowLeafColumn { array, levels })
+        .collect())
+}
+
+/// Returns an [`ArrowColumnWriter`] for each leaf column of `arrow`, in the order of
+/// the columns of `parquet`, its parquet schema as returned by [`arrow_to_parquet_schema`]
+///
+/// Unlike the column writers of a [`SerializedRowGroupWriter`], which must be written
+/// one after another, these buffer the leaf columns written to them until they are
+/// closed, at which point they encode them in memory. They are [`Send`], so that the
+/// columns of a row group may be encoded in parallel. The resulting
+/// [`ArrowColumnChunk`]s are then appended, in order, to a row group of a
+/// [`SerializedFileWriter`] with [`ArrowColumnChunk::append_to_row_group`].
+///
+/// Encryption is not supported, appending a column chunk to an encrypted column returns
+/// an error.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray};
+/// # use parquet::arrow::arrow_to_parquet_schema;
+/// # use parquet::arrow::arrow_writer::{compute_leaves, get_column_writers};
+/// # use parquet::file::properties::WriterProperties;
+/// # use parquet::file::writer::SerializedFileWriter;
+/// let ints = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+/// let strs = Arc::new(StringArray::from_iter_values((0..100).map(|x| x.to_string())));
+/// let batch = RecordBatch::try_from_iter([("i", ints), ("s", strs as ArrayRef)]).unwrap();
+/// let schema = batch.schema();
+///
+/// let parquet_schema = arrow_to_parquet_schema(&schema).unwrap();
+/// let props = Arc::new(WriterProperties::builder().build());
+/// let writers = get_column_writers(&parquet_schema, &props, &schema).unwrap();
+///
+/// // Encode each column on its own thread
+/// let mut workers = Vec::with_capacity(writers.len());
+/// let mut writers = writers.into_iter();
+/// for (field, column) in schema.fields().iter().zip(batch.columns()) {
+///     for leaf in compute_leaves(field, column).unwrap() {
+///         let mut writer = writers.next().unwrap();
+///         workers.push(std::thread::spawn(move || {
+///             writer.write(&leaf)?;
+///             writer.close()
+///         }));
+///     }
+/// }
+///
+/// // Assemble the encoded column chunks into a row group
+/// let mut buffer = Vec::new();
+/// let root_schema = parquet_schema.root_schema_ptr();
+/// let mut writer = SerializedFileWriter::new(&mut buffer, root_schema, props).unwrap();
+/// let mut row_group = writer.next_row_group().unwrap();
+/// for worker in workers {
+///     let chunk = worker.join().unwrap().unwrap();
+///     chunk.append_to_row_group(&mut row_group).unwrap();
+/// }
+/// row_group.close().unwrap();
+/// writer.close().unwrap();
+/// ```
+pub fn get_column_writers(
+    parquet: &SchemaDescriptor,
+    props: &WriterPropertiesPtr,
+    arrow: &SchemaRef,
+) -> Result<Vec<ArrowColumnWriter>> {
+    props.check_compression_levels(parquet)?;
+    let mut writers = Vec::with_capacity(parquet.num_columns());
+    let mut leaves = parquet.columns().iter();
+    for field in arrow.fields() {
+        get_arrow_column_writers(field.data_type(), props, &mut leaves, &mut writers)?;
+    }
+    if leaves.next().is_some() {
+        return Err(general_err!(
+            "Parquet schema has more columns than the arrow schema"
+        ));
+    }
+    Ok(writers)
+}
+
+/// Appends the [`ArrowColumnWriter`] of each leaf column of `data_type` to `writers`
+fn get_arrow_column_writers(
+    data_type: &ArrowDataType,
+    props: &WriterPropertiesPtr,
+    leaves: &mut std::slice::Iter<'_, ColumnDescPtr>,
+    writers: &mut Vec<ArrowColumnWriter>,
 ) -> Result<()> {
-    assert_eq!(arrays.len(), levels.len());
-    assert!(!arrays.is_empty());
-
-    let data_type = arrays.first().unwrap().data_type().clone();
-    assert!(arrays.iter().all(|a| a.data_type() == &data_type));
-
-    match &data_type {
-        ArrowDataType::Null
-        | ArrowDataType::Boolean
-        | ArrowDataType::Int8
-        | ArrowDataType::Int16
-        | ArrowDataType::Int32
-        | ArrowDataType::Int64
-        | ArrowDataType::UInt8
-        | ArrowDataType::UInt16
-        | ArrowDataType::UInt32
-        | ArrowDataType::UInt64
-        | ArrowDataType::Float32
-        | ArrowDataType::Float64
-        | ArrowDataType::Timestamp(_, _)
-        | ArrowDataType::Date32
-        | ArrowDataType::Date64
-        | ArrowDataType::Time32(_)
-        | ArrowDataType::Time64(_)
-        | ArrowDataType::Duration(_)
-        | ArrowDataType::Interval(_)
-        | ArrowDataType::Decimal128(_, _)
-        | ArrowDataType::Decimal256(_, _)
-        | ArrowDataType::FixedSizeBinary(_) => {
-            let mut col_writer = row_group_writer.next_column()?.unwrap();
-            for (array, levels) in arrays.iter().zip(levels.iter_mut()) {
-                write_leaf(col_writer.untyped(), array, levels.pop().expect("Levels exhausted"))?;
-            }
-            col_writer.close()
+    match data_type {
+        ArrowDataType::List(field)
+        | ArrowDataType::LargeList(field)
+        | ArrowDataType::FixedSizeList(field, _) => {
+            get_arrow_column_writers(field.data_type(), props, leaves, writers)
         }
-        ArrowDataType::LargeBinary
-        | ArrowDataType::Binary
-        | ArrowDataType::Utf8
-        | ArrowDataType::LargeUtf8 => {
-            let mut col_writer = row_group_writer.next_column_with_factory(ByteArrayWriter::new)?.unwrap();
-            for (array, levels) in arrays.iter().zip(levels.iter_mut()) {
-                col_writer.write(array, levels.pop().expect("Levels exhausted"))?;
+        ArrowDataType::Struct(fields) => {
+            for field in fields {
+                get_arrow_column_writers(field.data_type(), props, leaves, writers)?;
             }
-            col_writer.close()
+            Ok(())
         }
-        ArrowDataType::List(_) | ArrowDataType::LargeList(_) => {
-            let arrays: Vec<_> = arrays.iter().map(|array|{
-                // write the child list
-                let data = array.data();
-                arrow_array::make_array(data.child_data()[0].clone())
-            }).collect();
-
-            write_leaves(row_group_writer, &arrays, levels)?;
+        ArrowDataType::Map(field, _) => match field.data_type() {
+            ArrowDataType::Struct(fields) if fields.len() == 2 => {
+                get_arrow_column_writers(fields[0].data_type(), props, leaves, writers)?;
+                get_arrow_column_writers(fields[1].data_type(), props, leaves, writers)
+            }
+            _ => Err(arrow_err!("Invalid map type {:?}", data_type)),
+        },
+        ArrowDataType::Float16 => Err(ParquetError::ArrowError(
+            "Float16 arrays not supported".to_string(),
+        )),
+        ArrowDataType::Union(_, _, _) => Err(ParquetError::NYI(format!(
+            "Attempting to write an Arrow type {:?} to parquet that is not yet implemented",
+            data_type
+        ))),
+        _ => {
+            let descr = leaves.next().ok_or_else(|| {
+                general_err!("Arrow schema has more columns than the parquet schema")
+            })?;
+            writers.push(ArrowColumnWriter::new(
+                descr.clone(),
+                props,
+                is_byte_array_leaf(data_type),
+            ));
             Ok(())
         }
-        ArrowDataType::Struct(fields) => {
-            // Groups child arrays by field
-            let mut field_arrays = vec![Vec::with_capacity(arrays.len()); fields.len()];
+    }
+}
 
-            for array in arrays {
-                let struct_array: &arrow_array::StructArray = array
-                    .as_any()
-                    .downcast_ref::<arrow_array::StructArray>()
-                    .expect("Unable to get struct array");
+/// A [`PageWriter`] that buffers the pages of a column chunk in memory
+struct ArrowPageWriter<'a> {
+    buffer: &'a mut TrackedWrite<Vec<u8>>,
+    write_checksums: bool,
+}
 
-                assert_eq!(struct_array.columns().len(), fields.len());
+impl<'a> PageWriter for ArrowPageWriter<'a> {
+    fn write_page(&mut self, page: CompressedPage) -> Result<PageWriteSpec> {
+        SerializedPageWriter::new(self.buffer)
+            .with_page_checksums(self.write_checksums)
+            .write_page(page)
+    }
 
-                for (child_array, field) in field_arrays.iter_mut().zip(struct_array.columns()) {
-                    child_array.push(field.clone())
-                }
-            }
+    fn write_metadata(&mut self, _metadata: &ColumnChunkMetaData) -> Result<()> {
+        // The column chunk metadata is written to the footer when the chunk is appended
+        Ok(())
+    }
 
-            for field in field_arrays {
-                write_leaves(row_group_writer, &field, levels)?;
-            }
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
 
-            Ok(())
-        }
-        ArrowDataType::Map(_, _) => {
-            let mut keys = Vec::with_capacity(arrays.len());
-            let mut values = Vec::with_capacity(arrays.len());
-            for array in arrays {
-                let map_array: &arrow_array::MapArray = array
-                    .as_any()
-                    .downcast_ref::<arrow_array::MapArray>()
-                    .expect("Unable to get map array");
-                keys.push(map_array.keys());
-                values.push(map_array.values());
-            }
+/// Encodes a leaf column of a row group to an in-memory [`ArrowColumnChunk`], see
+/// [`get_column_writers`]
+#[derive(Debug)]
+pub struct ArrowColumnWriter {
+    descr: ColumnDescPtr,
+    props: WriterPropertiesPtr,
+    byte_array: bool,
+    leaves: Vec<ArrowLeafColumn>,
+}
 
-            write_leaves(row_group_writer, &keys, levels)?;
-            write_leaves(row_group_writer, &values, levels)?;
-            Ok(())
+impl ArrowColumnWriter {
+    fn new(descr: ColumnDescPtr, props: &WriterPropertiesPtr, byte_array: bool) -> Self {
+        Self {
+            descr,
+            props: props.clone(),
+            byte_array,
+            leaves: vec![],
         }
-        ArrowDataType::Dictionary(_, value_type) => match value_type.as_ref() {
-            ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Binary | ArrowDataType::LargeBinary => {
-                let mut col_writer = row_group_writer.next_column_with_factory(ByteArrayWriter::new)?.unwrap();
-                for (array, levels) in arrays.iter().zip(levels.iter_mut()) {
-                    col_writer.write(array, levels.pop().expect("Levels exhausted"))?;
+    }
+
+    /// Buffers `col`, the next [`ArrowLeafColumn`] of this column, to be encoded by
+    /// [`Self::close`]
+    pub fn write(&mut self, col: &ArrowLeafColumn) -> Result<()> {
+        self.leaves.push(col.clone());
+        Ok(())
+    }
+
+    /// Encodes the buffered leaf columns and returns the [`ArrowColumnChunk`]
+    pub fn close(self) -> Result<ArrowColumnChunk> {
+        let mut buffer = TrackedWrite::new(Vec::new());
+        let page_writer = Box::new(ArrowPageWriter {
+            buffer: &mut buffer,
+            write_checksums: self.props.write_page_checksums(),
+        });
+
+        let close = match self.byte_array {
+            true => {
+                let mut writer = GenericColumnWriter::<ByteArrayEncoder>::new(
+                    self.descr,
+                    self.props,
+                    page_writer,
+                );
+                for leaf in &self.leaves {
+                    writer.write_batch_internal(
+                        &leaf.array,
+                        Some(leaf.levels.non_null_indices()),
+                        leaf.levels.def_levels(),
+                        leaf.levels.rep_levels(),
+                        None,
+                        None,
+                        None,
+                    )?;
                 }
-                col_writer.close()
+                writer.close()?
             }
-            _ => {
-                let mut col_writer = row_group_writer.next_column()?.unwrap();
-                for (array, levels) in arrays.iter().zip(levels.iter_mut()) {
-                    write_leaf(col_writer.untyped(), array, levels.pop().expect("Levels exhausted"))?;
+            false => {
+                let mut writer = get_column_writer(self.descr, self.props, page_writer);
+                for leaf in &self.leaves {
+                    write_leaf(&mut writer, &leaf.array, &leaf.levels)?;
+                }
+                match writer {
+                    ColumnWriter::BoolColumnWriter(w) => w.close()?,
+                    ColumnWriter::Int32ColumnWriter(w) => w.close()?,
+                    ColumnWriter::Int64ColumnWriter(w) => w.close()?,
+                    ColumnWriter::Int96ColumnWriter(w) => w.close()?,
+                    ColumnWriter::FloatColumnWriter(w) => w.close()?,
+                    ColumnWriter::DoubleColumnWriter(w) => w.close()?,
+                    ColumnWriter::ByteArrayColumnWriter(w) => w.close()?,
+                    ColumnWriter::FixedLenByteArrayColumnWriter(w) => w.close()?,
                 }
-                col_writer.close()
             }
+        };
+
+        Ok(ArrowColumnChunk {
+            data: buffer.into_inner().into(),
+            close,
+        })
+    }
+}
+
+/// The encoded pages and metadata of a column chunk, written by an [`ArrowColumnWriter`]
+#[derive(Debug)]
+pub struct ArrowColumnChunk {
+    data: Bytes,
+    close: ColumnCloseResult,
+}
+
+impl ArrowColumnChunk {
+    /// Appends this column chunk as the next column of `writer`
+    pub fn append_to_row_group<W: Write>(
+        self,
+        writer: &mut SerializedRowGroupWriter<'_, W>,
+    ) -> Result<()> {
+        writer.append_column(&self.data, self.close)
+    }
+}
+
+/// Returns an estimate of the size in bytes of the values of `data` referenced by its
+/// offset and length, as opposed to the size of its underlying buffers
+fn estimated_slice_size(data: &ArrayData) -> usize {
+    let len = data.len();
+    if len == 0 {
+        return 0;
+    }
+
+    let nulls = data
+        .null_buffer()
+        .map(|_| (len + 7) / 8)
+        .unwrap_or_default();
+    let values = match data.data_type() {
+        ArrowDataType::Null => 0,
+        ArrowDataType::Boolean => (len + 7) / 8,
+        ArrowDataType::Utf8 | ArrowDataType::Binary => {
+            let offsets = &data.buffer::<i32>(0)[..=len];
+            (len + 1) * 4 + (offsets[len] - offsets[0]) as usize
+        }
+        ArrowDataType::LargeUtf8 | ArrowDataType::LargeBinary => {
+            let offsets = &data.buffer::<i64>(0)[..=len];
+            (len + 1) * 8 + (offsets[len] - offsets[0]) as usize
+        }
+        ArrowDataType::List(_) | ArrowDataType::Map(_, _) => {
+            let offsets = &data.buffer::<i32>(0)[..=len];
+            let (start, end) = (offsets[0] as usize, offsets[len] as usize);
+            let child = data.child_data()[0].slice(start, end - start);
+            (len + 1) * 4 + estimated_slice_size(&child)
+        }
+        ArrowDataType::LargeList(_) => {
+            let offsets = &data.buffer::<i64>(0)[..=len];
+            let (start, end) = (offsets[0] as usize, offsets[len] as usize);
+            let child = data.child_data()[0].slice(start, end - start);
+            (len + 1) * 8 + estimated_slice_size(&child)
+        }
+        ArrowDataType::FixedSizeList(_, size) => {
+            let size = *size as usize;
+            let child = data.child_data()[0].slice(data.offset() * size, len * size);
+            estimated_slice_size(&child)
+        }
+        // Struct children are sliced along with the parent
+        ArrowDataType::Struct(_) => {
+            data.child_data().iter().map(estimated_slice_size).sum()
+        }
+        // The dictionary values are written in full for each row group
+        ArrowDataType::Dictionary(key_type, _) => {
+            let keys = fixed_width(key_type).unwrap_or_default() * len;
+            keys + estimated_slice_size(&data.child_data()[0])
+        }
+        data_type => match fixed_width(data_type) {
+            Some(byte_width) => byte_width * len,
+            None => data.get_buffer_memory_size(),
+        },
+    };
+    nulls + values
+}
+
+/// Returns the width in bytes of the values of `data_type` if it is fixed width
+fn fixed_width(data_type: &ArrowDataType) -> Option<usize> {
+    match layout(data_type).buffers.first() {
+        Some(BufferSpec::FixedWidth { byte_width }) => Some(*byte_width),
+        _ => None,
+    }
+}
+
+/// Returns true if the leaf arrays of `data_type` are written by a [`ByteArrayWriter`]
+fn is_byte_array_leaf(data_type: &ArrowDataType) -> bool {
+    match data_type {
+        ArrowDataType::Utf8
+        | ArrowDataType::LargeUtf8
+        | ArrowDataType::Binary
+        | ArrowDataType::LargeBinary => true,
+        ArrowDataType::Dictionary(_, value_type) => matches!(
+            value_type.as_ref(),
+            ArrowDataType::Utf8
+                | ArrowDataType::LargeUtf8
+                | ArrowDataType::Binary
+                | ArrowDataType::LargeBinary
+        ),
+        _ => false,
+    }
+}
+
+/// Writes `leaves`, consecutive slices of the same leaf column, to the next column
+/// of `row_group_writer`
+fn write_leaf_column<W: Write>(
+    row_group_writer: &mut SerializedRowGroupWriter<'_, W>,
+    leaves: &[ArrowLeafColumn],
+) -> Result<()> {
+    assert!(!leaves.is_empty());
+
+    let data_type = leaves[0].array.data_type();
+    assert!(leaves.iter().all(|l| l.array.data_type() == data_type));
+
+    if is_byte_array_leaf(data_type) {
+        let mut col_writer = row_group_writer
+            .next_column_with_factory(ByteArrayWriter::new)?
+            .unwrap();
+        for leaf in leaves {
+            col_writer.write(&leaf.array, &leaf.levels)?;
+        }
+        col_writer.close()
+    } else {
+        let mut col_writer = row_group_writer.next_column()?.unwrap();
+        for leaf in leaves {
+            write_leaf(col_writer.untyped(), &leaf.array, &leaf.levels)?;
+        }
+        col_writer.close()
+    }
+}
+
+/// Appends the leaf arrays of `array` to `leaves`, in the order of the leaf columns
+/// of its parquet schema
+fn collect_leaves(array: &ArrayRef, leaves: &mut Vec<ArrayRef>) -> Result<()> {
+    match array.data_type() {
+        ArrowDataType::List(_)
+        | ArrowDataType::LargeList(_)
+        | ArrowDataType::FixedSizeList(_, _) => {
+            let child = arrow_array::make_array(array.data().child_data()[0].clone());
+            collect_leaves(&child, leaves)
+        }
+        ArrowDataType::Struct(_) => {
+            let struct_array: &arrow_array::StructArray = array
+                .as_any()
+                .downcast_ref::<arrow_array::StructArray>()
+                .expect("Unable to get struct array");
+
+            for child in struct_array.columns() {
+                collect_leaves(child, leaves)?;
+            }
+            Ok(())
+        }
+        ArrowDataType::Map(_, _) => {
+            let map_array: &arrow_array::MapArray = array
+                .as_any()
+                .downcast_ref::<arrow_array::MapArray>()
+                .expect("Unable to get map array");
+
+            collect_leaves(&map_array.keys(), leaves)?;
+            collect_leaves(&map_array.values(), leaves)
         }
         ArrowDataType::Float16 => Err(ParquetError::ArrowError(
             "Float16 arrays not supported".to_string(),
         )),
-        ArrowDataType::FixedSizeList(_, _) | ArrowDataType::Union(_, _, _) => {
-            Err(ParquetError::NYI(
-                format!(
-                    "Attempting to write an Arrow type {:?} to parquet that is not yet implemented",
-                    data_type
-                )
-            ))
+        ArrowDataType::Union(_, _, _) => Err(ParquetError::NYI(format!(
+            "Attempting to write an Arrow type {:?} to parquet that is not yet implemented",
+            array.data_type()
+        ))),
+        _ => {
+            leaves.push(array.clone());
+            Ok(())
         }
     }
 }
 
+/// Returns `schema` with the unit of all timestamps, including those nested
+/// in lists, structs, maps and dictionaries, replaced by `unit`
+fn coerce_schema(schema: &Schema, unit: TimestampUnit) -> Schema {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|f| coerce_field(f, unit))
+        .collect();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+fn coerce_field(field: &Field, unit: TimestampUnit) -> Field {
+    let data_type = coerce_data_type(field.data_type(), unit);
+    field.clone().with_data_type(data_type)
+}
+
+fn coerce_data_type(data_type: &ArrowDataType, unit: TimestampUnit) -> ArrowDataType {
+    match data_type {
+        ArrowDataType::Timestamp(_, tz) => {
+            let unit = match unit {
+                TimestampUnit::Millis => TimeUnit::Millisecond,
+                TimestampUnit::Micros => TimeUnit::Microsecond,
+            };
+            ArrowDataType::Timestamp(unit, tz.clone())
+        }
+        ArrowDataType::List(f) => ArrowDataType::List(Box::new(coerce_field(f, unit))),
+        ArrowDataType::LargeList(f) => {
+            ArrowDataType::LargeList(Box::new(coerce_field(f, unit)))
+        }
+        ArrowDataType::FixedSizeList(f, size) => {
+            ArrowDataType::FixedSizeList(Box::new(coerce_field(f, unit)), *size)
+        }
+        ArrowDataType::Struct(fields) => {
+            ArrowDataType::Struct(fields.iter().map(|f| coerce_field(f, unit)).collect())
+        }
+        ArrowDataType::Map(f, sorted) => {
+            ArrowDataType::Map(Box::new(coerce_field(f, unit)), *sorted)
+        }
+        ArrowDataType::Dictionary(key, value) => ArrowDataType::Dictionary(
+            key.clone(),
+            Box::new(coerce_data_type(value, unit)),
+        ),
+        _ => data_type.clone(),
+    }
+}
+
+/// Returns the arrow [`TimeUnit`] of timestamps written to the INT64 column `descr`
+fn column_time_unit(descr: &ColumnDescPtr) -> TimeUnit {
+    match descr.logical_type() {
+        Some(LogicalType::Timestamp { unit, .. }) => match unit {
+            ParquetTimeUnit::MILLIS(_) => TimeUnit::Millisecond,
+            ParquetTimeUnit::MICROS(_) => TimeUnit::Microsecond,
+            ParquetTimeUnit::NANOS(_) => TimeUnit::Nanosecond,
+        },
+        _ => TimeUnit::Second,
+    }
+}
+
+/// Returns the number of nanoseconds in `unit`
+fn time_unit_nanos(unit: &TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    }
+}
+
+/// Converts the timestamps `values` from `from` to `to`, returning an error if any
+/// of the non-null values at `indices` cannot be represented in `to` without loss
+fn coerce_timestamps(
+    values: &[i64],
+    indices: &[usize],
+    from: &TimeUnit,
+    to: &TimeUnit,
+) -> Result<Vec<i64>> {
+    let from_nanos = time_unit_nanos(from);
+    let to_nanos = time_unit_nanos(to);
+
+    // Null slots are left as zero
+    let mut coerced = vec![0; values.len()];
+    for &idx in indices {
+        let value = values[idx];
+        let converted = if from_nanos >= to_nanos {
+            value.checked_mul(from_nanos / to_nanos)
+        } else {
+            let divisor = to_nanos / from_nanos;
+            (value % divisor == 0).then(|| value / divisor)
+        };
+        coerced[idx] = converted.ok_or_else(|| {
+            ParquetError::ArrowError(format!(
+                "Cannot coerce timestamp {} from {:?} to {:?} without loss",
+                value, from, to
+            ))
+        })?;
+    }
+    Ok(coerced)
+}
+
 fn write_leaf(
     writer: &mut ColumnWriter<'_>,
     column: &ArrayRef,
-    levels: LevelInfo,
+    levels: &LevelInfo,
 ) -> Result<i64> {
     let indices = levels.non_null_indices();
     let written = match writer {
@@ -428,6 +932,24 @@ fn write_leaf(
                     let array: &[i64] = data.buffers()[0].typed_data();
                     write_primitive(typed, &array[offset..offset + data.len()], levels)?
                 }
+                ArrowDataType::Timestamp(unit, _)
+                    if *unit != column_time_unit(typed.get_descriptor()) =>
+                {
+                    let to = column_time_unit(typed.get_descriptor());
+                    let array = arrow_cast::cast(column, &ArrowDataType::Int64)?;
+                    let array = array
+                        .as_any()
+                        .downcast_ref::<arrow_array::Int64Array>()
+                        .expect("Unable to get i64 array");
+                    let values = coerce_timestamps(array.values(), indices, unit, &to)?;
+                    write_primitive(typed, &values, levels)?
+                }
+                ArrowDataType::Dictionary(_, value)
+                    if matches!(value.as_ref(), ArrowDataType::Timestamp(_, _)) =>
+                {
+                    let array = arrow_cast::cast(column, value)?;
+                    return write_leaf(writer, &array, levels);
+                }
                 _ => {
                     let array = arrow_cast::cast(column, &ArrowDataType::Int64)?;
                     let array = array
@@ -496,7 +1018,14 @@ fn write_leaf(
                         .as_any()
                         .downcast_ref::<arrow_array::Decimal128Array>()
                         .unwrap();
-                    get_decimal_array_slice(array, indices)
+                    get_decimal_128_array_slice(array, indices)
+                }
+                ArrowDataType::Decimal256(_, _) => {
+                    let array = column
+                        .as_any()
+                        .downcast_ref::<arrow_array::Decimal256Array>()
+                        .unwrap();
+                    get_decimal_256_array_slice(array, indices)
                 }
                 _ => {
                     return Err(ParquetError::NYI(
@@ -518,7 +1047,7 @@ fn write_leaf(
 fn write_primitive<'a, T: DataType>(
     writer: &mut ColumnWriterImpl<'a, T>,
     values: &[T::T],
-    levels: LevelInfo,
+    levels: &LevelInfo,
 ) -> Result<usize> {
     writer.write_batch_internal(
         values,
@@ -575,7 +1104,7 @@ fn get_interval_dt_array_slice(
     values
 }
 
-fn get_decimal_array_slice(
+fn get_decimal_128_array_slice(
     array: &arrow_array::Decimal128Array,
     indices: &[usize],
 ) -> Vec<FixedLenByteArray> {
@@ -589,6 +1118,20 @@ fn get_decimal_array_slice(
     values
 }
 
+fn get_decimal_256_array_slice(
+    array: &arrow_array::Decimal256Array,
+    indices: &[usize],
+) -> Vec<FixedLenByteArray> {
+    let mut values = Vec::with_capacity(indices.len());
+    let size = decimal_length_from_precision(array.precision());
+    for i in indices {
+        let as_be_bytes = array.value(*i).to_be_bytes();
+        let resized_value = as_be_bytes[(32 - size)..].to_vec();
+        values.push(FixedLenByteArray::from(ByteArray::from(resized_value)));
+    }
+    values
+}
+
 fn get_fsb_array_slice(
     array: &arrow_array::FixedSizeBinaryArray,
     indices: &[usize],
@@ -610,16 +1153,19 @@ mod tests {
     use std::sync::Arc;
 
     use crate::arrow::arrow_reader::{
-        ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder,
+        ArrowReaderOptions, ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder,
     };
     use arrow::datatypes::ToByteSlice;
-    use arrow::datatypes::{DataType, Field, Schema, UInt32Type, UInt8Type};
+    use arrow::datatypes::{
+        DataType, Field, Int32Type, Schema, TimeUnit, UInt32Type, UInt8Type,
+    };
     use arrow::error::Result as ArrowResult;
     use arrow::util::pretty::pretty_format_batches;
     use arrow::{array::*, buffer::Buffer};
     use arrow_array::RecordBatch;
+    use arrow_buffer::i256;
 
-    use crate::basic::Encoding;
+    use crate::basic::{Compression, ConvertedType, Encoding};
     use crate::file::metadata::ParquetMetaData;
     use crate::file::page_index::index_reader::read_pages_locations;
     use crate::file::properties::{ReaderProperties, WriterVersion};
@@ -628,6 +1174,7 @@ mod tests {
         reader::{FileReader, SerializedFileReader},
         statistics::Statistics,
     };
+    use crate::record::RowAccessor;
 
     #[test]
     fn arrow_writer() {
@@ -852,6 +1399,55 @@ mod tests {
         roundtrip(batch, Some(SMALL_SIZE / 2));
     }
 
+    #[test]
+    fn arrow_writer_decimal256() {
+        let values = [
+            Some(i256::from_i128(10_000)),
+            None,
+            Some(i256::from_i128(-100)),
+            Some(i256::from_parts(u128::MAX, 12_345)),
+            Some(i256::from_parts(1, -12_345)),
+        ];
+
+        for precision in [5, 38, 60, 76] {
+            let array = values
+                .iter()
+                .map(|v| {
+                    // Only values within the 128-bit range are valid for lower precisions
+                    v.filter(|v| precision > 38 || v.to_i128().is_some())
+                })
+                .collect::<Decimal256Array>()
+                .with_precision_and_scale(precision, 2)
+                .unwrap();
+
+            let field = Field::new("a", array.data_type().clone(), true);
+            let batch = RecordBatch::try_new(
+                Arc::new(Schema::new(vec![field])),
+                vec![Arc::new(array)],
+            )
+            .unwrap();
+
+            let files = roundtrip(batch.clone(), Some(SMALL_SIZE / 2));
+
+            // Without the embedded arrow schema precisions above 38 are still
+            // read as Decimal256
+            let options = ArrowReaderOptions::new().with_skip_arrow_metadata(true);
+            let file = files.into_iter().next().unwrap();
+            let mut reader =
+                ParquetRecordBatchReaderBuilder::try_new_with_options(file, options)
+                    .unwrap()
+                    .build()
+                    .unwrap();
+            let expected = match precision > 38 {
+                true => DataType::Decimal256(precision, 2),
+                false => DataType::Decimal128(precision, 2),
+            };
+            let actual = reader.next().unwrap().unwrap();
+            assert_eq!(actual.column(0).data_type(), &expected);
+            assert_eq!(actual.column(0).null_count(), batch.column(0).null_count());
+        }
+    }
+
     #[test]
     fn arrow_writer_complex() {
         // define schema
@@ -989,22 +1585,25 @@ mod tests {
             Field::new("key", DataType::Utf8, false),
             Field::new("value", DataType::Utf8, true),
         ]);
-        let stocks_field = Field::new(
-            "stocks",
-            DataType::Map(
-                Box::new(Field::new("entries", entries_struct_type, false)),
-                false,
-            ),
-            true,
-        );
-        let schema = Arc::new(Schema::new(vec![stocks_field]));
-        let builder = arrow::json::ReaderBuilder::new()
-            .with_schema(schema)
-            .with_batch_size(64);
-        let mut reader = builder.build(std::io::Cursor::new(json_content)).unwrap();
-
-        let batch = reader.next().unwrap().unwrap();
-        roundtrip(batch, None);
+        // keys_sorted should be preserved
+        for sorted in [false, true] {
+            let stocks_field = Field::new(
+                "stocks",
+                DataType::Map(
+                    Box::new(Field::new("entries", entries_struct_type.clone(), false)),
+                    sorted,
+                ),
+                true,
+            );
+            let schema = Arc::new(Schema::new(vec![stocks_field]));
+            let builder = arrow::json::ReaderBuilder::new()
+                .with_schema(schema)
+                .with_batch_size(64);
+            let mut reader = builder.build(std::io::Cursor::new(json_content)).unwrap();
+
+            let batch = reader.next().unwrap().unwrap();
+            roundtrip(batch, None);
+        }
     }
 
     #[test]
@@ -1280,6 +1879,9 @@ mod tests {
             | DataType::UInt32
             | DataType::UInt16
             | DataType::UInt8 => vec![Encoding::PLAIN, Encoding::DELTA_BINARY_PACKED],
+            DataType::Float32 | DataType::Float64 => {
+                vec![Encoding::PLAIN, Encoding::BYTE_STREAM_SPLIT]
+            }
             _ => vec![Encoding::PLAIN],
         };
 
@@ -1622,6 +2224,23 @@ mod tests {
         values_required::<BinaryArray, _>(many_vecs_iter);
     }
 
+    #[test]
+    fn lz4_compressed_roundtrip() {
+        let array = Arc::new(Int32Array::from_iter(0..SMALL_SIZE as i32)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("col", array)]).unwrap();
+
+        for compression in [Compression::LZ4, Compression::LZ4_RAW] {
+            let props = WriterProperties::builder()
+                .set_compression(compression)
+                .build();
+            let file = roundtrip_opts(&batch, props);
+
+            let reader = SerializedFileReader::new(file).unwrap();
+            let column = reader.metadata().row_group(0).column(0);
+            assert_eq!(column.compression(), compression);
+        }
+    }
+
     #[test]
     fn i32_column_bloom_filter() {
         let array = Arc::new(Int32Array::from_iter(0..SMALL_SIZE as i32));
@@ -1714,6 +2333,66 @@ mod tests {
         required_and_optional::<LargeStringArray, _>(raw_strs);
     }
 
+    #[test]
+    fn string_delta_multiple_pages() {
+        // Values sharing long prefixes, so that DELTA_BYTE_ARRAY encodes prefixes
+        let raw_values: Vec<_> = (0..1000)
+            .map(|i| (i % 10 != 0).then(|| format!("shared_prefix_{:04}", i / 3)))
+            .collect();
+        let values = Arc::new(StringArray::from_iter(raw_values.iter())) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("col", values)]).unwrap();
+
+        for encoding in [
+            Encoding::DELTA_BYTE_ARRAY,
+            Encoding::DELTA_LENGTH_BYTE_ARRAY,
+        ] {
+            for version in [WriterVersion::PARQUET_1_0, WriterVersion::PARQUET_2_0] {
+                let props = WriterProperties::builder()
+                    .set_writer_version(version)
+                    .set_dictionary_enabled(false)
+                    .set_encoding(encoding)
+                    .set_data_page_row_count_limit(100)
+                    .set_write_batch_size(50)
+                    .build();
+
+                let mut buffer = Vec::with_capacity(1024);
+                let mut writer =
+                    ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props))
+                        .unwrap();
+                writer.write(&batch).unwrap();
+                writer.close().unwrap();
+                let data = Bytes::from(buffer);
+
+                let read = ParquetRecordBatchReaderBuilder::try_new(data.clone())
+                    .unwrap()
+                    .build()
+                    .unwrap()
+                    .collect::<ArrowResult<Vec<_>>>()
+                    .unwrap();
+                assert_eq!(read, vec![batch.clone()]);
+
+                // The pages are also decodable by the non-arrow decoders
+                let reader = SerializedFileReader::new(data).unwrap();
+                let column = reader.metadata().row_group(0).column(0);
+                assert!(column.encodings().contains(&encoding));
+                let pages = reader
+                    .get_row_group(0)
+                    .unwrap()
+                    .get_column_page_reader(0)
+                    .unwrap()
+                    .count();
+                assert_eq!(pages, 10);
+
+                let read: Vec<_> = reader
+                    .get_row_iter(None)
+                    .unwrap()
+                    .map(|row| row.get_string(0).ok().cloned())
+                    .collect();
+                assert_eq!(read, raw_values);
+            }
+        }
+    }
+
     #[test]
     fn null_list_single_column() {
         let null_field = Field::new("item", DataType::Null, true);
@@ -1821,6 +2500,116 @@ mod tests {
         one_column_roundtrip(Arc::new(list), true);
     }
 
+    #[test]
+    fn fixed_size_list_single_column() {
+        let mut builder = FixedSizeListBuilder::new(Int32Builder::new(), 2);
+
+        // [[1, 2], null, [null, 4], [5, 6]]
+        builder.values().append_value(1);
+        builder.values().append_value(2);
+        builder.append(true);
+        builder.values().append_null();
+        builder.values().append_null();
+        builder.append(false);
+        builder.values().append_null();
+        builder.values().append_value(4);
+        builder.append(true);
+        builder.values().append_value(5);
+        builder.values().append_value(6);
+        builder.append(true);
+
+        let values = Arc::new(builder.finish());
+        one_column_roundtrip(values.clone(), true);
+        one_column_roundtrip(Arc::new(values.slice(1, 3)), true);
+    }
+
+    #[test]
+    fn fixed_size_list_nested() {
+        // A list of fixed size lists
+        let mut builder =
+            ListBuilder::new(FixedSizeListBuilder::new(Int32Builder::new(), 3));
+
+        // [[[1, 2, 3], null], null, [], [[null, 5, 6]]]
+        let fixed = builder.values();
+        fixed.values().append_slice(&[1, 2, 3]);
+        fixed.append(true);
+        fixed.values().append_nulls(3);
+        fixed.append(false);
+        builder.append(true);
+        builder.append(false);
+        builder.append(true);
+        let fixed = builder.values();
+        fixed.values().append_null();
+        fixed.values().append_slice(&[5, 6]);
+        fixed.append(true);
+        builder.append(true);
+
+        let values = Arc::new(builder.finish());
+        one_column_roundtrip(values.clone(), true);
+        one_column_roundtrip(Arc::new(values.slice(1, 3)), true);
+    }
+
+    #[test]
+    fn deeply_nested_roundtrip() {
+        // a: List<Struct<b: List<Int32>, c: Int32>>
+        let b = Field::new(
+            "b",
+            DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
+            true,
+        );
+        let c = Field::new("c", DataType::Int32, true);
+        let item = Field::new("item", DataType::Struct(vec![b, c]), true);
+        let a = Field::new("a", DataType::List(Box::new(item)), true);
+
+        // d: non-nullable List<Struct<e: Int32>>
+        let e = Field::new("e", DataType::Int32, true);
+        let item = Field::new("item", DataType::Struct(vec![e]), true);
+        let d = Field::new("d", DataType::List(Box::new(item)), false);
+
+        // f: Struct<g: List<Struct<h: Utf8>>>
+        let h = Field::new("h", DataType::Utf8, true);
+        let item = Field::new("item", DataType::Struct(vec![h]), true);
+        let g = Field::new("g", DataType::List(Box::new(item)), true);
+        let f = Field::new("f", DataType::Struct(vec![g]), true);
+
+        let schema = Arc::new(Schema::new(vec![a, d, f]));
+        let json = r#"
+{"a": [{"b": [1, null, 2], "c": 1}, null, {"b": null, "c": null}, {"b": [], "c": 3}], "d": [{"e": 1}, null, {"e": null}], "f": {"g": [{"h": "x"}, null, {"h": null}]}}
+{"a": null, "d": [], "f": null}
+{"a": [], "d": [null], "f": {"g": null}}
+{"a": [null, {"b": [null], "c": 4}], "d": [{"e": 5}], "f": {"g": []}}
+"#;
+        let batch = arrow::json::ReaderBuilder::new()
+            .with_schema(schema)
+            .build(std::io::Cursor::new(json.trim()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        for (offset, len) in [(0, 4), (1, 3), (1, 2), (3, 1)] {
+            let batch = batch.slice(offset, len);
+            let file = tempfile::tempfile().unwrap();
+            let mut writer =
+                ArrowWriter::try_new(file.try_clone().unwrap(), batch.schema(), None)
+                    .unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+
+            let read = ParquetRecordBatchReader::try_new(file, 1024)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap();
+
+            // Compare the formatted output as null slots may differ in their child data
+            assert_eq!(
+                pretty_format_batches(&[batch]).unwrap().to_string(),
+                pretty_format_batches(&[read]).unwrap().to_string(),
+            );
+        }
+    }
+
     #[test]
     fn struct_single_column() {
         let a_values = Int32Array::from(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
@@ -2156,6 +2945,429 @@ mod tests {
         assert_eq!(&values, &expected_values)
     }
 
+    #[test]
+    fn test_in_progress_size() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("int", ArrowDataType::Int32, true),
+            Field::new("str", ArrowDataType::Utf8, false),
+        ]));
+
+        let ints = Int32Array::from_iter((0..100).map(|x| (x % 2 == 0).then_some(x)));
+        let strs = StringArray::from_iter_values((0..100).map(|x| format!("{:03}", x)));
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(ints), Arc::new(strs)])
+                .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(30)
+            .build();
+
+        let mut writer = ArrowWriter::try_new(Vec::new(), schema, Some(props)).unwrap();
+        assert_eq!(writer.in_progress_rows(), 0);
+        assert_eq!(writer.in_progress_size(), 0);
+        assert_eq!(writer.memory_size(), 0);
+
+        writer.write(&batch).unwrap();
+        assert_eq!(writer.flushed_row_groups().len(), 3);
+        assert_eq!(writer.in_progress_rows(), 10);
+
+        // 10 rows: int values and nulls, and str offsets and values
+        assert_eq!(writer.in_progress_size(), 40 + 2 + 44 + 30);
+
+        // The remaining rows are a slice of the written batch
+        let memory_size = writer.memory_size();
+        assert!(memory_size >= 400 + 404 + 300, "{}", memory_size);
+
+        writer.flush().unwrap();
+        assert_eq!(writer.in_progress_rows(), 0);
+        assert_eq!(writer.in_progress_size(), 0);
+        assert_eq!(writer.memory_size(), 0);
+    }
+
+    #[test]
+    fn test_append_key_value_metadata() {
+        let ints = Arc::new(Int32Array::from_iter_values(0..10)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("int", ints)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                "key".to_string(),
+                "value".to_string(),
+            )]))
+            .build();
+        let mut buf = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.append_key_value_metadata(KeyValue::new(
+            "num_rows".to_string(),
+            batch.num_rows().to_string(),
+        ));
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(Bytes::from(buf)).unwrap();
+        let kv: Vec<_> = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .map(|kv| kv.key.as_str())
+            .collect();
+        assert_eq!(
+            kv,
+            vec!["key", crate::arrow::ARROW_SCHEMA_META_KEY, "num_rows"]
+        );
+
+        let value = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .last()
+            .unwrap()
+            .value
+            .clone();
+        assert_eq!(value.as_deref(), Some("10"));
+    }
+
+    #[test]
+    fn test_write_reader() {
+        let ints = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let strs = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|x| format!("s{}", x)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("int", ints), ("str", strs)]).unwrap();
+
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buf);
+
+        // Read back in batches that do not align with the row groups
+        let reader = ParquetRecordBatchReaderBuilder::try_new(data.clone())
+            .unwrap()
+            .with_batch_size(7)
+            .build()
+            .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(30)
+            .build();
+        let mut buf = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buf, reader.schema(), Some(props)).unwrap();
+        assert_eq!(writer.write_reader(reader).unwrap(), 100);
+        assert_eq!(writer.in_progress_rows(), 10);
+        writer.close().unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf)).unwrap();
+        let row_groups: Vec<_> = builder
+            .metadata()
+            .row_groups()
+            .iter()
+            .map(|rg| rg.num_rows())
+            .collect();
+        assert_eq!(row_groups, vec![30, 30, 30, 10]);
+
+        let batches = builder
+            .with_batch_size(100)
+            .build()
+            .unwrap()
+            .collect::<ArrowResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches, vec![batch]);
+
+        // The schema of the reader must match that of the writer
+        let reader = ParquetRecordBatchReaderBuilder::try_new(data)
+            .unwrap()
+            .build()
+            .unwrap();
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("int", DataType::Int32, false)]));
+        let mut writer = ArrowWriter::try_new(Vec::new(), schema, None).unwrap();
+        let err = writer.write_reader(reader).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow: Record batch reader schema does not match writer schema"
+        );
+    }
+
+    #[test]
+    fn test_coerce_timestamps() {
+        let nanos = TimestampNanosecondArray::from(vec![Some(1_000), None, Some(-5_000)])
+            .with_timezone("UTC".to_string());
+        let seconds = TimestampSecondArray::from(vec![Some(1), Some(2), None]);
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1)]),
+            None,
+            Some(vec![]),
+        ]);
+        let list = ListArray::from(
+            list.data()
+                .clone()
+                .into_builder()
+                .data_type(DataType::List(Box::new(Field::new(
+                    "item",
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    true,
+                ))))
+                .child_data(vec![TimestampNanosecondArray::from(vec![3_000])
+                    .data()
+                    .clone()])
+                .build()
+                .unwrap(),
+        );
+        let batch = RecordBatch::try_from_iter([
+            ("nanos", Arc::new(nanos) as ArrayRef),
+            ("seconds", Arc::new(seconds) as ArrayRef),
+            ("list", Arc::new(list) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_coerce_timestamps(Some(TimestampUnit::Micros))
+            .build();
+        let mut buf = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf)).unwrap();
+        let parquet_schema = builder.metadata().file_metadata().schema_descr_ptr();
+        for column in parquet_schema.columns() {
+            assert_eq!(
+                column.logical_type(),
+                Some(LogicalType::Timestamp {
+                    is_adjusted_to_u_t_c: column.name() == "nanos",
+                    unit: ParquetTimeUnit::MICROS(Default::default()),
+                })
+            );
+            assert_eq!(column.converted_type(), ConvertedType::TIMESTAMP_MICROS);
+        }
+
+        let read = builder.build().unwrap().next().unwrap().unwrap();
+        let expected = coerce_schema(&batch.schema(), TimestampUnit::Micros);
+        assert_eq!(read.schema().as_ref(), &expected);
+
+        let nanos = read.column(0);
+        let nanos = nanos
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(
+            nanos,
+            &TimestampMicrosecondArray::from(vec![Some(1), None, Some(-5)])
+                .with_timezone("UTC".to_string())
+        );
+
+        let seconds = read.column(1);
+        let seconds = seconds
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(
+            seconds,
+            &TimestampMicrosecondArray::from(vec![
+                Some(1_000_000),
+                Some(2_000_000),
+                None
+            ])
+        );
+
+        let list = read.column(2);
+        let list = list.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(list.value_offsets(), &[0, 1, 1, 1]);
+        assert!(list.is_null(1));
+        assert_eq!(
+            list.values().data(),
+            TimestampMicrosecondArray::from(vec![3]).data()
+        );
+
+        // Coercing to a coarser unit must not lose precision
+        let props = WriterProperties::builder()
+            .set_coerce_timestamps(Some(TimestampUnit::Millis))
+            .build();
+        let mut writer =
+            ArrowWriter::try_new(Vec::new(), batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        let err = writer.close().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow: Cannot coerce timestamp 1000 from Nanosecond to Millisecond without loss"
+        );
+    }
+
+    #[test]
+    fn test_parallel_column_writers() {
+        let ints = Int32Array::from_iter((0..1000).map(|x| (x % 7 != 0).then_some(x)));
+        let strs =
+            StringArray::from_iter_values((0..1000).map(|x| format!("s{}", x % 13)));
+        let dict: DictionaryArray<arrow::datatypes::Int32Type> =
+            (0..1000).map(|x| ["a", "b", "c"][x % 3]).collect();
+        let lists = ListArray::from_iter_primitive::<arrow::datatypes::Int32Type, _, _>(
+            (0..1000).map(|x| (x % 5 != 0).then(|| vec![Some(x), None, Some(x + 1)])),
+        );
+        let structs = StructArray::from(vec![(
+            Field::new("a", DataType::Int64, true),
+            Arc::new(Int64Array::from_iter(
+                (0..1000).map(|x| (x % 3 != 0).then_some(x)),
+            )) as ArrayRef,
+        )]);
+
+        let batch = RecordBatch::try_from_iter([
+            ("ints", Arc::new(ints) as ArrayRef),
+            ("strs", Arc::new(strs) as ArrayRef),
+            ("dict", Arc::new(dict) as ArrayRef),
+            ("lists", Arc::new(lists) as ArrayRef),
+            ("structs", Arc::new(structs) as ArrayRef),
+        ])
+        .unwrap();
+        let schema = batch.schema();
+
+        let parquet_schema = arrow_to_parquet_schema(&schema).unwrap();
+        let mut props = WriterProperties::builder()
+            .set_write_page_checksums(true)
+            .set_max_row_group_size(400)
+            .build();
+        add_encoded_arrow_schema_to_metadata(&schema, &mut props);
+        let props = Arc::new(props);
+
+        let mut buf = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut buf,
+            parquet_schema.root_schema_ptr(),
+            props.clone(),
+        )
+        .unwrap();
+
+        for offset in (0..batch.num_rows()).step_by(400) {
+            let len = 400.min(batch.num_rows() - offset);
+            let writers = get_column_writers(&parquet_schema, &props, &schema).unwrap();
+            assert_eq!(writers.len(), parquet_schema.num_columns());
+
+            let mut workers = Vec::with_capacity(writers.len());
+            let mut writers = writers.into_iter();
+            for (field, column) in schema.fields().iter().zip(batch.columns()) {
+                for leaf in compute_leaves(field, &column.slice(offset, len)).unwrap() {
+                    let mut writer = writers.next().unwrap();
+                    workers.push(std::thread::spawn(move || {
+                        writer.write(&leaf)?;
+                        writer.close()
+                    }));
+                }
+            }
+            assert!(writers.next().is_none());
+
+            let mut row_group = writer.next_row_group().unwrap();
+            for worker in workers {
+                let chunk = worker.join().unwrap().unwrap();
+                chunk.append_to_row_group(&mut row_group).unwrap();
+            }
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+
+        let options = ReaderProperties::builder()
+            .set_verify_page_checksums(true)
+            .build();
+        let reader = SerializedFileReader::new_with_options(
+            Bytes::from(buf.clone()),
+            ReadOptionsBuilder::new()
+                .with_reader_properties(options)
+                .build(),
+        )
+        .unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 3);
+        assert_eq!(reader.get_row_iter(None).unwrap().count(), 1000);
+
+        let read: Vec<_> = ParquetRecordBatchReader::try_new(Bytes::from(buf), 1000)
+            .unwrap()
+            .collect::<ArrowResult<_>>()
+            .unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0], batch);
+    }
+
+    #[test]
+    fn test_compute_leaves_mismatched_field() {
+        let structs = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![3, 4])) as ArrayRef,
+            ),
+        ]);
+        let field = Field::new(
+            "s",
+            DataType::Struct(vec![Field::new("a", DataType::Int32, true)]),
+            true,
+        );
+        let err = compute_leaves(&field, &(Arc::new(structs) as ArrayRef)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Expected 1 leaf columns for field s, got 2"
+        );
+    }
+
+    #[test]
+    fn test_max_row_group_bytes() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "int",
+            ArrowDataType::Int32,
+            false,
+        )]));
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(300)
+            .set_max_row_group_bytes(Some(1000))
+            .build();
+
+        let file = tempfile::tempfile().unwrap();
+        let mut writer =
+            ArrowWriter::try_new(file.try_clone().unwrap(), schema.clone(), Some(props))
+                .unwrap();
+
+        for range in [0..100, 100..1100, 1100..1200] {
+            let array = Int32Array::from_iter_values(range);
+            let batch =
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+            writer.write(&batch).unwrap();
+            assert!(writer.in_progress_size() < 1000);
+        }
+        writer.close().unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        assert_eq!(
+            &row_group_sizes(builder.metadata()),
+            &[250, 250, 250, 250, 200]
+        );
+
+        let batches = builder
+            .build()
+            .unwrap()
+            .collect::<ArrowResult<Vec<_>>>()
+            .unwrap();
+
+        let values: Vec<_> = batches
+            .iter()
+            .flat_map(|x| {
+                x.column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        assert_eq!(values, (0..1200).collect::<Vec<_>>());
+    }
+
     #[test]
     fn complex_aggregate() {
         // Tests aggregating nested data
@@ -2233,17 +3445,17 @@ mod tests {
         // Verify data is as expected
 
         let expected = r#"
-            +-------------------------------------------------------------------------------------------------------------------------------------+
-            | struct_b                                                                                                                            |
-            +-------------------------------------------------------------------------------------------------------------------------------------+
-            | {"list": [{"leaf_a": 1, "leaf_b": 1}]}                                                                                              |
-            | {"list": null}                                                                                                                      |
-            | {"list": [{"leaf_a": 2, "leaf_b": null}, {"leaf_a": 3, "leaf_b": 2}]}                                                               |
-            | {"list": null}                                                                                                                      |
-            | {"list": [{"leaf_a": 4, "leaf_b": null}, {"leaf_a": 5, "leaf_b": null}]}                                                            |
-            | {"list": [{"leaf_a": 6, "leaf_b": null}, {"leaf_a": 7, "leaf_b": null}, {"leaf_a": 8, "leaf_b": null}, {"leaf_a": 9, "leaf_b": 1}]} |
-            | {"list": [{"leaf_a": 10, "leaf_b": null}]}                                                                                          |
-            +-------------------------------------------------------------------------------------------------------------------------------------+
+            +-------------------------------------------------------------------------------------------------------------------+
+            | struct_b                                                                                                          |
+            +-------------------------------------------------------------------------------------------------------------------+
+            | {list: [{leaf_a: 1, leaf_b: 1}]}                                                                                  |
+            | {list: null}                                                                                                      |
+            | {list: [{leaf_a: 2, leaf_b: null}, {leaf_a: 3, leaf_b: 2}]}                                                       |
+            | {list: null}                                                                                                      |
+            | {list: [{leaf_a: 4, leaf_b: null}, {leaf_a: 5, leaf_b: null}]}                                                    |
+            | {list: [{leaf_a: 6, leaf_b: null}, {leaf_a: 7, leaf_b: null}, {leaf_a: 8, leaf_b: null}, {leaf_a: 9, leaf_b: 1}]} |
+            | {list: [{leaf_a: 10, leaf_b: null}]}                                                                              |
+            +-------------------------------------------------------------------------------------------------------------------+
         "#.trim().split('\n').map(|x| x.trim()).collect::<Vec<_>>().join("\n");
 
         let actual = pretty_format_batches(batches).unwrap().to_string();
@@ -2284,4 +3496,259 @@ mod tests {
         let actual = pretty_format_batches(&batches).unwrap().to_string();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    #[cfg(feature = "encryption")]
+    fn test_write_encrypted() {
+        use crate::arrow::arrow_reader::ArrowReaderOptions;
+        use crate::arrow::ProjectionMask;
+        use crate::encryption::decrypt::FileDecryptionProperties;
+        use crate::encryption::encrypt::{FileEncryptionProperties, ParquetCipher};
+        use crate::schema::types::ColumnPath;
+
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i % 7)),
+        )) as ArrayRef;
+        let c = Arc::new(Int64Array::from_iter(
+            (0..100).map(|i| (i % 3 != 0).then_some(i)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b), ("c", c)]).unwrap();
+
+        let footer_key = b"0123456789012345".to_vec();
+        let column_key = b"1234567890123450".to_vec();
+        let write = |encryption_properties: FileEncryptionProperties| {
+            let props = WriterProperties::builder()
+                .set_max_row_group_size(50)
+                .set_data_page_row_count_limit(10)
+                .set_write_batch_size(10)
+                .set_bloom_filter_enabled(true)
+                .set_file_encryption_properties(encryption_properties)
+                .build();
+            let mut buffer = Vec::with_capacity(1024);
+            let mut writer =
+                ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+            Bytes::from(buffer)
+        };
+        let read = |data: &Bytes, decryption_properties: FileDecryptionProperties| {
+            let options = ArrowReaderOptions::new()
+                .with_file_decryption_properties(decryption_properties);
+            let batches = ParquetRecordBatchReaderBuilder::try_new_with_options(
+                data.clone(),
+                options,
+            )?
+            .build()?
+            .collect::<ArrowResult<Vec<_>>>()?;
+            Ok::<_, ParquetError>(
+                arrow_select::concat::concat_batches(&batch.schema(), &batches).unwrap(),
+            )
+        };
+
+        for cipher in [ParquetCipher::AesGcmV1, ParquetCipher::AesGcmCtrV1] {
+            for plaintext_footer in [false, true] {
+                // All columns encrypted with the footer key
+                let data = write(
+                    FileEncryptionProperties::builder(footer_key.clone())
+                        .with_plaintext_footer(plaintext_footer)
+                        .with_cipher(cipher)
+                        .build()
+                        .unwrap(),
+                );
+                let decryption_properties =
+                    FileDecryptionProperties::builder(footer_key.clone())
+                        .build()
+                        .unwrap();
+                assert_eq!(read(&data, decryption_properties).unwrap(), batch);
+
+                let wrong_key = FileDecryptionProperties::builder(column_key.clone())
+                    .build()
+                    .unwrap();
+                read(&data, wrong_key).unwrap_err();
+
+                // Only column b encrypted with its own key
+                let data = write(
+                    FileEncryptionProperties::builder(footer_key.clone())
+                        .with_column_key(ColumnPath::from("b"), column_key.clone())
+                        .with_plaintext_footer(plaintext_footer)
+                        .with_cipher(cipher)
+                        .build()
+                        .unwrap(),
+                );
+                let decryption_properties =
+                    FileDecryptionProperties::builder(footer_key.clone())
+                        .with_column_key(ColumnPath::from("b"), column_key.clone())
+                        .build()
+                        .unwrap();
+                assert_eq!(read(&data, decryption_properties).unwrap(), batch);
+
+                let missing_key = FileDecryptionProperties::builder(footer_key.clone())
+                    .build()
+                    .unwrap();
+                let err = read(&data, missing_key).unwrap_err().to_string();
+                assert!(
+                    err.contains("No decryption key provided for encrypted column b"),
+                    "{}",
+                    err
+                );
+
+                if plaintext_footer {
+                    // The unencrypted columns can be read without any keys
+                    let builder =
+                        ParquetRecordBatchReaderBuilder::try_new(data.clone()).unwrap();
+                    let metadata = builder.metadata().row_group(0);
+                    assert!(metadata.column(0).crypto_metadata().is_none());
+                    assert!(metadata.column(0).bloom_filter_offset().is_some());
+                    assert!(metadata.column(1).crypto_metadata().is_some());
+                    assert!(metadata.column(1).statistics().is_none());
+                    assert!(metadata.column(1).bloom_filter_offset().is_none());
+                    assert!(metadata.column(1).offset_index_offset().is_none());
+
+                    let mask = ProjectionMask::leaves(builder.parquet_schema(), [0, 2]);
+                    let batches = builder
+                        .with_projection(mask)
+                        .build()
+                        .unwrap()
+                        .collect::<ArrowResult<Vec<_>>>()
+                        .unwrap();
+                    let expected = batch.project(&[0, 2]).unwrap();
+                    let read = arrow_select::concat::concat_batches(
+                        &expected.schema(),
+                        &batches,
+                    )
+                    .unwrap();
+                    assert_eq!(read, expected);
+                }
+            }
+        }
+
+        // An AAD prefix that isn't stored must be supplied by readers
+        let data = write(
+            FileEncryptionProperties::builder(footer_key.clone())
+                .with_aad_prefix(b"prefix".to_vec())
+                .with_aad_prefix_storage(false)
+                .build()
+                .unwrap(),
+        );
+        let decryption_properties = FileDecryptionProperties::builder(footer_key.clone())
+            .build()
+            .unwrap();
+        let err = read(&data, decryption_properties).unwrap_err().to_string();
+        assert!(err.contains("no AAD prefix was supplied"), "{}", err);
+
+        let decryption_properties = FileDecryptionProperties::builder(footer_key.clone())
+            .with_aad_prefix(b"other".to_vec())
+            .build()
+            .unwrap();
+        read(&data, decryption_properties).unwrap_err();
+
+        let decryption_properties = FileDecryptionProperties::builder(footer_key)
+            .with_aad_prefix(b"prefix".to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(read(&data, decryption_properties).unwrap(), batch);
+    }
+
+    #[test]
+    #[cfg(feature = "encryption")]
+    fn test_write_encrypted_kms() {
+        use crate::arrow::arrow_reader::ArrowReaderOptions;
+        use crate::encryption::decrypt::FileDecryptionProperties;
+        use crate::encryption::encrypt::{FileEncryptionProperties, ParquetCipher};
+        use crate::encryption::kms::tests::TestKmsClient;
+        use crate::encryption::kms::{generate_data_key, KmsKeyRetriever};
+        use crate::schema::types::ColumnPath;
+
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+
+        let client = Arc::new(TestKmsClient::new(&[
+            ("footer", b"0123456789012345"),
+            ("column", b"1234567890123450"),
+        ]));
+        let (footer_key, footer_metadata) =
+            generate_data_key(client.as_ref(), "footer", 16, true).unwrap();
+        let (column_key, column_metadata) =
+            generate_data_key(client.as_ref(), "column", 32, false).unwrap();
+
+        let write = |encryption_properties: FileEncryptionProperties| {
+            let props = WriterProperties::builder()
+                .set_max_row_group_size(25)
+                .set_file_encryption_properties(encryption_properties)
+                .build();
+            let mut buffer = Vec::with_capacity(1024);
+            let mut writer =
+                ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+            Bytes::from(buffer)
+        };
+        let read = |data: &Bytes, decryption_properties: FileDecryptionProperties| {
+            let options = ArrowReaderOptions::new()
+                .with_file_decryption_properties(decryption_properties);
+            let batches = ParquetRecordBatchReaderBuilder::try_new_with_options(
+                data.clone(),
+                options,
+            )?
+            .build()?
+            .collect::<ArrowResult<Vec<_>>>()?;
+            Ok::<_, ParquetError>(
+                arrow_select::concat::concat_batches(&batch.schema(), &batches).unwrap(),
+            )
+        };
+        let retriever = Arc::new(KmsKeyRetriever::new(client.clone()));
+
+        for cipher in [ParquetCipher::AesGcmV1, ParquetCipher::AesGcmCtrV1] {
+            for plaintext_footer in [false, true] {
+                let data = write(
+                    FileEncryptionProperties::builder(footer_key.clone())
+                        .with_footer_key_metadata(footer_metadata.clone())
+                        .with_column_key_and_metadata(
+                            ColumnPath::from("b"),
+                            column_key.clone(),
+                            column_metadata.clone(),
+                        )
+                        .with_plaintext_footer(plaintext_footer)
+                        .with_cipher(cipher)
+                        .build()
+                        .unwrap(),
+                );
+
+                // The keys of the footer and column b are each unwrapped once, even
+                // though column b is in four row groups
+                *client.unwrapped.lock().unwrap() = 0;
+                let decryption_properties =
+                    FileDecryptionProperties::with_key_retriever(retriever.clone())
+                        .build()
+                        .unwrap();
+                assert_eq!(read(&data, decryption_properties).unwrap(), batch);
+                assert_eq!(*client.unwrapped.lock().unwrap(), 2);
+
+                // Explicit keys take precedence over the key retriever
+                let decryption_properties =
+                    FileDecryptionProperties::with_key_retriever(retriever.clone())
+                        .with_column_key(ColumnPath::from("b"), footer_key.clone())
+                        .build()
+                        .unwrap();
+                read(&data, decryption_properties).unwrap_err();
+            }
+        }
+
+        // Keys can't be retrieved for a file without key metadata
+        let data = write(
+            FileEncryptionProperties::builder(footer_key)
+                .build()
+                .unwrap(),
+        );
+        let decryption_properties =
+            FileDecryptionProperties::with_key_retriever(retriever)
+                .build()
+                .unwrap();
+        let err = read(&data, decryption_properties).unwrap_err().to_string();
+        assert!(err.contains("no footer key metadata"), "{}", err);
+    }
 }
@@ -0,0 +1,459 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::future::Future;
+use std::io::Cursor;
+use std::ops::Range;
+
+use bytes::Bytes;
+use futures::future::{BoxFuture, FutureExt};
+use thrift::protocol::{TCompactInputProtocol, TSerializable};
+
+use crate::arrow::async_reader::AsyncFileReader;
+use crate::errors::{ParquetError, Result};
+use crate::file::footer::{decode_footer, decode_metadata};
+use crate::file::metadata::ParquetMetaData;
+use crate::file::page_index::index_reader;
+use crate::file::FOOTER_SIZE;
+use crate::format::OffsetIndex;
+
+/// A data source that can be used with [`MetadataLoader`] to load [`ParquetMetaData`]
+pub trait MetadataFetch {
+    /// Retrieve the bytes in `range`
+    fn fetch(&mut self, range: Range<usize>) -> BoxFuture<'_, Result<Bytes>>;
+}
+
+impl<T: AsyncFileReader> MetadataFetch for &mut T {
+    fn fetch(&mut self, range: Range<usize>) -> BoxFuture<'_, Result<Bytes>> {
+        self.get_bytes(range)
+    }
+}
+
+/// An asynchronous interface to load [`ParquetMetaData`] from an async source
+///
+/// This allows the footer, and optionally the page index, of a parquet file to be
+/// decoded once and the resulting [`ParquetMetaData`] cached and reused, for example
+/// by an implementation of [`AsyncFileReader::get_metadata`]
+pub struct MetadataLoader<F> {
+    /// Function that fetches byte ranges asynchronously
+    fetch: F,
+    /// The in-progress metadata
+    metadata: ParquetMetaData,
+    /// The offset and bytes of remaining unparsed data, fetched along with the footer
+    remainder: Option<(usize, Bytes)>,
+}
+
+impl<F: MetadataFetch> MetadataLoader<F> {
+    /// Create a new [`MetadataLoader`] by reading the footer information of a file
+    /// of `file_size` bytes
+    ///
+    /// The length of the metadata is not known up front, and so by default this will
+    /// first fetch the footer to determine this length, before fetching the metadata
+    /// itself in a second request.
+    ///
+    /// If `prefetch` is provided, the first request will instead fetch this many bytes
+    /// from the end of the file, avoiding the second request if the metadata and footer
+    /// fit within it. Any additional bytes fetched are retained and used to avoid
+    /// fetching the page index where possible
+    pub async fn load(
+        mut fetch: F,
+        file_size: usize,
+        prefetch: Option<usize>,
+    ) -> Result<Self> {
+        if file_size < FOOTER_SIZE {
+            return Err(eof_err!(
+                "file size of {} is less than footer size of {}",
+                file_size,
+                FOOTER_SIZE
+            ));
+        }
+
+        let hint = prefetch.unwrap_or_default().max(FOOTER_SIZE);
+        let suffix_start = file_size.saturating_sub(hint);
+        let suffix = fetch.fetch(suffix_start..file_size).await?;
+        if suffix.len() != file_size - suffix_start {
+            return Err(eof_err!(
+                "expected to fetch {} bytes, got {}",
+                file_size - suffix_start,
+                suffix.len()
+            ));
+        }
+
+        let footer_start = suffix.len() - FOOTER_SIZE;
+        let mut footer = [0; FOOTER_SIZE];
+        footer.copy_from_slice(&suffix[footer_start..]);
+        let metadata_len = decode_footer(&footer)?;
+
+        if file_size < metadata_len + FOOTER_SIZE {
+            return Err(eof_err!(
+                "file size of {} is less than footer + metadata size of {}",
+                file_size,
+                metadata_len + FOOTER_SIZE
+            ));
+        }
+
+        let (metadata, remainder) = if metadata_len <= footer_start {
+            // The prefetch was large enough to fetch all the metadata
+            let metadata_start = footer_start - metadata_len;
+            let metadata = decode_metadata(&suffix[metadata_start..footer_start])?;
+            let remainder = (metadata_start != 0)
+                .then(|| (suffix_start, suffix.slice(..metadata_start)));
+            (metadata, remainder)
+        } else {
+            // Fetch the remainder of the metadata not contained in the suffix
+            let metadata_start = file_size - metadata_len - FOOTER_SIZE;
+            let remainder = fetch.fetch(metadata_start..suffix_start).await?;
+
+            let mut metadata = Vec::with_capacity(metadata_len);
+            metadata.extend_from_slice(&remainder);
+            metadata.extend_from_slice(&suffix[..footer_start]);
+            (decode_metadata(&metadata)?, None)
+        };
+
+        Ok(Self {
+            fetch,
+            metadata,
+            remainder,
+        })
+    }
+
+    /// Create a new [`MetadataLoader`] from already decoded [`ParquetMetaData`],
+    /// for example to subsequently load its page index with [`Self::load_page_index`]
+    pub fn new(fetch: F, metadata: ParquetMetaData) -> Self {
+        Self {
+            fetch,
+            metadata,
+            remainder: None,
+        }
+    }
+
+    /// Loads the column index and / or the offset index of the file
+    ///
+    /// The page index of all row groups is fetched in a single request. If any row
+    /// group is missing the requested index data, the metadata is left unchanged
+    pub async fn load_page_index(
+        &mut self,
+        column_index: bool,
+        offset_index: bool,
+    ) -> Result<()> {
+        if !column_index && !offset_index {
+            return Ok(());
+        }
+
+        let mut range: Option<Range<usize>> = None;
+        let mut locations = Vec::with_capacity(self.metadata.num_row_groups());
+        let mut indexes = Vec::with_capacity(self.metadata.num_row_groups());
+
+        for rg in self.metadata.row_groups() {
+            if offset_index {
+                let (offset, length) =
+                    index_reader::get_location_offset_and_total_length(rg.columns())?;
+                if length == 0 {
+                    return Ok(());
+                }
+                let r = offset as usize..offset as usize + length;
+                range = Some(union(range, &r));
+                locations.push(r);
+            }
+
+            if column_index {
+                let (offset, lengths) =
+                    index_reader::get_index_offset_and_lengths(rg.columns())?;
+                let length = lengths.iter().sum::<usize>();
+                if length == 0 {
+                    return Ok(());
+                }
+                let r = offset as usize..offset as usize + length;
+                range = Some(union(range, &r));
+                indexes.push((r.start, lengths));
+            }
+        }
+
+        let range = match range {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+
+        let data = match &self.remainder {
+            Some((start, remainder))
+                if *start <= range.start && range.end <= start + remainder.len() =>
+            {
+                remainder.slice(range.start - start..range.end - start)
+            }
+            _ => self.fetch.fetch(range.clone()).await?,
+        };
+
+        let mut row_groups = self.metadata.row_groups().to_vec();
+
+        let offset_indexes = match offset_index {
+            true => {
+                let offset_indexes = row_groups
+                    .iter_mut()
+                    .zip(locations)
+                    .map(|(rg, location)| {
+                        let data = data.slice(location.start - range.start..);
+                        let mut cursor = Cursor::new(data);
+                        let offset_index = (0..rg.columns().len())
+                            .map(|_| {
+                                let mut prot = TCompactInputProtocol::new(&mut cursor);
+                                let offset =
+                                    OffsetIndex::read_from_in_protocol(&mut prot)?;
+                                Ok(offset.page_locations)
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        rg.set_page_offset(offset_index.clone());
+                        Ok(offset_index)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Some(offset_indexes)
+            }
+            false => self.metadata.offset_indexes().cloned(),
+        };
+
+        let column_indexes = match column_index {
+            true => {
+                let column_indexes = row_groups
+                    .iter()
+                    .zip(indexes)
+                    .map(|(rg, (offset, lengths))| {
+                        let mut start = offset - range.start;
+                        rg.columns()
+                            .iter()
+                            .zip(lengths)
+                            .map(|(column, length)| {
+                                let data = &data[start..start + length];
+                                start += length;
+                                index_reader::deserialize_column_index(
+                                    data,
+                                    column.column_type(),
+                                )
+                            })
+                            .collect::<Result<Vec<_>>>()
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Some(column_indexes)
+            }
+            false => self.metadata.page_indexes().cloned(),
+        };
+
+        let metadata = ParquetMetaData::new_with_page_index(
+            self.metadata.file_metadata().clone(),
+            row_groups,
+            column_indexes,
+            offset_indexes,
+        );
+
+        #[cfg(feature = "encryption")]
+        let metadata =
+            metadata.with_file_decryptor(self.metadata.file_decryptor().cloned());
+
+        self.metadata = metadata;
+        Ok(())
+    }
+
+    /// Returns the finished [`ParquetMetaData`]
+    pub fn finish(self) -> ParquetMetaData {
+        self.metadata
+    }
+}
+
+/// Returns the smallest range containing both `a` and `b`
+fn union(a: Option<Range<usize>>, b: &Range<usize>) -> Range<usize> {
+    match a {
+        Some(a) => a.start.min(b.start)..a.end.max(b.end),
+        None => b.clone(),
+    }
+}
+
+/// Adapts a closure returning a future to a [`MetadataFetch`]
+struct MetadataFetchFn<F>(F);
+
+impl<F, Fut> MetadataFetch for MetadataFetchFn<F>
+where
+    F: FnMut(Range<usize>) -> Fut + Send,
+    Fut: Future<Output = Result<Bytes>> + Send,
+{
+    fn fetch(&mut self, range: Range<usize>) -> BoxFuture<'_, Result<Bytes>> {
+        async move { self.0(range).await }.boxed()
+    }
+}
+
+/// Fetches the [`ParquetMetaData`] of a parquet file of `file_size` bytes, using `fetch`
+/// to retrieve byte ranges of the file
+///
+/// The length of the metadata is not known up front, and so by default this will
+/// first fetch the footer to determine this length, before fetching the metadata
+/// itself in a second request.
+///
+/// If `footer_size_hint` is provided, the first request will instead fetch this many
+/// bytes from the end of the file, avoiding the second request if the metadata and
+/// footer fit within the hint
+///
+/// See [`MetadataLoader`] to additionally load the page index
+pub async fn fetch_parquet_metadata<F, Fut>(
+    fetch: F,
+    file_size: usize,
+    footer_size_hint: Option<usize>,
+) -> Result<ParquetMetaData>
+where
+    F: FnMut(Range<usize>) -> Fut + Send,
+    Fut: Future<Output = Result<Bytes>> + Send,
+{
+    let loader =
+        MetadataLoader::load(MetadataFetchFn(fetch), file_size, footer_size_hint).await?;
+    Ok(loader.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::ArrowWriter;
+    use crate::file::properties::WriterProperties;
+    use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+    use futures::FutureExt;
+    use std::sync::{Arc, Mutex};
+
+    fn write_file() -> Bytes {
+        let col = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("col", col)]).unwrap();
+
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        buffer.into()
+    }
+
+    /// Fetches the metadata of `data`, returning it along with the fetched ranges
+    fn fetch(data: &Bytes, hint: Option<usize>) -> (ParquetMetaData, Vec<Range<usize>>) {
+        let mut fetches = vec![];
+        let fetch = |range: Range<usize>| {
+            fetches.push(range.clone());
+            futures::future::ready(Ok(data.slice(range)))
+        };
+
+        let metadata = fetch_parquet_metadata(fetch, data.len(), hint)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.file_metadata().num_rows(), 100);
+        (metadata, fetches)
+    }
+
+    #[test]
+    fn test_fetch_parquet_metadata() {
+        let data = write_file();
+        let len = data.len();
+
+        let (_, fetches) = fetch(&data, None);
+        assert_eq!(fetches.len(), 2);
+        assert_eq!(fetches[0], len - FOOTER_SIZE..len);
+
+        // A hint larger than the metadata only requires a single fetch
+        let (_, fetches) = fetch(&data, Some(len));
+        assert_eq!(fetches, vec![0..len]);
+
+        // A hint larger than the file is clamped to the file size
+        let (_, fetches) = fetch(&data, Some(len * 2));
+        assert_eq!(fetches, vec![0..len]);
+
+        // A hint smaller than the metadata requires a second fetch of the remainder
+        let (_, fetches) = fetch(&data, Some(20));
+        assert_eq!(fetches.len(), 2);
+        assert_eq!(fetches[0], len - 20..len);
+        assert_eq!(fetches[1].end, len - 20);
+
+        let fetch = |range: Range<usize>| futures::future::ready(Ok(data.slice(range)));
+        let err = fetch_parquet_metadata(fetch, 4, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("file size of 4 is less than footer"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_metadata_loader_page_index() {
+        let col = Arc::new(Int32Array::from_iter_values(0..1000)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("col", col)]).unwrap();
+        let props = WriterProperties::builder()
+            .set_data_pagesize_limit(256)
+            .set_write_batch_size(100)
+            .build();
+
+        let mut buffer = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+        let len = data.len();
+
+        let fetches = Mutex::new(vec![]);
+        let fetch = |range: Range<usize>| {
+            fetches.lock().unwrap().push(range.clone());
+            futures::future::ready(Ok(data.slice(range)))
+        };
+
+        // Without a prefetch the page index requires an additional fetch
+        let mut loader = MetadataLoader::load(MetadataFetchFn(&fetch), len, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert!(loader.metadata.page_indexes().is_none());
+        loader
+            .load_page_index(true, true)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        let metadata = loader.finish();
+        assert_eq!(fetches.lock().unwrap().len(), 3);
+
+        let column_index = metadata.page_indexes().unwrap();
+        let offset_index = metadata.offset_indexes().unwrap();
+        assert_eq!(column_index.len(), 1);
+        assert_eq!(offset_index[0].len(), 1);
+        assert!(offset_index[0][0].len() > 1);
+        assert_eq!(
+            metadata.row_group(0).page_offset_index().unwrap(),
+            &offset_index[0]
+        );
+
+        // A prefetch of the whole file is used to satisfy the page index
+        fetches.lock().unwrap().clear();
+        let mut loader = MetadataLoader::load(MetadataFetchFn(&fetch), len, Some(len))
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        loader
+            .load_page_index(true, true)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(*fetches.lock().unwrap(), vec![0..len]);
+        let loaded = loader.finish();
+        assert_eq!(loaded.offset_indexes(), metadata.offset_indexes());
+        assert_eq!(
+            loaded.page_indexes().unwrap()[0].len(),
+            column_index[0].len()
+        );
+    }
+}
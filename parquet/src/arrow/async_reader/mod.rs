@@ -78,18 +78,16 @@
 use std::collections::VecDeque;
 use std::fmt::Formatter;
 
-use std::io::{Cursor, SeekFrom};
+use std::io::SeekFrom;
 use std::ops::Range;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use crate::format::OffsetIndex;
 use bytes::{Buf, Bytes};
 use futures::future::{BoxFuture, FutureExt};
 use futures::ready;
 use futures::stream::Stream;
-use thrift::protocol::{TCompactInputProtocol, TSerializable};
 
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 
@@ -98,24 +96,38 @@ use arrow_schema::SchemaRef;
 
 use crate::arrow::array_reader::{build_array_reader, RowGroupCollection};
 use crate::arrow::arrow_reader::{
-    evaluate_predicate, selects_any, ArrowReaderBuilder, ArrowReaderOptions,
-    ParquetRecordBatchReader, RowFilter, RowSelection,
+    evaluate_predicate, reader_properties, selects_any, ArrowReaderBuilder,
+    ArrowReaderMetadata, ArrowReaderOptions, Int96Overflow, ParquetRecordBatchReader,
+    RowFilter, RowSelection,
 };
 use crate::arrow::schema::ParquetField;
 use crate::arrow::ProjectionMask;
 
+use crate::bloom_filter::{
+    bitset_length, read_bloom_filter_header_and_length, Sbbf, SBBF_HEADER_SIZE_ESTIMATE,
+};
 use crate::column::page::{PageIterator, PageReader};
 
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::FileDecryptor;
 use crate::errors::{ParquetError, Result};
 use crate::file::footer::{decode_footer, decode_metadata};
 use crate::file::metadata::{ParquetMetaData, RowGroupMetaData};
+use crate::file::properties::ReaderPropertiesPtr;
 use crate::file::reader::{ChunkReader, Length, SerializedPageReader};
 
-use crate::file::page_index::index_reader;
 use crate::file::FOOTER_SIZE;
 
 use crate::schema::types::{ColumnDescPtr, SchemaDescPtr};
 
+mod metadata;
+pub use metadata::{fetch_parquet_metadata, MetadataFetch, MetadataLoader};
+
+#[cfg(feature = "object_store")]
+mod store;
+#[cfg(feature = "object_store")]
+pub use store::ParquetObjectReader;
+
 /// The asynchronous interface used by [`ParquetRecordBatchStream`] to read parquet files
 pub trait AsyncFileReader: Send {
     /// Retrieve the bytes in `range`
@@ -191,8 +203,8 @@ impl<T: AsyncRead + AsyncSeek + Unpin + Send> AsyncFileReader for T {
             self.seek(SeekFrom::End(-FOOTER_SIZE_I64 - metadata_len as i64))
                 .await?;
 
-            let mut buf = Vec::with_capacity(metadata_len);
-            self.read_to_end(&mut buf).await?;
+            let mut buf = vec![0; metadata_len];
+            self.read_exact(&mut buf).await?;
 
             Ok(Arc::new(decode_metadata(&buf)?))
         }
@@ -215,15 +227,12 @@ pub struct AsyncReader<T>(T);
 ///
 pub type ParquetRecordBatchStreamBuilder<T> = ArrowReaderBuilder<AsyncReader<T>>;
 
-impl<T: AsyncFileReader + Send + 'static> ArrowReaderBuilder<AsyncReader<T>> {
-    /// Create a new [`ParquetRecordBatchStreamBuilder`] with the provided parquet file
-    pub async fn new(mut input: T) -> Result<Self> {
-        let metadata = input.get_metadata().await?;
-        Self::new_builder(AsyncReader(input), metadata, Default::default())
-    }
-
-    pub async fn new_with_options(
-        mut input: T,
+impl ArrowReaderMetadata {
+    /// Returns a new [`ArrowReaderMetadata`] for this builder
+    ///
+    /// See [`ParquetRecordBatchStreamBuilder::new_with_metadata`] for how this can be used
+    pub async fn load_async<T: AsyncFileReader>(
+        input: &mut T,
         options: ArrowReaderOptions,
     ) -> Result<Self> {
         let mut metadata = input.get_metadata().await?;
@@ -234,89 +243,130 @@ impl<T: AsyncFileReader + Send + 'static> ArrowReaderBuilder<AsyncReader<T>> {
                 .zip(metadata.offset_indexes())
                 .is_none()
         {
-            let mut fetch_ranges = vec![];
-            let mut index_lengths: Vec<Vec<usize>> = vec![];
-
-            for rg in metadata.row_groups() {
-                let (loc_offset, loc_length) =
-                    index_reader::get_location_offset_and_total_length(rg.columns())?;
-
-                let (idx_offset, idx_lengths) =
-                    index_reader::get_index_offset_and_lengths(rg.columns())?;
-                let idx_length = idx_lengths.iter().sum::<usize>();
-
-                // If index data is missing, return without any indexes
-                if loc_length == 0 || idx_length == 0 {
-                    return Self::new_builder(AsyncReader(input), metadata, options);
-                }
-
-                fetch_ranges.push(loc_offset as usize..loc_offset as usize + loc_length);
-                fetch_ranges.push(idx_offset as usize..idx_offset as usize + idx_length);
-                index_lengths.push(idx_lengths);
-            }
-
-            let mut chunks = input.get_byte_ranges(fetch_ranges).await?.into_iter();
-            let mut index_lengths = index_lengths.into_iter();
-
-            let mut row_groups = metadata.row_groups().to_vec();
-
-            let mut columns_indexes = vec![];
-            let mut offset_indexes = vec![];
+            let m = Arc::try_unwrap(metadata).unwrap_or_else(|e| e.as_ref().clone());
+            let mut loader = MetadataLoader::new(input, m);
+            loader.load_page_index(true, true).await?;
+            metadata = Arc::new(loader.finish())
+        }
 
-            for rg in row_groups.iter_mut() {
-                let columns = rg.columns();
+        Self::try_new(metadata, options)
+    }
+}
 
-                let location_data = chunks.next().unwrap();
-                let mut cursor = Cursor::new(location_data);
-                let mut offset_index = vec![];
+impl<T: AsyncFileReader + Send + 'static> ArrowReaderBuilder<AsyncReader<T>> {
+    /// Create a new [`ParquetRecordBatchStreamBuilder`] with the provided parquet file
+    pub async fn new(input: T) -> Result<Self> {
+        Self::new_with_options(input, Default::default()).await
+    }
 
-                for _ in 0..columns.len() {
-                    let mut prot = TCompactInputProtocol::new(&mut cursor);
-                    let offset = OffsetIndex::read_from_in_protocol(&mut prot)?;
-                    offset_index.push(offset.page_locations);
-                }
+    /// Create a new [`ParquetRecordBatchStreamBuilder`] with the provided parquet file
+    /// and [`ArrowReaderOptions`]
+    pub async fn new_with_options(
+        mut input: T,
+        options: ArrowReaderOptions,
+    ) -> Result<Self> {
+        let metadata = ArrowReaderMetadata::load_async(&mut input, options).await?;
+        Ok(Self::new_with_metadata(input, metadata))
+    }
 
-                rg.set_page_offset(offset_index.clone());
-                offset_indexes.push(offset_index);
-
-                let index_data = chunks.next().unwrap();
-                let index_lengths = index_lengths.next().unwrap();
-
-                let mut start = 0;
-                let data = index_lengths.into_iter().map(|length| {
-                    let r = index_data.slice(start..start + length);
-                    start += length;
-                    r
-                });
-
-                let indexes = rg
-                    .columns()
-                    .iter()
-                    .zip(data)
-                    .map(|(column, data)| {
-                        let column_type = column.column_type();
-                        index_reader::deserialize_column_index(&data, column_type)
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-                columns_indexes.push(indexes);
-            }
+    /// Create a [`ParquetRecordBatchStreamBuilder`] from the provided [`ArrowReaderMetadata`]
+    ///
+    /// This allows loading metadata once and using it to create multiple builders with
+    /// potentially different settings, without fetching and decoding the footer again
+    ///
+    /// ```
+    /// # use std::fs::metadata;
+    /// # use std::sync::Arc;
+    /// # use bytes::Bytes;
+    /// # use arrow_array::{Int32Array, RecordBatch};
+    /// # use arrow_schema::{DataType, Field, Schema};
+    /// # use parquet::arrow::arrow_reader::ArrowReaderMetadata;
+    /// # use parquet::arrow::{ArrowWriter, ParquetRecordBatchStreamBuilder};
+    /// # use tempfile::tempfile;
+    /// # use futures::StreamExt;
+    /// # #[tokio::main(flavor="current_thread")]
+    /// # async fn main() {
+    /// #
+    /// # let mut file = tempfile().unwrap();
+    /// # let schema = Arc::new(Schema::new(vec![Field::new("i32", DataType::Int32, false)]));
+    /// # let mut writer = ArrowWriter::try_new(&mut file, schema.clone(), None).unwrap();
+    /// # let batch = RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![1, 2, 3]))]).unwrap();
+    /// # writer.write(&batch).unwrap();
+    /// # writer.close().unwrap();
+    /// #
+    /// let mut a = tokio::fs::File::from_std(file.try_clone().unwrap());
+    /// let mut b = tokio::fs::File::from_std(file);
+    ///
+    /// // Load metadata once
+    /// let meta = ArrowReaderMetadata::load_async(&mut a, Default::default()).await.unwrap();
+    ///
+    /// // Create multiple readers sharing the same metadata
+    /// let mut a = ParquetRecordBatchStreamBuilder::new_with_metadata(a, meta.clone()).build().unwrap();
+    /// let mut b = ParquetRecordBatchStreamBuilder::new_with_metadata(b, meta).build().unwrap();
+    ///
+    /// // Should be able to read from both in parallel
+    /// assert_eq!(a.next().await.unwrap().unwrap(), b.next().await.unwrap().unwrap());
+    /// # }
+    /// ```
+    pub fn new_with_metadata(input: T, metadata: ArrowReaderMetadata) -> Self {
+        Self::new_builder(AsyncReader(input), metadata)
+    }
 
-            metadata = Arc::new(ParquetMetaData::new_with_page_index(
-                metadata.file_metadata().clone(),
-                row_groups,
-                Some(columns_indexes),
-                Some(offset_indexes),
+    /// Read the bloom filter of column `column_idx` in row group `row_group_idx`,
+    /// returning `None` if the column chunk has no bloom filter
+    ///
+    /// This allows skipping row groups that cannot contain a value, and should be
+    /// called after other forms of pruning, as it performs IO
+    pub async fn get_row_group_column_bloom_filter(
+        &mut self,
+        row_group_idx: usize,
+        column_idx: usize,
+    ) -> Result<Option<Sbbf>> {
+        let column = self.metadata.row_group(row_group_idx).column(column_idx);
+        let offset: usize = match column.bloom_filter_offset() {
+            Some(offset) => offset
+                .try_into()
+                .map_err(|_| general_err!("Bloom filter offset is invalid"))?,
+            None => return Ok(None),
+        };
+        if column.crypto_metadata().is_some() {
+            return Err(nyi_err!(
+                "Reading the bloom filter of encrypted column {}",
+                column.column_path().string()
             ));
         }
 
-        Self::new_builder(AsyncReader(input), metadata, options)
+        // read the header and bitset in a single request if the length is known
+        if let Some(length) = column.bloom_filter_length() {
+            let length: usize = length
+                .try_into()
+                .map_err(|_| general_err!("Bloom filter length is invalid"))?;
+            let buffer = self.input.0.get_bytes(offset..offset + length).await?;
+            return Sbbf::from_bytes(&buffer).map(Some);
+        }
+
+        let buffer = self
+            .input
+            .0
+            .get_bytes(offset..offset + SBBF_HEADER_SIZE_ESTIMATE)
+            .await?;
+        let (header, header_length) = read_bloom_filter_header_and_length(&buffer)?;
+        let length = bitset_length(&header)?;
+
+        let bitset_offset = offset + header_length as usize;
+        let bitset = self
+            .input
+            .0
+            .get_bytes(bitset_offset..bitset_offset + length)
+            .await?;
+        Ok(Some(Sbbf::new(&bitset)))
     }
 
     /// Build a new [`ParquetRecordBatchStream`]
-    pub fn build(self) -> Result<ParquetRecordBatchStream<T>> {
+    pub fn build(mut self) -> Result<ParquetRecordBatchStream<T>> {
         let num_row_groups = self.metadata.row_groups().len();
 
-        let row_groups = match self.row_groups {
+        let row_groups: Vec<_> = match self.row_groups.take() {
             Some(row_groups) => {
                 if let Some(col) = row_groups.iter().find(|x| **x >= num_row_groups) {
                     return Err(general_err!(
@@ -325,10 +375,16 @@ impl<T: AsyncFileReader + Send + 'static> ArrowReaderBuilder<AsyncReader<T>> {
                         num_row_groups
                     ));
                 }
-                row_groups.into()
+                row_groups
             }
             None => (0..self.metadata.row_groups().len()).collect(),
         };
+        let (row_groups, selection) = self.build_selection(row_groups)?;
+        let projection = self.build_projection();
+        let schema = match &self.target_schema {
+            Some(target) => target.clone(),
+            None => self.schema,
+        };
 
         // Try to avoid allocate large buffer
         let batch_size = self
@@ -339,15 +395,18 @@ impl<T: AsyncFileReader + Send + 'static> ArrowReaderBuilder<AsyncReader<T>> {
             filter: self.filter,
             metadata: self.metadata.clone(),
             fields: self.fields,
+            int96_overflow: self.int96_overflow,
+            props: reader_properties(self.max_page_size),
+            target_schema: self.target_schema,
         };
 
         Ok(ParquetRecordBatchStream {
             metadata: self.metadata,
             batch_size,
-            row_groups,
-            projection: self.projection,
-            selection: self.selection,
-            schema: self.schema,
+            row_groups: row_groups.into(),
+            projection,
+            selection,
+            schema,
             reader: Some(reader),
             state: StreamState::Init,
         })
@@ -363,9 +422,15 @@ struct ReaderFactory<T> {
 
     fields: Option<ParquetField>,
 
+    int96_overflow: Int96Overflow,
+
+    props: ReaderPropertiesPtr,
+
     input: T,
 
     filter: Option<RowFilter>,
+
+    target_schema: Option<SchemaRef>,
 }
 
 impl<T> ReaderFactory<T>
@@ -390,6 +455,9 @@ where
             // schema: meta.schema_descr_ptr(),
             row_count: meta.num_rows() as usize,
             column_chunks: vec![None; meta.columns().len()],
+            props: Arc::clone(&self.props),
+            #[cfg(feature = "encryption")]
+            file_decryptor: self.metadata.file_decryptor().cloned(),
         };
 
         if let Some(filter) = self.filter.as_mut() {
@@ -407,6 +475,7 @@ where
                     self.fields.as_ref(),
                     predicate_projection,
                     &row_group,
+                    self.int96_overflow,
                 )?;
 
                 selection = Some(evaluate_predicate(
@@ -428,10 +497,20 @@ where
 
         let reader = ParquetRecordBatchReader::new(
             batch_size,
-            build_array_reader(self.fields.as_ref(), &projection, &row_group)?,
+            build_array_reader(
+                self.fields.as_ref(),
+                &projection,
+                &row_group,
+                self.int96_overflow,
+            )?,
             selection,
         );
 
+        let reader = match &self.target_schema {
+            Some(schema) => reader.with_target_schema(schema.clone())?,
+            None => reader,
+        };
+
         Ok((self, Some(reader)))
     }
 }
@@ -571,6 +650,9 @@ struct InMemoryRowGroup<'a> {
     metadata: &'a RowGroupMetaData,
     column_chunks: Vec<Option<Arc<ColumnChunkData>>>,
     row_count: usize,
+    props: ReaderPropertiesPtr,
+    #[cfg(feature = "encryption")]
+    file_decryptor: Option<Arc<FileDecryptor>>,
 }
 
 impl<'a> InMemoryRowGroup<'a> {
@@ -693,18 +775,26 @@ impl<'a> RowGroupCollection for InMemoryRowGroup<'a> {
                     .page_offset_index()
                     .as_ref()
                     .map(|index| index[i].clone());
-                let page_reader: Box<dyn PageReader> =
-                    Box::new(SerializedPageReader::new(
-                        data.clone(),
-                        self.metadata.column(i),
-                        self.row_count,
-                        page_locations,
-                    )?);
+                let page_reader = SerializedPageReader::new_with_properties(
+                    data.clone(),
+                    self.metadata.column(i),
+                    self.row_count,
+                    page_locations,
+                    Arc::clone(&self.props),
+                )?;
+
+                #[cfg(feature = "encryption")]
+                let page_reader = page_reader.with_crypto_context(
+                    self.file_decryptor.as_deref(),
+                    self.metadata.column(i),
+                    self.metadata.ordinal(),
+                    i,
+                )?;
 
                 Ok(Box::new(ColumnChunkIterator {
                     schema: self.metadata.schema_descr_ptr(),
                     column_schema: self.metadata.schema_descr_ptr().columns()[i].clone(),
-                    reader: Some(Ok(page_reader)),
+                    reader: Some(Ok(Box::new(page_reader))),
                 }))
             }
         }
@@ -791,14 +881,17 @@ impl PageIterator for ColumnChunkIterator {
 mod tests {
     use super::*;
     use crate::arrow::arrow_reader::{
-        ArrowPredicateFn, ParquetRecordBatchReaderBuilder, RowSelector,
+        ArrowPredicateFn, PageFilter, ParquetRecordBatchReaderBuilder, RowSelector,
     };
     use crate::arrow::schema::parquet_to_array_schema_and_fields;
     use crate::arrow::ArrowWriter;
     use crate::file::footer::parse_metadata;
+    use crate::file::page_index::index::Index;
     use crate::file::page_index::index_reader;
+    use crate::file::properties::WriterProperties;
     use arrow::error::Result as ArrowResult;
     use arrow_array::{Array, ArrayRef, Int32Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
     use futures::TryStreamExt;
     use rand::{thread_rng, Rng};
     use std::sync::Mutex;
@@ -1282,6 +1375,7 @@ mod tests {
             metadata.file_metadata().schema_descr(),
             ProjectionMask::all(),
             None,
+            None,
         )
         .unwrap();
 
@@ -1293,8 +1387,11 @@ mod tests {
         let reader_factory = ReaderFactory {
             metadata,
             fields,
+            int96_overflow: Default::default(),
+            props: reader_properties(None),
             input: async_reader,
             filter: None,
+            target_schema: None,
         };
 
         let mut skip = true;
@@ -1362,4 +1459,287 @@ mod tests {
         assert_ne!(1024, file_rows);
         assert_eq!(stream.batch_size, file_rows as usize);
     }
+
+    #[tokio::test]
+    async fn test_async_reader_target_schema() {
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|x| x.to_string()),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(30)
+            .build();
+
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer =
+            ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buf);
+
+        let metadata = Arc::new(parse_metadata(&data).unwrap());
+        let async_reader = TestReader {
+            data,
+            metadata,
+            requests: Default::default(),
+        };
+
+        let target = Arc::new(Schema::new(vec![
+            Field::new("c", DataType::Float64, true),
+            Field::new("a", DataType::Int64, false),
+        ]));
+        let stream = ParquetRecordBatchStreamBuilder::new(async_reader)
+            .await
+            .unwrap()
+            .with_target_schema(target.clone())
+            .build()
+            .unwrap();
+        assert_eq!(stream.schema(), &target);
+
+        let batches: Vec<_> = stream.try_collect().await.unwrap();
+        assert_eq!(batches.len(), 4);
+
+        let expected = arrow_cast::cast(batch.column(0), &DataType::Int64).unwrap();
+        let mut offset = 0;
+        for batch in batches {
+            assert_eq!(batch.schema(), target);
+            assert_eq!(batch.column(0).null_count(), batch.num_rows());
+            assert_eq!(
+                batch.column(1).as_ref(),
+                expected.slice(offset, batch.num_rows()).as_ref()
+            );
+            offset += batch.num_rows();
+        }
+        assert_eq!(offset, 100);
+    }
+
+    #[tokio::test]
+    async fn test_async_reader_page_filter() {
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_data_page_row_count_limit(10)
+            .set_write_batch_size(10)
+            .set_dictionary_enabled(false)
+            .build();
+        let mut buffer = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        let async_reader = TestReader {
+            data: data.clone(),
+            metadata: Arc::new(parse_metadata(&data).unwrap()),
+            requests: Default::default(),
+        };
+        let requests = async_reader.requests.clone();
+
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        let builder =
+            ParquetRecordBatchStreamBuilder::new_with_options(async_reader, options)
+                .await
+                .unwrap();
+        let column_bytes: u64 = builder
+            .metadata()
+            .row_group(0)
+            .columns()
+            .iter()
+            .map(|c| c.byte_range().1)
+            .sum();
+
+        // Only reads the pages that may contain `a < 20`
+        let filter = PageFilter::new(0, |index| match index {
+            Index::INT32(index) => index
+                .indexes
+                .iter()
+                .map(|page| page.min().map(|min| *min < 20).unwrap_or(false))
+                .collect(),
+            _ => unreachable!(),
+        });
+        let stream = builder.with_page_filter(filter).build().unwrap();
+        let batches: Vec<_> = stream.try_collect().await.unwrap();
+        assert_eq!(batches, vec![batch.slice(0, 20)]);
+
+        let requests = requests.lock().unwrap();
+        let fetched: usize = requests.iter().map(|r| r.end - r.start).sum();
+        assert!(fetched < column_bytes as usize / 2, "{}", fetched);
+    }
+
+    #[tokio::test]
+    async fn test_async_reader_bloom_filter() {
+        let a = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i)),
+        )) as ArrayRef;
+        let b = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_column_bloom_filter_enabled("a".into(), true)
+            .build();
+        let mut buffer = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buffer);
+
+        let async_reader = TestReader {
+            data: data.clone(),
+            metadata: Arc::new(parse_metadata(&data).unwrap()),
+            requests: Default::default(),
+        };
+        let requests = async_reader.requests.clone();
+        let mut builder = ParquetRecordBatchStreamBuilder::new(async_reader)
+            .await
+            .unwrap();
+        let column = builder.metadata().row_group(0).column(0);
+        let offset = column.bloom_filter_offset().unwrap() as usize;
+        let length = column.bloom_filter_length().unwrap() as usize;
+
+        let sbbf = builder
+            .get_row_group_column_bloom_filter(0, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        // The header and bitset are read in a single request
+        let expected = offset..offset + length;
+        assert_eq!(requests.lock().unwrap().as_slice(), &[expected]);
+        for i in 0..100 {
+            assert!(sbbf.check(format!("value{}", i).as_str()));
+        }
+        assert!(!sbbf.check("missing"));
+
+        let sbbf = builder
+            .get_row_group_column_bloom_filter(0, 1)
+            .await
+            .unwrap();
+        assert!(sbbf.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_async_reader_tokio_file() {
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(30)
+            .build();
+        let mut file = tempfile::tempfile().unwrap();
+        let mut writer =
+            ArrowWriter::try_new(&mut file, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let file = tokio::fs::File::from_std(file);
+        let builder = ParquetRecordBatchStreamBuilder::new(file).await.unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 4);
+
+        let mask = ProjectionMask::leaves(builder.parquet_schema(), vec![1]);
+        let stream = builder
+            .with_projection(mask)
+            .with_row_groups(vec![1, 3])
+            .build()
+            .unwrap();
+        let batches: Vec<_> = stream.try_collect().await.unwrap();
+        assert_eq!(batches.len(), 2);
+
+        let expected = batch.project(&[1]).unwrap();
+        assert_eq!(batches[0], expected.slice(30, 30));
+        assert_eq!(batches[1], expected.slice(90, 10));
+    }
+
+    #[tokio::test]
+    async fn test_async_reader_row_range() {
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(30)
+            .build();
+        let mut file = tempfile::tempfile().unwrap();
+        let mut writer =
+            ArrowWriter::try_new(&mut file, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let file = tokio::fs::File::from_std(file);
+        let builder = ParquetRecordBatchStreamBuilder::new(file).await.unwrap();
+        assert_eq!(builder.row_group_num_rows(3), Some(10));
+        assert_eq!(builder.num_rows(), 100);
+
+        let stream = builder.with_row_range(55..95).build().unwrap();
+        let batches: Vec<_> = stream.try_collect().await.unwrap();
+
+        // The first row group is not read
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0], batch.slice(55, 5));
+        assert_eq!(batches[1], batch.slice(60, 30));
+        assert_eq!(batches[2], batch.slice(90, 5));
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "encryption")]
+    async fn test_async_reader_encrypted() {
+        use crate::encryption::decrypt::tests::encrypt_file;
+        use crate::encryption::decrypt::FileDecryptionProperties;
+        use crate::file::footer::parse_encrypted_metadata;
+        use crate::schema::types::ColumnPath;
+
+        let a = Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..100).map(|i| format!("value{}", i)),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(30)
+            .build();
+        let mut buffer = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let footer_key = b"0123456789012345".as_slice();
+        let column_key = b"1234567890123450".as_slice();
+        let data = Bytes::from(encrypt_file(
+            &buffer,
+            footer_key,
+            &[("b", column_key)],
+            true,
+            false,
+        ));
+        let properties = FileDecryptionProperties::builder(footer_key.to_vec())
+            .with_column_key(ColumnPath::from("b"), column_key.to_vec())
+            .build()
+            .unwrap();
+
+        let async_reader = TestReader {
+            data: data.clone(),
+            metadata: Arc::new(parse_encrypted_metadata(&data, &properties).unwrap()),
+            requests: Default::default(),
+        };
+        let stream = ParquetRecordBatchStreamBuilder::new(async_reader)
+            .await
+            .unwrap()
+            .with_batch_size(100)
+            .build()
+            .unwrap();
+        let batches: Vec<_> = stream.try_collect().await.unwrap();
+        assert_eq!(batches.len(), 4);
+
+        let read =
+            arrow_select::concat::concat_batches(&batch.schema(), &batches).unwrap();
+        assert_eq!(read, batch);
+    }
 }
@@ -0,0 +1,191 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::future::{BoxFuture, FutureExt, TryFutureExt};
+use object_store::{ObjectMeta, ObjectStore};
+
+use crate::arrow::async_reader::{fetch_parquet_metadata, AsyncFileReader};
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::ParquetMetaData;
+
+/// Implements [`AsyncFileReader`] for a parquet file in object storage
+///
+/// Multiple byte ranges are fetched with [`ObjectStore::get_ranges`], which coalesces
+/// nearby ranges into a smaller number of requests
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use futures::TryStreamExt;
+/// # use object_store::{local::LocalFileSystem, path::Path, ObjectStore};
+/// # use parquet::arrow::async_reader::ParquetObjectReader;
+/// # use parquet::arrow::ParquetRecordBatchStreamBuilder;
+/// # async fn example() {
+/// let store: Arc<dyn ObjectStore> = Arc::new(LocalFileSystem::new());
+/// let meta = store.head(&Path::from("data.parquet")).await.unwrap();
+///
+/// let reader = ParquetObjectReader::new(store, meta).with_footer_size_hint(64 * 1024);
+/// let stream = ParquetRecordBatchStreamBuilder::new(reader)
+///     .await
+///     .unwrap()
+///     .build()
+///     .unwrap();
+/// let batches: Vec<_> = stream.try_collect().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParquetObjectReader {
+    store: Arc<dyn ObjectStore>,
+    meta: ObjectMeta,
+    footer_size_hint: Option<usize>,
+}
+
+impl ParquetObjectReader {
+    /// Creates a new [`ParquetObjectReader`] for the provided [`ObjectStore`] and [`ObjectMeta`]
+    ///
+    /// [`ObjectMeta`] can be obtained using [`ObjectStore::list`] or [`ObjectStore::head`]
+    pub fn new(store: Arc<dyn ObjectStore>, meta: ObjectMeta) -> Self {
+        Self {
+            store,
+            meta,
+            footer_size_hint: None,
+        }
+    }
+
+    /// Provide a hint as to the size of the parquet file's footer, including the
+    /// metadata, see [`fetch_parquet_metadata`]
+    pub fn with_footer_size_hint(self, hint: usize) -> Self {
+        Self {
+            footer_size_hint: Some(hint),
+            ..self
+        }
+    }
+}
+
+/// Converts an [`object_store::Error`] to a [`ParquetError`]
+fn object_store_err(e: object_store::Error) -> ParquetError {
+    ParquetError::General(format!("ParquetObjectReader error: {}", e))
+}
+
+impl AsyncFileReader for ParquetObjectReader {
+    fn get_bytes(&mut self, range: Range<usize>) -> BoxFuture<'_, Result<Bytes>> {
+        self.store
+            .get_range(&self.meta.location, range)
+            .map_err(object_store_err)
+            .boxed()
+    }
+
+    fn get_byte_ranges(
+        &mut self,
+        ranges: Vec<Range<usize>>,
+    ) -> BoxFuture<'_, Result<Vec<Bytes>>> {
+        async move {
+            self.store
+                .get_ranges(&self.meta.location, &ranges)
+                .await
+                .map_err(object_store_err)
+        }
+        .boxed()
+    }
+
+    fn get_metadata(&mut self) -> BoxFuture<'_, Result<Arc<ParquetMetaData>>> {
+        async move {
+            let metadata = fetch_parquet_metadata(
+                |range| {
+                    self.store
+                        .get_range(&self.meta.location, range)
+                        .map_err(object_store_err)
+                },
+                self.meta.size,
+                self.footer_size_hint,
+            )
+            .await?;
+            Ok(Arc::new(metadata))
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::{ArrowWriter, ParquetRecordBatchStreamBuilder};
+    use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray};
+    use futures::TryStreamExt;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+
+    async fn write_store(batch: &RecordBatch) -> (Arc<dyn ObjectStore>, ObjectMeta) {
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), None).unwrap();
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = Path::from("test.parquet");
+        store.put(&path, buffer.into()).await.unwrap();
+        let meta = store.head(&path).await.unwrap();
+        (store, meta)
+    }
+
+    #[tokio::test]
+    async fn test_simple() {
+        let a = Arc::new(Int32Array::from_iter_values(0..1000)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (0..1000).map(|x| x.to_string()),
+        )) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+        let (store, meta) = write_store(&batch).await;
+
+        for hint in [None, Some(16), Some(1024 * 1024)] {
+            let mut reader = ParquetObjectReader::new(store.clone(), meta.clone());
+            if let Some(hint) = hint {
+                reader = reader.with_footer_size_hint(hint);
+            }
+
+            let batches: Vec<_> = ParquetRecordBatchStreamBuilder::new(reader)
+                .await
+                .unwrap()
+                .build()
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(batches.len(), 1);
+            assert_eq!(batches[0], batch);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_not_found() {
+        let a = Arc::new(Int32Array::from_iter_values(0..10)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", a)]).unwrap();
+        let (store, mut meta) = write_store(&batch).await;
+        meta.location = Path::from("missing.parquet");
+
+        let reader = ParquetObjectReader::new(store, meta);
+        let err = match ParquetRecordBatchStreamBuilder::new(reader).await {
+            Ok(_) => panic!("expected error"),
+            Err(e) => e.to_string(),
+        };
+        assert!(err.contains("ParquetObjectReader error"), "{}", err);
+    }
+}
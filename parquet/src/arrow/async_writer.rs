@@ -0,0 +1,321 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Contains asynchronous APIs for writing [`RecordBatch`]es to a parquet file
+//! via an [`AsyncWrite`]
+//!
+//! ```
+//! # #[tokio::main(flavor="current_thread")]
+//! # async fn main() {
+//! #
+//! use std::sync::Arc;
+//! use arrow_array::{ArrayRef, Int64Array, RecordBatch};
+//! use bytes::Bytes;
+//! use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+//! use parquet::arrow::AsyncArrowWriter;
+//!
+//! let col = Arc::new(Int64Array::from_iter_values([1, 2, 3])) as ArrayRef;
+//! let to_write = RecordBatch::try_from_iter([("col", col)]).unwrap();
+//!
+//! let mut buffer = Vec::new();
+//! let mut writer =
+//!     AsyncArrowWriter::try_new(&mut buffer, to_write.schema(), 0, None).unwrap();
+//! writer.write(&to_write).await.unwrap();
+//! writer.close().await.unwrap();
+//!
+//! let mut reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buffer))
+//!     .unwrap()
+//!     .build()
+//!     .unwrap();
+//! let read = reader.next().unwrap().unwrap();
+//!
+//! assert_eq!(to_write, read);
+//! # }
+//! ```
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use arrow_array::RecordBatch;
+use arrow_schema::SchemaRef;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::arrow::ArrowWriter;
+use crate::errors::Result;
+use crate::file::metadata::{KeyValue, RowGroupMetaDataPtr};
+use crate::file::properties::WriterProperties;
+use crate::format::FileMetaData;
+
+/// Encodes [`RecordBatch`]es to parquet, writing the encoded bytes to an [`AsyncWrite`]
+///
+/// The [`RecordBatch`]es are encoded by an [`ArrowWriter`] into an in-memory buffer,
+/// which is written to the [`AsyncWrite`] once it holds at least `buffer_size` bytes.
+/// As [`Self::write`] waits for the buffer to be written before returning, at most one
+/// row group more than `buffer_size` is kept in memory, even if the [`AsyncWrite`] is
+/// slower than the encoding
+///
+/// Note that the encoding itself is performed synchronously within [`Self::write`],
+/// [`Self::flush`] and [`Self::close`]
+pub struct AsyncArrowWriter<W> {
+    /// The writer encoding the batches into `buffer`
+    sync_writer: ArrowWriter<SharedBuffer>,
+
+    /// The destination of the encoded bytes
+    async_writer: W,
+
+    /// The encoded bytes not yet written to `async_writer`
+    buffer: SharedBuffer,
+
+    /// The number of buffered bytes at which `buffer` is written to `async_writer`
+    buffer_size: usize,
+}
+
+impl<W: AsyncWrite + Unpin + Send> AsyncArrowWriter<W> {
+    /// Try to create a new [`AsyncArrowWriter`] writing to `writer`, which buffers
+    /// up to `buffer_size` encoded bytes before writing them to `writer`
+    ///
+    /// Fails for the same reasons as [`ArrowWriter::try_new`]
+    pub fn try_new(
+        writer: W,
+        arrow_schema: SchemaRef,
+        buffer_size: usize,
+        props: Option<WriterProperties>,
+    ) -> Result<Self> {
+        let buffer = SharedBuffer::default();
+        let sync_writer = ArrowWriter::try_new(buffer.clone(), arrow_schema, props)?;
+        Ok(Self {
+            sync_writer,
+            async_writer: writer,
+            buffer,
+            buffer_size,
+        })
+    }
+
+    /// Returns metadata for any flushed row groups
+    pub fn flushed_row_groups(&self) -> &[RowGroupMetaDataPtr] {
+        self.sync_writer.flushed_row_groups()
+    }
+
+    /// Adds a [`KeyValue`] to the metadata written to the file footer
+    ///
+    /// See [`ArrowWriter::append_key_value_metadata`]
+    pub fn append_key_value_metadata(&mut self, kv_metadata: KeyValue) {
+        self.sync_writer.append_key_value_metadata(kv_metadata)
+    }
+
+    /// Enqueues the provided `RecordBatch` to be written
+    ///
+    /// Any row groups completed as a result are encoded, and written to the
+    /// underlying [`AsyncWrite`] if more than `buffer_size` bytes are now buffered
+    pub async fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.sync_writer.write(batch)?;
+        write_buffer(&self.buffer, &mut self.async_writer, self.buffer_size).await
+    }
+
+    /// Flushes all buffered rows into a new row group, and writes all the
+    /// encoded bytes to the underlying [`AsyncWrite`]
+    pub async fn flush(&mut self) -> Result<()> {
+        self.sync_writer.flush()?;
+        write_buffer(&self.buffer, &mut self.async_writer, 0).await
+    }
+
+    /// Close and finalize the parquet file, writing all remaining bytes to the
+    /// underlying [`AsyncWrite`] and shutting it down
+    pub async fn close(mut self) -> Result<FileMetaData> {
+        let metadata = self.sync_writer.close()?;
+        write_buffer(&self.buffer, &mut self.async_writer, 0).await?;
+        self.async_writer.shutdown().await?;
+        Ok(metadata)
+    }
+}
+
+/// Writes the bytes of `buffer` to `writer` if there are at least `threshold` of them
+async fn write_buffer<W: AsyncWrite + Unpin + Send>(
+    buffer: &SharedBuffer,
+    writer: &mut W,
+    threshold: usize,
+) -> Result<()> {
+    let mut bytes = {
+        let mut bytes = buffer.0.lock().unwrap();
+        if bytes.is_empty() || bytes.len() < threshold {
+            return Ok(());
+        }
+        std::mem::take(&mut *bytes)
+    };
+
+    writer.write_all(&bytes).await?;
+    writer.flush().await?;
+
+    // Reuse the allocation for the following bytes
+    bytes.clear();
+    *buffer.0.lock().unwrap() = bytes;
+    Ok(())
+}
+
+/// An in-memory buffer shared between an [`AsyncArrowWriter`] and its [`ArrowWriter`]
+#[derive(Debug, Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use arrow_array::{ArrayRef, Int64Array, StringArray};
+    use bytes::Bytes;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// An [`AsyncWrite`] recording the size of each write
+    #[derive(Default)]
+    struct TestWriter {
+        data: Vec<u8>,
+        writes: Vec<usize>,
+        shutdown: bool,
+    }
+
+    impl AsyncWrite for TestWriter {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.data.extend_from_slice(buf);
+            self.writes.push(buf.len());
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            self.shutdown = true;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn make_batch(start: i64, len: i64) -> RecordBatch {
+        let a = Arc::new(Int64Array::from_iter_values(start..start + len)) as ArrayRef;
+        let b = Arc::new(StringArray::from_iter_values(
+            (start..start + len).map(|i| format!("value{}", i)),
+        )) as ArrayRef;
+        RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap()
+    }
+
+    fn read_batches(data: Vec<u8>) -> Vec<RecordBatch> {
+        ParquetRecordBatchReaderBuilder::try_new(Bytes::from(data))
+            .unwrap()
+            .with_batch_size(1024)
+            .build()
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_async_writer() {
+        let batch = make_batch(0, 100);
+
+        let mut sync_buffer = Vec::new();
+        let mut writer =
+            ArrowWriter::try_new(&mut sync_buffer, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let mut async_writer = TestWriter::default();
+        let mut writer =
+            AsyncArrowWriter::try_new(&mut async_writer, batch.schema(), 1024, None)
+                .unwrap();
+        writer.write(&batch).await.unwrap();
+        writer.close().await.unwrap();
+
+        assert!(async_writer.shutdown);
+        assert_eq!(async_writer.data, sync_buffer);
+        assert_eq!(read_batches(async_writer.data), vec![batch]);
+    }
+
+    #[tokio::test]
+    async fn test_async_writer_buffer_size() {
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(100)
+            .build();
+        let schema = make_batch(0, 0).schema();
+
+        let mut async_writer = TestWriter::default();
+        let mut writer = AsyncArrowWriter::try_new(
+            &mut async_writer,
+            schema,
+            usize::MAX,
+            Some(props.clone()),
+        )
+        .unwrap();
+        for i in 0..3 {
+            writer.write(&make_batch(i * 100, 100)).await.unwrap();
+        }
+        // Everything is buffered until the end
+        assert_eq!(writer.flushed_row_groups().len(), 3);
+        writer.close().await.unwrap();
+        assert_eq!(async_writer.writes.len(), 1);
+
+        let mut async_writer = TestWriter::default();
+        let mut writer = AsyncArrowWriter::try_new(
+            &mut async_writer,
+            make_batch(0, 0).schema(),
+            1,
+            Some(props),
+        )
+        .unwrap();
+
+        // The magic header
+        writer.write(&make_batch(0, 50)).await.unwrap();
+        assert_eq!(writer.async_writer.writes.len(), 1);
+
+        // Each completed row group is written once encoded
+        writer.write(&make_batch(50, 100)).await.unwrap();
+        assert_eq!(writer.async_writer.writes.len(), 2);
+        writer.write(&make_batch(150, 100)).await.unwrap();
+        assert_eq!(writer.async_writer.writes.len(), 3);
+        assert!(writer.buffer.0.lock().unwrap().is_empty());
+
+        writer.flush().await.unwrap();
+        assert_eq!(writer.flushed_row_groups().len(), 3);
+        assert_eq!(writer.async_writer.writes.len(), 4);
+
+        // The footer
+        writer.close().await.unwrap();
+        assert_eq!(async_writer.writes.len(), 5);
+
+        assert_eq!(read_batches(async_writer.data), vec![make_batch(0, 250)]);
+    }
+}
@@ -19,27 +19,159 @@ use crate::arrow::buffer::offset_buffer::OffsetBuffer;
 use crate::arrow::record_reader::buffer::{
     BufferQueue, ScalarBuffer, ScalarValue, ValuesBuffer,
 };
+use crate::basic::Encoding;
 use crate::column::reader::decoder::ValuesBufferSlice;
+use crate::encodings::rle::RleDecoder;
 use crate::errors::{ParquetError, Result};
+use crate::util::bit_util::FromBytes;
+use crate::util::memory::ByteBufferPtr;
 use arrow_array::{make_array, Array, ArrayRef, OffsetSizeTrait};
 use arrow_buffer::{ArrowNativeType, Buffer};
 use arrow_data::ArrayDataBuilder;
 use arrow_schema::DataType as ArrowType;
+use std::ops::Range;
 use std::sync::Arc;
 
-/// An array of variable length byte arrays that are potentially dictionary encoded
-/// and can be converted into a corresponding [`ArrayRef`]
-pub enum DictionaryBuffer<K: ScalarValue, V: ScalarValue> {
+/// A [`ValuesBuffer`] that the keys of a [`GenericDictionaryBuffer`] can be spilled
+/// into, once it is no longer possible to preserve the dictionary encoding
+pub trait SpillBuffer: ValuesBuffer + Default {
+    /// Returns the number of values in this buffer
+    fn len(&self) -> usize;
+
+    /// Returns true if this buffer contains no values
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Splits off and returns the first `len` values of this buffer
+    fn take_values(&mut self, len: usize) -> Self;
+
+    /// Appends `len` arbitrary values to this buffer
+    ///
+    /// This is used to spill the keys of an empty dictionary, which must all be null
+    fn extend_nulls(&mut self, len: usize);
+
+    /// Appends the values of `dictionary` identified by `keys` to this buffer
+    fn extend_from_dictionary_array<K: ArrowNativeType>(
+        &mut self,
+        keys: &[K],
+        dictionary: &ArrayRef,
+    ) -> Result<()>;
+
+    /// As [`ValuesBuffer::pad_nulls`], but for values appended to the end of this
+    /// buffer, instead of written to [`BufferQueue::spare_capacity_mut`]
+    fn pad_appended_nulls(
+        &mut self,
+        read_offset: usize,
+        values_read: usize,
+        levels_read: usize,
+        valid_mask: &[u8],
+    );
+}
+
+impl<V: ScalarValue + OffsetSizeTrait> SpillBuffer for OffsetBuffer<V> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn take_values(&mut self, len: usize) -> Self {
+        self.split_off(len)
+    }
+
+    fn extend_nulls(&mut self, len: usize) {
+        let end_offset = *self.offsets.as_slice().last().unwrap();
+        self.offsets.reserve(len);
+        for _ in 0..len {
+            self.offsets.push(end_offset)
+        }
+    }
+
+    fn extend_from_dictionary_array<K: ArrowNativeType>(
+        &mut self,
+        keys: &[K],
+        dictionary: &ArrayRef,
+    ) -> Result<()> {
+        let dict_buffers = dictionary.data().buffers();
+        let dict_offsets = dict_buffers[0].typed_data::<V>();
+        let dict_values = dict_buffers[1].as_slice();
+        self.extend_from_dictionary(keys, dict_offsets, dict_values)
+    }
+
+    fn pad_appended_nulls(
+        &mut self,
+        read_offset: usize,
+        values_read: usize,
+        levels_read: usize,
+        valid_mask: &[u8],
+    ) {
+        self.pad_nulls(read_offset, values_read, levels_read, valid_mask)
+    }
+}
+
+impl<V: ScalarValue + ArrowNativeType> SpillBuffer for ScalarBuffer<V> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn take_values(&mut self, len: usize) -> Self {
+        self.take(len)
+    }
+
+    fn extend_nulls(&mut self, len: usize) {
+        self.resize(self.len() + len)
+    }
+
+    fn extend_from_dictionary_array<K: ArrowNativeType>(
+        &mut self,
+        keys: &[K],
+        dictionary: &ArrayRef,
+    ) -> Result<()> {
+        let dict_values =
+            &dictionary.data().buffers()[0].typed_data::<V>()[..dictionary.len()];
+
+        self.reserve(keys.len());
+        for key in keys {
+            let index = key.as_usize();
+            if index >= dict_values.len() {
+                return Err(general_err!(
+                    "dictionary key beyond bounds of dictionary: 0..{}",
+                    dict_values.len()
+                ));
+            }
+            self.push(dict_values[index]);
+        }
+        Ok(())
+    }
+
+    fn pad_appended_nulls(
+        &mut self,
+        read_offset: usize,
+        values_read: usize,
+        levels_read: usize,
+        valid_mask: &[u8],
+    ) {
+        self.resize(read_offset + levels_read);
+        self.pad_nulls(read_offset, values_read, levels_read, valid_mask)
+    }
+}
+
+/// An array of values that are potentially dictionary encoded, where `B` is the
+/// [`SpillBuffer`] used once the dictionary encoding can no longer be preserved
+pub enum GenericDictionaryBuffer<K: ScalarValue, B> {
     Dict {
         keys: ScalarBuffer<K>,
         values: ArrayRef,
     },
     Values {
-        values: OffsetBuffer<V>,
+        values: B,
     },
 }
 
-impl<K: ScalarValue, V: ScalarValue> Default for DictionaryBuffer<K, V> {
+/// An array of variable length byte arrays that are potentially dictionary encoded
+/// and can be converted into a corresponding [`ArrayRef`]
+pub type DictionaryBuffer<K, V> = GenericDictionaryBuffer<K, OffsetBuffer<V>>;
+
+impl<K: ScalarValue, B: Default> Default for GenericDictionaryBuffer<K, B> {
     fn default() -> Self {
         Self::Values {
             values: Default::default(),
@@ -47,8 +179,8 @@ impl<K: ScalarValue, V: ScalarValue> Default for DictionaryBuffer<K, V> {
     }
 }
 
-impl<K: ScalarValue + ArrowNativeType + Ord, V: ScalarValue + OffsetSizeTrait>
-    DictionaryBuffer<K, V>
+impl<K: ScalarValue + ArrowNativeType + Ord, B: SpillBuffer>
+    GenericDictionaryBuffer<K, B>
 {
     #[allow(unused)]
     pub fn len(&self) -> usize {
@@ -102,29 +234,22 @@ impl<K: ScalarValue + ArrowNativeType + Ord, V: ScalarValue + OffsetSizeTrait>
     ///
     /// If this is currently dictionary encoded, this will convert from the
     /// dictionary encoded representation
-    pub fn spill_values(&mut self) -> Result<&mut OffsetBuffer<V>> {
+    pub fn spill_values(&mut self) -> Result<&mut B> {
         match self {
             Self::Values { values } => Ok(values),
             Self::Dict { keys, values } => {
-                let mut spilled = OffsetBuffer::default();
-                let dict_buffers = values.data().buffers();
-                let dict_offsets = dict_buffers[0].typed_data::<V>();
-                let dict_values = dict_buffers[1].as_slice();
+                let mut spilled = B::default();
 
                 if values.is_empty() {
-                    // If dictionary is empty, zero pad offsets
-                    spilled.offsets.resize(keys.len() + 1);
+                    // If dictionary is empty, all values must be null
+                    spilled.extend_nulls(keys.len());
                 } else {
                     // Note: at this point null positions will have arbitrary dictionary keys
-                    // and this will hydrate them to the corresponding byte array. This is
+                    // and this will hydrate them to the corresponding value. This is
                     // likely sub-optimal, as we would prefer zero length null "slots", but
                     // spilling is already a degenerate case and so it is unclear if this is
                     // worth optimising for, e.g. by keeping a null mask around
-                    spilled.extend_from_dictionary(
-                        keys.as_slice(),
-                        dict_offsets,
-                        dict_values,
-                    )?;
+                    spilled.extend_from_dictionary_array(keys.as_slice(), values)?;
                 }
 
                 *self = Self::Values { values: spilled };
@@ -135,7 +260,11 @@ impl<K: ScalarValue + ArrowNativeType + Ord, V: ScalarValue + OffsetSizeTrait>
             }
         }
     }
+}
 
+impl<K: ScalarValue + ArrowNativeType + Ord, V: ScalarValue + OffsetSizeTrait>
+    DictionaryBuffer<K, V>
+{
     /// Converts this into an [`ArrayRef`] with the provided `data_type` and `null_buffer`
     pub fn into_array(
         self,
@@ -146,32 +275,7 @@ impl<K: ScalarValue + ArrowNativeType + Ord, V: ScalarValue + OffsetSizeTrait>
 
         match self {
             Self::Dict { keys, values } => {
-                // Validate keys unless dictionary is empty
-                if !values.is_empty() {
-                    let min = K::from_usize(0).unwrap();
-                    let max = K::from_usize(values.len()).unwrap();
-
-                    // It may be possible to use SIMD here
-                    if keys.as_slice().iter().any(|x| *x < min || *x >= max) {
-                        return Err(general_err!(
-                            "dictionary key beyond bounds of dictionary: 0..{}",
-                            values.len()
-                        ));
-                    }
-                }
-
-                let builder = ArrayDataBuilder::new(data_type.clone())
-                    .len(keys.len())
-                    .add_buffer(keys.into())
-                    .add_child_data(values.into_data())
-                    .null_bit_buffer(null_buffer);
-
-                let data = match cfg!(debug_assertions) {
-                    true => builder.build().unwrap(),
-                    false => unsafe { builder.build_unchecked() },
-                };
-
-                Ok(make_array(data))
+                keys_into_array(keys, values, null_buffer, data_type)
             }
             Self::Values { values } => {
                 let value_type = match data_type {
@@ -192,15 +296,49 @@ impl<K: ScalarValue + ArrowNativeType + Ord, V: ScalarValue + OffsetSizeTrait>
     }
 }
 
-impl<K: ScalarValue, V: ScalarValue> ValuesBufferSlice for DictionaryBuffer<K, V> {
+/// Converts `keys` into a dictionary array of `data_type` with the provided `values`
+/// and `null_buffer`, returning an error if any key is out of bounds
+pub fn keys_into_array<K: ScalarValue + ArrowNativeType + Ord>(
+    keys: ScalarBuffer<K>,
+    values: ArrayRef,
+    null_buffer: Option<Buffer>,
+    data_type: &ArrowType,
+) -> Result<ArrayRef> {
+    // Validate keys unless dictionary is empty
+    if !values.is_empty() {
+        let min = K::from_usize(0).unwrap();
+        let max = K::from_usize(values.len()).unwrap();
+
+        // It may be possible to use SIMD here
+        if keys.as_slice().iter().any(|x| *x < min || *x >= max) {
+            return Err(general_err!(
+                "dictionary key beyond bounds of dictionary: 0..{}",
+                values.len()
+            ));
+        }
+    }
+
+    let builder = ArrayDataBuilder::new(data_type.clone())
+        .len(keys.len())
+        .add_buffer(keys.into())
+        .add_child_data(values.into_data())
+        .null_bit_buffer(null_buffer);
+
+    let data = match cfg!(debug_assertions) {
+        true => builder.build().unwrap(),
+        false => unsafe { builder.build_unchecked() },
+    };
+
+    Ok(make_array(data))
+}
+
+impl<K: ScalarValue, B> ValuesBufferSlice for GenericDictionaryBuffer<K, B> {
     fn capacity(&self) -> usize {
         usize::MAX
     }
 }
 
-impl<K: ScalarValue, V: ScalarValue + OffsetSizeTrait> ValuesBuffer
-    for DictionaryBuffer<K, V>
-{
+impl<K: ScalarValue, B: SpillBuffer> ValuesBuffer for GenericDictionaryBuffer<K, B> {
     fn pad_nulls(
         &mut self,
         read_offset: usize,
@@ -213,16 +351,17 @@ impl<K: ScalarValue, V: ScalarValue + OffsetSizeTrait> ValuesBuffer
                 keys.resize(read_offset + levels_read);
                 keys.pad_nulls(read_offset, values_read, levels_read, valid_mask)
             }
-            Self::Values { values, .. } => {
-                values.pad_nulls(read_offset, values_read, levels_read, valid_mask)
-            }
+            Self::Values { values, .. } => values.pad_appended_nulls(
+                read_offset,
+                values_read,
+                levels_read,
+                valid_mask,
+            ),
         }
     }
 }
 
-impl<K: ScalarValue, V: ScalarValue + OffsetSizeTrait> BufferQueue
-    for DictionaryBuffer<K, V>
-{
+impl<K: ScalarValue, B: SpillBuffer> BufferQueue for GenericDictionaryBuffer<K, B> {
     type Output = Self;
     type Slice = Self;
 
@@ -233,7 +372,7 @@ impl<K: ScalarValue, V: ScalarValue + OffsetSizeTrait> BufferQueue
                 values: values.clone(),
             },
             Self::Values { values } => Self::Values {
-                values: values.split_off(len),
+                values: values.take_values(len),
             },
         }
     }
@@ -250,6 +389,122 @@ impl<K: ScalarValue, V: ScalarValue + OffsetSizeTrait> BufferQueue
     }
 }
 
+/// The decoder for a data page of a dictionary encoded column
+///
+/// If the data is dictionary encoded the keys are decoded directly, so that the
+/// dictionary encoding can be preserved. Otherwise the values are decoded using the
+/// fallback decoder `F`, and a fresh dictionary computed when converting to arrow
+pub enum MaybeDictionaryDecoder<F> {
+    Dict {
+        decoder: RleDecoder,
+        /// This is a maximum as the null count is not always known, e.g. value data from
+        /// a v1 data page
+        max_remaining_values: usize,
+    },
+    Fallback(F),
+}
+
+impl<F> MaybeDictionaryDecoder<F> {
+    /// Creates a decoder for a data page with the provided `encoding`, calling
+    /// `fallback` to create a decoder if the page is not dictionary encoded
+    pub fn new(
+        encoding: Encoding,
+        data: ByteBufferPtr,
+        num_levels: usize,
+        num_values: Option<usize>,
+        fallback: impl FnOnce(Encoding, ByteBufferPtr, usize, Option<usize>) -> Result<F>,
+    ) -> Result<Self> {
+        Ok(match encoding {
+            Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY => {
+                let bit_width = data[0];
+                let mut decoder = RleDecoder::new(bit_width);
+                decoder.set_data(data.start_from(1));
+                Self::Dict {
+                    decoder,
+                    max_remaining_values: num_values.unwrap_or(num_levels),
+                }
+            }
+            _ => Self::Fallback(fallback(encoding, data, num_levels, num_values)?),
+        })
+    }
+
+    /// Reads up to `range.end - range.start` values into `out`, preserving `dict` if
+    /// possible, and calling `fallback` to read values that are not dictionary encoded
+    pub fn read<K, B>(
+        &mut self,
+        out: &mut GenericDictionaryBuffer<K, B>,
+        range: Range<usize>,
+        dict: Option<&ArrayRef>,
+        fallback: impl FnOnce(&mut F, &mut B, usize) -> Result<usize>,
+    ) -> Result<usize>
+    where
+        K: FromBytes + ScalarValue + ArrowNativeType + Ord,
+        B: SpillBuffer,
+    {
+        let len = range.end - range.start;
+        match self {
+            Self::Fallback(decoder) => fallback(decoder, out.spill_values()?, len),
+            Self::Dict {
+                decoder,
+                max_remaining_values,
+            } => {
+                let len = len.min(*max_remaining_values);
+
+                let dict = dict
+                    .ok_or_else(|| general_err!("missing dictionary page for column"))?;
+
+                if dict.is_empty() {
+                    return Ok(0); // All data must be NULL
+                }
+
+                let read = match out.as_keys(dict) {
+                    Some(keys) => {
+                        // Happy path - can just copy keys
+                        // Keys will be validated on conversion to arrow
+                        let start = keys.len();
+                        let read = decoder.get_batch(keys.spare_capacity_mut(len))?;
+                        keys.set_len(start + read);
+                        read
+                    }
+                    None => {
+                        // Sad path - need to recompute dictionary
+                        //
+                        // This either means we crossed into a new column chunk whilst
+                        // reading this batch, or encountered non-dictionary encoded data
+                        let values = out.spill_values()?;
+                        let mut keys = vec![K::default(); len];
+                        let read = decoder.get_batch(&mut keys)?;
+                        values.extend_from_dictionary_array(&keys[..read], dict)?;
+                        read
+                    }
+                };
+                *max_remaining_values -= read;
+                Ok(read)
+            }
+        }
+    }
+
+    /// Skips up to `num_values` values, calling `fallback` to skip values that are not
+    /// dictionary encoded
+    pub fn skip(
+        &mut self,
+        num_values: usize,
+        fallback: impl FnOnce(&mut F, usize) -> Result<usize>,
+    ) -> Result<usize> {
+        match self {
+            Self::Fallback(decoder) => fallback(decoder, num_values),
+            Self::Dict {
+                decoder,
+                max_remaining_values,
+            } => {
+                let num_values = num_values.min(*max_remaining_values);
+                *max_remaining_values -= num_values;
+                decoder.skip(num_values)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
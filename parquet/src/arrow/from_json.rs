@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of newline-delimited JSON to parquet
+//!
+//! ```
+//! # use std::io::Cursor;
+//! # use arrow_json::ReaderBuilder;
+//! # use parquet::arrow::from_json::json_to_parquet;
+//! let json = r#"{"a": 1, "b": "hello"}
+//! {"a": 2, "b": "world"}
+//! "#;
+//!
+//! // Infer the schema from the first 100 records
+//! let builder = ReaderBuilder::new().infer_schema(Some(100));
+//!
+//! let mut parquet = Vec::new();
+//! let metadata = json_to_parquet(Cursor::new(json), &mut parquet, builder, None).unwrap();
+//! assert_eq!(metadata.num_rows, 2);
+//! ```
+
+use std::io::{Read, Seek, Write};
+
+use arrow_json::ReaderBuilder;
+
+use crate::arrow::ArrowWriter;
+use crate::errors::Result;
+use crate::file::properties::WriterProperties;
+use crate::format::FileMetaData;
+
+/// Converts the newline-delimited JSON of `input` to parquet, written to `output`
+/// with the writer properties `props`, returning the metadata of the written file
+///
+/// `builder` configures the decoding of `input`, and either supplies the schema of
+/// the parquet file with [`ReaderBuilder::with_schema`] or infers it from `input`
+/// with [`ReaderBuilder::infer_schema`], in which case `input` is read twice
+pub fn json_to_parquet<R: Read + Seek, W: Write>(
+    input: R,
+    output: W,
+    builder: ReaderBuilder,
+    props: Option<WriterProperties>,
+) -> Result<FileMetaData> {
+    let reader = builder.build(input)?;
+    let mut writer = ArrowWriter::try_new(output, reader.schema(), props)?;
+    for batch in reader {
+        writer.write(&batch?)?;
+    }
+    writer.close()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use arrow_array::cast::{as_primitive_array, as_string_array};
+    use arrow_array::types::Int64Type;
+    use arrow_schema::{DataType, Field, Schema};
+    use bytes::Bytes;
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    const JSON: &str = r#"{"a": 1, "b": "foo"}
+{"a": 2}
+{"a": 3, "b": "bar", "c": true}
+"#;
+
+    #[test]
+    fn test_json_to_parquet_infer_schema() {
+        let builder = ReaderBuilder::new().infer_schema(None).with_batch_size(2);
+        let mut buf = Vec::new();
+        let metadata =
+            json_to_parquet(Cursor::new(JSON), &mut buf, builder, None).unwrap();
+        assert_eq!(metadata.num_rows, 3);
+        assert_eq!(metadata.row_groups.len(), 1);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+        let schema = batch.schema();
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+
+        let a = as_primitive_array::<Int64Type>(batch.column(0));
+        assert_eq!(a.values(), &[1, 2, 3]);
+        let b = as_string_array(batch.column(1));
+        assert_eq!(
+            b.iter().collect::<Vec<_>>(),
+            vec![Some("foo"), None, Some("bar")]
+        );
+    }
+
+    #[test]
+    fn test_json_to_parquet_schema() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let builder = ReaderBuilder::new().with_schema(schema.clone());
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(2)
+            .build();
+
+        let mut buf = Vec::new();
+        let metadata =
+            json_to_parquet(Cursor::new(JSON), &mut buf, builder, Some(props)).unwrap();
+        assert_eq!(metadata.num_rows, 3);
+        assert_eq!(metadata.row_groups.len(), 2);
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf)).unwrap();
+        assert_eq!(builder.schema().fields(), schema.fields());
+    }
+
+    #[test]
+    fn test_json_to_parquet_invalid() {
+        let builder = ReaderBuilder::new().infer_schema(None);
+        let err = json_to_parquet(Cursor::new("{\"a\": "), Vec::new(), builder, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("Arrow: underlying Arrow error"));
+    }
+}
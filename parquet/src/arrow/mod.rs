@@ -29,8 +29,8 @@
 //! use arrow_array::RecordBatch;
 //! use parquet::arrow::arrow_writer::ArrowWriter;
 //! use parquet::file::properties::WriterProperties;
-//! use std::fs::File;
 //! use std::sync::Arc;
+//! # use tempfile::tempfile;
 //! let ids = Int32Array::from(vec![1, 2, 3, 4]);
 //! let vals = Int32Array::from(vec![5, 6, 7, 8]);
 //! let batch = RecordBatch::try_from_iter(vec![
@@ -38,7 +38,7 @@
 //!   ("val", Arc::new(vals) as ArrayRef),
 //! ]).unwrap();
 //!
-//! let file = File::create("data.parquet").unwrap();
+//! let file = tempfile().unwrap();
 //!
 //! // Default writer properties
 //! let props = WriterProperties::builder().build();
@@ -66,7 +66,6 @@
 //! # Example of reading parquet file into arrow record batch
 //!
 //! ```rust
-//! use std::fs::File;
 //! use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 //!
 //! # use std::sync::Arc;
@@ -74,26 +73,27 @@
 //! # use arrow::datatypes::{DataType, Field, Schema};
 //! # use arrow_array::RecordBatch;
 //! # use parquet::arrow::arrow_writer::ArrowWriter;
+//! # use tempfile::tempfile;
 //! #
 //! # let ids = Int32Array::from(vec![1, 2, 3, 4]);
 //! # let schema = Arc::new(Schema::new(vec![
 //! #     Field::new("id", DataType::Int32, false),
 //! # ]));
 //! #
-//! # let file = File::create("data.parquet").unwrap();
+//! # let file = tempfile().unwrap();
 //! #
 //! # let batch = RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(ids)]).unwrap();
 //! # let batches = vec![batch];
 //! #
-//! # let mut writer = ArrowWriter::try_new(file, Arc::clone(&schema), None).unwrap();
+//! # let mut writer =
+//! #     ArrowWriter::try_new(file.try_clone().unwrap(), Arc::clone(&schema), None)
+//! #         .unwrap();
 //! #
 //! # for batch in batches {
 //! #     writer.write(&batch).expect("Writing batch");
 //! # }
 //! # writer.close().unwrap();
 //!
-//! let file = File::open("data.parquet").unwrap();
-//!
 //! let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
 //! println!("Converted arrow schema is: {}", builder.schema());
 //!
@@ -112,6 +112,10 @@ mod decoder;
 
 #[cfg(feature = "async")]
 pub mod async_reader;
+#[cfg(feature = "async")]
+pub mod async_writer;
+#[cfg(feature = "arrow-json")]
+pub mod from_json;
 
 mod record_reader;
 experimental!(mod schema);
@@ -121,6 +125,8 @@ pub use self::arrow_reader::{ArrowReader, ParquetFileArrowReader};
 pub use self::arrow_writer::ArrowWriter;
 #[cfg(feature = "async")]
 pub use self::async_reader::ParquetRecordBatchStreamBuilder;
+#[cfg(feature = "async")]
+pub use self::async_writer::AsyncArrowWriter;
 use crate::schema::types::SchemaDescriptor;
 
 pub use self::schema::{
@@ -130,6 +136,13 @@ pub use self::schema::{
 /// Schema metadata key used to store serialized Arrow IPC schema
 pub const ARROW_SCHEMA_META_KEY: &str = "ARROW:schema";
 
+/// The value of this metadata key, if present on [`Field::metadata`], will be used
+/// to populate [`BasicTypeInfo::id`]
+///
+/// [`Field::metadata`]: arrow_schema::Field::metadata
+/// [`BasicTypeInfo::id`]: crate::schema::types::BasicTypeInfo::id
+pub const PARQUET_FIELD_ID_META_KEY: &str = "PARQUET:field_id";
+
 /// A [`ProjectionMask`] identifies a set of columns within a potentially nested schema to project
 ///
 /// In particular, a [`ProjectionMask`] can be constructed from a list of leaf column indices
@@ -211,8 +224,180 @@ impl ProjectionMask {
         Self { mask: Some(mask) }
     }
 
+    /// Create a [`ProjectionMask`] which selects the columns with the specified
+    /// dotted paths, e.g. `address.city`
+    ///
+    /// The path of a root or nested group column selects all of its leaves, e.g.
+    /// `address` selects both `address.city` and `address.street`, whereas the path
+    /// of a leaf column selects only that leaf
+    ///
+    /// Paths that do not match any column are ignored
+    pub fn columns<'a>(
+        schema: &SchemaDescriptor,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let mut mask = vec![false; schema.num_columns()];
+        for name in names {
+            for (leaf_idx, column) in schema.columns().iter().enumerate() {
+                let path = column.path().string();
+                let matches = match path.strip_prefix(name) {
+                    Some(suffix) => suffix.is_empty() || suffix.starts_with('.'),
+                    None => false,
+                };
+                mask[leaf_idx] |= matches;
+            }
+        }
+        Self { mask: Some(mask) }
+    }
+
     /// Returns true if the leaf column `leaf_idx` is included by the mask
     pub fn leaf_included(&self, leaf_idx: usize) -> bool {
         self.mask.as_ref().map(|m| m[leaf_idx]).unwrap_or(true)
     }
+
+    /// Returns true if any leaf of the root column `root_idx` is included by the mask
+    pub fn root_included(&self, schema: &SchemaDescriptor, root_idx: usize) -> bool {
+        (0..schema.num_columns()).any(|leaf_idx| {
+            schema.get_column_root_idx(leaf_idx) == root_idx
+                && self.leaf_included(leaf_idx)
+        })
+    }
+
+    /// Returns the indices of the root columns included by the mask
+    ///
+    /// The readers return a column for each of these, in the same order, and so the
+    /// `i`-th column of the returned [`RecordBatch`] is the root column at `root_indices[i]`
+    ///
+    /// [`RecordBatch`]: arrow_array::RecordBatch
+    pub fn root_indices(&self, schema: &SchemaDescriptor) -> Vec<usize> {
+        let num_root_columns = schema.root_schema().get_fields().len();
+        let mut root_mask = vec![false; num_root_columns];
+        for leaf_idx in 0..schema.num_columns() {
+            if self.leaf_included(leaf_idx) {
+                root_mask[schema.get_column_root_idx(leaf_idx)] = true;
+            }
+        }
+        (0..num_root_columns)
+            .filter(|idx| root_mask[*idx])
+            .collect()
+    }
+
+    /// Union this [`ProjectionMask`] with another, selecting the columns
+    /// selected by either mask
+    ///
+    /// # Panics
+    ///
+    /// Panics if the masks are for schemas with a different number of columns
+    pub fn union(&mut self, other: &Self) {
+        match (self.mask.as_mut(), other.mask.as_ref()) {
+            (None, _) => {}
+            (Some(_), None) => self.mask = None,
+            (Some(a), Some(b)) => {
+                assert_eq!(a.len(), b.len(), "mask length mismatch");
+                a.iter_mut().zip(b).for_each(|(a, b)| *a |= *b);
+            }
+        }
+    }
+
+    /// Intersect this [`ProjectionMask`] with another, selecting only the columns
+    /// selected by both masks
+    ///
+    /// # Panics
+    ///
+    /// Panics if the masks are for schemas with a different number of columns
+    pub fn intersect(&mut self, other: &Self) {
+        match (self.mask.as_mut(), other.mask.as_ref()) {
+            (_, None) => {}
+            (None, Some(_)) => self.mask = other.mask.clone(),
+            (Some(a), Some(b)) => {
+                assert_eq!(a.len(), b.len(), "mask length mismatch");
+                a.iter_mut().zip(b).for_each(|(a, b)| *a &= *b);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    fn test_schema() -> SchemaDescriptor {
+        let message_type = "
+        message test_schema {
+            REQUIRED INT32 id;
+            OPTIONAL group address {
+                OPTIONAL BYTE_ARRAY street (UTF8);
+                OPTIONAL BYTE_ARRAY city (UTF8);
+                OPTIONAL group location {
+                    REQUIRED DOUBLE lat;
+                    REQUIRED DOUBLE lon;
+                }
+            }
+            OPTIONAL BYTE_ARRAY addressee (UTF8);
+        }
+        ";
+        let schema = parse_message_type(message_type).unwrap();
+        SchemaDescriptor::new(Arc::new(schema))
+    }
+
+    fn leaves(mask: &ProjectionMask, schema: &SchemaDescriptor) -> Vec<usize> {
+        (0..schema.num_columns())
+            .filter(|idx| mask.leaf_included(*idx))
+            .collect()
+    }
+
+    #[test]
+    fn test_projection_mask_columns() {
+        let schema = test_schema();
+
+        let mask = ProjectionMask::columns(&schema, ["address"]);
+        assert_eq!(leaves(&mask, &schema), vec![1, 2, 3, 4]);
+
+        let mask = ProjectionMask::columns(&schema, ["address.city", "id"]);
+        assert_eq!(leaves(&mask, &schema), vec![0, 2]);
+
+        let mask = ProjectionMask::columns(&schema, ["address.location", "addressee"]);
+        assert_eq!(leaves(&mask, &schema), vec![3, 4, 5]);
+
+        let mask = ProjectionMask::columns(&schema, ["addr", "address.location.alt"]);
+        assert_eq!(leaves(&mask, &schema), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_projection_mask_roots() {
+        let schema = test_schema();
+
+        let mask = ProjectionMask::columns(&schema, ["address.city", "addressee"]);
+        assert!(!mask.root_included(&schema, 0));
+        assert!(mask.root_included(&schema, 1));
+        assert!(mask.root_included(&schema, 2));
+        assert_eq!(mask.root_indices(&schema), vec![1, 2]);
+
+        assert_eq!(ProjectionMask::all().root_indices(&schema), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_projection_mask_set_operations() {
+        let schema = test_schema();
+        let a = ProjectionMask::leaves(&schema, [0, 1, 2]);
+        let b = ProjectionMask::leaves(&schema, [2, 3]);
+
+        let mut union = a.clone();
+        union.union(&b);
+        assert_eq!(leaves(&union, &schema), vec![0, 1, 2, 3]);
+
+        let mut intersection = a.clone();
+        intersection.intersect(&b);
+        assert_eq!(leaves(&intersection, &schema), vec![2]);
+
+        let mut all = ProjectionMask::all();
+        all.intersect(&a);
+        assert_eq!(leaves(&all, &schema), vec![0, 1, 2]);
+
+        let mut all = a;
+        all.union(&ProjectionMask::all());
+        assert_eq!(leaves(&all, &schema), vec![0, 1, 2, 3, 4, 5]);
+    }
 }
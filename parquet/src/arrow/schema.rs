@@ -64,14 +64,23 @@ pub fn parquet_to_arrow_schema_by_columns(
     mask: ProjectionMask,
     key_value_metadata: Option<&Vec<KeyValue>>,
 ) -> Result<Schema> {
-    Ok(parquet_to_array_schema_and_fields(parquet_schema, mask, key_value_metadata)?.0)
+    Ok(parquet_to_array_schema_and_fields(
+        parquet_schema,
+        mask,
+        key_value_metadata,
+        None,
+    )?
+    .0)
 }
 
 /// Extracts the arrow metadata
+///
+/// If provided, INT96 columns are decoded to `int96_type` instead of a nanosecond timestamp
 pub(crate) fn parquet_to_array_schema_and_fields(
     parquet_schema: &SchemaDescriptor,
     mask: ProjectionMask,
     key_value_metadata: Option<&Vec<KeyValue>>,
+    int96_type: Option<&DataType>,
 ) -> Result<(Schema, Option<ParquetField>)> {
     let mut metadata = parse_key_value_metadata(key_value_metadata).unwrap_or_default();
     let maybe_schema = metadata
@@ -86,7 +95,12 @@ pub(crate) fn parquet_to_array_schema_and_fields(
         });
     }
 
-    match complex::convert_schema(parquet_schema, mask, maybe_schema.as_ref())? {
+    match complex::convert_schema(
+        parquet_schema,
+        mask,
+        maybe_schema.as_ref(),
+        int96_type,
+    )? {
         Some(field) => match &field.arrow_type {
             DataType::Struct(fields) => Ok((
                 Schema::new_with_metadata(fields.clone(), metadata),
@@ -251,14 +265,17 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
     } else {
         Repetition::REQUIRED
     };
+    let id = field_id(field);
     // create type from field
     match field.data_type() {
         DataType::Null => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Unknown))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Boolean => Type::primitive_type_builder(name, PhysicalType::BOOLEAN)
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Int8 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Integer {
@@ -266,6 +283,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 is_signed: true,
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Int16 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Integer {
@@ -273,12 +291,15 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 is_signed: true,
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Int32 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Int64 => Type::primitive_type_builder(name, PhysicalType::INT64)
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::UInt8 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Integer {
@@ -286,6 +307,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 is_signed: false,
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::UInt16 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Integer {
@@ -293,6 +315,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 is_signed: false,
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::UInt32 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Integer {
@@ -300,6 +323,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 is_signed: false,
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::UInt64 => Type::primitive_type_builder(name, PhysicalType::INT64)
             .with_logical_type(Some(LogicalType::Integer {
@@ -307,18 +331,22 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 is_signed: false,
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Float16 => Err(arrow_err!("Float16 arrays not supported")),
         DataType::Float32 => Type::primitive_type_builder(name, PhysicalType::FLOAT)
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Float64 => Type::primitive_type_builder(name, PhysicalType::DOUBLE)
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Timestamp(TimeUnit::Second, _) => {
             // Cannot represent seconds in LogicalType
             Type::primitive_type_builder(name, PhysicalType::INT64)
                 .with_repetition(repetition)
+                .with_id(id)
                 .build()
         }
         DataType::Timestamp(time_unit, tz) => {
@@ -340,21 +368,25 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                     },
                 }))
                 .with_repetition(repetition)
+                .with_id(id)
                 .build()
         }
         DataType::Date32 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Date))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         // date64 is cast to date32 (#1666)
         DataType::Date64 => Type::primitive_type_builder(name, PhysicalType::INT32)
             .with_logical_type(Some(LogicalType::Date))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Time32(TimeUnit::Second) => {
             // Cannot represent seconds in LogicalType
             Type::primitive_type_builder(name, PhysicalType::INT32)
                 .with_repetition(repetition)
+                .with_id(id)
                 .build()
         }
         DataType::Time32(unit) => Type::primitive_type_builder(name, PhysicalType::INT32)
@@ -366,6 +398,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 },
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Time64(unit) => Type::primitive_type_builder(name, PhysicalType::INT64)
             .with_logical_type(Some(LogicalType::Time {
@@ -377,6 +410,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 },
             }))
             .with_repetition(repetition)
+            .with_id(id)
             .build(),
         DataType::Duration(_) => {
             Err(arrow_err!("Converting Duration to parquet not supported",))
@@ -385,17 +419,20 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
             Type::primitive_type_builder(name, PhysicalType::FIXED_LEN_BYTE_ARRAY)
                 .with_converted_type(ConvertedType::INTERVAL)
                 .with_repetition(repetition)
+                .with_id(id)
                 .with_length(12)
                 .build()
         }
         DataType::Binary | DataType::LargeBinary => {
             Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
                 .with_repetition(repetition)
+                .with_id(id)
                 .build()
         }
         DataType::FixedSizeBinary(length) => {
             Type::primitive_type_builder(name, PhysicalType::FIXED_LEN_BYTE_ARRAY)
                 .with_repetition(repetition)
+                .with_id(id)
                 .with_length(*length)
                 .build()
         }
@@ -416,6 +453,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
             // };
             Type::primitive_type_builder(name, PhysicalType::FIXED_LEN_BYTE_ARRAY)
                 .with_repetition(repetition)
+                .with_id(id)
                 .with_length(decimal_length_from_precision(*precision) as i32)
                 .with_logical_type(Some(LogicalType::Decimal {
                     scale: *scale as i32,
@@ -429,6 +467,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
             Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
                 .with_logical_type(Some(LogicalType::String))
                 .with_repetition(repetition)
+                .with_id(id)
                 .build()
         }
         DataType::List(f) | DataType::FixedSizeList(f, _) | DataType::LargeList(f) => {
@@ -441,6 +480,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 )])
                 .with_logical_type(Some(LogicalType::List))
                 .with_repetition(repetition)
+                .with_id(id)
                 .build()
         }
         DataType::Struct(fields) => {
@@ -457,6 +497,7 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
             Type::group_type_builder(name)
                 .with_fields(&mut fields?)
                 .with_repetition(repetition)
+                .with_id(id)
                 .build()
         }
         DataType::Map(field, _) => {
@@ -465,22 +506,29 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                     .with_fields(&mut vec![Arc::new(
                         Type::group_type_builder(field.name())
                             .with_fields(&mut vec![
-                                Arc::new(arrow_to_parquet_type(&Field::new(
-                                    struct_fields[0].name(),
-                                    struct_fields[0].data_type().clone(),
-                                    false,
-                                ))?),
-                                Arc::new(arrow_to_parquet_type(&Field::new(
-                                    struct_fields[1].name(),
-                                    struct_fields[1].data_type().clone(),
-                                    struct_fields[1].is_nullable(),
-                                ))?),
+                                Arc::new(arrow_to_parquet_type(
+                                    &Field::new(
+                                        struct_fields[0].name(),
+                                        struct_fields[0].data_type().clone(),
+                                        false,
+                                    )
+                                    .with_metadata(struct_fields[0].metadata().clone()),
+                                )?),
+                                Arc::new(arrow_to_parquet_type(
+                                    &Field::new(
+                                        struct_fields[1].name(),
+                                        struct_fields[1].data_type().clone(),
+                                        struct_fields[1].is_nullable(),
+                                    )
+                                    .with_metadata(struct_fields[1].metadata().clone()),
+                                )?),
                             ])
                             .with_repetition(Repetition::REPEATED)
                             .build()?,
                     )])
                     .with_logical_type(Some(LogicalType::Map))
                     .with_repetition(repetition)
+                    .with_id(id)
                     .build()
             } else {
                 Err(arrow_err!(
@@ -488,15 +536,24 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 ))
             }
         }
-        DataType::Union(_, _, _) => unimplemented!("See ARROW-8817."),
+        DataType::Union(_, _, _) => Err(nyi_err!(
+            "Datatype {} is not yet supported",
+            field.data_type()
+        )),
         DataType::Dictionary(_, ref value) => {
             // Dictionary encoding not handled at the schema level
-            let dict_field = Field::new(name, *value.clone(), field.is_nullable());
+            let dict_field = Field::new(name, *value.clone(), field.is_nullable())
+                .with_metadata(field.metadata().clone());
             arrow_to_parquet_type(&dict_field)
         }
     }
 }
 
+fn field_id(field: &Field) -> Option<i32> {
+    let value = field.metadata().get(super::PARQUET_FIELD_ID_META_KEY)?;
+    value.parse().ok() // Fail quietly if not a valid integer
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -507,7 +564,10 @@ mod tests {
 
     use crate::file::metadata::KeyValue;
     use crate::{
-        arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+        arrow::{
+            arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter,
+            PARQUET_FIELD_ID_META_KEY,
+        },
         schema::{parser::parse_message_type, types::SchemaDescriptor},
     };
 
@@ -559,6 +619,8 @@ mod tests {
                     REQUIRED INT64 decimal2 (DECIMAL(12,2));
                     REQUIRED FIXED_LEN_BYTE_ARRAY (16) decimal3 (DECIMAL(30,2));
                     REQUIRED BYTE_ARRAY decimal4 (DECIMAL(33,2));
+                    REQUIRED FIXED_LEN_BYTE_ARRAY (32) decimal5 (DECIMAL(60,2));
+                    REQUIRED BYTE_ARRAY decimal6 (DECIMAL(39,2));
         }
         ";
 
@@ -573,6 +635,8 @@ mod tests {
             Field::new("decimal2", DataType::Decimal128(12, 2), false),
             Field::new("decimal3", DataType::Decimal128(30, 2), false),
             Field::new("decimal4", DataType::Decimal128(33, 2), false),
+            Field::new("decimal5", DataType::Decimal256(60, 2), false),
+            Field::new("decimal6", DataType::Decimal256(39, 2), false),
         ];
         assert_eq!(&arrow_fields, converted_arrow_schema.fields());
     }
@@ -680,6 +744,17 @@ mod tests {
               REQUIRED BINARY str (UTF8);
             }
           }
+          OPTIONAL GROUP my_list (LIST) {
+            REPEATED GROUP array (LIST) {
+              REPEATED INT32 array;
+            }
+          }
+          OPTIONAL GROUP my_list (LIST) {
+            REPEATED GROUP key_value (MAP_KEY_VALUE) {
+              REQUIRED BINARY key (UTF8);
+              OPTIONAL INT32 value;
+            }
+          }
           REPEATED INT32 name;
         }
         ";
@@ -817,6 +892,43 @@ mod tests {
             ));
         }
 
+        // // List<List<Integer>> (nullable list, non-null elements)
+        // optional group my_list (LIST) {
+        //   repeated group array (LIST) {
+        //     repeated int32 array;
+        //   };
+        // }
+        // Special case: repeated group is itself a list
+        {
+            let arrow_inner_list =
+                DataType::List(Box::new(Field::new("array", DataType::Int32, false)));
+            arrow_fields.push(Field::new(
+                "my_list",
+                DataType::List(Box::new(Field::new("array", arrow_inner_list, false))),
+                true,
+            ));
+        }
+
+        // // List<Tuple<String, Integer>> (nullable list, non-null elements)
+        // optional group my_list (LIST) {
+        //   repeated group key_value (MAP_KEY_VALUE) {
+        //     required binary key (UTF8);
+        //     optional int32 value;
+        //   };
+        // }
+        // Special case: repeated group is annotated MAP_KEY_VALUE, but is not a map
+        {
+            let arrow_struct = DataType::Struct(vec![
+                Field::new("key", DataType::Utf8, false),
+                Field::new("value", DataType::Int32, true),
+            ]);
+            arrow_fields.push(Field::new(
+                "my_list",
+                DataType::List(Box::new(Field::new("key_value", arrow_struct, false))),
+                true,
+            ));
+        }
+
         // One-level encoding: Only allows required lists with required cells
         //   repeated value_type name
         {
@@ -1622,7 +1734,7 @@ mod tests {
                 // ),
                 Field::new("c35", DataType::Null, true),
                 Field::new("c36", DataType::Decimal128(2, 1), false),
-                Field::new("c37", DataType::Decimal128(50, 20), false),
+                Field::new("c37", DataType::Decimal256(50, 20), false),
                 Field::new("c38", DataType::Decimal128(18, 12), true),
                 Field::new(
                     "c39",
@@ -1777,6 +1889,98 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parquet_field_ids() {
+        let message_type = "
+        message test_schema {
+            REQUIRED INT32 a = 1;
+            OPTIONAL group b (LIST) = 2 {
+                REPEATED group list {
+                    OPTIONAL BYTE_ARRAY element (UTF8) = 3;
+                }
+            }
+            OPTIONAL INT64 c;
+        }
+        ";
+        let parquet_group_type = parse_message_type(message_type).unwrap();
+        let parquet_schema = SchemaDescriptor::new(Arc::new(parquet_group_type));
+        let converted_arrow_schema =
+            parquet_to_arrow_schema(&parquet_schema, None).unwrap();
+
+        let fields = converted_arrow_schema.fields();
+        let field_id = |f: &Field| f.metadata().get(PARQUET_FIELD_ID_META_KEY).cloned();
+        assert_eq!(field_id(&fields[0]).as_deref(), Some("1"));
+        assert_eq!(field_id(&fields[1]).as_deref(), Some("2"));
+        match fields[1].data_type() {
+            DataType::List(item) => assert_eq!(field_id(item).as_deref(), Some("3")),
+            d => panic!("unexpected data type {}", d),
+        }
+        assert_eq!(field_id(&fields[2]), None);
+    }
+
+    #[test]
+    fn test_arrow_schema_roundtrip_field_ids() -> Result<()> {
+        let field_id = |id: i32| {
+            vec![(PARQUET_FIELD_ID_META_KEY.to_string(), id.to_string())]
+                .into_iter()
+                .collect::<HashMap<_, _>>()
+        };
+
+        let schema = Schema::new(vec![
+            Field::new("c1", DataType::Utf8, false).with_metadata(field_id(1)),
+            Field::new("c2", DataType::Int32, true),
+            Field::new(
+                "c3",
+                DataType::List(Box::new(
+                    Field::new("item", DataType::Int64, true).with_metadata(field_id(4)),
+                )),
+                true,
+            )
+            .with_metadata(field_id(3)),
+            Field::new(
+                "c4",
+                DataType::Struct(vec![
+                    Field::new("a", DataType::Int16, true).with_metadata(field_id(6))
+                ]),
+                false,
+            )
+            .with_metadata(field_id(5)),
+        ]);
+
+        let parquet_schema = arrow_to_parquet_schema(&schema)?;
+        let ids: Vec<_> = parquet_schema
+            .columns()
+            .iter()
+            .map(|c| {
+                let info = c.self_type().get_basic_info();
+                info.has_id().then(|| info.id())
+            })
+            .collect();
+        assert_eq!(ids, vec![Some(1), None, Some(4), Some(6)]);
+
+        let fields = parquet_schema.root_schema().get_fields();
+        assert_eq!(fields[2].get_basic_info().id(), 3);
+        assert_eq!(fields[3].get_basic_info().id(), 5);
+
+        // Field ids are recovered from the parquet schema without an embedded arrow schema
+        let converted = parquet_to_arrow_schema(&parquet_schema, None)?;
+        assert_eq!(converted, schema);
+
+        // write to an empty parquet file and read back
+        let file = tempfile::tempfile().unwrap();
+        let writer = ArrowWriter::try_new(
+            file.try_clone().unwrap(),
+            Arc::new(schema.clone()),
+            None,
+        )?;
+        writer.close()?;
+
+        let arrow_reader = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let read_schema = arrow_reader.schema();
+        assert_eq!(&schema, read_schema.as_ref());
+        Ok(())
+    }
+
     #[test]
     fn test_get_arrow_schema_from_metadata() {
         assert!(get_arrow_schema_from_metadata("").is_err());
@@ -16,10 +16,11 @@
 // under the License.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::arrow::schema::primitive::convert_primitive;
-use crate::arrow::ProjectionMask;
-use crate::basic::{ConvertedType, Repetition};
+use crate::arrow::{ProjectionMask, PARQUET_FIELD_ID_META_KEY};
+use crate::basic::{ConvertedType, Repetition, Type as PhysicalType};
 use crate::errors::ParquetError;
 use crate::errors::Result;
 use crate::schema::types::{SchemaDescriptor, Type, TypePtr};
@@ -34,6 +35,7 @@ fn get_repetition(t: &Type) -> Repetition {
 }
 
 /// Representation of a parquet file, in terms of arrow schema elements
+#[derive(Debug, Clone)]
 pub struct ParquetField {
     /// The level which represents an insertion into the current list
     /// i.e. guaranteed to be > 0 for a list type
@@ -81,6 +83,7 @@ impl ParquetField {
     }
 }
 
+#[derive(Debug, Clone)]
 pub enum ParquetFieldType {
     Primitive {
         /// The index of the column in parquet
@@ -124,6 +127,9 @@ struct Visitor {
 
     /// Mask of columns to include
     mask: ProjectionMask,
+
+    /// The arrow type to decode INT96 columns to, if not a nanosecond timestamp
+    int96_type: Option<DataType>,
 }
 
 impl Visitor {
@@ -142,7 +148,14 @@ impl Visitor {
         let repetition = get_repetition(primitive_type);
         let (def_level, rep_level, nullable) = context.levels(repetition);
 
-        let arrow_type = convert_primitive(primitive_type, context.data_type)?;
+        let arrow_type = match &self.int96_type {
+            Some(int96_type)
+                if primitive_type.get_physical_type() == PhysicalType::INT96 =>
+            {
+                int96_type.clone()
+            }
+            _ => convert_primitive(primitive_type, context.data_type)?,
+        };
 
         let primitive_field = ParquetField {
             rep_level,
@@ -442,31 +455,49 @@ impl Visitor {
         }
 
         let items = repeated_field.get_fields();
-        if items.len() != 1
-            || repeated_field.name() == "array"
-            || repeated_field.name() == format!("{}_tuple", list_type.name())
-        {
-            // If the repeated field is a group with multiple fields, then its type is the element type and elements are required.
-            //
-            // If the repeated field is a group with one field and is named either array or uses the LIST-annotated group's name
-            // with _tuple appended then the repeated type is the element type and elements are required.
-            let context = VisitorContext {
-                rep_level: context.rep_level,
-                def_level,
-                data_type: arrow_field.map(|f| f.data_type().clone()),
-            };
+        let item_type = match repeated_field.get_basic_info().converted_type() {
+            ConvertedType::LIST | ConvertedType::MAP => {
+                // If the repeated field is itself an annotated list or map, as written by
+                // some legacy writers, then the repeated type is the element type.
+                //
+                // optional group my_list (LIST) {
+                //   repeated group array (LIST) {
+                //     repeated int32 array;
+                //   }
+                // }
+                //
+                // This is equivalent to a required element within a 3-level list.
+                //
+                // A repeated group only annotated MAP_KEY_VALUE is not a map, and is
+                // handled as a struct below, as it was by legacy writers
+                required_element(repeated_field)?
+            }
+            _ if items.len() != 1
+                || repeated_field.name() == "array"
+                || repeated_field.name() == format!("{}_tuple", list_type.name()) =>
+            {
+                // If the repeated field is a group with multiple fields, then its type is the element type and elements are required.
+                //
+                // If the repeated field is a group with one field and is named either array or uses the LIST-annotated group's name
+                // with _tuple appended then the repeated type is the element type and elements are required.
+                let context = VisitorContext {
+                    rep_level: context.rep_level,
+                    def_level,
+                    data_type: arrow_field.map(|f| f.data_type().clone()),
+                };
 
-            return match self.visit_struct(repeated_field, context) {
-                Ok(Some(mut field)) => {
-                    field.nullable = nullable;
-                    Ok(Some(field))
-                }
-                r => r,
-            };
-        }
+                return match self.visit_struct(repeated_field, context) {
+                    Ok(Some(mut field)) => {
+                        field.nullable = nullable;
+                        Ok(Some(field))
+                    }
+                    r => r,
+                };
+            }
+            _ => items[0].clone(),
+        };
 
         // Regular list handling logic
-        let item_type = &items[0];
         let rep_level = context.rep_level + 1;
         let def_level = def_level + 1;
 
@@ -476,9 +507,9 @@ impl Visitor {
             data_type: arrow_field.map(|f| f.data_type().clone()),
         };
 
-        match self.dispatch(item_type, new_context) {
+        match self.dispatch(&item_type, new_context) {
             Ok(Some(item)) => {
-                let item_field = Box::new(convert_field(item_type, &item, arrow_field));
+                let item_field = Box::new(convert_field(&item_type, &item, arrow_field));
 
                 // Use arrow type as hint for index size
                 let arrow_type = match context.data_type {
@@ -522,6 +553,20 @@ impl Visitor {
     }
 }
 
+/// Returns a copy of the repeated group `repeated` with [`Repetition::REQUIRED`]
+fn required_element(repeated: &Type) -> Result<TypePtr> {
+    let info = repeated.get_basic_info();
+    let mut fields = repeated.get_fields().to_vec();
+    let builder = Type::group_type_builder(info.name())
+        .with_repetition(Repetition::REQUIRED)
+        .with_converted_type(info.converted_type())
+        .with_logical_type(info.logical_type())
+        .with_fields(&mut fields)
+        .with_id(info.has_id().then(|| info.id()));
+
+    Ok(Arc::new(builder.build()?))
+}
+
 /// Computes the [`Field`] for a child column
 ///
 /// The resulting [`Field`] will have the type dictated by `field`, a name
@@ -545,12 +590,23 @@ fn convert_field(
                 _ => Field::new(name, data_type, nullable),
             };
 
-            field.with_metadata(hint.metadata().clone())
+            let mut metadata = hint.metadata().clone();
+            metadata.extend(field_id_metadata(parquet_type));
+            field.with_metadata(metadata)
         }
-        None => Field::new(name, data_type, nullable),
+        None => Field::new(name, data_type, nullable)
+            .with_metadata(field_id_metadata(parquet_type).collect()),
     }
 }
 
+/// Returns the [`PARQUET_FIELD_ID_META_KEY`] metadata entry for `parquet_type`, if any
+fn field_id_metadata(parquet_type: &Type) -> impl Iterator<Item = (String, String)> {
+    let info = parquet_type.get_basic_info();
+    info.has_id()
+        .then(|| (PARQUET_FIELD_ID_META_KEY.to_string(), info.id().to_string()))
+        .into_iter()
+}
+
 /// Computes the [`ParquetField`] for the provided [`SchemaDescriptor`] with `leaf_columns` listing
 /// the indexes of leaf columns to project, and `embedded_arrow_schema` the optional
 /// [`Schema`] embedded in the parquet metadata
@@ -560,10 +616,12 @@ pub fn convert_schema(
     schema: &SchemaDescriptor,
     mask: ProjectionMask,
     embedded_arrow_schema: Option<&Schema>,
+    int96_type: Option<&DataType>,
 ) -> Result<Option<ParquetField>> {
     let mut visitor = Visitor {
         next_col_idx: 0,
         mask,
+        int96_type: int96_type.cloned(),
     };
 
     let context = VisitorContext {
@@ -580,6 +638,7 @@ pub fn convert_type(parquet_type: &TypePtr) -> Result<ParquetField> {
     let mut visitor = Visitor {
         next_col_idx: 0,
         mask: ProjectionMask::all(),
+        int96_type: None,
     };
 
     let context = VisitorContext {
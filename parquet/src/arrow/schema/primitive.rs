@@ -20,7 +20,7 @@ use crate::basic::{
 };
 use crate::errors::{ParquetError, Result};
 use crate::schema::types::{BasicTypeInfo, Type};
-use arrow_schema::{DataType, IntervalUnit, TimeUnit};
+use arrow_schema::{DataType, IntervalUnit, TimeUnit, DECIMAL128_MAX_PRECISION};
 
 /// Converts [`Type`] to [`DataType`] with an optional `arrow_type_hint`
 /// provided by the arrow schema
@@ -62,6 +62,13 @@ fn apply_hint(parquet: DataType, hint: DataType) -> DataType {
         // Determine interval time unit (#1666)
         (DataType::Interval(_), DataType::Interval(_)) => hint,
 
+        // Widen decimal to 256-bit
+        (DataType::Decimal128(p, s), DataType::Decimal256(h_p, h_s))
+            if p == h_p && s == h_s =>
+        {
+            hint
+        }
+
         // Potentially preserve dictionary encoding
         (_, DataType::Dictionary(_, value)) => {
             // Apply hint to inner type
@@ -112,7 +119,10 @@ fn decimal_type(scale: i32, precision: i32) -> Result<DataType> {
         .try_into()
         .map_err(|_| arrow_err!("precision cannot be negative: {}", precision))?;
 
-    Ok(DataType::Decimal128(precision, scale))
+    match precision <= DECIMAL128_MAX_PRECISION {
+        true => Ok(DataType::Decimal128(precision, scale)),
+        false => Ok(DataType::Decimal256(precision, scale)),
+    }
 }
 
 fn from_int32(info: &BasicTypeInfo, scale: i32, precision: i32) -> Result<DataType> {
@@ -235,14 +245,10 @@ fn from_byte_array(info: &BasicTypeInfo, precision: i32, scale: i32) -> Result<D
         (None, ConvertedType::BSON) => Ok(DataType::Binary),
         (None, ConvertedType::ENUM) => Ok(DataType::Binary),
         (None, ConvertedType::UTF8) => Ok(DataType::Utf8),
-        (Some(LogicalType::Decimal { precision, scale }), _) => Ok(DataType::Decimal128(
-            precision.try_into().unwrap(),
-            scale.try_into().unwrap(),
-        )),
-        (None, ConvertedType::DECIMAL) => Ok(DataType::Decimal128(
-            precision.try_into().unwrap(),
-            scale.try_into().unwrap(),
-        )),
+        (Some(LogicalType::Decimal { precision, scale }), _) => {
+            decimal_type(scale, precision)
+        }
+        (None, ConvertedType::DECIMAL) => decimal_type(scale, precision),
         (logical, converted) => Err(arrow_err!(
             "Unable to convert parquet BYTE_ARRAY logical type {:?} or converted type {}",
             logical,
@@ -988,6 +988,28 @@ impl str::FromStr for LogicalType {
     }
 }
 
+impl str::FromStr for Compression {
+    type Err = ParquetError;
+
+    /// Parses a compression codec from its case-insensitive name, e.g. `zstd`
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "UNCOMPRESSED" => Ok(Compression::UNCOMPRESSED),
+            "SNAPPY" => Ok(Compression::SNAPPY),
+            "GZIP" => Ok(Compression::GZIP),
+            "LZO" => Ok(Compression::LZO),
+            "BROTLI" => Ok(Compression::BROTLI),
+            "LZ4" => Ok(Compression::LZ4),
+            "LZ4_RAW" => Ok(Compression::LZ4_RAW),
+            "ZSTD" => Ok(Compression::ZSTD),
+            other => Err(general_err!(
+                "Unknown compression {}: possible values UNCOMPRESSED, SNAPPY, GZIP, LZO, BROTLI, LZ4, LZ4_RAW, ZSTD",
+                other
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1783,6 +1805,24 @@ mod tests {
         assert_eq!(Compression::ZSTD.to_string(), "ZSTD");
     }
 
+    #[test]
+    fn test_from_string_into_compression() {
+        assert_eq!(
+            "UNCOMPRESSED".parse::<Compression>().unwrap(),
+            Compression::UNCOMPRESSED
+        );
+        assert_eq!("snappy".parse::<Compression>().unwrap(), Compression::SNAPPY);
+        assert_eq!("Gzip".parse::<Compression>().unwrap(), Compression::GZIP);
+        assert_eq!("lzo".parse::<Compression>().unwrap(), Compression::LZO);
+        assert_eq!("brotli".parse::<Compression>().unwrap(), Compression::BROTLI);
+        assert_eq!("lz4".parse::<Compression>().unwrap(), Compression::LZ4);
+        assert_eq!("lz4_raw".parse::<Compression>().unwrap(), Compression::LZ4_RAW);
+        assert_eq!("zstd".parse::<Compression>().unwrap(), Compression::ZSTD);
+
+        let err = "zip".parse::<Compression>().unwrap_err().to_string();
+        assert!(err.contains("Unknown compression ZIP"), "{}", err);
+    }
+
     #[test]
     fn test_from_compression() {
         assert_eq!(
@@ -0,0 +1,117 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Binary that concatenates the row groups of parquet files with identical schemas
+//! into a single file, without decoding or re-encoding their pages
+//!
+//! # Install
+//!
+//! `parquet-concat` can be installed using `cargo`:
+//! ```
+//! cargo install parquet --features=cli
+//! ```
+//! After this `parquet-concat` should be available:
+//! ```
+//! parquet-concat out.parquet a.parquet b.parquet
+//! ```
+//!
+//! The binary can also be built from the source code and run as follows:
+//! ```
+//! cargo run --features=cli --bin parquet-concat out.parquet a.parquet b.parquet
+//! ```
+//!
+//! Note that the key-value metadata of the output is merged from the inputs, keeping
+//! the first value of any key present in more than one input.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::sync::Arc;
+
+use clap::Parser;
+
+use parquet::errors::{ParquetError, Result};
+use parquet::file::footer::parse_metadata;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+
+#[derive(Debug, Parser)]
+#[clap(author, version, about("Concatenates one or more parquet files"), long_about = None)]
+struct Args {
+    #[clap(help("Path to the output file"))]
+    output: String,
+
+    #[clap(help("Paths to the parquet files to concatenate"), required(true))]
+    input: Vec<String>,
+}
+
+impl Args {
+    fn run(&self) -> Result<()> {
+        let inputs = self
+            .input
+            .iter()
+            .map(|path| {
+                let file = Arc::new(File::open(path)?);
+                let metadata = parse_metadata(file.as_ref())?;
+                Ok((file, metadata))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let expected = inputs[0].1.file_metadata().schema_descr();
+        for (path, (_, metadata)) in self.input.iter().zip(&inputs).skip(1) {
+            let actual = metadata.file_metadata().schema_descr();
+            if expected.root_schema() != actual.root_schema() {
+                return Err(ParquetError::General(format!(
+                    "Schema of {} does not match that of {}",
+                    path, self.input[0]
+                )));
+            }
+        }
+
+        let mut keys = HashSet::new();
+        let key_value_metadata: Vec<_> = inputs
+            .iter()
+            .flat_map(|(_, metadata)| metadata.file_metadata().key_value_metadata())
+            .flatten()
+            .filter(|kv| keys.insert(kv.key.clone()))
+            .cloned()
+            .collect();
+
+        let output = File::create(&self.output)?;
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_key_value_metadata(
+                    (!key_value_metadata.is_empty()).then_some(key_value_metadata),
+                )
+                .build(),
+        );
+        let schema = expected.root_schema_ptr();
+        let mut writer = SerializedFileWriter::new(output, schema, props)?;
+
+        for (input, metadata) in &inputs {
+            for row_group in metadata.row_groups() {
+                writer.append_row_group(input.clone(), row_group)?;
+            }
+        }
+
+        writer.close()?;
+        Ok(())
+    }
+}
+
+fn main() -> Result<()> {
+    Args::parse().run()
+}
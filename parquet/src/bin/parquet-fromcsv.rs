@@ -68,6 +68,7 @@ use std::{
     fmt::Display,
     fs::{read_to_string, File},
     path::{Path, PathBuf},
+    str::FromStr,
     sync::Arc,
 };
 
@@ -189,11 +190,11 @@ struct Args {
     #[clap(short('D'), long, help("double quote"))]
     double_quote: Option<bool>,
     #[clap(short('c'), long, help("compression mode"), default_value_t=Compression::SNAPPY)]
-    #[clap(value_parser=compression_from_str)]
+    #[clap(value_parser=Compression::from_str)]
     parquet_compression: Compression,
 
     #[clap(short, long, help("writer version"))]
-    #[clap(value_parser=writer_version_from_str)]
+    #[clap(value_parser=WriterVersion::from_str)]
     writer_version: Option<WriterVersion>,
     #[clap(short, long, help("max row group size"))]
     max_row_group_size: Option<usize>,
@@ -204,32 +205,6 @@ struct Args {
     help: Option<bool>,
 }
 
-fn compression_from_str(cmp: &str) -> Result<Compression, String> {
-    match cmp.to_uppercase().as_str() {
-        "UNCOMPRESSED" => Ok(Compression::UNCOMPRESSED),
-        "SNAPPY" => Ok(Compression::SNAPPY),
-        "GZIP" => Ok(Compression::GZIP),
-        "LZO" => Ok(Compression::LZO),
-        "BROTLI" => Ok(Compression::BROTLI),
-        "LZ4" => Ok(Compression::LZ4),
-        "ZSTD" => Ok(Compression::ZSTD),
-        v => Err(
-            format!("Unknown compression {0} : possible values UNCOMPRESSED, SNAPPY, GZIP, LZO, BROTLI, LZ4, ZSTD \n\nFor more information try --help",v)
-        )
-    }
-}
-
-fn writer_version_from_str(cmp: &str) -> Result<WriterVersion, String> {
-    match cmp.to_uppercase().as_str() {
-        "1" => Ok(WriterVersion::PARQUET_1_0),
-        "2" => Ok(WriterVersion::PARQUET_2_0),
-        v => Err(format!(
-            "Unknown writer version {0} : possible values 1, 2",
-            v
-        )),
-    }
-}
-
 impl Args {
     fn schema_path(&self) -> &Path {
         self.schema.as_path()
@@ -511,6 +486,8 @@ mod tests {
         assert_eq!(args.parquet_compression, Compression::LZO);
         let args = parse_args(vec!["--parquet-compression", "lz4"]).unwrap();
         assert_eq!(args.parquet_compression, Compression::LZ4);
+        let args = parse_args(vec!["--parquet-compression", "lz4_raw"]).unwrap();
+        assert_eq!(args.parquet_compression, Compression::LZ4_RAW);
         let args = parse_args(vec!["--parquet-compression", "brotli"]).unwrap();
         assert_eq!(args.parquet_compression, Compression::BROTLI);
         let args = parse_args(vec!["--parquet-compression", "zstd"]).unwrap();
@@ -521,9 +498,14 @@ mod tests {
     fn test_parse_arg_compression_format_fail() {
         match parse_args(vec!["--parquet-compression", "zip"]) {
             Ok(_) => panic!("unexpected success"),
-            Err(e) => assert_eq!(
-                format!("{}", e),
-                "error: Invalid value 'zip' for '--parquet-compression <PARQUET_COMPRESSION>': Unknown compression ZIP : possible values UNCOMPRESSED, SNAPPY, GZIP, LZO, BROTLI, LZ4, ZSTD \n\nFor more information try --help\n"),
+            Err(e) => {
+                let err = e.to_string();
+                assert!(
+                    err.contains("'--parquet-compression <PARQUET_COMPRESSION>': Parquet error: Unknown compression ZIP: possible values UNCOMPRESSED, SNAPPY, GZIP, LZO, BROTLI, LZ4, LZ4_RAW, ZSTD"),
+                    "{}",
+                    err
+                )
+            }
         }
     }
 
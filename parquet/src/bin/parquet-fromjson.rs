@@ -0,0 +1,343 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Binary file to convert newline-delimited JSON to Parquet file
+//!
+//! # Install
+//!
+//! `parquet-fromjson` can be installed using `cargo`:
+//!
+//! ```text
+//! cargo install parquet --features=cli
+//! ```
+//!
+//! After this `parquet-fromjson` should be available:
+//!
+//! ```text
+//! parquet-fromjson --input-file input.json --output-file output.parquet
+//! ```
+//!
+//! The binary can also be built from the source code and run as follows:
+//!
+//! ```text
+//! cargo run --features=cli --bin parquet-fromjson --input-file input.json \
+//!    --output-file output.parquet
+//! ```
+//!
+//! # Options
+//!
+//! - `-s`, `--schema` : Path to message schema for generated Parquet file, if not
+//!   specified the schema is inferred from the input
+//! - `-n`, `--max-infer-records` : Max number of records read to infer the schema,
+//!   default is all records
+//! - `-i`, `--input-file` : Path to input newline-delimited JSON file
+//! - `-o`, `--output-file` : Path to output Parquet file
+//! - `-b`, `--batch-size` : Batch size for Parquet
+//! - `-c`, `--parquet-compression` : Compression option for Parquet, default is SNAPPY
+//! - `-w`, `--writer-version` : Writer version
+//! - `-m`, `--max-row-group-size` : Max row group size
+//!
+
+use std::{
+    fs::{read_to_string, File},
+    io::BufReader,
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+};
+
+use arrow_json::{reader::infer_json_schema_from_seekable, ReaderBuilder};
+use arrow_schema::SchemaRef;
+use clap::Parser;
+use parquet::{
+    arrow::{from_json::json_to_parquet, parquet_to_arrow_schema},
+    basic::Compression,
+    errors::{ParquetError, Result},
+    file::properties::{WriterProperties, WriterVersion},
+    schema::{parser::parse_message_type, types::SchemaDescriptor},
+};
+
+#[derive(Debug, Parser)]
+#[clap(author, version, about("Binary to convert newline-delimited JSON to Parquet"), long_about=None)]
+struct Args {
+    #[clap(
+        short,
+        long,
+        help("message schema for output Parquet, inferred if not set")
+    )]
+    schema: Option<PathBuf>,
+    #[clap(
+        short('n'),
+        long,
+        help("max number of records read to infer the schema")
+    )]
+    max_infer_records: Option<usize>,
+    #[clap(short, long, help("input newline-delimited JSON file"))]
+    input_file: PathBuf,
+    #[clap(short, long, help("output Parquet file"))]
+    output_file: PathBuf,
+    #[clap(short, long, help("batch size"), default_value_t = 1024)]
+    batch_size: usize,
+    #[clap(short('c'), long, help("compression mode"), default_value_t=Compression::SNAPPY)]
+    #[clap(value_parser=Compression::from_str)]
+    parquet_compression: Compression,
+    #[clap(short, long, help("writer version"))]
+    #[clap(value_parser=WriterVersion::from_str)]
+    writer_version: Option<WriterVersion>,
+    #[clap(short, long, help("max row group size"))]
+    max_row_group_size: Option<usize>,
+    #[clap(long, help("whether to enable bloom filter writing"))]
+    enable_bloom_filter: Option<bool>,
+}
+
+impl Args {
+    fn writer_properties(&self) -> WriterProperties {
+        let mut builder =
+            WriterProperties::builder().set_compression(self.parquet_compression);
+        if let Some(writer_version) = self.writer_version {
+            builder = builder.set_writer_version(writer_version);
+        }
+        if let Some(max_row_group_size) = self.max_row_group_size {
+            builder = builder.set_max_row_group_size(max_row_group_size);
+        }
+        if let Some(enable_bloom_filter) = self.enable_bloom_filter {
+            builder = builder.set_bloom_filter_enabled(enable_bloom_filter);
+        }
+        builder.build()
+    }
+
+    /// Returns the arrow schema of the output, read from the schema file if
+    /// specified, and otherwise inferred from `input`
+    fn arrow_schema(&self, input: &mut File) -> Result<SchemaRef> {
+        match &self.schema {
+            Some(path) => {
+                let schema = read_to_string(path).map_err(|e| {
+                    ParquetError::General(format!(
+                        "Failed to open schema file {:?}: {}",
+                        path, e
+                    ))
+                })?;
+                let schema = Arc::new(parse_message_type(&schema)?);
+                let descr = SchemaDescriptor::new(schema);
+                Ok(Arc::new(parquet_to_arrow_schema(&descr, None)?))
+            }
+            None => {
+                let mut reader = BufReader::new(input);
+                let schema =
+                    infer_json_schema_from_seekable(&mut reader, self.max_infer_records)?;
+                Ok(Arc::new(schema))
+            }
+        }
+    }
+
+    fn run(&self) -> Result<()> {
+        let mut input = File::open(&self.input_file).map_err(|e| {
+            ParquetError::General(format!(
+                "Failed to open input file {:?}: {}",
+                self.input_file, e
+            ))
+        })?;
+        let schema = self.arrow_schema(&mut input)?;
+        let builder = ReaderBuilder::new()
+            .with_batch_size(self.batch_size)
+            .with_schema(schema);
+
+        let output = File::create(&self.output_file).map_err(|e| {
+            ParquetError::General(format!(
+                "Failed to create output file {:?}: {}",
+                self.output_file, e
+            ))
+        })?;
+
+        let props = Some(self.writer_properties());
+        json_to_parquet(input, output, builder, props)?;
+        Ok(())
+    }
+}
+
+fn main() -> Result<()> {
+    Args::parse().run()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, path::Path};
+
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use tempfile::NamedTempFile;
+
+    fn parse_args(mut extra_args: Vec<&str>) -> Result<Args, clap::Error> {
+        let mut args = vec![
+            "test",
+            "--input-file",
+            "infile.json",
+            "--output-file",
+            "out.parquet",
+        ];
+        args.append(&mut extra_args);
+        Args::try_parse_from(args.iter())
+    }
+
+    #[test]
+    fn test_parse_arg_minimum() {
+        let args = parse_args(vec![]).unwrap();
+
+        assert_eq!(args.input_file, PathBuf::from("infile.json"));
+        assert_eq!(args.output_file, PathBuf::from("out.parquet"));
+        // test default values
+        assert_eq!(args.schema, None);
+        assert_eq!(args.max_infer_records, None);
+        assert_eq!(args.batch_size, 1024);
+        assert_eq!(args.parquet_compression, Compression::SNAPPY);
+        assert_eq!(args.writer_version, None);
+        assert_eq!(args.max_row_group_size, None);
+        assert_eq!(args.enable_bloom_filter, None);
+    }
+
+    #[test]
+    fn test_parse_arg_writer_properties() {
+        let args = parse_args(vec![
+            "--parquet-compression",
+            "zstd",
+            "--writer-version",
+            "2",
+            "--max-row-group-size",
+            "100",
+            "--enable-bloom-filter",
+            "true",
+        ])
+        .unwrap();
+        assert_eq!(args.parquet_compression, Compression::ZSTD);
+        assert_eq!(args.writer_version, Some(WriterVersion::PARQUET_2_0));
+
+        let props = args.writer_properties();
+        assert_eq!(props.writer_version(), WriterVersion::PARQUET_2_0);
+        assert_eq!(props.max_row_group_size(), 100);
+        assert_eq!(props.compression(&"col".into()), Compression::ZSTD);
+        assert!(props.bloom_filter_properties(&"col".into()).is_some());
+    }
+
+    #[test]
+    fn test_parse_arg_compression_format_fail() {
+        let err = parse_args(vec!["--parquet-compression", "zip"]).unwrap_err();
+        assert!(err.to_string().contains(
+            "Parquet error: Unknown compression ZIP: possible values UNCOMPRESSED, SNAPPY, GZIP, LZO, BROTLI, LZ4, LZ4_RAW, ZSTD"
+        ));
+
+        let err = parse_args(vec!["--writer-version", "3"]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Parquet error: Unknown writer version 3: possible values 1, 2"));
+    }
+
+    fn input_file() -> NamedTempFile {
+        let mut input = NamedTempFile::new().unwrap();
+        writeln!(input, r#"{{"a": 1, "b": "foo"}}"#).unwrap();
+        writeln!(input, r#"{{"a": 2, "b": "bar"}}"#).unwrap();
+        writeln!(input, r#"{{"a": 3}}"#).unwrap();
+        input.flush().unwrap();
+        input
+    }
+
+    fn args(input: &NamedTempFile, output: &Path, schema: Option<&Path>) -> Args {
+        let mut args = vec![
+            "test".to_string(),
+            "--input-file".to_string(),
+            input.path().to_str().unwrap().to_string(),
+            "--output-file".to_string(),
+            output.to_str().unwrap().to_string(),
+        ];
+        if let Some(schema) = schema {
+            args.push("--schema".to_string());
+            args.push(schema.to_str().unwrap().to_string());
+        }
+        Args::try_parse_from(args.iter()).unwrap()
+    }
+
+    fn read_output(output: &Path) -> (String, i64) {
+        let reader = SerializedFileReader::new(File::open(output).unwrap()).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        let mut schema = Vec::new();
+        parquet::schema::printer::print_schema(&mut schema, metadata.schema());
+        (String::from_utf8(schema).unwrap(), metadata.num_rows())
+    }
+
+    #[test]
+    fn test_convert_inferred_schema() {
+        let input = input_file();
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("out.parquet");
+
+        args(&input, &output, None).run().unwrap();
+
+        let (schema, num_rows) = read_output(&output);
+        assert_eq!(num_rows, 3);
+        assert!(schema.contains("OPTIONAL INT64 a;"), "{}", schema);
+        assert!(
+            schema.contains("OPTIONAL BYTE_ARRAY b (STRING);"),
+            "{}",
+            schema
+        );
+    }
+
+    #[test]
+    fn test_convert_schema_file() {
+        let input = input_file();
+        let mut schema_file = NamedTempFile::new().unwrap();
+        write!(
+            schema_file,
+            "message schema {{ REQUIRED INT32 a; OPTIONAL BYTE_ARRAY b (UTF8); }}"
+        )
+        .unwrap();
+        schema_file.flush().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("out.parquet");
+
+        args(&input, &output, Some(schema_file.path()))
+            .run()
+            .unwrap();
+
+        let (schema, num_rows) = read_output(&output);
+        assert_eq!(num_rows, 3);
+        assert!(schema.contains("REQUIRED INT32 a;"), "{}", schema);
+        assert!(
+            schema.contains("OPTIONAL BYTE_ARRAY b (STRING);"),
+            "{}",
+            schema
+        );
+    }
+
+    #[test]
+    fn test_invalid_schema_does_not_create_output() {
+        let input = input_file();
+        let mut schema_file = NamedTempFile::new().unwrap();
+        write!(schema_file, "message schema {{ REQUIRED INT32 }}").unwrap();
+        schema_file.flush().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("out.parquet");
+
+        let missing = dir.path().join("missing.schema");
+        args(&input, &output, Some(&missing)).run().unwrap_err();
+        assert!(!output.exists());
+
+        args(&input, &output, Some(schema_file.path()))
+            .run()
+            .unwrap_err();
+        assert!(!output.exists());
+    }
+}
@@ -26,7 +26,6 @@ use crate::format::{
     BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader,
     SplitBlockAlgorithm, Uncompressed, XxHash,
 };
-use bytes::{Buf, Bytes};
 use std::hash::Hasher;
 use std::io::{BufWriter, Write};
 use std::sync::Arc;
@@ -89,7 +88,7 @@ fn block_check(block: &Block, hash: u32) -> bool {
 #[derive(Debug, Clone)]
 pub struct Sbbf(Vec<Block>);
 
-const SBBF_HEADER_SIZE_ESTIMATE: usize = 20;
+pub(crate) const SBBF_HEADER_SIZE_ESTIMATE: usize = 20;
 
 /// given an initial offset, and a [ChunkReader], try to read out a bloom filter header and return
 /// both the header and the offset after it (for bitset).
@@ -98,26 +97,47 @@ fn chunk_read_bloom_filter_header_and_offset<R: ChunkReader>(
     reader: Arc<R>,
 ) -> Result<(BloomFilterHeader, u64), ParquetError> {
     let buffer = reader.get_bytes(offset as u64, SBBF_HEADER_SIZE_ESTIMATE)?;
-    let (header, length) = read_bloom_filter_header_and_length(buffer)?;
+    let (header, length) = read_bloom_filter_header_and_length(&buffer)?;
     Ok((header, offset + length))
 }
 
-/// given a [Bytes] buffer, try to read out a bloom filter header and return both the header and
+/// given a buffer, try to read out a bloom filter header and return both the header and
 /// length of the header.
 #[inline]
-fn read_bloom_filter_header_and_length(
-    buffer: Bytes,
+pub(crate) fn read_bloom_filter_header_and_length(
+    buffer: &[u8],
 ) -> Result<(BloomFilterHeader, u64), ParquetError> {
     let total_length = buffer.len();
-    let mut buf_reader = buffer.reader();
+    let mut buf_reader = buffer;
     let mut prot = TCompactInputProtocol::new(&mut buf_reader);
     let header = BloomFilterHeader::read_from_in_protocol(&mut prot).map_err(|e| {
         ParquetError::General(format!("Could not read bloom filter header: {}", e))
     })?;
-    Ok((
-        header,
-        (total_length - buf_reader.into_inner().remaining()) as u64,
-    ))
+    Ok((header, (total_length - buf_reader.len()) as u64))
+}
+
+/// given a bloom filter header, check it describes a supported bloom filter and return
+/// the length in bytes of its bitset.
+pub(crate) fn bitset_length(header: &BloomFilterHeader) -> Result<usize, ParquetError> {
+    match header.algorithm {
+        BloomFilterAlgorithm::BLOCK(_) => {
+            // this match exists to future proof the singleton algorithm enum
+        }
+    }
+    match header.compression {
+        BloomFilterCompression::UNCOMPRESSED(_) => {
+            // this match exists to future proof the singleton compression enum
+        }
+    }
+    match header.hash {
+        BloomFilterHash::XXHASH(_) => {
+            // this match exists to future proof the singleton hash enum
+        }
+    }
+    header
+        .num_bytes
+        .try_into()
+        .map_err(|_| ParquetError::General("Bloom filter length is invalid".to_string()))
 }
 
 pub(crate) const BITSET_MIN_LENGTH: usize = 32;
@@ -162,7 +182,7 @@ impl Sbbf {
         Self::new(&bitset)
     }
 
-    fn new(bitset: &[u8]) -> Self {
+    pub(crate) fn new(bitset: &[u8]) -> Self {
         let data = bitset
             .chunks_exact(4 * 8)
             .map(|chunk| {
@@ -222,6 +242,15 @@ impl Sbbf {
         column_metadata: &ColumnChunkMetaData,
         reader: Arc<R>,
     ) -> Result<Option<Self>, ParquetError> {
+        if column_metadata.bloom_filter_offset().is_some()
+            && column_metadata.crypto_metadata().is_some()
+        {
+            return Err(nyi_err!(
+                "Reading the bloom filter of encrypted column {}",
+                column_metadata.column_path().string()
+            ));
+        }
+
         let offset: u64 = if let Some(offset) = column_metadata.bloom_filter_offset() {
             offset.try_into().map_err(|_| {
                 ParquetError::General("Bloom filter offset is invalid".to_string())
@@ -230,32 +259,41 @@ impl Sbbf {
             return Ok(None);
         };
 
+        // read the header and bitset in a single request if the length is known
+        if let Some(length) = column_metadata.bloom_filter_length() {
+            let length: usize = length.try_into().map_err(|_| {
+                ParquetError::General("Bloom filter length is invalid".to_string())
+            })?;
+            let buffer = reader.get_bytes(offset, length)?;
+            return Self::from_bytes(&buffer).map(Some);
+        }
+
         let (header, bitset_offset) =
             chunk_read_bloom_filter_header_and_offset(offset, reader.clone())?;
 
-        match header.algorithm {
-            BloomFilterAlgorithm::BLOCK(_) => {
-                // this match exists to future proof the singleton algorithm enum
-            }
-        }
-        match header.compression {
-            BloomFilterCompression::UNCOMPRESSED(_) => {
-                // this match exists to future proof the singleton compression enum
-            }
-        }
-        match header.hash {
-            BloomFilterHash::XXHASH(_) => {
-                // this match exists to future proof the singleton hash enum
-            }
-        }
         // length in bytes
-        let length: usize = header.num_bytes.try_into().map_err(|_| {
-            ParquetError::General("Bloom filter length is invalid".to_string())
-        })?;
+        let length = bitset_length(&header)?;
         let bitset = reader.get_bytes(bitset_offset, length)?;
         Ok(Some(Self::new(&bitset)))
     }
 
+    /// Read a bloom filter from `bytes`, which start with its header, such as the bytes
+    /// at the [`ColumnChunkMetaData::bloom_filter_offset`] of a column chunk.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParquetError> {
+        let (header, header_length) = read_bloom_filter_header_and_length(bytes)?;
+        let length = bitset_length(&header)?;
+        let bitset = bytes
+            .get(header_length as usize..header_length as usize + length)
+            .ok_or_else(|| {
+                ParquetError::EOF(format!(
+                    "Expected {} bytes of bloom filter bit set, got {}",
+                    length,
+                    bytes.len() - header_length as usize
+                ))
+            })?;
+        Ok(Self::new(bitset))
+    }
+
     #[inline]
     fn hash_to_block_index(&self, hash: u64) -> usize {
         // unchecked_mul is unstable, but in reality this is safe, we'd just use saturating mul
@@ -276,7 +314,7 @@ impl Sbbf {
     }
 
     /// Check if an [AsBytes] value is probably present or definitely absent in the filter
-    pub fn check<T: AsBytes>(&self, value: &T) -> bool {
+    pub fn check<T: AsBytes + ?Sized>(&self, value: &T) -> bool {
         self.check_hash(hash_as_bytes(value))
     }
 
@@ -339,6 +377,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sbbf_from_bytes() {
+        let mut sbbf = Sbbf::new_with_num_of_bytes(64);
+        for i in 0..10 {
+            sbbf.insert(format!("a{}", i).as_str());
+        }
+        let mut buffer = vec![];
+        sbbf.write(&mut buffer).unwrap();
+
+        let read = Sbbf::from_bytes(&buffer).unwrap();
+        assert_eq!(read.0, sbbf.0);
+        for i in 0..10 {
+            assert!(read.check(format!("a{}", i).as_str()));
+        }
+
+        let err = Sbbf::from_bytes(&buffer[..buffer.len() - 1]).unwrap_err();
+        assert!(err.to_string().contains("bit set"), "{}", err);
+    }
+
     #[test]
     fn test_with_fixture() {
         // bloom filter produced by parquet-mr/spark for a column of i64 f"a{i}" for i in 0..10
@@ -368,7 +425,7 @@ mod tests {
                 num_bytes,
             },
             read_length,
-        ) = read_bloom_filter_header_and_length(Bytes::copy_from_slice(buffer)).unwrap();
+        ) = read_bloom_filter_header_and_length(buffer).unwrap();
         assert_eq!(read_length, 15);
         assert_eq!(
             algorithm,
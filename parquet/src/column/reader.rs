@@ -310,13 +310,23 @@ where
                     continue;
                 }
 
+                // The number of rows of a page is only known from its levels if
+                // the column is not repeated, as otherwise a row may span many levels
+                let num_rows = metadata.num_rows.or_else(|| {
+                    metadata
+                        .num_levels
+                        .filter(|_| self.descr.max_rep_level() == 0)
+                });
+
                 // If page has less rows than the remaining records to
-                // be skipped, skip entire page
-                if metadata.num_rows <= remaining {
-                    self.page_reader.skip_next_page()?;
-                    remaining -= metadata.num_rows;
-                    continue;
-                };
+                // be skipped, skip entire page without decoding it
+                if let Some(num_rows) = num_rows {
+                    if num_rows <= remaining {
+                        self.page_reader.skip_next_page()?;
+                        remaining -= num_rows;
+                        continue;
+                    }
+                }
                 // because self.num_buffered_values == self.num_decoded_values means
                 // we need reads a new page and set up the decoders for levels
                 if !self.read_new_page()? {
@@ -325,12 +335,15 @@ where
             }
 
             // start skip values in page level
-            let to_read = remaining
-                .min((self.num_buffered_values - self.num_decoded_values) as usize);
+            let remaining_levels =
+                (self.num_buffered_values - self.num_decoded_values) as usize;
 
             let (records_read, rep_levels_read) = match self.rep_level_decoder.as_mut() {
-                Some(decoder) => decoder.skip_rep_levels(to_read)?,
-                None => (to_read, to_read),
+                Some(decoder) => decoder.skip_rep_levels(remaining, remaining_levels)?,
+                None => {
+                    let to_read = remaining.min(remaining_levels);
+                    (to_read, to_read)
+                }
             };
 
             let (values_read, def_levels_read) = match self.def_level_decoder.as_mut() {
@@ -523,7 +536,8 @@ where
             // TODO: should we return false if read_new_page() = true and
             // num_buffered_values = 0?
             match self.page_reader.peek_next_page()? {
-                Some(next_page) => Ok(next_page.num_rows != 0),
+                Some(next_page) => Ok(!next_page.is_dict
+                    && next_page.num_rows.or(next_page.num_levels) != Some(0)),
                 None => Ok(false),
             }
         } else {
@@ -585,7 +599,9 @@ mod tests {
 
     use crate::basic::Type as PhysicalType;
     use crate::schema::types::{ColumnDescriptor, ColumnPath, Type as SchemaType};
-    use crate::util::test_common::page_util::InMemoryPageReader;
+    use crate::util::test_common::page_util::{
+        DataPageBuilder, DataPageBuilderImpl, InMemoryPageReader,
+    };
     use crate::util::test_common::rand_gen::make_pages;
 
     const NUM_LEVELS: usize = 128;
@@ -1052,6 +1068,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_skip_records_repeated_v1_pages() {
+        let desc = Arc::new(ColumnDescriptor::new(
+            Arc::new(get_test_int32_type()),
+            1,
+            1,
+            ColumnPath::new(Vec::new()),
+        ));
+
+        // The records of each page: [[1, 2, 3], [4, 5]], [[6], [7, 8]], [[9], [10]]
+        let pages: Vec<_> = [
+            (vec![0, 1, 1, 0, 1], vec![1, 2, 3, 4, 5]),
+            (vec![0, 0, 1], vec![6, 7, 8]),
+            (vec![0, 0], vec![9, 10]),
+        ]
+        .into_iter()
+        .map(|(rep_levels, values)| {
+            let mut builder =
+                DataPageBuilderImpl::new(desc.clone(), values.len() as u32, false);
+            builder.add_rep_levels(1, &rep_levels);
+            builder.add_def_levels(1, &vec![1; values.len()]);
+            builder.add_values::<Int32Type>(Encoding::PLAIN, &values);
+            builder.consume()
+        })
+        .collect();
+
+        let page_reader = InMemoryPageReader::new(pages);
+        let column_reader = get_column_reader(desc, Box::new(page_reader));
+        let mut reader = get_typed_column_reader::<Int32Type>(column_reader);
+
+        // The number of rows of a v1 page of a repeated column is not known, so the
+        // pages must be decoded, as the 5 levels of the first page are only 2 rows
+        assert_eq!(reader.skip_records(5).unwrap(), 5);
+
+        let mut values = [0; 2];
+        let mut def_levels = [0; 2];
+        let mut rep_levels = [0; 2];
+        let (values_read, levels_read) = reader
+            .read_batch(2, Some(&mut def_levels), Some(&mut rep_levels), &mut values)
+            .unwrap();
+        assert_eq!((values_read, levels_read), (1, 1));
+        assert_eq!(values[0], 10);
+        assert_eq!(reader.skip_records(1).unwrap(), 0);
+    }
+
     // ----------------------------------------------------------------------
     // Helper methods to make pages and test
     //
@@ -83,10 +83,14 @@ pub trait ColumnLevelDecoder {
 
 pub trait RepetitionLevelDecoder: ColumnLevelDecoder {
     /// Skips over repetition level corresponding to `num_records` records, where a record
-    /// is delimited by a repetition level of 0
+    /// is delimited by a repetition level of 0, reading at most `num_levels` levels
     ///
     /// Returns the number of records skipped, and the number of levels skipped
-    fn skip_rep_levels(&mut self, num_records: usize) -> Result<(usize, usize)>;
+    fn skip_rep_levels(
+        &mut self,
+        num_records: usize,
+        num_levels: usize,
+    ) -> Result<(usize, usize)>;
 }
 
 pub trait DefinitionLevelDecoder: ColumnLevelDecoder {
@@ -395,22 +399,30 @@ impl DefinitionLevelDecoder for ColumnLevelDecoderImpl {
 }
 
 impl RepetitionLevelDecoder for ColumnLevelDecoderImpl {
-    fn skip_rep_levels(&mut self, num_records: usize) -> Result<(usize, usize)> {
+    fn skip_rep_levels(
+        &mut self,
+        num_records: usize,
+        num_levels: usize,
+    ) -> Result<(usize, usize)> {
         let mut level_skip = 0;
         let mut record_skip = 0;
 
-        loop {
+        while level_skip < num_levels {
+            let remaining_levels = num_levels - level_skip;
+
             if self.buffer.is_empty() {
-                // Read SKIP_BUFFER_SIZE as we don't know how many to read
-                self.read_to_buffer(SKIP_BUFFER_SIZE)?;
+                // Read SKIP_BUFFER_SIZE as we don't know how many to read, but no more
+                // than the levels remaining, as the data may be padded beyond them
+                self.read_to_buffer(remaining_levels.min(SKIP_BUFFER_SIZE))?;
                 if self.buffer.is_empty() {
                     // Reached end of page
                     break;
                 }
             }
+            let max_skip = self.buffer.len().min(remaining_levels);
 
             let mut to_skip = 0;
-            while to_skip < self.buffer.len() && record_skip != num_records {
+            while to_skip < max_skip && record_skip != num_records {
                 if self.buffer[to_skip] == 0 {
                     record_skip += 1;
                 }
@@ -418,19 +430,22 @@ impl RepetitionLevelDecoder for ColumnLevelDecoderImpl {
             }
 
             // Find end of record
-            while to_skip < self.buffer.len() && self.buffer[to_skip] != 0 {
+            while to_skip < max_skip && self.buffer[to_skip] != 0 {
                 to_skip += 1;
             }
 
             level_skip += to_skip;
-            if to_skip >= self.buffer.len() {
+            if to_skip == self.buffer.len() {
                 // Need to to read more values
                 self.buffer.clear();
                 continue;
             }
 
             self.split_off_buffer(to_skip);
-            break;
+            if to_skip < max_skip {
+                // Reached the start of the next record
+                break;
+            }
         }
 
         Ok((record_skip, level_skip))
@@ -497,8 +512,9 @@ mod tests {
             });
 
             test_skip_levels(&encoded, data.clone(), |decoder, read, to_read| {
-                let (records_skipped, levels_skipped) =
-                    decoder.skip_rep_levels(to_read).unwrap();
+                let (records_skipped, levels_skipped) = decoder
+                    .skip_rep_levels(to_read, encoded.len() - *read)
+                    .unwrap();
 
                 // If not run out of values
                 if levels_skipped + *read != encoded.len() {
@@ -224,7 +224,9 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
         page_writer: Box<dyn PageWriter + 'a>,
     ) -> Self {
         let codec = props.compression(descr.path());
-        let codec_options = CodecOptionsBuilder::default().build();
+        let codec_options = CodecOptionsBuilder::default()
+            .set_compression_level(props.compression_level(descr.path()))
+            .build();
         let compressor = create_codec(codec, &codec_options).unwrap();
         let encoder = E::try_new(&descr, props.as_ref()).unwrap();
 
@@ -449,11 +451,15 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
         let metadata = self.write_column_metadata()?;
         self.page_writer.close()?;
 
-        let (column_index, offset_index) = if self.column_index_builder.valid() {
-            // build the column and offset index
-            let column_index = self.column_index_builder.build_to_thrift();
+        let (column_index, offset_index) = if self.props.write_page_index() {
+            // the column index requires page statistics, whereas the offset
+            // index only records the location and first row of each page
+            let column_index = self
+                .column_index_builder
+                .valid()
+                .then(|| self.column_index_builder.build_to_thrift());
             let offset_index = self.offset_index_builder.build_to_thrift();
-            (Some(column_index), Some(offset_index))
+            (column_index, Some(offset_index))
         } else {
             (None, None)
         };
@@ -652,13 +658,13 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
             (Some(min), Some(max)) => {
                 update_min(&self.descr, &min, &mut self.column_metrics.min_column_value);
                 update_max(&self.descr, &max, &mut self.column_metrics.max_column_value);
-                Some(Statistics::new(
+                Some(self.truncate_statistics(Statistics::new(
                     Some(min),
                     Some(max),
                     None,
                     self.page_metrics.num_page_nulls,
                     false,
-                ))
+                )))
             }
             _ => None,
         };
@@ -732,12 +738,22 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
                 let uncompressed_size =
                     rep_levels_byte_len + def_levels_byte_len + values_data.buf.len();
 
-                // Data Page v2 compresses values only.
-                match self.compressor {
-                    Some(ref mut cmpr) => {
-                        cmpr.compress(values_data.buf.data(), &mut buffer)?;
+                // Data Page v2 compresses values only, and stores them uncompressed
+                // if compression does not reduce their size.
+                let values = values_data.buf.data();
+                let mut is_compressed = false;
+                if let Some(ref mut cmpr) = self.compressor {
+                    if !values.is_empty() {
+                        let mut compressed_buf = Vec::with_capacity(values.len());
+                        cmpr.compress(values, &mut compressed_buf)?;
+                        if compressed_buf.len() < values.len() {
+                            buffer.extend_from_slice(&compressed_buf);
+                            is_compressed = true;
+                        }
                     }
-                    None => buffer.extend_from_slice(values_data.buf.data()),
+                }
+                if !is_compressed {
+                    buffer.extend_from_slice(values);
                 }
 
                 let data_page = Page::DataPageV2 {
@@ -748,7 +764,7 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
                     num_rows: self.page_metrics.num_buffered_rows,
                     def_levels_byte_len: def_levels_byte_len as u32,
                     rep_levels_byte_len: rep_levels_byte_len as u32,
-                    is_compressed: self.compressor.is_some(),
+                    is_compressed,
                     statistics: page_statistics,
                 };
 
@@ -824,7 +840,7 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
                 self.column_metrics.num_column_nulls,
                 false,
             );
-            builder = builder.set_statistics(statistics);
+            builder = builder.set_statistics(self.truncate_statistics(statistics));
         }
 
         let metadata = builder.build()?;
@@ -833,6 +849,37 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
         Ok(metadata)
     }
 
+    /// Truncates the min and max values of `BYTE_ARRAY` statistics to
+    /// [`WriterProperties::statistics_truncate_length`]
+    fn truncate_statistics(&self, statistics: Statistics) -> Statistics {
+        let length = match self.props.statistics_truncate_length() {
+            Some(length) => length,
+            None => return statistics,
+        };
+
+        // Decimals are compared as signed integers, and so cannot be truncated
+        if self.descr.converted_type() == ConvertedType::DECIMAL
+            || matches!(self.descr.logical_type(), Some(LogicalType::Decimal { .. }))
+        {
+            return statistics;
+        }
+
+        match &statistics {
+            Statistics::ByteArray(s) if s.has_min_max_set() => {
+                let min = truncate_min_value(s.min().data(), length);
+                let max = truncate_max_value(s.max().data(), length);
+                Statistics::byte_array(
+                    Some(min.into()),
+                    Some(max.into()),
+                    statistics.distinct_count(),
+                    statistics.null_count(),
+                    statistics.is_min_max_deprecated(),
+                )
+            }
+            _ => statistics,
+        }
+    }
+
     /// Encodes definition or repetition levels for Data Page v1.
     #[inline]
     fn encode_levels_v1(
@@ -972,6 +1019,68 @@ where
     }
 }
 
+/// Returns a lower bound for `data` of at most `length` bytes
+///
+/// If `data` is valid UTF-8 the result is also valid UTF-8
+fn truncate_min_value(data: &[u8], length: usize) -> Vec<u8> {
+    if data.len() <= length {
+        return data.to_vec();
+    }
+    match std::str::from_utf8(data) {
+        Ok(s) => truncate_utf8(s, length).as_bytes().to_vec(),
+        Err(_) => data[..length].to_vec(),
+    }
+}
+
+/// Returns an upper bound for `data` of at most `length` bytes, or `data` itself if
+/// no such bound exists
+///
+/// If `data` is valid UTF-8 the result is also valid UTF-8
+fn truncate_max_value(data: &[u8], length: usize) -> Vec<u8> {
+    if data.len() <= length {
+        return data.to_vec();
+    }
+    let truncated = match std::str::from_utf8(data) {
+        Ok(s) => increment_utf8(truncate_utf8(s, length)),
+        Err(_) => increment(&data[..length]),
+    };
+    truncated.unwrap_or_else(|| data.to_vec())
+}
+
+/// Returns the longest prefix of `s` that is at most `length` bytes
+fn truncate_utf8(s: &str, length: usize) -> &str {
+    let mut end = length.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Returns the shortest byte string greater than all strings prefixed by `data`,
+/// and no longer than `data`, or `None` if `data` consists only of `0xFF`
+fn increment(data: &[u8]) -> Option<Vec<u8>> {
+    let idx = data.iter().rposition(|b| *b != u8::MAX)?;
+    let mut result = data[..=idx].to_vec();
+    result[idx] += 1;
+    Some(result)
+}
+
+/// As [`increment`], but incrementing characters such that the result remains
+/// valid UTF-8 and no longer than `s`
+fn increment_utf8(s: &str) -> Option<Vec<u8>> {
+    for (idx, c) in s.char_indices().rev() {
+        // Skip over the surrogate range, which are not valid chars
+        let next = (c as u32 + 1..=char::MAX as u32).find_map(char::from_u32);
+        if let Some(next) = next.filter(|n| n.len_utf8() <= c.len_utf8()) {
+            let mut result = s.as_bytes()[..idx].to_vec();
+            let mut buf = [0; 4];
+            result.extend_from_slice(next.encode_utf8(&mut buf).as_bytes());
+            return Some(result);
+        }
+    }
+    None
+}
+
 /// Evaluate `a > b` according to underlying logical type.
 fn compare_greater<T: ParquetValueType>(descr: &ColumnDescriptor, a: &T, b: &T) -> bool {
     if let Some(LogicalType::Integer { is_signed, .. }) = descr.logical_type() {
@@ -1581,6 +1690,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_column_writer_truncate_byte_array_statistics() {
+        let page_writer = get_test_page_writer();
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_statistics_truncate_length(Some(4))
+                .build(),
+        );
+        let mut writer =
+            get_test_column_writer::<ByteArrayType>(page_writer, 0, 0, props);
+        writer
+            .write_batch(
+                &[
+                    ByteArray::from("bbbbbbbb"),
+                    ByteArray::from("zzzzzz"),
+                    ByteArray::from("c"),
+                ],
+                None,
+                None,
+            )
+            .unwrap();
+        let metadata = writer.close().unwrap().metadata;
+        if let Some(Statistics::ByteArray(stats)) = metadata.statistics() {
+            assert_eq!(stats.min(), &ByteArray::from("bbbb"));
+            assert_eq!(stats.max(), &ByteArray::from("zzz{"));
+        } else {
+            panic!("expecting Statistics::ByteArray");
+        }
+
+        // Decimal statistics are never truncated
+        let page_writer = get_test_page_writer();
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_statistics_truncate_length(Some(1))
+                .build(),
+        );
+        let mut writer =
+            get_test_decimals_column_writer::<ByteArrayType>(page_writer, 0, 0, props);
+        let values = [
+            ByteArray::from(vec![1_u8, 2, 3]),
+            ByteArray::from(vec![4_u8, 5]),
+        ];
+        writer.write_batch(&values, None, None).unwrap();
+        let metadata = writer.close().unwrap().metadata;
+        if let Some(Statistics::ByteArray(stats)) = metadata.statistics() {
+            assert_eq!(stats.min(), &values[1]);
+            assert_eq!(stats.max(), &values[0]);
+        } else {
+            panic!("expecting Statistics::ByteArray");
+        }
+    }
+
+    #[test]
+    fn test_truncate_statistics_values() {
+        // Values no longer than the length are unchanged
+        assert_eq!(truncate_min_value(b"abc", 3), b"abc");
+        assert_eq!(truncate_max_value(b"abc", 3), b"abc");
+
+        assert_eq!(truncate_min_value(b"abcdef", 3), b"abc");
+        assert_eq!(truncate_max_value(b"abcdef", 3), b"abd");
+
+        // Binary max values carry into the previous byte
+        let data = [1_u8, 0xFF, 0xFF, 0xFF, 0xFE];
+        assert_eq!(truncate_min_value(&data, 3), &data[..3]);
+        assert_eq!(truncate_max_value(&data, 3), &[2]);
+
+        // A max value of all 0xFF cannot be truncated
+        let data = [0xFF_u8; 5];
+        assert_eq!(truncate_max_value(&data, 3), data);
+
+        // UTF-8 is truncated on character boundaries
+        let data = "ééé".as_bytes();
+        assert_eq!(truncate_min_value(data, 3), "é".as_bytes());
+        assert_eq!(truncate_max_value(data, 3), "ê".as_bytes());
+
+        // Incrementing U+007F would increase its encoded length
+        let data = "a\u{7F}bc".as_bytes();
+        assert_eq!(truncate_max_value(data, 2), b"b");
+
+        // Surrogates are skipped when incrementing
+        let data = "\u{D7FF}\u{D7FF}".as_bytes();
+        assert_eq!(truncate_max_value(data, 3), "\u{E000}".as_bytes());
+    }
+
     #[test]
     fn test_column_writer_uint32_converted_type_min_max() {
         let page_writer = get_test_page_writer();
@@ -1837,6 +2030,93 @@ mod tests {
         column_roundtrip_random::<Int32Type>(props, 2048, i32::MIN, i32::MAX, 10, 10);
     }
 
+    #[test]
+    fn test_column_writer_column_compression_level() {
+        let props = WriterProperties::builder()
+            .set_compression(Compression::GZIP)
+            .set_compression_level(1)
+            .set_column_compression(ColumnPath::from("col"), Compression::ZSTD)
+            .set_column_compression_level(ColumnPath::from("col"), 19)
+            .build();
+        column_roundtrip_random::<Int32Type>(props, 2048, i32::MIN, i32::MAX, 10, 10);
+    }
+
+    #[test]
+    fn test_column_writer_compression_v2_pages() {
+        let write_pages = |values: &[i32], def_levels: &[i16]| {
+            let mut buf = Vec::with_capacity(100);
+            let mut write = TrackedWrite::new(&mut buf);
+            let page_writer = Box::new(SerializedPageWriter::new(&mut write));
+            let props = WriterProperties::builder()
+                .set_writer_version(WriterVersion::PARQUET_2_0)
+                .set_compression(Compression::SNAPPY)
+                .set_dictionary_enabled(false)
+                .set_encoding(Encoding::PLAIN)
+                .build();
+
+            let mut writer =
+                get_test_column_writer::<Int32Type>(page_writer, 1, 0, Arc::new(props));
+            writer.write_batch(values, Some(def_levels), None).unwrap();
+            let r = writer.close().unwrap();
+
+            let reader = SerializedPageReader::new(
+                Arc::new(Bytes::from(buf)),
+                &r.metadata,
+                r.rows_written as usize,
+                None,
+            )
+            .unwrap();
+            let pages = reader.collect::<Result<Vec<_>>>().unwrap();
+            assert_eq!(pages.len(), 1);
+            pages.into_iter().next().unwrap()
+        };
+
+        // Compressible values, and a null
+        let page = write_pages(&[7; 100], &[[1; 100].as_slice(), &[0]].concat());
+        match page {
+            Page::DataPageV2 {
+                buf,
+                num_values,
+                num_nulls,
+                def_levels_byte_len,
+                is_compressed,
+                statistics,
+                ..
+            } => {
+                assert!(is_compressed);
+                assert_eq!(num_values, 101);
+                assert_eq!(num_nulls, 1);
+                // Decompressed levels followed by the plain encoded values
+                assert_eq!(buf.len(), def_levels_byte_len as usize + 400);
+                let statistics = statistics.unwrap();
+                assert_eq!(statistics.null_count(), 1);
+                assert_eq!(
+                    statistics,
+                    Statistics::int32(Some(7), Some(7), None, 1, false)
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        // Only nulls, so that there are no values to compress
+        let page = write_pages(&[], &[0; 10]);
+        match page {
+            Page::DataPageV2 {
+                num_values,
+                num_nulls,
+                is_compressed,
+                statistics,
+                ..
+            } => {
+                assert!(!is_compressed);
+                assert_eq!(num_values, 10);
+                assert_eq!(num_nulls, 10);
+                assert!(statistics.is_none());
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn test_column_writer_add_data_pages_with_dict() {
         // ARROW-5129: Test verifies that we add data page in case of dictionary encoding
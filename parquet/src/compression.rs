@@ -79,6 +79,8 @@ pub trait Codec: Send {
 pub struct CodecOptions {
     /// Whether or not to fallback to other LZ4 older implementations on error in LZ4_HADOOP.
     backward_compatible_lz4: bool,
+    /// The compression level to use for codecs that support it.
+    compression_level: Option<u32>,
 }
 
 impl Default for CodecOptions {
@@ -90,12 +92,15 @@ impl Default for CodecOptions {
 pub struct CodecOptionsBuilder {
     /// Whether or not to fallback to other LZ4 older implementations on error in LZ4_HADOOP.
     backward_compatible_lz4: bool,
+    /// The compression level to use for codecs that support it.
+    compression_level: Option<u32>,
 }
 
 impl Default for CodecOptionsBuilder {
     fn default() -> Self {
         Self {
             backward_compatible_lz4: true,
+            compression_level: None,
         }
     }
 }
@@ -114,13 +119,49 @@ impl CodecOptionsBuilder {
         self
     }
 
+    /// Sets the compression level, or `None` to use the default level of the codec.
+    ///
+    /// Only GZIP (0-9), BROTLI (0-11) and ZSTD (1-22) support a compression level,
+    /// it is ignored by all other codecs.
+    pub fn set_compression_level(mut self, value: Option<u32>) -> CodecOptionsBuilder {
+        self.compression_level = value;
+        self
+    }
+
     pub fn build(self) -> CodecOptions {
         CodecOptions {
             backward_compatible_lz4: self.backward_compatible_lz4,
+            compression_level: self.compression_level,
         }
     }
 }
 
+/// Returns an error if `level` is not a valid compression level for `codec`
+///
+/// Only GZIP (0-9), BROTLI (0-11) and ZSTD (1-22) support a compression level, any
+/// level is valid for other codecs, as it is ignored by them.
+pub(crate) fn check_compression_level(
+    codec: CodecType,
+    level: Option<u32>,
+) -> Result<()> {
+    let range = match codec {
+        CodecType::GZIP => 0..=9,
+        CodecType::BROTLI => 0..=11,
+        CodecType::ZSTD => 1..=22,
+        _ => return Ok(()),
+    };
+    match level {
+        Some(level) if !range.contains(&level) => Err(general_err!(
+            "Invalid compression level {} for codec {}, expected {}..={}",
+            level,
+            codec,
+            range.start(),
+            range.end()
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// Given the compression type `codec`, returns a codec used to compress and decompress
 /// bytes for the compression type.
 /// This returns `None` if the codec type is `UNCOMPRESSED`.
@@ -128,11 +169,14 @@ pub fn create_codec(
     codec: CodecType,
     _options: &CodecOptions,
 ) -> Result<Option<Box<dyn Codec>>> {
+    check_compression_level(codec, _options.compression_level)?;
     match codec {
         #[cfg(any(feature = "brotli", test))]
-        CodecType::BROTLI => Ok(Some(Box::new(BrotliCodec::new()))),
+        CodecType::BROTLI => {
+            Ok(Some(Box::new(BrotliCodec::new(_options.compression_level))))
+        }
         #[cfg(any(feature = "flate2", test))]
-        CodecType::GZIP => Ok(Some(Box::new(GZipCodec::new()))),
+        CodecType::GZIP => Ok(Some(Box::new(GZipCodec::new(_options.compression_level)))),
         #[cfg(any(feature = "snap", test))]
         CodecType::SNAPPY => Ok(Some(Box::new(SnappyCodec::new()))),
         #[cfg(any(feature = "lz4", test))]
@@ -140,7 +184,7 @@ pub fn create_codec(
             _options.backward_compatible_lz4,
         )))),
         #[cfg(any(feature = "zstd", test))]
-        CodecType::ZSTD => Ok(Some(Box::new(ZSTDCodec::new()))),
+        CodecType::ZSTD => Ok(Some(Box::new(ZSTDCodec::new(_options.compression_level)))),
         #[cfg(any(feature = "lz4", test))]
         CodecType::LZ4_RAW => Ok(Some(Box::new(LZ4RawCodec::new()))),
         CodecType::UNCOMPRESSED => Ok(None),
@@ -215,12 +259,16 @@ mod gzip_codec {
     use crate::errors::Result;
 
     /// Codec for GZIP compression algorithm.
-    pub struct GZipCodec {}
+    pub struct GZipCodec {
+        level: Compression,
+    }
 
     impl GZipCodec {
-        /// Creates new GZIP compression codec.
-        pub(crate) fn new() -> Self {
-            Self {}
+        /// Creates new GZIP compression codec, using the default level if `None`.
+        pub(crate) fn new(level: Option<u32>) -> Self {
+            Self {
+                level: level.map(Compression::new).unwrap_or_default(),
+            }
         }
     }
 
@@ -236,7 +284,7 @@ mod gzip_codec {
         }
 
         fn compress(&mut self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
-            let mut encoder = write::GzEncoder::new(output_buf, Compression::default());
+            let mut encoder = write::GzEncoder::new(output_buf, self.level);
             encoder.write_all(input_buf)?;
             encoder.try_finish().map_err(|e| e.into())
         }
@@ -254,16 +302,20 @@ mod brotli_codec {
     use crate::errors::Result;
 
     const BROTLI_DEFAULT_BUFFER_SIZE: usize = 4096;
-    const BROTLI_DEFAULT_COMPRESSION_QUALITY: u32 = 1; // supported levels 0-9
+    const BROTLI_DEFAULT_COMPRESSION_QUALITY: u32 = 1; // supported levels 0-11
     const BROTLI_DEFAULT_LG_WINDOW_SIZE: u32 = 22; // recommended between 20-22
 
     /// Codec for Brotli compression algorithm.
-    pub struct BrotliCodec {}
+    pub struct BrotliCodec {
+        quality: u32,
+    }
 
     impl BrotliCodec {
-        /// Creates new Brotli compression codec.
-        pub(crate) fn new() -> Self {
-            Self {}
+        /// Creates new Brotli compression codec, using the default quality if `None`.
+        pub(crate) fn new(quality: Option<u32>) -> Self {
+            Self {
+                quality: quality.unwrap_or(BROTLI_DEFAULT_COMPRESSION_QUALITY),
+            }
         }
     }
 
@@ -284,7 +336,7 @@ mod brotli_codec {
             let mut encoder = brotli::CompressorWriter::new(
                 output_buf,
                 BROTLI_DEFAULT_BUFFER_SIZE,
-                BROTLI_DEFAULT_COMPRESSION_QUALITY,
+                self.quality,
                 BROTLI_DEFAULT_LG_WINDOW_SIZE,
             );
             encoder.write_all(input_buf)?;
@@ -361,16 +413,20 @@ mod zstd_codec {
     use crate::errors::Result;
 
     /// Codec for Zstandard compression algorithm.
-    pub struct ZSTDCodec {}
+    pub struct ZSTDCodec {
+        level: i32,
+    }
 
     impl ZSTDCodec {
-        /// Creates new Zstandard compression codec.
-        pub(crate) fn new() -> Self {
-            Self {}
+        /// Creates new Zstandard compression codec, using the default level if `None`.
+        pub(crate) fn new(level: Option<u32>) -> Self {
+            Self {
+                level: level.map(|l| l as i32).unwrap_or(ZSTD_COMPRESSION_LEVEL),
+            }
         }
     }
 
-    /// Compression level (1-21) for ZSTD. Choose 1 here for better compression speed.
+    /// Compression level (1-22) for ZSTD. Choose 1 here for better compression speed.
     const ZSTD_COMPRESSION_LEVEL: i32 = 1;
 
     impl Codec for ZSTDCodec {
@@ -388,7 +444,7 @@ mod zstd_codec {
         }
 
         fn compress(&mut self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
-            let mut encoder = zstd::Encoder::new(output_buf, ZSTD_COMPRESSION_LEVEL)?;
+            let mut encoder = zstd::Encoder::new(output_buf, self.level)?;
             encoder.write_all(input_buf)?;
             match encoder.finish() {
                 Ok(_) => Ok(()),
@@ -550,7 +606,7 @@ mod lz4_hadoop_codec {
             }
             let decompressed_size = lz4::block::decompress_to_buffer(
                 &input[..expected_compressed_size as usize],
-                Some(output_len as i32),
+                Some(expected_decompressed_size as i32),
                 output,
             )?;
             if decompressed_size != expected_decompressed_size as usize {
@@ -562,12 +618,8 @@ mod lz4_hadoop_codec {
             input_len -= expected_compressed_size as usize;
             output_len -= expected_decompressed_size as usize;
             read_bytes += expected_decompressed_size as usize;
-            if input_len > expected_compressed_size as usize {
-                input = &input[expected_compressed_size as usize..];
-                output = &mut output[expected_decompressed_size as usize..];
-            } else {
-                break;
-            }
+            input = &input[expected_compressed_size as usize..];
+            output = &mut output[expected_decompressed_size as usize..];
         }
         if input_len == 0 {
             Ok(read_bytes)
@@ -609,25 +661,28 @@ mod lz4_hadoop_codec {
                 Err(e) if !self.backward_compatible_lz4 => Err(e.into()),
                 // Fallback done to be backward compatible with older versions of this
                 // libray and older versions of parquet-cpp.
-                Err(_) => {
-                    // Truncate any inserted element before tryingg next algorithm.
+                Err(e) => {
+                    // Truncate any inserted element before trying next algorithm.
                     output_buf.truncate(output_len);
                     match LZ4Codec::new().decompress(
                         input_buf,
                         output_buf,
                         uncompress_size,
                     ) {
-                        Ok(n) => Ok(n),
-                        Err(_) => {
-                            // Truncate any inserted element before tryingg next algorithm.
-                            output_buf.truncate(output_len);
-                            LZ4RawCodec::new().decompress(
-                                input_buf,
-                                output_buf,
-                                uncompress_size,
-                            )
-                        }
+                        Ok(n) if n == required_len => return Ok(n),
+                        _ => {}
                     }
+
+                    // Truncate any inserted element before trying next algorithm.
+                    output_buf.truncate(output_len);
+                    LZ4RawCodec::new()
+                        .decompress(input_buf, output_buf, uncompress_size)
+                        .map_err(|_| {
+                            // None of the LZ4 framings could decompress the data,
+                            // report the error of the standard LZ4 framing
+                            output_buf.truncate(output_len);
+                            ParquetError::from(e)
+                        })
                 }
             }
         }
@@ -763,8 +818,100 @@ mod tests {
         test_codec_without_size(CodecType::ZSTD);
     }
 
+    #[test]
+    fn test_codec_compression_level() {
+        let data = random_bytes(10000);
+        for (codec, levels) in [
+            (CodecType::GZIP, [0, 9]),
+            (CodecType::BROTLI, [0, 11]),
+            (CodecType::ZSTD, [1, 22]),
+        ] {
+            for level in levels {
+                let options = CodecOptionsBuilder::default()
+                    .set_compression_level(Some(level))
+                    .build();
+                let mut c = create_codec(codec, &options).unwrap().unwrap();
+
+                let mut compressed = Vec::new();
+                let mut decompressed = Vec::new();
+                c.compress(&data, &mut compressed).unwrap();
+                c.decompress(&compressed, &mut decompressed, Some(data.len()))
+                    .unwrap();
+                assert_eq!(data, decompressed);
+            }
+        }
+
+        // Level is ignored by codecs that do not support it
+        let options = CodecOptionsBuilder::default()
+            .set_compression_level(Some(100))
+            .build();
+        assert!(create_codec(CodecType::SNAPPY, &options).is_ok());
+
+        for codec in [CodecType::GZIP, CodecType::BROTLI, CodecType::ZSTD] {
+            let err = create_codec(codec, &options).err().unwrap().to_string();
+            assert!(err.contains("Invalid compression level 100"), "{}", err);
+        }
+    }
+
     #[test]
     fn test_codec_lz4_raw() {
         test_codec_with_size(CodecType::LZ4_RAW);
     }
+
+    #[test]
+    fn test_lz4_hadoop_multiple_frames() {
+        let data = random_bytes(10000);
+
+        // The second frame is smaller than the first one
+        let mut compressed = vec![];
+        for chunk in [&data[..8000], &data[8000..]] {
+            let mut frame = vec![];
+            LZ4RawCodec::new().compress(chunk, &mut frame).unwrap();
+            compressed.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+            compressed.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+            compressed.extend_from_slice(&frame);
+        }
+
+        let mut decompressed = vec![];
+        let size = LZ4HadoopCodec::new(false)
+            .decompress(&compressed, &mut decompressed, Some(data.len()))
+            .unwrap();
+        assert_eq!(size, data.len());
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_lz4_hadoop_fallback() {
+        let data = random_bytes(10000);
+
+        let mut frame_compressed = vec![];
+        LZ4Codec::new()
+            .compress(&data, &mut frame_compressed)
+            .unwrap();
+        let mut raw_compressed = vec![];
+        LZ4RawCodec::new()
+            .compress(&data, &mut raw_compressed)
+            .unwrap();
+
+        for compressed in [&frame_compressed, &raw_compressed] {
+            let mut decompressed = vec![];
+            let size = LZ4HadoopCodec::new(true)
+                .decompress(compressed, &mut decompressed, Some(data.len()))
+                .unwrap();
+            assert_eq!(size, data.len());
+            assert_eq!(decompressed, data);
+
+            let mut decompressed = vec![];
+            LZ4HadoopCodec::new(false)
+                .decompress(compressed, &mut decompressed, Some(data.len()))
+                .unwrap_err();
+        }
+
+        // Data that is not LZ4 compressed with any framing
+        let mut decompressed = vec![1, 2, 3];
+        LZ4HadoopCodec::new(true)
+            .decompress(&data, &mut decompressed, Some(data.len()))
+            .unwrap_err();
+        assert_eq!(decompressed, vec![1, 2, 3]);
+    }
 }
@@ -1053,15 +1053,12 @@ pub(crate) mod private {
                 .as_mut()
                 .expect("set_data should have been called");
             let num_values = std::cmp::min(num_values, decoder.num_values);
-            for _ in 0..num_values {
-                let len = decoder.type_length as usize;
-
-                if data.len() < decoder.start + len {
-                    return Err(eof_err!("Not enough bytes to skip"));
-                }
+            let bytes_to_skip = decoder.type_length as usize * num_values;
 
-                decoder.start += len;
+            if data.len() < decoder.start + bytes_to_skip {
+                return Err(eof_err!("Not enough bytes to skip"));
             }
+            decoder.start += bytes_to_skip;
             decoder.num_values -= num_values;
 
             Ok(num_values)
@@ -33,6 +33,10 @@ use crate::util::{
     memory::ByteBufferPtr,
 };
 
+/// The maximum number of values decoded at a time by decoders that must decode values
+/// in order to skip them
+const SKIP_BATCH_SIZE: usize = 1024;
+
 pub(crate) mod private {
     use super::*;
 
@@ -61,7 +65,8 @@ pub(crate) mod private {
             Encoding::RLE
             | Encoding::DELTA_BINARY_PACKED
             | Encoding::DELTA_BYTE_ARRAY
-            | Encoding::DELTA_LENGTH_BYTE_ARRAY => Err(general_err!(
+            | Encoding::DELTA_LENGTH_BYTE_ARRAY
+            | Encoding::BYTE_STREAM_SPLIT => Err(general_err!(
                 "Encoding {} is not supported for type",
                 encoding
             )),
@@ -105,8 +110,33 @@ pub(crate) mod private {
         }
     }
 
-    impl GetDecoder for f32 {}
-    impl GetDecoder for f64 {}
+    impl GetDecoder for f32 {
+        fn get_decoder<T: DataType<T = Self>>(
+            descr: ColumnDescPtr,
+            encoding: Encoding,
+        ) -> Result<Box<dyn Decoder<T>>> {
+            match encoding {
+                Encoding::BYTE_STREAM_SPLIT => {
+                    Ok(Box::new(ByteStreamSplitDecoder::new()))
+                }
+                _ => get_decoder_default(descr, encoding),
+            }
+        }
+    }
+
+    impl GetDecoder for f64 {
+        fn get_decoder<T: DataType<T = Self>>(
+            descr: ColumnDescPtr,
+            encoding: Encoding,
+        ) -> Result<Box<dyn Decoder<T>>> {
+            match encoding {
+                Encoding::BYTE_STREAM_SPLIT => {
+                    Ok(Box::new(ByteStreamSplitDecoder::new()))
+                }
+                _ => get_decoder_default(descr, encoding),
+            }
+        }
+    }
 
     impl GetDecoder for ByteArray {
         fn get_decoder<T: DataType<T = Self>>(
@@ -947,7 +977,7 @@ pub struct DeltaByteArrayDecoder<T: DataType> {
     suffix_decoder: Option<DeltaLengthByteArrayDecoder<ByteArrayType>>,
 
     // The last byte array, used to derive the current prefix
-    previous_value: Vec<u8>,
+    previous_value: ByteArray,
 
     // Number of values left
     num_values: usize,
@@ -969,7 +999,7 @@ impl<T: DataType> DeltaByteArrayDecoder<T> {
             prefix_lengths: vec![],
             current_idx: 0,
             suffix_decoder: None,
-            previous_value: vec![],
+            previous_value: ByteArray::from(vec![]),
             num_values: 0,
             _phantom: PhantomData,
         }
@@ -992,7 +1022,7 @@ impl<T: DataType> Decoder<T> for DeltaByteArrayDecoder<T> {
                 self.suffix_decoder = Some(suffix_decoder);
                 self.num_values = num_prefixes;
                 self.current_idx = 0;
-                self.previous_value.clear();
+                self.previous_value = ByteArray::from(vec![]);
                 Ok(())
             }
             _ => {
@@ -1013,33 +1043,39 @@ impl<T: DataType> Decoder<T> for DeltaByteArrayDecoder<T> {
                     // TODO: this is awkward - maybe we should add a non-vectorized API?
                     let suffix_decoder = self.suffix_decoder.as_mut().expect("decoder not initialized");
                     suffix_decoder.get(&mut v[..])?;
-                    let suffix = v[0].data();
 
                     // Extract current prefix length, can be 0
                     let prefix_len = self.prefix_lengths[self.current_idx] as usize;
 
-                    // Concatenate prefix with suffix
-                    let mut result = Vec::new();
-                    result.extend_from_slice(&self.previous_value[0..prefix_len]);
-                    result.extend_from_slice(suffix);
-
-                    let data = ByteBufferPtr::new(result.clone());
+                    // Concatenate prefix with suffix, a value without a prefix references
+                    // the suffix in the page buffer instead of copying it
+                    let value = match prefix_len {
+                        0 => v[0].clone(),
+                        _ => {
+                            let prefix = &self.previous_value.data()[0..prefix_len];
+                            let suffix = v[0].data();
+                            let mut result = Vec::with_capacity(prefix_len + suffix.len());
+                            result.extend_from_slice(prefix);
+                            result.extend_from_slice(suffix);
+                            ByteArray::from(result)
+                        }
+                    };
 
                     match ty {
-                        Type::BYTE_ARRAY => item
-                            .as_mut_any()
-                            .downcast_mut::<ByteArray>()
-                            .unwrap()
-                            .set_data(data),
-                        Type::FIXED_LEN_BYTE_ARRAY => item
-                            .as_mut_any()
-                            .downcast_mut::<FixedLenByteArray>()
-                            .unwrap()
-                            .set_data(data),
+                        Type::BYTE_ARRAY => {
+                            *item.as_mut_any().downcast_mut::<ByteArray>().unwrap() =
+                                value.clone()
+                        }
+                        Type::FIXED_LEN_BYTE_ARRAY => {
+                            *item
+                                .as_mut_any()
+                                .downcast_mut::<FixedLenByteArray>()
+                                .unwrap() = value.clone().into()
+                        }
                         _ => unreachable!(),
                     };
 
-                    self.previous_value = result;
+                    self.previous_value = value;
                     self.current_idx += 1;
                 }
 
@@ -1063,8 +1099,121 @@ impl<T: DataType> Decoder<T> for DeltaByteArrayDecoder<T> {
     }
 
     fn skip(&mut self, num_values: usize) -> Result<usize> {
-        let mut buffer = vec![T::T::default(); num_values];
-        self.get(&mut buffer)
+        // Each value is encoded relative to the previous, so values must be decoded
+        // to be skipped, but they need only be buffered a batch at a time
+        let to_skip = num_values.min(self.num_values);
+        let mut buffer = vec![T::T::default(); to_skip.min(SKIP_BATCH_SIZE)];
+        let mut skipped = 0;
+        while skipped < to_skip {
+            let batch = (to_skip - skipped).min(buffer.len());
+            let read = self.get(&mut buffer[..batch])?;
+            if read == 0 {
+                break;
+            }
+            skipped += read;
+        }
+        Ok(skipped)
+    }
+}
+
+// ----------------------------------------------------------------------
+// BYTE_STREAM_SPLIT Decoding
+
+/// Byte stream split decoder.
+/// Only applied to floating-point values, the i-th byte of each value is stored in
+/// the i-th of K concatenated byte streams, where K is the size in bytes of the type.
+/// See [`ByteStreamSplitEncoder`](crate::encoding::ByteStreamSplitEncoder) for more
+/// information.
+pub struct ByteStreamSplitDecoder<T: DataType> {
+    // Encoded byte streams
+    data: Option<ByteBufferPtr>,
+
+    // Number of values encoded in `data`, which is the length of each byte stream
+    num_encoded_values: usize,
+
+    // Index of the next value to decode
+    current_idx: usize,
+
+    // Placeholder to allow `T` as generic parameter
+    _phantom: PhantomData<T>,
+}
+
+impl<T: DataType> Default for ByteStreamSplitDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DataType> ByteStreamSplitDecoder<T> {
+    /// Creates new byte stream split decoder.
+    pub fn new() -> Self {
+        Self {
+            data: None,
+            num_encoded_values: 0,
+            current_idx: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: DataType> Decoder<T> for ByteStreamSplitDecoder<T> {
+    fn set_data(&mut self, data: ByteBufferPtr, _num_values: usize) -> Result<()> {
+        match T::get_physical_type() {
+            Type::FLOAT | Type::DOUBLE => {
+                let type_size = mem::size_of::<T::T>();
+                if data.len() % type_size != 0 {
+                    return Err(eof_err!(
+                        "Byte stream split data of {} bytes is not a multiple of {}",
+                        data.len(),
+                        type_size
+                    ));
+                }
+                // `num_values` may include nulls, the stream length is given by the data
+                self.num_encoded_values = data.len() / type_size;
+                self.data = Some(data);
+                self.current_idx = 0;
+                Ok(())
+            }
+            other_type => Err(general_err!(
+                "ByteStreamSplitDecoder not support {}, only support float and double",
+                other_type
+            )),
+        }
+    }
+
+    fn get(&mut self, buffer: &mut [T::T]) -> Result<usize> {
+        assert!(self.data.is_some());
+
+        let data = self.data.as_ref().unwrap().data();
+        let num_values = cmp::min(buffer.len(), self.values_left());
+        let type_size = mem::size_of::<T::T>();
+
+        // SAFETY: only floating-point types are supported, for which every bit
+        // pattern is a valid value
+        let raw = unsafe { T::T::slice_as_bytes_mut(&mut buffer[..num_values]) };
+        for (idx, value) in raw.chunks_exact_mut(type_size).enumerate() {
+            let offset = self.current_idx + idx;
+            for (stream, byte) in value.iter_mut().enumerate() {
+                *byte = data[stream * self.num_encoded_values + offset];
+            }
+        }
+
+        self.current_idx += num_values;
+        Ok(num_values)
+    }
+
+    fn values_left(&self) -> usize {
+        self.num_encoded_values - self.current_idx
+    }
+
+    fn encoding(&self) -> Encoding {
+        Encoding::BYTE_STREAM_SPLIT
+    }
+
+    fn skip(&mut self, num_values: usize) -> Result<usize> {
+        let num_values = cmp::min(num_values, self.values_left());
+        self.current_idx += num_values;
+        Ok(num_values)
     }
 }
 
@@ -1092,6 +1241,8 @@ mod tests {
         );
         create_and_check_decoder::<ByteArrayType>(Encoding::DELTA_BYTE_ARRAY, None);
         create_and_check_decoder::<BoolType>(Encoding::RLE, None);
+        create_and_check_decoder::<FloatType>(Encoding::BYTE_STREAM_SPLIT, None);
+        create_and_check_decoder::<DoubleType>(Encoding::BYTE_STREAM_SPLIT, None);
 
         // error when initializing
         create_and_check_decoder::<Int32Type>(
@@ -1118,6 +1269,12 @@ mod tests {
                 "Encoding DELTA_BYTE_ARRAY is not supported for type"
             )),
         );
+        create_and_check_decoder::<Int32Type>(
+            Encoding::BYTE_STREAM_SPLIT,
+            Some(general_err!(
+                "Encoding BYTE_STREAM_SPLIT is not supported for type"
+            )),
+        );
 
         // unsupported
         create_and_check_decoder::<Int32Type>(
@@ -1854,6 +2011,101 @@ mod tests {
         test_delta_byte_array_decode(data);
     }
 
+    #[test]
+    fn test_byte_stream_split_decode_float() {
+        let values = [1.5f32, -2.25, 0.0];
+        // The i-th byte of every value, followed by the (i+1)-th byte of every value
+        let mut streams = vec![];
+        for stream in 0..4 {
+            streams.extend(values.iter().map(|v| v.to_le_bytes()[stream]));
+        }
+
+        let mut decoder = ByteStreamSplitDecoder::<FloatType>::new();
+        decoder.set_data(ByteBufferPtr::new(streams), 3).unwrap();
+        let mut result = vec![0.0; 3];
+        assert_eq!(decoder.get(&mut result).unwrap(), 3);
+        assert_eq!(result, values);
+        assert_eq!(decoder.values_left(), 0);
+
+        test_encode_decode::<FloatType>(
+            vec![values.to_vec(), vec![f32::MAX, f32::MIN]],
+            Encoding::BYTE_STREAM_SPLIT,
+        );
+    }
+
+    #[test]
+    fn test_byte_stream_split_decode_double() {
+        let data = vec![
+            vec![0.1f64, 1e300, -7.0],
+            vec![f64::EPSILON],
+            vec![f64::INFINITY, f64::MIN_POSITIVE],
+        ];
+        test_encode_decode::<DoubleType>(data, Encoding::BYTE_STREAM_SPLIT);
+    }
+
+    #[test]
+    fn test_byte_stream_split_invalid_length() {
+        let mut decoder = ByteStreamSplitDecoder::<DoubleType>::new();
+        let err = decoder
+            .set_data(ByteBufferPtr::new(vec![0; 12]), 2)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "EOF: Byte stream split data of 12 bytes is not a multiple of 8"
+        );
+    }
+
+    #[test]
+    fn test_skip_byte_stream_split() {
+        let data = vec![1.0f32, 2.0, 3.0, 4.0, 5.0];
+        test_skip::<FloatType>(data.clone(), Encoding::BYTE_STREAM_SPLIT, 2);
+        test_skip::<FloatType>(data, Encoding::BYTE_STREAM_SPLIT, 10);
+    }
+
+    #[test]
+    fn test_skip_delta_byte_array() {
+        let data: Vec<ByteArray> = (0..3000)
+            .map(|x| format!("prefix{}", x / 7).as_str().into())
+            .collect();
+        test_skip::<ByteArrayType>(data.clone(), Encoding::DELTA_BYTE_ARRAY, 5);
+        test_skip::<ByteArrayType>(data.clone(), Encoding::DELTA_BYTE_ARRAY, 2500);
+        test_skip::<ByteArrayType>(data, Encoding::DELTA_BYTE_ARRAY, 5000);
+    }
+
+    #[test]
+    fn test_byte_array_decode_zero_copy() {
+        let data: Vec<ByteArray> =
+            vec!["apple".into(), "applesauce".into(), "banana".into()];
+        let col_descr = create_test_col_desc_ptr(-1, Type::BYTE_ARRAY);
+
+        // Whether each decoded value references the encoded buffer
+        let expected: [(Encoding, &[bool]); 3] = [
+            (Encoding::PLAIN, &[true, true, true]),
+            (Encoding::DELTA_LENGTH_BYTE_ARRAY, &[true, true, true]),
+            // Values sharing a prefix with the previous value are copied
+            (Encoding::DELTA_BYTE_ARRAY, &[true, false, true]),
+        ];
+        for (encoding, zero_copy) in expected {
+            let mut encoder = get_encoder::<ByteArrayType>(encoding).unwrap();
+            encoder.put(&data).unwrap();
+            let bytes = encoder.flush_buffer().unwrap();
+            let range = bytes.as_ref().as_ptr_range();
+
+            let mut decoder =
+                get_decoder::<ByteArrayType>(col_descr.clone(), encoding).unwrap();
+            decoder.set_data(bytes.clone(), data.len()).unwrap();
+            let mut result = vec![ByteArray::new(); data.len()];
+            assert_eq!(decoder.get(&mut result).unwrap(), data.len());
+            assert_eq!(result, data);
+
+            let referenced: Vec<_> = result
+                .iter()
+                .map(|v| range.contains(&v.data().as_ptr()))
+                .collect();
+            assert_eq!(referenced, zero_copy, "{}", encoding);
+        }
+    }
+
     fn test_rle_value_decode<T: DataType>(data: Vec<Vec<T::T>>) {
         test_encode_decode::<T>(data, Encoding::RLE);
     }
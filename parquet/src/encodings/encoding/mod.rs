@@ -87,6 +87,15 @@ pub fn get_encoder<T: DataType>(encoding: Encoding) -> Result<Box<dyn Encoder<T>
         Encoding::DELTA_BINARY_PACKED => Box::new(DeltaBitPackEncoder::new()),
         Encoding::DELTA_LENGTH_BYTE_ARRAY => Box::new(DeltaLengthByteArrayEncoder::new()),
         Encoding::DELTA_BYTE_ARRAY => Box::new(DeltaByteArrayEncoder::new()),
+        Encoding::BYTE_STREAM_SPLIT => match T::get_physical_type() {
+            Type::FLOAT | Type::DOUBLE => Box::new(ByteStreamSplitEncoder::new()),
+            _ => {
+                return Err(general_err!(
+                    "Encoding {} is not supported for type",
+                    encoding
+                ))
+            }
+        },
         e => return Err(nyi_err!("Encoding {} is not supported", e)),
     };
     Ok(encoder)
@@ -719,6 +728,74 @@ impl<T: DataType> Encoder<T> for DeltaByteArrayEncoder<T> {
     }
 }
 
+// ----------------------------------------------------------------------
+// BYTE_STREAM_SPLIT encoding
+
+/// Encoding for floating-point data.
+/// The bytes of each value are scattered into K streams, where K is the size in bytes
+/// of the data type, and the streams are then concatenated. This does not reduce the
+/// size of the data by itself, but typically improves the compression ratio.
+pub struct ByteStreamSplitEncoder<T: DataType> {
+    buffer: Vec<u8>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: DataType> Default for ByteStreamSplitEncoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DataType> ByteStreamSplitEncoder<T> {
+    /// Creates new byte stream split encoder.
+    pub fn new() -> Self {
+        Self {
+            buffer: vec![],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: DataType> Encoder<T> for ByteStreamSplitEncoder<T> {
+    fn put(&mut self, values: &[T::T]) -> Result<()> {
+        ensure_phys_ty!(
+            Type::FLOAT | Type::DOUBLE,
+            "ByteStreamSplitEncoder only supports FloatType and DoubleType"
+        );
+
+        self.buffer
+            .extend_from_slice(<T::T as SliceAsBytes>::slice_as_bytes(values));
+        Ok(())
+    }
+
+    // Performance Note:
+    // As far as can be seen these functions are rarely called and as such we can hint to the
+    // compiler that they dont need to be folded into hot locations in the final output.
+    #[cold]
+    fn encoding(&self) -> Encoding {
+        Encoding::BYTE_STREAM_SPLIT
+    }
+
+    fn estimated_data_encoded_size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+        let type_size = std::mem::size_of::<T::T>();
+        let num_values = self.buffer.len() / type_size;
+
+        let mut encoded = vec![0; self.buffer.len()];
+        for (idx, value) in self.buffer.chunks_exact(type_size).enumerate() {
+            for (stream, byte) in value.iter().enumerate() {
+                encoded[stream * num_values + idx] = *byte;
+            }
+        }
+        self.buffer.clear();
+
+        Ok(ByteBufferPtr::new(encoded))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -741,6 +818,8 @@ mod tests {
         create_and_check_encoder::<Int32Type>(Encoding::DELTA_LENGTH_BYTE_ARRAY, None);
         create_and_check_encoder::<Int32Type>(Encoding::DELTA_BYTE_ARRAY, None);
         create_and_check_encoder::<BoolType>(Encoding::RLE, None);
+        create_and_check_encoder::<FloatType>(Encoding::BYTE_STREAM_SPLIT, None);
+        create_and_check_encoder::<DoubleType>(Encoding::BYTE_STREAM_SPLIT, None);
 
         // error when initializing
         create_and_check_encoder::<Int32Type>(
@@ -755,6 +834,12 @@ mod tests {
                 "Cannot initialize this encoding through this function"
             )),
         );
+        create_and_check_encoder::<Int32Type>(
+            Encoding::BYTE_STREAM_SPLIT,
+            Some(general_err!(
+                "Encoding BYTE_STREAM_SPLIT is not supported for type"
+            )),
+        );
 
         // unsupported
         create_and_check_encoder::<Int32Type>(
@@ -794,12 +879,14 @@ mod tests {
     fn test_float() {
         FloatType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
         FloatType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+        FloatType::test(Encoding::BYTE_STREAM_SPLIT, TEST_SET_SIZE, -1);
     }
 
     #[test]
     fn test_double() {
         DoubleType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
         DoubleType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+        DoubleType::test(Encoding::BYTE_STREAM_SPLIT, TEST_SET_SIZE, -1);
     }
 
     #[test]
@@ -918,6 +1005,9 @@ mod tests {
             3, // only suffix bytes, length encoder is not flushed yet
             0,
         );
+
+        // BYTE_STREAM_SPLIT
+        run_test::<FloatType>(Encoding::BYTE_STREAM_SPLIT, -1, &[0.1; 1024], 0, 4096, 0);
     }
 
     // See: https://github.com/sunchao/parquet-rs/issues/47
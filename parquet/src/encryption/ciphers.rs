@@ -0,0 +1,475 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The ciphers used to encrypt and decrypt the modules of an encrypted parquet file
+//!
+//! Each encrypted module is stored as a 4 byte little endian length, followed by
+//! that many bytes containing a 12 byte nonce and the ciphertext. For AES-GCM the
+//! ciphertext is followed by a 16 byte authentication tag
+
+use std::fmt::{Debug, Formatter};
+
+use aes_gcm::aead::consts::U12;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use aes_gcm::aes::{Aes128, Aes192, Aes256};
+use aes_gcm::{AeadInPlace, AesGcm, KeyInit, Nonce, Tag};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use ctr::Ctr128BE;
+
+use crate::errors::{ParquetError, Result};
+
+/// The length of the length prefix of an encrypted module
+pub(crate) const SIZE_LEN: usize = 4;
+
+/// The length of the nonce of an encrypted module
+pub(crate) const NONCE_LEN: usize = 12;
+
+/// The length of the authentication tag of a module encrypted with AES-GCM
+pub(crate) const TAG_LEN: usize = 16;
+
+/// Decrypts a single module of an encrypted parquet file
+pub(crate) trait BlockDecryptor: Debug + Send + Sync {
+    /// Decrypts `ciphertext`, which starts with its length prefix, authenticating
+    /// it with the additional authenticated data `aad` if supported by the cipher
+    fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Encrypts a single module of an encrypted parquet file
+pub(crate) trait BlockEncryptor: Debug + Send + Sync {
+    /// Encrypts `plaintext` with a random nonce, authenticating it with the additional
+    /// authenticated data `aad` if supported by the cipher, and returns the module
+    /// including its length prefix
+    fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Vec<u8>;
+}
+
+/// Returns an error if `key` is not a valid AES key
+pub(crate) fn validate_key(key: &[u8]) -> Result<()> {
+    match key.len() {
+        16 | 24 | 32 => Ok(()),
+        len => Err(general_err!(
+            "Invalid AES key length {}, expected 16, 24 or 32 bytes",
+            len
+        )),
+    }
+}
+
+/// Returns the nonce and the remaining bytes of the module `ciphertext`, which
+/// must hold at least `min_len` bytes after its length prefix
+fn split_module(ciphertext: &[u8], min_len: usize) -> Result<(&[u8], &[u8])> {
+    if ciphertext.len() < SIZE_LEN {
+        return Err(eof_err!("Encrypted module is missing its length"));
+    }
+    let len = u32::from_le_bytes(ciphertext[..SIZE_LEN].try_into().unwrap()) as usize;
+    let module = ciphertext.get(SIZE_LEN..SIZE_LEN + len).ok_or_else(|| {
+        eof_err!(
+            "Expected encrypted module of {} bytes, got {}",
+            len,
+            ciphertext.len() - SIZE_LEN
+        )
+    })?;
+    if len < min_len {
+        return Err(general_err!(
+            "Encrypted module of {} bytes is shorter than the minimum of {} bytes",
+            len,
+            min_len
+        ));
+    }
+    Ok(module.split_at(NONCE_LEN))
+}
+
+/// Returns a random nonce, which must never be reused with the same key
+fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    nonce
+}
+
+/// Applies the AES-CTR keystream for `key` and `nonce` to `buf`, which both encrypts
+/// and decrypts it, where `key` must have been validated with [`validate_key`]
+fn apply_ctr_keystream(key: &[u8], nonce: &[u8], buf: &mut [u8]) {
+    // The initial counter block is the nonce followed by a 32 bit counter of 1
+    let mut iv = [0_u8; 16];
+    iv[..NONCE_LEN].copy_from_slice(nonce);
+    iv[15] = 1;
+
+    match key.len() {
+        16 => Ctr128BE::<Aes128>::new_from_slices(key, &iv)
+            .unwrap()
+            .apply_keystream(buf),
+        24 => Ctr128BE::<Aes192>::new_from_slices(key, &iv)
+            .unwrap()
+            .apply_keystream(buf),
+        _ => Ctr128BE::<Aes256>::new_from_slices(key, &iv)
+            .unwrap()
+            .apply_keystream(buf),
+    }
+}
+
+/// Returns `ciphertext` prefixed by its length and `nonce`, followed by `tag`
+fn encode_module(nonce: &[u8], ciphertext: &[u8], tag: &[u8]) -> Vec<u8> {
+    let len = NONCE_LEN + ciphertext.len() + tag.len();
+    let mut module = Vec::with_capacity(SIZE_LEN + len);
+    module.extend_from_slice(&(len as u32).to_le_bytes());
+    module.extend_from_slice(nonce);
+    module.extend_from_slice(ciphertext);
+    module.extend_from_slice(tag);
+    module
+}
+
+/// An AES-GCM cipher for one of the supported key lengths
+enum GcmCipher {
+    Aes128(AesGcm<Aes128, U12>),
+    Aes192(AesGcm<Aes192, U12>),
+    Aes256(AesGcm<Aes256, U12>),
+}
+
+impl GcmCipher {
+    fn new(key: &[u8]) -> Result<Self> {
+        validate_key(key)?;
+        // The key length has been validated above
+        Ok(match key.len() {
+            16 => Self::Aes128(AesGcm::new_from_slice(key).unwrap()),
+            24 => Self::Aes192(AesGcm::new_from_slice(key).unwrap()),
+            _ => Self::Aes256(AesGcm::new_from_slice(key).unwrap()),
+        })
+    }
+
+    fn decrypt(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> bool {
+        let nonce = Nonce::<U12>::from_slice(nonce);
+        let tag = Tag::from_slice(tag);
+        match self {
+            Self::Aes128(c) => c.decrypt_in_place_detached(nonce, aad, buf, tag),
+            Self::Aes192(c) => c.decrypt_in_place_detached(nonce, aad, buf, tag),
+            Self::Aes256(c) => c.decrypt_in_place_detached(nonce, aad, buf, tag),
+        }
+        .is_ok()
+    }
+
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Vec<u8> {
+        let nonce = Nonce::<U12>::from_slice(nonce);
+        // Encryption only fails for plaintexts too large to be parquet modules
+        match self {
+            Self::Aes128(c) => c.encrypt_in_place_detached(nonce, aad, buf),
+            Self::Aes192(c) => c.encrypt_in_place_detached(nonce, aad, buf),
+            Self::Aes256(c) => c.encrypt_in_place_detached(nonce, aad, buf),
+        }
+        .unwrap()
+        .to_vec()
+    }
+}
+
+/// A [`BlockDecryptor`] for modules encrypted with AES-GCM, which is used for all
+/// modules with `AES_GCM_V1`, and for all modules but pages with `AES_GCM_CTR_V1`
+pub(crate) struct AesGcmDecryptor {
+    cipher: GcmCipher,
+}
+
+impl AesGcmDecryptor {
+    /// Create a new [`AesGcmDecryptor`] for `key`
+    pub(crate) fn new(key: &[u8]) -> Result<Self> {
+        Ok(Self {
+            cipher: GcmCipher::new(key)?,
+        })
+    }
+
+    /// Returns an error unless `signature`, a nonce followed by an authentication tag,
+    /// is the signature of the plaintext footer `plaintext` authenticated with `aad`
+    pub(crate) fn verify_signature(
+        &self,
+        plaintext: &[u8],
+        signature: &[u8],
+        aad: &[u8],
+    ) -> Result<()> {
+        if signature.len() != NONCE_LEN + TAG_LEN {
+            return Err(general_err!(
+                "Invalid footer signature of {} bytes",
+                signature.len()
+            ));
+        }
+        let (nonce, tag) = signature.split_at(NONCE_LEN);
+        let mut buf = plaintext.to_vec();
+        match self.cipher.encrypt(nonce, aad, &mut buf) == tag {
+            true => Ok(()),
+            false => Err(general_err!("Footer signature verification failed")),
+        }
+    }
+}
+
+impl Debug for AesGcmDecryptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesGcmDecryptor").finish_non_exhaustive()
+    }
+}
+
+impl BlockDecryptor for AesGcmDecryptor {
+    fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let (nonce, rest) = split_module(ciphertext, NONCE_LEN + TAG_LEN)?;
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+        let mut buf = ciphertext.to_vec();
+        match self.cipher.decrypt(nonce, aad, &mut buf, tag) {
+            true => Ok(buf),
+            false => Err(general_err!(
+                "Failed to decrypt module, the key or AAD may be incorrect"
+            )),
+        }
+    }
+}
+
+/// A [`BlockDecryptor`] for pages encrypted with AES-CTR by `AES_GCM_CTR_V1`,
+/// which are not authenticated
+pub(crate) struct AesCtrDecryptor {
+    key: Vec<u8>,
+}
+
+impl AesCtrDecryptor {
+    /// Create a new [`AesCtrDecryptor`] for `key`
+    pub(crate) fn new(key: &[u8]) -> Result<Self> {
+        validate_key(key)?;
+        Ok(Self { key: key.to_vec() })
+    }
+}
+
+impl Debug for AesCtrDecryptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesCtrDecryptor").finish_non_exhaustive()
+    }
+}
+
+impl BlockDecryptor for AesCtrDecryptor {
+    fn decrypt(&self, ciphertext: &[u8], _aad: &[u8]) -> Result<Vec<u8>> {
+        let (nonce, ciphertext) = split_module(ciphertext, NONCE_LEN)?;
+        let mut buf = ciphertext.to_vec();
+        // The key length was validated on construction
+        apply_ctr_keystream(&self.key, nonce, &mut buf);
+        Ok(buf)
+    }
+}
+
+/// A [`BlockEncryptor`] for modules encrypted with AES-GCM, which is used for all
+/// modules with `AES_GCM_V1`, and for all modules but pages with `AES_GCM_CTR_V1`
+pub(crate) struct AesGcmEncryptor {
+    cipher: GcmCipher,
+}
+
+impl AesGcmEncryptor {
+    /// Create a new [`AesGcmEncryptor`] for `key`
+    pub(crate) fn new(key: &[u8]) -> Result<Self> {
+        Ok(Self {
+            cipher: GcmCipher::new(key)?,
+        })
+    }
+
+    /// Encrypts `plaintext` with the provided `nonce` into a module
+    pub(crate) fn encrypt_with_nonce(
+        &self,
+        nonce: &[u8],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Vec<u8> {
+        let mut buf = plaintext.to_vec();
+        let tag = self.cipher.encrypt(nonce, aad, &mut buf);
+        encode_module(nonce, &buf, &tag)
+    }
+
+    /// Returns the signature of the plaintext footer `plaintext`, a random nonce
+    /// followed by the authentication tag of `plaintext` with `aad`
+    pub(crate) fn sign(&self, plaintext: &[u8], aad: &[u8]) -> Vec<u8> {
+        let nonce = random_nonce();
+        let mut buf = plaintext.to_vec();
+        let tag = self.cipher.encrypt(&nonce, aad, &mut buf);
+        [nonce.as_slice(), &tag].concat()
+    }
+}
+
+impl Debug for AesGcmEncryptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesGcmEncryptor").finish_non_exhaustive()
+    }
+}
+
+impl BlockEncryptor for AesGcmEncryptor {
+    fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Vec<u8> {
+        self.encrypt_with_nonce(&random_nonce(), plaintext, aad)
+    }
+}
+
+/// A [`BlockEncryptor`] for pages encrypted with AES-CTR by `AES_GCM_CTR_V1`
+pub(crate) struct AesCtrEncryptor {
+    key: Vec<u8>,
+}
+
+impl AesCtrEncryptor {
+    /// Create a new [`AesCtrEncryptor`] for `key`
+    pub(crate) fn new(key: &[u8]) -> Result<Self> {
+        validate_key(key)?;
+        Ok(Self { key: key.to_vec() })
+    }
+
+    /// Encrypts `plaintext` with the provided `nonce` into a module
+    pub(crate) fn encrypt_with_nonce(&self, nonce: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut buf = plaintext.to_vec();
+        apply_ctr_keystream(&self.key, nonce, &mut buf);
+        encode_module(nonce, &buf, &[])
+    }
+}
+
+impl Debug for AesCtrEncryptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesCtrEncryptor").finish_non_exhaustive()
+    }
+}
+
+impl BlockEncryptor for AesCtrEncryptor {
+    fn encrypt(&self, plaintext: &[u8], _aad: &[u8]) -> Vec<u8> {
+        self.encrypt_with_nonce(&random_nonce(), plaintext)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Encrypts `plaintext` with AES-GCM into a module decodable by [`AesGcmDecryptor`]
+    pub(crate) fn encrypt_gcm(
+        key: &[u8],
+        nonce: &[u8],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Vec<u8> {
+        AesGcmEncryptor::new(key)
+            .unwrap()
+            .encrypt_with_nonce(nonce, plaintext, aad)
+    }
+
+    /// Encrypts `plaintext` with AES-CTR into a module decodable by [`AesCtrDecryptor`]
+    pub(crate) fn encrypt_ctr(key: &[u8], nonce: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        AesCtrEncryptor::new(key)
+            .unwrap()
+            .encrypt_with_nonce(nonce, plaintext)
+    }
+
+    #[test]
+    fn test_validate_key() {
+        for len in [16, 24, 32] {
+            validate_key(&vec![0; len]).unwrap();
+        }
+        let err = validate_key(&[0; 10]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Invalid AES key length 10, expected 16, 24 or 32 bytes"
+        );
+    }
+
+    #[test]
+    fn test_gcm_decrypt() {
+        let nonce = [7; NONCE_LEN];
+        for key in [vec![1; 16], vec![2; 24], vec![3; 32]] {
+            let module = encrypt_gcm(&key, &nonce, b"hello parquet", b"aad");
+            assert_eq!(module.len(), SIZE_LEN + NONCE_LEN + 13 + TAG_LEN);
+
+            let decryptor = AesGcmDecryptor::new(&key).unwrap();
+            let decrypted = decryptor.decrypt(&module, b"aad").unwrap();
+            assert_eq!(decrypted, b"hello parquet");
+
+            // Authentication fails with the wrong AAD or key
+            decryptor.decrypt(&module, b"other").unwrap_err();
+            let other = AesGcmDecryptor::new(&[9; 16]).unwrap();
+            let err = other.decrypt(&module, b"aad").unwrap_err();
+            assert!(err.to_string().contains("Failed to decrypt"), "{}", err);
+
+            // Or if the ciphertext has been tampered with
+            let mut tampered = module.clone();
+            tampered[SIZE_LEN + NONCE_LEN] ^= 1;
+            decryptor.decrypt(&tampered, b"aad").unwrap_err();
+
+            let err = decryptor.decrypt(&module[..20], b"aad").unwrap_err();
+            assert!(err
+                .to_string()
+                .starts_with("EOF: Expected encrypted module of 41 bytes"));
+        }
+    }
+
+    #[test]
+    fn test_gcm_signature() {
+        let key = [4; 16];
+        let nonce = [5; NONCE_LEN];
+        let module = encrypt_gcm(&key, &nonce, b"footer", b"aad");
+        let signature = &module[module.len() - NONCE_LEN - TAG_LEN..];
+        let signature = [&nonce, &signature[NONCE_LEN..]].concat();
+
+        let decryptor = AesGcmDecryptor::new(&key).unwrap();
+        decryptor
+            .verify_signature(b"footer", &signature, b"aad")
+            .unwrap();
+        decryptor
+            .verify_signature(b"footer!", &signature, b"aad")
+            .unwrap_err();
+        decryptor
+            .verify_signature(b"footer", &signature, b"other")
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_ctr_decrypt() {
+        let key = [6; 16];
+        let nonce = [8; NONCE_LEN];
+        let plaintext = (0..100).collect::<Vec<u8>>();
+
+        let mut iv = [0_u8; 16];
+        iv[..NONCE_LEN].copy_from_slice(&nonce);
+        iv[15] = 1;
+        let mut buf = plaintext.clone();
+        Ctr128BE::<Aes128>::new_from_slices(&key, &iv)
+            .unwrap()
+            .apply_keystream(&mut buf);
+        let len = (NONCE_LEN + buf.len()) as u32;
+        let module = [&len.to_le_bytes(), &nonce[..], &buf].concat();
+
+        let decryptor = AesCtrDecryptor::new(&key).unwrap();
+        assert_eq!(decryptor.decrypt(&module, &[]).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let plaintext = (0..100).collect::<Vec<u8>>();
+        for key in [vec![1; 16], vec![2; 24], vec![3; 32]] {
+            let encryptor = AesGcmEncryptor::new(&key).unwrap();
+            let decryptor = AesGcmDecryptor::new(&key).unwrap();
+            let module = encryptor.encrypt(&plaintext, b"aad");
+            assert_eq!(decryptor.decrypt(&module, b"aad").unwrap(), plaintext);
+
+            // Each module is encrypted with a new nonce
+            let other = encryptor.encrypt(&plaintext, b"aad");
+            assert_ne!(
+                module[SIZE_LEN..SIZE_LEN + NONCE_LEN],
+                other[SIZE_LEN..SIZE_LEN + NONCE_LEN]
+            );
+
+            let signature = encryptor.sign(b"footer", b"aad");
+            decryptor
+                .verify_signature(b"footer", &signature, b"aad")
+                .unwrap();
+
+            let encryptor = AesCtrEncryptor::new(&key).unwrap();
+            let decryptor = AesCtrDecryptor::new(&key).unwrap();
+            let module = encryptor.encrypt(&plaintext, b"aad");
+            assert_eq!(module.len(), SIZE_LEN + NONCE_LEN + plaintext.len());
+            assert_eq!(decryptor.decrypt(&module, &[]).unwrap(), plaintext);
+        }
+    }
+}
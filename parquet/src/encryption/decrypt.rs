@@ -0,0 +1,835 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Configuration and state for decrypting encrypted parquet files
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+
+use thrift::protocol::{TCompactInputProtocol, TSerializable};
+
+use crate::encryption::ciphers::{
+    validate_key, AesCtrDecryptor, AesGcmDecryptor, BlockDecryptor, SIZE_LEN,
+};
+use crate::encryption::modules::{create_footer_aad, create_module_aad, ModuleType};
+use crate::errors::{ParquetError, Result};
+use crate::format::{ColumnCryptoMetaData, EncryptionAlgorithm, PageHeader, PageType};
+use crate::schema::types::ColumnPath;
+
+/// Retrieves the keys used to decrypt an encrypted parquet file from the key metadata
+/// stored in the file, such as by unwrapping them with a key management service
+///
+/// See [`KmsKeyRetriever`](crate::encryption::kms::KmsKeyRetriever) for a retriever
+/// of keys stored with envelope encryption
+pub trait KeyRetriever: Send + Sync {
+    /// Returns the key identified by `key_metadata`, which must be 16, 24 or 32
+    /// bytes long
+    fn retrieve_key(&self, key_metadata: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// A shared [`KeyRetriever`], compared by identity
+#[derive(Clone)]
+struct SharedKeyRetriever(Arc<dyn KeyRetriever>);
+
+impl PartialEq for SharedKeyRetriever {
+    fn eq(&self, other: &Self) -> bool {
+        // Compare the data pointers only, as vtable pointers are not unique
+        std::ptr::eq(
+            Arc::as_ptr(&self.0) as *const u8,
+            Arc::as_ptr(&other.0) as *const u8,
+        )
+    }
+}
+
+impl Eq for SharedKeyRetriever {}
+
+/// The keys and AAD prefix used to decrypt an encrypted parquet file
+///
+/// Columns encrypted with the footer key are decrypted with the footer key, all other
+/// encrypted columns require a key to be provided with
+/// [`FileDecryptionPropertiesBuilder::with_column_key`], or retrieved from their key
+/// metadata with a [`KeyRetriever`]
+///
+/// ```
+/// # use parquet::encryption::decrypt::FileDecryptionProperties;
+/// # use parquet::schema::types::ColumnPath;
+/// let properties = FileDecryptionProperties::builder(b"0123456789012345".to_vec())
+///     .with_column_key(ColumnPath::from("a"), b"1234567890123450".to_vec())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct FileDecryptionProperties {
+    footer_key: Option<Vec<u8>>,
+    column_keys: HashMap<ColumnPath, Vec<u8>>,
+    key_retriever: Option<SharedKeyRetriever>,
+    aad_prefix: Option<Vec<u8>>,
+}
+
+impl FileDecryptionProperties {
+    /// Returns a [`FileDecryptionPropertiesBuilder`] for a file with the footer
+    /// key `footer_key`, which must be 16, 24 or 32 bytes long
+    pub fn builder(footer_key: Vec<u8>) -> FileDecryptionPropertiesBuilder {
+        FileDecryptionPropertiesBuilder {
+            footer_key: Some(footer_key),
+            column_keys: HashMap::new(),
+            key_retriever: None,
+            aad_prefix: None,
+        }
+    }
+
+    /// Returns a [`FileDecryptionPropertiesBuilder`] that retrieves the footer key,
+    /// and the keys of columns not provided with
+    /// [`FileDecryptionPropertiesBuilder::with_column_key`], from their key metadata
+    /// with `key_retriever`
+    pub fn with_key_retriever(
+        key_retriever: Arc<dyn KeyRetriever>,
+    ) -> FileDecryptionPropertiesBuilder {
+        FileDecryptionPropertiesBuilder {
+            footer_key: None,
+            column_keys: HashMap::new(),
+            key_retriever: Some(SharedKeyRetriever(key_retriever)),
+            aad_prefix: None,
+        }
+    }
+
+    /// Returns the AAD prefix supplied for the file, if any
+    pub fn aad_prefix(&self) -> Option<&[u8]> {
+        self.aad_prefix.as_deref()
+    }
+}
+
+impl Debug for FileDecryptionProperties {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't print the keys
+        f.debug_struct("FileDecryptionProperties")
+            .field("columns", &self.column_keys.keys().collect::<Vec<_>>())
+            .field("key_retriever", &self.key_retriever.is_some())
+            .field("aad_prefix", &self.aad_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A builder for [`FileDecryptionProperties`]
+pub struct FileDecryptionPropertiesBuilder {
+    footer_key: Option<Vec<u8>>,
+    column_keys: HashMap<ColumnPath, Vec<u8>>,
+    key_retriever: Option<SharedKeyRetriever>,
+    aad_prefix: Option<Vec<u8>>,
+}
+
+impl FileDecryptionPropertiesBuilder {
+    /// Sets the key used to decrypt the column `column_path`, which must be
+    /// 16, 24 or 32 bytes long
+    pub fn with_column_key(mut self, column_path: ColumnPath, key: Vec<u8>) -> Self {
+        self.column_keys.insert(column_path, key);
+        self
+    }
+
+    /// Sets the AAD prefix of the file, which must be provided for files written
+    /// without storing their AAD prefix
+    pub fn with_aad_prefix(mut self, aad_prefix: Vec<u8>) -> Self {
+        self.aad_prefix = Some(aad_prefix);
+        self
+    }
+
+    /// Returns the [`FileDecryptionProperties`], or an error if any key is invalid
+    pub fn build(self) -> Result<FileDecryptionProperties> {
+        for key in self.footer_key.iter().chain(self.column_keys.values()) {
+            validate_key(key)?;
+        }
+        Ok(FileDecryptionProperties {
+            footer_key: self.footer_key,
+            column_keys: self.column_keys,
+            key_retriever: self.key_retriever,
+            aad_prefix: self.aad_prefix,
+        })
+    }
+}
+
+/// Decrypts the footer and column chunks of an encrypted parquet file, with the
+/// keys of [`FileDecryptionProperties`] and the encryption algorithm of the file
+#[derive(Debug, Clone)]
+pub(crate) struct FileDecryptor {
+    properties: FileDecryptionProperties,
+    /// The AAD prefix followed by the unique file identifier
+    file_aad: Vec<u8>,
+    /// Whether pages are encrypted with AES-CTR instead of AES-GCM
+    ctr_pages: bool,
+    footer_key: Vec<u8>,
+    footer_decryptor: Arc<AesGcmDecryptor>,
+    /// The column keys retrieved with the key retriever, by their key metadata
+    retrieved_keys: Arc<Mutex<HashMap<Vec<u8>, Vec<u8>>>>,
+}
+
+impl FileDecryptor {
+    /// Create a new [`FileDecryptor`] for a file encrypted with `algorithm`, whose
+    /// footer key is identified by `footer_key_metadata`
+    pub(crate) fn new(
+        properties: &FileDecryptionProperties,
+        algorithm: &EncryptionAlgorithm,
+        footer_key_metadata: Option<&[u8]>,
+    ) -> Result<Self> {
+        let (stored_prefix, file_unique, supply_prefix, ctr_pages) = match algorithm {
+            EncryptionAlgorithm::AESGCMV1(a) => (
+                &a.aad_prefix,
+                &a.aad_file_unique,
+                a.supply_aad_prefix,
+                false,
+            ),
+            EncryptionAlgorithm::AESGCMCTRV1(a) => {
+                (&a.aad_prefix, &a.aad_file_unique, a.supply_aad_prefix, true)
+            }
+        };
+
+        let aad_prefix = match (stored_prefix, &properties.aad_prefix) {
+            (Some(stored), Some(supplied)) if stored != supplied => {
+                return Err(general_err!(
+                    "AAD prefix of the file does not match the supplied AAD prefix"
+                ))
+            }
+            (_, Some(supplied)) => supplied.as_slice(),
+            (Some(stored), None) => stored.as_slice(),
+            (None, None) if supply_prefix == Some(true) => {
+                return Err(general_err!(
+                    "Parquet file was encrypted with an AAD prefix that is not stored in the file, \
+                     but no AAD prefix was supplied"
+                ))
+            }
+            (None, None) => &[],
+        };
+        let file_unique = file_unique.as_deref().unwrap_or_default();
+
+        let footer_key = match (&properties.footer_key, &properties.key_retriever) {
+            (Some(key), _) => key.clone(),
+            (None, Some(retriever)) => {
+                let key_metadata = footer_key_metadata.ok_or_else(|| {
+                    general_err!(
+                        "Parquet file has no footer key metadata to retrieve the footer key"
+                    )
+                })?;
+                retrieve_key(retriever.0.as_ref(), key_metadata)?
+            }
+            (None, None) => unreachable!("footer key or key retriever is always set"),
+        };
+
+        Ok(Self {
+            properties: properties.clone(),
+            file_aad: [aad_prefix, file_unique].concat(),
+            ctr_pages,
+            footer_decryptor: Arc::new(AesGcmDecryptor::new(&footer_key)?),
+            footer_key,
+            retrieved_keys: Default::default(),
+        })
+    }
+
+    /// Decrypts the encrypted footer `ciphertext`, returning the serialized `FileMetaData`
+    pub(crate) fn decrypt_footer(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let aad = create_footer_aad(&self.file_aad);
+        self.footer_decryptor.decrypt(ciphertext, &aad)
+    }
+
+    /// Verifies the signature of the plaintext footer `footer` of a file
+    pub(crate) fn verify_footer_signature(
+        &self,
+        footer: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        let aad = create_footer_aad(&self.file_aad);
+        self.footer_decryptor
+            .verify_signature(footer, signature, &aad)
+            .map_err(|e| {
+                general_err!("Failed to verify the plaintext footer of the file: {}", e)
+            })
+    }
+
+    /// Decrypts the `encrypted_column_metadata` of the column chunk `column_ordinal`
+    /// of the row group `row_group_ordinal`, returning the serialized `ColumnMetaData`
+    pub(crate) fn decrypt_column_metadata(
+        &self,
+        crypto_metadata: &ColumnCryptoMetaData,
+        ciphertext: &[u8],
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+    ) -> Result<Vec<u8>> {
+        let aad = create_module_aad(
+            &self.file_aad,
+            ModuleType::ColumnMetaData,
+            row_group_ordinal,
+            column_ordinal,
+            None,
+        )?;
+        self.metadata_decryptor(crypto_metadata)?
+            .decrypt(ciphertext, &aad)
+    }
+
+    /// Returns the decryptor for the metadata modules of a column chunk
+    fn metadata_decryptor(
+        &self,
+        crypto_metadata: &ColumnCryptoMetaData,
+    ) -> Result<Arc<AesGcmDecryptor>> {
+        match self.column_key(crypto_metadata)? {
+            None => Ok(self.footer_decryptor.clone()),
+            Some(key) => Ok(Arc::new(AesGcmDecryptor::new(&key)?)),
+        }
+    }
+
+    /// Returns the key of a column chunk, or `None` if encrypted with the footer key
+    fn column_key(
+        &self,
+        crypto_metadata: &ColumnCryptoMetaData,
+    ) -> Result<Option<Vec<u8>>> {
+        let column = match crypto_metadata {
+            ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(_) => return Ok(None),
+            ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(c) => c,
+        };
+        let path = ColumnPath::new(column.path_in_schema.clone());
+        if let Some(key) = self.properties.column_keys.get(&path) {
+            return Ok(Some(key.clone()));
+        }
+        match (&self.properties.key_retriever, &column.key_metadata) {
+            (Some(retriever), Some(key_metadata)) => {
+                let retrieved = self
+                    .retrieved_keys
+                    .lock()
+                    .unwrap()
+                    .get(key_metadata)
+                    .cloned();
+                if let Some(key) = retrieved {
+                    return Ok(Some(key));
+                }
+                // Don't hold the lock while retrieving the key, which may be a remote call
+                let key = retrieve_key(retriever.0.as_ref(), key_metadata)?;
+                self.retrieved_keys
+                    .lock()
+                    .unwrap()
+                    .insert(key_metadata.clone(), key.clone());
+                Ok(Some(key))
+            }
+            _ => Err(general_err!(
+                "No decryption key provided for encrypted column {}",
+                path.string()
+            )),
+        }
+    }
+}
+
+/// Retrieves the key identified by `key_metadata` with `retriever`, returning an
+/// error if it is not a valid key
+fn retrieve_key(retriever: &dyn KeyRetriever, key_metadata: &[u8]) -> Result<Vec<u8>> {
+    let key = retriever.retrieve_key(key_metadata)?;
+    validate_key(&key)?;
+    Ok(key)
+}
+
+/// Decrypts the pages of an encrypted column chunk, which must be read in order
+#[derive(Debug)]
+pub(crate) struct CryptoContext {
+    row_group_ordinal: usize,
+    column_ordinal: usize,
+    file_aad: Vec<u8>,
+    metadata_decryptor: Arc<dyn BlockDecryptor>,
+    data_decryptor: Arc<dyn BlockDecryptor>,
+    /// The ordinal of the next data page
+    page_ordinal: usize,
+    /// Whether the next page is the dictionary page
+    dictionary_page: bool,
+}
+
+impl CryptoContext {
+    /// Create a new [`CryptoContext`] for the column chunk `column_ordinal` of the row
+    /// group `row_group_ordinal`, whose first page is a dictionary page if
+    /// `has_dictionary_page` is true
+    pub(crate) fn new(
+        file_decryptor: &FileDecryptor,
+        crypto_metadata: &ColumnCryptoMetaData,
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+        has_dictionary_page: bool,
+    ) -> Result<Self> {
+        let metadata_decryptor = file_decryptor.metadata_decryptor(crypto_metadata)?;
+        let data_decryptor: Arc<dyn BlockDecryptor> = match file_decryptor.ctr_pages {
+            true => {
+                let key = file_decryptor
+                    .column_key(crypto_metadata)?
+                    .unwrap_or_else(|| file_decryptor.footer_key.clone());
+                Arc::new(AesCtrDecryptor::new(&key)?)
+            }
+            false => metadata_decryptor.clone(),
+        };
+        Ok(Self {
+            row_group_ordinal,
+            column_ordinal,
+            file_aad: file_decryptor.file_aad.clone(),
+            metadata_decryptor,
+            data_decryptor,
+            page_ordinal: 0,
+            dictionary_page: has_dictionary_page,
+        })
+    }
+
+    /// Returns the AAD of a module of the next page
+    fn page_aad(
+        &self,
+        data_type: ModuleType,
+        dictionary_type: ModuleType,
+    ) -> Result<Vec<u8>> {
+        let (module_type, page_ordinal) = match self.dictionary_page {
+            true => (dictionary_type, None),
+            false => (data_type, Some(self.page_ordinal)),
+        };
+        create_module_aad(
+            &self.file_aad,
+            module_type,
+            self.row_group_ordinal,
+            self.column_ordinal,
+            page_ordinal,
+        )
+    }
+
+    /// Reads and decrypts the header of the next page from `input`, returning the
+    /// number of bytes read
+    pub(crate) fn read_page_header<T: Read>(
+        &self,
+        input: &mut T,
+    ) -> Result<(usize, PageHeader)> {
+        let mut len = [0_u8; SIZE_LEN];
+        input.read_exact(&mut len)?;
+        let mut ciphertext = len.to_vec();
+        let len = u32::from_le_bytes(len) as usize;
+        let read = input.take(len as u64).read_to_end(&mut ciphertext)?;
+        if read != len {
+            return Err(eof_err!(
+                "Expected to read {} bytes of encrypted page header, read only {}",
+                len,
+                read
+            ));
+        }
+
+        let aad =
+            self.page_aad(ModuleType::DataPageHeader, ModuleType::DictionaryPageHeader)?;
+        let plaintext = self.metadata_decryptor.decrypt(&ciphertext, &aad)?;
+        let mut prot = TCompactInputProtocol::new(plaintext.as_slice());
+        let header = PageHeader::read_from_in_protocol(&mut prot)?;
+        Ok((ciphertext.len(), header))
+    }
+
+    /// Decrypts the data of the next page, whose header is `header`, and advances
+    /// to the following page
+    pub(crate) fn decrypt_page(
+        &mut self,
+        header: &PageHeader,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>> {
+        let aad = self.page_aad(ModuleType::DataPage, ModuleType::DictionaryPage)?;
+        let plaintext = self.data_decryptor.decrypt(ciphertext, &aad)?;
+        self.advance_page(header.type_ == PageType::DICTIONARY_PAGE);
+        Ok(plaintext)
+    }
+
+    /// Advances past the next page, which is the dictionary page if `is_dictionary`
+    pub(crate) fn advance_page(&mut self, is_dictionary: bool) {
+        match is_dictionary {
+            true => self.dictionary_page = false,
+            false => self.page_ordinal += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::encryption::ciphers::tests::{encrypt_ctr, encrypt_gcm};
+    use crate::encryption::ciphers::TAG_LEN;
+    use crate::encryption::kms::tests::TestKmsClient;
+    use crate::encryption::kms::KmsKeyRetriever;
+    use crate::format::{
+        AesGcmCtrV1, AesGcmV1, DataPageHeader, Encoding, EncryptionWithColumnKey,
+        FileCryptoMetaData, FileMetaData,
+    };
+    use thrift::protocol::TCompactOutputProtocol;
+
+    const NONCE: [u8; 12] = [1; 12];
+
+    fn serialize<T: TSerializable>(value: &T) -> Vec<u8> {
+        let mut buf = vec![];
+        let mut prot = TCompactOutputProtocol::new(&mut buf);
+        value.write_to_out_protocol(&mut prot).unwrap();
+        buf
+    }
+
+    /// Encrypts the parquet file `data` with `footer_key`, and the keys of `column_keys`
+    /// for the columns they contain, for testing reading encrypted files
+    ///
+    /// The column chunks are rewritten without their page index and bloom filters
+    pub(crate) fn encrypt_file(
+        data: &[u8],
+        footer_key: &[u8],
+        column_keys: &[(&str, &[u8])],
+        encrypted_footer: bool,
+        ctr: bool,
+    ) -> Vec<u8> {
+        let footer_len = data.len() - 8;
+        let metadata_len =
+            u32::from_le_bytes(data[footer_len..footer_len + 4].try_into().unwrap());
+        let mut metadata = &data[footer_len - metadata_len as usize..footer_len];
+        let mut prot = TCompactInputProtocol::new(&mut metadata);
+        let mut file_metadata = FileMetaData::read_from_in_protocol(&mut prot).unwrap();
+
+        let file_aad = b"file_unique".to_vec();
+        let algorithm = match ctr {
+            true => EncryptionAlgorithm::AESGCMCTRV1(AesGcmCtrV1::new(
+                None,
+                file_aad.clone(),
+                None,
+            )),
+            false => {
+                EncryptionAlgorithm::AESGCMV1(AesGcmV1::new(None, file_aad.clone(), None))
+            }
+        };
+
+        let mut out = b"PAR1".to_vec();
+        for (rg_idx, rg) in file_metadata.row_groups.iter_mut().enumerate() {
+            rg.ordinal = Some(rg_idx as i16);
+            rg.file_offset = None;
+            rg.total_compressed_size = None;
+
+            for (col_idx, cc) in rg.columns.iter_mut().enumerate() {
+                let mut meta = cc.meta_data.take().unwrap();
+                let path = meta.path_in_schema.join(".");
+                let column_key = column_keys
+                    .iter()
+                    .find(|(column, _)| *column == path)
+                    .map(|(_, key)| *key);
+                let key = column_key.unwrap_or(footer_key);
+
+                let start = meta.dictionary_page_offset.unwrap_or(meta.data_page_offset);
+                let end = start + meta.total_compressed_size;
+                let mut input = &data[start as usize..end as usize];
+                let chunk_start = out.len() as i64;
+                let mut data_page_offset = None;
+                let mut page_ordinal = 0;
+
+                while !input.is_empty() {
+                    let mut prot = TCompactInputProtocol::new(&mut input);
+                    let mut header =
+                        PageHeader::read_from_in_protocol(&mut prot).unwrap();
+                    let (page, rest) =
+                        input.split_at(header.compressed_page_size as usize);
+                    input = rest;
+
+                    let (page_type, header_type, ordinal) = match header.type_ {
+                        PageType::DICTIONARY_PAGE => (
+                            ModuleType::DictionaryPage,
+                            ModuleType::DictionaryPageHeader,
+                            None,
+                        ),
+                        _ => {
+                            data_page_offset.get_or_insert(out.len() as i64);
+                            page_ordinal += 1;
+                            (
+                                ModuleType::DataPage,
+                                ModuleType::DataPageHeader,
+                                Some(page_ordinal - 1),
+                            )
+                        }
+                    };
+
+                    let aad =
+                        create_module_aad(&file_aad, page_type, rg_idx, col_idx, ordinal)
+                            .unwrap();
+                    let page = match ctr {
+                        true => encrypt_ctr(key, &NONCE, page),
+                        false => encrypt_gcm(key, &NONCE, page, &aad),
+                    };
+                    header.compressed_page_size = page.len() as i32;
+
+                    let aad = create_module_aad(
+                        &file_aad,
+                        header_type,
+                        rg_idx,
+                        col_idx,
+                        ordinal,
+                    )
+                    .unwrap();
+                    out.extend(encrypt_gcm(key, &NONCE, &serialize(&header), &aad));
+                    out.extend(page);
+                }
+
+                meta.dictionary_page_offset =
+                    meta.dictionary_page_offset.map(|_| chunk_start);
+                meta.data_page_offset = data_page_offset.unwrap();
+                meta.total_compressed_size = out.len() as i64 - chunk_start;
+                meta.bloom_filter_offset = None;
+                meta.bloom_filter_length = None;
+                cc.file_offset = chunk_start;
+                cc.offset_index_offset = None;
+                cc.offset_index_length = None;
+                cc.column_index_offset = None;
+                cc.column_index_length = None;
+
+                cc.crypto_metadata = Some(match column_key {
+                    Some(_) => ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(
+                        EncryptionWithColumnKey::new(meta.path_in_schema.clone(), None),
+                    ),
+                    None => {
+                        ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(Default::default())
+                    }
+                });
+                if column_key.is_some() || !encrypted_footer {
+                    let aad = create_module_aad(
+                        &file_aad,
+                        ModuleType::ColumnMetaData,
+                        rg_idx,
+                        col_idx,
+                        None,
+                    )
+                    .unwrap();
+                    cc.encrypted_column_metadata =
+                        Some(encrypt_gcm(key, &NONCE, &serialize(&meta), &aad));
+                }
+                if column_key.is_none() || !encrypted_footer {
+                    cc.meta_data = Some(meta);
+                }
+            }
+        }
+
+        let metadata_start = out.len();
+        let aad = create_footer_aad(&file_aad);
+        if encrypted_footer {
+            out.extend(serialize(&FileCryptoMetaData::new(algorithm, None)));
+            let footer = serialize(&file_metadata);
+            out.extend(encrypt_gcm(footer_key, &NONCE, &footer, &aad));
+        } else {
+            file_metadata.encryption_algorithm = Some(algorithm);
+            let footer = serialize(&file_metadata);
+            let module = encrypt_gcm(footer_key, &NONCE, &footer, &aad);
+            out.extend(&footer);
+            out.extend(NONCE);
+            out.extend(&module[module.len() - TAG_LEN..]);
+        }
+        let metadata_len = (out.len() - metadata_start) as u32;
+        out.extend(metadata_len.to_le_bytes());
+        match encrypted_footer {
+            true => out.extend(b"PARE"),
+            false => out.extend(b"PAR1"),
+        }
+        out
+    }
+
+    fn gcm_algorithm(prefix: Option<&[u8]>, supply: bool) -> EncryptionAlgorithm {
+        EncryptionAlgorithm::AESGCMV1(AesGcmV1 {
+            aad_prefix: prefix.map(|p| p.to_vec()),
+            aad_file_unique: Some(b"unique".to_vec()),
+            supply_aad_prefix: Some(supply),
+        })
+    }
+
+    #[test]
+    fn test_properties() {
+        let err = FileDecryptionProperties::builder(vec![0; 10])
+            .build()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid AES key length 10"),
+            "{}",
+            err
+        );
+
+        let err = FileDecryptionProperties::builder(vec![0; 16])
+            .with_column_key(ColumnPath::from("a"), vec![0; 8])
+            .build()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid AES key length 8"),
+            "{}",
+            err
+        );
+
+        let properties = FileDecryptionProperties::builder(vec![0; 16])
+            .with_column_key(ColumnPath::from("a"), vec![1; 32])
+            .with_aad_prefix(b"prefix".to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(properties.aad_prefix(), Some(b"prefix".as_slice()));
+
+        // The keys are not printed
+        let debug = format!("{:?}", properties);
+        assert!(!debug.contains("footer_key"), "{}", debug);
+        assert_eq!(properties, properties.clone());
+
+        // Key retrievers are compared by identity
+        let retriever =
+            || Arc::new(KmsKeyRetriever::new(Arc::new(TestKmsClient::default())));
+        let shared: Arc<dyn KeyRetriever> = retriever();
+        let a = FileDecryptionProperties::with_key_retriever(shared.clone())
+            .build()
+            .unwrap();
+        let b = FileDecryptionProperties::with_key_retriever(shared)
+            .build()
+            .unwrap();
+        let c = FileDecryptionProperties::with_key_retriever(retriever())
+            .build()
+            .unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, properties);
+    }
+
+    #[test]
+    fn test_file_aad() {
+        let properties = FileDecryptionProperties::builder(vec![0; 16])
+            .build()
+            .unwrap();
+        let decryptor =
+            FileDecryptor::new(&properties, &gcm_algorithm(Some(b"stored"), false), None)
+                .unwrap();
+        assert_eq!(decryptor.file_aad, b"storedunique");
+
+        let decryptor =
+            FileDecryptor::new(&properties, &gcm_algorithm(None, false), None).unwrap();
+        assert_eq!(decryptor.file_aad, b"unique");
+
+        let err = FileDecryptor::new(&properties, &gcm_algorithm(None, true), None)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("no AAD prefix was supplied"),
+            "{}",
+            err
+        );
+
+        let properties = FileDecryptionProperties::builder(vec![0; 16])
+            .with_aad_prefix(b"supplied".to_vec())
+            .build()
+            .unwrap();
+        let decryptor =
+            FileDecryptor::new(&properties, &gcm_algorithm(None, true), None).unwrap();
+        assert_eq!(decryptor.file_aad, b"suppliedunique");
+
+        let err =
+            FileDecryptor::new(&properties, &gcm_algorithm(Some(b"stored"), false), None)
+                .unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{}", err);
+    }
+
+    #[test]
+    fn test_column_keys() {
+        let footer_key = vec![1; 16];
+        let column_key = vec![2; 16];
+        let properties = FileDecryptionProperties::builder(footer_key.clone())
+            .with_column_key(ColumnPath::from("a"), column_key.clone())
+            .build()
+            .unwrap();
+        let decryptor =
+            FileDecryptor::new(&properties, &gcm_algorithm(None, false), None).unwrap();
+
+        let footer_aad = create_footer_aad(b"unique");
+        let footer = encrypt_gcm(&footer_key, &[0; 12], b"footer", &footer_aad);
+        assert_eq!(decryptor.decrypt_footer(&footer).unwrap(), b"footer");
+
+        let aad =
+            create_module_aad(b"unique", ModuleType::ColumnMetaData, 1, 2, None).unwrap();
+        let column = ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(
+            EncryptionWithColumnKey::new(vec!["a".to_string()], None),
+        );
+        let metadata = encrypt_gcm(&column_key, &[0; 12], b"metadata", &aad);
+        let decrypted = decryptor
+            .decrypt_column_metadata(&column, &metadata, 1, 2)
+            .unwrap();
+        assert_eq!(decrypted, b"metadata");
+
+        // The wrong ordinals result in the wrong AAD
+        decryptor
+            .decrypt_column_metadata(&column, &metadata, 2, 1)
+            .unwrap_err();
+
+        let missing = ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(
+            EncryptionWithColumnKey::new(vec!["b".to_string(), "c".to_string()], None),
+        );
+        let err = decryptor
+            .decrypt_column_metadata(&missing, &metadata, 1, 2)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: No decryption key provided for encrypted column b.c"
+        );
+    }
+
+    #[test]
+    fn test_page_ordinals() {
+        let key = vec![3; 16];
+        let properties = FileDecryptionProperties::builder(key.clone())
+            .build()
+            .unwrap();
+        let decryptor =
+            FileDecryptor::new(&properties, &gcm_algorithm(None, false), None).unwrap();
+        let column = ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(Default::default());
+        let mut context = CryptoContext::new(&decryptor, &column, 0, 1, false).unwrap();
+
+        let header = PageHeader {
+            type_: PageType::DATA_PAGE,
+            uncompressed_page_size: 4,
+            compressed_page_size: 4,
+            crc: None,
+            data_page_header: Some(DataPageHeader::new(
+                1,
+                Encoding::PLAIN,
+                Encoding::RLE,
+                Encoding::RLE,
+                None,
+            )),
+            index_page_header: None,
+            dictionary_page_header: None,
+            data_page_header_v2: None,
+        };
+        let mut serialized = vec![];
+        header
+            .write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut serialized))
+            .unwrap();
+
+        // Encrypt the second data page of the column chunk
+        let header_aad =
+            create_module_aad(b"unique", ModuleType::DataPageHeader, 0, 1, Some(1))
+                .unwrap();
+        let encrypted_header = encrypt_gcm(&key, &[4; 12], &serialized, &header_aad);
+        let page_aad =
+            create_module_aad(b"unique", ModuleType::DataPage, 0, 1, Some(1)).unwrap();
+        let encrypted_page = encrypt_gcm(&key, &[5; 12], b"page", &page_aad);
+
+        context
+            .read_page_header(&mut encrypted_header.as_slice())
+            .unwrap_err();
+        context.advance_page(false);
+
+        let (len, read) = context
+            .read_page_header(&mut encrypted_header.as_slice())
+            .unwrap();
+        assert_eq!(len, encrypted_header.len());
+        assert_eq!(read, header);
+        assert_eq!(
+            context.decrypt_page(&read, &encrypted_page).unwrap(),
+            b"page"
+        );
+
+        // The context has moved on to the third page
+        context
+            .read_page_header(&mut encrypted_header.as_slice())
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,723 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Configuration and state for writing encrypted parquet files
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::io::Write;
+use std::sync::Arc;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use thrift::protocol::{TCompactOutputProtocol, TOutputProtocol, TSerializable};
+
+use crate::encryption::ciphers::{
+    validate_key, AesCtrEncryptor, AesGcmEncryptor, BlockEncryptor,
+};
+use crate::encryption::modules::{
+    create_footer_aad, create_module_aad, to_ordinal, ModuleType,
+};
+use crate::errors::{ParquetError, Result};
+use crate::format::{
+    AesGcmCtrV1, AesGcmV1, ColumnChunk, ColumnCryptoMetaData, EncryptionAlgorithm,
+    EncryptionWithColumnKey, EncryptionWithFooterKey, FileCryptoMetaData, FileMetaData,
+    PageHeader, PageType, RowGroup,
+};
+use crate::schema::types::{ColumnPath, SchemaDescriptor};
+
+/// The length of the random unique identifier of an encrypted file
+const AAD_FILE_UNIQUE_LEN: usize = 8;
+
+/// The algorithm used to encrypt a parquet file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParquetCipher {
+    /// AES-GCM for all modules, which authenticates the entire file
+    #[default]
+    AesGcmV1,
+    /// AES-GCM for all modules but pages, which are encrypted with AES-CTR, which is
+    /// faster but does not authenticate them
+    AesGcmCtrV1,
+}
+
+/// The key and key metadata of an encrypted column
+#[derive(Clone, PartialEq, Eq)]
+struct ColumnKey {
+    key: Vec<u8>,
+    key_metadata: Option<Vec<u8>>,
+}
+
+/// The keys and options used to encrypt a parquet file
+///
+/// If no column keys are provided all columns are encrypted with the footer key,
+/// otherwise only the columns with a key provided by
+/// [`FileEncryptionPropertiesBuilder::with_column_key`] are encrypted
+///
+/// ```
+/// # use parquet::encryption::encrypt::FileEncryptionProperties;
+/// # use parquet::file::properties::WriterProperties;
+/// # use parquet::schema::types::ColumnPath;
+/// let encryption_properties = FileEncryptionProperties::builder(b"0123456789012345".to_vec())
+///     .with_column_key(ColumnPath::from("a"), b"1234567890123450".to_vec())
+///     .build()
+///     .unwrap();
+/// let props = WriterProperties::builder()
+///     .set_file_encryption_properties(encryption_properties)
+///     .build();
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct FileEncryptionProperties {
+    footer_key: Vec<u8>,
+    footer_key_metadata: Option<Vec<u8>>,
+    column_keys: HashMap<ColumnPath, ColumnKey>,
+    aad_prefix: Option<Vec<u8>>,
+    store_aad_prefix: bool,
+    encrypted_footer: bool,
+    cipher: ParquetCipher,
+}
+
+impl FileEncryptionProperties {
+    /// Returns a [`FileEncryptionPropertiesBuilder`] with the footer key `footer_key`,
+    /// which must be 16, 24 or 32 bytes long
+    pub fn builder(footer_key: Vec<u8>) -> FileEncryptionPropertiesBuilder {
+        FileEncryptionPropertiesBuilder {
+            footer_key,
+            footer_key_metadata: None,
+            column_keys: HashMap::new(),
+            aad_prefix: None,
+            store_aad_prefix: true,
+            encrypted_footer: true,
+            cipher: ParquetCipher::default(),
+        }
+    }
+
+    /// Returns `true` if the footer is encrypted, otherwise it is written in
+    /// plaintext and signed with the footer key
+    pub fn encrypted_footer(&self) -> bool {
+        self.encrypted_footer
+    }
+
+    /// Returns the algorithm used to encrypt the file
+    pub fn cipher(&self) -> ParquetCipher {
+        self.cipher
+    }
+
+    /// Returns the AAD prefix of the file, if any
+    pub fn aad_prefix(&self) -> Option<&[u8]> {
+        self.aad_prefix.as_deref()
+    }
+
+    /// Returns `true` if the column `column_path` is encrypted
+    pub fn is_column_encrypted(&self, column_path: &ColumnPath) -> bool {
+        self.column_keys.is_empty() || self.column_keys.contains_key(column_path)
+    }
+}
+
+impl Debug for FileEncryptionProperties {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't print the keys
+        f.debug_struct("FileEncryptionProperties")
+            .field("columns", &self.column_keys.keys().collect::<Vec<_>>())
+            .field("aad_prefix", &self.aad_prefix)
+            .field("store_aad_prefix", &self.store_aad_prefix)
+            .field("encrypted_footer", &self.encrypted_footer)
+            .field("cipher", &self.cipher)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A builder for [`FileEncryptionProperties`]
+pub struct FileEncryptionPropertiesBuilder {
+    footer_key: Vec<u8>,
+    footer_key_metadata: Option<Vec<u8>>,
+    column_keys: HashMap<ColumnPath, ColumnKey>,
+    aad_prefix: Option<Vec<u8>>,
+    store_aad_prefix: bool,
+    encrypted_footer: bool,
+    cipher: ParquetCipher,
+}
+
+impl FileEncryptionPropertiesBuilder {
+    /// Sets the metadata stored with the footer, which readers can use to retrieve
+    /// the footer key
+    pub fn with_footer_key_metadata(mut self, key_metadata: Vec<u8>) -> Self {
+        self.footer_key_metadata = Some(key_metadata);
+        self
+    }
+
+    /// Encrypts the column `column_path` with `key`, which must be 16, 24 or 32 bytes
+    /// long
+    pub fn with_column_key(mut self, column_path: ColumnPath, key: Vec<u8>) -> Self {
+        let column_key = ColumnKey {
+            key,
+            key_metadata: None,
+        };
+        self.column_keys.insert(column_path, column_key);
+        self
+    }
+
+    /// Encrypts the column `column_path` with `key`, storing `key_metadata` in the
+    /// column metadata for readers to retrieve the key with
+    pub fn with_column_key_and_metadata(
+        mut self,
+        column_path: ColumnPath,
+        key: Vec<u8>,
+        key_metadata: Vec<u8>,
+    ) -> Self {
+        let column_key = ColumnKey {
+            key,
+            key_metadata: Some(key_metadata),
+        };
+        self.column_keys.insert(column_path, column_key);
+        self
+    }
+
+    /// Sets the AAD prefix of the file, which identifies the file to protect against
+    /// its modules being swapped with those of other files
+    pub fn with_aad_prefix(mut self, aad_prefix: Vec<u8>) -> Self {
+        self.aad_prefix = Some(aad_prefix);
+        self
+    }
+
+    /// Sets whether the AAD prefix is stored in the file (defaults to `true`),
+    /// otherwise readers must supply it to decrypt the file
+    pub fn with_aad_prefix_storage(mut self, store_aad_prefix: bool) -> Self {
+        self.store_aad_prefix = store_aad_prefix;
+        self
+    }
+
+    /// Sets whether the footer is written in plaintext (defaults to `false`), which
+    /// allows readers without the keys to read the unencrypted columns of the file
+    ///
+    /// A plaintext footer is signed with the footer key to detect tampering
+    pub fn with_plaintext_footer(mut self, plaintext_footer: bool) -> Self {
+        self.encrypted_footer = !plaintext_footer;
+        self
+    }
+
+    /// Sets the algorithm used to encrypt the file (defaults to
+    /// [`ParquetCipher::AesGcmV1`])
+    pub fn with_cipher(mut self, cipher: ParquetCipher) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Returns the [`FileEncryptionProperties`], or an error if any key is invalid
+    pub fn build(self) -> Result<FileEncryptionProperties> {
+        validate_key(&self.footer_key)?;
+        for column_key in self.column_keys.values() {
+            validate_key(&column_key.key)?;
+        }
+        Ok(FileEncryptionProperties {
+            footer_key: self.footer_key,
+            footer_key_metadata: self.footer_key_metadata,
+            column_keys: self.column_keys,
+            aad_prefix: self.aad_prefix,
+            store_aad_prefix: self.store_aad_prefix,
+            encrypted_footer: self.encrypted_footer,
+            cipher: self.cipher,
+        })
+    }
+}
+
+/// Encrypts the footer and column chunks of a parquet file with the keys of
+/// [`FileEncryptionProperties`]
+#[derive(Debug)]
+pub(crate) struct FileEncryptor {
+    properties: FileEncryptionProperties,
+    /// The random unique identifier of the file
+    aad_file_unique: Vec<u8>,
+    /// The AAD prefix followed by the unique file identifier
+    file_aad: Vec<u8>,
+    footer_encryptor: Arc<AesGcmEncryptor>,
+}
+
+impl FileEncryptor {
+    /// Create a new [`FileEncryptor`] for a file with the schema `schema`
+    pub(crate) fn new(
+        properties: FileEncryptionProperties,
+        schema: &SchemaDescriptor,
+    ) -> Result<Self> {
+        for column_path in properties.column_keys.keys() {
+            if !schema.columns().iter().any(|c| c.path() == column_path) {
+                return Err(general_err!(
+                    "Encryption key provided for column {}, which is not in the schema",
+                    column_path.string()
+                ));
+            }
+        }
+
+        let mut aad_file_unique = vec![0; AAD_FILE_UNIQUE_LEN];
+        OsRng.fill_bytes(&mut aad_file_unique);
+        let aad_prefix = properties.aad_prefix.as_deref().unwrap_or_default();
+        let file_aad = [aad_prefix, &aad_file_unique].concat();
+        let footer_encryptor = Arc::new(AesGcmEncryptor::new(&properties.footer_key)?);
+
+        Ok(Self {
+            properties,
+            aad_file_unique,
+            file_aad,
+            footer_encryptor,
+        })
+    }
+
+    /// Returns `true` if the footer of the file is encrypted
+    pub(crate) fn encrypted_footer(&self) -> bool {
+        self.properties.encrypted_footer
+    }
+
+    /// Returns `true` if the column `column_path` is encrypted
+    pub(crate) fn is_column_encrypted(&self, column_path: &ColumnPath) -> bool {
+        self.properties.is_column_encrypted(column_path)
+    }
+
+    /// Returns the algorithm stored in the metadata of the file
+    fn encryption_algorithm(&self) -> EncryptionAlgorithm {
+        let supply_aad_prefix = self
+            .properties
+            .aad_prefix
+            .as_ref()
+            .map(|_| !self.properties.store_aad_prefix);
+        let aad_prefix = match self.properties.store_aad_prefix {
+            true => self.properties.aad_prefix.clone(),
+            false => None,
+        };
+        let aad_file_unique = Some(self.aad_file_unique.clone());
+        match self.properties.cipher {
+            ParquetCipher::AesGcmV1 => EncryptionAlgorithm::AESGCMV1(AesGcmV1::new(
+                aad_prefix,
+                aad_file_unique,
+                supply_aad_prefix,
+            )),
+            ParquetCipher::AesGcmCtrV1 => EncryptionAlgorithm::AESGCMCTRV1(
+                AesGcmCtrV1::new(aad_prefix, aad_file_unique, supply_aad_prefix),
+            ),
+        }
+    }
+
+    /// Returns the key of the column `column_path`, which must be encrypted
+    fn column_key(&self, column_path: &ColumnPath) -> &[u8] {
+        match self.properties.column_keys.get(column_path) {
+            Some(column_key) => &column_key.key,
+            None => &self.properties.footer_key,
+        }
+    }
+
+    /// Returns the encryptor for the metadata modules of the column `column_path`
+    fn metadata_encryptor(
+        &self,
+        column_path: &ColumnPath,
+    ) -> Result<Arc<AesGcmEncryptor>> {
+        match self.properties.column_keys.get(column_path) {
+            Some(column_key) => Ok(Arc::new(AesGcmEncryptor::new(&column_key.key)?)),
+            None => Ok(self.footer_encryptor.clone()),
+        }
+    }
+
+    /// Returns the [`PageEncryptor`] for the column chunk `column_ordinal` of the row
+    /// group `row_group_ordinal`, or `None` if the column `column_path` is not encrypted
+    pub(crate) fn page_encryptor(
+        &self,
+        column_path: &ColumnPath,
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+    ) -> Result<Option<PageEncryptor>> {
+        if !self.is_column_encrypted(column_path) {
+            return Ok(None);
+        }
+        let metadata_encryptor = self.metadata_encryptor(column_path)?;
+        let data_encryptor: Arc<dyn BlockEncryptor> = match self.properties.cipher {
+            ParquetCipher::AesGcmV1 => metadata_encryptor.clone(),
+            ParquetCipher::AesGcmCtrV1 => {
+                Arc::new(AesCtrEncryptor::new(self.column_key(column_path))?)
+            }
+        };
+        Ok(Some(PageEncryptor {
+            row_group_ordinal,
+            column_ordinal,
+            file_aad: self.file_aad.clone(),
+            metadata_encryptor,
+            data_encryptor,
+            page_ordinal: 0,
+        }))
+    }
+
+    /// Sets the ordinal of each of `row_groups`, and the crypto metadata of their
+    /// encrypted column chunks, encrypting their column metadata as required
+    pub(crate) fn encrypt_row_groups(&self, row_groups: &mut [RowGroup]) -> Result<()> {
+        for (row_group_ordinal, row_group) in row_groups.iter_mut().enumerate() {
+            row_group.ordinal = Some(to_ordinal(row_group_ordinal, "row group")?);
+            for (column_ordinal, column) in row_group.columns.iter_mut().enumerate() {
+                self.encrypt_column_chunk(column, row_group_ordinal, column_ordinal)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the crypto metadata of `column` if it is encrypted
+    ///
+    /// The column metadata of columns encrypted with a column key is encrypted, as it
+    /// is for all encrypted columns of a file with a plaintext footer, which keep a
+    /// copy of their column metadata without statistics in plaintext
+    fn encrypt_column_chunk(
+        &self,
+        column: &mut ColumnChunk,
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+    ) -> Result<()> {
+        let meta_data = match column.meta_data.as_mut() {
+            Some(meta_data) => meta_data,
+            None => return Ok(()),
+        };
+        let column_path = ColumnPath::new(meta_data.path_in_schema.clone());
+        if !self.is_column_encrypted(&column_path) {
+            return Ok(());
+        }
+
+        let column_key = self.properties.column_keys.get(&column_path);
+        column.crypto_metadata = Some(match column_key {
+            Some(column_key) => ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(
+                EncryptionWithColumnKey::new(
+                    meta_data.path_in_schema.clone(),
+                    column_key.key_metadata.clone(),
+                ),
+            ),
+            None => ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(
+                EncryptionWithFooterKey::new(),
+            ),
+        });
+
+        if column_key.is_none() && self.encrypted_footer() {
+            // The column metadata is encrypted with the footer
+            return Ok(());
+        }
+
+        let aad = create_module_aad(
+            &self.file_aad,
+            ModuleType::ColumnMetaData,
+            row_group_ordinal,
+            column_ordinal,
+            None,
+        )?;
+        let plaintext = serialize(&*meta_data)?;
+        column.encrypted_column_metadata = Some(
+            self.metadata_encryptor(&column_path)?
+                .encrypt(&plaintext, &aad),
+        );
+
+        match self.encrypted_footer() {
+            true => column.meta_data = None,
+            false => meta_data.statistics = None,
+        }
+        Ok(())
+    }
+
+    /// Writes `file_metadata` to `sink`, encrypted or signed with the footer key
+    ///
+    /// The encryption algorithm of a file with a plaintext footer is stored in
+    /// `file_metadata`, which is otherwise preceded by the crypto metadata of the file
+    pub(crate) fn write_file_metadata<W: Write>(
+        &self,
+        file_metadata: &mut FileMetaData,
+        sink: &mut W,
+    ) -> Result<()> {
+        let aad = create_footer_aad(&self.file_aad);
+        let footer_key_metadata = self.properties.footer_key_metadata.clone();
+        if self.encrypted_footer() {
+            let crypto_metadata =
+                FileCryptoMetaData::new(self.encryption_algorithm(), footer_key_metadata);
+            sink.write_all(&serialize(&crypto_metadata)?)?;
+            let footer = serialize(&*file_metadata)?;
+            sink.write_all(&self.footer_encryptor.encrypt(&footer, &aad))?;
+        } else {
+            file_metadata.encryption_algorithm = Some(self.encryption_algorithm());
+            file_metadata.footer_signing_key_metadata = footer_key_metadata;
+            let footer = serialize(&*file_metadata)?;
+            sink.write_all(&footer)?;
+            sink.write_all(&self.footer_encryptor.sign(&footer, &aad))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the thrift compact serialization of `value`
+fn serialize<T: TSerializable>(value: &T) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut protocol = TCompactOutputProtocol::new(&mut buf);
+    value.write_to_out_protocol(&mut protocol)?;
+    protocol.flush()?;
+    Ok(buf)
+}
+
+/// Encrypts the pages of an encrypted column chunk, which must be written in order
+#[derive(Debug)]
+pub(crate) struct PageEncryptor {
+    row_group_ordinal: usize,
+    column_ordinal: usize,
+    file_aad: Vec<u8>,
+    metadata_encryptor: Arc<dyn BlockEncryptor>,
+    data_encryptor: Arc<dyn BlockEncryptor>,
+    /// The ordinal of the next data page
+    page_ordinal: usize,
+}
+
+impl PageEncryptor {
+    /// Returns the AAD of a module of the page with the header `header`
+    fn page_aad(
+        &self,
+        header: &PageHeader,
+        data_type: ModuleType,
+        dictionary_type: ModuleType,
+    ) -> Result<Vec<u8>> {
+        let (module_type, page_ordinal) = match header.type_ {
+            PageType::DICTIONARY_PAGE => (dictionary_type, None),
+            _ => (data_type, Some(self.page_ordinal)),
+        };
+        create_module_aad(
+            &self.file_aad,
+            module_type,
+            self.row_group_ordinal,
+            self.column_ordinal,
+            page_ordinal,
+        )
+    }
+
+    /// Encrypts the data of the next page, whose header is `header`
+    ///
+    /// The page data must be encrypted before its header, which contains the size of
+    /// the encrypted data
+    pub(crate) fn encrypt_page(
+        &self,
+        header: &PageHeader,
+        data: &[u8],
+    ) -> Result<Vec<u8>> {
+        let aad =
+            self.page_aad(header, ModuleType::DataPage, ModuleType::DictionaryPage)?;
+        Ok(self.data_encryptor.encrypt(data, &aad))
+    }
+
+    /// Encrypts the header of the next page, and advances to the following page
+    pub(crate) fn encrypt_page_header(&mut self, header: &PageHeader) -> Result<Vec<u8>> {
+        let aad = self.page_aad(
+            header,
+            ModuleType::DataPageHeader,
+            ModuleType::DictionaryPageHeader,
+        )?;
+        let encrypted = self.metadata_encryptor.encrypt(&serialize(header)?, &aad);
+        if header.type_ != PageType::DICTIONARY_PAGE {
+            self.page_ordinal += 1;
+        }
+        Ok(encrypted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::basic::Type;
+    use crate::encryption::decrypt::{
+        CryptoContext, FileDecryptionProperties, FileDecryptor,
+    };
+    use crate::format::{DataPageHeader, DictionaryPageHeader, Encoding};
+    use crate::schema::types::Type as SchemaType;
+
+    fn schema() -> SchemaDescriptor {
+        let fields = ["a", "b"]
+            .iter()
+            .map(|name| {
+                Arc::new(
+                    SchemaType::primitive_type_builder(name, Type::INT32)
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+        let schema = SchemaType::group_type_builder("schema")
+            .with_fields(&mut { fields })
+            .build()
+            .unwrap();
+        SchemaDescriptor::new(Arc::new(schema))
+    }
+
+    fn page_header(type_: PageType) -> PageHeader {
+        PageHeader {
+            type_,
+            uncompressed_page_size: 4,
+            compressed_page_size: 4,
+            crc: None,
+            data_page_header: (type_ == PageType::DATA_PAGE).then(|| {
+                DataPageHeader::new(
+                    1,
+                    Encoding::PLAIN,
+                    Encoding::RLE,
+                    Encoding::RLE,
+                    None,
+                )
+            }),
+            index_page_header: None,
+            dictionary_page_header: (type_ == PageType::DICTIONARY_PAGE)
+                .then(|| DictionaryPageHeader::new(1, Encoding::PLAIN, None)),
+            data_page_header_v2: None,
+        }
+    }
+
+    #[test]
+    fn test_properties() {
+        let err = FileEncryptionProperties::builder(vec![0; 10])
+            .build()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid AES key length 10"),
+            "{}",
+            err
+        );
+
+        let err = FileEncryptionProperties::builder(vec![0; 16])
+            .with_column_key(ColumnPath::from("a"), vec![0; 8])
+            .build()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid AES key length 8"),
+            "{}",
+            err
+        );
+
+        let properties = FileEncryptionProperties::builder(vec![0; 16])
+            .build()
+            .unwrap();
+        assert!(properties.encrypted_footer());
+        assert_eq!(properties.cipher(), ParquetCipher::AesGcmV1);
+        assert!(properties.is_column_encrypted(&ColumnPath::from("a")));
+
+        let properties = FileEncryptionProperties::builder(vec![0; 16])
+            .with_column_key(ColumnPath::from("a"), vec![1; 32])
+            .with_aad_prefix(b"prefix".to_vec())
+            .with_plaintext_footer(true)
+            .with_cipher(ParquetCipher::AesGcmCtrV1)
+            .build()
+            .unwrap();
+        assert!(!properties.encrypted_footer());
+        assert_eq!(properties.aad_prefix(), Some(b"prefix".as_slice()));
+        assert!(properties.is_column_encrypted(&ColumnPath::from("a")));
+        assert!(!properties.is_column_encrypted(&ColumnPath::from("b")));
+
+        // The keys are not printed
+        let debug = format!("{:?}", properties);
+        assert!(!debug.contains("footer_key"), "{}", debug);
+    }
+
+    #[test]
+    fn test_unknown_column() {
+        let properties = FileEncryptionProperties::builder(vec![0; 16])
+            .with_column_key(ColumnPath::from("c"), vec![1; 16])
+            .build()
+            .unwrap();
+        let err = FileEncryptor::new(properties, &schema()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Encryption key provided for column c, which is not in the schema"
+        );
+    }
+
+    #[test]
+    fn test_encryption_algorithm() {
+        let properties = FileEncryptionProperties::builder(vec![0; 16])
+            .with_aad_prefix(b"prefix".to_vec())
+            .with_aad_prefix_storage(false)
+            .with_cipher(ParquetCipher::AesGcmCtrV1)
+            .build()
+            .unwrap();
+        let encryptor = FileEncryptor::new(properties, &schema()).unwrap();
+        assert_eq!(encryptor.aad_file_unique.len(), AAD_FILE_UNIQUE_LEN);
+        assert_eq!(&encryptor.file_aad[..6], b"prefix");
+
+        match encryptor.encryption_algorithm() {
+            EncryptionAlgorithm::AESGCMCTRV1(a) => {
+                assert_eq!(a.aad_prefix, None);
+                assert_eq!(a.aad_file_unique, Some(encryptor.aad_file_unique.clone()));
+                assert_eq!(a.supply_aad_prefix, Some(true));
+            }
+            a => panic!("unexpected algorithm {:?}", a),
+        }
+
+        // Each file has a different unique identifier
+        let properties = FileEncryptionProperties::builder(vec![0; 16])
+            .build()
+            .unwrap();
+        let other = FileEncryptor::new(properties, &schema()).unwrap();
+        assert_ne!(encryptor.aad_file_unique, other.aad_file_unique);
+    }
+
+    #[test]
+    fn test_page_encryptor() {
+        for cipher in [ParquetCipher::AesGcmV1, ParquetCipher::AesGcmCtrV1] {
+            let properties = FileEncryptionProperties::builder(vec![1; 16])
+                .with_column_key(ColumnPath::from("b"), vec![2; 16])
+                .with_cipher(cipher)
+                .build()
+                .unwrap();
+            let encryptor = FileEncryptor::new(properties, &schema()).unwrap();
+            assert!(encryptor
+                .page_encryptor(&ColumnPath::from("a"), 0, 0)
+                .unwrap()
+                .is_none());
+            let mut page_encryptor = encryptor
+                .page_encryptor(&ColumnPath::from("b"), 2, 1)
+                .unwrap()
+                .unwrap();
+
+            let decryption_properties = FileDecryptionProperties::builder(vec![1; 16])
+                .with_column_key(ColumnPath::from("b"), vec![2; 16])
+                .build()
+                .unwrap();
+            let decryptor = FileDecryptor::new(
+                &decryption_properties,
+                &encryptor.encryption_algorithm(),
+                None,
+            )
+            .unwrap();
+            let crypto_metadata = ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(
+                EncryptionWithColumnKey::new(vec!["b".to_string()], None),
+            );
+            let mut context =
+                CryptoContext::new(&decryptor, &crypto_metadata, 2, 1, true).unwrap();
+
+            for (i, type_) in [
+                PageType::DICTIONARY_PAGE,
+                PageType::DATA_PAGE,
+                PageType::DATA_PAGE,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let header = page_header(type_);
+                let data = vec![i as u8; 4];
+                let encrypted_data = page_encryptor.encrypt_page(&header, &data).unwrap();
+                let encrypted_header =
+                    page_encryptor.encrypt_page_header(&header).unwrap();
+
+                let (len, read) = context
+                    .read_page_header(&mut encrypted_header.as_slice())
+                    .unwrap();
+                assert_eq!(len, encrypted_header.len());
+                assert_eq!(read, header);
+                let decrypted = context.decrypt_page(&read, &encrypted_data).unwrap();
+                assert_eq!(decrypted, data);
+            }
+        }
+    }
+}
@@ -0,0 +1,461 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Envelope encryption of the keys of encrypted parquet files with a key management
+//! service (KMS)
+//!
+//! Each file, or column, is encrypted with a random data encryption key, which is
+//! wrapped (encrypted) with a master key stored in the KMS. The wrapped key and the
+//! identifier of its master key are stored in the key metadata of the file, as
+//! [`KeyMaterial`], so that the data encryption key can later be unwrapped by the
+//! KMS with [`KmsKeyRetriever`]
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use parquet::encryption::decrypt::FileDecryptionProperties;
+//! # use parquet::encryption::encrypt::FileEncryptionProperties;
+//! # use parquet::encryption::kms::{KmsClient, KmsKeyRetriever, generate_data_key};
+//! # use parquet::errors::Result;
+//! # fn example(client: Arc<dyn KmsClient>) -> Result<()> {
+//! let (key, key_metadata) =
+//!     generate_data_key(client.as_ref(), "footer_master_key", 16, true)?;
+//! let encryption_properties = FileEncryptionProperties::builder(key)
+//!     .with_footer_key_metadata(key_metadata)
+//!     .build()?;
+//!
+//! let retriever = Arc::new(KmsKeyRetriever::new(client));
+//! let decryption_properties = FileDecryptionProperties::with_key_retriever(retriever)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use serde_json::{json, Value};
+
+use crate::encryption::ciphers::{validate_key, AesGcmDecryptor, BlockDecryptor};
+use crate::encryption::decrypt::KeyRetriever;
+use crate::errors::{ParquetError, Result};
+
+/// The type of the key material written by parquet-mr
+const KEY_MATERIAL_TYPE: &str = "PKMT1";
+
+/// The KMS instance identifier and URL stored with footer keys, as the defaults of
+/// parquet-mr
+const KMS_INSTANCE_DEFAULT: &str = "DEFAULT";
+
+/// A client of a key management service, which wraps and unwraps data encryption
+/// keys with the master keys it stores
+pub trait KmsClient: Send + Sync {
+    /// Wraps `key` with the master key `master_key_id`, returning the wrapped key
+    fn wrap_key(&self, key: &[u8], master_key_id: &str) -> Result<String>;
+
+    /// Unwraps `wrapped_key`, as returned by [`Self::wrap_key`] for the master
+    /// key `master_key_id`
+    fn unwrap_key(&self, wrapped_key: &str, master_key_id: &str) -> Result<Vec<u8>>;
+}
+
+/// A key encryption key, wrapped with a master key, that wraps a data encryption key
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KeyEncryptionKey {
+    /// The base64 encoded identifier of the key, used as the AAD of the wrapped key
+    id: String,
+    wrapped_key: String,
+}
+
+/// A data encryption key wrapped with a master key, as stored in the key metadata
+/// of an encrypted file
+///
+/// This is serialized as the key material JSON of parquet-mr, so that keys wrapped by
+/// either may be unwrapped by the other. The data encryption key is either wrapped by
+/// the KMS directly (single wrapping), or wrapped locally with a key encryption key
+/// that is itself wrapped by the KMS (double wrapping, the default of parquet-mr).
+/// Both are read, but only single wrapping is written. Key material stored outside
+/// of the file is not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMaterial {
+    is_footer_key: bool,
+    master_key_id: String,
+    wrapped_key: String,
+    key_encryption_key: Option<KeyEncryptionKey>,
+}
+
+impl KeyMaterial {
+    /// Create a new [`KeyMaterial`] for `wrapped_key`, the footer key if
+    /// `is_footer_key` or otherwise a column key, wrapped with the master key
+    /// `master_key_id`
+    pub fn new(is_footer_key: bool, master_key_id: String, wrapped_key: String) -> Self {
+        Self {
+            is_footer_key,
+            master_key_id,
+            wrapped_key,
+            key_encryption_key: None,
+        }
+    }
+
+    /// Returns true if this is the material of the footer key
+    pub fn is_footer_key(&self) -> bool {
+        self.is_footer_key
+    }
+
+    /// Returns the identifier of the master key
+    pub fn master_key_id(&self) -> &str {
+        &self.master_key_id
+    }
+
+    /// Returns the wrapped data encryption key
+    pub fn wrapped_key(&self) -> &str {
+        &self.wrapped_key
+    }
+
+    /// Returns true if the data encryption key is wrapped with a key encryption key
+    pub fn is_double_wrapped(&self) -> bool {
+        self.key_encryption_key.is_some()
+    }
+
+    /// Returns the key metadata storing this [`KeyMaterial`]
+    pub fn to_key_metadata(&self) -> Vec<u8> {
+        let mut material = json!({
+            "keyMaterialType": KEY_MATERIAL_TYPE,
+            "internalStorage": true,
+            "isFooterKey": self.is_footer_key,
+            "masterKeyID": self.master_key_id,
+            "wrappedDEK": self.wrapped_key,
+            "doubleWrapping": self.key_encryption_key.is_some(),
+        });
+        let fields = material.as_object_mut().unwrap();
+        if self.is_footer_key {
+            fields.insert("kmsInstanceID".to_string(), KMS_INSTANCE_DEFAULT.into());
+            fields.insert("kmsInstanceURL".to_string(), KMS_INSTANCE_DEFAULT.into());
+        }
+        if let Some(key_encryption_key) = &self.key_encryption_key {
+            fields.insert(
+                "keyEncryptionKeyID".to_string(),
+                key_encryption_key.id.as_str().into(),
+            );
+            fields.insert(
+                "wrappedKEK".to_string(),
+                key_encryption_key.wrapped_key.as_str().into(),
+            );
+        }
+        material.to_string().into_bytes()
+    }
+
+    /// Parses the [`KeyMaterial`] stored in `key_metadata`
+    pub fn from_key_metadata(key_metadata: &[u8]) -> Result<Self> {
+        let material: Value = serde_json::from_slice(key_metadata)
+            .map_err(|e| general_err!("Key metadata is not valid key material: {}", e))?;
+        let field = |name: &str| {
+            material
+                .get(name)
+                .ok_or_else(|| general_err!("Key material has no {} field", name))
+        };
+        let string = |name: &str| match field(name)? {
+            Value::String(value) => Ok(value.clone()),
+            _ => Err(general_err!("Key material field {} is not a string", name)),
+        };
+        let boolean = |name: &str| match field(name)? {
+            Value::Bool(value) => Ok(*value),
+            _ => Err(general_err!("Key material field {} is not a boolean", name)),
+        };
+
+        let material_type = string("keyMaterialType")?;
+        if material_type != KEY_MATERIAL_TYPE {
+            return Err(general_err!(
+                "Unsupported key material type {}",
+                material_type
+            ));
+        }
+        if !boolean("internalStorage")? {
+            return Err(general_err!(
+                "Key material stored outside of the file is not supported"
+            ));
+        }
+        let key_encryption_key = match boolean("doubleWrapping")? {
+            true => Some(KeyEncryptionKey {
+                id: string("keyEncryptionKeyID")?,
+                wrapped_key: string("wrappedKEK")?,
+            }),
+            false => None,
+        };
+        Ok(Self {
+            is_footer_key: boolean("isFooterKey")?,
+            master_key_id: string("masterKeyID")?,
+            wrapped_key: string("wrappedDEK")?,
+            key_encryption_key,
+        })
+    }
+}
+
+/// Generates a random data encryption key of `len` bytes, which must be 16, 24 or 32,
+/// returning the key and the key metadata storing it wrapped with the master key
+/// `master_key_id` by `client`, see [`wrap_key`]
+pub fn generate_data_key(
+    client: &dyn KmsClient,
+    master_key_id: &str,
+    len: usize,
+    is_footer_key: bool,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let mut key = vec![0; len];
+    validate_key(&key)?;
+    OsRng.fill_bytes(&mut key);
+    let key_metadata = wrap_key(client, master_key_id, &key, is_footer_key)?;
+    Ok((key, key_metadata))
+}
+
+/// Returns the key metadata storing `key`, the footer key if `is_footer_key` or
+/// otherwise a column key, wrapped with the master key `master_key_id` by `client`
+pub fn wrap_key(
+    client: &dyn KmsClient,
+    master_key_id: &str,
+    key: &[u8],
+    is_footer_key: bool,
+) -> Result<Vec<u8>> {
+    let wrapped_key = client.wrap_key(key, master_key_id)?;
+    let material =
+        KeyMaterial::new(is_footer_key, master_key_id.to_string(), wrapped_key);
+    Ok(material.to_key_metadata())
+}
+
+/// A [`KeyRetriever`] that unwraps the [`KeyMaterial`] stored in key metadata with
+/// a [`KmsClient`]
+#[derive(Clone)]
+pub struct KmsKeyRetriever {
+    client: Arc<dyn KmsClient>,
+}
+
+impl KmsKeyRetriever {
+    /// Create a new [`KmsKeyRetriever`] unwrapping keys with `client`
+    pub fn new(client: Arc<dyn KmsClient>) -> Self {
+        Self { client }
+    }
+}
+
+impl Debug for KmsKeyRetriever {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KmsKeyRetriever").finish_non_exhaustive()
+    }
+}
+
+impl KeyRetriever for KmsKeyRetriever {
+    fn retrieve_key(&self, key_metadata: &[u8]) -> Result<Vec<u8>> {
+        let material = KeyMaterial::from_key_metadata(key_metadata)?;
+        let master_key_id = material.master_key_id();
+        match &material.key_encryption_key {
+            None => self
+                .client
+                .unwrap_key(material.wrapped_key(), master_key_id),
+            Some(key_encryption_key) => {
+                let unwrapped = self
+                    .client
+                    .unwrap_key(&key_encryption_key.wrapped_key, master_key_id)?;
+                let aad = decode_base64(&key_encryption_key.id)?;
+                let wrapped_key = decode_base64(material.wrapped_key())?;
+                // Keys are wrapped locally as a nonce, ciphertext and tag, without
+                // the length prefix of a module
+                let module = [&(wrapped_key.len() as u32).to_le_bytes(), &*wrapped_key];
+                AesGcmDecryptor::new(&unwrapped)?.decrypt(&module.concat(), &aad)
+            }
+        }
+    }
+}
+
+/// Decodes the base64 encoded `value` of key material
+fn decode_base64(value: &str) -> Result<Vec<u8>> {
+    base64::decode(value)
+        .map_err(|e| general_err!("Invalid base64 in key material: {}", e))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::encryption::ciphers::AesGcmEncryptor;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// A [`KmsClient`] for testing, which wraps keys by XOR-ing them with its master
+    /// keys, and counts the keys it unwraps
+    #[derive(Debug, Default)]
+    pub(crate) struct TestKmsClient {
+        master_keys: HashMap<String, Vec<u8>>,
+        pub(crate) unwrapped: Mutex<usize>,
+    }
+
+    impl TestKmsClient {
+        pub(crate) fn new(master_keys: &[(&str, &[u8])]) -> Self {
+            Self {
+                master_keys: master_keys
+                    .iter()
+                    .map(|(id, key)| (id.to_string(), key.to_vec()))
+                    .collect(),
+                unwrapped: Default::default(),
+            }
+        }
+
+        fn master_key(&self, master_key_id: &str) -> Result<&[u8]> {
+            self.master_keys
+                .get(master_key_id)
+                .map(|k| k.as_slice())
+                .ok_or_else(|| general_err!("Unknown master key {}", master_key_id))
+        }
+    }
+
+    impl KmsClient for TestKmsClient {
+        fn wrap_key(&self, key: &[u8], master_key_id: &str) -> Result<String> {
+            let master_key = self.master_key(master_key_id)?;
+            Ok(key
+                .iter()
+                .zip(master_key.iter().cycle())
+                .map(|(k, m)| format!("{:02x}", k ^ m))
+                .collect())
+        }
+
+        fn unwrap_key(&self, wrapped_key: &str, master_key_id: &str) -> Result<Vec<u8>> {
+            let master_key = self.master_key(master_key_id)?;
+            *self.unwrapped.lock().unwrap() += 1;
+            (0..wrapped_key.len())
+                .step_by(2)
+                .zip(master_key.iter().cycle())
+                .map(|(i, m)| {
+                    u8::from_str_radix(&wrapped_key[i..i + 2], 16)
+                        .map(|k| k ^ m)
+                        .map_err(|e| general_err!("Invalid wrapped key: {}", e))
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_key_material() {
+        let material = KeyMaterial::new(false, "master".to_string(), "key".to_string());
+        let key_metadata = material.to_key_metadata();
+        assert_eq!(
+            std::str::from_utf8(&key_metadata).unwrap(),
+            r#"{"doubleWrapping":false,"internalStorage":true,"isFooterKey":false,"keyMaterialType":"PKMT1","masterKeyID":"master","wrappedDEK":"key"}"#
+        );
+        assert_eq!(
+            KeyMaterial::from_key_metadata(&key_metadata).unwrap(),
+            material
+        );
+
+        let material = KeyMaterial::new(true, "master".to_string(), "key".to_string());
+        let key_metadata = material.to_key_metadata();
+        assert_eq!(
+            std::str::from_utf8(&key_metadata).unwrap(),
+            r#"{"doubleWrapping":false,"internalStorage":true,"isFooterKey":true,"keyMaterialType":"PKMT1","kmsInstanceID":"DEFAULT","kmsInstanceURL":"DEFAULT","masterKeyID":"master","wrappedDEK":"key"}"#
+        );
+        assert_eq!(
+            KeyMaterial::from_key_metadata(&key_metadata).unwrap(),
+            material
+        );
+
+        // As written by parquet-mr, with double wrapping
+        let key_metadata = br#"{"keyMaterialType":"PKMT1","internalStorage":true,"isFooterKey":false,"masterKeyID":"kc1","wrappedDEK":"dek","doubleWrapping":true,"keyEncryptionKeyID":"kek_id","wrappedKEK":"kek"}"#;
+        let material = KeyMaterial::from_key_metadata(key_metadata).unwrap();
+        assert!(!material.is_footer_key());
+        assert!(material.is_double_wrapped());
+        assert_eq!(material.master_key_id(), "kc1");
+        assert_eq!(material.wrapped_key(), "dek");
+        assert_eq!(
+            KeyMaterial::from_key_metadata(&material.to_key_metadata()).unwrap(),
+            material
+        );
+
+        for (invalid, expected) in [
+            (b"PKM1\nmaster\nkey".as_slice(), "Key metadata is not valid key material"),
+            (b"{}", "Key material has no keyMaterialType field"),
+            (
+                br#"{"keyMaterialType":"PKMT2"}"#,
+                "Unsupported key material type PKMT2",
+            ),
+            (
+                br#"{"keyMaterialType":"PKMT1","internalStorage":false,"keyReference":"ref"}"#,
+                "Key material stored outside of the file is not supported",
+            ),
+            (
+                br#"{"keyMaterialType":"PKMT1","internalStorage":true,"doubleWrapping":"no"}"#,
+                "Key material field doubleWrapping is not a boolean",
+            ),
+        ] {
+            let err = KeyMaterial::from_key_metadata(invalid).unwrap_err();
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_kms_key_retriever() {
+        let client = Arc::new(TestKmsClient::new(&[("master", &[7; 16])]));
+        let (key, key_metadata) =
+            generate_data_key(client.as_ref(), "master", 24, false).unwrap();
+        assert_eq!(key.len(), 24);
+
+        let retriever = KmsKeyRetriever::new(client.clone());
+        assert_eq!(retriever.retrieve_key(&key_metadata).unwrap(), key);
+
+        let err = generate_data_key(client.as_ref(), "master", 10, false).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid AES key length 10"),
+            "{}",
+            err
+        );
+
+        let err = generate_data_key(client.as_ref(), "missing", 16, true).unwrap_err();
+        assert_eq!(err.to_string(), "Parquet error: Unknown master key missing");
+    }
+
+    #[test]
+    fn test_kms_key_retriever_double_wrapping() {
+        let client = Arc::new(TestKmsClient::new(&[("master", &[7; 16])]));
+        let key = [5_u8; 16];
+        let key_encryption_key = [9_u8; 32];
+        let key_encryption_key_id = [3_u8; 16];
+
+        // Wrap the key locally as parquet-mr does, without the length prefix
+        let module = AesGcmEncryptor::new(&key_encryption_key)
+            .unwrap()
+            .encrypt_with_nonce(&[1; 12], &key, &key_encryption_key_id);
+        let material = KeyMaterial {
+            is_footer_key: false,
+            master_key_id: "master".to_string(),
+            wrapped_key: base64::encode(&module[4..]),
+            key_encryption_key: Some(KeyEncryptionKey {
+                id: base64::encode(key_encryption_key_id),
+                wrapped_key: client.wrap_key(&key_encryption_key, "master").unwrap(),
+            }),
+        };
+
+        let retriever = KmsKeyRetriever::new(client);
+        let key_metadata = material.to_key_metadata();
+        assert_eq!(retriever.retrieve_key(&key_metadata).unwrap(), key);
+
+        let material = KeyMaterial {
+            key_encryption_key: Some(KeyEncryptionKey {
+                id: base64::encode([4_u8; 16]),
+                ..material.key_encryption_key.clone().unwrap()
+            }),
+            ..material
+        };
+        let err = retriever
+            .retrieve_key(&material.to_key_metadata())
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to decrypt"), "{}", err);
+    }
+}
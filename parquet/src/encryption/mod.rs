@@ -0,0 +1,49 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Support for [parquet modular encryption], which encrypts the footer and column
+//! chunks of a parquet file with AES-GCM or AES-GCM-CTR
+//!
+//! Encrypted files are written by providing [`FileEncryptionProperties`] with the keys
+//! of the file to [`WriterPropertiesBuilder::set_file_encryption_properties`], which
+//! can encrypt all columns with the footer key, or selected columns with their own
+//! keys. The page index and bloom filters of encrypted columns are not written.
+//!
+//! Encrypted files are read by providing [`FileDecryptionProperties`] with the keys
+//! of the file to [`SerializedFileReader::new_with_options`], or to
+//! [`ArrowReaderOptions::with_file_decryption_properties`]. Both files with an
+//! encrypted footer and files with a plaintext footer are supported, though
+//! the page index and bloom filters of encrypted columns can't currently be read.
+//!
+//! Keys are either provided explicitly, or retrieved from the key metadata stored
+//! in the file by a [`KeyRetriever`], such as [`KmsKeyRetriever`] for keys stored
+//! with envelope encryption by a key management service
+//!
+//! [parquet modular encryption]: https://github.com/apache/parquet-format/blob/master/Encryption.md
+//! [`FileEncryptionProperties`]: encrypt::FileEncryptionProperties
+//! [`WriterPropertiesBuilder::set_file_encryption_properties`]: crate::file::properties::WriterPropertiesBuilder::set_file_encryption_properties
+//! [`FileDecryptionProperties`]: decrypt::FileDecryptionProperties
+//! [`SerializedFileReader::new_with_options`]: crate::file::serialized_reader::SerializedFileReader::new_with_options
+//! [`ArrowReaderOptions::with_file_decryption_properties`]: crate::arrow::arrow_reader::ArrowReaderOptions::with_file_decryption_properties
+//! [`KeyRetriever`]: decrypt::KeyRetriever
+//! [`KmsKeyRetriever`]: kms::KmsKeyRetriever
+
+pub(crate) mod ciphers;
+pub mod decrypt;
+pub mod encrypt;
+pub mod kms;
+pub(crate) mod modules;
@@ -0,0 +1,106 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The additional authenticated data (AAD) of the encrypted modules of a parquet file
+
+use crate::errors::{ParquetError, Result};
+
+/// The types of module in an encrypted parquet file, which are part of their AAD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub(crate) enum ModuleType {
+    Footer = 0,
+    ColumnMetaData = 1,
+    DataPage = 2,
+    DictionaryPage = 3,
+    DataPageHeader = 4,
+    DictionaryPageHeader = 5,
+    ColumnIndex = 6,
+    OffsetIndex = 7,
+    BloomFilterHeader = 8,
+    BloomFilterBitset = 9,
+}
+
+/// Returns the AAD of the footer of a file with the file AAD `file_aad`
+pub(crate) fn create_footer_aad(file_aad: &[u8]) -> Vec<u8> {
+    [file_aad, &[ModuleType::Footer as u8]].concat()
+}
+
+/// Returns the AAD of a module of the column chunk `column_ordinal` of the row group
+/// `row_group_ordinal`, where `page_ordinal` must be provided for data pages and
+/// their headers only
+pub(crate) fn create_module_aad(
+    file_aad: &[u8],
+    module_type: ModuleType,
+    row_group_ordinal: usize,
+    column_ordinal: usize,
+    page_ordinal: Option<usize>,
+) -> Result<Vec<u8>> {
+    let mut aad = Vec::with_capacity(file_aad.len() + 7);
+    aad.extend_from_slice(file_aad);
+    aad.push(module_type as u8);
+    aad.extend_from_slice(&ordinal_bytes(row_group_ordinal, "row group")?);
+    aad.extend_from_slice(&ordinal_bytes(column_ordinal, "column")?);
+    if let Some(page_ordinal) = page_ordinal {
+        aad.extend_from_slice(&ordinal_bytes(page_ordinal, "page")?);
+    }
+    Ok(aad)
+}
+
+/// Returns the ordinal of a `name` module as an `i16`, as stored in encrypted files
+pub(crate) fn to_ordinal(ordinal: usize, name: &str) -> Result<i16> {
+    ordinal.try_into().map_err(|_| {
+        general_err!(
+            "Encrypted parquet files can't have more than {} {}s, got ordinal {}",
+            i16::MAX,
+            name,
+            ordinal
+        )
+    })
+}
+
+/// Returns the little endian bytes of an ordinal, which must fit in an `i16`
+fn ordinal_bytes(ordinal: usize, name: &str) -> Result<[u8; 2]> {
+    Ok(to_ordinal(ordinal, name)?.to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_aad() {
+        let file_aad = b"prefix";
+        assert_eq!(create_footer_aad(file_aad), b"prefix\x00");
+
+        let aad = create_module_aad(file_aad, ModuleType::ColumnMetaData, 1, 258, None)
+            .unwrap();
+        assert_eq!(aad, b"prefix\x01\x01\x00\x02\x01");
+
+        let aad =
+            create_module_aad(file_aad, ModuleType::DataPage, 2, 3, Some(4)).unwrap();
+        assert_eq!(aad, b"prefix\x02\x02\x00\x03\x00\x04\x00");
+
+        let err =
+            create_module_aad(file_aad, ModuleType::DataPageHeader, 0, 0, Some(40000))
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Encrypted parquet files can't have more than 32767 pages, got ordinal 40000"
+        );
+    }
+}
@@ -17,13 +17,25 @@
 
 use std::{io::Read, sync::Arc};
 
-use crate::format::{ColumnOrder as TColumnOrder, FileMetaData as TFileMetaData};
-use thrift::protocol::{TCompactInputProtocol, TSerializable};
+use crate::format::{
+    ColumnOrder as TColumnOrder, FileMetaData as TFileMetaData, OffsetIndex,
+    TypeDefinedOrder,
+};
+use thrift::protocol::{
+    TCompactInputProtocol, TCompactOutputProtocol, TOutputProtocol, TSerializable,
+};
 
 use crate::basic::ColumnOrder;
 
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::{FileDecryptionProperties, FileDecryptor};
 use crate::errors::{ParquetError, Result};
-use crate::file::{metadata::*, reader::ChunkReader, FOOTER_SIZE, PARQUET_MAGIC};
+use crate::file::{
+    metadata::*, page_index::index_reader::deserialize_column_index, reader::ChunkReader,
+    FOOTER_SIZE, PARQUET_MAGIC, PARQUET_MAGIC_ENCR_FOOTER,
+};
+#[cfg(feature = "encryption")]
+use crate::format::{ColumnMetaData as TColumnMetaData, FileCryptoMetaData};
 
 use crate::schema::types::{self, SchemaDescriptor};
 
@@ -36,6 +48,28 @@ use crate::schema::types::{self, SchemaDescriptor};
 /// The reader first reads DEFAULT_FOOTER_SIZE bytes from the end of the file.
 /// If it is not enough according to the length indicated in the footer, it reads more bytes.
 pub fn parse_metadata<R: ChunkReader>(chunk_reader: &R) -> Result<ParquetMetaData> {
+    let (metadata, encrypted_footer) = read_metadata(chunk_reader)?;
+    if encrypted_footer {
+        return Err(encrypted_footer_err());
+    }
+    decode_metadata(&metadata)
+}
+
+/// Parses the [`ParquetMetaData`] of a file encrypted with parquet modular encryption,
+/// decrypting its footer and column metadata with `decryption_properties`
+///
+/// Files that are not encrypted are read as by [`parse_metadata`]
+#[cfg(feature = "encryption")]
+pub fn parse_encrypted_metadata<R: ChunkReader>(
+    chunk_reader: &R,
+    decryption_properties: &FileDecryptionProperties,
+) -> Result<ParquetMetaData> {
+    let (metadata, encrypted_footer) = read_metadata(chunk_reader)?;
+    decode_encrypted_metadata(&metadata, encrypted_footer, decryption_properties)
+}
+
+/// Reads the metadata bytes of a file, returning them and whether they are encrypted
+fn read_metadata<R: ChunkReader>(chunk_reader: &R) -> Result<(bytes::Bytes, bool)> {
     // check file is large enough to hold footer
     let file_size = chunk_reader.len();
     if file_size < (FOOTER_SIZE as u64) {
@@ -49,7 +83,7 @@ pub fn parse_metadata<R: ChunkReader>(chunk_reader: &R) -> Result<ParquetMetaDat
         .get_read(file_size - 8, 8)?
         .read_exact(&mut footer)?;
 
-    let metadata_len = decode_footer(&footer)?;
+    let (metadata_len, encrypted_footer) = decode_footer_magic(&footer)?;
     let footer_metadata_len = FOOTER_SIZE + metadata_len;
 
     if footer_metadata_len > file_size as usize {
@@ -64,7 +98,7 @@ pub fn parse_metadata<R: ChunkReader>(chunk_reader: &R) -> Result<ParquetMetaDat
     let metadata =
         chunk_reader.get_bytes(file_size - footer_metadata_len as u64, metadata_len)?;
 
-    decode_metadata(&metadata)
+    Ok((metadata, encrypted_footer))
 }
 
 /// Decodes [`ParquetMetaData`] from the provided bytes
@@ -73,6 +107,302 @@ pub fn decode_metadata(metadata_read: &[u8]) -> Result<ParquetMetaData> {
     let mut prot = TCompactInputProtocol::new(metadata_read);
     let t_file_metadata: TFileMetaData = TFileMetaData::read_from_in_protocol(&mut prot)
         .map_err(|e| ParquetError::General(format!("Could not parse metadata: {}", e)))?;
+    decode_file_metadata(t_file_metadata)
+}
+
+/// Decodes [`ParquetMetaData`] from the provided bytes of a file encrypted with parquet
+/// modular encryption, where `encrypted_footer` is true if the file has an encrypted
+/// footer, as returned by [`decode_footer_with_encryption`]
+///
+/// Files that are not encrypted are decoded as by [`decode_metadata`]
+#[cfg(feature = "encryption")]
+pub fn decode_encrypted_metadata(
+    metadata_read: &[u8],
+    encrypted_footer: bool,
+    decryption_properties: &FileDecryptionProperties,
+) -> Result<ParquetMetaData> {
+    let mut buf = metadata_read;
+    let (mut t_file_metadata, file_decryptor) = if encrypted_footer {
+        let mut prot = TCompactInputProtocol::new(&mut buf);
+        let crypto_metadata = FileCryptoMetaData::read_from_in_protocol(&mut prot)
+            .map_err(|e| general_err!("Could not parse crypto metadata: {}", e))?;
+        let file_decryptor = FileDecryptor::new(
+            decryption_properties,
+            &crypto_metadata.encryption_algorithm,
+            crypto_metadata.key_metadata.as_deref(),
+        )?;
+
+        // The encrypted footer follows the crypto metadata
+        let footer = file_decryptor.decrypt_footer(buf)?;
+        let mut prot = TCompactInputProtocol::new(footer.as_slice());
+        let t_file_metadata = TFileMetaData::read_from_in_protocol(&mut prot)
+            .map_err(|e| general_err!("Could not parse metadata: {}", e))?;
+        (t_file_metadata, file_decryptor)
+    } else {
+        let mut prot = TCompactInputProtocol::new(&mut buf);
+        let t_file_metadata = TFileMetaData::read_from_in_protocol(&mut prot)
+            .map_err(|e| general_err!("Could not parse metadata: {}", e))?;
+        let file_decryptor = match &t_file_metadata.encryption_algorithm {
+            Some(algorithm) => FileDecryptor::new(
+                decryption_properties,
+                algorithm,
+                t_file_metadata.footer_signing_key_metadata.as_deref(),
+            )?,
+            None => return decode_file_metadata(t_file_metadata),
+        };
+
+        // The plaintext footer is followed by its signature
+        let footer = &metadata_read[..metadata_read.len() - buf.len()];
+        file_decryptor.verify_footer_signature(footer, buf)?;
+        (t_file_metadata, file_decryptor)
+    };
+
+    for (rg_idx, rg) in t_file_metadata.row_groups.iter_mut().enumerate() {
+        let ordinal = match rg.ordinal {
+            Some(ordinal) => ordinal,
+            None => {
+                let ordinal = rg_idx.try_into().map_err(|_| {
+                    general_err!(
+                        "Encrypted parquet files can't have more than 32767 row groups"
+                    )
+                })?;
+                rg.ordinal = Some(ordinal);
+                ordinal
+            }
+        };
+        for (col_idx, cc) in rg.columns.iter_mut().enumerate() {
+            if let (Some(crypto_metadata), Some(ciphertext)) =
+                (&cc.crypto_metadata, &cc.encrypted_column_metadata)
+            {
+                let column_metadata = file_decryptor.decrypt_column_metadata(
+                    crypto_metadata,
+                    ciphertext,
+                    ordinal as usize,
+                    col_idx,
+                )?;
+                let mut prot = TCompactInputProtocol::new(column_metadata.as_slice());
+                let column_metadata = TColumnMetaData::read_from_in_protocol(&mut prot)
+                    .map_err(|e| {
+                    general_err!("Could not parse column metadata: {}", e)
+                })?;
+                cc.meta_data = Some(column_metadata);
+            }
+        }
+    }
+
+    Ok(decode_file_metadata(t_file_metadata)?
+        .with_file_decryptor(Some(Arc::new(file_decryptor))))
+}
+
+/// Encodes [`ParquetMetaData`], including its page index if loaded, so that it may be
+/// stored separately from the file it describes and later read by
+/// [`decode_encoded_metadata`]
+///
+/// The bytes are laid out as the end of a parquet file: the column index and offset
+/// index of each column chunk, followed by the thrift-encoded metadata and the footer.
+/// The page index offsets of the encoded column chunks are positions within the returned
+/// bytes, all other offsets, such as those of the data pages, refer to the original file.
+///
+/// Metadata read without its page index is encoded without page index offsets,
+/// as these refer to the original file
+pub fn encode_metadata(metadata: &ParquetMetaData) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut row_groups = Vec::with_capacity(metadata.num_row_groups());
+    for row_group in metadata.row_groups() {
+        let mut row_group = row_group.to_thrift();
+        for column in row_group.columns.iter_mut() {
+            column.column_index_offset = None;
+            column.column_index_length = None;
+            column.offset_index_offset = None;
+            column.offset_index_length = None;
+        }
+        row_groups.push(row_group);
+    }
+
+    if let Some(column_indexes) = metadata.page_indexes() {
+        for (row_group, indexes) in row_groups.iter_mut().zip(column_indexes) {
+            for (column, index) in row_group.columns.iter_mut().zip(indexes) {
+                if let Some(index) = index.to_thrift() {
+                    let start = buf.len();
+                    write_thrift(&index, &mut buf)?;
+                    column.column_index_offset = Some(start as i64);
+                    column.column_index_length = Some((buf.len() - start) as i32);
+                }
+            }
+        }
+    }
+
+    if let Some(offset_indexes) = metadata.offset_indexes() {
+        for (row_group, locations) in row_groups.iter_mut().zip(offset_indexes) {
+            for (column, locations) in row_group.columns.iter_mut().zip(locations) {
+                let start = buf.len();
+                write_thrift(&OffsetIndex::new(locations.clone()), &mut buf)?;
+                column.offset_index_offset = Some(start as i64);
+                column.offset_index_length = Some((buf.len() - start) as i32);
+            }
+        }
+    }
+
+    let file_metadata = metadata.file_metadata();
+    // Only type defined orders can be represented in thrift
+    let column_orders = file_metadata
+        .column_orders()
+        .filter(|orders| {
+            orders
+                .iter()
+                .all(|order| matches!(order, ColumnOrder::TYPE_DEFINED_ORDER(_)))
+        })
+        .map(|orders| {
+            orders
+                .iter()
+                .map(|_| TColumnOrder::TYPEORDER(TypeDefinedOrder::new()))
+                .collect()
+        });
+
+    let t_file_metadata = TFileMetaData {
+        version: file_metadata.version(),
+        schema: types::to_thrift(file_metadata.schema())?,
+        num_rows: file_metadata.num_rows(),
+        row_groups,
+        key_value_metadata: file_metadata.key_value_metadata().cloned(),
+        created_by: file_metadata.created_by().map(str::to_string),
+        column_orders,
+        encryption_algorithm: None,
+        footer_signing_key_metadata: None,
+    };
+
+    let start = buf.len();
+    write_thrift(&t_file_metadata, &mut buf)?;
+    let metadata_len = (buf.len() - start) as i32;
+    buf.extend_from_slice(&metadata_len.to_le_bytes());
+    buf.extend_from_slice(&PARQUET_MAGIC);
+    Ok(buf)
+}
+
+/// Serializes a thrift object to `buf`
+fn write_thrift<T: TSerializable>(object: &T, buf: &mut Vec<u8>) -> Result<()> {
+    let mut protocol = TCompactOutputProtocol::new(buf);
+    object.write_to_out_protocol(&mut protocol)?;
+    protocol.flush()?;
+    Ok(())
+}
+
+/// Decodes [`ParquetMetaData`] and its page index, if any, from bytes produced by
+/// [`encode_metadata`]
+///
+/// Use [`decode_metadata`] to decode the thrift-encoded metadata read from a file
+pub fn decode_encoded_metadata(data: &[u8]) -> Result<ParquetMetaData> {
+    if data.len() < FOOTER_SIZE {
+        return Err(general_err!(
+            "Invalid encoded metadata. Size is smaller than footer"
+        ));
+    }
+    let footer_start = data.len() - FOOTER_SIZE;
+    let metadata_len = decode_footer(data[footer_start..].try_into().unwrap())?;
+    let metadata_start = footer_start.checked_sub(metadata_len).ok_or_else(|| {
+        general_err!(
+            "Invalid encoded metadata. Reported metadata length of {} + {} byte footer, but only {} bytes",
+            metadata_len,
+            FOOTER_SIZE,
+            data.len()
+        )
+    })?;
+
+    let mut prot = TCompactInputProtocol::new(&data[metadata_start..footer_start]);
+    let t_file_metadata: TFileMetaData = TFileMetaData::read_from_in_protocol(&mut prot)
+        .map_err(|e| ParquetError::General(format!("Could not parse metadata: {}", e)))?;
+    let (file_metadata, mut row_groups) = decode_file_metadata_parts(t_file_metadata)?;
+
+    let has_page_index = row_groups
+        .iter()
+        .flat_map(|rg| rg.columns())
+        .any(|c| c.column_index_offset().is_some() || c.offset_index_offset().is_some());
+    if !has_page_index {
+        return Ok(ParquetMetaData::new(file_metadata, row_groups));
+    }
+
+    let index_bytes = &data[..metadata_start];
+    let mut column_indexes = Vec::with_capacity(row_groups.len());
+    let mut offset_indexes = Vec::with_capacity(row_groups.len());
+    for row_group in row_groups.iter_mut() {
+        let column_index = row_group
+            .columns()
+            .iter()
+            .map(|c| {
+                let data = index_slice(
+                    index_bytes,
+                    c.column_index_offset(),
+                    c.column_index_length(),
+                )?;
+                deserialize_column_index(data, c.column_type())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // As for a file, the offset index of a row group is empty if any is missing
+        let offset_index = row_group
+            .columns()
+            .iter()
+            .map(|c| {
+                if c.offset_index_offset().is_none() {
+                    return Ok(None);
+                }
+                let data = index_slice(
+                    index_bytes,
+                    c.offset_index_offset(),
+                    c.offset_index_length(),
+                )?;
+                let mut prot = TCompactInputProtocol::new(data);
+                Ok(Some(
+                    OffsetIndex::read_from_in_protocol(&mut prot)?.page_locations,
+                ))
+            })
+            .collect::<Result<Option<Vec<_>>>>()?
+            .unwrap_or_default();
+
+        row_group.set_page_offset(offset_index.clone());
+        column_indexes.push(column_index);
+        offset_indexes.push(offset_index);
+    }
+
+    Ok(ParquetMetaData::new_with_page_index(
+        file_metadata,
+        row_groups,
+        Some(column_indexes),
+        Some(offset_indexes),
+    ))
+}
+
+/// Returns the bytes of an encoded page index at `offset` with `length`, or an empty
+/// slice if there is none
+fn index_slice(data: &[u8], offset: Option<i64>, length: Option<i32>) -> Result<&[u8]> {
+    let (offset, length) = match (offset, length) {
+        (Some(offset), Some(length)) => (offset, length),
+        _ => return Ok(&[]),
+    };
+    usize::try_from(offset)
+        .ok()
+        .zip(usize::try_from(length).ok())
+        .and_then(|(offset, length)| data.get(offset..offset.checked_add(length)?))
+        .ok_or_else(|| {
+            general_err!(
+                "Invalid encoded metadata. Page index at {} with length {} out of bounds",
+                offset,
+                length
+            )
+        })
+}
+
+/// Converts the thrift [`TFileMetaData`] of a file into [`ParquetMetaData`]
+fn decode_file_metadata(t_file_metadata: TFileMetaData) -> Result<ParquetMetaData> {
+    let (file_metadata, row_groups) = decode_file_metadata_parts(t_file_metadata)?;
+    Ok(ParquetMetaData::new(file_metadata, row_groups))
+}
+
+/// Converts the thrift [`TFileMetaData`] of a file into its [`FileMetaData`]
+/// and [`RowGroupMetaData`]
+fn decode_file_metadata_parts(
+    t_file_metadata: TFileMetaData,
+) -> Result<(FileMetaData, Vec<RowGroupMetaData>)> {
     let schema = types::from_thrift(&t_file_metadata.schema)?;
     let schema_descr = Arc::new(SchemaDescriptor::new(schema));
     let mut row_groups = Vec::new();
@@ -89,24 +419,51 @@ pub fn decode_metadata(metadata_read: &[u8]) -> Result<ParquetMetaData> {
         schema_descr,
         column_orders,
     );
-    Ok(ParquetMetaData::new(file_metadata, row_groups))
+    Ok((file_metadata, row_groups))
 }
 
 /// Decodes the footer returning the metadata length in bytes
 pub fn decode_footer(slice: &[u8; FOOTER_SIZE]) -> Result<usize> {
-    // check this is indeed a parquet file
-    if slice[4..] != PARQUET_MAGIC {
-        return Err(general_err!("Invalid Parquet file. Corrupt footer"));
+    match decode_footer_magic(slice)? {
+        (_, true) => Err(encrypted_footer_err()),
+        (metadata_len, false) => Ok(metadata_len),
     }
+}
+
+/// Decodes the footer of a file that may have an encrypted footer, returning the
+/// metadata length in bytes and whether the footer is encrypted
+#[cfg(feature = "encryption")]
+pub fn decode_footer_with_encryption(slice: &[u8; FOOTER_SIZE]) -> Result<(usize, bool)> {
+    decode_footer_magic(slice)
+}
+
+/// Returns the error for a file with an encrypted footer read without decryption
+fn encrypted_footer_err() -> ParquetError {
+    general_err!(
+        "Parquet file has an encrypted footer, which requires FileDecryptionProperties \
+         and the encryption feature to read"
+    )
+}
+
+/// Decodes the footer returning the metadata length in bytes and whether the
+/// footer is encrypted
+fn decode_footer_magic(slice: &[u8; FOOTER_SIZE]) -> Result<(usize, bool)> {
+    // check this is indeed a parquet file
+    let encrypted_footer = match slice[4..].try_into().unwrap() {
+        PARQUET_MAGIC => false,
+        PARQUET_MAGIC_ENCR_FOOTER => true,
+        _ => return Err(general_err!("Invalid Parquet file. Corrupt footer")),
+    };
 
     // get the metadata length from the footer
     let metadata_len = i32::from_le_bytes(slice[..4].try_into().unwrap());
-    metadata_len.try_into().map_err(|_| {
+    let metadata_len = metadata_len.try_into().map_err(|_| {
         general_err!(
             "Invalid Parquet file. Metadata length is less than zero ({})",
             metadata_len
         )
-    })
+    })?;
+    Ok((metadata_len, encrypted_footer))
 }
 
 /// Parses column orders from Thrift definition.
@@ -200,6 +557,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_metadata_encrypted_footer() {
+        let test_file = Bytes::from(vec![0, 0, 0, 0, b'P', b'A', b'R', b'E']);
+        let reader_result = parse_metadata(&test_file);
+        assert_eq!(reader_result.err().unwrap(), encrypted_footer_err());
+    }
+
     #[test]
     fn test_metadata_column_orders_parse() {
         // Define simple schema, we do not need to provide logical types.
@@ -249,4 +613,89 @@ mod tests {
 
         parse_column_orders(t_column_orders, &schema_descr);
     }
+
+    #[test]
+    fn test_encode_decode_metadata() {
+        use crate::data_type::{ByteArrayType, Int32Type};
+        use crate::file::properties::WriterProperties;
+        use crate::file::reader::FileReader;
+        use crate::file::serialized_reader::{ReadOptionsBuilder, SerializedFileReader};
+        use crate::file::writer::SerializedFileWriter;
+        use crate::schema::parser::parse_message_type;
+
+        let schema = Arc::new(
+            parse_message_type("message schema { REQUIRED INT32 a; OPTIONAL BINARY b; }")
+                .unwrap(),
+        );
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_data_page_row_count_limit(10)
+                .set_write_batch_size(10)
+                .build(),
+        );
+        let mut buf = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        for _ in 0..2 {
+            let mut row_group = writer.next_row_group().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            let values: Vec<_> = (0..50).collect();
+            column
+                .typed::<Int32Type>()
+                .write_batch(&values, None, None)
+                .unwrap();
+            column.close().unwrap();
+            // Leave the second column entirely null
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&[], Some(&[0; 50]), None)
+                .unwrap();
+            column.close().unwrap();
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+
+        let data = Bytes::from(buf);
+        let options = ReadOptionsBuilder::new().with_page_index().build();
+        let reader =
+            SerializedFileReader::new_with_options(data.clone(), options).unwrap();
+        let expected = reader.metadata();
+        assert!(expected.page_indexes().is_some());
+
+        let encoded = encode_metadata(expected).unwrap();
+        let decoded = decode_encoded_metadata(&encoded).unwrap();
+        assert_eq!(decoded.num_row_groups(), 2);
+        assert_eq!(
+            decoded.file_metadata().schema(),
+            expected.file_metadata().schema()
+        );
+        assert_eq!(
+            decoded.file_metadata().num_rows(),
+            expected.file_metadata().num_rows()
+        );
+        assert_eq!(decoded.page_indexes(), expected.page_indexes());
+        assert_eq!(decoded.offset_indexes(), expected.offset_indexes());
+        for (a, b) in decoded.row_groups().iter().zip(expected.row_groups()) {
+            assert_eq!(a.page_offset_index(), b.page_offset_index());
+            for (a, b) in a.columns().iter().zip(b.columns()) {
+                assert_eq!(a.byte_range(), b.byte_range());
+                assert_eq!(a.statistics(), b.statistics());
+            }
+        }
+
+        // Metadata read without its page index is encoded without it
+        let reader = SerializedFileReader::new(data).unwrap();
+        let encoded = encode_metadata(reader.metadata()).unwrap();
+        let decoded = decode_encoded_metadata(&encoded).unwrap();
+        assert!(decoded.page_indexes().is_none());
+        assert!(decoded.offset_indexes().is_none());
+        assert_eq!(decoded.num_row_groups(), 2);
+
+        // The thrift-encoded metadata is decoded by decode_metadata
+        let metadata_len = encoded.len() - FOOTER_SIZE;
+        let footer = encoded[metadata_len..].try_into().unwrap();
+        let metadata_start = metadata_len - decode_footer(footer).unwrap();
+        let decoded = decode_metadata(&encoded[metadata_start..metadata_len]).unwrap();
+        assert_eq!(decoded.num_row_groups(), 2);
+    }
 }
@@ -36,11 +36,13 @@
 use std::sync::Arc;
 
 use crate::format::{
-    BoundaryOrder, ColumnChunk, ColumnIndex, ColumnMetaData, OffsetIndex, PageLocation,
-    RowGroup, SortingColumn,
+    BoundaryOrder, ColumnChunk, ColumnCryptoMetaData, ColumnIndex, ColumnMetaData,
+    OffsetIndex, PageLocation, RowGroup, SortingColumn,
 };
 
 use crate::basic::{ColumnOrder, Compression, Encoding, Type};
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::FileDecryptor;
 use crate::errors::{ParquetError, Result};
 use crate::file::page_encoding_stats::{self, PageEncodingStats};
 use crate::file::page_index::index::Index;
@@ -62,6 +64,9 @@ pub struct ParquetMetaData {
     page_indexes: Option<ParquetColumnIndex>,
     /// Offset index for all pages in each column chunk
     offset_indexes: Option<ParquetOffsetIndex>,
+    /// Decryptor for the column chunks of an encrypted file
+    #[cfg(feature = "encryption")]
+    file_decryptor: Option<Arc<FileDecryptor>>,
 }
 
 impl ParquetMetaData {
@@ -73,6 +78,8 @@ impl ParquetMetaData {
             row_groups,
             page_indexes: None,
             offset_indexes: None,
+            #[cfg(feature = "encryption")]
+            file_decryptor: None,
         }
     }
 
@@ -87,6 +94,8 @@ impl ParquetMetaData {
             row_groups,
             page_indexes,
             offset_indexes,
+            #[cfg(feature = "encryption")]
+            file_decryptor: None,
         }
     }
 
@@ -120,6 +129,22 @@ impl ParquetMetaData {
     pub fn offset_indexes(&self) -> Option<&ParquetOffsetIndex> {
         self.offset_indexes.as_ref()
     }
+
+    /// Returns the decryptor for the column chunks of an encrypted file
+    #[cfg(feature = "encryption")]
+    pub(crate) fn file_decryptor(&self) -> Option<&Arc<FileDecryptor>> {
+        self.file_decryptor.as_ref()
+    }
+
+    /// Sets the decryptor for the column chunks of an encrypted file
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_file_decryptor(
+        mut self,
+        file_decryptor: Option<Arc<FileDecryptor>>,
+    ) -> Self {
+        self.file_decryptor = file_decryptor;
+        self
+    }
 }
 
 pub type KeyValue = crate::format::KeyValue;
@@ -233,6 +258,7 @@ pub struct RowGroupMetaData {
     total_byte_size: i64,
     schema_descr: SchemaDescPtr,
     page_offset_index: Option<Vec<Vec<PageLocation>>>,
+    ordinal: Option<i16>,
 }
 
 impl RowGroupMetaData {
@@ -281,6 +307,11 @@ impl RowGroupMetaData {
         self.page_offset_index.as_ref()
     }
 
+    /// Returns the position of this row group in the file, if known
+    pub fn ordinal(&self) -> Option<i16> {
+        self.ordinal
+    }
+
     /// Returns reference to a schema descriptor.
     pub fn schema_descr(&self) -> &SchemaDescriptor {
         self.schema_descr.as_ref()
@@ -317,6 +348,7 @@ impl RowGroupMetaData {
             total_byte_size,
             schema_descr,
             page_offset_index: None,
+            ordinal: rg.ordinal,
         })
     }
 
@@ -329,7 +361,7 @@ impl RowGroupMetaData {
             sorting_columns: self.sorting_columns().cloned(),
             file_offset: None,
             total_compressed_size: None,
-            ordinal: None,
+            ordinal: self.ordinal,
         }
     }
 }
@@ -342,6 +374,7 @@ pub struct RowGroupMetaDataBuilder {
     sorting_columns: Option<Vec<SortingColumn>>,
     total_byte_size: i64,
     page_offset_index: Option<Vec<Vec<PageLocation>>>,
+    ordinal: Option<i16>,
 }
 
 impl RowGroupMetaDataBuilder {
@@ -354,6 +387,7 @@ impl RowGroupMetaDataBuilder {
             sorting_columns: None,
             total_byte_size: 0,
             page_offset_index: None,
+            ordinal: None,
         }
     }
 
@@ -387,6 +421,12 @@ impl RowGroupMetaDataBuilder {
         self
     }
 
+    /// Sets the position of this row group in the file.
+    pub fn set_ordinal(mut self, value: i16) -> Self {
+        self.ordinal = Some(value);
+        self
+    }
+
     /// Builds row group metadata.
     pub fn build(self) -> Result<RowGroupMetaData> {
         if self.schema_descr.num_columns() != self.columns.len() {
@@ -404,6 +444,7 @@ impl RowGroupMetaDataBuilder {
             total_byte_size: self.total_byte_size,
             schema_descr: self.schema_descr,
             page_offset_index: self.page_offset_index,
+            ordinal: self.ordinal,
         })
     }
 }
@@ -427,10 +468,12 @@ pub struct ColumnChunkMetaData {
     statistics: Option<Statistics>,
     encoding_stats: Option<Vec<PageEncodingStats>>,
     bloom_filter_offset: Option<i64>,
+    bloom_filter_length: Option<i32>,
     offset_index_offset: Option<i64>,
     offset_index_length: Option<i32>,
     column_index_offset: Option<i64>,
     column_index_length: Option<i32>,
+    crypto_metadata: Option<ColumnCryptoMetaData>,
 }
 
 /// Represents common operations for a column chunk.
@@ -544,6 +587,13 @@ impl ColumnChunkMetaData {
         self.bloom_filter_offset
     }
 
+    /// Returns the length in bytes of the bloom filter, including its header.
+    ///
+    /// This is not written by writers of parquet-format versions before 2.10
+    pub fn bloom_filter_length(&self) -> Option<i32> {
+        self.bloom_filter_length
+    }
+
     /// Returns the offset for the column index.
     pub fn column_index_offset(&self) -> Option<i64> {
         self.column_index_offset
@@ -564,6 +614,11 @@ impl ColumnChunkMetaData {
         self.offset_index_length
     }
 
+    /// Returns how this column chunk is encrypted, if it is encrypted.
+    pub fn crypto_metadata(&self) -> Option<&ColumnCryptoMetaData> {
+        self.crypto_metadata.as_ref()
+    }
+
     /// Method to convert from Thrift.
     pub fn from_thrift(column_descr: ColumnDescPtr, cc: ColumnChunk) -> Result<Self> {
         if cc.meta_data.is_none() {
@@ -597,10 +652,12 @@ impl ColumnChunkMetaData {
             })
             .transpose()?;
         let bloom_filter_offset = col_metadata.bloom_filter_offset;
+        let bloom_filter_length = col_metadata.bloom_filter_length;
         let offset_index_offset = cc.offset_index_offset;
         let offset_index_length = cc.offset_index_length;
         let column_index_offset = cc.column_index_offset;
         let column_index_length = cc.column_index_length;
+        let crypto_metadata = cc.crypto_metadata;
 
         let result = ColumnChunkMetaData {
             column_type,
@@ -619,10 +676,12 @@ impl ColumnChunkMetaData {
             statistics,
             encoding_stats,
             bloom_filter_offset,
+            bloom_filter_length,
             offset_index_offset,
             offset_index_length,
             column_index_offset,
             column_index_length,
+            crypto_metadata,
         };
         Ok(result)
     }
@@ -639,7 +698,7 @@ impl ColumnChunkMetaData {
             offset_index_length: self.offset_index_length,
             column_index_offset: self.column_index_offset,
             column_index_length: self.column_index_length,
-            crypto_metadata: None,
+            crypto_metadata: self.crypto_metadata.clone(),
             encrypted_column_metadata: None,
         }
     }
@@ -664,6 +723,7 @@ impl ColumnChunkMetaData {
                 .as_ref()
                 .map(|vec| vec.iter().map(page_encoding_stats::to_thrift).collect()),
             bloom_filter_offset: self.bloom_filter_offset,
+            bloom_filter_length: self.bloom_filter_length,
         }
     }
 }
@@ -684,10 +744,12 @@ pub struct ColumnChunkMetaDataBuilder {
     statistics: Option<Statistics>,
     encoding_stats: Option<Vec<PageEncodingStats>>,
     bloom_filter_offset: Option<i64>,
+    bloom_filter_length: Option<i32>,
     offset_index_offset: Option<i64>,
     offset_index_length: Option<i32>,
     column_index_offset: Option<i64>,
     column_index_length: Option<i32>,
+    crypto_metadata: Option<ColumnCryptoMetaData>,
 }
 
 impl ColumnChunkMetaDataBuilder {
@@ -708,10 +770,12 @@ impl ColumnChunkMetaDataBuilder {
             statistics: None,
             encoding_stats: None,
             bloom_filter_offset: None,
+            bloom_filter_length: None,
             offset_index_offset: None,
             offset_index_length: None,
             column_index_offset: None,
             column_index_length: None,
+            crypto_metadata: None,
         }
     }
 
@@ -793,6 +857,12 @@ impl ColumnChunkMetaDataBuilder {
         self
     }
 
+    /// Sets optional bloom filter length in bytes.
+    pub fn set_bloom_filter_length(mut self, value: Option<i32>) -> Self {
+        self.bloom_filter_length = value;
+        self
+    }
+
     /// Sets optional offset index offset in bytes.
     pub fn set_offset_index_offset(mut self, value: Option<i64>) -> Self {
         self.offset_index_offset = value;
@@ -817,6 +887,12 @@ impl ColumnChunkMetaDataBuilder {
         self
     }
 
+    /// Sets how this column chunk is encrypted, if it is encrypted.
+    pub fn set_crypto_metadata(mut self, value: Option<ColumnCryptoMetaData>) -> Self {
+        self.crypto_metadata = value;
+        self
+    }
+
     /// Builds column chunk metadata.
     pub fn build(self) -> Result<ColumnChunkMetaData> {
         Ok(ColumnChunkMetaData {
@@ -836,10 +912,12 @@ impl ColumnChunkMetaDataBuilder {
             statistics: self.statistics,
             encoding_stats: self.encoding_stats,
             bloom_filter_offset: self.bloom_filter_offset,
+            bloom_filter_length: self.bloom_filter_length,
             offset_index_offset: self.offset_index_offset,
             offset_index_length: self.offset_index_length,
             column_index_offset: self.column_index_offset,
             column_index_length: self.column_index_length,
+            crypto_metadata: self.crypto_metadata,
         })
     }
 }
@@ -975,6 +1053,7 @@ mod tests {
             .set_num_rows(1000)
             .set_total_byte_size(2000)
             .set_column_metadata(columns)
+            .set_ordinal(1)
             .build()
             .unwrap();
 
@@ -1029,10 +1108,14 @@ mod tests {
                 },
             ])
             .set_bloom_filter_offset(Some(6000))
+            .set_bloom_filter_length(Some(100))
             .set_offset_index_offset(Some(7000))
             .set_offset_index_length(Some(25))
             .set_column_index_offset(Some(8000))
             .set_column_index_length(Some(25))
+            .set_crypto_metadata(Some(ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(
+                Default::default(),
+            )))
             .build()
             .unwrap();
 
@@ -100,7 +100,9 @@ pub mod metadata;
 pub mod page_encoding_stats;
 pub mod page_index;
 pub mod properties;
+pub mod pruning;
 pub mod reader;
+pub mod rewriter;
 pub mod serialized_reader;
 pub mod statistics;
 pub mod writer;
@@ -108,3 +110,4 @@ pub mod writer;
 /// The length of the parquet footer in bytes
 pub const FOOTER_SIZE: usize = 8;
 const PARQUET_MAGIC: [u8; 4] = [b'P', b'A', b'R', b'1'];
+const PARQUET_MAGIC_ENCR_FOOTER: [u8; 4] = [b'P', b'A', b'R', b'E'];
@@ -17,7 +17,7 @@
 
 use crate::basic::Type;
 use crate::data_type::private::ParquetValueType;
-use crate::data_type::Int96;
+use crate::data_type::{AsBytes, Int96};
 use crate::errors::ParquetError;
 use crate::format::{BoundaryOrder, ColumnIndex};
 use crate::util::bit_util::from_le_slice;
@@ -46,6 +46,28 @@ impl<T> PageIndex<T> {
     }
 }
 
+/// Converts the statistics of each page back into a thrift [`ColumnIndex`]
+fn page_indexes_to_thrift<T: AsBytes>(
+    indexes: &[PageIndex<T>],
+    boundary_order: BoundaryOrder,
+) -> ColumnIndex {
+    let to_bytes = |v: Option<&T>| v.map(|v| v.as_bytes().to_vec()).unwrap_or_default();
+    let null_pages = indexes.iter().map(|x| x.min.is_none()).collect();
+    let min_values = indexes.iter().map(|x| to_bytes(x.min())).collect();
+    let max_values = indexes.iter().map(|x| to_bytes(x.max())).collect();
+    let null_counts = indexes
+        .iter()
+        .map(|x| x.null_count())
+        .collect::<Option<_>>();
+    ColumnIndex::new(
+        null_pages,
+        min_values,
+        max_values,
+        boundary_order,
+        null_counts,
+    )
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[allow(non_camel_case_types)]
 pub enum Index {
@@ -88,6 +110,35 @@ impl Index {
             Index::FIXED_LEN_BYTE_ARRAY(index) => Some(index.boundary_order),
         }
     }
+
+    /// Converts this page index back into a thrift [`ColumnIndex`], returning
+    /// `None` for [`Index::NONE`]
+    pub(crate) fn to_thrift(&self) -> Option<ColumnIndex> {
+        match self {
+            Index::NONE => None,
+            Index::BOOLEAN(index) => {
+                Some(page_indexes_to_thrift(&index.indexes, index.boundary_order))
+            }
+            Index::INT32(index) => {
+                Some(page_indexes_to_thrift(&index.indexes, index.boundary_order))
+            }
+            Index::INT64(index) => {
+                Some(page_indexes_to_thrift(&index.indexes, index.boundary_order))
+            }
+            Index::INT96(index) => {
+                Some(page_indexes_to_thrift(&index.indexes, index.boundary_order))
+            }
+            Index::FLOAT(index) => {
+                Some(page_indexes_to_thrift(&index.indexes, index.boundary_order))
+            }
+            Index::DOUBLE(index) => {
+                Some(page_indexes_to_thrift(&index.indexes, index.boundary_order))
+            }
+            Index::BYTE_ARRAY(index) | Index::FIXED_LEN_BYTE_ARRAY(index) => {
+                Some(page_indexes_to_thrift(&index.indexes, index.boundary_order))
+            }
+        }
+    }
 }
 
 /// An index of a column of [`Type`] physical representation
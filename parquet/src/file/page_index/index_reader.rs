@@ -88,18 +88,33 @@ pub fn read_pages_locations<R: ChunkReader>(
     Ok(result)
 }
 
+/// Returns an error if any of `chunks` is encrypted, as reading the page index of
+/// encrypted columns is not yet supported
+fn check_not_encrypted(chunks: &[ColumnChunkMetaData]) -> Result<(), ParquetError> {
+    match chunks
+        .iter()
+        .find(|chunk| chunk.crypto_metadata().is_some())
+    {
+        Some(chunk) => Err(nyi_err!(
+            "Reading the page index of encrypted column {}",
+            chunk.column_path().string()
+        )),
+        None => Ok(()),
+    }
+}
+
 //Get File offsets of every ColumnChunk's page_index
 //If there are invalid offset return a zero offset with empty lengths.
 pub(crate) fn get_index_offset_and_lengths(
     chunks: &[ColumnChunkMetaData],
 ) -> Result<(u64, Vec<usize>), ParquetError> {
-    let first_col_metadata = if let Some(chunk) = chunks.first() {
-        chunk
-    } else {
-        return Ok((0, vec![]));
-    };
+    check_not_encrypted(chunks)?;
 
-    let offset: u64 = if let Some(offset) = first_col_metadata.column_index_offset() {
+    // The column indexes of a row group are contiguous, but columns without
+    // page statistics have none, and so the first column may not have one
+    let offset: u64 = if let Some(offset) =
+        chunks.iter().find_map(|chunk| chunk.column_index_offset())
+    {
         offset.try_into().unwrap()
     } else {
         return Ok((0, vec![]));
@@ -122,6 +137,8 @@ pub(crate) fn get_index_offset_and_lengths(
 pub(crate) fn get_location_offset_and_total_length(
     chunks: &[ColumnChunkMetaData],
 ) -> Result<(u64, usize), ParquetError> {
+    check_not_encrypted(chunks)?;
+
     let metadata = if let Some(chunk) = chunks.first() {
         chunk
     } else {
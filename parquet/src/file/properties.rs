@@ -64,13 +64,16 @@
 //!     .build();
 //! ```
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use crate::basic::{Compression, Encoding};
-use crate::compression::{CodecOptions, CodecOptionsBuilder};
+use crate::compression::{check_compression_level, CodecOptions, CodecOptionsBuilder};
+#[cfg(feature = "encryption")]
+use crate::encryption::encrypt::FileEncryptionProperties;
+use crate::errors::{ParquetError, Result};
 use crate::file::metadata::KeyValue;
 use crate::format::SortingColumn;
-use crate::schema::types::ColumnPath;
+use crate::schema::types::{ColumnPath, SchemaDescriptor};
 
 const DEFAULT_PAGE_SIZE: usize = 1024 * 1024;
 const DEFAULT_WRITE_BATCH_SIZE: usize = 1024;
@@ -80,7 +83,12 @@ const DEFAULT_DICTIONARY_ENABLED: bool = true;
 const DEFAULT_DICTIONARY_PAGE_SIZE_LIMIT: usize = DEFAULT_PAGE_SIZE;
 const DEFAULT_STATISTICS_ENABLED: EnabledStatistics = EnabledStatistics::Page;
 const DEFAULT_MAX_STATISTICS_SIZE: usize = 4096;
+const DEFAULT_STATISTICS_TRUNCATE_LENGTH: Option<usize> = None;
 const DEFAULT_MAX_ROW_GROUP_SIZE: usize = 1024 * 1024;
+const DEFAULT_MAX_ROW_GROUP_BYTES: Option<usize> = None;
+const DEFAULT_WRITE_PAGE_INDEX: bool = true;
+const DEFAULT_WRITE_PAGE_CHECKSUMS: bool = false;
+const DEFAULT_COERCE_TIMESTAMPS: Option<TimestampUnit> = None;
 const DEFAULT_CREATED_BY: &str =
     concat!("parquet-rs version ", env!("CARGO_PKG_VERSION"));
 /// default value for the false positive probability used in a bloom filter.
@@ -108,6 +116,22 @@ impl WriterVersion {
     }
 }
 
+impl FromStr for WriterVersion {
+    type Err = ParquetError;
+
+    /// Parses a writer version from its number, i.e. `1` or `2`
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(WriterVersion::PARQUET_1_0),
+            "2" => Ok(WriterVersion::PARQUET_2_0),
+            other => Err(general_err!(
+                "Unknown writer version {}: possible values 1, 2",
+                other
+            )),
+        }
+    }
+}
+
 /// Reference counted writer properties.
 pub type WriterPropertiesPtr = Arc<WriterProperties>;
 
@@ -122,12 +146,19 @@ pub struct WriterProperties {
     data_page_row_count_limit: usize,
     write_batch_size: usize,
     max_row_group_size: usize,
+    max_row_group_bytes: Option<usize>,
     writer_version: WriterVersion,
     created_by: String,
     pub(crate) key_value_metadata: Option<Vec<KeyValue>>,
     default_column_properties: ColumnProperties,
     column_properties: HashMap<ColumnPath, ColumnProperties>,
     sorting_columns: Option<Vec<SortingColumn>>,
+    write_page_index: bool,
+    write_page_checksums: bool,
+    statistics_truncate_length: Option<usize>,
+    coerce_timestamps: Option<TimestampUnit>,
+    #[cfg(feature = "encryption")]
+    file_encryption_properties: Option<FileEncryptionProperties>,
 }
 
 impl WriterProperties {
@@ -174,6 +205,15 @@ impl WriterProperties {
         self.max_row_group_size
     }
 
+    /// Returns the estimated number of bytes at which to flush a row group, if any.
+    ///
+    /// Note: this is used by `ArrowWriter` in addition to
+    /// [`Self::max_row_group_size`], and is based on the estimated size of the buffered
+    /// data prior to encoding and compression
+    pub fn max_row_group_bytes(&self) -> Option<usize> {
+        self.max_row_group_bytes
+    }
+
     /// Returns configured writer version.
     pub fn writer_version(&self) -> WriterVersion {
         self.writer_version
@@ -194,6 +234,52 @@ impl WriterProperties {
         self.sorting_columns.as_ref()
     }
 
+    /// Returns `true` if the page index, i.e. the `ColumnIndex` and `OffsetIndex`
+    /// of each column chunk, should be written.
+    ///
+    /// The `ColumnIndex` of a column is only written if its statistics are
+    /// [`EnabledStatistics::Page`], whereas the `OffsetIndex` is always written.
+    pub fn write_page_index(&self) -> bool {
+        self.write_page_index
+    }
+
+    /// Returns `true` if the `crc` field of each page header should be populated
+    /// with the CRC32 checksum of the page, as written to the file.
+    pub fn write_page_checksums(&self) -> bool {
+        self.write_page_checksums
+    }
+
+    /// Returns the maximum length in bytes of the min and max statistics of
+    /// `BYTE_ARRAY` columns, or `None` if they are not truncated.
+    ///
+    /// The min value is truncated to a prefix of at most this length, and the max
+    /// value to a prefix whose last byte, or character for valid UTF-8, is incremented
+    /// so that it remains an upper bound. Values that cannot be truncated, e.g. a max
+    /// value consisting entirely of `0xFF` bytes, are written in full.
+    ///
+    /// This applies to the column chunk statistics, and to the page statistics
+    /// written to the data page headers and the `ColumnIndex`.
+    pub fn statistics_truncate_length(&self) -> Option<usize> {
+        self.statistics_truncate_length
+    }
+
+    /// Returns the unit arrow timestamps are coerced to when written, if any.
+    ///
+    /// Note: this is only used by `ArrowWriter`. Timestamps of any unit, including
+    /// nanoseconds and seconds, are written as `INT64` with the `TIMESTAMP` logical
+    /// type of this unit, which unlike nanoseconds also has a legacy converted type
+    /// understood by older readers. Coercing to a coarser unit returns an error if
+    /// it would lose precision
+    pub fn coerce_timestamps(&self) -> Option<TimestampUnit> {
+        self.coerce_timestamps
+    }
+
+    /// Returns the [`FileEncryptionProperties`] used to encrypt the file, if any
+    #[cfg(feature = "encryption")]
+    pub fn file_encryption_properties(&self) -> Option<&FileEncryptionProperties> {
+        self.file_encryption_properties.as_ref()
+    }
+
     /// Returns encoding for a data page, when dictionary encoding is enabled.
     /// This is not configurable.
     #[inline]
@@ -233,6 +319,31 @@ impl WriterProperties {
             .unwrap_or(DEFAULT_COMPRESSION)
     }
 
+    /// Returns compression level for a column, or `None` to use the default level
+    /// of the compression codec.
+    pub fn compression_level(&self, col: &ColumnPath) -> Option<u32> {
+        self.column_properties
+            .get(col)
+            .and_then(|c| c.compression_level())
+            .or_else(|| self.default_column_properties.compression_level())
+    }
+
+    /// Returns an error if the compression level of any column in `schema` is not valid
+    /// for the compression codec of that column.
+    pub(crate) fn check_compression_levels(
+        &self,
+        schema: &SchemaDescriptor,
+    ) -> Result<()> {
+        for column in schema.columns() {
+            let path = column.path();
+            check_compression_level(
+                self.compression(path),
+                self.compression_level(path),
+            )?;
+        }
+        Ok(())
+    }
+
     /// Returns `true` if dictionary encoding is enabled for a column.
     pub fn dictionary_enabled(&self, col: &ColumnPath) -> bool {
         self.column_properties
@@ -263,15 +374,16 @@ impl WriterProperties {
 
     /// Returns the [`BloomFilterProperties`] for the given column
     ///
-    /// Returns `None` if bloom filter is disabled
+    /// Returns `None` if bloom filter is disabled, either for this column or, if not
+    /// set for this column, for all columns
     pub fn bloom_filter_properties(
         &self,
         col: &ColumnPath,
     ) -> Option<&BloomFilterProperties> {
-        self.column_properties
-            .get(col)
-            .and_then(|c| c.bloom_filter_properties())
-            .or_else(|| self.default_column_properties.bloom_filter_properties())
+        match self.column_properties.get(col) {
+            Some(c) if c.bloom_filter_enabled().is_some() => c.bloom_filter_properties(),
+            _ => self.default_column_properties.bloom_filter_properties(),
+        }
     }
 }
 
@@ -282,12 +394,19 @@ pub struct WriterPropertiesBuilder {
     data_page_row_count_limit: usize,
     write_batch_size: usize,
     max_row_group_size: usize,
+    max_row_group_bytes: Option<usize>,
     writer_version: WriterVersion,
     created_by: String,
     key_value_metadata: Option<Vec<KeyValue>>,
     default_column_properties: ColumnProperties,
     column_properties: HashMap<ColumnPath, ColumnProperties>,
     sorting_columns: Option<Vec<SortingColumn>>,
+    write_page_index: bool,
+    write_page_checksums: bool,
+    statistics_truncate_length: Option<usize>,
+    coerce_timestamps: Option<TimestampUnit>,
+    #[cfg(feature = "encryption")]
+    file_encryption_properties: Option<FileEncryptionProperties>,
 }
 
 impl WriterPropertiesBuilder {
@@ -299,12 +418,19 @@ impl WriterPropertiesBuilder {
             data_page_row_count_limit: usize::MAX,
             write_batch_size: DEFAULT_WRITE_BATCH_SIZE,
             max_row_group_size: DEFAULT_MAX_ROW_GROUP_SIZE,
+            max_row_group_bytes: DEFAULT_MAX_ROW_GROUP_BYTES,
             writer_version: DEFAULT_WRITER_VERSION,
             created_by: DEFAULT_CREATED_BY.to_string(),
             key_value_metadata: None,
             default_column_properties: Default::default(),
             column_properties: HashMap::new(),
             sorting_columns: None,
+            write_page_index: DEFAULT_WRITE_PAGE_INDEX,
+            write_page_checksums: DEFAULT_WRITE_PAGE_CHECKSUMS,
+            statistics_truncate_length: DEFAULT_STATISTICS_TRUNCATE_LENGTH,
+            coerce_timestamps: DEFAULT_COERCE_TIMESTAMPS,
+            #[cfg(feature = "encryption")]
+            file_encryption_properties: None,
         }
     }
 
@@ -316,12 +442,19 @@ impl WriterPropertiesBuilder {
             data_page_row_count_limit: self.data_page_row_count_limit,
             write_batch_size: self.write_batch_size,
             max_row_group_size: self.max_row_group_size,
+            max_row_group_bytes: self.max_row_group_bytes,
             writer_version: self.writer_version,
             created_by: self.created_by,
             key_value_metadata: self.key_value_metadata,
             default_column_properties: self.default_column_properties,
             column_properties: self.column_properties,
             sorting_columns: self.sorting_columns,
+            write_page_index: self.write_page_index,
+            write_page_checksums: self.write_page_checksums,
+            statistics_truncate_length: self.statistics_truncate_length,
+            coerce_timestamps: self.coerce_timestamps,
+            #[cfg(feature = "encryption")]
+            file_encryption_properties: self.file_encryption_properties,
         }
     }
 
@@ -329,6 +462,20 @@ impl WriterPropertiesBuilder {
     // Writer properties related to a file
 
     /// Sets writer version.
+    ///
+    /// [`WriterVersion::PARQUET_1_0`] writes `DATA_PAGE` pages, whereas
+    /// [`WriterVersion::PARQUET_2_0`] writes `DATA_PAGE_V2` pages, which store the
+    /// repetition and definition levels uncompressed ahead of the compressed values.
+    /// Values are left uncompressed, and the page marked as such, when compression
+    /// would not reduce their size.
+    ///
+    /// The writer version also determines the encoding of columns without an encoding
+    /// set, once their dictionary is full or if dictionary encoding is disabled.
+    /// [`WriterVersion::PARQUET_1_0`] uses `PLAIN`, whereas
+    /// [`WriterVersion::PARQUET_2_0`] uses `DELTA_BINARY_PACKED` for integer columns
+    /// and `DELTA_BYTE_ARRAY` for byte array columns, which stores the prefix each
+    /// value shares with the previous one once. Byte array columns can instead use
+    /// `DELTA_LENGTH_BYTE_ARRAY` with [`Self::set_column_encoding`].
     pub fn set_writer_version(mut self, value: WriterVersion) -> Self {
         self.writer_version = value;
         self
@@ -386,6 +533,16 @@ impl WriterPropertiesBuilder {
         self
     }
 
+    /// Sets the estimated number of bytes at which to flush a row group, or `None` to
+    /// only flush based on the number of rows (defaults to `None`).
+    ///
+    /// See [`WriterProperties::max_row_group_bytes`]
+    pub fn set_max_row_group_bytes(mut self, value: Option<usize>) -> Self {
+        assert!(value != Some(0), "Cannot have a 0 max row group bytes");
+        self.max_row_group_bytes = value;
+        self
+    }
+
     /// Sets "created by" property.
     pub fn set_created_by(mut self, value: String) -> Self {
         self.created_by = value;
@@ -404,6 +561,53 @@ impl WriterPropertiesBuilder {
         self
     }
 
+    /// Sets whether to write the page index, which allows readers to skip pages
+    /// based on their statistics and row offsets (defaults to `true`).
+    ///
+    /// See [`WriterProperties::write_page_index`]
+    pub fn set_write_page_index(mut self, value: bool) -> Self {
+        self.write_page_index = value;
+        self
+    }
+
+    /// Sets whether to write the CRC32 checksum of each page to its header, which
+    /// allows readers to detect corrupted pages (defaults to `false`).
+    ///
+    /// See [`WriterProperties::write_page_checksums`]
+    pub fn set_write_page_checksums(mut self, value: bool) -> Self {
+        self.write_page_checksums = value;
+        self
+    }
+
+    /// Sets the maximum length in bytes of the min and max statistics of
+    /// `BYTE_ARRAY` columns, or `None` to disable truncation (defaults to `None`).
+    ///
+    /// See [`WriterProperties::statistics_truncate_length`]
+    pub fn set_statistics_truncate_length(mut self, value: Option<usize>) -> Self {
+        self.statistics_truncate_length = value;
+        self
+    }
+
+    /// Sets the unit arrow timestamps are coerced to when written, or `None` to
+    /// write them in their own unit (defaults to `None`).
+    ///
+    /// See [`WriterProperties::coerce_timestamps`]
+    pub fn set_coerce_timestamps(mut self, value: Option<TimestampUnit>) -> Self {
+        self.coerce_timestamps = value;
+        self
+    }
+
+    /// Sets the [`FileEncryptionProperties`] used to encrypt the file with
+    /// parquet modular encryption
+    #[cfg(feature = "encryption")]
+    pub fn set_file_encryption_properties(
+        mut self,
+        value: FileEncryptionProperties,
+    ) -> Self {
+        self.file_encryption_properties = Some(value);
+        self
+    }
+
     // ----------------------------------------------------------------------
     // Setters for any column (global)
 
@@ -426,6 +630,16 @@ impl WriterPropertiesBuilder {
         self
     }
 
+    /// Sets compression level for any column.
+    ///
+    /// Only applicable to GZIP (0-9), BROTLI (0-11) and ZSTD (1-22), and ignored by
+    /// other codecs. Creating a writer returns an error if the level is out of range for
+    /// the codec of any column.
+    pub fn set_compression_level(mut self, value: u32) -> Self {
+        self.default_column_properties.set_compression_level(value);
+        self
+    }
+
     /// Sets flag to enable/disable dictionary encoding for any column.
     ///
     /// Use this method to set dictionary encoding, instead of explicitly specifying
@@ -506,6 +720,13 @@ impl WriterPropertiesBuilder {
         self
     }
 
+    /// Sets compression level for a column.
+    /// Takes precedence over globally defined settings.
+    pub fn set_column_compression_level(mut self, col: ColumnPath, value: u32) -> Self {
+        self.get_mut_props(col).set_compression_level(value);
+        self
+    }
+
     /// Sets flag to enable/disable dictionary encoding for a column.
     /// Takes precedence over globally defined settings.
     pub fn set_column_dictionary_enabled(mut self, col: ColumnPath, value: bool) -> Self {
@@ -536,7 +757,8 @@ impl WriterPropertiesBuilder {
     }
 
     /// Sets whether a bloom filter should be created for a specific column.
-    /// The behavior is similar to [`set_bloom_filter_enabled`](Self::set_bloom_filter_enabled).
+    /// The behavior is similar to [`set_bloom_filter_enabled`](Self::set_bloom_filter_enabled),
+    /// and disabling it overrides bloom filters enabled for all columns.
     /// Takes precedence over globally defined settings.
     pub fn set_column_bloom_filter_enabled(
         mut self,
@@ -564,6 +786,16 @@ impl WriterPropertiesBuilder {
     }
 }
 
+/// The unit arrow timestamps are coerced to when written, see
+/// [`WriterPropertiesBuilder::set_coerce_timestamps`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TimestampUnit {
+    /// Milliseconds, written as `TIMESTAMP(MILLIS)`
+    Millis,
+    /// Microseconds, written as `TIMESTAMP(MICROS)`
+    Micros,
+}
+
 /// Controls the level of statistics to be computed by the writer
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum EnabledStatistics {
@@ -626,9 +858,12 @@ impl Default for BloomFilterProperties {
 struct ColumnProperties {
     encoding: Option<Encoding>,
     codec: Option<Compression>,
+    compression_level: Option<u32>,
     dictionary_enabled: Option<bool>,
     statistics_enabled: Option<EnabledStatistics>,
     max_statistics_size: Option<usize>,
+    /// Whether bloom filter is enabled, `None` if not set
+    bloom_filter_enabled: Option<bool>,
     /// bloom filter related properties
     bloom_filter_properties: Option<BloomFilterProperties>,
 }
@@ -655,6 +890,11 @@ impl ColumnProperties {
         self.codec = Some(value);
     }
 
+    /// Sets compression level for this column.
+    fn set_compression_level(&mut self, value: u32) {
+        self.compression_level = Some(value);
+    }
+
     /// Sets whether or not dictionary encoding is enabled for this column.
     fn set_dictionary_enabled(&mut self, enabled: bool) {
         self.dictionary_enabled = Some(enabled);
@@ -674,6 +914,7 @@ impl ColumnProperties {
     /// otherwise it is a no-op.
     /// If `value` is `false`, resets bloom filter properties to `None`.
     fn set_bloom_filter_enabled(&mut self, value: bool) {
+        self.bloom_filter_enabled = Some(value);
         if value && self.bloom_filter_properties.is_none() {
             self.bloom_filter_properties = Some(Default::default())
         } else if !value {
@@ -694,6 +935,7 @@ impl ColumnProperties {
             value
         );
 
+        self.bloom_filter_enabled = Some(true);
         self.bloom_filter_properties
             .get_or_insert_with(Default::default)
             .fpp = value;
@@ -702,6 +944,7 @@ impl ColumnProperties {
     /// Sets the number of distinct (unique) values for bloom filter for this column, and implicitly
     /// enables bloom filter if not previously enabled.
     fn set_bloom_filter_ndv(&mut self, value: u64) {
+        self.bloom_filter_enabled = Some(true);
         self.bloom_filter_properties
             .get_or_insert_with(Default::default)
             .ndv = value;
@@ -717,6 +960,11 @@ impl ColumnProperties {
         self.codec
     }
 
+    /// Returns optional compression level for this column.
+    fn compression_level(&self) -> Option<u32> {
+        self.compression_level
+    }
+
     /// Returns `Some(true)` if dictionary encoding is enabled for this column, if
     /// disabled then returns `Some(false)`. If result is `None`, then no setting has
     /// been provided.
@@ -735,6 +983,12 @@ impl ColumnProperties {
         self.max_statistics_size
     }
 
+    /// Returns `Some(true)` if bloom filter is enabled for this column, if disabled then
+    /// returns `Some(false)`. If result is `None`, then no setting has been provided.
+    fn bloom_filter_enabled(&self) -> Option<bool> {
+        self.bloom_filter_enabled
+    }
+
     /// Returns the bloom filter properties, or `None` if not enabled
     fn bloom_filter_properties(&self) -> Option<&BloomFilterProperties> {
         self.bloom_filter_properties.as_ref()
@@ -745,6 +999,7 @@ impl ColumnProperties {
 pub type ReaderPropertiesPtr = Arc<ReaderProperties>;
 
 const DEFAULT_READ_BLOOM_FILTER: bool = false;
+const DEFAULT_VERIFY_PAGE_CHECKSUMS: bool = false;
 
 /// Reader properties.
 ///
@@ -753,6 +1008,8 @@ const DEFAULT_READ_BLOOM_FILTER: bool = false;
 pub struct ReaderProperties {
     codec_options: CodecOptions,
     read_bloom_filter: bool,
+    verify_page_checksums: bool,
+    max_page_size: Option<usize>,
 }
 
 impl ReaderProperties {
@@ -770,12 +1027,24 @@ impl ReaderProperties {
     pub(crate) fn read_bloom_filter(&self) -> bool {
         self.read_bloom_filter
     }
+
+    /// Returns whether to verify the checksums of pages
+    pub(crate) fn verify_page_checksums(&self) -> bool {
+        self.verify_page_checksums
+    }
+
+    /// Returns the maximum size of a page that will be read, if limited
+    pub(crate) fn max_page_size(&self) -> Option<usize> {
+        self.max_page_size
+    }
 }
 
 /// Reader properties builder.
 pub struct ReaderPropertiesBuilder {
     codec_options_builder: CodecOptionsBuilder,
     read_bloom_filter: Option<bool>,
+    verify_page_checksums: Option<bool>,
+    max_page_size: Option<usize>,
 }
 
 /// Reader properties builder.
@@ -785,6 +1054,8 @@ impl ReaderPropertiesBuilder {
         Self {
             codec_options_builder: CodecOptionsBuilder::default(),
             read_bloom_filter: None,
+            verify_page_checksums: None,
+            max_page_size: None,
         }
     }
 
@@ -795,6 +1066,10 @@ impl ReaderPropertiesBuilder {
             read_bloom_filter: self
                 .read_bloom_filter
                 .unwrap_or(DEFAULT_READ_BLOOM_FILTER),
+            verify_page_checksums: self
+                .verify_page_checksums
+                .unwrap_or(DEFAULT_VERIFY_PAGE_CHECKSUMS),
+            max_page_size: self.max_page_size,
         }
     }
 
@@ -823,6 +1098,32 @@ impl ReaderPropertiesBuilder {
         self.read_bloom_filter = Some(value);
         self
     }
+
+    /// Enable/disable verifying page checksums
+    ///
+    /// If verifying page checksums is enabled, the CRC32 checksum of each page with
+    /// a `crc` in its header is compared to that of its data before it is decrypted
+    /// and decompressed, and an error is returned on mismatch. Pages without a `crc`
+    /// are read as is.
+    ///
+    /// By default page checksums are not verified.
+    pub fn set_verify_page_checksums(mut self, value: bool) -> Self {
+        self.verify_page_checksums = Some(value);
+        self
+    }
+
+    /// Sets the maximum size in bytes of a page that will be read
+    ///
+    /// Pages are buffered and decompressed in their entirety, and so reading a corrupt
+    /// or malicious file declaring very large pages could exhaust memory. If set, an
+    /// error is returned for any page whose compressed or uncompressed size exceeds
+    /// this limit, before it is buffered.
+    ///
+    /// By default the size of pages is not limited.
+    pub fn set_max_page_size(mut self, value: usize) -> Self {
+        self.max_page_size = Some(value);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -835,6 +1136,18 @@ mod tests {
         assert_eq!(WriterVersion::PARQUET_2_0.as_num(), 2);
     }
 
+    #[test]
+    fn test_writer_version_from_str() {
+        assert_eq!("1".parse::<WriterVersion>().unwrap(), WriterVersion::PARQUET_1_0);
+        assert_eq!("2".parse::<WriterVersion>().unwrap(), WriterVersion::PARQUET_2_0);
+
+        let err = "3".parse::<WriterVersion>().unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Parquet error: Unknown writer version 3: possible values 1, 2"
+        );
+    }
+
     #[test]
     fn test_writer_properties_default_settings() {
         let props = WriterProperties::builder().build();
@@ -848,6 +1161,11 @@ mod tests {
         assert_eq!(props.writer_version(), DEFAULT_WRITER_VERSION);
         assert_eq!(props.created_by(), DEFAULT_CREATED_BY);
         assert_eq!(props.key_value_metadata(), None);
+        assert_eq!(props.write_page_index(), DEFAULT_WRITE_PAGE_INDEX);
+        assert_eq!(props.write_page_checksums(), DEFAULT_WRITE_PAGE_CHECKSUMS);
+        assert_eq!(props.statistics_truncate_length(), None);
+        assert_eq!(props.max_row_group_bytes(), DEFAULT_MAX_ROW_GROUP_BYTES);
+        assert_eq!(props.coerce_timestamps(), None);
         assert_eq!(props.encoding(&ColumnPath::from("col")), None);
         assert_eq!(
             props.compression(&ColumnPath::from("col")),
@@ -931,7 +1249,12 @@ mod tests {
             .set_dictionary_pagesize_limit(20)
             .set_write_batch_size(30)
             .set_max_row_group_size(40)
+            .set_max_row_group_bytes(Some(1024))
             .set_created_by("default".to_owned())
+            .set_write_page_index(false)
+            .set_write_page_checksums(true)
+            .set_statistics_truncate_length(Some(64))
+            .set_coerce_timestamps(Some(TimestampUnit::Micros))
             .set_key_value_metadata(Some(vec![KeyValue::new(
                 "key".to_string(),
                 "value".to_string(),
@@ -939,12 +1262,14 @@ mod tests {
             // global column settings
             .set_encoding(Encoding::DELTA_BINARY_PACKED)
             .set_compression(Compression::GZIP)
+            .set_compression_level(6)
             .set_dictionary_enabled(false)
             .set_statistics_enabled(EnabledStatistics::None)
             .set_max_statistics_size(50)
             // specific column settings
             .set_column_encoding(ColumnPath::from("col"), Encoding::RLE)
-            .set_column_compression(ColumnPath::from("col"), Compression::SNAPPY)
+            .set_column_compression(ColumnPath::from("col"), Compression::ZSTD)
+            .set_column_compression_level(ColumnPath::from("col"), 10)
             .set_column_dictionary_enabled(ColumnPath::from("col"), true)
             .set_column_statistics_enabled(
                 ColumnPath::from("col"),
@@ -961,7 +1286,12 @@ mod tests {
         assert_eq!(props.dictionary_pagesize_limit(), 20);
         assert_eq!(props.write_batch_size(), 30);
         assert_eq!(props.max_row_group_size(), 40);
+        assert_eq!(props.max_row_group_bytes(), Some(1024));
         assert_eq!(props.created_by(), "default");
+        assert!(!props.write_page_index());
+        assert!(props.write_page_checksums());
+        assert_eq!(props.statistics_truncate_length(), Some(64));
+        assert_eq!(props.coerce_timestamps(), Some(TimestampUnit::Micros));
         assert_eq!(
             props.key_value_metadata(),
             Some(&vec![
@@ -974,6 +1304,7 @@ mod tests {
             Some(Encoding::DELTA_BINARY_PACKED)
         );
         assert_eq!(props.compression(&ColumnPath::from("a")), Compression::GZIP);
+        assert_eq!(props.compression_level(&ColumnPath::from("a")), Some(6));
         assert!(!props.dictionary_enabled(&ColumnPath::from("a")));
         assert_eq!(
             props.statistics_enabled(&ColumnPath::from("a")),
@@ -987,8 +1318,9 @@ mod tests {
         );
         assert_eq!(
             props.compression(&ColumnPath::from("col")),
-            Compression::SNAPPY
+            Compression::ZSTD
         );
+        assert_eq!(props.compression_level(&ColumnPath::from("col")), Some(10));
         assert!(props.dictionary_enabled(&ColumnPath::from("col")));
         assert_eq!(
             props.statistics_enabled(&ColumnPath::from("col")),
@@ -1018,6 +1350,7 @@ mod tests {
             props.compression(&ColumnPath::from("col")),
             Compression::GZIP
         );
+        assert_eq!(props.compression_level(&ColumnPath::from("col")), None);
         assert_eq!(
             props.dictionary_enabled(&ColumnPath::from("col")),
             DEFAULT_DICTIONARY_ENABLED
@@ -1061,6 +1394,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_writer_properties_column_bloom_filter() {
+        let props = WriterProperties::builder()
+            .set_bloom_filter_enabled(true)
+            .set_bloom_filter_fpp(0.1)
+            .set_column_bloom_filter_enabled(ColumnPath::from("disabled"), false)
+            .set_column_bloom_filter_ndv(ColumnPath::from("small"), 100)
+            .set_column_encoding(ColumnPath::from("other"), Encoding::PLAIN)
+            .build();
+
+        assert_eq!(
+            props.bloom_filter_properties(&ColumnPath::from("disabled")),
+            None
+        );
+        assert_eq!(
+            props.bloom_filter_properties(&ColumnPath::from("small")),
+            Some(&BloomFilterProperties {
+                fpp: 0.05,
+                ndv: 100
+            })
+        );
+        assert_eq!(
+            props.bloom_filter_properties(&ColumnPath::from("other")),
+            Some(&BloomFilterProperties {
+                fpp: 0.1,
+                ndv: 1_000_000_u64
+            })
+        );
+
+        let props = WriterProperties::builder()
+            .set_column_bloom_filter_fpp(ColumnPath::from("col"), 0.01)
+            .build();
+        assert_eq!(
+            props.bloom_filter_properties(&ColumnPath::from("col")),
+            Some(&BloomFilterProperties {
+                fpp: 0.01,
+                ndv: 1_000_000_u64
+            })
+        );
+        assert_eq!(
+            props.bloom_filter_properties(&ColumnPath::from("other")),
+            None
+        );
+    }
+
     #[test]
     fn test_reader_properties_default_settings() {
         let props = ReaderProperties::builder().build();
@@ -1071,12 +1449,16 @@ mod tests {
 
         assert_eq!(props.codec_options(), &codec_options);
         assert!(!props.read_bloom_filter());
+        assert!(!props.verify_page_checksums());
+        assert_eq!(props.max_page_size(), None);
     }
 
     #[test]
     fn test_reader_properties_builder() {
         let props = ReaderProperties::builder()
             .set_backward_compatible_lz4(false)
+            .set_verify_page_checksums(true)
+            .set_max_page_size(1024)
             .build();
 
         let codec_options = CodecOptionsBuilder::default()
@@ -1084,5 +1466,7 @@ mod tests {
             .build();
 
         assert_eq!(props.codec_options(), &codec_options);
+        assert!(props.verify_page_checksums());
+        assert_eq!(props.max_page_size(), Some(1024));
     }
 }
@@ -0,0 +1,595 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Contains [`RowGroupPruner`] for selecting the row groups of a parquet file that
+//! may contain rows matching simple column predicates, based on their statistics
+//! and bloom filters
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::fs::File;
+//! # use parquet::file::pruning::{ColumnPredicate, RowGroupPruner};
+//! # use parquet::file::reader::SerializedFileReader;
+//! # use parquet::file::serialized_reader::ReadOptionsBuilder;
+//! # use parquet::file::properties::ReaderProperties;
+//! let file = File::open("data.parquet").unwrap();
+//! let options = ReadOptionsBuilder::new()
+//!     .with_reader_properties(
+//!         ReaderProperties::builder().set_read_bloom_filter(true).build(),
+//!     )
+//!     .build();
+//! let reader = SerializedFileReader::new_with_options(file, options).unwrap();
+//!
+//! let row_groups = RowGroupPruner::new()
+//!     .with_predicate("id", ColumnPredicate::Between(100.into(), 200.into()))
+//!     .with_predicate("name", ColumnPredicate::Eq("parquet".into()))
+//!     .prune(&reader)
+//!     .unwrap();
+//! ```
+
+use crate::basic::{ColumnOrder, SortOrder, Type};
+use crate::bloom_filter::Sbbf;
+use crate::data_type::ByteArray;
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::{ParquetMetaData, RowGroupMetaData};
+use crate::file::reader::FileReader;
+use crate::file::statistics::Statistics;
+use crate::schema::types::{ColumnDescriptor, ColumnPath};
+use std::cmp::Ordering;
+
+/// A value compared with the values of a column by a [`ColumnPredicate`].
+///
+/// Values are compared with the statistics of a column according to its physical
+/// type, and must therefore be of the variant corresponding to that type, e.g.
+/// [`ColumnValue::Int32`] for an `INT32` column, whether it is annotated as a date
+/// or an unsigned integer. [`ColumnValue::ByteArray`] is used for both `BYTE_ARRAY`
+/// and `FIXED_LEN_BYTE_ARRAY` columns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValue {
+    Boolean(bool),
+    Int32(i32),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(ByteArray),
+}
+
+impl From<bool> for ColumnValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<i32> for ColumnValue {
+    fn from(value: i32) -> Self {
+        Self::Int32(value)
+    }
+}
+
+impl From<i64> for ColumnValue {
+    fn from(value: i64) -> Self {
+        Self::Int64(value)
+    }
+}
+
+impl From<f32> for ColumnValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<f64> for ColumnValue {
+    fn from(value: f64) -> Self {
+        Self::Double(value)
+    }
+}
+
+impl From<&str> for ColumnValue {
+    fn from(value: &str) -> Self {
+        Self::ByteArray(value.into())
+    }
+}
+
+impl From<Vec<u8>> for ColumnValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::ByteArray(value.into())
+    }
+}
+
+impl ColumnValue {
+    /// Returns the physical type of the columns this value can be compared with
+    fn physical_type(&self) -> Type {
+        match self {
+            ColumnValue::Boolean(_) => Type::BOOLEAN,
+            ColumnValue::Int32(_) => Type::INT32,
+            ColumnValue::Int64(_) => Type::INT64,
+            ColumnValue::Float(_) => Type::FLOAT,
+            ColumnValue::Double(_) => Type::DOUBLE,
+            ColumnValue::ByteArray(_) => Type::BYTE_ARRAY,
+        }
+    }
+
+    /// Returns `true` if `bloom_filter` may contain this value
+    fn check_bloom_filter(&self, bloom_filter: &Sbbf) -> bool {
+        match self {
+            ColumnValue::Boolean(v) => bloom_filter.check(v),
+            ColumnValue::Int32(v) => bloom_filter.check(v),
+            ColumnValue::Int64(v) => bloom_filter.check(v),
+            ColumnValue::Float(v) => bloom_filter.check(v),
+            ColumnValue::Double(v) => bloom_filter.check(v),
+            ColumnValue::ByteArray(v) => bloom_filter.check(v),
+        }
+    }
+}
+
+/// A predicate on the non-null values of a column, evaluated by [`RowGroupPruner`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnPredicate {
+    /// Values equal to the given value
+    Eq(ColumnValue),
+    /// Values less than the given value
+    Lt(ColumnValue),
+    /// Values greater than the given value
+    Gt(ColumnValue),
+    /// Values between the given values, inclusive
+    Between(ColumnValue, ColumnValue),
+    /// Values equal to any of the given values
+    In(Vec<ColumnValue>),
+}
+
+impl ColumnPredicate {
+    /// Returns the values compared with the values of the column
+    fn values(&self) -> Vec<&ColumnValue> {
+        match self {
+            ColumnPredicate::Eq(v) | ColumnPredicate::Lt(v) | ColumnPredicate::Gt(v) => {
+                vec![v]
+            }
+            ColumnPredicate::Between(lo, hi) => vec![lo, hi],
+            ColumnPredicate::In(values) => values.iter().collect(),
+        }
+    }
+
+    /// Returns `false` if no value between the min and max of a column may match
+    /// this predicate, where `cmp(v, true)` compares `v` with the min, and
+    /// `cmp(v, false)` with the max
+    fn may_match_range(
+        &self,
+        cmp: impl Fn(&ColumnValue, bool) -> Option<Ordering>,
+    ) -> bool {
+        // Whether the value is less than the min, or greater than the max
+        let below_min = |v| cmp(v, true) == Some(Ordering::Less);
+        let above_max = |v| cmp(v, false) == Some(Ordering::Greater);
+        match self {
+            ColumnPredicate::Eq(v) => !below_min(v) && !above_max(v),
+            ColumnPredicate::Lt(v) => {
+                !matches!(cmp(v, true), Some(Ordering::Less | Ordering::Equal))
+            }
+            ColumnPredicate::Gt(v) => {
+                !matches!(cmp(v, false), Some(Ordering::Greater | Ordering::Equal))
+            }
+            ColumnPredicate::Between(lo, hi) => !above_max(lo) && !below_min(hi),
+            ColumnPredicate::In(values) => {
+                values.iter().any(|v| !below_min(v) && !above_max(v))
+            }
+        }
+    }
+
+    /// Returns `false` if no value in `bloom_filter` may match this predicate
+    fn may_match_bloom_filter(&self, bloom_filter: &Sbbf) -> bool {
+        match self {
+            ColumnPredicate::Eq(v) => v.check_bloom_filter(bloom_filter),
+            ColumnPredicate::In(values) => {
+                values.iter().any(|v| v.check_bloom_filter(bloom_filter))
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Selects the row groups of a parquet file that may contain rows matching all of a
+/// set of [`ColumnPredicate`]s.
+///
+/// Row groups are pruned if the null count of a column shows all its values are
+/// null, if its min and max statistics show none of its values match, or, for
+/// [`ColumnPredicate::Eq`] and [`ColumnPredicate::In`], if its bloom filter does
+/// not contain any of the values. As statistics and bloom filters are optional,
+/// and the latter may return false positives, the selected row groups may still
+/// contain no matching rows.
+///
+/// Min and max statistics are only used if they are ordered as the values are
+/// compared, that is according to the sort order of the column, and in particular
+/// are ignored for `INT96` columns and columns with a signed byte-wise order, such
+/// as decimals stored as byte arrays.
+#[derive(Debug, Clone, Default)]
+pub struct RowGroupPruner {
+    predicates: Vec<(ColumnPath, ColumnPredicate)>,
+}
+
+impl RowGroupPruner {
+    /// Creates a new [`RowGroupPruner`] without any predicates, selecting all
+    /// row groups
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a predicate on the leaf column at `column`
+    pub fn with_predicate(
+        mut self,
+        column: impl Into<ColumnPath>,
+        predicate: ColumnPredicate,
+    ) -> Self {
+        self.predicates.push((column.into(), predicate));
+        self
+    }
+
+    /// Returns the indices of the row groups of `metadata` that may match the
+    /// predicates, based on their statistics only
+    pub fn prune_metadata(&self, metadata: &ParquetMetaData) -> Result<Vec<usize>> {
+        let columns = self.resolve(metadata)?;
+        Ok((0..metadata.num_row_groups())
+            .filter(|i| self.may_match_statistics(&columns, metadata.row_group(*i)))
+            .collect())
+    }
+
+    /// Returns the indices of the row groups of `reader` that may match the
+    /// predicates, based on their statistics and any bloom filters read by `reader`
+    ///
+    /// Bloom filters are only read if enabled by
+    /// [`ReaderPropertiesBuilder::set_read_bloom_filter`].
+    ///
+    /// [`ReaderPropertiesBuilder::set_read_bloom_filter`]: crate::file::properties::ReaderPropertiesBuilder::set_read_bloom_filter
+    pub fn prune(&self, reader: &dyn FileReader) -> Result<Vec<usize>> {
+        let metadata = reader.metadata();
+        let columns = self.resolve(metadata)?;
+        let checks_bloom_filters = self.predicates.iter().any(|(_, predicate)| {
+            matches!(predicate, ColumnPredicate::Eq(_) | ColumnPredicate::In(_))
+        });
+
+        let mut selected = Vec::new();
+        for i in 0..metadata.num_row_groups() {
+            if !self.may_match_statistics(&columns, metadata.row_group(i)) {
+                continue;
+            }
+            if checks_bloom_filters {
+                let row_group = reader.get_row_group(i)?;
+                let may_match = self.predicates.iter().zip(&columns).all(
+                    |((_, predicate), (column, _))| match row_group
+                        .get_column_bloom_filter(*column)
+                    {
+                        Some(bloom_filter) => {
+                            predicate.may_match_bloom_filter(bloom_filter)
+                        }
+                        None => true,
+                    },
+                );
+                if !may_match {
+                    continue;
+                }
+            }
+            selected.push(i);
+        }
+        Ok(selected)
+    }
+
+    /// Returns the index and descriptor of the column of each predicate, checking
+    /// the types of their values
+    fn resolve<'a>(
+        &self,
+        metadata: &'a ParquetMetaData,
+    ) -> Result<Vec<(usize, &'a ColumnDescriptor)>> {
+        let schema = metadata.file_metadata().schema_descr();
+        self.predicates
+            .iter()
+            .map(|(path, predicate)| {
+                let index = schema
+                    .columns()
+                    .iter()
+                    .position(|c| c.path() == path)
+                    .ok_or_else(|| general_err!("Column {} not found", path))?;
+                let descr = schema.columns()[index].as_ref();
+                let physical_type = match descr.physical_type() {
+                    Type::FIXED_LEN_BYTE_ARRAY => Type::BYTE_ARRAY,
+                    physical_type => physical_type,
+                };
+                if let Some(v) = predicate
+                    .values()
+                    .into_iter()
+                    .find(|v| v.physical_type() != physical_type)
+                {
+                    return Err(general_err!(
+                        "Cannot compare {:?} with column {} of type {}",
+                        v,
+                        path,
+                        descr.physical_type()
+                    ));
+                }
+                Ok((index, descr))
+            })
+            .collect()
+    }
+
+    /// Returns `false` if the statistics of `row_group` show no rows match
+    fn may_match_statistics(
+        &self,
+        columns: &[(usize, &ColumnDescriptor)],
+        row_group: &RowGroupMetaData,
+    ) -> bool {
+        self.predicates
+            .iter()
+            .zip(columns)
+            .all(|((_, predicate), (index, descr))| {
+                let column = row_group.column(*index);
+                let statistics = match column.statistics() {
+                    Some(statistics) => statistics,
+                    None => return true,
+                };
+                if column.num_values() > 0
+                    && statistics.null_count() == column.num_values() as u64
+                {
+                    return false;
+                }
+                if !statistics.has_min_max_set() {
+                    return true;
+                }
+
+                let sort_order = ColumnOrder::get_sort_order(
+                    descr.logical_type(),
+                    descr.converted_type(),
+                    descr.physical_type(),
+                );
+                // Deprecated min and max are ordered as signed values
+                if statistics.is_min_max_deprecated() && sort_order != SortOrder::SIGNED {
+                    return true;
+                }
+                predicate
+                    .may_match_range(|v, min| compare(v, statistics, min, sort_order))
+            })
+    }
+}
+
+/// Compares `value` with the min, if `min`, or max of `statistics`, according
+/// to `sort_order`, returning `None` if they cannot be compared
+fn compare(
+    value: &ColumnValue,
+    statistics: &Statistics,
+    min: bool,
+    sort_order: SortOrder,
+) -> Option<Ordering> {
+    macro_rules! pick {
+        ($s:expr) => {
+            if min {
+                $s.min()
+            } else {
+                $s.max()
+            }
+        };
+    }
+
+    match (value, statistics, sort_order) {
+        (ColumnValue::Boolean(v), Statistics::Boolean(s), _) => v.partial_cmp(pick!(s)),
+        (ColumnValue::Int32(v), Statistics::Int32(s), SortOrder::SIGNED) => {
+            v.partial_cmp(pick!(s))
+        }
+        (ColumnValue::Int32(v), Statistics::Int32(s), SortOrder::UNSIGNED) => {
+            (*v as u32).partial_cmp(&(*pick!(s) as u32))
+        }
+        (ColumnValue::Int64(v), Statistics::Int64(s), SortOrder::SIGNED) => {
+            v.partial_cmp(pick!(s))
+        }
+        (ColumnValue::Int64(v), Statistics::Int64(s), SortOrder::UNSIGNED) => {
+            (*v as u64).partial_cmp(&(*pick!(s) as u64))
+        }
+        (ColumnValue::Float(v), Statistics::Float(s), SortOrder::SIGNED) => {
+            v.partial_cmp(pick!(s))
+        }
+        (ColumnValue::Double(v), Statistics::Double(s), SortOrder::SIGNED) => {
+            v.partial_cmp(pick!(s))
+        }
+        (ColumnValue::ByteArray(v), Statistics::ByteArray(s), SortOrder::UNSIGNED) => {
+            v.partial_cmp(pick!(s))
+        }
+        (
+            ColumnValue::ByteArray(v),
+            Statistics::FixedLenByteArray(s),
+            SortOrder::UNSIGNED,
+        ) => v.partial_cmp(pick!(s)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::data_type::{ByteArrayType, Int32Type};
+    use crate::file::properties::{ReaderProperties, WriterProperties};
+    use crate::file::reader::SerializedFileReader;
+    use crate::file::serialized_reader::ReadOptionsBuilder;
+    use crate::file::writer::SerializedFileWriter;
+    use crate::schema::parser::parse_message_type;
+    use bytes::Bytes;
+    use std::sync::Arc;
+
+    /// The values of the columns `id`, `maybe_id` and `name` of a row group
+    type RowGroupValues<'a> = (Vec<i32>, Vec<Option<i32>>, Vec<&'a str>);
+
+    /// Writes a file with a row group for each of `row_groups`
+    fn write_file(row_groups: &[RowGroupValues<'_>]) -> Bytes {
+        let schema = Arc::new(
+            parse_message_type(
+                "message schema {
+                    REQUIRED INT32 id;
+                    OPTIONAL INT32 maybe_id (INTEGER(32, false));
+                    REQUIRED BYTE_ARRAY name (STRING);
+                }",
+            )
+            .unwrap(),
+        );
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_column_bloom_filter_enabled("name".into(), true)
+                .build(),
+        );
+        let mut buf = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        for (ids, maybe_ids, names) in row_groups {
+            let mut row_group_writer = writer.next_row_group().unwrap();
+
+            let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer
+                .typed::<Int32Type>()
+                .write_batch(ids, None, None)
+                .unwrap();
+            column_writer.close().unwrap();
+
+            let values: Vec<_> = maybe_ids.iter().flatten().cloned().collect();
+            let def_levels: Vec<_> =
+                maybe_ids.iter().map(|v| v.is_some() as i16).collect();
+            let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer
+                .typed::<Int32Type>()
+                .write_batch(&values, Some(&def_levels), None)
+                .unwrap();
+            column_writer.close().unwrap();
+
+            let values: Vec<ByteArray> = names.iter().map(|v| (*v).into()).collect();
+            let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer
+                .typed::<ByteArrayType>()
+                .write_batch(&values, None, None)
+                .unwrap();
+            column_writer.close().unwrap();
+
+            row_group_writer.close().unwrap();
+        }
+        writer.close().unwrap();
+        buf.into()
+    }
+
+    fn test_file() -> SerializedFileReader<Bytes> {
+        let data = write_file(&[
+            (
+                vec![1, 2, 3],
+                vec![Some(1), None, Some(-1)],
+                vec!["a", "b", "c"],
+            ),
+            (vec![4, 5, 6], vec![None, None, None], vec!["d", "f", "h"]),
+            (
+                vec![7, 8, 9],
+                vec![Some(7), None, Some(9)],
+                vec!["i", "j", "k"],
+            ),
+        ]);
+        let options = ReadOptionsBuilder::new()
+            .with_reader_properties(
+                ReaderProperties::builder()
+                    .set_read_bloom_filter(true)
+                    .build(),
+            )
+            .build();
+        SerializedFileReader::new_with_options(data, options).unwrap()
+    }
+
+    fn prune(predicates: Vec<(&str, ColumnPredicate)>) -> Vec<usize> {
+        let reader = test_file();
+        let pruner = predicates
+            .into_iter()
+            .fold(RowGroupPruner::new(), |pruner, (column, predicate)| {
+                pruner.with_predicate(column, predicate)
+            });
+        pruner.prune(&reader).unwrap()
+    }
+
+    #[test]
+    fn test_prune_statistics() {
+        use ColumnPredicate::*;
+
+        assert_eq!(prune(vec![]), vec![0, 1, 2]);
+        assert_eq!(prune(vec![("id", Eq(5.into()))]), vec![1]);
+        assert_eq!(prune(vec![("id", Eq(10.into()))]), Vec::<usize>::new());
+        assert_eq!(prune(vec![("id", Lt(4.into()))]), vec![0]);
+        assert_eq!(prune(vec![("id", Lt(5.into()))]), vec![0, 1]);
+        assert_eq!(prune(vec![("id", Gt(6.into()))]), vec![2]);
+        assert_eq!(
+            prune(vec![("id", Between(3.into(), 7.into()))]),
+            vec![0, 1, 2]
+        );
+        assert_eq!(prune(vec![("id", Between(4.into(), 6.into()))]), vec![1]);
+        assert_eq!(
+            prune(vec![("id", In(vec![1.into(), 9.into()]))]),
+            vec![0, 2]
+        );
+        assert_eq!(
+            prune(vec![("id", Gt(2.into())), ("id", Lt(8.into()))]),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            prune(vec![("id", Gt(2.into())), ("name", Lt("d".into()))]),
+            vec![0]
+        );
+
+        // Row group 1 has only nulls, and -1 is the max of row group 0 as unsigned
+        assert_eq!(prune(vec![("maybe_id", Gt(1.into()))]), vec![0, 2]);
+        assert_eq!(prune(vec![("maybe_id", Eq((-1).into()))]), vec![0]);
+        assert_eq!(prune(vec![("maybe_id", Lt(7.into()))]), vec![0]);
+    }
+
+    #[test]
+    fn test_prune_bloom_filter() {
+        use ColumnPredicate::*;
+
+        // "e" and "g" are between the min and max of row group 1 but not in it
+        let reader = test_file();
+        let pruner = RowGroupPruner::new().with_predicate("name", Eq("e".into()));
+        assert_eq!(pruner.prune_metadata(reader.metadata()).unwrap(), vec![1]);
+        assert_eq!(pruner.prune(&reader).unwrap(), Vec::<usize>::new());
+
+        assert_eq!(prune(vec![("name", Eq("f".into()))]), vec![1]);
+        assert_eq!(
+            prune(vec![("name", In(vec!["e".into(), "g".into()]))]),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            prune(vec![("name", In(vec!["b".into(), "g".into()]))]),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn test_prune_errors() {
+        let reader = test_file();
+
+        let err = RowGroupPruner::new()
+            .with_predicate("missing", ColumnPredicate::Eq(1.into()))
+            .prune(&reader)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Column \"missing\" not found"
+        );
+
+        let err = RowGroupPruner::new()
+            .with_predicate("id", ColumnPredicate::Eq(1_i64.into()))
+            .prune(&reader)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Cannot compare Int64(1) with column \"id\" of type INT32"
+        );
+    }
+}
@@ -0,0 +1,874 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Contains [`ParquetRewriter`] for rewriting an existing parquet file with
+//! different [`WriterProperties`], e.g. to change its compression
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::fs::File;
+//! # use parquet::basic::Compression;
+//! # use parquet::file::properties::WriterProperties;
+//! # use parquet::file::rewriter::ParquetRewriter;
+//! let input = File::open("data.parquet").unwrap();
+//! let output = File::create("data.zstd.parquet").unwrap();
+//!
+//! let props = WriterProperties::builder()
+//!     .set_compression(Compression::ZSTD)
+//!     .build();
+//!
+//! ParquetRewriter::try_new(input, props)
+//!     .unwrap()
+//!     .with_dropped_columns(["address.city"])
+//!     .write(output)
+//!     .unwrap();
+//! ```
+
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::basic::{PageType, Type as PhysicalType};
+use crate::column::page::{CompressedPage, Page, PageReader};
+use crate::column::reader::ColumnReaderImpl;
+use crate::column::writer::{
+    get_typed_column_writer_mut, ColumnCloseResult, ColumnWriter,
+};
+use crate::compression::{create_codec, Codec, CodecOptionsBuilder};
+use crate::data_type::*;
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::{ColumnChunkMetaData, OffsetIndexBuilder};
+use crate::file::properties::{WriterProperties, WriterPropertiesPtr};
+use crate::file::reader::{ChunkReader, FileReader, RowGroupReader};
+use crate::file::serialized_reader::SerializedFileReader;
+use crate::file::writer::{
+    SerializedColumnWriter, SerializedFileWriter, SerializedRowGroupWriter,
+};
+use crate::format as parquet;
+use crate::schema::types::{ColumnDescPtr, Type, TypePtr};
+use crate::util::memory::ByteBufferPtr;
+
+/// Rewrites an existing parquet file with different [`WriterProperties`]
+///
+/// Where possible column chunks are rewritten page by page, decompressing each
+/// page and compressing it again with the codec selected by [`WriterProperties::compression`],
+/// without decoding its values. This preserves the encodings and page layout of the
+/// original file, along with the page and column chunk statistics.
+///
+/// The values of a column chunk are instead decoded and encoded again, as if written by
+/// a [`SerializedFileWriter`], if:
+///
+/// * [`Self::with_reencode`] is set
+/// * [`WriterProperties::encoding`] is set for the column
+/// * [`WriterProperties::bloom_filter_properties`] is set for the column
+/// * The row group contains more than [`WriterProperties::max_row_group_size`] rows,
+///   in which case it is split into multiple row groups
+///
+/// Row groups are never combined, and so each row group of the original file results in
+/// one or more row groups in the rewritten file.
+///
+/// The key value metadata of the original file is preserved, unless it contains a key
+/// also present in [`WriterProperties::key_value_metadata`].
+pub struct ParquetRewriter<R: ChunkReader> {
+    reader: SerializedFileReader<R>,
+    props: WriterPropertiesPtr,
+    dropped_columns: Vec<String>,
+    reencode: bool,
+}
+
+impl<R: 'static + ChunkReader> ParquetRewriter<R> {
+    /// Create a new [`ParquetRewriter`] that will rewrite the parquet file read from
+    /// `reader` using `props`
+    pub fn try_new(reader: R, props: WriterProperties) -> Result<Self> {
+        Ok(Self {
+            reader: SerializedFileReader::new(reader)?,
+            props: Arc::new(props),
+            dropped_columns: vec![],
+            reencode: false,
+        })
+    }
+
+    /// Drop the columns with the specified dotted paths, e.g. `address.city`,
+    /// from the rewritten file
+    ///
+    /// The path of a group column drops all of its leaves, and any group column
+    /// left without any leaves is also dropped
+    pub fn with_dropped_columns<'a>(
+        mut self,
+        paths: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.dropped_columns
+            .extend(paths.into_iter().map(ToString::to_string));
+        self
+    }
+
+    /// Decode and encode again the values of all column chunks, instead of
+    /// rewriting their pages, e.g. to change dictionary encoding or the data page version
+    pub fn with_reencode(self, reencode: bool) -> Self {
+        Self { reencode, ..self }
+    }
+
+    /// Write the rewritten file to `writer`, returning its metadata
+    pub fn write<W: Write>(self, writer: W) -> Result<parquet::FileMetaData> {
+        let metadata = self.reader.metadata();
+        let file_metadata = metadata.file_metadata();
+        let schema = file_metadata.schema_descr();
+
+        let columns: Vec<_> = schema
+            .columns()
+            .iter()
+            .map(|c| {
+                let path = c.path().string();
+                !self.dropped_columns.iter().any(|dropped| {
+                    match path.strip_prefix(dropped.as_str()) {
+                        Some(suffix) => suffix.is_empty() || suffix.starts_with('.'),
+                        None => false,
+                    }
+                })
+            })
+            .collect();
+
+        let root = prune_type(&schema.root_schema_ptr(), &mut columns.iter().copied())?
+            .ok_or_else(|| general_err!("Cannot drop all columns"))?;
+
+        let mut props = self.props.clone();
+        if let Some(kv) = file_metadata.key_value_metadata() {
+            let existing = props.key_value_metadata().cloned().unwrap_or_default();
+            let missing: Vec<_> = kv
+                .iter()
+                .filter(|kv| !existing.iter().any(|x| x.key == kv.key))
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                Arc::make_mut(&mut props).key_value_metadata =
+                    Some(existing.into_iter().chain(missing).collect());
+            }
+        }
+        let mut writer = SerializedFileWriter::new(writer, root, props)?;
+
+        let columns: Vec<_> = columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, keep)| keep.then_some(idx))
+            .collect();
+
+        for rg_idx in 0..metadata.num_row_groups() {
+            let row_group = self.reader.get_row_group(rg_idx)?;
+            let num_rows = row_group.metadata().num_rows() as usize;
+            let max_rows = self.props.max_row_group_size();
+
+            if num_rows > max_rows {
+                self.split_row_group(&mut writer, row_group.as_ref(), &columns)?;
+                continue;
+            }
+
+            let mut rg_writer = writer.next_row_group()?;
+            for col_idx in columns.iter() {
+                let column = row_group.metadata().column(*col_idx);
+                let pages = row_group.get_column_page_reader(*col_idx)?;
+                let descr = column.column_descr_ptr();
+                match self.needs_reencode(&descr) {
+                    true => {
+                        let mut copier = record_copier(descr, pages);
+                        let mut col_writer = next_column(&mut rg_writer)?;
+                        copier.copy_records(num_rows, col_writer.untyped())?;
+                        col_writer.close()?;
+                    }
+                    false => copy_pages(&mut rg_writer, pages, column, num_rows)?,
+                }
+            }
+            rg_writer.close()?;
+        }
+
+        writer.close()
+    }
+
+    /// Returns true if `column` cannot be rewritten by recompressing its pages
+    fn needs_reencode(&self, column: &ColumnDescPtr) -> bool {
+        self.reencode
+            || self.props.encoding(column.path()).is_some()
+            || self.props.bloom_filter_properties(column.path()).is_some()
+    }
+
+    /// Rewrites the row group `row_group` into row groups containing at most
+    /// [`WriterProperties::max_row_group_size`] rows
+    fn split_row_group<W: Write>(
+        &self,
+        writer: &mut SerializedFileWriter<W>,
+        row_group: &dyn RowGroupReader,
+        columns: &[usize],
+    ) -> Result<()> {
+        let schema = row_group.metadata().schema_descr();
+        let mut copiers = columns
+            .iter()
+            .map(|idx| {
+                let pages = row_group.get_column_page_reader(*idx)?;
+                Ok(record_copier(schema.column(*idx), pages))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let num_rows = row_group.metadata().num_rows() as usize;
+        let max_rows = self.props.max_row_group_size();
+        for offset in (0..num_rows).step_by(max_rows) {
+            let to_copy = max_rows.min(num_rows - offset);
+            let mut rg_writer = writer.next_row_group()?;
+            for copier in copiers.iter_mut() {
+                let mut col_writer = next_column(&mut rg_writer)?;
+                copier.copy_records(to_copy, col_writer.untyped())?;
+                col_writer.close()?;
+            }
+            rg_writer.close()?;
+        }
+        Ok(())
+    }
+}
+
+fn next_column<'a, W: Write>(
+    rg_writer: &'a mut SerializedRowGroupWriter<'_, W>,
+) -> Result<SerializedColumnWriter<'a>> {
+    rg_writer
+        .next_column()?
+        .ok_or_else(|| general_err!("Missing column writer"))
+}
+
+/// Returns a copy of `tp` with only the leaves selected by `leaves`, or
+/// `None` if no leaves are selected
+fn prune_type(
+    tp: &TypePtr,
+    leaves: &mut impl Iterator<Item = bool>,
+) -> Result<Option<TypePtr>> {
+    if tp.is_primitive() {
+        let keep = leaves
+            .next()
+            .ok_or_else(|| general_err!("Schema contains more leaves than expected"))?;
+        return Ok(keep.then(|| tp.clone()));
+    }
+
+    let children = tp.get_fields();
+    let mut fields = Vec::with_capacity(children.len());
+    for child in children {
+        if let Some(child) = prune_type(child, leaves)? {
+            fields.push(child);
+        }
+    }
+
+    if fields.is_empty() {
+        return Ok(None);
+    }
+
+    let unchanged = fields.iter().zip(children).all(|(a, b)| Arc::ptr_eq(a, b));
+    if fields.len() == children.len() && unchanged {
+        return Ok(Some(tp.clone()));
+    }
+
+    let info = tp.get_basic_info();
+    let mut builder = Type::group_type_builder(info.name())
+        .with_converted_type(info.converted_type())
+        .with_logical_type(info.logical_type())
+        .with_fields(&mut fields)
+        .with_id(info.has_id().then(|| info.id()));
+    if info.has_repetition() {
+        builder = builder.with_repetition(info.repetition());
+    }
+    Ok(Some(Arc::new(builder.build()?)))
+}
+
+/// Writes the pages of `pages` to the next column of `rg_writer`, compressing them with
+/// the codec selected by [`WriterProperties::compression`]
+fn copy_pages<W: Write>(
+    rg_writer: &mut SerializedRowGroupWriter<'_, W>,
+    mut pages: Box<dyn PageReader>,
+    column: &ColumnChunkMetaData,
+    num_rows: usize,
+) -> Result<()> {
+    rg_writer
+        .next_column_with_factory(|descr, props, mut page_writer, on_close| {
+            let codec = props.compression(descr.path());
+            let options = CodecOptionsBuilder::default()
+                .set_compression_level(props.compression_level(descr.path()))
+                .build();
+            let mut compressor = create_codec(codec, &options)?;
+
+            let mut total_compressed_size = 0;
+            let mut total_uncompressed_size = 0;
+            let mut bytes_written = 0;
+            let mut data_page_offset = None;
+            let mut dictionary_page_offset = None;
+
+            let mut offset_index = Some(OffsetIndexBuilder::new());
+            while let Some(page) = pages.get_next_page()? {
+                let page_rows = match &page {
+                    Page::DataPageV2 { num_rows, .. } => Some(*num_rows),
+                    Page::DataPage { num_values, .. } if descr.max_rep_level() == 0 => {
+                        Some(*num_values)
+                    }
+                    _ => None,
+                };
+
+                let page = compress_page(page, compressor.as_mut())?;
+                let page_type = page.page_type();
+                let spec = page_writer.write_page(page)?;
+
+                total_compressed_size += spec.compressed_size as i64;
+                total_uncompressed_size += spec.uncompressed_size as i64;
+                bytes_written += spec.bytes_written;
+
+                match page_type {
+                    PageType::DICTIONARY_PAGE => {
+                        dictionary_page_offset = Some(spec.offset as i64);
+                    }
+                    _ => {
+                        data_page_offset.get_or_insert(spec.offset as i64);
+                        // The offset index requires the number of rows in each page
+                        match (offset_index.as_mut(), page_rows) {
+                            (Some(builder), Some(rows)) => {
+                                builder.append_row_count(rows as i64);
+                                builder.append_offset_and_size(
+                                    spec.offset as i64,
+                                    spec.compressed_size as i32,
+                                );
+                            }
+                            _ => offset_index = None,
+                        }
+                    }
+                }
+            }
+
+            let data_page_offset = data_page_offset.unwrap_or(0);
+            let file_offset = dictionary_page_offset.unwrap_or(data_page_offset)
+                + total_compressed_size;
+
+            let mut builder = ColumnChunkMetaData::builder(descr)
+                .set_compression(codec)
+                .set_encodings(column.encodings().clone())
+                .set_file_offset(file_offset)
+                .set_total_compressed_size(total_compressed_size)
+                .set_total_uncompressed_size(total_uncompressed_size)
+                .set_num_values(column.num_values())
+                .set_data_page_offset(data_page_offset)
+                .set_dictionary_page_offset(dictionary_page_offset);
+            if let Some(statistics) = column.statistics() {
+                builder = builder.set_statistics(statistics.clone());
+            }
+            if let Some(stats) = column.page_encoding_stats() {
+                builder = builder.set_page_encoding_stats(stats.clone());
+            }
+            let metadata = builder.build()?;
+
+            page_writer.write_metadata(&metadata)?;
+            page_writer.close()?;
+
+            let offset_index = match props.write_page_index() {
+                true => offset_index.map(|b| b.build_to_thrift()),
+                false => None,
+            };
+
+            on_close(ColumnCloseResult {
+                bytes_written,
+                rows_written: num_rows as u64,
+                metadata,
+                bloom_filter: None,
+                column_index: None,
+                offset_index,
+            })
+        })?
+        .ok_or_else(|| general_err!("Missing column writer"))
+}
+
+/// Compresses the uncompressed `page` returned by a [`PageReader`] with `compressor`
+fn compress_page(
+    page: Page,
+    compressor: Option<&mut Box<dyn Codec>>,
+) -> Result<CompressedPage> {
+    Ok(match page {
+        Page::DataPage {
+            buf,
+            num_values,
+            encoding,
+            def_level_encoding,
+            rep_level_encoding,
+            statistics,
+        } => {
+            let uncompressed_size = buf.len();
+            let page = Page::DataPage {
+                buf: compress_buffer(compressor, buf.data())?,
+                num_values,
+                encoding,
+                def_level_encoding,
+                rep_level_encoding,
+                statistics,
+            };
+            CompressedPage::new(page, uncompressed_size)
+        }
+        Page::DataPageV2 {
+            buf,
+            num_values,
+            encoding,
+            num_nulls,
+            num_rows,
+            def_levels_byte_len,
+            rep_levels_byte_len,
+            statistics,
+            ..
+        } => {
+            let uncompressed_size = buf.len();
+            let levels_len = (def_levels_byte_len + rep_levels_byte_len) as usize;
+            let (levels, values) = buf.data().split_at(levels_len);
+
+            // Data Page v2 compresses values only, and stores them uncompressed
+            // if compression does not reduce their size.
+            let mut output = levels.to_vec();
+            let mut is_compressed = false;
+            if let Some(compressor) = compressor {
+                if !values.is_empty() {
+                    let mut compressed = Vec::with_capacity(values.len());
+                    compressor.compress(values, &mut compressed)?;
+                    if compressed.len() < values.len() {
+                        output.extend_from_slice(&compressed);
+                        is_compressed = true;
+                    }
+                }
+            }
+            if !is_compressed {
+                output.extend_from_slice(values);
+            }
+
+            let page = Page::DataPageV2 {
+                buf: ByteBufferPtr::new(output),
+                num_values,
+                encoding,
+                num_nulls,
+                num_rows,
+                def_levels_byte_len,
+                rep_levels_byte_len,
+                is_compressed,
+                statistics,
+            };
+            CompressedPage::new(page, uncompressed_size)
+        }
+        Page::DictionaryPage {
+            buf,
+            num_values,
+            encoding,
+            is_sorted,
+        } => {
+            let uncompressed_size = buf.len();
+            let page = Page::DictionaryPage {
+                buf: compress_buffer(compressor, buf.data())?,
+                num_values,
+                encoding,
+                is_sorted,
+            };
+            CompressedPage::new(page, uncompressed_size)
+        }
+    })
+}
+
+/// Compresses `buf` with `compressor`, if any
+fn compress_buffer(
+    compressor: Option<&mut Box<dyn Codec>>,
+    buf: &[u8],
+) -> Result<ByteBufferPtr> {
+    let mut output = Vec::with_capacity(buf.len());
+    match compressor {
+        Some(compressor) => compressor.compress(buf, &mut output)?,
+        None => output.extend_from_slice(buf),
+    }
+    Ok(ByteBufferPtr::new(output))
+}
+
+/// Copies records from a column chunk to a [`ColumnWriter`] by decoding their values
+trait CopyRecords {
+    /// Copies the next `num_records` records to `writer`
+    fn copy_records(
+        &mut self,
+        num_records: usize,
+        writer: &mut ColumnWriter<'_>,
+    ) -> Result<()>;
+}
+
+/// The number of levels decoded from the column chunk at a time
+const BATCH_SIZE: usize = 1024;
+
+/// A [`CopyRecords`] that buffers decoded levels and values, so that records
+/// are not split across calls to [`CopyRecords::copy_records`]
+struct RecordCopierImpl<T: DataType> {
+    reader: ColumnReaderImpl<T>,
+    descr: ColumnDescPtr,
+    values: Vec<T::T>,
+    def_levels: Vec<i16>,
+    rep_levels: Vec<i16>,
+}
+
+/// Returns a [`CopyRecords`] for the column chunk described by `descr` with `pages`
+fn record_copier(
+    descr: ColumnDescPtr,
+    pages: Box<dyn PageReader>,
+) -> Box<dyn CopyRecords> {
+    fn new<T: DataType>(
+        descr: ColumnDescPtr,
+        pages: Box<dyn PageReader>,
+    ) -> Box<dyn CopyRecords> {
+        Box::new(RecordCopierImpl::<T> {
+            reader: ColumnReaderImpl::new(descr.clone(), pages),
+            descr,
+            values: vec![],
+            def_levels: vec![],
+            rep_levels: vec![],
+        })
+    }
+
+    match descr.physical_type() {
+        PhysicalType::BOOLEAN => new::<BoolType>(descr, pages),
+        PhysicalType::INT32 => new::<Int32Type>(descr, pages),
+        PhysicalType::INT64 => new::<Int64Type>(descr, pages),
+        PhysicalType::INT96 => new::<Int96Type>(descr, pages),
+        PhysicalType::FLOAT => new::<FloatType>(descr, pages),
+        PhysicalType::DOUBLE => new::<DoubleType>(descr, pages),
+        PhysicalType::BYTE_ARRAY => new::<ByteArrayType>(descr, pages),
+        PhysicalType::FIXED_LEN_BYTE_ARRAY => new::<FixedLenByteArrayType>(descr, pages),
+    }
+}
+
+impl<T: DataType> RecordCopierImpl<T> {
+    /// Returns the number of buffered levels that make up the first `num_records`
+    /// records, or `None` if it cannot yet be determined from the buffered levels
+    fn split_point(&self, num_records: usize) -> Option<usize> {
+        match self.descr.max_rep_level() {
+            0 => (self.num_buffered_levels() >= num_records).then_some(num_records),
+            _ => self
+                .rep_levels
+                .iter()
+                .enumerate()
+                .filter(|(_, level)| **level == 0)
+                .nth(num_records)
+                .map(|(idx, _)| idx),
+        }
+    }
+
+    fn num_buffered_levels(&self) -> usize {
+        match self.descr.max_def_level() {
+            0 => self.values.len(),
+            _ => self.def_levels.len(),
+        }
+    }
+
+    /// Decodes the next batch of levels and values, returning the number of levels read
+    fn read_batch(&mut self) -> Result<usize> {
+        let mut values = vec![T::T::default(); BATCH_SIZE];
+        let mut def_levels = vec![0; BATCH_SIZE];
+        let mut rep_levels = vec![0; BATCH_SIZE];
+
+        let max_def_level = self.descr.max_def_level();
+        let max_rep_level = self.descr.max_rep_level();
+        let (values_read, levels_read) = self.reader.read_batch(
+            BATCH_SIZE,
+            (max_def_level > 0).then_some(&mut def_levels[..]),
+            (max_rep_level > 0).then_some(&mut rep_levels[..]),
+            &mut values,
+        )?;
+
+        self.values.extend(values.drain(..values_read));
+        if max_def_level > 0 {
+            self.def_levels
+                .extend_from_slice(&def_levels[..levels_read]);
+        }
+        if max_rep_level > 0 {
+            self.rep_levels
+                .extend_from_slice(&rep_levels[..levels_read]);
+        }
+        Ok(levels_read)
+    }
+}
+
+impl<T: DataType> CopyRecords for RecordCopierImpl<T> {
+    fn copy_records(
+        &mut self,
+        num_records: usize,
+        writer: &mut ColumnWriter<'_>,
+    ) -> Result<()> {
+        let levels = loop {
+            if let Some(levels) = self.split_point(num_records) {
+                break levels;
+            }
+            if self.read_batch()? == 0 {
+                break self.num_buffered_levels();
+            }
+        };
+
+        let max_def_level = self.descr.max_def_level();
+        let num_values = match max_def_level {
+            0 => levels,
+            _ => self.def_levels[..levels]
+                .iter()
+                .filter(|level| **level == max_def_level)
+                .count(),
+        };
+
+        let def_levels = (max_def_level > 0).then(|| &self.def_levels[..levels]);
+        let rep_levels =
+            (self.descr.max_rep_level() > 0).then(|| &self.rep_levels[..levels]);
+
+        let writer = get_typed_column_writer_mut::<T>(writer);
+        writer.write_batch(&self.values[..num_values], def_levels, rep_levels)?;
+
+        self.values.drain(..num_values);
+        if def_levels.is_some() {
+            self.def_levels.drain(..levels);
+        }
+        if rep_levels.is_some() {
+            self.rep_levels.drain(..levels);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bytes::Bytes;
+
+    use crate::basic::{Compression, Encoding};
+    use crate::file::metadata::KeyValue;
+    use crate::file::properties::WriterVersion;
+    use crate::record::{Row, RowAccessor};
+    use crate::schema::parser::parse_message_type;
+
+    /// Writes a file containing `num_rows` rows in row groups of `row_group_size`
+    fn write_file(
+        num_rows: usize,
+        row_group_size: usize,
+        version: WriterVersion,
+    ) -> Bytes {
+        let message_type = "
+        message test_schema {
+            REQUIRED INT32 id;
+            OPTIONAL BYTE_ARRAY name (UTF8);
+            OPTIONAL group tags (LIST) {
+                REPEATED group list {
+                    OPTIONAL INT64 element;
+                }
+            }
+        }
+        ";
+        let schema = Arc::new(parse_message_type(message_type).unwrap());
+        let props = WriterProperties::builder()
+            .set_writer_version(version)
+            .set_data_page_row_count_limit(7)
+            .set_write_batch_size(7)
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                "key".to_string(),
+                "value".to_string(),
+            )]))
+            .build();
+
+        let mut buffer = Vec::new();
+        let mut writer =
+            SerializedFileWriter::new(&mut buffer, schema, Arc::new(props)).unwrap();
+
+        for offset in (0..num_rows).step_by(row_group_size) {
+            let rows = row_group_size.min(num_rows - offset);
+            let mut rg_writer = writer.next_row_group().unwrap();
+
+            let ids: Vec<_> = (offset as i32..(offset + rows) as i32).collect();
+            let mut col = rg_writer.next_column().unwrap().unwrap();
+            col.typed::<Int32Type>()
+                .write_batch(&ids, None, None)
+                .unwrap();
+            col.close().unwrap();
+
+            let names: Vec<ByteArray> = ids
+                .iter()
+                .filter(|x| *x % 3 != 0)
+                .map(|x| ByteArray::from(format!("name_{}", x % 5).as_str()))
+                .collect();
+            let def: Vec<_> = ids.iter().map(|x| (x % 3 != 0) as i16).collect();
+            let mut col = rg_writer.next_column().unwrap().unwrap();
+            col.typed::<ByteArrayType>()
+                .write_batch(&names, Some(&def), None)
+                .unwrap();
+            col.close().unwrap();
+
+            // Row `x` contains `x % 4` tags, with every fifth tag null
+            let (mut values, mut def, mut rep) = (vec![], vec![], vec![]);
+            for x in &ids {
+                let len = *x as i64 % 4;
+                if len == 0 {
+                    def.push(1);
+                    rep.push(0);
+                }
+                for i in 0..len {
+                    rep.push((i != 0) as i16);
+                    match (*x as i64 + i) % 5 {
+                        0 => def.push(2),
+                        v => {
+                            def.push(3);
+                            values.push(v);
+                        }
+                    }
+                }
+            }
+            let mut col = rg_writer.next_column().unwrap().unwrap();
+            col.typed::<Int64Type>()
+                .write_batch(&values, Some(&def), Some(&rep))
+                .unwrap();
+            col.close().unwrap();
+
+            rg_writer.close().unwrap();
+        }
+        writer.close().unwrap();
+        Bytes::from(buffer)
+    }
+
+    fn rows(reader: &SerializedFileReader<Bytes>) -> Vec<Row> {
+        reader.get_row_iter(None).unwrap().collect()
+    }
+
+    fn read_rows(data: Bytes) -> Vec<Row> {
+        rows(&SerializedFileReader::new(data).unwrap())
+    }
+
+    fn rewrite(rewriter: ParquetRewriter<Bytes>) -> SerializedFileReader<Bytes> {
+        let mut buffer = Vec::new();
+        rewriter.write(&mut buffer).unwrap();
+        SerializedFileReader::new(Bytes::from(buffer)).unwrap()
+    }
+
+    #[test]
+    fn test_rewrite_compression() {
+        for version in [WriterVersion::PARQUET_1_0, WriterVersion::PARQUET_2_0] {
+            let data = write_file(100, 40, version);
+            let props = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+
+            let rewriter = ParquetRewriter::try_new(data.clone(), props).unwrap();
+            let reader = rewrite(rewriter);
+            let metadata = reader.metadata();
+            assert_eq!(metadata.num_row_groups(), 3);
+
+            let source = SerializedFileReader::new(data.clone()).unwrap();
+            let row_groups = metadata.row_groups().iter();
+            for (rg, expected) in row_groups.zip(source.metadata().row_groups()) {
+                assert_eq!(rg.num_rows(), expected.num_rows());
+                for (col, expected) in rg.columns().iter().zip(expected.columns()) {
+                    assert_eq!(col.compression(), Compression::SNAPPY);
+                    assert_eq!(col.encodings(), expected.encodings());
+                    assert_eq!(col.num_values(), expected.num_values());
+                    assert_eq!(col.statistics(), expected.statistics());
+                }
+            }
+
+            let kv = metadata.file_metadata().key_value_metadata().unwrap();
+            assert_eq!(
+                kv,
+                &vec![KeyValue::new("key".to_string(), "value".to_string())]
+            );
+
+            assert_eq!(rows(&reader), read_rows(data));
+        }
+    }
+
+    #[test]
+    fn test_rewrite_dropped_columns() {
+        let data = write_file(50, 50, WriterVersion::PARQUET_1_0);
+
+        let rewriter =
+            ParquetRewriter::try_new(data.clone(), WriterProperties::builder().build())
+                .unwrap()
+                .with_dropped_columns(["name", "tags.list.element"]);
+        let reader = rewrite(rewriter);
+
+        let schema = reader.metadata().file_metadata().schema_descr();
+        assert_eq!(schema.num_columns(), 1);
+        assert_eq!(schema.root_schema().get_fields().len(), 1);
+        assert_eq!(schema.column(0).name(), "id");
+
+        let ids: Vec<_> = rows(&reader)
+            .iter()
+            .map(|row| row.get_int(0).unwrap())
+            .collect();
+        assert_eq!(ids, (0..50).collect::<Vec<_>>());
+
+        let result = ParquetRewriter::try_new(data, WriterProperties::builder().build())
+            .unwrap()
+            .with_dropped_columns(["id", "name", "tags"])
+            .write(Vec::new());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Parquet error: Cannot drop all columns"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_split_row_groups() {
+        for version in [WriterVersion::PARQUET_1_0, WriterVersion::PARQUET_2_0] {
+            let data = write_file(100, 60, version);
+            let props = WriterProperties::builder()
+                .set_max_row_group_size(25)
+                .build();
+
+            let rewriter = ParquetRewriter::try_new(data.clone(), props).unwrap();
+            let reader = rewrite(rewriter);
+
+            let row_counts: Vec<_> = reader
+                .metadata()
+                .row_groups()
+                .iter()
+                .map(|rg| rg.num_rows())
+                .collect();
+            assert_eq!(row_counts, vec![25, 25, 10, 25, 15]);
+
+            assert_eq!(rows(&reader), read_rows(data));
+        }
+    }
+
+    #[test]
+    fn test_rewrite_reencode() {
+        let data = write_file(100, 100, WriterVersion::PARQUET_1_0);
+        let source = SerializedFileReader::new(data.clone()).unwrap();
+        let encodings = source.metadata().row_group(0).column(1).encodings();
+        assert!(encodings.contains(&Encoding::RLE_DICTIONARY));
+
+        let props = WriterProperties::builder()
+            .set_dictionary_enabled(false)
+            .set_column_encoding("id".into(), Encoding::DELTA_BINARY_PACKED)
+            .build();
+
+        // Without reencoding only the explicitly encoded column is changed
+        let rewriter = ParquetRewriter::try_new(data.clone(), props.clone()).unwrap();
+        let reader = rewrite(rewriter);
+        let rg = reader.metadata().row_group(0);
+        assert!(rg
+            .column(0)
+            .encodings()
+            .contains(&Encoding::DELTA_BINARY_PACKED));
+        assert_eq!(rg.column(1).encodings(), encodings);
+        assert_eq!(rows(&reader), read_rows(data.clone()));
+
+        let rewriter = ParquetRewriter::try_new(data.clone(), props)
+            .unwrap()
+            .with_reencode(true);
+        let reader = rewrite(rewriter);
+        let rg = reader.metadata().row_group(0);
+        for column in rg.columns() {
+            assert!(!column.encodings().contains(&Encoding::RLE_DICTIONARY));
+        }
+        assert_eq!(rows(&reader), read_rows(data));
+    }
+}
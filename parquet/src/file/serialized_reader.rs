@@ -27,6 +27,10 @@ use crate::basic::{Encoding, Type};
 use crate::bloom_filter::Sbbf;
 use crate::column::page::{Page, PageMetadata, PageReader};
 use crate::compression::{create_codec, Codec};
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::{
+    CryptoContext, FileDecryptionProperties, FileDecryptor,
+};
 use crate::errors::{ParquetError, Result};
 use crate::file::page_index::index_reader;
 use crate::file::{
@@ -39,7 +43,7 @@ use crate::file::{
 use crate::format::{PageHeader, PageLocation, PageType};
 use crate::record::reader::RowIter;
 use crate::record::Row;
-use crate::schema::types::Type as SchemaType;
+use crate::schema::types::{ColumnPath, Type as SchemaType};
 use crate::util::{io::TryClone, memory::ByteBufferPtr};
 use bytes::{Buf, Bytes};
 use thrift::protocol::{TCompactInputProtocol, TSerializable};
@@ -162,6 +166,8 @@ pub struct ReadOptionsBuilder {
     predicates: Vec<ReadGroupPredicate>,
     enable_page_index: bool,
     props: Option<ReaderProperties>,
+    #[cfg(feature = "encryption")]
+    file_decryption_properties: Option<FileDecryptionProperties>,
 }
 
 impl ReadOptionsBuilder {
@@ -201,6 +207,16 @@ impl ReadOptionsBuilder {
         self
     }
 
+    /// Set the [`FileDecryptionProperties`] used to read an encrypted file
+    #[cfg(feature = "encryption")]
+    pub fn with_file_decryption_properties(
+        mut self,
+        properties: FileDecryptionProperties,
+    ) -> Self {
+        self.file_decryption_properties = Some(properties);
+        self
+    }
+
     /// Seal the builder and return the read options
     pub fn build(self) -> ReadOptions {
         let props = self
@@ -210,6 +226,8 @@ impl ReadOptionsBuilder {
             predicates: self.predicates,
             enable_page_index: self.enable_page_index,
             props,
+            #[cfg(feature = "encryption")]
+            file_decryption_properties: self.file_decryption_properties,
         }
     }
 }
@@ -222,6 +240,8 @@ pub struct ReadOptions {
     predicates: Vec<ReadGroupPredicate>,
     enable_page_index: bool,
     props: ReaderProperties,
+    #[cfg(feature = "encryption")]
+    file_decryption_properties: Option<FileDecryptionProperties>,
 }
 
 impl<R: 'static + ChunkReader> SerializedFileReader<R> {
@@ -239,64 +259,88 @@ impl<R: 'static + ChunkReader> SerializedFileReader<R> {
 
     /// Creates file reader from a Parquet file with read options.
     /// Returns error if Parquet file does not exist or is corrupt.
-    pub fn new_with_options(chunk_reader: R, options: ReadOptions) -> Result<Self> {
-        let metadata = footer::parse_metadata(&chunk_reader)?;
-        let mut predicates = options.predicates;
-        let row_groups = metadata.row_groups().to_vec();
-        let mut filtered_row_groups = Vec::<RowGroupMetaData>::new();
-        for (i, rg_meta) in row_groups.into_iter().enumerate() {
-            let mut keep = true;
-            for predicate in &mut predicates {
-                if !predicate(&rg_meta, i) {
-                    keep = false;
-                    break;
-                }
-            }
-            if keep {
-                filtered_row_groups.push(rg_meta);
-            }
+    pub fn new_with_options(chunk_reader: R, mut options: ReadOptions) -> Result<Self> {
+        let metadata = read_metadata(&chunk_reader, &mut options)?;
+        Ok(Self {
+            chunk_reader: Arc::new(chunk_reader),
+            metadata: Arc::new(metadata),
+            props: Arc::new(options.props),
+        })
+    }
+
+    /// Creates file reader from a Parquet file and its already decoded [`ParquetMetaData`]
+    #[cfg(feature = "arrow")]
+    pub(crate) fn new_with_metadata(
+        chunk_reader: R,
+        metadata: Arc<ParquetMetaData>,
+        props: ReaderPropertiesPtr,
+    ) -> Self {
+        Self {
+            chunk_reader: Arc::new(chunk_reader),
+            metadata,
+            props,
         }
+    }
+}
 
-        if options.enable_page_index {
-            let mut columns_indexes = vec![];
-            let mut offset_indexes = vec![];
-
-            for rg in &mut filtered_row_groups {
-                let column_index =
-                    index_reader::read_columns_indexes(&chunk_reader, rg.columns())?;
-                let offset_index =
-                    index_reader::read_pages_locations(&chunk_reader, rg.columns())?;
-                rg.set_page_offset(offset_index.clone());
-                columns_indexes.push(column_index);
-                offset_indexes.push(offset_index);
+/// Reads the [`ParquetMetaData`] of a Parquet file, filtering its row groups and
+/// loading its page index as configured by `options`
+pub(crate) fn read_metadata<R: ChunkReader>(
+    chunk_reader: &R,
+    options: &mut ReadOptions,
+) -> Result<ParquetMetaData> {
+    #[cfg(feature = "encryption")]
+    let metadata = match &options.file_decryption_properties {
+        Some(properties) => footer::parse_encrypted_metadata(chunk_reader, properties)?,
+        None => footer::parse_metadata(chunk_reader)?,
+    };
+    #[cfg(not(feature = "encryption"))]
+    let metadata = footer::parse_metadata(chunk_reader)?;
+    let predicates = &mut options.predicates;
+    let row_groups = metadata.row_groups().to_vec();
+    let mut filtered_row_groups = Vec::<RowGroupMetaData>::new();
+    for (i, rg_meta) in row_groups.into_iter().enumerate() {
+        let mut keep = true;
+        for predicate in predicates.iter_mut() {
+            if !predicate(&rg_meta, i) {
+                keep = false;
+                break;
             }
-
-            Ok(Self {
-                chunk_reader: Arc::new(chunk_reader),
-                metadata: Arc::new(ParquetMetaData::new_with_page_index(
-                    metadata.file_metadata().clone(),
-                    filtered_row_groups,
-                    Some(columns_indexes),
-                    Some(offset_indexes),
-                )),
-                props: Arc::new(options.props),
-            })
-        } else {
-            Ok(Self {
-                chunk_reader: Arc::new(chunk_reader),
-                metadata: Arc::new(ParquetMetaData::new(
-                    metadata.file_metadata().clone(),
-                    filtered_row_groups,
-                )),
-                props: Arc::new(options.props),
-            })
+        }
+        if keep {
+            filtered_row_groups.push(rg_meta);
         }
     }
 
-    #[cfg(feature = "arrow")]
-    pub(crate) fn metadata_ref(&self) -> &Arc<ParquetMetaData> {
-        &self.metadata
-    }
+    let filtered_metadata = if options.enable_page_index {
+        let mut columns_indexes = vec![];
+        let mut offset_indexes = vec![];
+
+        for rg in &mut filtered_row_groups {
+            let column_index =
+                index_reader::read_columns_indexes(chunk_reader, rg.columns())?;
+            let offset_index =
+                index_reader::read_pages_locations(chunk_reader, rg.columns())?;
+            rg.set_page_offset(offset_index.clone());
+            columns_indexes.push(column_index);
+            offset_indexes.push(offset_index);
+        }
+
+        ParquetMetaData::new_with_page_index(
+            metadata.file_metadata().clone(),
+            filtered_row_groups,
+            Some(columns_indexes),
+            Some(offset_indexes),
+        )
+    } else {
+        ParquetMetaData::new(metadata.file_metadata().clone(), filtered_row_groups)
+    };
+
+    #[cfg(feature = "encryption")]
+    let filtered_metadata =
+        filtered_metadata.with_file_decryptor(metadata.file_decryptor().cloned());
+
+    Ok(filtered_metadata)
 }
 
 /// Get midpoint offset for a row group
@@ -321,13 +365,13 @@ impl<R: 'static + ChunkReader> FileReader for SerializedFileReader<R> {
     }
 
     fn get_row_group(&self, i: usize) -> Result<Box<dyn RowGroupReader + '_>> {
-        let row_group_metadata = self.metadata.row_group(i);
         // Row groups should be processed sequentially.
         let props = Arc::clone(&self.props);
         let f = Arc::clone(&self.chunk_reader);
         Ok(Box::new(SerializedRowGroupReader::new_with_properties(
             f,
-            row_group_metadata,
+            &self.metadata,
+            i,
             props,
         )?))
     }
@@ -343,15 +387,19 @@ pub struct SerializedRowGroupReader<'a, R: ChunkReader> {
     metadata: &'a RowGroupMetaData,
     props: ReaderPropertiesPtr,
     bloom_filters: Vec<Option<Sbbf>>,
+    #[cfg(feature = "encryption")]
+    file_decryptor: Option<Arc<FileDecryptor>>,
 }
 
 impl<'a, R: ChunkReader> SerializedRowGroupReader<'a, R> {
-    /// Creates new row group reader from a file, row group metadata and custom config.
+    /// Creates new row group reader for the row group `i` of a file with custom config.
     fn new_with_properties(
         chunk_reader: Arc<R>,
-        metadata: &'a RowGroupMetaData,
+        file_metadata: &'a ParquetMetaData,
+        i: usize,
         props: ReaderPropertiesPtr,
     ) -> Result<Self> {
+        let metadata = file_metadata.row_group(i);
         let bloom_filters = if props.read_bloom_filter() {
             metadata
                 .columns()
@@ -366,6 +414,8 @@ impl<'a, R: ChunkReader> SerializedRowGroupReader<'a, R> {
             metadata,
             props,
             bloom_filters,
+            #[cfg(feature = "encryption")]
+            file_decryptor: file_metadata.file_decryptor().cloned(),
         })
     }
 }
@@ -390,13 +440,23 @@ impl<'a, R: 'static + ChunkReader> RowGroupReader for SerializedRowGroupReader<'
             .map(|x| x[i].clone());
 
         let props = Arc::clone(&self.props);
-        Ok(Box::new(SerializedPageReader::new_with_properties(
+        let page_reader = SerializedPageReader::new_with_properties(
             Arc::clone(&self.chunk_reader),
             col,
             self.metadata.num_rows() as usize,
             page_locations,
             props,
-        )?))
+        )?;
+
+        #[cfg(feature = "encryption")]
+        let page_reader = page_reader.with_crypto_context(
+            self.file_decryptor.as_deref(),
+            col,
+            self.metadata.ordinal(),
+            i,
+        )?;
+
+        Ok(Box::new(page_reader))
     }
 
     /// get bloom filter for the `i`th column
@@ -440,6 +500,47 @@ fn read_page_header_len<T: Read>(input: &mut T) -> Result<(usize, PageHeader)> {
     Ok((tracked.bytes_read, header))
 }
 
+/// Verifies the CRC32 checksum in `page_header`, if any, against that of `buffer`,
+/// the data of the page at `offset` of the column chunk of `column`
+fn verify_page_checksum(
+    page_header: &PageHeader,
+    buffer: &[u8],
+    column: &ColumnPath,
+    offset: usize,
+) -> Result<()> {
+    if let Some(expected) = page_header.crc {
+        let actual = crc32fast::hash(buffer);
+        if actual != expected as u32 {
+            return Err(general_err!(
+                "Page checksum mismatch for column {} at offset {}: expected {:#010x}, computed {:#010x}",
+                column,
+                offset,
+                expected as u32,
+                actual
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns an error if the compressed or uncompressed size of the page with
+/// `page_header` exceeds `max_page_size`
+fn check_page_size(page_header: &PageHeader, max_page_size: Option<usize>) -> Result<()> {
+    if let Some(max_page_size) = max_page_size {
+        let size = page_header
+            .compressed_page_size
+            .max(page_header.uncompressed_page_size);
+        if usize::try_from(size).map_or(true, |size| size > max_page_size) {
+            return Err(general_err!(
+                "Page size of {} bytes exceeds the maximum page size of {} bytes",
+                size,
+                max_page_size
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Decodes a [`Page`] from the provided `buffer`
 pub(crate) fn decode_page(
     page_header: PageHeader,
@@ -572,6 +673,77 @@ pub struct SerializedPageReader<R: ChunkReader> {
     physical_type: Type,
 
     state: SerializedPageReaderState,
+
+    /// Decrypts the pages of an encrypted column chunk.
+    decryptor: PageDecryptor,
+
+    /// The path of the column, if the checksums of its pages should be verified.
+    verify_checksums: Option<ColumnPath>,
+
+    /// The maximum size of a page that will be read, if limited.
+    max_page_size: Option<usize>,
+}
+
+/// Decrypts the pages of a column chunk, if it is encrypted
+#[derive(Debug, Default)]
+struct PageDecryptor {
+    #[cfg(feature = "encryption")]
+    context: Option<CryptoContext>,
+}
+
+#[cfg(feature = "encryption")]
+impl PageDecryptor {
+    /// Reads the next [`PageHeader`] from `input`, returning the number of bytes read
+    fn read_page_header_len<T: Read>(
+        &self,
+        input: &mut T,
+    ) -> Result<(usize, PageHeader)> {
+        match &self.context {
+            Some(context) => context.read_page_header(input),
+            None => read_page_header_len(input),
+        }
+    }
+
+    /// Decrypts the data of the next page, whose header is `header`
+    fn decrypt_page(
+        &mut self,
+        header: &PageHeader,
+        buffer: ByteBufferPtr,
+    ) -> Result<ByteBufferPtr> {
+        match &mut self.context {
+            Some(context) => Ok(ByteBufferPtr::new(
+                context.decrypt_page(header, buffer.data())?,
+            )),
+            None => Ok(buffer),
+        }
+    }
+
+    /// Skips the next page, which is the dictionary page if `is_dictionary`
+    fn skip_page(&mut self, is_dictionary: bool) {
+        if let Some(context) = &mut self.context {
+            context.advance_page(is_dictionary)
+        }
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+impl PageDecryptor {
+    fn read_page_header_len<T: Read>(
+        &self,
+        input: &mut T,
+    ) -> Result<(usize, PageHeader)> {
+        read_page_header_len(input)
+    }
+
+    fn decrypt_page(
+        &mut self,
+        _header: &PageHeader,
+        buffer: ByteBufferPtr,
+    ) -> Result<ByteBufferPtr> {
+        Ok(buffer)
+    }
+
+    fn skip_page(&mut self, _is_dictionary: bool) {}
 }
 
 impl<R: ChunkReader> SerializedPageReader<R> {
@@ -600,6 +772,14 @@ impl<R: ChunkReader> SerializedPageReader<R> {
         page_locations: Option<Vec<PageLocation>>,
         props: ReaderPropertiesPtr,
     ) -> Result<Self> {
+        #[cfg(not(feature = "encryption"))]
+        if meta.crypto_metadata().is_some() {
+            return Err(general_err!(
+                "Column {} is encrypted, which requires the encryption feature to read",
+                meta.column_path().string()
+            ));
+        }
+
         let decompressor = create_codec(meta.compression(), props.codec_options())?;
         let (start, len) = meta.byte_range();
 
@@ -635,8 +815,50 @@ impl<R: ChunkReader> SerializedPageReader<R> {
             decompressor,
             state,
             physical_type: meta.column_type(),
+            decryptor: PageDecryptor::default(),
+            verify_checksums: props
+                .verify_page_checksums()
+                .then(|| meta.column_path().clone()),
+            max_page_size: props.max_page_size(),
         })
     }
+
+    /// Decrypts the pages of the column chunk `column_ordinal` of the row group
+    /// `row_group_ordinal` with `file_decryptor`, if it is encrypted
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_crypto_context(
+        mut self,
+        file_decryptor: Option<&FileDecryptor>,
+        meta: &ColumnChunkMetaData,
+        row_group_ordinal: Option<i16>,
+        column_ordinal: usize,
+    ) -> Result<Self> {
+        let crypto_metadata = match meta.crypto_metadata() {
+            Some(crypto_metadata) => crypto_metadata,
+            None => return Ok(self),
+        };
+        let file_decryptor = file_decryptor.ok_or_else(|| {
+            general_err!(
+                "Column {} is encrypted, which requires FileDecryptionProperties to read",
+                meta.column_path().string()
+            )
+        })?;
+        let row_group_ordinal = row_group_ordinal.ok_or_else(|| {
+            general_err!(
+                "Missing row group ordinal of encrypted column {}",
+                meta.column_path().string()
+            )
+        })?;
+
+        self.decryptor.context = Some(CryptoContext::new(
+            file_decryptor,
+            crypto_metadata,
+            row_group_ordinal as usize,
+            column_ordinal,
+            meta.dictionary_page_offset().is_some(),
+        )?);
+        Ok(self)
+    }
 }
 
 impl<R: ChunkReader> Iterator for SerializedPageReader<R> {
@@ -660,16 +882,26 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                         return Ok(None);
                     }
 
-                    let mut read = self.reader.get_read(*offset as u64, *remaining)?;
                     let header = if let Some(header) = next_page_header.take() {
                         *header
                     } else {
-                        let (header_len, header) = read_page_header_len(&mut read)?;
+                        let mut read =
+                            self.reader.get_read(*offset as u64, *remaining)?;
+                        let (header_len, header) =
+                            self.decryptor.read_page_header_len(&mut read)?;
                         *offset += header_len;
                         *remaining -= header_len;
                         header
                     };
                     let data_len = header.compressed_page_size as usize;
+                    if data_len > *remaining {
+                        return Err(eof_err!(
+                            "Expected to read {} bytes of page, read only {}",
+                            data_len,
+                            *remaining
+                        ));
+                    }
+                    let data_offset = *offset;
                     *offset += data_len;
                     *remaining -= data_len;
 
@@ -677,20 +909,18 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                         continue;
                     }
 
-                    let mut buffer = Vec::with_capacity(data_len);
-                    let read = read.take(data_len as u64).read_to_end(&mut buffer)?;
+                    check_page_size(&header, self.max_page_size)?;
+                    // Uncompressed pages, and so the values decoded from them, reference
+                    // the bytes of readers such as `Bytes` without copying them
+                    let buffer = self.reader.get_bytes(data_offset as u64, data_len)?;
 
-                    if read != data_len {
-                        return Err(eof_err!(
-                            "Expected to read {} bytes of page, read only {}",
-                            data_len,
-                            read
-                        ));
+                    if let Some(column) = &self.verify_checksums {
+                        verify_page_checksum(&header, &buffer, column, data_offset)?;
                     }
-
+                    let buffer = self.decryptor.decrypt_page(&header, buffer.into())?;
                     decode_page(
                         header,
-                        ByteBufferPtr::new(buffer),
+                        buffer,
                         self.physical_type,
                         self.decompressor.as_mut(),
                     )?
@@ -713,13 +943,19 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                     let buffer = self.reader.get_bytes(front.offset as u64, page_len)?;
 
                     let mut cursor = Cursor::new(buffer.as_ref());
-                    let header = read_page_header(&mut cursor)?;
-                    let offset = cursor.position();
-
-                    let bytes = buffer.slice(offset as usize..);
+                    let (offset, header) =
+                        self.decryptor.read_page_header_len(&mut cursor)?;
+                    check_page_size(&header, self.max_page_size)?;
+
+                    let bytes = buffer.slice(offset..);
+                    if let Some(column) = &self.verify_checksums {
+                        let data_offset = front.offset as usize + offset;
+                        verify_page_checksum(&header, &bytes, column, data_offset)?;
+                    }
+                    let bytes = self.decryptor.decrypt_page(&header, bytes.into())?;
                     decode_page(
                         header,
-                        bytes.into(),
+                        bytes,
                         self.physical_type,
                         self.decompressor.as_mut(),
                     )?
@@ -752,7 +988,8 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                     } else {
                         let mut read =
                             self.reader.get_read(*offset as u64, *remaining_bytes)?;
-                        let (header_len, header) = read_page_header_len(&mut read)?;
+                        let (header_len, header) =
+                            self.decryptor.read_page_header_len(&mut read)?;
                         *offset += header_len;
                         *remaining_bytes -= header_len;
                         let page_meta = if let Ok(page_meta) = (&header).try_into() {
@@ -773,7 +1010,8 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
             } => {
                 if dictionary_page.is_some() {
                     Ok(Some(PageMetadata {
-                        num_rows: 0,
+                        num_rows: None,
+                        num_levels: None,
                         is_dict: true,
                     }))
                 } else if let Some(page) = page_locations.front() {
@@ -783,7 +1021,8 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                         .unwrap_or(*total_rows);
 
                     Ok(Some(PageMetadata {
-                        num_rows: next_rows - page.first_row_index as usize,
+                        num_rows: Some(next_rows - page.first_row_index as usize),
+                        num_levels: None,
                         is_dict: false,
                     }))
                 } else {
@@ -804,18 +1043,25 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                     // The next page header has already been peeked, so just advance the offset
                     *offset += buffered_header.compressed_page_size as usize;
                     *remaining_bytes -= buffered_header.compressed_page_size as usize;
+                    self.decryptor
+                        .skip_page(buffered_header.type_ == PageType::DICTIONARY_PAGE);
                 } else {
                     let mut read =
                         self.reader.get_read(*offset as u64, *remaining_bytes)?;
-                    let (header_len, header) = read_page_header_len(&mut read)?;
+                    let (header_len, header) =
+                        self.decryptor.read_page_header_len(&mut read)?;
                     let data_page_size = header.compressed_page_size as usize;
                     *offset += header_len + data_page_size;
                     *remaining_bytes -= header_len + data_page_size;
+                    self.decryptor
+                        .skip_page(header.type_ == PageType::DICTIONARY_PAGE);
                 }
                 Ok(())
             }
             SerializedPageReaderState::Pages { page_locations, .. } => {
-                page_locations.pop_front();
+                if page_locations.pop_front().is_some() {
+                    self.decryptor.skip_page(false);
+                }
 
                 Ok(())
             }
@@ -1682,11 +1928,11 @@ mod tests {
             // have checked with `parquet-tools column-index   -c string_col  ./alltypes_tiny_pages.parquet`
             // page meta has two scenarios(21, 20) of num_rows expect last page has 11 rows.
             if i != 351 {
-                assert!((meta.num_rows == 21) || (meta.num_rows == 20));
+                assert!((meta.num_rows == Some(21)) || (meta.num_rows == Some(20)));
             } else {
                 // last page first row index is 7290, total row count is 7300
                 // because first row start with zero, last page row count should be 10.
-                assert_eq!(meta.num_rows, 10);
+                assert_eq!(meta.num_rows, Some(10));
             }
             assert!(!meta.is_dict);
             vec.push(meta);
@@ -1723,12 +1969,12 @@ mod tests {
             let meta = column_page_reader.peek_next_page().unwrap().unwrap();
             // have checked with `parquet-tools column-index   -c string_col  ./alltypes_tiny_pages.parquet`
             // page meta has two scenarios(21, 20) of num_rows expect last page has 11 rows.
+            // the number of rows of a v1 data page is only known from the offset index
+            assert_eq!(meta.num_rows, None);
             if i != 351 {
-                assert!((meta.num_rows == 21) || (meta.num_rows == 20));
+                assert!((meta.num_levels == Some(21)) || (meta.num_levels == Some(20)));
             } else {
-                // last page first row index is 7290, total row count is 7300
-                // because first row start with zero, last page row count should be 10.
-                assert_eq!(meta.num_rows, 10);
+                assert_eq!(meta.num_levels, Some(10));
             }
             assert!(!meta.is_dict);
             vec.push(meta);
@@ -20,9 +20,14 @@
 
 use crate::bloom_filter::Sbbf;
 use crate::format as parquet;
-use crate::format::{ColumnIndex, OffsetIndex, RowGroup};
-use std::{io::Write, sync::Arc};
-use thrift::protocol::{TCompactOutputProtocol, TOutputProtocol, TSerializable};
+use crate::format::{ColumnIndex, OffsetIndex, RowGroup, SortingColumn};
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
+use thrift::protocol::{
+    TCompactInputProtocol, TCompactOutputProtocol, TOutputProtocol, TSerializable,
+};
 
 use crate::basic::PageType;
 use crate::column::writer::{
@@ -33,9 +38,13 @@ use crate::column::{
     writer::{get_column_writer, ColumnWriter},
 };
 use crate::data_type::DataType;
+#[cfg(feature = "encryption")]
+use crate::encryption::encrypt::{FileEncryptor, PageEncryptor};
 use crate::errors::{ParquetError, Result};
+#[cfg(feature = "encryption")]
+use crate::file::PARQUET_MAGIC_ENCR_FOOTER;
 use crate::file::{
-    metadata::*, properties::WriterPropertiesPtr,
+    metadata::*, properties::WriterPropertiesPtr, reader::ChunkReader,
     statistics::to_thrift as statistics_to_thrift, PARQUET_MAGIC,
 };
 use crate::schema::types::{
@@ -121,23 +130,52 @@ pub struct SerializedFileWriter<W: Write> {
     column_indexes: Vec<Vec<Option<ColumnIndex>>>,
     offset_indexes: Vec<Vec<Option<OffsetIndex>>>,
     row_group_index: usize,
+    // kv_metadatas will be appended to `props` when `write_metadata`
+    kv_metadatas: Vec<KeyValue>,
+    #[cfg(feature = "encryption")]
+    file_encryptor: Option<Arc<FileEncryptor>>,
 }
 
 impl<W: Write> SerializedFileWriter<W> {
     /// Creates new file writer.
     pub fn new(buf: W, schema: TypePtr, properties: WriterPropertiesPtr) -> Result<Self> {
         let mut buf = TrackedWrite::new(buf);
-        Self::start_file(&mut buf)?;
+        let descr = Arc::new(SchemaDescriptor::new(schema.clone()));
+        properties.check_compression_levels(&descr)?;
+
+        #[cfg(feature = "encryption")]
+        let file_encryptor = match properties.file_encryption_properties() {
+            Some(encryption_properties) => Some(Arc::new(FileEncryptor::new(
+                encryption_properties.clone(),
+                &descr,
+            )?)),
+            None => None,
+        };
+
+        #[cfg(feature = "encryption")]
+        let magic = match &file_encryptor {
+            Some(file_encryptor) if file_encryptor.encrypted_footer() => {
+                PARQUET_MAGIC_ENCR_FOOTER
+            }
+            _ => PARQUET_MAGIC,
+        };
+        #[cfg(not(feature = "encryption"))]
+        let magic = PARQUET_MAGIC;
+
+        Self::start_file(&mut buf, magic)?;
         Ok(Self {
             buf,
-            schema: schema.clone(),
-            descr: Arc::new(SchemaDescriptor::new(schema)),
+            schema,
+            descr,
             props: properties,
             row_groups: vec![],
             bloom_filters: vec![],
             column_indexes: Vec::new(),
             offset_indexes: Vec::new(),
             row_group_index: 0,
+            kv_metadatas: Vec::new(),
+            #[cfg(feature = "encryption")]
+            file_encryptor,
         })
     }
 
@@ -172,14 +210,79 @@ impl<W: Write> SerializedFileWriter<W> {
             &mut self.buf,
             Some(Box::new(on_close)),
         );
+        #[cfg(feature = "encryption")]
+        let row_group_writer = row_group_writer
+            .with_file_encryptor(self.file_encryptor.clone(), self.row_group_index - 1);
         Ok(row_group_writer)
     }
 
+    /// Appends the row group `metadata` of another file, read from `reader`, as the
+    /// next row group of this file, returning its new metadata.
+    ///
+    /// The column chunks are copied byte for byte, without decoding or re-encoding
+    /// their pages, along with any bloom filters, page index and sorting columns.
+    /// The schema of the row group must therefore match that of this file, and its
+    /// compression and encodings are preserved regardless of the
+    /// [`WriterProperties`] of this writer.
+    ///
+    /// [`WriterProperties`]: crate::file::properties::WriterProperties
+    pub fn append_row_group<R: ChunkReader>(
+        &mut self,
+        reader: Arc<R>,
+        metadata: &RowGroupMetaData,
+    ) -> Result<RowGroupMetaDataPtr> {
+        if metadata.num_columns() != self.descr.num_columns() {
+            return Err(general_err!(
+                "Cannot append row group with {} columns to file with {} columns",
+                metadata.num_columns(),
+                self.descr.num_columns()
+            ));
+        }
+
+        let sorting_columns = metadata.sorting_columns();
+        check_sorting_columns(&self.descr, sorting_columns)?;
+
+        let mut row_group_writer = self.next_row_group()?;
+        row_group_writer.sorting_columns = sorting_columns.cloned();
+        for column in metadata.columns() {
+            let column_index = read_thrift(
+                reader.as_ref(),
+                column.column_index_offset(),
+                column.column_index_length(),
+            )?;
+            let offset_index = read_thrift(
+                reader.as_ref(),
+                column.offset_index_offset(),
+                column.offset_index_length(),
+            )?;
+            let bloom_filter = Sbbf::read_from_column_chunk(column, reader.clone())?;
+
+            let close = ColumnCloseResult {
+                bytes_written: column.compressed_size() as u64,
+                rows_written: metadata.num_rows() as u64,
+                metadata: column.clone(),
+                bloom_filter,
+                column_index,
+                offset_index,
+            };
+            row_group_writer.append_column(reader.as_ref(), close)?;
+        }
+        row_group_writer.close()
+    }
+
     /// Returns metadata for any flushed row groups
     pub fn flushed_row_groups(&self) -> &[RowGroupMetaDataPtr] {
         &self.row_groups
     }
 
+    /// Adds a [`KeyValue`] to the metadata written to the file footer, in addition
+    /// to any [`WriterProperties::key_value_metadata`]
+    ///
+    /// [`WriterProperties::key_value_metadata`]: crate::file::properties::WriterProperties::key_value_metadata
+    pub fn append_key_value_metadata(&mut self, kv_metadata: KeyValue) {
+        self.kv_metadatas.push(kv_metadata);
+    }
+
     /// Closes and finalises file writer, returning the file metadata.
     pub fn close(mut self) -> Result<parquet::FileMetaData> {
         self.assert_previous_writer_closed()?;
@@ -188,8 +291,8 @@ impl<W: Write> SerializedFileWriter<W> {
     }
 
     /// Writes magic bytes at the beginning of the file.
-    fn start_file(buf: &mut TrackedWrite<W>) -> Result<()> {
-        buf.write_all(&PARQUET_MAGIC)?;
+    fn start_file(buf: &mut TrackedWrite<W>, magic: [u8; 4]) -> Result<()> {
+        buf.write_all(&magic)?;
         Ok(())
     }
 
@@ -231,12 +334,15 @@ impl<W: Write> SerializedFileWriter<W> {
                     Some(bloom_filter) => {
                         let start_offset = self.buf.bytes_written();
                         bloom_filter.write(&mut self.buf)?;
-                        // set offset and index for bloom filter
-                        column_chunk
+                        let end_offset = self.buf.bytes_written();
+                        // set offset and length for bloom filter
+                        let column_metadata = column_chunk
                             .meta_data
                             .as_mut()
-                            .expect("can't have bloom filter without column metadata")
-                            .bloom_filter_offset = Some(start_offset as i64);
+                            .expect("can't have bloom filter without column metadata");
+                        column_metadata.bloom_filter_offset = Some(start_offset as i64);
+                        column_metadata.bloom_filter_length =
+                            Some((end_offset - start_offset) as i32);
                     }
                     None => {}
                 }
@@ -288,12 +394,23 @@ impl<W: Write> SerializedFileWriter<W> {
         self.write_column_indexes(&mut row_groups)?;
         self.write_offset_indexes(&mut row_groups)?;
 
-        let file_metadata = parquet::FileMetaData {
+        let key_value_metadata = match self.props.key_value_metadata() {
+            Some(kv) => Some(kv.iter().chain(&self.kv_metadatas).cloned().collect()),
+            None if self.kv_metadatas.is_empty() => None,
+            None => Some(self.kv_metadatas.clone()),
+        };
+
+        #[cfg(feature = "encryption")]
+        if let Some(file_encryptor) = &self.file_encryptor {
+            file_encryptor.encrypt_row_groups(&mut row_groups)?;
+        }
+
+        let mut file_metadata = parquet::FileMetaData {
             num_rows,
             row_groups,
             version: self.props.writer_version().as_num(),
             schema: types::to_thrift(self.schema.as_ref())?,
-            key_value_metadata: self.props.key_value_metadata().cloned(),
+            key_value_metadata,
             created_by: Some(self.props.created_by().to_owned()),
             column_orders: None,
             encryption_algorithm: None,
@@ -302,21 +419,38 @@ impl<W: Write> SerializedFileWriter<W> {
 
         // Write file metadata
         let start_pos = self.buf.bytes_written();
-        {
-            let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
-            file_metadata.write_to_out_protocol(&mut protocol)?;
-            protocol.flush()?;
-        }
+        let magic = self.write_file_metadata(&mut file_metadata)?;
         let end_pos = self.buf.bytes_written();
 
         // Write footer
         let metadata_len = (end_pos - start_pos) as i32;
 
         self.buf.write_all(&metadata_len.to_le_bytes())?;
-        self.buf.write_all(&PARQUET_MAGIC)?;
+        self.buf.write_all(&magic)?;
         Ok(file_metadata)
     }
 
+    /// Serializes the file metadata, encrypting or signing it if the file is
+    /// encrypted, and returns the magic bytes of the footer.
+    fn write_file_metadata(
+        &mut self,
+        file_metadata: &mut parquet::FileMetaData,
+    ) -> Result<[u8; 4]> {
+        #[cfg(feature = "encryption")]
+        if let Some(file_encryptor) = &self.file_encryptor {
+            file_encryptor.write_file_metadata(file_metadata, &mut self.buf)?;
+            return match file_encryptor.encrypted_footer() {
+                true => Ok(PARQUET_MAGIC_ENCR_FOOTER),
+                false => Ok(PARQUET_MAGIC),
+            };
+        }
+
+        let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
+        file_metadata.write_to_out_protocol(&mut protocol)?;
+        protocol.flush()?;
+        Ok(PARQUET_MAGIC)
+    }
+
     #[inline]
     fn assert_previous_writer_closed(&self) -> Result<()> {
         if self.row_group_index != self.row_groups.len() {
@@ -357,7 +491,12 @@ pub struct SerializedRowGroupWriter<'a, W: Write> {
     bloom_filters: Vec<Option<Sbbf>>,
     column_indexes: Vec<Option<ColumnIndex>>,
     offset_indexes: Vec<Option<OffsetIndex>>,
+    sorting_columns: Option<Vec<SortingColumn>>,
     on_close: Option<OnCloseRowGroup<'a>>,
+    #[cfg(feature = "encryption")]
+    file_encryptor: Option<Arc<FileEncryptor>>,
+    #[cfg(feature = "encryption")]
+    row_group_ordinal: usize,
 }
 
 impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
@@ -379,6 +518,7 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
             on_close,
             total_rows_written: None,
             descr: schema_descr,
+            sorting_columns: properties.sorting_columns().cloned(),
             props: properties,
             column_index: 0,
             row_group_metadata: None,
@@ -387,6 +527,41 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
             column_indexes: Vec::with_capacity(num_columns),
             offset_indexes: Vec::with_capacity(num_columns),
             total_bytes_written: 0,
+            #[cfg(feature = "encryption")]
+            file_encryptor: None,
+            #[cfg(feature = "encryption")]
+            row_group_ordinal: 0,
+        }
+    }
+
+    /// Encrypts the column chunks of this row group, the `row_group_ordinal`-th of
+    /// the file, with `file_encryptor`
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_file_encryptor(
+        mut self,
+        file_encryptor: Option<Arc<FileEncryptor>>,
+        row_group_ordinal: usize,
+    ) -> Self {
+        self.file_encryptor = file_encryptor;
+        self.row_group_ordinal = row_group_ordinal;
+        self
+    }
+
+    /// Returns the [`PageEncryptor`] of the column `column`, the `column_ordinal`-th of
+    /// this row group, or `None` if the column is not encrypted
+    #[cfg(feature = "encryption")]
+    fn page_encryptor(
+        &self,
+        column: &ColumnDescPtr,
+        column_ordinal: usize,
+    ) -> Result<Option<PageEncryptor>> {
+        match &self.file_encryptor {
+            Some(file_encryptor) => file_encryptor.page_encryptor(
+                column.path(),
+                self.row_group_ordinal,
+                column_ordinal,
+            ),
+            None => Ok(None),
         }
     }
 
@@ -409,7 +584,21 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
         if self.column_index >= self.descr.num_columns() {
             return Ok(None);
         }
-        let page_writer = Box::new(SerializedPageWriter::new(self.buf));
+        let column = self.descr.column(self.column_index);
+
+        #[cfg(feature = "encryption")]
+        let page_encryptor = self.page_encryptor(&column, self.column_index)?;
+        // The page index and bloom filters of encrypted columns are not written
+        #[cfg(feature = "encryption")]
+        let encrypted = page_encryptor.is_some();
+        #[cfg(not(feature = "encryption"))]
+        let encrypted = false;
+
+        let page_writer = SerializedPageWriter::new(self.buf)
+            .with_page_checksums(self.props.write_page_checksums());
+        #[cfg(feature = "encryption")]
+        let page_writer = page_writer.with_page_encryptor(page_encryptor);
+        let page_writer = Box::new(page_writer);
 
         let total_bytes_written = &mut self.total_bytes_written;
         let total_rows_written = &mut self.total_rows_written;
@@ -418,13 +607,22 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
         let offset_indexes = &mut self.offset_indexes;
         let bloom_filters = &mut self.bloom_filters;
 
-        let on_close = |r: ColumnCloseResult| {
+        let on_close = move |r: ColumnCloseResult| {
             // Update row group writer metrics
             *total_bytes_written += r.bytes_written;
             column_chunks.push(r.metadata);
-            bloom_filters.push(r.bloom_filter);
-            column_indexes.push(r.column_index);
-            offset_indexes.push(r.offset_index);
+            match encrypted {
+                true => {
+                    bloom_filters.push(None);
+                    column_indexes.push(None);
+                    offset_indexes.push(None);
+                }
+                false => {
+                    bloom_filters.push(r.bloom_filter);
+                    column_indexes.push(r.column_index);
+                    offset_indexes.push(r.offset_index);
+                }
+            }
 
             if let Some(rows) = *total_rows_written {
                 if rows != r.rows_written {
@@ -441,7 +639,6 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
             Ok(())
         };
 
-        let column = self.descr.column(self.column_index);
         self.column_index += 1;
 
         Ok(Some(factory(
@@ -462,6 +659,116 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
         })
     }
 
+    /// Appends the column chunk described by `close`, read from `reader`, as the next
+    /// column of this row group.
+    ///
+    /// The pages of the column chunk are copied byte for byte, and the offsets in its
+    /// metadata and offset index updated to their new location in this file. Returns
+    /// an error if the column chunk does not match the next column of the schema.
+    pub fn append_column<R: ChunkReader>(
+        &mut self,
+        reader: &R,
+        mut close: ColumnCloseResult,
+    ) -> Result<()> {
+        self.assert_previous_writer_closed()?;
+
+        if self.column_index >= self.descr.num_columns() {
+            return Err(general_err!("Cannot append column, all columns written"));
+        }
+        let column = self.descr.column(self.column_index);
+
+        let metadata = &close.metadata;
+        if metadata.column_descr() != column.as_ref() {
+            return Err(general_err!(
+                "Cannot append column chunk, expected column {:?} got {:?}",
+                column,
+                metadata.column_descr()
+            ));
+        }
+        if metadata.crypto_metadata().is_some() {
+            return Err(nyi_err!(
+                "Appending encrypted column chunk {}",
+                column.path().string()
+            ));
+        }
+        #[cfg(feature = "encryption")]
+        if self.page_encryptor(&column, self.column_index)?.is_some() {
+            return Err(nyi_err!(
+                "Appending column chunk to encrypted column {}",
+                column.path().string()
+            ));
+        }
+
+        let src_data_offset = metadata.data_page_offset();
+        let src_dictionary_offset = metadata.dictionary_page_offset();
+        let src_offset = src_dictionary_offset.unwrap_or(src_data_offset);
+        let src_length = metadata.compressed_size();
+
+        let read_offset = u64::try_from(src_offset)
+            .map_err(|_| general_err!("Invalid column chunk offset {}", src_offset))?;
+        let read_length = usize::try_from(src_length)
+            .map_err(|_| general_err!("Invalid column chunk length {}", src_length))?;
+
+        let write_offset = self.buf.bytes_written() as i64;
+        let mut read = reader.get_read(read_offset, read_length)?;
+        let write_length = std::io::copy(&mut read, &mut self.buf)?;
+        if write_length != src_length as u64 {
+            return Err(eof_err!(
+                "Expected to copy {} bytes of column chunk, copied {}",
+                src_length,
+                write_length
+            ));
+        }
+
+        let map_offset = |offset: i64| offset - src_offset + write_offset;
+        let mut builder = ColumnChunkMetaData::builder(metadata.column_descr_ptr())
+            .set_encodings(metadata.encodings().clone())
+            .set_compression(metadata.compression())
+            .set_num_values(metadata.num_values())
+            .set_total_compressed_size(src_length)
+            .set_total_uncompressed_size(metadata.uncompressed_size())
+            .set_data_page_offset(map_offset(src_data_offset))
+            .set_dictionary_page_offset(src_dictionary_offset.map(map_offset));
+        if let Some(statistics) = metadata.statistics() {
+            builder = builder.set_statistics(statistics.clone());
+        }
+        if let Some(stats) = metadata.page_encoding_stats() {
+            builder = builder.set_page_encoding_stats(stats.clone());
+        }
+        close.metadata = builder.build()?;
+
+        if let Some(offset_index) = close.offset_index.as_mut() {
+            for location in &mut offset_index.page_locations {
+                location.offset = map_offset(location.offset);
+            }
+        }
+
+        // Record the column chunk as if written by a column writer
+        let on_close = self
+            .next_column_with_factory(|_, _, _, on_close| Ok(on_close))?
+            .ok_or_else(|| general_err!("Cannot append column, all columns written"))?;
+        on_close(close)
+    }
+
+    /// Sets the sort ordering of the rows in this row group, overriding
+    /// [`WriterProperties::sorting_columns`]
+    ///
+    /// The rows are not sorted by the writer, the ordering is only recorded in
+    /// the row group metadata for readers to exploit.
+    ///
+    /// Returns an error, leaving the sort ordering unchanged, if a sorting column
+    /// does not refer to a leaf column of the schema.
+    ///
+    /// [`WriterProperties::sorting_columns`]: crate::file::properties::WriterProperties::sorting_columns
+    pub fn set_sorting_columns(
+        &mut self,
+        value: Option<Vec<SortingColumn>>,
+    ) -> Result<()> {
+        check_sorting_columns(&self.descr, value.as_ref())?;
+        self.sorting_columns = value;
+        Ok(())
+    }
+
     /// Closes this row group writer and returns row group metadata.
     pub fn close(mut self) -> Result<RowGroupMetaDataPtr> {
         if self.row_group_metadata.is_none() {
@@ -472,7 +779,7 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
                 .set_column_metadata(column_chunks)
                 .set_total_byte_size(self.total_bytes_written as i64)
                 .set_num_rows(self.total_rows_written.unwrap_or(0) as i64)
-                .set_sorting_columns(self.props.sorting_columns().cloned())
+                .set_sorting_columns(self.sorting_columns.take())
                 .build()?;
 
             let metadata = Arc::new(row_group_metadata);
@@ -502,6 +809,48 @@ impl<'a, W: Write> SerializedRowGroupWriter<'a, W> {
     }
 }
 
+/// Reads the thrift struct of `length` bytes at `offset` of `reader`, if any
+fn read_thrift<R: ChunkReader, T: TSerializable>(
+    reader: &R,
+    offset: Option<i64>,
+    length: Option<i32>,
+) -> Result<Option<T>> {
+    match (offset, length) {
+        (Some(offset), Some(length)) => {
+            let offset = u64::try_from(offset)
+                .map_err(|_| general_err!("Invalid thrift offset {}", offset))?;
+            let length = usize::try_from(length)
+                .map_err(|_| general_err!("Invalid thrift length {}", length))?;
+            let mut data = Vec::with_capacity(length);
+            reader.get_read(offset, length)?.read_to_end(&mut data)?;
+            let mut prot = TCompactInputProtocol::new(data.as_slice());
+            Ok(Some(T::read_from_in_protocol(&mut prot)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns an error if any of `sorting_columns` is not a leaf column of `descr`
+fn check_sorting_columns(
+    descr: &SchemaDescriptor,
+    sorting_columns: Option<&Vec<SortingColumn>>,
+) -> Result<()> {
+    let num_columns = descr.num_columns();
+    match sorting_columns
+        .into_iter()
+        .flatten()
+        .map(|c| c.column_idx)
+        .find(|idx| *idx < 0 || *idx as usize >= num_columns)
+    {
+        Some(column) => Err(general_err!(
+            "Sorting column {} out of range for row group with {} columns",
+            column,
+            num_columns
+        )),
+        None => Ok(()),
+    }
+}
+
 /// A wrapper around a [`ColumnWriter`] that invokes a callback on [`Self::close`]
 pub struct SerializedColumnWriter<'a> {
     inner: ColumnWriter<'a>,
@@ -555,18 +904,49 @@ impl<'a> SerializedColumnWriter<'a> {
 /// `SerializedPageWriter` should not be used after calling `close()`.
 pub struct SerializedPageWriter<'a, W> {
     sink: &'a mut TrackedWrite<W>,
+    write_checksums: bool,
+    #[cfg(feature = "encryption")]
+    page_encryptor: Option<PageEncryptor>,
 }
 
 impl<'a, W: Write> SerializedPageWriter<'a, W> {
     /// Creates new page writer.
     pub fn new(sink: &'a mut TrackedWrite<W>) -> Self {
-        Self { sink }
+        Self {
+            sink,
+            write_checksums: false,
+            #[cfg(feature = "encryption")]
+            page_encryptor: None,
+        }
+    }
+
+    /// Writes the CRC32 checksum of each page to its header if `write_checksums`
+    pub(crate) fn with_page_checksums(mut self, write_checksums: bool) -> Self {
+        self.write_checksums = write_checksums;
+        self
+    }
+
+    /// Encrypts the pages written by this writer with `page_encryptor`, if any
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_page_encryptor(
+        mut self,
+        page_encryptor: Option<PageEncryptor>,
+    ) -> Self {
+        self.page_encryptor = page_encryptor;
+        self
     }
 
     /// Serializes page header into Thrift.
     /// Returns number of bytes that have been written into the sink.
     #[inline]
     fn serialize_page_header(&mut self, header: parquet::PageHeader) -> Result<usize> {
+        #[cfg(feature = "encryption")]
+        if let Some(page_encryptor) = &mut self.page_encryptor {
+            let encrypted = page_encryptor.encrypt_page_header(&header)?;
+            self.sink.write_all(&encrypted)?;
+            return Ok(encrypted.len());
+        }
+
         let start_pos = self.sink.bytes_written();
         {
             let mut protocol = TCompactOutputProtocol::new(&mut self.sink);
@@ -589,7 +969,6 @@ impl<'a, W: Write> PageWriter for SerializedPageWriter<'a, W> {
             type_: page_type.into(),
             uncompressed_page_size: uncompressed_size as i32,
             compressed_page_size: compressed_size as i32,
-            // TODO: Add support for crc checksum
             crc: None,
             data_page_header: None,
             index_page_header: None,
@@ -644,15 +1023,31 @@ impl<'a, W: Write> PageWriter for SerializedPageWriter<'a, W> {
             }
         }
 
+        #[cfg(feature = "encryption")]
+        let encrypted = match &self.page_encryptor {
+            Some(page_encryptor) => {
+                Some(page_encryptor.encrypt_page(&page_header, page.data())?)
+            }
+            None => None,
+        };
+        #[cfg(feature = "encryption")]
+        let data = encrypted.as_deref().unwrap_or_else(|| page.data());
+        #[cfg(not(feature = "encryption"))]
+        let data = page.data();
+        page_header.compressed_page_size = data.len() as i32;
+        if self.write_checksums {
+            page_header.crc = Some(crc32fast::hash(data) as i32);
+        }
+
         let start_pos = self.sink.bytes_written() as u64;
 
         let header_size = self.serialize_page_header(page_header)?;
-        self.sink.write_all(page.data())?;
+        self.sink.write_all(data)?;
 
         let mut spec = PageWriteSpec::new();
         spec.page_type = page_type;
         spec.uncompressed_size = uncompressed_size + header_size;
-        spec.compressed_size = compressed_size + header_size;
+        spec.compressed_size = data.len() + header_size;
         spec.offset = start_pos;
         spec.bytes_written = self.sink.bytes_written() as u64 - start_pos;
         // Number of values is incremented for data pages only
@@ -664,6 +1059,12 @@ impl<'a, W: Write> PageWriter for SerializedPageWriter<'a, W> {
     }
 
     fn write_metadata(&mut self, metadata: &ColumnChunkMetaData) -> Result<()> {
+        // The metadata of encrypted columns is only written to the footer
+        #[cfg(feature = "encryption")]
+        if self.page_encryptor.is_some() {
+            return Ok(());
+        }
+
         let mut protocol = TCompactOutputProtocol::new(&mut self.sink);
         metadata
             .to_column_metadata_thrift()
@@ -687,15 +1088,20 @@ mod tests {
 
     use crate::basic::{Compression, Encoding, LogicalType, Repetition, Type};
     use crate::column::page::PageReader;
+    use crate::column::reader::ColumnReader;
     use crate::compression::{create_codec, Codec, CodecOptionsBuilder};
-    use crate::data_type::{BoolType, Int32Type};
+    use crate::data_type::{BoolType, ByteArray, ByteArrayType, Int32Type};
     use crate::file::reader::ChunkReader;
     use crate::file::{
-        properties::{ReaderProperties, WriterProperties, WriterVersion},
+        page_index::index::Index,
+        properties::{
+            EnabledStatistics, ReaderProperties, WriterProperties,
+            WriterPropertiesBuilder, WriterVersion,
+        },
         reader::{FileReader, SerializedFileReader, SerializedPageReader},
+        serialized_reader::ReadOptionsBuilder,
         statistics::{from_thrift, to_thrift, Statistics},
     };
-    use crate::format::SortingColumn;
     use crate::record::{Row, RowAccessor};
     use crate::schema::types::{ColumnDescriptor, ColumnPath};
     use crate::util::memory::ByteBufferPtr;
@@ -795,6 +1201,48 @@ mod tests {
         assert_eq!(reader.get_row_iter(None).unwrap().count(), 0);
     }
 
+    #[test]
+    fn test_file_writer_invalid_compression_level() {
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![
+                    Arc::new(
+                        types::Type::primitive_type_builder("col1", Type::INT32)
+                            .build()
+                            .unwrap(),
+                    ),
+                    Arc::new(
+                        types::Type::primitive_type_builder("col2", Type::INT32)
+                            .build()
+                            .unwrap(),
+                    ),
+                ])
+                .build()
+                .unwrap(),
+        );
+
+        // Level is ignored by codecs that do not support it
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_compression_level(30)
+            .build();
+        SerializedFileWriter::new(vec![], schema.clone(), Arc::new(props)).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_compression(Compression::ZSTD)
+            .set_compression_level(22)
+            .set_column_compression_level(ColumnPath::from("col2"), 30)
+            .build();
+        let err = SerializedFileWriter::new(vec![], schema, Arc::new(props))
+            .err()
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            err,
+            "Parquet error: Invalid compression level 30 for codec ZSTD, expected 1..=22"
+        );
+    }
+
     #[test]
     fn test_file_writer_with_metadata() {
         let file = tempfile::tempfile().unwrap();
@@ -834,6 +1282,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_file_writer_append_key_value_metadata() {
+        let file = tempfile::tempfile().unwrap();
+
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![Arc::new(
+                    types::Type::primitive_type_builder("col1", Type::INT32)
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        );
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_key_value_metadata(Some(vec![KeyValue::new(
+                    "key".to_string(),
+                    "value".to_string(),
+                )]))
+                .build(),
+        );
+        let mut writer =
+            SerializedFileWriter::new(file.try_clone().unwrap(), schema, props).unwrap();
+        writer.append_key_value_metadata(KeyValue::new(
+            "appended".to_string(),
+            "other".to_string(),
+        ));
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(file).unwrap();
+        let kv = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap();
+        assert_eq!(
+            kv,
+            &vec![
+                KeyValue::new("key".to_string(), "value".to_string()),
+                KeyValue::new("appended".to_string(), "other".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_file_writer_v2_with_metadata() {
         let file = tempfile::tempfile().unwrap();
@@ -946,6 +1439,96 @@ mod tests {
         assert_eq!(expected_result.as_ref(), result[0]);
     }
 
+    #[test]
+    fn test_file_writer_row_group_sorting_columns() {
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![Arc::new(
+                    types::Type::primitive_type_builder("col1", Type::INT32)
+                        .with_repetition(Repetition::REQUIRED)
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        );
+        let default_order = vec![SortingColumn {
+            column_idx: 0,
+            descending: false,
+            nulls_first: true,
+        }];
+        let descending = vec![SortingColumn {
+            column_idx: 0,
+            descending: true,
+            nulls_first: false,
+        }];
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_sorting_columns(Some(default_order.clone()))
+                .build(),
+        );
+
+        let mut buf = Vec::new();
+        let mut writer =
+            SerializedFileWriter::new(&mut buf, schema.clone(), props.clone()).unwrap();
+        for sorting_columns in [None, Some(Some(descending.clone())), Some(None)] {
+            let mut row_group_writer = writer.next_row_group().unwrap();
+            if let Some(sorting_columns) = sorting_columns {
+                row_group_writer
+                    .set_sorting_columns(sorting_columns)
+                    .unwrap();
+            }
+            let column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer.close().unwrap();
+            row_group_writer.close().unwrap();
+        }
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(Bytes::from(buf)).unwrap();
+        let result: Vec<_> = reader
+            .metadata()
+            .row_groups()
+            .iter()
+            .map(|rg| rg.sorting_columns().cloned())
+            .collect();
+        assert_eq!(
+            result,
+            vec![Some(default_order.clone()), Some(descending), None]
+        );
+
+        let out_of_range = vec![SortingColumn {
+            column_idx: 1,
+            descending: false,
+            nulls_first: false,
+        }];
+        let mut writer =
+            SerializedFileWriter::new(Vec::new(), schema.clone(), props).unwrap();
+        let mut row_group_writer = writer.next_row_group().unwrap();
+        let err = row_group_writer
+            .set_sorting_columns(Some(out_of_range.clone()))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Sorting column 1 out of range for row group with 1 columns"
+        );
+        let column_writer = row_group_writer.next_column().unwrap().unwrap();
+        column_writer.close().unwrap();
+        let metadata = row_group_writer.close().unwrap();
+        assert_eq!(metadata.sorting_columns(), Some(&default_order));
+
+        // Sorting columns of the writer properties are recorded as is
+        let props = WriterProperties::builder()
+            .set_sorting_columns(Some(out_of_range.clone()))
+            .build();
+        let mut writer =
+            SerializedFileWriter::new(Vec::new(), schema, Arc::new(props)).unwrap();
+        let mut row_group_writer = writer.next_row_group().unwrap();
+        let column_writer = row_group_writer.next_column().unwrap().unwrap();
+        column_writer.close().unwrap();
+        let metadata = row_group_writer.close().unwrap();
+        assert_eq!(metadata.sorting_columns(), Some(&out_of_range));
+    }
+
     #[test]
     fn test_file_writer_empty_row_groups() {
         let file = tempfile::tempfile().unwrap();
@@ -981,6 +1564,345 @@ mod tests {
         );
     }
 
+    /// Writes a file of `data` with a bloom filter and page index, one row group per
+    /// element of `data`
+    fn write_indexed_file(data: &[Vec<i32>]) -> Bytes {
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![Arc::new(
+                    types::Type::primitive_type_builder("col1", Type::INT32)
+                        .with_repetition(Repetition::REQUIRED)
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        );
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_statistics_enabled(EnabledStatistics::Page)
+                .set_bloom_filter_enabled(true)
+                .set_dictionary_enabled(false)
+                .set_data_pagesize_limit(16)
+                .set_write_batch_size(4)
+                .build(),
+        );
+        let mut buf = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        for values in data {
+            let mut row_group_writer = writer.next_row_group().unwrap();
+            let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+            column_writer
+                .typed::<Int32Type>()
+                .write_batch(values, None, None)
+                .unwrap();
+            column_writer.close().unwrap();
+            row_group_writer.close().unwrap();
+        }
+        writer.close().unwrap();
+        buf.into()
+    }
+
+    #[test]
+    fn test_file_writer_append_row_group() {
+        let a = Arc::new(write_indexed_file(&[(0..20).collect()]));
+        let b = Arc::new(write_indexed_file(&[
+            (20..30).collect(),
+            (30..50).collect(),
+        ]));
+
+        let a_reader = SerializedFileReader::new(a.as_ref().clone()).unwrap();
+        let b_reader = SerializedFileReader::new(b.as_ref().clone()).unwrap();
+
+        let mut buf = Vec::new();
+        let schema = a_reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .root_schema_ptr();
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        for row_group in a_reader.metadata().row_groups() {
+            writer.append_row_group(a.clone(), row_group).unwrap();
+        }
+        for row_group in b_reader.metadata().row_groups() {
+            writer.append_row_group(b.clone(), row_group).unwrap();
+        }
+        writer.close().unwrap();
+
+        let options = ReadOptionsBuilder::new()
+            .with_page_index()
+            .with_reader_properties(
+                ReaderProperties::builder()
+                    .set_read_bloom_filter(true)
+                    .build(),
+            )
+            .build();
+        let reader =
+            SerializedFileReader::new_with_options(Bytes::from(buf), options).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 3);
+        assert_eq!(metadata.file_metadata().num_rows(), 50);
+
+        let offset_indexes = metadata.offset_indexes().unwrap();
+        let page_indexes = metadata.page_indexes().unwrap();
+        for (idx, row_group) in metadata.row_groups().iter().enumerate() {
+            let locations = &offset_indexes[idx][0];
+            assert!(locations.len() > 1);
+            let (start, length) = row_group.column(0).byte_range();
+            assert_eq!(locations[0].offset as u64, start);
+            let last = locations.last().unwrap();
+            assert_eq!(
+                (last.offset + last.compressed_page_size as i64) as u64,
+                start + length
+            );
+            assert!(matches!(page_indexes[idx][0], Index::INT32(_)));
+
+            let row_group_reader = reader.get_row_group(idx).unwrap();
+            let bloom_filter = row_group_reader.get_column_bloom_filter(0).unwrap();
+            let first = [0, 20, 30][idx];
+            assert!(bloom_filter.check(&first));
+        }
+
+        let values: Vec<i32> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.get_int(0).unwrap())
+            .collect();
+        assert_eq!(values, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_file_writer_append_row_group_schema_mismatch() {
+        let data = Arc::new(write_indexed_file(&[vec![1, 2, 3]]));
+        let reader = SerializedFileReader::new(data.as_ref().clone()).unwrap();
+
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![Arc::new(
+                    types::Type::primitive_type_builder("col1", Type::INT64)
+                        .with_repetition(Repetition::REQUIRED)
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        );
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(Vec::new(), schema, props).unwrap();
+        let err = writer
+            .append_row_group(data.clone(), reader.metadata().row_group(0))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Parquet error: Cannot append column chunk, expected column"));
+    }
+
+    #[test]
+    fn test_file_writer_append_row_group_invalid_index_length() {
+        let data = Arc::new(write_indexed_file(&[vec![1, 2, 3]]));
+        let reader = SerializedFileReader::new(data.as_ref().clone()).unwrap();
+        let metadata = reader.metadata();
+
+        let mut row_group = metadata.row_group(0).to_thrift();
+        row_group.columns[0].column_index_length = Some(-1);
+        let schema_descr = metadata.file_metadata().schema_descr_ptr();
+        let row_group = RowGroupMetaData::from_thrift(schema_descr, row_group).unwrap();
+
+        let schema = metadata.file_metadata().schema_descr().root_schema_ptr();
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(Vec::new(), schema, props).unwrap();
+        let err = writer
+            .append_row_group(data.clone(), &row_group)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "Parquet error: Invalid thrift length -1");
+    }
+
+    #[test]
+    fn test_file_writer_page_checksums() {
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![Arc::new(
+                    types::Type::primitive_type_builder("col1", Type::INT32)
+                        .with_repetition(Repetition::REQUIRED)
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        );
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_dictionary_enabled(false)
+                .set_statistics_enabled(EnabledStatistics::None)
+                .set_write_page_checksums(true)
+                .build(),
+        );
+        let values = [1, 2, 0x0BADCAFE, 4];
+        let mut buf = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        let mut row_group_writer = writer.next_row_group().unwrap();
+        let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+        column_writer
+            .typed::<Int32Type>()
+            .write_batch(&values, None, None)
+            .unwrap();
+        column_writer.close().unwrap();
+        row_group_writer.close().unwrap();
+        writer.close().unwrap();
+
+        let read = |data: Vec<u8>, verify: bool| -> Result<Vec<i32>> {
+            let options = ReadOptionsBuilder::new()
+                .with_reader_properties(
+                    ReaderProperties::builder()
+                        .set_verify_page_checksums(verify)
+                        .build(),
+                )
+                .build();
+            let reader =
+                SerializedFileReader::new_with_options(Bytes::from(data), options)?;
+            let mut page_reader = reader.get_row_group(0)?.get_column_page_reader(0)?;
+            let mut values = Vec::new();
+            while let Some(page) = page_reader.get_next_page()? {
+                values.extend(
+                    page.buffer()
+                        .data()
+                        .chunks(4)
+                        .map(|c| i32::from_le_bytes(c.try_into().unwrap())),
+                );
+            }
+            Ok(values)
+        };
+
+        assert_eq!(read(buf.clone(), true).unwrap(), values);
+
+        // Corrupt the third value of the page
+        let pos = buf
+            .windows(4)
+            .position(|w| w == 0x0BADCAFE_i32.to_le_bytes())
+            .unwrap();
+        buf[pos] ^= 1;
+
+        assert_eq!(read(buf.clone(), false).unwrap(), [1, 2, 0x0BADCAFF, 4]);
+        let err = read(buf, true).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Parquet error: Page checksum mismatch for column \"col1\" at offset"
+        ));
+    }
+
+    #[test]
+    fn test_file_reader_max_page_size() {
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![Arc::new(
+                    types::Type::primitive_type_builder("col1", Type::INT32)
+                        .with_repetition(Repetition::REQUIRED)
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        );
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_dictionary_enabled(false)
+                .build(),
+        );
+        let values: Vec<_> = (0..1000).collect();
+        let mut buf = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        let mut row_group_writer = writer.next_row_group().unwrap();
+        let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+        column_writer
+            .typed::<Int32Type>()
+            .write_batch(&values, None, None)
+            .unwrap();
+        column_writer.close().unwrap();
+        row_group_writer.close().unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buf);
+
+        let read = |max_page_size: usize, page_index: bool| -> Result<usize> {
+            let mut options = ReadOptionsBuilder::new().with_reader_properties(
+                ReaderProperties::builder()
+                    .set_max_page_size(max_page_size)
+                    .build(),
+            );
+            if page_index {
+                options = options.with_page_index();
+            }
+            let reader =
+                SerializedFileReader::new_with_options(data.clone(), options.build())?;
+            let mut page_reader = reader.get_row_group(0)?.get_column_page_reader(0)?;
+            let mut num_pages = 0;
+            while page_reader.get_next_page()?.is_some() {
+                num_pages += 1;
+            }
+            Ok(num_pages)
+        };
+
+        for page_index in [false, true] {
+            assert_eq!(read(4000, page_index).unwrap(), 1);
+            let err = read(3999, page_index).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Parquet error: Page size of 4000 bytes exceeds the maximum page size of 3999 bytes"
+            );
+        }
+    }
+
+    #[test]
+    fn test_file_reader_bytes_zero_copy() {
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![Arc::new(
+                    types::Type::primitive_type_builder("col1", Type::BYTE_ARRAY)
+                        .with_repetition(Repetition::REQUIRED)
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        );
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_dictionary_enabled(false)
+                .build(),
+        );
+        let values: Vec<ByteArray> = (0..100)
+            .map(|i| format!("value{}", i).as_str().into())
+            .collect();
+        let mut buf = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        let mut row_group_writer = writer.next_row_group().unwrap();
+        let mut column_writer = row_group_writer.next_column().unwrap().unwrap();
+        column_writer
+            .typed::<ByteArrayType>()
+            .write_batch(&values, None, None)
+            .unwrap();
+        column_writer.close().unwrap();
+        row_group_writer.close().unwrap();
+        writer.close().unwrap();
+        let data = Bytes::from(buf);
+        let range = data.as_ref().as_ptr_range();
+
+        // The values of uncompressed pages reference the bytes of the file
+        let reader = SerializedFileReader::new(data.clone()).unwrap();
+        let row_group = reader.get_row_group(0).unwrap();
+        let mut column_reader = match row_group.get_column_reader(0).unwrap() {
+            ColumnReader::ByteArrayColumnReader(r) => r,
+            _ => unreachable!(),
+        };
+        let mut read = vec![ByteArray::new(); 100];
+        let (num_values, _) = column_reader
+            .read_batch(100, None, None, &mut read)
+            .unwrap();
+        assert_eq!(num_values, 100);
+        assert_eq!(read, values);
+        assert!(read.iter().all(|v| range.contains(&v.data().as_ptr())));
+    }
+
     #[test]
     fn test_page_writer_data_pages() {
         let pages = vec![
@@ -1334,4 +2256,99 @@ mod tests {
             })
         });
     }
+
+    /// Writes two row groups of columns `a` and `b`, where `a` only has chunk statistics
+    fn write_page_index_file(props: WriterPropertiesBuilder) -> Bytes {
+        let schema = Arc::new(
+            types::Type::group_type_builder("schema")
+                .with_fields(&mut vec![
+                    Arc::new(
+                        types::Type::primitive_type_builder("a", Type::INT32)
+                            .with_repetition(Repetition::REQUIRED)
+                            .build()
+                            .unwrap(),
+                    ),
+                    Arc::new(
+                        types::Type::primitive_type_builder("b", Type::INT32)
+                            .with_repetition(Repetition::REQUIRED)
+                            .build()
+                            .unwrap(),
+                    ),
+                ])
+                .build()
+                .unwrap(),
+        );
+        let props = props
+            .set_column_statistics_enabled(
+                ColumnPath::from("a"),
+                EnabledStatistics::Chunk,
+            )
+            .set_data_page_row_count_limit(2)
+            .set_write_batch_size(2)
+            .build();
+
+        let mut file = Vec::new();
+        let mut writer =
+            SerializedFileWriter::new(&mut file, schema, Arc::new(props)).unwrap();
+        for _ in 0..2 {
+            let mut row_group_writer = writer.next_row_group().unwrap();
+            while let Some(mut column) = row_group_writer.next_column().unwrap() {
+                column
+                    .typed::<Int32Type>()
+                    .write_batch(&[1, 2, 3, 4, 5, 6], None, None)
+                    .unwrap();
+                column.close().unwrap();
+            }
+            row_group_writer.close().unwrap();
+        }
+        writer.close().unwrap();
+        Bytes::from(file)
+    }
+
+    #[test]
+    fn test_write_page_index() {
+        let file = write_page_index_file(WriterProperties::builder());
+        let options = ReadOptionsBuilder::new().with_page_index().build();
+        let reader = SerializedFileReader::new_with_options(file, options).unwrap();
+        let metadata = reader.metadata();
+
+        let column_indexes = metadata.page_indexes().unwrap();
+        let offset_indexes = metadata.offset_indexes().unwrap();
+        assert_eq!(column_indexes.len(), 2);
+        assert_eq!(offset_indexes.len(), 2);
+
+        for (row_group, (column_index, offset_index)) in metadata
+            .row_groups()
+            .iter()
+            .zip(column_indexes.iter().zip(offset_indexes))
+        {
+            // `a` has no page statistics and so only has an offset index
+            assert!(row_group.column(0).column_index_offset().is_none());
+            assert!(row_group.column(1).column_index_offset().is_some());
+            assert!(matches!(column_index[0], Index::NONE));
+            match &column_index[1] {
+                Index::INT32(index) => {
+                    let min: Vec<_> = index.indexes.iter().map(|i| i.min).collect();
+                    assert_eq!(min, vec![Some(1), Some(3), Some(5)]);
+                }
+                _ => unreachable!(),
+            }
+
+            for locations in offset_index {
+                let first_rows: Vec<_> =
+                    locations.iter().map(|l| l.first_row_index).collect();
+                assert_eq!(first_rows, vec![0, 2, 4]);
+            }
+        }
+
+        let props = WriterProperties::builder().set_write_page_index(false);
+        let file = write_page_index_file(props);
+        let reader = SerializedFileReader::new(file).unwrap();
+        for row_group in reader.metadata().row_groups() {
+            for column in row_group.columns() {
+                assert!(column.column_index_offset().is_none());
+                assert!(column.offset_index_offset().is_none());
+            }
+        }
+    }
 }
@@ -42,6 +42,44 @@ macro_rules! nyi {
     }};
 }
 
+/// Macro to generate type-safe get_xxx_by_name methods for primitive types,
+/// e.g. `get_bool_by_name`, `get_short_by_name`.
+macro_rules! row_named_primitive_accessor {
+    ($METHOD:ident, $VARIANT:ident, $TY:ty) => {
+        /// Get the value of the field named `name`, see [`Row::get_field`].
+        pub fn $METHOD(&self, name: &str) -> Result<$TY> {
+            match self.get_field(name)? {
+                Field::$VARIANT(v) => Ok(*v),
+                field => Err(general_err!(
+                    "Cannot access field {} of type {} as {}",
+                    name,
+                    field.get_type_name(),
+                    stringify!($VARIANT)
+                )),
+            }
+        }
+    };
+}
+
+/// Macro to generate type-safe get_xxx_by_name methods for reference types,
+/// e.g. `get_list_by_name`, `get_map_by_name`.
+macro_rules! row_named_complex_accessor {
+    ($METHOD:ident, $VARIANT:ident, $TY:ty) => {
+        /// Get the value of the field named `name`, see [`Row::get_field`].
+        pub fn $METHOD(&self, name: &str) -> Result<&$TY> {
+            match self.get_field(name)? {
+                Field::$VARIANT(v) => Ok(v),
+                field => Err(general_err!(
+                    "Cannot access field {} of type {} as {}",
+                    name,
+                    field.get_type_name(),
+                    stringify!($VARIANT)
+                )),
+            }
+        }
+    };
+}
+
 /// `Row` represents a nested Parquet record.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Row {
@@ -79,6 +117,61 @@ impl Row {
         }
     }
 
+    /// Get the field named `name`, returning an error if there is none.
+    ///
+    /// Fields within groups can be accessed by their dotted path, e.g. `"a.b"`
+    /// for the field `b` of the group `a`.
+    pub fn get_field(&self, name: &str) -> Result<&Field> {
+        if let Some((_, field)) = self.fields.iter().find(|(n, _)| n == name) {
+            return Ok(field);
+        }
+        let field = name.split_once('.').and_then(|(group, rest)| {
+            match self.fields.iter().find(|(n, _)| n == group) {
+                Some((_, Field::Group(row))) => row.get_field(rest).ok(),
+                _ => None,
+            }
+        });
+        field.ok_or_else(|| general_err!("Field {} not found in row", name))
+    }
+
+    row_named_primitive_accessor!(get_bool_by_name, Bool, bool);
+
+    row_named_primitive_accessor!(get_byte_by_name, Byte, i8);
+
+    row_named_primitive_accessor!(get_short_by_name, Short, i16);
+
+    row_named_primitive_accessor!(get_int_by_name, Int, i32);
+
+    row_named_primitive_accessor!(get_long_by_name, Long, i64);
+
+    row_named_primitive_accessor!(get_ubyte_by_name, UByte, u8);
+
+    row_named_primitive_accessor!(get_ushort_by_name, UShort, u16);
+
+    row_named_primitive_accessor!(get_uint_by_name, UInt, u32);
+
+    row_named_primitive_accessor!(get_ulong_by_name, ULong, u64);
+
+    row_named_primitive_accessor!(get_float_by_name, Float, f32);
+
+    row_named_primitive_accessor!(get_double_by_name, Double, f64);
+
+    row_named_primitive_accessor!(get_timestamp_millis_by_name, TimestampMillis, u64);
+
+    row_named_primitive_accessor!(get_timestamp_micros_by_name, TimestampMicros, u64);
+
+    row_named_complex_accessor!(get_decimal_by_name, Decimal, Decimal);
+
+    row_named_complex_accessor!(get_string_by_name, Str, String);
+
+    row_named_complex_accessor!(get_bytes_by_name, Bytes, ByteArray);
+
+    row_named_complex_accessor!(get_group_by_name, Group, Row);
+
+    row_named_complex_accessor!(get_list_by_name, ListInternal, List);
+
+    row_named_complex_accessor!(get_map_by_name, MapInternal, Map);
+
     #[cfg(any(feature = "json", test))]
     pub fn to_json_value(&self) -> Value {
         Value::Object(
@@ -1545,6 +1638,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_row_named_accessors() {
+        let row = make_row(vec![
+            ("a".to_string(), Field::Null),
+            ("b".to_string(), Field::Bool(true)),
+            ("c.d".to_string(), Field::Long(6)),
+            (
+                "e".to_string(),
+                Field::Group(make_row(vec![
+                    ("x".to_string(), Field::Str("abc".to_string())),
+                    (
+                        "y".to_string(),
+                        Field::Group(make_row(vec![("z".to_string(), Field::Int(2))])),
+                    ),
+                ])),
+            ),
+            (
+                "f".to_string(),
+                Field::ListInternal(make_list(vec![Field::Int(2), Field::Int(1)])),
+            ),
+        ]);
+
+        assert_eq!(row.get_field("a").unwrap(), &Field::Null);
+        assert!(row.get_bool_by_name("b").unwrap());
+        assert_eq!(6, row.get_long_by_name("c.d").unwrap());
+        assert_eq!("abc", row.get_string_by_name("e.x").unwrap());
+        assert_eq!(2, row.get_int_by_name("e.y.z").unwrap());
+        assert_eq!(2, row.get_group_by_name("e").unwrap().len());
+        assert_eq!(2, row.get_list_by_name("f").unwrap().len());
+
+        assert_eq!(
+            ParquetError::General("Field g not found in row".to_string()),
+            row.get_int_by_name("g").unwrap_err()
+        );
+        assert_eq!(
+            ParquetError::General("Field e.y.w not found in row".to_string()),
+            row.get_int_by_name("e.y.w").unwrap_err()
+        );
+        assert_eq!(
+            ParquetError::General("Field b.x not found in row".to_string()),
+            row.get_field("b.x").unwrap_err()
+        );
+        assert_eq!(
+            ParquetError::General(
+                "Cannot access field e.y of type Group as Int".to_string()
+            ),
+            row.get_int_by_name("e.y").unwrap_err()
+        );
+        assert_eq!(
+            ParquetError::General(
+                "Cannot access field a of type Null as Str".to_string()
+            ),
+            row.get_string_by_name("a").unwrap_err()
+        );
+    }
+
     #[test]
     fn test_list_primitive_accessors() {
         // primitives
@@ -19,6 +19,7 @@
 
 mod api;
 pub mod reader;
+mod record_reader;
 mod record_writer;
 mod triplet;
 
@@ -27,5 +28,6 @@ pub use self::{
         Field, List, ListAccessor, Map, MapAccessor, Row, RowAccessor, RowColumnIter,
         RowFormatter,
     },
-    record_writer::RecordWriter,
+    record_reader::{RecordGroupReader, RecordReader},
+    record_writer::{RecordGroupWriter, RecordSlot, RecordWriter},
 };
@@ -729,6 +729,36 @@ impl<'a> RowIter<'a> {
         }
     }
 
+    /// Tries to create a iterator of [`Row`](crate::record::Row)s projected to the
+    /// columns at the dotted `paths`, e.g. `"a.b.c"`, of the file schema. Returns a
+    /// error if a file reader is not the source of this iterator, or if a path is not
+    /// found in the file schema.
+    ///
+    /// A path to a group selects all the columns within it, and the columns are
+    /// returned in the order of the file schema regardless of the order of `paths`.
+    pub fn project_paths<S: AsRef<str>>(self, paths: &[S]) -> Result<Self> {
+        let schema = match self.file_reader {
+            Some(ref either) => either
+                .reader()
+                .metadata()
+                .file_metadata()
+                .schema_descr()
+                .root_schema_ptr(),
+            None => {
+                return Err(general_err!("File reader is required to use projections"))
+            }
+        };
+        let paths: Vec<Vec<&str>> = paths
+            .iter()
+            .map(|path| path.as_ref().split('.').collect())
+            .collect();
+        for path in &paths {
+            check_path(&schema, path)?;
+        }
+        let projection = project_type(&schema, &paths)?;
+        self.project(Some(projection))
+    }
+
     /// Helper method to get schema descriptor for projected schema.
     /// If projection is None, then full schema is returned.
     #[inline]
@@ -757,6 +787,60 @@ impl<'a> RowIter<'a> {
     }
 }
 
+/// Returns an error if `path` is not the path of a field within `tpe`
+fn check_path(tpe: &Type, path: &[&str]) -> Result<()> {
+    let mut current = tpe;
+    for name in path {
+        current = match current {
+            Type::GroupType { fields, .. } => fields
+                .iter()
+                .find(|field| field.name() == *name)
+                .ok_or_else(|| {
+                    general_err!("Column {} not found in schema", path.join("."))
+                })?,
+            Type::PrimitiveType { .. } => {
+                return Err(general_err!(
+                    "Column {} not found in schema, {} is not a group",
+                    path.join("."),
+                    current.name()
+                ))
+            }
+        };
+    }
+    Ok(())
+}
+
+/// Returns `tpe` with only the fields at `paths`, relative to `tpe`, in schema order.
+/// An empty path selects `tpe` in its entirety.
+fn project_type(tpe: &TypePtr, paths: &[Vec<&str>]) -> Result<Type> {
+    if paths.iter().any(|path| path.is_empty()) || tpe.is_primitive() {
+        return Ok(tpe.as_ref().clone());
+    }
+
+    let mut fields = Vec::new();
+    for field in tpe.get_fields() {
+        let field_paths: Vec<_> = paths
+            .iter()
+            .filter(|path| path[0] == field.name())
+            .map(|path| path[1..].to_vec())
+            .collect();
+        if !field_paths.is_empty() {
+            fields.push(Arc::new(project_type(field, &field_paths)?));
+        }
+    }
+
+    let info = tpe.get_basic_info();
+    let mut builder = Type::group_type_builder(info.name())
+        .with_converted_type(info.converted_type())
+        .with_logical_type(info.logical_type())
+        .with_id(info.has_id().then(|| info.id()))
+        .with_fields(&mut fields);
+    if info.has_repetition() {
+        builder = builder.with_repetition(info.repetition());
+    }
+    builder.build()
+}
+
 impl<'a> Iterator for RowIter<'a> {
     type Item = Row;
 
@@ -1624,6 +1708,117 @@ mod tests {
         assert_eq!(rows, expected_rows);
     }
 
+    #[test]
+    fn test_file_reader_rows_project_paths() {
+        use crate::data_type::{
+            ByteArray, ByteArrayType, DoubleType, Int32Type, Int64Type,
+        };
+        use crate::file::properties::WriterProperties;
+        use crate::file::writer::SerializedFileWriter;
+        use bytes::Bytes;
+
+        let schema = Arc::new(
+            parse_message_type(
+                "message schema {
+                    REQUIRED INT32 a;
+                    REQUIRED GROUP b {
+                        REQUIRED INT64 c;
+                        REQUIRED BYTE_ARRAY d (UTF8);
+                    }
+                    REQUIRED DOUBLE e;
+                }",
+            )
+            .unwrap(),
+        );
+        let mut buf = Vec::new();
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(&mut buf, schema, props).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int32Type>()
+            .write_batch(&[1, 2], None, None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&[10, 20], None, None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        let values = [ByteArray::from("x"), ByteArray::from("y")];
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&values, None, None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<DoubleType>()
+            .write_batch(&[1.5, 2.5], None, None)
+            .unwrap();
+        column.close().unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(Bytes::from(buf)).unwrap();
+        let rows = |paths: &[&str]| -> Result<Vec<Row>> {
+            Ok(reader.get_row_iter(None)?.project_paths(paths)?.collect())
+        };
+
+        let expected = vec![
+            row![
+                (
+                    "b".to_string(),
+                    group![("d".to_string(), Field::Str("x".to_string()))]
+                ),
+                ("e".to_string(), Field::Double(1.5))
+            ],
+            row![
+                (
+                    "b".to_string(),
+                    group![("d".to_string(), Field::Str("y".to_string()))]
+                ),
+                ("e".to_string(), Field::Double(2.5))
+            ],
+        ];
+        // Columns are returned in schema order
+        assert_eq!(rows(&["e", "b.d"]).unwrap(), expected);
+
+        let expected = vec![
+            row![(
+                "b".to_string(),
+                group![
+                    ("c".to_string(), Field::Long(10)),
+                    ("d".to_string(), Field::Str("x".to_string()))
+                ]
+            )],
+            row![(
+                "b".to_string(),
+                group![
+                    ("c".to_string(), Field::Long(20)),
+                    ("d".to_string(), Field::Str("y".to_string()))
+                ]
+            )],
+        ];
+        // A group selects all of its columns
+        assert_eq!(rows(&["b", "b.c"]).unwrap(), expected);
+
+        let err = rows(&["a", "b.x"]).unwrap_err();
+        assert_eq!(
+            err,
+            ParquetError::General("Column b.x not found in schema".to_string())
+        );
+        let err = rows(&["a.x"]).unwrap_err();
+        assert_eq!(
+            err,
+            ParquetError::General(
+                "Column a.x not found in schema, a is not a group".to_string()
+            )
+        );
+    }
+
     fn test_file_reader_rows(file_name: &str, schema: Option<Type>) -> Result<Vec<Row>> {
         let file = get_test_file(file_name);
         let file_reader: Box<dyn FileReader> = Box::new(SerializedFileReader::new(file)?);
@@ -17,6 +17,8 @@
 
 use crate::schema::types::TypePtr;
 
+use super::super::column::writer::{ColumnWriter, ColumnWriterImpl};
+use super::super::data_type::DataType;
 use super::super::errors::ParquetError;
 use super::super::file::writer::SerializedRowGroupWriter;
 
@@ -29,3 +31,182 @@ pub trait RecordWriter<T> {
     /// Generated schema
     fn schema(&self) -> Result<TypePtr, ParquetError>;
 }
+
+/// Writes the columns of a struct nested within a record as a parquet group.
+///
+/// Implemented by `#[derive(ParquetRecordWriter)]`, so that derived structs can be
+/// used as the fields of other derived structs.
+pub trait RecordGroupWriter {
+    /// The number of leaf columns of this group
+    fn num_columns() -> usize;
+
+    /// The fields of this group
+    fn group_fields() -> Result<Vec<TypePtr>, ParquetError>;
+
+    /// Writes the `column`-th leaf column of this group to `column_writer`, for the
+    /// occurrences of this group in `slots`, nested within `rep_depth` repeated fields
+    fn write_group_column(
+        column: usize,
+        slots: &[RecordSlot<'_, Self>],
+        rep_depth: i16,
+        column_writer: &mut ColumnWriter<'_>,
+    ) -> Result<(), ParquetError>;
+}
+
+/// An occurrence of a possibly absent value within the records being written, with
+/// the definition and repetition levels of its position in a column.
+///
+/// The fields of nested records are written by mapping the slots of their parent to
+/// their own with [`Self::map_required`], [`Self::map_optional`] and
+/// [`Self::map_list`], and writing the slots of the leaves with
+/// [`Self::write_column`].
+#[derive(Debug)]
+pub struct RecordSlot<'a, T: ?Sized> {
+    /// The value, or `None` if it or one of its ancestors is absent
+    pub value: Option<&'a T>,
+    /// The definition level of this slot
+    pub def_level: i16,
+    /// The repetition level of this slot
+    pub rep_level: i16,
+}
+
+impl<'a, T: ?Sized> Clone for RecordSlot<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: ?Sized> Copy for RecordSlot<'a, T> {}
+
+impl<'a, T: ?Sized> RecordSlot<'a, T> {
+    /// Creates the slot of a top-level record
+    pub fn new(value: &'a T) -> Self {
+        Self {
+            value: Some(value),
+            def_level: 0,
+            rep_level: 0,
+        }
+    }
+
+    /// Maps `slots` to those of a required field, extracted with `f`
+    pub fn map_required<U: ?Sized>(
+        slots: &[Self],
+        f: impl Fn(&'a T) -> &'a U,
+    ) -> Vec<RecordSlot<'a, U>> {
+        slots
+            .iter()
+            .map(|slot| RecordSlot {
+                value: slot.value.map(&f),
+                def_level: slot.def_level,
+                rep_level: slot.rep_level,
+            })
+            .collect()
+    }
+
+    /// Maps `slots` to those of an optional field, extracted with `f`
+    pub fn map_optional<U: ?Sized>(
+        slots: &[Self],
+        f: impl Fn(&'a T) -> Option<&'a U>,
+    ) -> Vec<RecordSlot<'a, U>> {
+        slots
+            .iter()
+            .map(|slot| match slot.value.and_then(&f) {
+                Some(value) => RecordSlot {
+                    value: Some(value),
+                    def_level: slot.def_level + 1,
+                    rep_level: slot.rep_level,
+                },
+                None => RecordSlot {
+                    value: None,
+                    def_level: slot.def_level,
+                    rep_level: slot.rep_level,
+                },
+            })
+            .collect()
+    }
+
+    /// Maps `slots` to those of the elements of a list field, extracted with `f`,
+    /// nested within `rep_depth` repeated fields
+    pub fn map_list<U>(
+        slots: &[Self],
+        rep_depth: i16,
+        f: impl Fn(&'a T) -> &'a [U],
+    ) -> Vec<RecordSlot<'a, U>> {
+        let mut mapped = Vec::with_capacity(slots.len());
+        for slot in slots {
+            match slot.value.map(&f) {
+                Some(elements) if !elements.is_empty() => {
+                    mapped.extend(elements.iter().enumerate().map(|(idx, value)| {
+                        RecordSlot {
+                            value: Some(value),
+                            def_level: slot.def_level + 1,
+                            rep_level: match idx {
+                                0 => slot.rep_level,
+                                _ => rep_depth + 1,
+                            },
+                        }
+                    }))
+                }
+                _ => mapped.push(RecordSlot {
+                    value: None,
+                    def_level: slot.def_level,
+                    rep_level: slot.rep_level,
+                }),
+            }
+        }
+        mapped
+    }
+
+    /// Writes the values of `slots`, converted with `f`, and their levels to `writer`
+    pub fn write_column<D: DataType>(
+        slots: &[Self],
+        writer: &mut ColumnWriterImpl<'_, D>,
+        f: impl Fn(&'a T) -> D::T,
+    ) -> Result<(), ParquetError> {
+        let values: Vec<D::T> =
+            slots.iter().filter_map(|slot| slot.value).map(f).collect();
+
+        let descr = writer.get_descriptor();
+        let def_levels: Option<Vec<i16>> = (descr.max_def_level() > 0)
+            .then(|| slots.iter().map(|slot| slot.def_level).collect());
+        let rep_levels: Option<Vec<i16>> = (descr.max_rep_level() > 0)
+            .then(|| slots.iter().map(|slot| slot.rep_level).collect());
+
+        writer.write_batch(&values, def_levels.as_deref(), rep_levels.as_deref())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_slot_levels() {
+        struct Record {
+            a: Option<Vec<Option<i32>>>,
+        }
+
+        let records = [
+            Record { a: None },
+            Record { a: Some(vec![]) },
+            Record {
+                a: Some(vec![Some(1), None, Some(2)]),
+            },
+        ];
+
+        let slots: Vec<_> = records.iter().map(RecordSlot::new).collect();
+        let slots = RecordSlot::map_required(&slots, |r| &r.a);
+        let slots = RecordSlot::map_optional(&slots, |v| v.as_ref());
+        let slots = RecordSlot::map_list(&slots, 0, |v| v.as_slice());
+        let slots = RecordSlot::map_optional(&slots, |v| v.as_ref());
+
+        let values: Vec<_> = slots.iter().map(|s| s.value.copied()).collect();
+        let def_levels: Vec<_> = slots.iter().map(|s| s.def_level).collect();
+        let rep_levels: Vec<_> = slots.iter().map(|s| s.rep_level).collect();
+
+        assert_eq!(values, vec![None, None, Some(1), None, Some(2)]);
+        assert_eq!(def_levels, vec![0, 1, 3, 2, 3]);
+        assert_eq!(rep_levels, vec![0, 0, 0, 1, 1]);
+    }
+}
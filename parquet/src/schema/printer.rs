@@ -109,6 +109,21 @@ pub fn print_schema(out: &mut dyn io::Write, tp: &Type) {
 fn print_row_group_metadata(out: &mut dyn io::Write, rg_metadata: &RowGroupMetaData) {
     writeln!(out, "total byte size: {}", rg_metadata.total_byte_size());
     writeln!(out, "num of rows: {}", rg_metadata.num_rows());
+    if let Some(sorting_columns) = rg_metadata.sorting_columns() {
+        let sorting_strs: Vec<_> = sorting_columns
+            .iter()
+            .map(|c| {
+                let column = match rg_metadata.columns().get(c.column_idx as usize) {
+                    Some(cc) => cc.column_path().string(),
+                    None => c.column_idx.to_string(),
+                };
+                let order = if c.descending { "DESC" } else { "ASC" };
+                let nulls = if c.nulls_first { "FIRST" } else { "LAST" };
+                format!("{} {} NULLS {}", column, order, nulls)
+            })
+            .collect();
+        writeln!(out, "sorting columns: {}", sorting_strs.join(", "));
+    }
     writeln!(out);
     writeln!(out, "num of columns: {}", rg_metadata.num_columns());
     writeln!(out, "columns: ");
@@ -386,7 +401,11 @@ mod tests {
 
     use crate::basic::{LogicalType, Repetition, Type as PhysicalType};
     use crate::errors::Result;
-    use crate::schema::{parser::parse_message_type, types::Type};
+    use crate::format::SortingColumn;
+    use crate::schema::{
+        parser::parse_message_type,
+        types::{SchemaDescriptor, Type},
+    };
 
     fn assert_print_parse_message(message: Type) {
         let mut s = String::new();
@@ -694,22 +713,22 @@ mod tests {
             let f1 = Type::primitive_type_builder("f1", PhysicalType::INT32)
                 .with_repetition(Repetition::REQUIRED)
                 .with_converted_type(ConvertedType::INT_32)
-                .with_id(0)
+                .with_id(Some(0))
                 .build();
             let f2 = Type::primitive_type_builder("f2", PhysicalType::BYTE_ARRAY)
                 .with_converted_type(ConvertedType::UTF8)
-                .with_id(1)
+                .with_id(Some(1))
                 .build();
             let f3 = Type::primitive_type_builder("f3", PhysicalType::BYTE_ARRAY)
                 .with_logical_type(Some(LogicalType::String))
-                .with_id(1)
+                .with_id(Some(1))
                 .build();
             let f4 =
                 Type::primitive_type_builder("f4", PhysicalType::FIXED_LEN_BYTE_ARRAY)
                     .with_repetition(Repetition::REPEATED)
                     .with_converted_type(ConvertedType::INTERVAL)
                     .with_length(12)
-                    .with_id(2)
+                    .with_id(Some(2))
                     .build();
 
             let mut struct_fields = vec![
@@ -720,14 +739,14 @@ mod tests {
             let field = Type::group_type_builder("field")
                 .with_repetition(Repetition::OPTIONAL)
                 .with_fields(&mut struct_fields)
-                .with_id(1)
+                .with_id(Some(1))
                 .build()
                 .unwrap();
 
             let mut fields = vec![Arc::new(field), Arc::new(f4.unwrap())];
             let message = Type::group_type_builder("schema")
                 .with_fields(&mut fields)
-                .with_id(2)
+                .with_id(Some(2))
                 .build()
                 .unwrap();
             p.print(&message);
@@ -866,4 +885,60 @@ mod tests {
 
         assert_print_parse_message(message);
     }
+
+    #[test]
+    fn test_print_row_group_sorting_columns() {
+        let schema = parse_message_type(
+            "message schema { REQUIRED INT32 a; OPTIONAL BYTE_ARRAY b; }",
+        )
+        .unwrap();
+        let descr = Arc::new(SchemaDescriptor::new(Arc::new(schema)));
+        let columns = descr
+            .columns()
+            .iter()
+            .map(|c| ColumnChunkMetaData::builder(c.clone()).build().unwrap())
+            .collect();
+        let sorting_columns = vec![
+            SortingColumn {
+                column_idx: 1,
+                descending: true,
+                nulls_first: true,
+            },
+            SortingColumn {
+                column_idx: 0,
+                descending: false,
+                nulls_first: false,
+            },
+            SortingColumn {
+                column_idx: 2,
+                descending: false,
+                nulls_first: true,
+            },
+        ];
+        let rg_metadata = RowGroupMetaData::builder(descr)
+            .set_column_metadata(columns)
+            .set_sorting_columns(Some(sorting_columns))
+            .build()
+            .unwrap();
+
+        let mut out = Vec::new();
+        print_row_group_metadata(&mut out, &rg_metadata);
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.contains(
+                "sorting columns: b DESC NULLS FIRST, a ASC NULLS LAST, 2 ASC NULLS FIRST\n"
+            ),
+            "{}",
+            out
+        );
+
+        let rg_metadata = RowGroupMetaData::builder(rg_metadata.schema_descr_ptr())
+            .set_column_metadata(rg_metadata.columns().to_vec())
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+        print_row_group_metadata(&mut out, &rg_metadata);
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("sorting columns"), "{}", out);
+    }
 }
@@ -262,8 +262,8 @@ impl<'a> PrimitiveTypeBuilder<'a> {
     }
 
     /// Sets optional field id and returns itself.
-    pub fn with_id(mut self, id: i32) -> Self {
-        self.id = Some(id);
+    pub fn with_id(mut self, id: Option<i32>) -> Self {
+        self.id = id;
         self
     }
 
@@ -578,8 +578,8 @@ impl<'a> GroupTypeBuilder<'a> {
     }
 
     /// Sets optional field id and returns itself.
-    pub fn with_id(mut self, id: i32) -> Self {
-        self.id = Some(id);
+    pub fn with_id(mut self, id: Option<i32>) -> Self {
+        self.id = id;
         self
     }
 
@@ -1082,16 +1082,14 @@ fn from_thrift_helper(
             let scale = elements[index].scale.unwrap_or(-1);
             let precision = elements[index].precision.unwrap_or(-1);
             let name = &elements[index].name;
-            let mut builder = Type::primitive_type_builder(name, physical_type)
+            let builder = Type::primitive_type_builder(name, physical_type)
                 .with_repetition(repetition)
                 .with_converted_type(converted_type)
                 .with_logical_type(logical_type)
                 .with_length(length)
                 .with_precision(precision)
-                .with_scale(scale);
-            if let Some(id) = field_id {
-                builder = builder.with_id(id);
-            }
+                .with_scale(scale)
+                .with_id(field_id);
             Ok((index + 1, Arc::new(builder.build()?)))
         }
         Some(n) => {
@@ -1111,7 +1109,8 @@ fn from_thrift_helper(
             let mut builder = Type::group_type_builder(&elements[index].name)
                 .with_converted_type(converted_type)
                 .with_logical_type(logical_type)
-                .with_fields(&mut fields);
+                .with_fields(&mut fields)
+                .with_id(field_id);
             if let Some(rep) = repetition {
                 // Sometimes parquet-cpp and parquet-mr set repetition level REQUIRED or
                 // REPEATED for root node.
@@ -1124,9 +1123,6 @@ fn from_thrift_helper(
                     builder = builder.with_repetition(rep);
                 }
             }
-            if let Some(id) = field_id {
-                builder = builder.with_id(id);
-            }
             Ok((next_index, Arc::new(builder.build().unwrap())))
         }
     }
@@ -1232,7 +1228,7 @@ mod tests {
                 bit_width: 32,
                 is_signed: true,
             }))
-            .with_id(0)
+            .with_id(Some(0))
             .build();
         assert!(result.is_ok());
 
@@ -1514,12 +1510,12 @@ mod tests {
     fn test_group_type() {
         let f1 = Type::primitive_type_builder("f1", PhysicalType::INT32)
             .with_converted_type(ConvertedType::INT_32)
-            .with_id(0)
+            .with_id(Some(0))
             .build();
         assert!(f1.is_ok());
         let f2 = Type::primitive_type_builder("f2", PhysicalType::BYTE_ARRAY)
             .with_converted_type(ConvertedType::UTF8)
-            .with_id(1)
+            .with_id(Some(1))
             .build();
         assert!(f2.is_ok());
 
@@ -1529,7 +1525,7 @@ mod tests {
             .with_repetition(Repetition::REPEATED)
             .with_logical_type(Some(LogicalType::List))
             .with_fields(&mut fields)
-            .with_id(1)
+            .with_id(Some(1))
             .build();
         assert!(result.is_ok());
 
@@ -34,6 +34,10 @@ mod parquet_field;
 /// the correct writing code for each field of the struct. Column writers
 /// are generated in the order they are defined.
 ///
+/// Also derives `RecordGroupWriter`, so that the struct may itself be a
+/// field of another derived struct, written as a parquet group. `Vec<T>`
+/// fields, other than `Vec<u8>`, are written as parquet lists.
+///
 /// It is up to the programmer to keep the order of the struct
 /// fields lined up with the schema.
 ///
@@ -86,8 +90,55 @@ pub fn parquet_record_writer(input: proc_macro::TokenStream) -> proc_macro::Toke
 
     let field_infos: Vec<_> = fields.iter().map(parquet_field::Field::from).collect();
 
-    let writer_snippets: Vec<proc_macro2::TokenStream> =
-        field_infos.iter().map(|x| x.writer_snippet()).collect();
+    let writer_snippets: Vec<proc_macro2::TokenStream> = field_infos
+        .iter()
+        .map(|x| {
+            if x.is_flat() {
+                let writer_snippet = x.writer_snippet();
+                quote! {
+                    let mut some_column_writer = row_group_writer.next_column().unwrap();
+                    if let Some(mut column_writer) = some_column_writer {
+                        #writer_snippet
+                        column_writer.close()?;
+                    } else {
+                        return Err(::parquet::errors::ParquetError::General("Failed to get next column".into()))
+                    }
+                }
+            } else {
+                let num_columns = x.num_columns();
+                let writer_snippet = x.nested_writer_snippet(
+                    quote! { &slots },
+                    quote! { column },
+                    quote! { 0 },
+                );
+                quote! {
+                    let slots: Vec<_> = records.iter().map(::parquet::record::RecordSlot::new).collect();
+                    for column in 0..#num_columns {
+                        if let Some(mut serialized) = row_group_writer.next_column()? {
+                            let column_writer = serialized.untyped();
+                            #writer_snippet?;
+                            serialized.close()?;
+                        } else {
+                            return Err(::parquet::errors::ParquetError::General("Failed to get next column".into()))
+                        }
+                    }
+                }
+            }
+        })
+        .collect();
+
+    let num_columns: Vec<proc_macro2::TokenStream> =
+        field_infos.iter().map(|x| x.num_columns()).collect();
+    let group_writer_snippets: Vec<proc_macro2::TokenStream> = field_infos
+        .iter()
+        .map(|x| {
+            x.nested_writer_snippet(
+                quote! { slots },
+                quote! { column },
+                quote! { rep_depth },
+            )
+        })
+        .collect();
 
     let derived_for = input.ident;
     let generics = input.generics;
@@ -108,13 +159,7 @@ pub fn parquet_record_writer(input: proc_macro::TokenStream) -> proc_macro::Toke
 
         #(
           {
-              let mut some_column_writer = row_group_writer.next_column().unwrap();
-              if let Some(mut column_writer) = some_column_writer {
-                  #writer_snippets
-                  column_writer.close()?;
-              } else {
-                  return Err(::parquet::errors::ParquetError::General("Failed to get next column".into()))
-              }
+              #writer_snippets
           }
         );*
 
@@ -136,5 +181,153 @@ pub fn parquet_record_writer(input: proc_macro::TokenStream) -> proc_macro::Toke
         Ok(group.into())
       }
     }
+
+    impl #generics ::parquet::record::RecordGroupWriter for #derived_for #generics {
+      fn num_columns() -> usize {
+        0 #( + #num_columns )*
+      }
+
+      fn group_fields() -> Result<::std::vec::Vec<::parquet::schema::types::TypePtr>, ::parquet::errors::ParquetError> {
+        use ::parquet::schema::types::Type as ParquetType;
+        use ::parquet::schema::types::TypePtr;
+        use ::parquet::basic::LogicalType;
+
+        let mut fields: ::std::vec::Vec<TypePtr> = ::std::vec::Vec::new();
+        #(
+          #field_types
+        );*;
+        Ok(fields)
+      }
+
+      #[allow(unused_variables, unused_assignments)]
+      fn write_group_column(
+        column: usize,
+        slots: &[::parquet::record::RecordSlot<'_, Self>],
+        rep_depth: i16,
+        column_writer: &mut ::parquet::column::writer::ColumnWriter<'_>,
+      ) -> Result<(), ::parquet::errors::ParquetError> {
+        use ::parquet::column::writer::ColumnWriter;
+
+        let mut column = column;
+        #(
+          if column < #num_columns {
+            return #group_writer_snippets;
+          }
+          column -= #num_columns;
+        )*
+        Err(::parquet::errors::ParquetError::General(format!(
+          "Column {} out of range for {}", column, stringify!{#derived_for}
+        )))
+      }
+    }
+  }).into()
+}
+
+/// Derive flat, simple RecordReader implementations. Works by parsing
+/// a struct tagged with `#[derive(ParquetRecordReader)]` and emitting
+/// the correct reading code for each field of the struct, using the
+/// record API to read the rows of a row group.
+///
+/// Columns are matched to the fields of the struct by name, so the
+/// order of the struct fields need not match the schema. Only owned
+/// types can be read, as references cannot be constructed from a row.
+///
+/// Also derives `RecordGroupReader`, so that the struct may itself be a
+/// field of another derived struct, read from a parquet group.
+///
+/// Example:
+///
+/// ```ignore
+/// use parquet::record::RecordReader;
+/// use parquet::file::reader::{FileReader, SerializedFileReader};
+///
+/// #[derive(ParquetRecordReader)]
+/// struct ACompleteRecord {
+///   pub a_bool: bool,
+///   pub a_string: String,
+///   pub maybe_a_float: Option<f32>,
+/// }
+///
+/// pub fn read_some_records() -> Vec<ACompleteRecord> {
+///   let mut samples: Vec<ACompleteRecord> = Vec::new();
+///
+///   let reader = SerializedFileReader::new(file).unwrap();
+///   let mut row_group = reader.get_row_group(0).unwrap();
+///   samples.read_from_row_group(&mut *row_group, 1).unwrap();
+///   samples
+/// }
+/// ```
+///
+#[proc_macro_derive(ParquetRecordReader)]
+pub fn parquet_record_reader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let fields = match input.data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(_) => unimplemented!("Enum currently is not supported"),
+        Data::Union(_) => unimplemented!("Union currently is not supported"),
+    };
+
+    let field_infos: Vec<_> = fields.iter().map(parquet_field::Field::from).collect();
+
+    let field_names: Vec<_> = fields.iter().map(|f| f.ident.clone()).collect();
+    let field_indices: Vec<_> = (0..field_infos.len()).map(syn::Index::from).collect();
+    let reader_snippets: Vec<proc_macro2::TokenStream> =
+        field_infos.iter().map(|x| x.reader_snippet()).collect();
+
+    let derived_for = input.ident;
+    let generics = input.generics;
+
+    (quote! {
+    impl #generics ::parquet::record::RecordReader<#derived_for #generics> for Vec<#derived_for #generics> {
+      fn read_from_row_group(
+        &mut self,
+        row_group_reader: &mut dyn ::parquet::file::reader::RowGroupReader,
+        num_records: usize,
+      ) -> Result<(), ::parquet::errors::ParquetError> {
+        // The position of the column of each field in the rows read
+        let columns = row_group_reader.metadata().schema_descr().root_schema().get_fields();
+        let indices = [#(
+          columns
+            .iter()
+            .position(|c| c.name() == stringify!{#field_names})
+            .ok_or_else(|| ::parquet::errors::ParquetError::General(format!(
+              "Column {} not found", stringify!{#field_names}
+            )))?
+        ),*];
+
+        for row in row_group_reader.get_row_iter(None)?.take(num_records) {
+          let fields: Vec<_> = row.get_column_iter().map(|(_, field)| field).collect();
+          self.push(#derived_for {
+            #(
+              #field_names: {
+                let field = fields[indices[#field_indices]];
+                #reader_snippets
+              }
+            ),*
+          });
+        }
+
+        Ok(())
+      }
+    }
+
+    impl #generics ::parquet::record::RecordGroupReader for #derived_for #generics {
+      fn read_from_row(row: &::parquet::record::Row) -> Result<Self, ::parquet::errors::ParquetError> {
+        Ok(#derived_for {
+          #(
+            #field_names: {
+              let field = row
+                .get_column_iter()
+                .find(|(name, _)| name.as_str() == stringify!{#field_names})
+                .map(|(_, field)| field)
+                .ok_or_else(|| ::parquet::errors::ParquetError::General(format!(
+                  "Column {} not found", stringify!{#field_names}
+                )))?;
+              #reader_snippets
+            }
+          ),*
+        })
+      }
+    }
   }).into()
 }
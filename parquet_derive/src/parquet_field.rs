@@ -40,7 +40,8 @@ enum ThirdPartyType {
 impl Field {
     pub fn from(f: &syn::Field) -> Self {
         let ty = Type::from(f);
-        let is_a_byte_buf = ty.physical_type() == parquet::basic::Type::BYTE_ARRAY;
+        let is_a_byte_buf =
+            !ty.is_group() && ty.physical_type() == parquet::basic::Type::BYTE_ARRAY;
 
         let third_party_type = match &ty.last_part()[..] {
             "NaiveDateTime" => Some(ThirdPartyType::ChronoNaiveDateTime),
@@ -79,7 +80,8 @@ impl Field {
     /// }
     ///
     /// because this parsing logic is not sophisticated enough for definition
-    /// levels beyond 2.
+    /// levels beyond 2. Such fields are written by
+    /// [`Self::nested_writer_snippet`] instead.
     pub fn writer_snippet(&self) -> proc_macro2::TokenStream {
         let ident = &self.ident;
         let column_writer = self.ty.column_writer();
@@ -174,37 +176,196 @@ impl Field {
         }
     }
 
+    /// Takes the parsed field of the struct and emits an expression
+    /// converting `field`, the `::parquet::record::Field` read for its
+    /// column by the record API, to the type of the struct field.
+    ///
+    /// Can only generate readers for owned types and `Option`s of owned
+    /// types, for example:
+    ///
+    /// struct Record {
+    ///   a_string: String,
+    ///   maybe_a_bool: `Option<bool>`
+    /// }
+    ///
+    /// but not references, such as `&str`, as these cannot be constructed
+    /// from a row.
+    pub fn reader_snippet(&self) -> proc_macro2::TokenStream {
+        self.read_value(&self.ty)
+    }
+
+    /// Emits an expression converting `field` to the value of `ty`, a
+    /// type within the type of this field
+    fn read_value(&self, ty: &Type) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let mismatch = quote! {
+            _ => return Err(::parquet::errors::ParquetError::General(format!(
+                "Schema and struct disagree on type for {}", stringify!{#ident}
+            ))),
+        };
+
+        match ty {
+            Type::Option(ref first_type) if first_type.is_leaf() && !ty.is_group() => {
+                let arms = self.read_arms(first_type, true);
+                quote! {
+                    match field {
+                        ::parquet::record::Field::Null => None,
+                        #arms
+                        #mismatch
+                    }
+                }
+            }
+            Type::Option(ref first_type) => {
+                let value = self.read_value(first_type);
+                quote! {
+                    match field {
+                        ::parquet::record::Field::Null => None,
+                        field => Some(#value),
+                    }
+                }
+            }
+            Type::Vec(ref first_type) if !ty.is_leaf() => {
+                let element = self.read_value(first_type);
+                quote! {
+                    match field {
+                        ::parquet::record::Field::ListInternal(list) => list
+                            .elements()
+                            .iter()
+                            .map(|field| -> Result<_, ::parquet::errors::ParquetError> {
+                                Ok(#element)
+                            })
+                            .collect::<Result<Vec<_>, ::parquet::errors::ParquetError>>()?,
+                        #mismatch
+                    }
+                }
+            }
+            Type::TypePath(_) if ty.is_group() => {
+                let inner_type = ty.inner_type();
+                quote! {
+                    match field {
+                        ::parquet::record::Field::Group(row) => {
+                            <#inner_type as ::parquet::record::RecordGroupReader>::read_from_row(row)?
+                        }
+                        #mismatch
+                    }
+                }
+            }
+            Type::TypePath(_) | Type::Vec(_) => {
+                let arms = self.read_arms(ty, false);
+                quote! {
+                    match field {
+                        #arms
+                        #mismatch
+                    }
+                }
+            }
+            f => unimplemented!("Unsupported: {:#?}", f),
+        }
+    }
+
+    /// Emits the match arms converting the `::parquet::record::Field`
+    /// variants that may be read for `leaf`, the primitive type of this
+    /// field, wrapping the converted value in `Some` if `optional`
+    fn read_arms(&self, leaf: &Type, optional: bool) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let wrap = |value: proc_macro2::TokenStream| {
+            if optional {
+                quote! { Some(#value) }
+            } else {
+                value
+            }
+        };
+        let invalid = quote! {
+            ::parquet::errors::ParquetError::General(format!(
+                "Invalid value for {}", stringify!{#ident}
+            ))
+        };
+
+        match self.third_party_type {
+            Some(ThirdPartyType::ChronoNaiveDateTime) => {
+                let value = wrap(quote! {
+                    ::chrono::NaiveDateTime::from_timestamp_millis(*v as i64)
+                        .ok_or_else(|| #invalid)?
+                });
+                return quote! { ::parquet::record::Field::TimestampMillis(v) => #value, };
+            }
+            Some(ThirdPartyType::ChronoNaiveDate) => {
+                let value = wrap(quote! {
+                    ::chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
+                        .unwrap()
+                        .checked_add_signed(::chrono::Duration::days(*v as i32 as i64))
+                        .ok_or_else(|| #invalid)?
+                });
+                return quote! { ::parquet::record::Field::Date(v) => #value, };
+            }
+            Some(ThirdPartyType::Uuid) => {
+                let from_str = wrap(quote! {
+                    ::uuid::Uuid::parse_str(v).map_err(|_| #invalid)?
+                });
+                let from_bytes = wrap(quote! {
+                    ::uuid::Uuid::parse_str(v.as_utf8()?).map_err(|_| #invalid)?
+                });
+                return quote! {
+                    ::parquet::record::Field::Str(v) => #from_str,
+                    ::parquet::record::Field::Bytes(v) => #from_bytes,
+                };
+            }
+            None => {}
+        }
+
+        let last_part = self.ty.last_part();
+        let arms: Vec<(proc_macro2::TokenStream, proc_macro2::TokenStream)> =
+            match (leaf, last_part.as_str()) {
+                (Type::Vec(_), "u8") => {
+                    vec![(quote! { Bytes(v) }, quote! { v.data().to_vec() })]
+                }
+                (Type::Vec(_), f) => {
+                    unimplemented!("Reading Vec<{}> currently is not supported", f)
+                }
+                (_, "bool") => vec![(quote! { Bool(v) }, quote! { *v })],
+                (_, "i8") => vec![(quote! { Byte(v) }, quote! { *v })],
+                (_, "i16") => vec![(quote! { Short(v) }, quote! { *v })],
+                (_, "i32") => vec![(quote! { Int(v) }, quote! { *v })],
+                (_, "i64") => vec![(quote! { Long(v) }, quote! { *v })],
+                (_, "u8") => vec![(quote! { UByte(v) }, quote! { *v })],
+                (_, "u16") => vec![(quote! { UShort(v) }, quote! { *v })],
+                (_, "u32") => vec![(quote! { UInt(v) }, quote! { *v })],
+                (_, "u64") => vec![(quote! { ULong(v) }, quote! { *v })],
+                (_, "usize") => vec![
+                    (quote! { ULong(v) }, quote! { *v as usize }),
+                    (quote! { UInt(v) }, quote! { *v as usize }),
+                ],
+                (_, "isize") => vec![
+                    (quote! { Long(v) }, quote! { *v as isize }),
+                    (quote! { Int(v) }, quote! { *v as isize }),
+                ],
+                (_, "f32") => vec![(quote! { Float(v) }, quote! { *v })],
+                (_, "f64") => vec![(quote! { Double(v) }, quote! { *v })],
+                (_, "String") => vec![(quote! { Str(v) }, quote! { v.clone() })],
+                (_, f) => unimplemented!("Reading {} currently is not supported", f),
+            };
+
+        let arms = arms.into_iter().map(|(variant, value)| {
+            let value = wrap(value);
+            quote! { ::parquet::record::Field::#variant => #value, }
+        });
+        quote! { #(#arms)* }
+    }
+
     pub fn parquet_type(&self) -> proc_macro2::TokenStream {
-        // TODO: Support group types
         // TODO: Add length if dealing with fixedlenbinary
 
         let field_name = &self.ident.to_string();
-        let physical_type = match self.ty.physical_type() {
-            parquet::basic::Type::BOOLEAN => quote! {
-                ::parquet::basic::Type::BOOLEAN
-            },
-            parquet::basic::Type::INT32 => quote! {
-                ::parquet::basic::Type::INT32
-            },
-            parquet::basic::Type::INT64 => quote! {
-                ::parquet::basic::Type::INT64
-            },
-            parquet::basic::Type::INT96 => quote! {
-                ::parquet::basic::Type::INT96
-            },
-            parquet::basic::Type::FLOAT => quote! {
-                ::parquet::basic::Type::FLOAT
-            },
-            parquet::basic::Type::DOUBLE => quote! {
-                ::parquet::basic::Type::DOUBLE
-            },
-            parquet::basic::Type::BYTE_ARRAY => quote! {
-                ::parquet::basic::Type::BYTE_ARRAY
-            },
-            parquet::basic::Type::FIXED_LEN_BYTE_ARRAY => quote! {
-                ::parquet::basic::Type::FIXED_LEN_BYTE_ARRAY
-            },
-        };
+        if !self.is_flat() {
+            let field_type = self.nested_type(
+                &self.ty,
+                quote! { #field_name },
+                quote! { ::parquet::basic::Repetition::REQUIRED },
+            );
+            return quote! { fields.push(#field_type) };
+        }
+
+        let physical_type = self.ty.physical_type_path();
         let logical_type = self.ty.logical_type();
         let repetition = self.ty.repetition();
         let converted_type = self.ty.converted_type();
@@ -229,6 +390,199 @@ impl Field {
         }
     }
 
+    /// Whether this field is written as a single, non-repeated column
+    /// by [`Self::writer_snippet`], as opposed to a group or list written
+    /// by [`Self::nested_writer_snippet`]
+    pub fn is_flat(&self) -> bool {
+        fn is_flat(ty: &Type) -> bool {
+            match ty {
+                Type::TypePath(_) => !ty.is_group(),
+                Type::Option(ref first_type) | Type::Reference(_, ref first_type) => {
+                    is_flat(first_type)
+                }
+                Type::Vec(_) => false,
+                Type::Array(_) => true,
+            }
+        }
+        is_flat(&self.ty)
+    }
+
+    /// The number of leaf columns of this field
+    pub fn num_columns(&self) -> proc_macro2::TokenStream {
+        if self.ty.is_group() {
+            let inner_type = self.ty.inner_type();
+            quote! { <#inner_type as ::parquet::record::RecordGroupWriter>::num_columns() }
+        } else {
+            quote! { 1 }
+        }
+    }
+
+    /// Emits an expression writing the `column`-th leaf column of this
+    /// field to `column_writer`, a `&mut ColumnWriter`, for the records
+    /// in `slots`, a slice of `::parquet::record::RecordSlot`, nested
+    /// within `rep_depth` repeated fields
+    ///
+    /// Unlike [`Self::writer_snippet`], supports fields of structs that
+    /// implement `RecordGroupWriter`, written as parquet groups, and
+    /// `Vec`s other than `Vec<u8>`, written as parquet lists, along with
+    /// any nesting of these within `Option`s.
+    pub fn nested_writer_snippet(
+        &self,
+        slots: proc_macro2::TokenStream,
+        column: proc_macro2::TokenStream,
+        rep_depth: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let mut steps = vec![quote! {
+            let slots = ::parquet::record::RecordSlot::map_required(#slots, |r| &r.#ident);
+        }];
+
+        let mut lists = 0_i16;
+        let mut ty = &self.ty;
+        loop {
+            match ty {
+                Type::Option(ref first_type) => {
+                    steps.push(quote! {
+                        let slots = ::parquet::record::RecordSlot::map_optional(&slots, |v| v.as_ref());
+                    });
+                    ty = first_type;
+                }
+                Type::Reference(_, ref first_type) => {
+                    steps.push(quote! {
+                        let slots = ::parquet::record::RecordSlot::map_required(&slots, |v| *v);
+                    });
+                    ty = first_type;
+                }
+                Type::Vec(ref first_type) if !ty.is_leaf() => {
+                    steps.push(quote! {
+                        let slots = ::parquet::record::RecordSlot::map_list(
+                            &slots, #rep_depth + #lists, |v| v.as_slice()
+                        );
+                    });
+                    lists += 1;
+                    ty = first_type;
+                }
+                Type::TypePath(_) | Type::Vec(_) => break,
+                Type::Array(_) => {
+                    return quote! {
+                        Err(::parquet::errors::ParquetError::NYI(format!(
+                            "Writing {} within a nested record", stringify!{#ident}
+                        )))
+                    }
+                }
+            }
+        }
+
+        let write = if ty.is_group() {
+            let inner_type = ty.inner_type();
+            quote! {
+                <#inner_type as ::parquet::record::RecordGroupWriter>::write_group_column(
+                    #column, &slots, #rep_depth + #lists, column_writer
+                )
+            }
+        } else {
+            let column_writer = self.ty.column_writer();
+            let value = self.nested_value();
+            quote! {
+                if let #column_writer(ref mut typed) = *column_writer {
+                    ::parquet::record::RecordSlot::write_column(&slots, typed, |v| #value)
+                } else {
+                    panic!("Schema and struct disagree on type for {}", stringify!{#ident})
+                }
+            }
+        };
+
+        quote! {
+            {
+                #(#steps)*
+                #write
+            }
+        }
+    }
+
+    /// Emits an expression converting `v`, a reference to the primitive
+    /// value of this field, to its physical type
+    fn nested_value(&self) -> proc_macro2::TokenStream {
+        match self.third_party_type {
+            Some(ThirdPartyType::ChronoNaiveDateTime) => quote! { v.timestamp_millis() },
+            Some(ThirdPartyType::ChronoNaiveDate) => {
+                quote! { v.signed_duration_since(::chrono::NaiveDate::from_ymd(1970, 1, 1)).num_days() as i32 }
+            }
+            Some(ThirdPartyType::Uuid) => quote! { (&v.to_string()[..]).into() },
+            None if self.is_a_byte_buf && self.ty.last_part() == "u8" => {
+                quote! { v.clone().into() }
+            }
+            None if self.is_a_byte_buf => quote! { (&v[..]).into() },
+            None => match self.ty.physical_type() {
+                parquet::basic::Type::INT32 => quote! { *v as i32 },
+                parquet::basic::Type::INT64 => quote! { *v as i64 },
+                _ => quote! { *v },
+            },
+        }
+    }
+
+    /// Emits an expression building the `TypePtr` of `ty`, a type within
+    /// the type of this field, named `name` and with `repetition`
+    fn nested_type(
+        &self,
+        ty: &Type,
+        name: proc_macro2::TokenStream,
+        repetition: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match ty {
+            Type::Option(ref first_type) => self.nested_type(
+                first_type,
+                name,
+                quote! { ::parquet::basic::Repetition::OPTIONAL },
+            ),
+            Type::Reference(_, ref first_type) => {
+                self.nested_type(first_type, name, repetition)
+            }
+            Type::Vec(ref first_type) if !ty.is_leaf() => {
+                let element = self.nested_type(
+                    first_type,
+                    quote! { "element" },
+                    quote! { ::parquet::basic::Repetition::REQUIRED },
+                );
+                quote! {
+                    ::std::sync::Arc::new(ParquetType::group_type_builder(#name)
+                        .with_fields(&mut vec![::std::sync::Arc::new(
+                            ParquetType::group_type_builder("list")
+                                .with_fields(&mut vec![#element])
+                                .with_repetition(::parquet::basic::Repetition::REPEATED)
+                                .build()?,
+                        )])
+                        .with_logical_type(Some(LogicalType::List))
+                        .with_repetition(#repetition)
+                        .build()?)
+                }
+            }
+            Type::TypePath(_) if ty.is_group() => {
+                let inner_type = ty.inner_type();
+                quote! {
+                    ::std::sync::Arc::new(ParquetType::group_type_builder(#name)
+                        .with_fields(&mut <#inner_type as ::parquet::record::RecordGroupWriter>::group_fields()?)
+                        .with_repetition(#repetition)
+                        .build()?)
+                }
+            }
+            _ => {
+                let physical_type = self.ty.physical_type_path();
+                let logical_type = self.ty.logical_type();
+                let converted_type = self.ty.converted_type().map(|converted_type| {
+                    quote! { .with_converted_type(#converted_type) }
+                });
+                quote! {
+                    ::std::sync::Arc::new(ParquetType::primitive_type_builder(#name, #physical_type)
+                        .with_logical_type(#logical_type)
+                        .with_repetition(#repetition)
+                        #converted_type
+                        .build()?)
+                }
+            }
+        }
+    }
+
     fn option_into_vals(&self) -> proc_macro2::TokenStream {
         let field_name = &self.ident;
         let is_a_byte_buf = self.is_a_byte_buf;
@@ -352,6 +706,62 @@ impl Type {
         }
     }
 
+    /// Whether the leaf type of this type is a struct, written as a
+    /// parquet group, as opposed to a primitive type
+    fn is_group(&self) -> bool {
+        !matches!(
+            self.last_part().as_str(),
+            "bool"
+                | "u8"
+                | "u16"
+                | "u32"
+                | "u64"
+                | "usize"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "isize"
+                | "f32"
+                | "f64"
+                | "String"
+                | "str"
+                | "NaiveDateTime"
+                | "NaiveDate"
+                | "Uuid"
+        )
+    }
+
+    /// Whether this type is a leaf, that is a type path or `Vec<u8>`,
+    /// as opposed to a type wrapping another
+    fn is_leaf(&self) -> bool {
+        match self {
+            Type::TypePath(_) => true,
+            Type::Vec(ref first_type) => {
+                matches!(**first_type, Type::TypePath(_)) && self.last_part() == "u8"
+            }
+            _ => false,
+        }
+    }
+
+    /// The path of the parquet physical type of this type
+    fn physical_type_path(&self) -> proc_macro2::TokenStream {
+        match self.physical_type() {
+            parquet::basic::Type::BOOLEAN => quote! { ::parquet::basic::Type::BOOLEAN },
+            parquet::basic::Type::INT32 => quote! { ::parquet::basic::Type::INT32 },
+            parquet::basic::Type::INT64 => quote! { ::parquet::basic::Type::INT64 },
+            parquet::basic::Type::INT96 => quote! { ::parquet::basic::Type::INT96 },
+            parquet::basic::Type::FLOAT => quote! { ::parquet::basic::Type::FLOAT },
+            parquet::basic::Type::DOUBLE => quote! { ::parquet::basic::Type::DOUBLE },
+            parquet::basic::Type::BYTE_ARRAY => {
+                quote! { ::parquet::basic::Type::BYTE_ARRAY }
+            }
+            parquet::basic::Type::FIXED_LEN_BYTE_ARRAY => {
+                quote! { ::parquet::basic::Type::FIXED_LEN_BYTE_ARRAY }
+            }
+        }
+    }
+
     /// Helper to simplify a nested field definition to its leaf type
     ///
     /// Ex:
@@ -750,6 +1160,141 @@ mod test {
         }).to_string());
     }
 
+    #[test]
+    fn test_generating_a_simple_reader_snippet() {
+        let snippet: proc_macro2::TokenStream = quote! {
+          struct ABoringStruct {
+            counter: usize,
+          }
+        };
+
+        let fields = extract_fields(snippet);
+        let counter = Field::from(&fields[0]);
+
+        let snippet = counter.reader_snippet().to_string();
+        assert_eq!(
+            snippet,
+            (quote! {
+                 match field {
+                     ::parquet::record::Field::ULong(v) => *v as usize,
+                     ::parquet::record::Field::UInt(v) => *v as usize,
+                     _ => return Err(::parquet::errors::ParquetError::General(format!(
+                         "Schema and struct disagree on type for {}", stringify!{counter}
+                     ))),
+                 }
+            })
+            .to_string()
+        )
+    }
+
+    #[test]
+    fn test_optional_to_reader_snippet() {
+        let snippet: proc_macro2::TokenStream = quote! {
+          struct StringOwner {
+            optional_string: Option<String>,
+            optional_bytes: Option<Vec<u8>>,
+          }
+        };
+
+        let fields = extract_fields(snippet);
+
+        let optional = Field::from(&fields[0]);
+        assert_eq!(optional.reader_snippet().to_string(),
+                   (quote!{
+                        match field {
+                            ::parquet::record::Field::Null => None,
+                            ::parquet::record::Field::Str(v) => Some(v.clone()),
+                            _ => return Err(::parquet::errors::ParquetError::General(format!(
+                                "Schema and struct disagree on type for {}", stringify!{optional_string}
+                            ))),
+                        }
+                   }).to_string()
+        );
+
+        let optional = Field::from(&fields[1]);
+        assert_eq!(optional.reader_snippet().to_string(),
+                   (quote!{
+                        match field {
+                            ::parquet::record::Field::Null => None,
+                            ::parquet::record::Field::Bytes(v) => Some(v.data().to_vec()),
+                            _ => return Err(::parquet::errors::ParquetError::General(format!(
+                                "Schema and struct disagree on type for {}", stringify!{optional_bytes}
+                            ))),
+                        }
+                   }).to_string()
+        );
+    }
+
+    #[test]
+    fn test_nested_fields_are_not_flat() {
+        let snippet: proc_macro2::TokenStream = quote! {
+          struct ANestedStruct<'a> {
+            a_str: &'a str,
+            maybe_bytes: Option<[u8; 10]>,
+            bytes: Vec<u8>,
+            numbers: Vec<i32>,
+            inner: AnInnerStruct,
+            maybe_inner: Option<AnInnerStruct>,
+          }
+        };
+
+        let fields = extract_fields(snippet);
+        let flat: Vec<_> = fields.iter().map(|f| Field::from(f).is_flat()).collect();
+        assert_eq!(flat, vec![true, true, false, false, false, false]);
+
+        let num_columns: Vec<_> = fields
+            .iter()
+            .map(|f| Field::from(f).num_columns().to_string())
+            .collect();
+        assert_eq!(
+            num_columns,
+            vec![
+                "1".to_string(),
+                "1".to_string(),
+                "1".to_string(),
+                "1".to_string(),
+                (quote! { <AnInnerStruct as ::parquet::record::RecordGroupWriter>::num_columns() })
+                    .to_string(),
+                (quote! { <AnInnerStruct as ::parquet::record::RecordGroupWriter>::num_columns() })
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_to_nested_writer_snippet() {
+        let snippet: proc_macro2::TokenStream = quote! {
+          struct AListStruct {
+            maybe_numbers: Option<Vec<i32>>,
+          }
+        };
+
+        let fields = extract_fields(snippet);
+        let maybe_numbers = Field::from(&fields[0]);
+
+        let snippet = maybe_numbers
+            .nested_writer_snippet(quote! { &slots }, quote! { column }, quote! { 0 })
+            .to_string();
+        assert_eq!(
+            snippet,
+            (quote! {
+                {
+                    let slots = ::parquet::record::RecordSlot::map_required(&slots, |r| &r.maybe_numbers);
+                    let slots = ::parquet::record::RecordSlot::map_optional(&slots, |v| v.as_ref());
+                    let slots = ::parquet::record::RecordSlot::map_list(
+                        &slots, 0 + 0i16, |v| v.as_slice()
+                    );
+                    if let ColumnWriter::Int32ColumnWriter(ref mut typed) = *column_writer {
+                        ::parquet::record::RecordSlot::write_column(&slots, typed, |v| *v as i32)
+                    } else {
+                        panic!("Schema and struct disagree on type for {}", stringify!{maybe_numbers})
+                    }
+                }
+            })
+            .to_string()
+        );
+    }
+
     #[test]
     fn test_converting_to_column_writer_type() {
         let snippet: proc_macro2::TokenStream = quote! {
@@ -17,7 +17,7 @@
 
 #![allow(clippy::approx_constant)]
 
-use parquet_derive::ParquetRecordWriter;
+use parquet_derive::{ParquetRecordReader, ParquetRecordWriter};
 
 #[derive(ParquetRecordWriter)]
 struct ACompleteRecord<'a> {
@@ -44,6 +44,50 @@ struct ACompleteRecord<'a> {
     pub now: chrono::NaiveDateTime,
 }
 
+#[derive(PartialEq, ParquetRecordWriter, ParquetRecordReader, Debug)]
+struct APartiallyCompleteRecord {
+    pub bool: bool,
+    pub string: String,
+    pub i16: i16,
+    pub i32: i32,
+    pub u64: u64,
+    pub isize: isize,
+    pub float: f32,
+    pub double: f64,
+    pub now: chrono::NaiveDateTime,
+    pub date: chrono::NaiveDate,
+}
+
+#[derive(PartialEq, ParquetRecordWriter, ParquetRecordReader, Debug)]
+struct APartiallyCompleteOptionalRecord {
+    pub maybe_bool: Option<bool>,
+    pub maybe_string: Option<String>,
+    pub maybe_u8: Option<u8>,
+    pub maybe_usize: Option<usize>,
+    pub maybe_double: Option<f64>,
+    pub maybe_now: Option<chrono::NaiveDateTime>,
+    pub maybe_date: Option<chrono::NaiveDate>,
+}
+
+#[derive(PartialEq, ParquetRecordWriter, ParquetRecordReader, Debug)]
+struct AnInnerRecord {
+    pub id: i32,
+    pub name: String,
+    pub maybe_tag: Option<String>,
+}
+
+#[derive(PartialEq, ParquetRecordWriter, ParquetRecordReader, Debug)]
+struct ANestedRecord {
+    pub id: i64,
+    pub inner: AnInnerRecord,
+    pub maybe_inner: Option<AnInnerRecord>,
+    pub numbers: Vec<i32>,
+    pub maybe_strings: Option<Vec<String>>,
+    pub inners: Vec<AnInnerRecord>,
+    pub matrix: Vec<Vec<u64>>,
+    pub bytes: Vec<u8>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,8 +95,12 @@ mod tests {
     use std::{env, fs, io::Write, sync::Arc};
 
     use parquet::{
-        file::{properties::WriterProperties, writer::SerializedFileWriter},
-        record::RecordWriter,
+        file::{
+            properties::WriterProperties,
+            reader::{FileReader, SerializedFileReader},
+            writer::SerializedFileWriter,
+        },
+        record::{RecordReader, RecordWriter},
         schema::parser::parse_message_type,
     };
 
@@ -131,6 +179,218 @@ mod tests {
         writer.close().unwrap();
     }
 
+    #[test]
+    fn test_parquet_derive_read_write_combined() {
+        let file = get_temp_file("test_parquet_derive_combined", &[]);
+
+        let mut drs: Vec<APartiallyCompleteRecord> = vec![
+            APartiallyCompleteRecord {
+                bool: true,
+                string: "a string".into(),
+                i16: -45,
+                i32: 456,
+                u64: 4563424,
+                isize: -365,
+                float: 3.5,
+                double: f64::MAX,
+                now: chrono::NaiveDateTime::from_timestamp_millis(1671548397123).unwrap(),
+                date: chrono::NaiveDate::from_ymd_opt(2022, 12, 20).unwrap(),
+            },
+            APartiallyCompleteRecord {
+                bool: false,
+                string: "".into(),
+                i16: 0,
+                i32: 0,
+                u64: 0,
+                isize: 0,
+                float: 0.,
+                double: 0.,
+                now: chrono::NaiveDateTime::from_timestamp_millis(0).unwrap(),
+                date: chrono::NaiveDate::from_ymd_opt(1969, 7, 20).unwrap(),
+            },
+        ];
+
+        let mut out: Vec<APartiallyCompleteRecord> = Vec::new();
+
+        let generated_schema = drs.as_slice().schema().unwrap();
+
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer =
+            SerializedFileWriter::new(file.try_clone().unwrap(), generated_schema, props)
+                .unwrap();
+
+        let mut row_group = writer.next_row_group().unwrap();
+        drs.as_slice().write_to_row_group(&mut row_group).unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(file).unwrap();
+        let mut row_group = reader.get_row_group(0).unwrap();
+        out.read_from_row_group(&mut *row_group, 2).unwrap();
+
+        assert_eq!(drs, out);
+
+        // Reads no more records than requested
+        let mut out: Vec<APartiallyCompleteRecord> = Vec::new();
+        out.read_from_row_group(&mut *row_group, 1).unwrap();
+        assert_eq!(&drs[..1], &out[..]);
+
+        drs.truncate(1);
+        assert_eq!(drs, out);
+    }
+
+    #[test]
+    fn test_parquet_derive_read_optional() {
+        let file = get_temp_file("test_parquet_derive_read_optional", &[]);
+
+        let drs: Vec<APartiallyCompleteOptionalRecord> = vec![
+            APartiallyCompleteOptionalRecord {
+                maybe_bool: Some(true),
+                maybe_string: Some("a string".into()),
+                maybe_u8: Some(8),
+                maybe_usize: Some(4456),
+                maybe_double: Some(f64::MIN),
+                maybe_now: chrono::NaiveDateTime::from_timestamp_millis(1671548397123),
+                maybe_date: chrono::NaiveDate::from_ymd_opt(2022, 12, 20),
+            },
+            APartiallyCompleteOptionalRecord {
+                maybe_bool: None,
+                maybe_string: None,
+                maybe_u8: None,
+                maybe_usize: None,
+                maybe_double: None,
+                maybe_now: None,
+                maybe_date: None,
+            },
+        ];
+
+        let generated_schema = drs.as_slice().schema().unwrap();
+
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer =
+            SerializedFileWriter::new(file.try_clone().unwrap(), generated_schema, props)
+                .unwrap();
+
+        let mut row_group = writer.next_row_group().unwrap();
+        drs.as_slice().write_to_row_group(&mut row_group).unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(file).unwrap();
+        let mut row_group = reader.get_row_group(0).unwrap();
+        let mut out: Vec<APartiallyCompleteOptionalRecord> = Vec::new();
+        out.read_from_row_group(&mut *row_group, 2).unwrap();
+
+        assert_eq!(drs, out);
+    }
+
+    #[test]
+    fn test_parquet_derive_nested() {
+        let file = get_temp_file("test_parquet_derive_nested", &[]);
+
+        let inner = |id: i32, maybe_tag: Option<&str>| AnInnerRecord {
+            id,
+            name: format!("inner {}", id),
+            maybe_tag: maybe_tag.map(String::from),
+        };
+        let drs: Vec<ANestedRecord> = vec![
+            ANestedRecord {
+                id: 1,
+                inner: inner(1, Some("a tag")),
+                maybe_inner: Some(inner(2, None)),
+                numbers: vec![1, 2, 3],
+                maybe_strings: Some(vec!["a".into(), "b".into()]),
+                inners: vec![inner(3, None), inner(4, Some("another tag"))],
+                matrix: vec![vec![1, 2], vec![], vec![3]],
+                bytes: vec![1, 2, 3],
+            },
+            ANestedRecord {
+                id: 2,
+                inner: inner(5, None),
+                maybe_inner: None,
+                numbers: vec![],
+                maybe_strings: None,
+                inners: vec![],
+                matrix: vec![],
+                bytes: vec![],
+            },
+            ANestedRecord {
+                id: 3,
+                inner: inner(6, None),
+                maybe_inner: Some(inner(7, Some("a third tag"))),
+                numbers: vec![4],
+                maybe_strings: Some(vec![]),
+                inners: vec![inner(8, None)],
+                matrix: vec![vec![4, 5, 6]],
+                bytes: vec![4],
+            },
+        ];
+
+        let schema_str = "message rust_schema {
+            REQUIRED INT64        id;
+            REQUIRED group inner {
+                REQUIRED INT32        id;
+                REQUIRED BINARY       name (STRING);
+                OPTIONAL BINARY       maybe_tag (STRING);
+            }
+            OPTIONAL group maybe_inner {
+                REQUIRED INT32        id;
+                REQUIRED BINARY       name (STRING);
+                OPTIONAL BINARY       maybe_tag (STRING);
+            }
+            REQUIRED group numbers (LIST) {
+                REPEATED group list {
+                    REQUIRED INT32        element;
+                }
+            }
+            OPTIONAL group maybe_strings (LIST) {
+                REPEATED group list {
+                    REQUIRED BINARY       element (STRING);
+                }
+            }
+            REQUIRED group inners (LIST) {
+                REPEATED group list {
+                    REQUIRED group element {
+                        REQUIRED INT32        id;
+                        REQUIRED BINARY       name (STRING);
+                        OPTIONAL BINARY       maybe_tag (STRING);
+                    }
+                }
+            }
+            REQUIRED group matrix (LIST) {
+                REPEATED group list {
+                    REQUIRED group element (LIST) {
+                        REPEATED group list {
+                            REQUIRED INT64        element (INTEGER(64,false));
+                        }
+                    }
+                }
+            }
+            REQUIRED BINARY       bytes;
+        }";
+
+        let schema = Arc::new(parse_message_type(schema_str).unwrap());
+        let generated_schema = drs.as_slice().schema().unwrap();
+        assert_eq!(&schema, &generated_schema);
+
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer =
+            SerializedFileWriter::new(file.try_clone().unwrap(), generated_schema, props)
+                .unwrap();
+
+        let mut row_group = writer.next_row_group().unwrap();
+        drs.as_slice().write_to_row_group(&mut row_group).unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(file).unwrap();
+        let mut row_group = reader.get_row_group(0).unwrap();
+        let mut out: Vec<ANestedRecord> = Vec::new();
+        out.read_from_row_group(&mut *row_group, 3).unwrap();
+
+        assert_eq!(drs, out);
+    }
+
     /// Returns file handle for a temp file in 'target' directory with a provided content
     pub fn get_temp_file(file_name: &str, content: &[u8]) -> fs::File {
         // build tmp path to a file in "target/debug/testdata"